@@ -0,0 +1,485 @@
+//! Exact Whirlpool CLMM Swap Simulation
+//!
+//! `math::get_amount_out_clmm` treats the pool as a single virtual-reserve
+//! curve, which only holds while the swap stays inside the current tick. This
+//! module replays the on-chain swap loop instead: Q64.64 fixed-point prices,
+//! per-step fee accounting and liquidity changes at every initialized tick the
+//! price crosses. Rounding follows the program (inputs round up, outputs and
+//! prices round against the trader), so quotes land within a lamport or two.
+
+pub const TICK_ARRAY_SIZE: usize = 88;
+pub const MIN_TICK_INDEX: i32 = -443_636;
+pub const MAX_TICK_INDEX: i32 = 443_636;
+pub const MIN_SQRT_PRICE_X64: u128 = 4_295_048_016;
+pub const MAX_SQRT_PRICE_X64: u128 = 79_226_673_515_401_279_992_447_579_055;
+
+/// Whirlpool fee rates are expressed in hundredths of a basis point
+pub const FEE_RATE_DENOMINATOR: u128 = 1_000_000;
+
+const Q64: u128 = 1 << 64;
+const LO_64: u128 = u64::MAX as u128;
+
+// TickArray account: discriminator, start_tick_index, ticks, whirlpool
+const TICK_ARRAY_START_OFFSET: usize = 8;
+const TICKS_OFFSET: usize = 12;
+const TICK_SIZE: usize = 113;
+pub const TICK_ARRAY_ACCOUNT_LEN: usize = TICKS_OFFSET + TICK_ARRAY_SIZE * TICK_SIZE + 32;
+
+// ---------------------------------------------------------------------------
+// 256-bit helpers
+// ---------------------------------------------------------------------------
+
+/// a * b as (high, low) 128-bit halves
+fn full_mul(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = (a >> 64, a & LO_64);
+    let (b1, b0) = (b >> 64, b & LO_64);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+    let mid = (p00 >> 64) + (p01 & LO_64) + (p10 & LO_64);
+    let lo = (p00 & LO_64) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+/// (hi:lo) / d as (quotient, remainder). None if d is zero or the quotient overflows u128.
+fn div_wide(hi: u128, lo: u128, d: u128) -> Option<(u128, u128)> {
+    if d == 0 || hi >= d {
+        return None;
+    }
+    let mut rem = hi;
+    let mut quot = 0u128;
+    for i in (0..128).rev() {
+        let carry = rem >> 127;
+        rem = (rem << 1) | ((lo >> i) & 1);
+        quot <<= 1;
+        if carry == 1 || rem >= d {
+            rem = rem.wrapping_sub(d);
+            quot |= 1;
+        }
+    }
+    Some((quot, rem))
+}
+
+fn mul_div(a: u128, b: u128, d: u128, round_up: bool) -> Option<u128> {
+    let (hi, lo) = full_mul(a, b);
+    let (quot, rem) = div_wide(hi, lo, d)?;
+    if round_up && rem > 0 {
+        quot.checked_add(1)
+    } else {
+        Some(quot)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tick math
+// ---------------------------------------------------------------------------
+
+// sqrt(1.0001)^-(2^i) in Q128.128
+const TICK_RATIOS_Q128: [u128; 19] = [
+    0xfffcb933bd6fad37aa2d162d1a594001,
+    0xfff97272373d413259a46990580e213a,
+    0xfff2e50f5f656932ef12357cf3c7fdcc,
+    0xffe5caca7e10e4e61c3624eaa0941cd0,
+    0xffcb9843d60f6159c9db58835c926644,
+    0xff973b41fa98c081472e6896dfb254c0,
+    0xff2ea16466c96a3843ec78b326b52861,
+    0xfe5dee046a99a2a811c461f1969c3053,
+    0xfcbe86c7900a88aedcffc83b479aa3a4,
+    0xf987a7253ac413176f2b074cf7815e54,
+    0xf3392b0822b70005940c7a398e4b70f3,
+    0xe7159475a2c29b7443b29c7fa6e889d9,
+    0xd097f3bdfd2022b8845ad8f792aa5825,
+    0xa9f746462d870fdf8a65dc1f90e061e5,
+    0x70d869a156d2a1b890bb3df62baf32f7,
+    0x31be135f97d08fd981231505542fcfa6,
+    0x9aa508b5b7a84e1c677de54f3e99bc9,
+    0x5d6af8dedb81196699c329225ee604,
+    0x2216e584f5fa1ea926041bedfe98,
+];
+
+/// Q64.64 sqrt price at a tick boundary
+pub fn sqrt_price_from_tick_index(tick: i32) -> u128 {
+    let tick = tick.clamp(MIN_TICK_INDEX, MAX_TICK_INDEX);
+    let abs = tick.unsigned_abs();
+
+    // Product of the ratios for each set bit; None stands for exactly 1.0 (2^128 doesn't fit)
+    let mut ratio: Option<u128> = None;
+    for (bit, factor) in TICK_RATIOS_Q128.iter().enumerate() {
+        if abs & (1 << bit) != 0 {
+            ratio = Some(match ratio {
+                None => *factor,
+                Some(r) => full_mul(r, *factor).0,
+            });
+        }
+    }
+
+    match ratio {
+        None => Q64,
+        Some(r) if tick > 0 => div_wide(1 << 64, 0, r).map(|(q, _)| q).unwrap_or(MAX_SQRT_PRICE_X64),
+        Some(r) => r >> 64,
+    }
+}
+
+/// Greatest tick whose sqrt price is <= `sqrt_price_x64`
+pub fn tick_index_from_sqrt_price(sqrt_price_x64: u128) -> i32 {
+    let price = (sqrt_price_x64 as f64 / Q64 as f64).powi(2);
+    let mut tick = (price.ln() / 1.0001f64.ln()).floor() as i32;
+    tick = tick.clamp(MIN_TICK_INDEX, MAX_TICK_INDEX);
+    // The float guess can be off by one either way near a boundary
+    while tick < MAX_TICK_INDEX && sqrt_price_from_tick_index(tick + 1) <= sqrt_price_x64 {
+        tick += 1;
+    }
+    while tick > MIN_TICK_INDEX && sqrt_price_from_tick_index(tick) > sqrt_price_x64 {
+        tick -= 1;
+    }
+    tick
+}
+
+// ---------------------------------------------------------------------------
+// Swap step math
+// ---------------------------------------------------------------------------
+
+/// Token A between two sqrt prices: L * (upper - lower) / (upper * lower)
+fn amount_a_delta(sqrt_a: u128, sqrt_b: u128, liquidity: u128, round_up: bool) -> Option<u128> {
+    let (lower, upper) = if sqrt_a < sqrt_b { (sqrt_a, sqrt_b) } else { (sqrt_b, sqrt_a) };
+    if lower == 0 {
+        return None;
+    }
+    let scaled = mul_div(liquidity, upper - lower, upper, round_up)?;
+    mul_div(scaled, Q64, lower, round_up)
+}
+
+/// Token B between two sqrt prices: L * (upper - lower)
+fn amount_b_delta(sqrt_a: u128, sqrt_b: u128, liquidity: u128, round_up: bool) -> Option<u128> {
+    let diff = sqrt_a.abs_diff(sqrt_b);
+    mul_div(liquidity, diff, Q64, round_up)
+}
+
+/// Price after adding `amount` of token A. Rounds up so the price moves no further than on-chain.
+fn next_sqrt_price_from_a(sqrt_price: u128, liquidity: u128, amount: u128) -> Option<u128> {
+    if amount == 0 {
+        return Some(sqrt_price);
+    }
+    let virtual_a = mul_div(liquidity, Q64, sqrt_price, false)?;
+    mul_div(liquidity, Q64, virtual_a.checked_add(amount)?, true)
+}
+
+/// Price after adding `amount` of token B. Rounds down for the same reason.
+fn next_sqrt_price_from_b(sqrt_price: u128, liquidity: u128, amount: u128) -> Option<u128> {
+    if liquidity == 0 {
+        return None;
+    }
+    sqrt_price.checked_add(mul_div(amount, Q64, liquidity, false)?)
+}
+
+struct SwapStep {
+    next_sqrt_price: u128,
+    amount_in: u128,
+    amount_out: u128,
+    fee_amount: u128,
+}
+
+fn compute_swap_step(
+    amount_remaining: u128,
+    fee_rate: u128,
+    liquidity: u128,
+    sqrt_price: u128,
+    target_sqrt_price: u128,
+    a_to_b: bool,
+) -> Option<SwapStep> {
+    let amount_after_fee = mul_div(amount_remaining, FEE_RATE_DENOMINATOR - fee_rate, FEE_RATE_DENOMINATOR, false)?;
+
+    let input_to_target = if a_to_b {
+        amount_a_delta(target_sqrt_price, sqrt_price, liquidity, true)?
+    } else {
+        amount_b_delta(sqrt_price, target_sqrt_price, liquidity, true)?
+    };
+
+    let next_sqrt_price = if amount_after_fee >= input_to_target {
+        target_sqrt_price
+    } else if a_to_b {
+        next_sqrt_price_from_a(sqrt_price, liquidity, amount_after_fee)?
+    } else {
+        next_sqrt_price_from_b(sqrt_price, liquidity, amount_after_fee)?
+    };
+    let reached_target = next_sqrt_price == target_sqrt_price;
+
+    let (amount_in, amount_out) = if a_to_b {
+        let amount_in = if reached_target { input_to_target } else { amount_a_delta(next_sqrt_price, sqrt_price, liquidity, true)? };
+        (amount_in, amount_b_delta(next_sqrt_price, sqrt_price, liquidity, false)?)
+    } else {
+        let amount_in = if reached_target { input_to_target } else { amount_b_delta(sqrt_price, next_sqrt_price, liquidity, true)? };
+        (amount_in, amount_a_delta(sqrt_price, next_sqrt_price, liquidity, false)?)
+    };
+
+    // A partial step consumes everything that's left; the remainder is fee
+    let fee_amount = if reached_target {
+        mul_div(amount_in, fee_rate, FEE_RATE_DENOMINATOR - fee_rate, true)?
+    } else {
+        amount_remaining.saturating_sub(amount_in)
+    };
+
+    Some(SwapStep { next_sqrt_price, amount_in, amount_out, fee_amount })
+}
+
+// ---------------------------------------------------------------------------
+// Tick arrays
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitializedTick {
+    pub index: i32,
+    pub liquidity_net: i128,
+}
+
+/// Decoded Whirlpool TickArray account, keeping only what the swap loop needs
+#[derive(Debug, Clone)]
+pub struct TickArray {
+    pub start_tick_index: i32,
+    pub ticks: Vec<Option<i128>>,
+}
+
+impl TickArray {
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < TICK_ARRAY_ACCOUNT_LEN {
+            return None;
+        }
+        let start_tick_index = i32::from_le_bytes(data[TICK_ARRAY_START_OFFSET..TICKS_OFFSET].try_into().ok()?);
+        let ticks = (0..TICK_ARRAY_SIZE)
+            .map(|i| {
+                let base = TICKS_OFFSET + i * TICK_SIZE;
+                let initialized = data[base] != 0;
+                let liquidity_net = i128::from_le_bytes(data[base + 1..base + 17].try_into().unwrap());
+                initialized.then_some(liquidity_net)
+            })
+            .collect();
+        Some(Self { start_tick_index, ticks })
+    }
+}
+
+/// Initialized ticks covered by a contiguous run of loaded tick arrays.
+/// A swap that would leave `[lower, upper)` fails, as it would on-chain without more arrays.
+#[derive(Debug, Clone, Default)]
+pub struct TickRange {
+    pub ticks: Vec<InitializedTick>,
+    pub lower: i32,
+    pub upper: i32,
+}
+
+impl TickRange {
+    pub fn from_arrays(arrays: &[TickArray], tick_spacing: u16) -> Self {
+        let span = TICK_ARRAY_SIZE as i32 * tick_spacing as i32;
+        let mut ticks: Vec<InitializedTick> = arrays
+            .iter()
+            .flat_map(|array| {
+                array.ticks.iter().enumerate().filter_map(move |(offset, net)| {
+                    net.map(|liquidity_net| InitializedTick {
+                        index: array.start_tick_index + offset as i32 * tick_spacing as i32,
+                        liquidity_net,
+                    })
+                })
+            })
+            .collect();
+        ticks.sort_by_key(|t| t.index);
+        ticks.dedup_by_key(|t| t.index);
+
+        let lower = arrays.iter().map(|a| a.start_tick_index).min().unwrap_or(0);
+        let upper = arrays.iter().map(|a| a.start_tick_index + span).max().unwrap_or(0);
+        Self { ticks, lower, upper }
+    }
+
+    /// Next tick the price reaches: at or below `tick` when selling A, strictly above it otherwise
+    fn next_tick(&self, tick: i32, a_to_b: bool) -> Option<InitializedTick> {
+        if a_to_b {
+            self.ticks.iter().rev().find(|t| t.index <= tick).copied()
+        } else {
+            self.ticks.iter().find(|t| t.index > tick).copied()
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Swap
+// ---------------------------------------------------------------------------
+
+/// Pool state the swap starts from
+#[derive(Debug, Clone, Copy)]
+pub struct ClmmState {
+    pub sqrt_price_x64: u128,
+    pub tick_current_index: i32,
+    pub liquidity: u128,
+    /// Hundredths of a basis point (3000 = 0.30%)
+    pub fee_rate: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClmmSwapResult {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee_amount: u64,
+    pub end_sqrt_price_x64: u128,
+    pub end_tick_index: i32,
+    pub end_liquidity: u128,
+    pub ticks_crossed: u32,
+}
+
+/// Exact-input swap across initialized ticks. None if the swap runs past the loaded
+/// tick arrays, hits the price bounds with input left over, or overflows.
+pub fn swap_exact_in(state: &ClmmState, ticks: &TickRange, amount_in: u64, a_to_b: bool) -> Option<ClmmSwapResult> {
+    let fee_rate = state.fee_rate as u128;
+    if fee_rate >= FEE_RATE_DENOMINATOR {
+        return None;
+    }
+
+    let mut remaining = amount_in as u128;
+    let mut amount_out = 0u128;
+    let mut fee_total = 0u128;
+    let mut sqrt_price = state.sqrt_price_x64;
+    let mut tick_current = state.tick_current_index;
+    let mut liquidity = state.liquidity;
+    let mut ticks_crossed = 0u32;
+
+    while remaining > 0 {
+        // 1. Target: the next initialized tick, or the edge of the loaded arrays
+        let next = ticks.next_tick(tick_current, a_to_b);
+        let boundary = if a_to_b { ticks.lower } else { ticks.upper };
+        let target_tick = match next {
+            Some(t) => t.index,
+            None => boundary,
+        };
+        let target_sqrt_price = sqrt_price_from_tick_index(target_tick);
+
+        // 2. Move as far as the remaining input allows
+        let step = compute_swap_step(remaining, fee_rate, liquidity, sqrt_price, target_sqrt_price, a_to_b)?;
+        remaining = remaining.checked_sub(step.amount_in + step.fee_amount)?;
+        amount_out += step.amount_out;
+        fee_total += step.fee_amount;
+        sqrt_price = step.next_sqrt_price;
+
+        if sqrt_price != target_sqrt_price {
+            tick_current = tick_index_from_sqrt_price(sqrt_price);
+            break;
+        }
+
+        // 3. Reached the target: cross it, or give up at the edge of the data
+        let crossed = next?;
+        liquidity = if a_to_b {
+            (liquidity as i128).checked_sub(crossed.liquidity_net)?
+        } else {
+            (liquidity as i128).checked_add(crossed.liquidity_net)?
+        }
+        .try_into()
+        .ok()?;
+        tick_current = if a_to_b { crossed.index - 1 } else { crossed.index };
+        ticks_crossed += 1;
+    }
+
+    Some(ClmmSwapResult {
+        amount_in: (amount_in as u128 - remaining).try_into().ok()?,
+        amount_out: amount_out.try_into().ok()?,
+        fee_amount: fee_total.try_into().ok()?,
+        end_sqrt_price_x64: sqrt_price,
+        end_tick_index: tick_current,
+        end_liquidity: liquidity,
+        ticks_crossed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_at_tick(tick: i32, liquidity: u128) -> ClmmState {
+        ClmmState {
+            sqrt_price_x64: sqrt_price_from_tick_index(tick),
+            tick_current_index: tick,
+            liquidity,
+            fee_rate: 3000,
+        }
+    }
+
+    fn range(ticks: &[(i32, i128)], lower: i32, upper: i32) -> TickRange {
+        TickRange {
+            ticks: ticks.iter().map(|&(index, liquidity_net)| InitializedTick { index, liquidity_net }).collect(),
+            lower,
+            upper,
+        }
+    }
+
+    #[test]
+    fn test_tick_math_matches_float_and_round_trips() {
+        assert_eq!(sqrt_price_from_tick_index(0), Q64);
+        for tick in [MIN_TICK_INDEX, -50_000, -1, 1, 7, 64, 12_345, 200_000, MAX_TICK_INDEX] {
+            let exact = sqrt_price_from_tick_index(tick) as f64 / Q64 as f64;
+            let expected = 1.0001f64.powf(tick as f64 / 2.0);
+            assert!((exact / expected - 1.0).abs() < 1e-9, "tick {}", tick);
+            assert_eq!(tick_index_from_sqrt_price(sqrt_price_from_tick_index(tick)), tick);
+        }
+        let max = sqrt_price_from_tick_index(MAX_TICK_INDEX) as f64;
+        assert!((max / MAX_SQRT_PRICE_X64 as f64 - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_single_tick_swap_matches_constant_product() {
+        let liquidity = 1_000_000_000_000u128;
+        let state = state_at_tick(0, liquidity);
+        let ticks = range(&[], -8 * 88, 8 * 88);
+
+        let result = swap_exact_in(&state, &ticks, 1_000_000, true).unwrap();
+        assert_eq!(result.ticks_crossed, 0);
+        assert_eq!(result.amount_in, 1_000_000);
+
+        // At price 1 the virtual reserves are both L
+        let net_in = 1_000_000u128 * 997 / 1000;
+        let cpmm = liquidity * net_in / (liquidity + net_in);
+        assert!((result.amount_out as i128 - cpmm as i128).abs() <= 2);
+        assert!(result.end_sqrt_price_x64 < state.sqrt_price_x64);
+    }
+
+    #[test]
+    fn test_crossing_tick_drops_liquidity() {
+        let liquidity = 1_000_000_000u128;
+        let state = state_at_tick(10, liquidity);
+        // Position [-10, 20) holds all the liquidity; below -10 it thins out
+        let ticks = range(&[(-10, 900_000_000), (20, -900_000_000)], -704, 704);
+
+        // Enough A to exhaust the range above -10 and spill into the thin part
+        let to_boundary = amount_a_delta(sqrt_price_from_tick_index(-10), state.sqrt_price_x64, liquidity, true).unwrap() as u64;
+        let amount_in = to_boundary * 2;
+
+        let exact = swap_exact_in(&state, &ticks, amount_in, true).unwrap();
+        assert_eq!(exact.ticks_crossed, 1);
+        assert_eq!(exact.end_liquidity, 100_000_000);
+        assert!(exact.end_tick_index < -10);
+
+        // Single-curve approximation ignores the liquidity cliff and over-quotes
+        let approx = crate::math::get_amount_out_clmm(amount_in, state.sqrt_price_x64, liquidity, 30, true);
+        assert!(approx > exact.amount_out);
+    }
+
+    #[test]
+    fn test_swap_past_loaded_arrays_fails() {
+        let state = state_at_tick(0, 1_000_000);
+        let ticks = range(&[], -88, 88);
+        assert!(swap_exact_in(&state, &ticks, u64::MAX / 2, false).is_none());
+        assert!(swap_exact_in(&state, &ticks, 10, false).is_some());
+    }
+
+    #[test]
+    fn test_decode_tick_array() {
+        let mut data = vec![0u8; TICK_ARRAY_ACCOUNT_LEN];
+        data[TICK_ARRAY_START_OFFSET..TICKS_OFFSET].copy_from_slice(&(-5632i32).to_le_bytes());
+        let base = TICKS_OFFSET + 3 * TICK_SIZE;
+        data[base] = 1;
+        data[base + 1..base + 17].copy_from_slice(&(-42i128).to_le_bytes());
+
+        let array = TickArray::decode(&data).unwrap();
+        assert_eq!(array.start_tick_index, -5632);
+        let range = TickRange::from_arrays(&[array], 64);
+        assert_eq!(range.ticks, vec![InitializedTick { index: -5632 + 3 * 64, liquidity_net: -42 }]);
+        assert_eq!((range.lower, range.upper), (-5632, 0));
+        assert!(TickArray::decode(&data[..100]).is_none());
+    }
+}
@@ -0,0 +1,140 @@
+/// Venue-agnostic limit order book, as decoded from an on-chain CLOB market.
+///
+/// Prices are integers in quote atoms per base lot, so a fill never needs
+/// floating point: `lots * price` is the exact quote amount before fees.
+/// Taker fees are charged on the quote side, which is how the Solana CLOBs
+/// charge them.
+use solana_sdk::pubkey::Pubkey;
+
+/// Aggregated resting size at one price
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Level {
+    /// Quote atoms per base lot
+    pub price: u64,
+    pub lots: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OrderBook {
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    /// Base atoms per lot
+    pub base_lot_size: u64,
+    pub taker_fee_bps: u16,
+    /// Best (highest) first
+    pub bids: Vec<Level>,
+    /// Best (lowest) first
+    pub asks: Vec<Level>,
+}
+
+impl OrderBook {
+    pub fn best_bid(&self) -> Option<Level> {
+        self.bids.first().copied()
+    }
+
+    pub fn best_ask(&self) -> Option<Level> {
+        self.asks.first().copied()
+    }
+
+    /// Exact-in taker fill from the top of the book down. Selling base walks the
+    /// bids, buying base walks the asks. Input smaller than a lot is left unspent.
+    /// Returns 0 if the book can't take the whole order.
+    pub fn get_amount_out(&self, amount_in: u64, base_in: bool) -> u64 {
+        if self.base_lot_size == 0 {
+            return 0;
+        }
+        let fee_bps = self.taker_fee_bps.min(10_000) as u128;
+        if base_in {
+            let mut lots = amount_in / self.base_lot_size;
+            let mut gross: u128 = 0;
+            for level in &self.bids {
+                if lots == 0 {
+                    break;
+                }
+                let take = lots.min(level.lots);
+                gross += take as u128 * level.price as u128;
+                lots -= take;
+            }
+            if lots > 0 {
+                return 0;
+            }
+            let fee = (gross * fee_bps).div_ceil(10_000);
+            (gross - fee).min(u64::MAX as u128) as u64
+        } else {
+            // The fee comes on top of the matched quote, so only part of the input buys base
+            let mut budget = amount_in as u128 * 10_000 / (10_000 + fee_bps);
+            let mut lots: u128 = 0;
+            let mut last_price = 0;
+            for level in self.asks.iter().filter(|l| l.price > 0) {
+                let take = (budget / level.price as u128).min(level.lots as u128);
+                lots += take;
+                budget -= take * level.price as u128;
+                last_price = level.price as u128;
+                if take < level.lots as u128 {
+                    break;
+                }
+            }
+            // Every ask taken with enough left over to buy more
+            if last_price == 0 || budget >= last_price {
+                return 0;
+            }
+            (lots * self.base_lot_size as u128).min(u64::MAX as u128) as u64
+        }
+    }
+
+    /// Marginal rate at the touch after the taker fee, in output atoms per input atom.
+    /// What the first lot fills at, unlike the mid price of `virtual_reserves`.
+    pub fn touch_rate(&self, base_in: bool) -> f64 {
+        if self.base_lot_size == 0 {
+            return 0.0;
+        }
+        let fee = self.taker_fee_bps.min(10_000) as f64 / 10_000.0;
+        let level = if base_in { self.best_bid() } else { self.best_ask() };
+        match level {
+            Some(level) if level.price > 0 && base_in => level.price as f64 / self.base_lot_size as f64 * (1.0 - fee),
+            Some(level) if level.price > 0 => self.base_lot_size as f64 / level.price as f64 / (1.0 + fee),
+            _ => 0.0,
+        }
+    }
+
+    /// Input that takes every resting order on the side a swap in this direction hits
+    pub fn depth_in(&self, base_in: bool) -> u64 {
+        let depth: u128 = if base_in {
+            self.bids.iter().map(|l| l.lots as u128 * self.base_lot_size as u128).sum()
+        } else {
+            let quote: u128 = self.asks.iter().map(|l| l.lots as u128 * l.price as u128).sum();
+            quote * (10_000 + self.taker_fee_bps.min(10_000) as u128) / 10_000
+        };
+        depth.min(u64::MAX as u128) as u64
+    }
+
+    /// The book as a constant-product pool at the mid price (base atoms, quote atoms),
+    /// for consumers that only read reserves. Quoting goes through `get_amount_out`.
+    pub fn virtual_reserves(&self) -> (u64, u64) {
+        let mid = match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => (bid.price as u128 + ask.price as u128) / 2,
+            (Some(level), None) | (None, Some(level)) => level.price as u128,
+            (None, None) => return (0, 0),
+        };
+        let lots: u128 = self.bids.iter().chain(&self.asks).map(|l| l.lots as u128).sum();
+        let base = lots * self.base_lot_size as u128;
+        let quote = lots * mid;
+        (base.min(u64::MAX as u128) as u64, quote.min(u64::MAX as u128) as u64)
+    }
+}
+
+/// Merges orders into levels, best first. Orders arrive as (price, lots) in any order.
+pub fn aggregate_levels(orders: impl IntoIterator<Item = (u64, u64)>, descending: bool) -> Vec<Level> {
+    let mut levels: std::collections::BTreeMap<u64, u64> = std::collections::BTreeMap::new();
+    for (price, lots) in orders {
+        if lots > 0 {
+            *levels.entry(price).or_default() += lots;
+        }
+    }
+    let levels = levels.into_iter().map(|(price, lots)| Level { price, lots });
+    if descending {
+        levels.rev().collect()
+    } else {
+        levels.collect()
+    }
+}
@@ -0,0 +1,176 @@
+/// Unified Time Source
+///
+/// One place that maps monotonic time onto unix time and chain slots. Unix
+/// time is anchored once and advanced by the monotonic clock, so wall-clock
+/// steps never reorder timestamps. Slots are extrapolated from the last slot
+/// the chain reported (400ms per slot), or from unix time before any arrive.
+/// Live code uses the shared `system()` clock; replays and tests drive a
+/// `ManualClock` so recorded data lands on the same timeline every run.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use lazy_static::lazy_static;
+
+pub const SLOT_DURATION_MS: u64 = 400;
+
+pub trait Clock: Send + Sync {
+    /// Monotonic now; never goes backwards
+    fn now(&self) -> Instant;
+
+    /// Milliseconds since the unix epoch, on the same timeline as `now()`
+    fn unix_ms(&self) -> u64;
+
+    /// Last slot reported by the chain, extrapolated to `now()`
+    fn slot(&self) -> u64;
+
+    /// Anchors chain time: `slot` was observed just now
+    fn observe_slot(&self, slot: u64);
+
+    fn unix_secs(&self) -> u64 {
+        self.unix_ms() / 1000
+    }
+
+    /// Unix time of `instant`, as long as it came from this clock
+    fn unix_ms_at(&self, instant: Instant) -> u64 {
+        let now = self.now();
+        let unix_now = self.unix_ms();
+        if instant >= now {
+            unix_now + (instant - now).as_millis() as u64
+        } else {
+            unix_now.saturating_sub((now - instant).as_millis() as u64)
+        }
+    }
+}
+
+/// Latest (slot, instant) pair the chain gave us
+#[derive(Default)]
+struct SlotAnchor(RwLock<Option<(u64, Instant)>>);
+
+impl SlotAnchor {
+    fn observe(&self, slot: u64, at: Instant) {
+        let mut anchor = self.0.write().unwrap_or_else(|e| e.into_inner());
+        // Out-of-order notifications must not pull chain time back
+        if anchor.is_none_or(|(last, _)| slot > last) {
+            *anchor = Some((slot, at));
+        }
+    }
+
+    fn slot_at(&self, at: Instant, unix_ms: u64) -> u64 {
+        match *self.0.read().unwrap_or_else(|e| e.into_inner()) {
+            Some((slot, anchored)) => slot + (at.saturating_duration_since(anchored).as_millis() as u64) / SLOT_DURATION_MS,
+            None => unix_ms / SLOT_DURATION_MS,
+        }
+    }
+}
+
+/// Wall clock read once at startup, advanced monotonically afterwards
+pub struct SystemClock {
+    origin: Instant,
+    origin_unix_ms: u64,
+    slots: SlotAnchor,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+            origin_unix_ms: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64,
+            slots: SlotAnchor::default(),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn unix_ms(&self) -> u64 {
+        self.origin_unix_ms + self.origin.elapsed().as_millis() as u64
+    }
+
+    fn slot(&self) -> u64 {
+        self.slots.slot_at(self.now(), self.unix_ms())
+    }
+
+    fn observe_slot(&self, slot: u64) {
+        self.slots.observe(slot, self.now());
+    }
+}
+
+/// Clock that only moves when told to (replays and tests)
+pub struct ManualClock {
+    origin: Instant,
+    origin_unix_ms: u64,
+    elapsed_ms: AtomicU64,
+    slots: SlotAnchor,
+}
+
+impl ManualClock {
+    pub fn new(unix_ms: u64) -> Self {
+        Self { origin: Instant::now(), origin_unix_ms: unix_ms, elapsed_ms: AtomicU64::new(0), slots: SlotAnchor::default() }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.elapsed_ms.fetch_add(by.as_millis() as u64, Ordering::SeqCst);
+    }
+
+    /// Moves to `unix_ms`; earlier times are ignored so the clock stays monotonic
+    pub fn set_unix_ms(&self, unix_ms: u64) {
+        self.elapsed_ms.fetch_max(unix_ms.saturating_sub(self.origin_unix_ms), Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.origin + Duration::from_millis(self.elapsed_ms.load(Ordering::SeqCst))
+    }
+
+    fn unix_ms(&self) -> u64 {
+        self.origin_unix_ms + self.elapsed_ms.load(Ordering::SeqCst)
+    }
+
+    fn slot(&self) -> u64 {
+        self.slots.slot_at(self.now(), self.unix_ms())
+    }
+
+    fn observe_slot(&self, slot: u64) {
+        self.slots.observe(slot, self.now());
+    }
+}
+
+lazy_static! {
+    static ref SYSTEM: Arc<SystemClock> = Arc::new(SystemClock::default());
+}
+
+/// Process-wide live clock; ingestion feeds it slots
+pub fn system() -> Arc<dyn Clock> {
+    SYSTEM.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_maps_slots_and_unix_time() {
+        let clock = ManualClock::new(1_700_000_000_000);
+        let start = clock.now();
+
+        // No chain anchor yet: slots follow unix time
+        assert_eq!(clock.slot(), 1_700_000_000_000 / SLOT_DURATION_MS);
+
+        clock.observe_slot(250_000_000);
+        clock.advance(Duration::from_millis(1_000));
+        assert_eq!((clock.slot(), clock.unix_secs()), (250_000_002, 1_700_000_001));
+        assert_eq!(clock.unix_ms_at(start), 1_700_000_000_000);
+
+        // Stale slots and earlier times never move the clock back
+        clock.observe_slot(249_999_999);
+        clock.set_unix_ms(1_600_000_000_000);
+        assert_eq!((clock.slot(), clock.unix_ms()), (250_000_002, 1_700_000_001_000));
+
+        clock.set_unix_ms(1_700_000_004_000);
+        assert_eq!(clock.now() - start, Duration::from_secs(4));
+    }
+}
@@ -0,0 +1,128 @@
+use solana_sdk::pubkey::Pubkey;
+
+/// Trade fee of the default LaunchLab global config (0.25%); platform fees come on top
+pub const DEFAULT_TRADE_FEE_BPS: u16 = 25;
+
+/// Smallest `PoolState` we can read (discriminator + fields up to `creator`)
+pub const POOL_STATE_MIN_LEN: usize = 365;
+
+/// Raydium LaunchLab bonding-curve pool (`PoolState`).
+/// Only constant-product curves are modelled: price moves along
+/// (virtual_base - real_base) * (virtual_quote + real_quote) = k.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchLabPool {
+    pub status: u8,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+    pub supply: u64,
+    pub total_base_sell: u64,
+    pub virtual_base: u64,
+    pub virtual_quote: u64,
+    pub real_base: u64,
+    pub real_quote: u64,
+    pub total_quote_fund_raising: u64,
+    pub global_config: Pubkey,
+    pub platform_config: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub creator: Pubkey,
+}
+
+impl LaunchLabPool {
+    /// Parses the raw account, Anchor discriminator included
+    pub fn from_account_data(data: &[u8]) -> Result<Self, String> {
+        if data.len() < POOL_STATE_MIN_LEN {
+            return Err(format!("Account too small: {} bytes (need at least {})", data.len(), POOL_STATE_MIN_LEN));
+        }
+        let u64_at = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        let key_at = |offset: usize| Pubkey::new_from_array(data[offset..offset + 32].try_into().unwrap());
+
+        Ok(Self {
+            status: data[17],
+            base_decimals: data[18],
+            quote_decimals: data[19],
+            supply: u64_at(21),
+            total_base_sell: u64_at(29),
+            virtual_base: u64_at(37),
+            virtual_quote: u64_at(45),
+            real_base: u64_at(53),
+            real_quote: u64_at(61),
+            total_quote_fund_raising: u64_at(69),
+            global_config: key_at(141),
+            platform_config: key_at(173),
+            base_mint: key_at(205),
+            quote_mint: key_at(237),
+            base_vault: key_at(269),
+            quote_vault: key_at(301),
+            creator: key_at(333),
+        })
+    }
+
+    /// Status 0 = trading on the curve; anything else is migrating or migrated
+    pub fn is_trading(&self) -> bool {
+        self.status == 0
+    }
+
+    /// Effective (base, quote) reserves the curve prices against
+    pub fn virtual_reserves(&self) -> (u64, u64) {
+        (
+            self.virtual_base.saturating_sub(self.real_base),
+            self.virtual_quote.saturating_add(self.real_quote),
+        )
+    }
+
+    pub fn calculate_price_in_quote(&self) -> f64 {
+        let (base, quote) = self.virtual_reserves();
+        if base == 0 {
+            return 0.0;
+        }
+        quote as f64 / base as f64
+    }
+
+    /// Exact-in quote. Buys (quote -> base) are capped by what is left to sell on the curve.
+    pub fn get_amount_out(&self, amount_in: u64, buy: bool) -> u64 {
+        let (base, quote) = self.virtual_reserves();
+        let out = if buy {
+            crate::math::get_amount_out_cpmm(amount_in, quote, base, DEFAULT_TRADE_FEE_BPS)
+        } else {
+            crate::math::get_amount_out_cpmm(amount_in, base, quote, DEFAULT_TRADE_FEE_BPS)
+        };
+        if buy {
+            out.min(self.total_base_sell.saturating_sub(self.real_base))
+        } else {
+            out.min(self.real_quote)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launchlab_pool_layout_and_quote() {
+        let mut data = vec![0u8; 429];
+        data[21..29].copy_from_slice(&1_000_000_000_000_000u64.to_le_bytes()); // supply
+        data[29..37].copy_from_slice(&793_100_000_000_000u64.to_le_bytes());   // total_base_sell
+        data[37..45].copy_from_slice(&1_073_025_605_596_382u64.to_le_bytes()); // virtual_base
+        data[45..53].copy_from_slice(&30_000_852_951u64.to_le_bytes());       // virtual_quote
+        let base_mint = Pubkey::new_unique();
+        data[205..237].copy_from_slice(base_mint.as_ref());
+        data[237..269].copy_from_slice(crate::constants::SOL_MINT.as_ref());
+
+        let pool = LaunchLabPool::from_account_data(&data).unwrap();
+        assert!(pool.is_trading());
+        assert_eq!((pool.base_mint, pool.quote_mint), (base_mint, crate::constants::SOL_MINT));
+        assert_eq!(pool.virtual_reserves(), (1_073_025_605_596_382, 30_000_852_951));
+
+        // 1 SOL buys roughly 3.4% of the virtual base reserve
+        let tokens = pool.get_amount_out(1_000_000_000, true);
+        assert!(tokens > 34_000_000_000_000 && tokens < 35_000_000_000_000, "{}", tokens);
+        // Nothing has been bought yet, so there is no SOL to sell into
+        assert_eq!(pool.get_amount_out(tokens, false), 0);
+
+        assert!(LaunchLabPool::from_account_data(&data[..100]).is_err());
+    }
+}
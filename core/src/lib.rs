@@ -1,10 +1,21 @@
+#![recursion_limit = "256"]
+
 pub mod raydium;
 pub mod orca;
+pub mod raydium_clmm;
 pub mod meteora;
+pub mod clob;
+pub mod phoenix;
+pub mod openbook;
+pub mod pyth;
 pub mod math;
+pub mod clmm;
 pub mod pump_fun;
+pub mod launchlab;
+pub mod moonshot;
 pub mod telemetry;
 pub mod pool_weight;
+pub mod clock;
 
 use serde::{Serialize, Deserialize};
 use solana_sdk::pubkey::Pubkey;
@@ -45,7 +56,53 @@ pub struct MarketUpdate {
     pub pc_reserve: u64,
     pub price_sqrt: Option<u128>, // CLMM support
     pub liquidity: Option<u128>,  // CLMM support
+    #[serde(default)]
+    pub fee_bps: Option<u16>,     // Fee tier, when the pool account stores its own (Whirlpool)
     pub timestamp: i64,
+    /// Where the update came from; routes found from it are attributed to this
+    #[serde(default)]
+    pub trigger: Trigger,
+}
+
+/// What set off the search that found a route, for PnL attribution
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    /// A watched pool's account changed
+    #[default]
+    AccountUpdate,
+    /// First state of a newly discovered pool
+    Discovery,
+    /// A large swap that already landed on the pool
+    Backrun,
+    /// Per-slot re-pricing of a cached cycle
+    Reprice,
+    /// Periodic full cycle search
+    Sweep,
+}
+
+impl Trigger {
+    pub const ALL: [Trigger; 5] = [Trigger::AccountUpdate, Trigger::Discovery, Trigger::Backrun, Trigger::Reprice, Trigger::Sweep];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Trigger::AccountUpdate => "account_update",
+            Trigger::Discovery => "discovery",
+            Trigger::Backrun => "backrun",
+            Trigger::Reprice => "reprice",
+            Trigger::Sweep => "sweep",
+        }
+    }
+
+    /// The `STRATEGY_SCHEDULE` strategy that runs searches from this trigger
+    pub fn strategy(&self) -> &'static str {
+        match self {
+            Trigger::AccountUpdate | Trigger::Discovery => "arb",
+            Trigger::Backrun => "backrun",
+            Trigger::Reprice => "reprice",
+            Trigger::Sweep => "sweep",
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -57,7 +114,7 @@ pub struct SwapStep {
     pub expected_output: u64, // Added to track amount through multi-hop
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ArbitrageOpportunity {
     pub steps: SmallVec<[SwapStep; 8]>,
     pub expected_profit_lamports: u64,
@@ -72,6 +129,75 @@ pub struct ArbitrageOpportunity {
     // Metadata for Success Library (Phase 6)
     pub initial_liquidity_lamports: Option<u64>,
     pub launch_hour_utc: Option<u8>,
+
+    /// Input borrowed through a flash loan instead of taken from inventory (0 = none)
+    #[serde(default)]
+    pub flash_loan_amount: u64,
+    /// Expected value at the EV gate (lamports), once the route has reached it
+    #[serde(default)]
+    pub expected_value_lamports: Option<i64>,
+    /// Input of every step when a leg is split across pools; empty = each step swaps the previous one's output
+    #[serde(default)]
+    pub step_inputs: SmallVec<[u64; 8]>,
+    /// Profit valued in SOL lamports when the cycle starts from another mint; None = already lamports
+    #[serde(default)]
+    pub profit_sol_lamports: Option<u64>,
+    /// What set off the search that found the route
+    #[serde(default)]
+    pub trigger: Trigger,
+    /// Model win probability at the AI gate; None when no model scored the route
+    #[serde(default)]
+    pub ai_confidence: Option<f32>,
+    /// Success-library match score at the DNA gate, once the route has reached it
+    #[serde(default)]
+    pub dna_score: Option<u64>,
+}
+
+impl ArbitrageOpportunity {
+    /// Floor on the last hop's output. A cycle must return its input less slippage; a
+    /// one-way route (unwinding a token) must return its quoted output less slippage.
+    pub fn min_amount_out(&self, max_slippage_bps: u16) -> u64 {
+        let reference = match (self.steps.first(), self.steps.last()) {
+            (Some(first), Some(last)) if first.input_mint != last.output_mint => last.expected_output,
+            _ => self.input_amount,
+        };
+        (reference as u128 * (10000 - max_slippage_bps.min(10000)) as u128 / 10000) as u64
+    }
+
+    /// Profit in SOL lamports, whatever mint the cycle starts from. `expected_profit_lamports`
+    /// stays in the start mint's raw units: it is what the route's own amounts are quoted in.
+    pub fn profit_in_sol(&self) -> u64 {
+        self.profit_sol_lamports.unwrap_or(self.expected_profit_lamports)
+    }
+
+    /// `amount` raw units of the start mint in SOL lamports, at the rate the profit was valued at
+    pub fn start_units_in_sol(&self, amount: i64) -> i64 {
+        match self.profit_sol_lamports {
+            Some(sol) if self.expected_profit_lamports > 0 => (amount as f64 * sol as f64 / self.expected_profit_lamports as f64) as i64,
+            _ => amount,
+        }
+    }
+
+    /// Mint the route's input and profit are denominated in
+    pub fn start_mint(&self) -> Option<Pubkey> {
+        self.steps.first().map(|step| step.input_mint)
+    }
+
+    /// Amount step `i` swaps in
+    pub fn step_input(&self, i: usize) -> u64 {
+        if let Some(&amount) = self.step_inputs.get(i) {
+            return amount;
+        }
+        match i.checked_sub(1) {
+            Some(previous) => self.steps[previous].expected_output,
+            None => self.input_amount,
+        }
+    }
+}
+
+/// A route's legs: consecutive steps on the same pair are one leg spread over several pools
+pub fn route_legs(steps: &[SwapStep]) -> impl Iterator<Item = &[SwapStep]> {
+    steps.chunk_by(|a, b| a.input_mint == b.input_mint && a.output_mint == b.output_mint)
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
@@ -89,7 +215,9 @@ pub mod constants {
     
     pub const RAYDIUM_V4_PROGRAM: Pubkey = pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
     pub const ORCA_WHIRLPOOL_PROGRAM: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+    pub const RAYDIUM_CLMM_PROGRAM: Pubkey = pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
     pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+    pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
 
     // Token Mints
     pub const SOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
@@ -108,8 +236,25 @@ pub mod constants {
     // Discovery Constants
     pub const PUMP_FUN_PROGRAM: Pubkey = pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
     pub const METEORA_PROGRAM_ID: Pubkey = pubkey!("LbSndVRSRBrs9P2ra3Sg949UasT5pU832A87W5YyWvM");
+    pub const PHOENIX_PROGRAM: Pubkey = pubkey!("PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY");
+    pub const OPENBOOK_V2_PROGRAM: Pubkey = pubkey!("opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb");
     pub const RAYDIUM_AMM_LOG_TRIGGER: &str = "initialize2";
     pub const PUMP_FUN_LOG_TRIGGER: &str = "Create";
+    pub const RAYDIUM_LAUNCHLAB_PROGRAM: Pubkey = pubkey!("LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj");
+    pub const MOONSHOT_PROGRAM: Pubkey = pubkey!("MoonCVVNZFSYkqNXP6bxHLPL6QQJiMagDL3qcqUQTrG");
+    // Launchpad triggers are only matched inside their own program's invocation
+    pub const LAUNCHLAB_LOG_TRIGGER: &str = "Instruction: Initialize";
+    pub const MOONSHOT_LOG_TRIGGER: &str = "Instruction: TokenMint";
+
+    /// Pools priced from sqrt_price/liquidity rather than reserves
+    pub fn is_clmm(program_id: &Pubkey) -> bool {
+        *program_id == ORCA_WHIRLPOOL_PROGRAM || *program_id == RAYDIUM_CLMM_PROGRAM
+    }
+
+    /// Order-book venues, quoted from a decoded book rather than reserves
+    pub fn is_clob(program_id: &Pubkey) -> bool {
+        *program_id == PHOENIX_PROGRAM || *program_id == OPENBOOK_V2_PROGRAM
+    }
 }
 
 /// A "Success Story" or "Library Entry" represents the DNA of a profitable trade
@@ -40,6 +40,31 @@ pub fn calculate_effective_price(
     amount_out as f64 / amount_in as f64
 }
 
+/// Pump.fun-style bonding curve on virtual reserves. The fee is charged in SOL:
+/// on top of the cost when buying, out of the proceeds when selling.
+#[inline(always)]
+pub fn get_amount_out_bonding_curve(
+    amount_in: u64,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    fee_bps: u16,
+    buy: bool,
+) -> u64 {
+    if amount_in == 0 || virtual_sol_reserves == 0 || virtual_token_reserves == 0 {
+        return 0;
+    }
+    let (sol, tokens) = (virtual_sol_reserves as u128, virtual_token_reserves as u128);
+
+    if buy {
+        // Budget covers cost + fee
+        let sol_in = amount_in as u128 * 10000 / (10000 + fee_bps as u128);
+        (tokens * sol_in / (sol + sol_in)) as u64
+    } else {
+        let sol_out = sol * amount_in as u128 / (tokens + amount_in as u128);
+        (sol_out * (10000 - fee_bps as u128) / 10000) as u64
+    }
+}
+
 /// Placeholder for Concentrated Liquidity (CLMM) math (e.g., Orca Whirlpool).
 /// This is significantly more complex and usually involves tick traversal.
 /// Implementation of simplified CLMM math using virtual reserves for high-frequency discovery.
@@ -106,6 +131,20 @@ mod tests {
         assert!(impact > 0.09 && impact < 0.10);
     }
 
+    #[test]
+    fn test_bonding_curve_round_trip_loses_fees() {
+        // Fresh Pump.fun curve: 30 SOL / 1.073B tokens (6 decimals)
+        let (sol, tokens) = (30_000_000_000u64, 1_073_000_000_000_000u64);
+        let bought = get_amount_out_bonding_curve(1_000_000_000, sol, tokens, 100, true);
+        // ~1/31 of the token reserve, less the 1% fee
+        assert!(bought > 34_000_000_000_000 && bought < 34_300_000_000_000, "{}", bought);
+
+        let (sol_after, tokens_after) = (sol + 990_099_009, tokens - bought);
+        let sold = get_amount_out_bonding_curve(bought, sol_after, tokens_after, 100, false);
+        assert!(sold < 1_000_000_000 && sold > 970_000_000, "{}", sold);
+        assert_eq!(get_amount_out_bonding_curve(1, 0, tokens, 100, true), 0);
+    }
+
     #[test]
     fn test_clmm_math_accurate() {
         let amount_in = 1_000_000u64; // 1 USDC
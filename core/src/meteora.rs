@@ -1,12 +1,16 @@
 use bytemuck::{Pod, Zeroable};
 use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+
+/// Size of the `LbPair` account, discriminator included
+pub const LB_PAIR_LEN: usize = 904;
 
 /// Meteora DLMM (Dynamic Liquidity Market Maker) pool structure
 /// Uses bin-based liquidity for concentrated liquidity
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct MeteoraDLMM {
-    pub data: [u8; 1024],
+    pub data: [u8; LB_PAIR_LEN],
 }
 
 unsafe impl Zeroable for MeteoraDLMM {}
@@ -15,32 +19,56 @@ unsafe impl Pod for MeteoraDLMM {}
 impl MeteoraDLMM {
     #[inline(always)]
     pub fn token_x_mint(&self) -> Pubkey {
-        // Offset for token X mint (needs to be verified with actual Meteora layout)
-        Pubkey::new_from_array(self.data[8..40].try_into().unwrap())
+        Pubkey::new_from_array(self.data[88..120].try_into().unwrap())
     }
 
     #[inline(always)]
     pub fn token_y_mint(&self) -> Pubkey {
-        // Offset for token Y mint
-        Pubkey::new_from_array(self.data[40..72].try_into().unwrap())
+        Pubkey::new_from_array(self.data[120..152].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn reserve_x(&self) -> Pubkey {
+        Pubkey::new_from_array(self.data[152..184].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn reserve_y(&self) -> Pubkey {
+        Pubkey::new_from_array(self.data[184..216].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn oracle(&self) -> Pubkey {
+        Pubkey::new_from_array(self.data[552..584].try_into().unwrap())
     }
 
     #[inline(always)]
     pub fn active_bin_id(&self) -> i32 {
         // Current active bin ID
-        i32::from_le_bytes(self.data[72..76].try_into().unwrap())
+        i32::from_le_bytes(self.data[76..80].try_into().unwrap())
     }
 
     #[inline(always)]
     pub fn bin_step(&self) -> u16 {
         // Price step between bins in basis points
-        u16::from_le_bytes(self.data[76..78].try_into().unwrap())
+        u16::from_le_bytes(self.data[80..82].try_into().unwrap())
     }
 
+    /// Base fee in basis points, rounded up.
+    /// On-chain: base_factor * bin_step * 10 * 10^power at 1e9 precision.
+    /// The volatility-driven variable fee is not included.
     #[inline(always)]
     pub fn base_fee_rate(&self) -> u16 {
-        // Base fee in basis points
-        u16::from_le_bytes(self.data[78..80].try_into().unwrap())
+        let base_factor = u16::from_le_bytes(self.data[8..10].try_into().unwrap()) as u128;
+        let power = self.data[34] as u32;
+        let rate = base_factor * self.bin_step() as u128 * 10 * 10u128.pow(power);
+        rate.div_ceil(100_000).min(10_000) as u16
+    }
+
+    /// Bin arrays around the active bin: the active one and one on each side
+    pub fn bin_array_window(&self) -> [i64; 3] {
+        let active = BinArray::index_for_bin(self.active_bin_id());
+        [active - 1, active, active + 1]
     }
 
     /// Calculate price from bin ID
@@ -91,3 +119,236 @@ pub struct MeteoraSwapKeys {
     pub user_token_y: Pubkey,
     pub user_owner: Pubkey,
 }
+
+/// Bins per `BinArray` account
+pub const MAX_BIN_PER_ARRAY: usize = 70;
+const BIN_LEN: usize = 144;
+const BINS_OFFSET: usize = 56;
+/// Size of the `BinArray` account, discriminator included
+pub const BIN_ARRAY_LEN: usize = BINS_OFFSET + MAX_BIN_PER_ARRAY * BIN_LEN;
+
+/// Liquidity held in one bin. `price` is Y per X in raw units, Q64.64.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Bin {
+    pub amount_x: u64,
+    pub amount_y: u64,
+    pub price: u128,
+}
+
+/// One `BinArray` account: 70 consecutive bins starting at `index * 70`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinArray {
+    pub index: i64,
+    pub lb_pair: Pubkey,
+    pub bins: Vec<Bin>,
+}
+
+impl BinArray {
+    /// Parses the raw account, Anchor discriminator included
+    pub fn from_account_data(data: &[u8]) -> Result<Self, String> {
+        if data.len() < BIN_ARRAY_LEN {
+            return Err(format!("Account too small: {} bytes (need at least {})", data.len(), BIN_ARRAY_LEN));
+        }
+        let bins = (0..MAX_BIN_PER_ARRAY)
+            .map(|i| {
+                let bin = &data[BINS_OFFSET + i * BIN_LEN..];
+                Bin {
+                    amount_x: u64::from_le_bytes(bin[0..8].try_into().unwrap()),
+                    amount_y: u64::from_le_bytes(bin[8..16].try_into().unwrap()),
+                    price: u128::from_le_bytes(bin[16..32].try_into().unwrap()),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            index: i64::from_le_bytes(data[8..16].try_into().unwrap()),
+            lb_pair: Pubkey::new_from_array(data[24..56].try_into().unwrap()),
+            bins,
+        })
+    }
+
+    pub fn index_for_bin(bin_id: i32) -> i64 {
+        (bin_id as i64).div_euclid(MAX_BIN_PER_ARRAY as i64)
+    }
+
+    pub fn derive_pda(lb_pair: &Pubkey, index: i64, program_id: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"bin_array", lb_pair.as_ref(), &index.to_le_bytes()], program_id).0
+    }
+
+    /// Bin id of the first bin in this array
+    pub fn lower_bin_id(&self) -> i32 {
+        (self.index * MAX_BIN_PER_ARRAY as i64) as i32
+    }
+}
+
+/// What the strategy needs to quote a DLMM pair: its fee, active bin and the bins loaded around it
+#[derive(Debug, Clone, Default)]
+pub struct DlmmLiquidity {
+    pub token_x_mint: Pubkey,
+    pub token_y_mint: Pubkey,
+    pub active_id: i32,
+    pub fee_bps: u16,
+    pub bins: BTreeMap<i32, Bin>,
+}
+
+impl DlmmLiquidity {
+    /// Bins that were never initialised carry no price; theirs is derived from the bin id
+    pub fn new(pair: &MeteoraDLMM, arrays: &[BinArray]) -> Self {
+        let mut bins = BTreeMap::new();
+        for array in arrays {
+            for (offset, bin) in array.bins.iter().enumerate() {
+                if bin.amount_x == 0 && bin.amount_y == 0 {
+                    continue;
+                }
+                let id = array.lower_bin_id() + offset as i32;
+                let price = if bin.price > 0 {
+                    bin.price
+                } else {
+                    (pair.calculate_price_from_bin(id) * (1u128 << 64) as f64) as u128
+                };
+                bins.insert(id, Bin { price, ..*bin });
+            }
+        }
+
+        Self {
+            token_x_mint: pair.token_x_mint(),
+            token_y_mint: pair.token_y_mint(),
+            active_id: pair.active_bin_id(),
+            fee_bps: pair.base_fee_rate(),
+            bins,
+        }
+    }
+
+    pub fn get_amount_out(&self, amount_in: u64, x_to_y: bool) -> u64 {
+        get_amount_out_dlmm(amount_in, &self.bins, self.active_id, self.fee_bps, x_to_y)
+    }
+
+    /// Input needed to drain every loaded bin in the swap direction
+    pub fn depth_in(&self, x_to_y: bool) -> u64 {
+        let depth: u128 = if x_to_y {
+            self.bins.range(..=self.active_id).map(|(_, b)| shl_64_div_ceil(b.amount_y, b.price)).sum()
+        } else {
+            self.bins.range(self.active_id..).map(|(_, b)| mul_shr_64_ceil(b.amount_x, b.price)).sum()
+        };
+        depth.min(u64::MAX as u128) as u64
+    }
+}
+
+/// Exact-in quote walking bins away from the active one at each bin's fixed price:
+/// X -> Y drains Y from the active bin downward, Y -> X drains X upward.
+/// The fee comes off the input up front. Returns 0 if the loaded bins can't fill the order.
+pub fn get_amount_out_dlmm(
+    amount_in: u64,
+    bins: &BTreeMap<i32, Bin>,
+    active_id: i32,
+    fee_bps: u16,
+    x_to_y: bool,
+) -> u64 {
+    let mut remaining = (amount_in as u128 * (10000 - fee_bps.min(10000)) as u128 / 10000) as u64;
+    let mut out: u128 = 0;
+
+    let walk: Box<dyn Iterator<Item = &Bin>> = if x_to_y {
+        Box::new(bins.range(..=active_id).rev().map(|(_, b)| b))
+    } else {
+        Box::new(bins.range(active_id..).map(|(_, b)| b))
+    };
+
+    for bin in walk {
+        if remaining == 0 {
+            break;
+        }
+        if bin.price == 0 {
+            continue;
+        }
+        let (liquidity_out, max_in) = if x_to_y {
+            (bin.amount_y, shl_64_div_ceil(bin.amount_y, bin.price))
+        } else {
+            (bin.amount_x, mul_shr_64_ceil(bin.amount_x, bin.price))
+        };
+        if liquidity_out == 0 {
+            continue;
+        }
+
+        if remaining as u128 >= max_in {
+            out += liquidity_out as u128;
+            remaining -= max_in as u64;
+        } else {
+            let partial = if x_to_y {
+                mul_shr_64(remaining, bin.price)
+            } else {
+                ((remaining as u128) << 64) / bin.price
+            };
+            out += partial.min(liquidity_out as u128);
+            remaining = 0;
+        }
+    }
+
+    if remaining > 0 {
+        return 0;
+    }
+    out.min(u64::MAX as u128) as u64
+}
+
+/// (amount * price) >> 64 without overflowing for prices above 1
+fn mul_shr_64(amount: u64, price: u128) -> u128 {
+    let amount = amount as u128;
+    amount * (price >> 64) + ((amount * (price & u64::MAX as u128)) >> 64)
+}
+
+fn mul_shr_64_ceil(amount: u64, price: u128) -> u128 {
+    let rounded = (amount as u128 * (price & u64::MAX as u128)) & u64::MAX as u128 != 0;
+    mul_shr_64(amount, price) + rounded as u128
+}
+
+fn shl_64_div_ceil(amount: u64, price: u128) -> u128 {
+    if price == 0 {
+        return u128::MAX;
+    }
+    let shifted = (amount as u128) << 64;
+    shifted / price + (!shifted.is_multiple_of(price)) as u128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE: u128 = 1 << 64;
+
+    #[test]
+    fn test_dlmm_walks_bins_away_from_active() {
+        let mut data = vec![0u8; BIN_ARRAY_LEN];
+        data[8..16].copy_from_slice(&(-1i64).to_le_bytes());
+        let mut put = |id: i32, x: u64, y: u64, price: u128| {
+            let at = BINS_OFFSET + (id + 70) as usize * BIN_LEN;
+            data[at..at + 8].copy_from_slice(&x.to_le_bytes());
+            data[at + 8..at + 16].copy_from_slice(&y.to_le_bytes());
+            data[at + 16..at + 32].copy_from_slice(&price.to_le_bytes());
+        };
+        // Array -1 holds bins -70..=-1; both bins below are Y-only at prices 1.0 and 0.5
+        put(-1, 0, 1_000, ONE);
+        put(-2, 0, 1_000, ONE / 2);
+        let array = BinArray::from_account_data(&data).unwrap();
+        assert_eq!((array.index, array.lower_bin_id()), (-1, -70));
+        assert_eq!(BinArray::index_for_bin(-1), -1);
+        assert_eq!(BinArray::index_for_bin(69), 0);
+
+        let bins: BTreeMap<i32, Bin> = array.bins.iter().enumerate()
+            .filter(|(_, b)| b.amount_y > 0)
+            .map(|(i, b)| (array.lower_bin_id() + i as i32, *b))
+            .collect();
+
+        // 1000 X drains the active bin at 1.0; the next 1000 X buys 500 Y at 0.5
+        assert_eq!(get_amount_out_dlmm(1_000, &bins, -1, 0, true), 1_000);
+        assert_eq!(get_amount_out_dlmm(2_000, &bins, -1, 0, true), 1_500);
+        // 1% fee comes off the input
+        assert_eq!(get_amount_out_dlmm(2_000, &bins, -1, 100, true), 1_490);
+        // More than both bins can absorb: no quote
+        assert_eq!(get_amount_out_dlmm(3_001, &bins, -1, 0, true), 0);
+        // Nothing above the active bin to sell Y into
+        assert_eq!(get_amount_out_dlmm(100, &bins, -1, 0, false), 0);
+
+        let liquidity = DlmmLiquidity { active_id: -1, bins, ..Default::default() };
+        assert_eq!(liquidity.depth_in(true), 3_000);
+        assert!(BinArray::from_account_data(&data[..100]).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use solana_sdk::pubkey::Pubkey;
+
+/// Moonshot charges 1% on every curve trade
+pub const TRADE_FEE_BPS: u16 = 100;
+
+/// Constant-product V1 curve: virtual reserves at launch (9-decimal tokens, lamports)
+pub const INITIAL_VIRTUAL_TOKEN_RESERVES: u128 = 1_073_000_000_000_000_000;
+pub const INITIAL_VIRTUAL_COLLATERAL_RESERVES: u128 = 30_000_000_000;
+
+/// Smallest `CurveAccount` we can read (discriminator + fields up to `coef_b`)
+pub const CURVE_ACCOUNT_MIN_LEN: usize = 80;
+
+/// Moonshot bonding curve (`CurveAccount`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoonshotCurve {
+    pub total_supply: u64,
+    /// Tokens still held by the curve
+    pub curve_amount: u64,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub collateral_currency: u8,
+    pub curve_type: u8,
+    pub marketcap_threshold: u64,
+}
+
+impl MoonshotCurve {
+    /// Parses the raw account, Anchor discriminator included
+    pub fn from_account_data(data: &[u8]) -> Result<Self, String> {
+        if data.len() < CURVE_ACCOUNT_MIN_LEN {
+            return Err(format!("Account too small: {} bytes (need at least {})", data.len(), CURVE_ACCOUNT_MIN_LEN));
+        }
+        let u64_at = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+        Ok(Self {
+            total_supply: u64_at(8),
+            curve_amount: u64_at(16),
+            mint: Pubkey::new_from_array(data[24..56].try_into().unwrap()),
+            decimals: data[56],
+            collateral_currency: data[57],
+            curve_type: data[58],
+            marketcap_threshold: u64_at(59),
+        })
+    }
+
+    /// Collateral 0 = SOL, curve type 0 = constant-product V1; the only combination we price
+    pub fn is_supported(&self) -> bool {
+        self.collateral_currency == 0 && self.curve_type == 0
+    }
+
+    /// Tokens sold so far move the curve along x * y = k from its initial virtual reserves
+    pub fn virtual_reserves(&self) -> (u64, u64) {
+        let sold = self.total_supply.saturating_sub(self.curve_amount) as u128;
+        let tokens = INITIAL_VIRTUAL_TOKEN_RESERVES.saturating_sub(sold);
+        if tokens == 0 {
+            return (0, 0);
+        }
+        let collateral = INITIAL_VIRTUAL_TOKEN_RESERVES * INITIAL_VIRTUAL_COLLATERAL_RESERVES / tokens;
+        (tokens.min(u64::MAX as u128) as u64, collateral.min(u64::MAX as u128) as u64)
+    }
+
+    pub fn calculate_price_in_sol(&self) -> f64 {
+        let (tokens, sol) = self.virtual_reserves();
+        if tokens == 0 {
+            return 0.0;
+        }
+        sol as f64 / tokens as f64
+    }
+
+    /// Exact-in quote. Buys are capped by the tokens left on the curve.
+    pub fn get_amount_out(&self, amount_in: u64, buy: bool) -> u64 {
+        let (tokens, sol) = self.virtual_reserves();
+        if buy {
+            crate::math::get_amount_out_cpmm(amount_in, sol, tokens, TRADE_FEE_BPS).min(self.curve_amount)
+        } else {
+            crate::math::get_amount_out_cpmm(amount_in, tokens, sol, TRADE_FEE_BPS)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moonshot_curve_moves_with_sales() {
+        let mut data = vec![0u8; 82];
+        data[8..16].copy_from_slice(&1_000_000_000_000_000_000u64.to_le_bytes());
+        data[16..24].copy_from_slice(&1_000_000_000_000_000_000u64.to_le_bytes());
+        let mint = Pubkey::new_unique();
+        data[24..56].copy_from_slice(mint.as_ref());
+        data[56] = 9;
+
+        let fresh = MoonshotCurve::from_account_data(&data).unwrap();
+        assert!(fresh.is_supported());
+        assert_eq!(fresh.mint, mint);
+        assert_eq!(fresh.virtual_reserves(), (1_073_000_000_000_000_000, 30_000_000_000));
+
+        // After 100M tokens sold, the price is higher and the same SOL buys fewer tokens
+        data[16..24].copy_from_slice(&900_000_000_000_000_000u64.to_le_bytes());
+        let traded = MoonshotCurve::from_account_data(&data).unwrap();
+        assert!(traded.calculate_price_in_sol() > fresh.calculate_price_in_sol());
+        assert!(traded.get_amount_out(1_000_000_000, true) < fresh.get_amount_out(1_000_000_000, true));
+
+        assert!(MoonshotCurve::from_account_data(&data[..40]).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+/// OpenBook v2 central limit order book
+///
+/// A market is three accounts: the `Market` (mints, vaults, lot sizes, fees)
+/// and one `BookSide` each for bids and asks. A book side holds two critbit
+/// trees, fixed-price orders and oracle-pegged orders; only the fixed tree is
+/// read, since pegged prices move with an oracle we don't track.
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use crate::clob::{aggregate_levels, OrderBook};
+
+/// Anchor discriminator + `Market`
+pub const MARKET_LEN: usize = 8 + 840;
+/// Anchor discriminator + `BookSide` (1024 nodes)
+pub const BOOK_SIDE_LEN: usize = 8 + 90_944;
+
+const MARKET_DISCRIMINATOR: [u8; 8] = [219, 190, 213, 55, 0, 227, 198, 154];
+const BOOK_SIDE_DISCRIMINATOR: [u8; 8] = [72, 44, 225, 141, 178, 130, 97, 57];
+
+/// roots (2 x 8) + reserved roots (4 x 8) + reserved (256), then the node header
+const NODES_HEADER_AT: usize = 8 + 304;
+/// order_tree_type, padding, bump_index, free_list_len, free_list_head, reserved (512)
+const NODES_AT: usize = NODES_HEADER_AT + 528;
+const NODE_LEN: usize = 88;
+const MAX_NODES: usize = 1024;
+
+const TAG_INNER: u8 = 1;
+const TAG_LEAF: u8 = 2;
+
+fn u64_at(data: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(data[at..at + 8].try_into().unwrap())
+}
+
+fn u32_at(data: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(data[at..at + 4].try_into().unwrap())
+}
+
+fn pubkey_at(data: &[u8], at: usize) -> Pubkey {
+    Pubkey::new_from_array(data[at..at + 32].try_into().unwrap())
+}
+
+/// Unset optional pubkeys are stored as all zeroes
+fn optional_pubkey_at(data: &[u8], at: usize) -> Option<Pubkey> {
+    Some(pubkey_at(data, at)).filter(|k| *k != Pubkey::default())
+}
+
+#[derive(Debug, Clone)]
+pub struct OpenBookMarket {
+    pub market_authority: Pubkey,
+    pub bids: Pubkey,
+    pub asks: Pubkey,
+    pub event_heap: Pubkey,
+    pub oracle_a: Option<Pubkey>,
+    pub oracle_b: Option<Pubkey>,
+    pub quote_lot_size: u64,
+    pub base_lot_size: u64,
+    /// Millionths of the quote amount
+    pub taker_fee: i64,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+}
+
+impl OpenBookMarket {
+    pub fn from_account_data(data: &[u8]) -> Result<Self, String> {
+        if data.len() != MARKET_LEN || data[..8] != MARKET_DISCRIMINATOR {
+            return Err(format!("Not an OpenBook v2 market ({} bytes)", data.len()));
+        }
+        let at = |offset: usize| 8 + offset;
+        let market = Self {
+            market_authority: pubkey_at(data, at(8)),
+            bids: pubkey_at(data, at(192)),
+            asks: pubkey_at(data, at(224)),
+            event_heap: pubkey_at(data, at(256)),
+            oracle_a: optional_pubkey_at(data, at(288)),
+            oracle_b: optional_pubkey_at(data, at(320)),
+            quote_lot_size: u64_at(data, at(440)),
+            base_lot_size: u64_at(data, at(448)),
+            taker_fee: u64_at(data, at(480)) as i64,
+            base_mint: pubkey_at(data, at(568)),
+            quote_mint: pubkey_at(data, at(600)),
+            base_vault: pubkey_at(data, at(632)),
+            quote_vault: pubkey_at(data, at(672)),
+        };
+        if market.base_lot_size == 0 || market.quote_lot_size == 0 {
+            return Err("OpenBook market has zero lot sizes".into());
+        }
+        Ok(market)
+    }
+
+    pub fn taker_fee_bps(&self) -> u16 {
+        (self.taker_fee.max(0) / 100).min(10_000) as u16
+    }
+
+    /// Live fixed-price orders from both book side accounts as a generic book
+    pub fn order_book(&self, bids: &[u8], asks: &[u8], unix_secs: u64) -> Result<OrderBook, String> {
+        let levels = |data: &[u8], descending| -> Result<_, String> {
+            let orders = read_fixed_orders(data, unix_secs)?
+                .into_iter()
+                .map(|(price_lots, base_lots)| (price_lots.saturating_mul(self.quote_lot_size), base_lots));
+            Ok(aggregate_levels(orders, descending))
+        };
+        Ok(OrderBook {
+            base_mint: self.base_mint,
+            quote_mint: self.quote_mint,
+            base_lot_size: self.base_lot_size,
+            taker_fee_bps: self.taker_fee_bps(),
+            bids: levels(bids, true)?,
+            asks: levels(asks, false)?,
+        })
+    }
+}
+
+/// (price in quote lots per base lot, base lots) of every unexpired leaf in the fixed tree
+pub fn read_fixed_orders(data: &[u8], unix_secs: u64) -> Result<Vec<(u64, u64)>, String> {
+    if data.len() != BOOK_SIDE_LEN || data[..8] != BOOK_SIDE_DISCRIMINATOR {
+        return Err(format!("Not an OpenBook v2 book side ({} bytes)", data.len()));
+    }
+    // roots[0] is the fixed tree: maybe_node, leaf_count
+    let (root, leaf_count) = (u32_at(data, 8) as usize, u32_at(data, 12) as usize);
+    let mut orders = Vec::with_capacity(leaf_count);
+    if leaf_count == 0 {
+        return Ok(orders);
+    }
+
+    let mut stack = vec![root];
+    let mut visited = 0;
+    while let Some(index) = stack.pop() {
+        visited += 1;
+        if index >= MAX_NODES || visited > MAX_NODES {
+            return Err("OpenBook book side tree is corrupt".into());
+        }
+        let node = NODES_AT + index * NODE_LEN;
+        match data[node] {
+            TAG_INNER => {
+                stack.push(u32_at(data, node + 24) as usize);
+                stack.push(u32_at(data, node + 28) as usize);
+            }
+            TAG_LEAF => {
+                let time_in_force = u16::from_le_bytes([data[node + 2], data[node + 3]]) as u64;
+                let price_lots = u64_at(data, node + 16); // high half of the u128 key
+                let quantity = u64_at(data, node + 56) as i64;
+                let placed_at = u64_at(data, node + 64);
+                let expired = time_in_force > 0 && placed_at + time_in_force < unix_secs;
+                if quantity > 0 && price_lots as i64 > 0 && !expired {
+                    orders.push((price_lots, quantity as u64));
+                }
+            }
+            _ => return Err("OpenBook book side tree is corrupt".into()),
+        }
+    }
+    Ok(orders)
+}
+
+/// Accounts for `place_take_order`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct OpenBookSwapKeys {
+    pub market: Pubkey,
+    pub market_authority: Pubkey,
+    pub bids: Pubkey,
+    pub asks: Pubkey,
+    pub event_heap: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub oracle_a: Option<Pubkey>,
+    pub oracle_b: Option<Pubkey>,
+    pub base_lot_size: u64,
+    pub quote_lot_size: u64,
+    pub signer: Pubkey,
+    pub user_base_account: Pubkey,
+    pub user_quote_account: Pubkey,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_book_side_walks_fixed_tree() {
+        let mut data = vec![0u8; BOOK_SIDE_LEN];
+        data[..8].copy_from_slice(&BOOK_SIDE_DISCRIMINATOR);
+        let put_u32 = |data: &mut Vec<u8>, at: usize, v: u32| data[at..at + 4].copy_from_slice(&v.to_le_bytes());
+        let put_u64 = |data: &mut Vec<u8>, at: usize, v: u64| data[at..at + 8].copy_from_slice(&v.to_le_bytes());
+        let node = |index: usize| NODES_AT + index * NODE_LEN;
+        let leaf = |data: &mut Vec<u8>, index: usize, price_lots: u64, quantity: u64, time_in_force: u16, placed_at: u64| {
+            let at = node(index);
+            data[at] = TAG_LEAF;
+            data[at + 2..at + 4].copy_from_slice(&time_in_force.to_le_bytes());
+            put_u64(data, at + 16, price_lots);
+            put_u64(data, at + 56, quantity);
+            put_u64(data, at + 64, placed_at);
+        };
+
+        // Root inner node 0 -> leaves 1 and 2; a stray leaf at 3 is not in the tree
+        put_u32(&mut data, 8, 0);
+        put_u32(&mut data, 12, 2);
+        data[node(0)] = TAG_INNER;
+        put_u32(&mut data, node(0) + 24, 1);
+        put_u32(&mut data, node(0) + 28, 2);
+        leaf(&mut data, 1, 100, 5, 0, 0);
+        leaf(&mut data, 2, 99, 7, 10, 1_000);
+        leaf(&mut data, 3, 150, 9, 0, 0);
+
+        let mut orders = read_fixed_orders(&data, 1_005).unwrap();
+        orders.sort();
+        assert_eq!(orders, vec![(99, 7), (100, 5)]);
+        // The order at 99 had a 10s time in force
+        assert_eq!(read_fixed_orders(&data, 1_011).unwrap(), vec![(100, 5)]);
+
+        let market = OpenBookMarket {
+            market_authority: Pubkey::new_unique(),
+            bids: Pubkey::new_unique(),
+            asks: Pubkey::new_unique(),
+            event_heap: Pubkey::new_unique(),
+            oracle_a: None,
+            oracle_b: None,
+            quote_lot_size: 10,
+            base_lot_size: 1_000,
+            taker_fee: 400, // 4 bps
+            base_mint: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            base_vault: Pubkey::new_unique(),
+            quote_vault: Pubkey::new_unique(),
+        };
+        let mut empty = vec![0u8; BOOK_SIDE_LEN];
+        empty[..8].copy_from_slice(&BOOK_SIDE_DISCRIMINATOR);
+        let book = market.order_book(&data, &empty, 1_005).unwrap();
+        assert_eq!(book.taker_fee_bps, 4);
+        assert_eq!(book.best_bid().map(|l| (l.price, l.lots)), Some((1_000, 5)));
+        assert!(market.order_book(&data[..100], &empty, 0).is_err());
+    }
+}
@@ -54,11 +54,17 @@ impl Whirlpool {
         u16::from_le_bytes(self.data[41..43].try_into().unwrap())
     }
 
+    /// Fee in hundredths of a basis point (3000 = 0.30%)
     #[inline(always)]
     pub fn fee_rate(&self) -> u16 {
         u16::from_le_bytes(self.data[45..47].try_into().unwrap())
     }
 
+    /// The pool's fee tier in basis points; every Whirlpool tier is a whole number of them
+    pub fn fee_bps(&self) -> u16 {
+        self.fee_rate() / 100
+    }
+
     /// Calculate the current price in the pool (quote/base)
     /// For concentrated liquidity, price = (sqrt_price / 2^64)^2
     pub fn calculate_price(&self) -> f64 {
@@ -108,7 +114,7 @@ impl Whirlpool {
             reserve_b: 0, // Not used for CLMM
             price_sqrt: Some(self.sqrt_price()),
             liquidity: Some(self.liquidity()),
-            fee_bps: self.fee_rate(), // Orca fee_rate is in bps
+            fee_bps: self.fee_bps(),
             timestamp,
         }
     }
@@ -211,6 +217,7 @@ mod tests {
         assert_eq!(whirlpool.tick_current_index(), tick_current_index);
         assert_eq!(whirlpool.tick_spacing(), tick_spacing);
         assert_eq!(whirlpool.fee_rate(), fee_rate);
+        assert_eq!(whirlpool.fee_bps(), 30);
         assert_eq!(whirlpool.token_mint_a(), mint_a);
         assert_eq!(whirlpool.token_mint_b(), mint_b);
         assert_eq!(whirlpool.token_vault_a(), vault_a);
@@ -0,0 +1,248 @@
+/// Phoenix on-chain order book (phoenix-v1)
+///
+/// A market account is a fixed `MarketHeader` followed by the FIFO market:
+/// fee and lot parameters, then three red-black trees (bids, asks, seats)
+/// whose capacities come from the header. Account size therefore varies per
+/// market and is checked against the header instead of a constant.
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use crate::clob::{aggregate_levels, OrderBook};
+use crate::constants::PHOENIX_PROGRAM;
+
+const HEADER_LEN: usize = 576;
+/// `_padding: [u64; 32]` then six u64 market parameters
+const MARKET_PARAMS_LEN: usize = 256 + 48;
+const TREE_HEADER_LEN: usize = 32;
+/// Four u32 tree registers + FIFOOrderId (16) + FIFORestingOrder (32)
+const ORDER_NODE_LEN: usize = 64;
+/// Four u32 tree registers + trader Pubkey + TraderState (96)
+const SEAT_NODE_LEN: usize = 144;
+
+/// A resting order as stored in the book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestingOrder {
+    pub price_in_ticks: u64,
+    pub num_base_lots: u64,
+    /// 0 = no expiry
+    pub last_valid_slot: u64,
+    pub last_valid_unix_timestamp: u64,
+}
+
+impl RestingOrder {
+    fn is_live(&self, slot: u64, unix_secs: u64) -> bool {
+        (self.last_valid_slot == 0 || self.last_valid_slot >= slot)
+            && (self.last_valid_unix_timestamp == 0 || self.last_valid_unix_timestamp >= unix_secs)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PhoenixMarket {
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub base_lot_size: u64,
+    pub quote_lot_size: u64,
+    pub base_lots_per_base_unit: u64,
+    pub tick_size_in_quote_lots_per_base_unit: u64,
+    pub taker_fee_bps: u64,
+    pub bids: Vec<RestingOrder>,
+    pub asks: Vec<RestingOrder>,
+}
+
+fn u64_at(data: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(data[at..at + 8].try_into().unwrap())
+}
+
+fn u32_at(data: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(data[at..at + 4].try_into().unwrap())
+}
+
+fn pubkey_at(data: &[u8], at: usize) -> Pubkey {
+    Pubkey::new_from_array(data[at..at + 32].try_into().unwrap())
+}
+
+impl PhoenixMarket {
+    /// Size a market account must have for the capacities in its header
+    pub fn expected_len(bids_size: u64, asks_size: u64, num_seats: u64) -> usize {
+        HEADER_LEN + MARKET_PARAMS_LEN
+            + 2 * TREE_HEADER_LEN + (bids_size + asks_size) as usize * ORDER_NODE_LEN
+            + TREE_HEADER_LEN + num_seats as usize * SEAT_NODE_LEN
+    }
+
+    pub fn from_account_data(data: &[u8]) -> Result<Self, String> {
+        if data.len() < HEADER_LEN + MARKET_PARAMS_LEN {
+            return Err(format!("Account too small for a Phoenix market: {} bytes", data.len()));
+        }
+        let (bids_size, asks_size, num_seats) = (u64_at(data, 16), u64_at(data, 24), u64_at(data, 32));
+        if bids_size == 0 || asks_size == 0 || bids_size > 1 << 20 || asks_size > 1 << 20 || num_seats > 1 << 20 {
+            return Err("Implausible Phoenix market capacities".into());
+        }
+        let expected = Self::expected_len(bids_size, asks_size, num_seats);
+        if data.len() != expected {
+            return Err(format!("Phoenix market size mismatch: {} bytes, header implies {}", data.len(), expected));
+        }
+
+        let market = Self {
+            base_mint: pubkey_at(data, 48),
+            base_vault: pubkey_at(data, 80),
+            base_lot_size: u64_at(data, 112),
+            quote_mint: pubkey_at(data, 128),
+            quote_vault: pubkey_at(data, 160),
+            quote_lot_size: u64_at(data, 192),
+            base_lots_per_base_unit: u64_at(data, HEADER_LEN + 256),
+            tick_size_in_quote_lots_per_base_unit: u64_at(data, HEADER_LEN + 264),
+            taker_fee_bps: u64_at(data, HEADER_LEN + 280),
+            bids: Vec::new(),
+            asks: Vec::new(),
+        };
+        if market.base_lot_size == 0 || market.quote_lot_size == 0 || market.base_lots_per_base_unit == 0 {
+            return Err("Phoenix market has zero lot sizes".into());
+        }
+
+        let bids_at = HEADER_LEN + MARKET_PARAMS_LEN;
+        let asks_at = bids_at + TREE_HEADER_LEN + bids_size as usize * ORDER_NODE_LEN;
+        Ok(Self {
+            bids: read_orders(data, bids_at, bids_size as usize),
+            asks: read_orders(data, asks_at, asks_size as usize),
+            ..market
+        })
+    }
+
+    /// Quote atoms per base lot for a price in ticks
+    pub fn price_per_lot(&self, price_in_ticks: u64) -> u64 {
+        let quote_lots = price_in_ticks as u128 * self.tick_size_in_quote_lots_per_base_unit as u128
+            / self.base_lots_per_base_unit as u128;
+        (quote_lots * self.quote_lot_size as u128).min(u64::MAX as u128) as u64
+    }
+
+    /// Live orders as a generic book; orders past their slot or time expiry are skipped
+    pub fn order_book(&self, slot: u64, unix_secs: u64) -> OrderBook {
+        let levels = |orders: &[RestingOrder], descending| aggregate_levels(
+            orders.iter()
+                .filter(|o| o.is_live(slot, unix_secs))
+                .map(|o| (self.price_per_lot(o.price_in_ticks), o.num_base_lots)),
+            descending,
+        );
+        OrderBook {
+            base_mint: self.base_mint,
+            quote_mint: self.quote_mint,
+            base_lot_size: self.base_lot_size,
+            taker_fee_bps: self.taker_fee_bps.min(10_000) as u16,
+            bids: levels(&self.bids, true),
+            asks: levels(&self.asks, false),
+        }
+    }
+}
+
+/// Reads every allocated node of one order tree. Node indices are 1-based;
+/// nodes below the bump index are allocated unless they sit on the free list,
+/// which is chained through each free node's first register.
+fn read_orders(data: &[u8], tree_at: usize, capacity: usize) -> Vec<RestingOrder> {
+    let bump_index = (u32_at(data, tree_at + 24) as usize).min(capacity + 1);
+    let nodes_at = tree_at + TREE_HEADER_LEN;
+    let node = |index: usize| nodes_at + (index - 1) * ORDER_NODE_LEN;
+
+    let mut free = vec![false; capacity + 1];
+    let mut next = u32_at(data, tree_at + 28) as usize;
+    while next != 0 && next <= capacity && !free[next] {
+        free[next] = true;
+        next = u32_at(data, node(next)) as usize;
+    }
+
+    (1..bump_index)
+        .filter(|&i| !free[i])
+        .map(|i| {
+            let at = node(i) + 16;
+            RestingOrder {
+                price_in_ticks: u64_at(data, at),
+                num_base_lots: u64_at(data, at + 24),
+                last_valid_slot: u64_at(data, at + 32),
+                last_valid_unix_timestamp: u64_at(data, at + 40),
+            }
+        })
+        .filter(|o| o.num_base_lots > 0)
+        .collect()
+}
+
+/// Accounts for the Phoenix `Swap` instruction
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PhoenixSwapKeys {
+    pub market: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub base_lot_size: u64,
+    pub quote_lot_size: u64,
+    pub trader: Pubkey,
+    pub trader_base_account: Pubkey,
+    pub trader_quote_account: Pubkey,
+}
+
+impl PhoenixSwapKeys {
+    pub fn log_authority() -> Pubkey {
+        Pubkey::find_program_address(&[b"log"], &PHOENIX_PROGRAM).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phoenix_market_decodes_live_orders() {
+        let (bids_size, asks_size, num_seats) = (4u64, 4u64, 2u64);
+        let mut data = vec![0u8; PhoenixMarket::expected_len(bids_size, asks_size, num_seats)];
+        let put = |data: &mut Vec<u8>, at: usize, v: u64| data[at..at + 8].copy_from_slice(&v.to_le_bytes());
+        put(&mut data, 16, bids_size);
+        put(&mut data, 24, asks_size);
+        put(&mut data, 32, num_seats);
+        put(&mut data, 112, 1_000);                // base lot: 1000 atoms
+        put(&mut data, 192, 1);                    // quote lot: 1 atom
+        put(&mut data, HEADER_LEN + 256, 1_000);   // base lots per base unit
+        put(&mut data, HEADER_LEN + 264, 1_000);   // tick: 1000 quote lots per base unit
+        put(&mut data, HEADER_LEN + 280, 5);       // taker fee
+
+        let bids_at = HEADER_LEN + MARKET_PARAMS_LEN;
+        let asks_at = bids_at + TREE_HEADER_LEN + bids_size as usize * ORDER_NODE_LEN;
+        let order = |data: &mut Vec<u8>, tree_at: usize, index: usize, ticks: u64, lots: u64, last_valid_slot: u64| {
+            let at = tree_at + TREE_HEADER_LEN + (index - 1) * ORDER_NODE_LEN + 16;
+            put(data, at, ticks);
+            put(data, at + 24, lots);
+            put(data, at + 32, last_valid_slot);
+        };
+        // Bids: two at 100 ticks, one at 99, node 3 freed (stale data left behind)
+        order(&mut data, bids_at, 1, 100, 5, 0);
+        order(&mut data, bids_at, 2, 99, 7, 0);
+        order(&mut data, bids_at, 3, 150, 9, 0);
+        order(&mut data, bids_at, 4, 100, 3, 0);
+        data[bids_at + 24..bids_at + 28].copy_from_slice(&5u32.to_le_bytes());
+        data[bids_at + 28..bids_at + 32].copy_from_slice(&3u32.to_le_bytes());
+        // Asks: one at 101, one at 102 that expired at slot 10
+        order(&mut data, asks_at, 1, 101, 4, 0);
+        order(&mut data, asks_at, 2, 102, 4, 10);
+        data[asks_at + 24..asks_at + 28].copy_from_slice(&3u32.to_le_bytes());
+
+        let market = PhoenixMarket::from_account_data(&data).unwrap();
+        assert_eq!((market.bids.len(), market.asks.len()), (3, 2));
+        // 100 ticks * 1000 quote lots / 1000 base lots = 100 quote atoms per base lot
+        assert_eq!(market.price_per_lot(100), 100);
+
+        let book = market.order_book(20, 0);
+        assert_eq!(book.best_bid().map(|l| (l.price, l.lots)), Some((100, 8)));
+        assert_eq!(book.bids.len(), 2);
+        assert_eq!(book.asks.len(), 1);
+
+        // Selling 10 lots: 8 at 100, 2 at 99, less 5 bps taker fee
+        assert_eq!(book.get_amount_out(10_000, true), 997);
+        // Deeper than the bids: no quote
+        assert_eq!(book.get_amount_out(16_000, true), 0);
+        // Buying with 300 quote atoms: 2 lots at 101, the rest is dust
+        assert_eq!(book.get_amount_out(300, false), 2_000);
+        // More than the live asks can fill
+        assert_eq!(book.get_amount_out(1_000, false), 0);
+
+        assert!(PhoenixMarket::from_account_data(&data[..data.len() - 1]).is_err());
+    }
+}
@@ -26,6 +26,7 @@ pub mod weight_constants {
     pub const BASE_WEIGHT: f64 = 10.0;
     pub const ACTIVITY_BONUS: f64 = 5.0;
     pub const DNA_BONUS_MULTIPLIER: f64 = 1.0;
+    pub const FLOW_BONUS_MULTIPLIER: f64 = 2.0;
     pub const DECAY_PER_SEC: f64 = 0.1;
     pub const MAX_WEIGHT: f64 = 1000.0;
     pub const MIN_WEIGHT_TO_SUBSCRBE: f64 = 5.0;
@@ -1,6 +1,12 @@
 use serde::{Serialize, Deserialize};
 use borsh::{BorshDeserialize, BorshSerialize};
 
+/// 1% of the SOL side of every curve trade
+pub const TRADE_FEE_BPS: u16 = 100;
+
+/// Bonding-curve account sizes seen on chain (discriminator included), by program version
+pub const CURVE_ACCOUNT_LENS: [usize; 4] = [49, 137, 150, 151];
+
 #[derive(Debug, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 pub struct PumpFunBondingCurve {
     pub virtual_token_reserves: u64,
@@ -40,6 +46,29 @@ impl PumpFunBondingCurve {
         cost as u64
     }
 
+    /// Curve as a (token, SOL) pool. A completed curve has migrated and no longer trades,
+    /// so it reports empty reserves. The account doesn't store its mint; the caller supplies it.
+    pub fn to_market_update(&self, pool_address: solana_sdk::pubkey::Pubkey, mint: solana_sdk::pubkey::Pubkey, timestamp: i64) -> crate::MarketUpdate {
+        let (coin_reserve, pc_reserve) = if self.complete {
+            (0, 0)
+        } else {
+            (self.virtual_token_reserves, self.virtual_sol_reserves)
+        };
+        crate::MarketUpdate {
+            pool_address,
+            program_id: crate::constants::PUMP_FUN_PROGRAM,
+            coin_mint: mint,
+            pc_mint: crate::constants::SOL_MINT,
+            coin_reserve,
+            pc_reserve,
+            price_sqrt: None,
+            liquidity: None,
+            fee_bps: None,
+            timestamp,
+            trigger: crate::Trigger::AccountUpdate,
+        }
+    }
+
     /// Manual deserialization to handle variable account sizes (49 or 137 bytes)
     /// Reads only the fields we need, ignoring extra bytes
     pub fn from_account_data(data: &[u8]) -> Result<Self, String> {
@@ -0,0 +1,75 @@
+/// Pyth price account (push oracle, v2 layout)
+///
+/// Only the aggregate price is read: the value, its confidence interval and
+/// the exponent shared by both, plus when it was last published.
+const MAGIC: u32 = 0xa1b2c3d4;
+const ACCOUNT_TYPE_PRICE: u32 = 3;
+const EXPO_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGG_OFFSET: usize = 208;
+const STATUS_TRADING: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PythPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    /// Unix seconds of the last aggregate update
+    pub publish_time: i64,
+}
+
+impl PythPrice {
+    /// Decodes the aggregate price. Fails unless the feed is currently trading.
+    pub fn from_account_data(data: &[u8]) -> Result<Self, String> {
+        if data.len() < AGG_OFFSET + 32 {
+            return Err(format!("Account too small for a Pyth price: {} bytes", data.len()));
+        }
+        let u32_at = |at: usize| u32::from_le_bytes(data[at..at + 4].try_into().unwrap());
+        let u64_at = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+        if u32_at(0) != MAGIC || u32_at(8) != ACCOUNT_TYPE_PRICE {
+            return Err("Not a Pyth price account".into());
+        }
+        if u32_at(AGG_OFFSET + 16) != STATUS_TRADING {
+            return Err("Pyth feed is not trading".into());
+        }
+        Ok(Self {
+            price: u64_at(AGG_OFFSET) as i64,
+            conf: u64_at(AGG_OFFSET + 8),
+            expo: u32_at(EXPO_OFFSET) as i32,
+            publish_time: u64_at(TIMESTAMP_OFFSET) as i64,
+        })
+    }
+
+    pub fn price_f64(&self) -> f64 {
+        self.price as f64 * 10f64.powi(self.expo)
+    }
+
+    pub fn conf_f64(&self) -> f64 {
+        self.conf as f64 * 10f64.powi(self.expo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decodes_aggregate_price() {
+        let mut data = vec![0u8; 3312];
+        data[0..4].copy_from_slice(&MAGIC.to_le_bytes());
+        data[8..12].copy_from_slice(&ACCOUNT_TYPE_PRICE.to_le_bytes());
+        data[EXPO_OFFSET..EXPO_OFFSET + 4].copy_from_slice(&(-8i32).to_le_bytes());
+        data[TIMESTAMP_OFFSET..TIMESTAMP_OFFSET + 8].copy_from_slice(&1_700_000_000i64.to_le_bytes());
+        data[AGG_OFFSET..AGG_OFFSET + 8].copy_from_slice(&15_012_345_678i64.to_le_bytes());
+        data[AGG_OFFSET + 8..AGG_OFFSET + 16].copy_from_slice(&5_000_000u64.to_le_bytes());
+
+        // Halted feeds are refused
+        assert!(PythPrice::from_account_data(&data).is_err());
+        data[AGG_OFFSET + 16..AGG_OFFSET + 20].copy_from_slice(&STATUS_TRADING.to_le_bytes());
+
+        let price = PythPrice::from_account_data(&data).unwrap();
+        assert_eq!(price.publish_time, 1_700_000_000);
+        assert!((price.price_f64() - 150.12345678).abs() < 1e-9);
+        assert!((price.conf_f64() - 0.05).abs() < 1e-12);
+    }
+}
@@ -133,6 +133,80 @@ pub struct RaydiumSwapKeys {
     pub token_program: Pubkey,
 }
 
+/// Prefix of the swap events the AMM writes to program logs
+pub const RAY_LOG_PREFIX: &str = "ray_log: ";
+
+// ray_log type tags (bincode-encoded enum discriminant)
+const LOG_TYPE_SWAP_BASE_IN: u8 = 3;
+const LOG_TYPE_SWAP_BASE_OUT: u8 = 4;
+const SWAP_LOG_LEN: usize = 57;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapDirection {
+    /// Quote in, base out (a buy of the coin)
+    PcToCoin,
+    /// Base in, quote out (a sell of the coin)
+    CoinToPc,
+}
+
+/// Swap event decoded from a `ray_log` payload.
+/// `pool_coin`/`pool_pc` are the pool's balances before the swap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaySwapLog {
+    pub direction: SwapDirection,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub pool_coin: u64,
+    pub pool_pc: u64,
+}
+
+impl RaySwapLog {
+    /// Decodes the base64-decoded body of a `ray_log` line. Non-swap logs (init, deposit, withdraw) yield None.
+    ///
+    /// SwapBaseIn:  type, amount_in, minimum_out, direction, user_source, pool_coin, pool_pc, out_amount
+    /// SwapBaseOut: type, max_in, amount_out, direction, user_source, pool_coin, pool_pc, deduct_in
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < SWAP_LOG_LEN {
+            return None;
+        }
+        let field = |i: usize| u64::from_le_bytes(data[1 + i * 8..9 + i * 8].try_into().unwrap());
+        let direction = match field(2) {
+            1 => SwapDirection::PcToCoin,
+            2 => SwapDirection::CoinToPc,
+            _ => return None,
+        };
+        let (amount_in, amount_out) = match data[0] {
+            LOG_TYPE_SWAP_BASE_IN => (field(0), field(6)),
+            LOG_TYPE_SWAP_BASE_OUT => (field(6), field(1)),
+            _ => return None,
+        };
+        Some(Self { direction, amount_in, amount_out, pool_coin: field(4), pool_pc: field(5) })
+    }
+
+    pub fn is_buy(&self) -> bool {
+        self.direction == SwapDirection::PcToCoin
+    }
+
+    /// Traded size in quote units
+    pub fn quote_volume(&self) -> u64 {
+        if self.is_buy() { self.amount_in } else { self.amount_out }
+    }
+
+    /// (coin, pc) balances once the swap settled
+    pub fn reserves_after(&self) -> (u64, u64) {
+        match self.direction {
+            SwapDirection::PcToCoin => (
+                self.pool_coin.saturating_sub(self.amount_out),
+                self.pool_pc.saturating_add(self.amount_in),
+            ),
+            SwapDirection::CoinToPc => (
+                self.pool_coin.saturating_add(self.amount_in),
+                self.pool_pc.saturating_sub(self.amount_out),
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,4 +236,32 @@ mod tests {
 
         assert_eq!(price, 20.0);
     }
+
+    #[test]
+    fn test_decode_ray_swap_logs() {
+        let encode = |tag: u8, fields: [u64; 7]| {
+            let mut data = vec![tag];
+            for f in fields {
+                data.extend_from_slice(&f.to_le_bytes());
+            }
+            data
+        };
+
+        // SwapBaseIn: sell 1_000 coin for 1_990 pc
+        let sell = RaySwapLog::decode(&encode(3, [1_000, 1_900, 2, 0, 100_000, 200_000, 1_990])).unwrap();
+        assert_eq!(sell.direction, SwapDirection::CoinToPc);
+        assert_eq!((sell.amount_in, sell.amount_out), (1_000, 1_990));
+        assert_eq!(sell.quote_volume(), 1_990);
+        assert_eq!(sell.reserves_after(), (101_000, 198_010));
+
+        // SwapBaseOut: buy exactly 500 coin, paying 1_010 pc
+        let buy = RaySwapLog::decode(&encode(4, [1_100, 500, 1, 0, 100_000, 200_000, 1_010])).unwrap();
+        assert!(buy.is_buy());
+        assert_eq!((buy.amount_in, buy.amount_out), (1_010, 500));
+        assert_eq!(buy.reserves_after(), (99_500, 201_010));
+
+        // Deposit log and truncated payloads are ignored
+        assert!(RaySwapLog::decode(&encode(1, [0; 7])).is_none());
+        assert!(RaySwapLog::decode(&[3u8; 20]).is_none());
+    }
 }
@@ -0,0 +1,159 @@
+use bytemuck::{Pod, Zeroable};
+use serde::{Serialize, Deserialize};
+use solana_sdk::pubkey::Pubkey;
+
+pub const POOL_STATE_LEN: usize = 1544;
+
+pub const MIN_SQRT_PRICE_X64: u128 = 4295048016;
+pub const MAX_SQRT_PRICE_X64: u128 = 79226673521066979257578248091;
+
+/// Raydium CLMM pool account (`PoolState`, 8-byte Anchor discriminator included)
+/// sqrt_price_x64 is sqrt(token_1 / token_0) in Q64.64, same convention as Whirlpool
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct PoolState {
+    pub data: [u8; POOL_STATE_LEN],
+}
+
+unsafe impl Zeroable for PoolState {}
+unsafe impl Pod for PoolState {}
+
+impl PoolState {
+    #[inline(always)]
+    pub fn amm_config(&self) -> Pubkey {
+        Pubkey::new_from_array(self.data[9..41].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn token_mint_0(&self) -> Pubkey {
+        Pubkey::new_from_array(self.data[73..105].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn token_mint_1(&self) -> Pubkey {
+        Pubkey::new_from_array(self.data[105..137].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn token_vault_0(&self) -> Pubkey {
+        Pubkey::new_from_array(self.data[137..169].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn token_vault_1(&self) -> Pubkey {
+        Pubkey::new_from_array(self.data[169..201].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn observation_key(&self) -> Pubkey {
+        Pubkey::new_from_array(self.data[201..233].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn tick_spacing(&self) -> u16 {
+        u16::from_le_bytes(self.data[235..237].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn liquidity(&self) -> u128 {
+        u128::from_le_bytes(self.data[237..253].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn sqrt_price_x64(&self) -> u128 {
+        u128::from_le_bytes(self.data[253..269].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn tick_current(&self) -> i32 {
+        i32::from_le_bytes(self.data[269..273].try_into().unwrap())
+    }
+
+    /// Bit 4 disables swaps
+    #[inline(always)]
+    pub fn swap_disabled(&self) -> bool {
+        self.data[389] & (1 << 4) != 0
+    }
+}
+
+/// Accounts for the Raydium CLMM `swap` instruction (SPL Token mints only)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RaydiumClmmSwapKeys {
+    pub pool_state: Pubkey,
+    pub amm_config: Pubkey,
+    pub observation_state: Pubkey,
+    pub mint_0: Pubkey,
+    pub mint_1: Pubkey,
+    pub token_vault_0: Pubkey,
+    pub token_vault_1: Pubkey,
+    pub user_owner: Pubkey,
+    pub user_token_0: Pubkey,
+    pub user_token_1: Pubkey,
+    /// Tick array holding the current tick, and its neighbours in each swap direction
+    pub tick_array_current: Pubkey,
+    pub tick_array_lower: Pubkey,
+    pub tick_array_upper: Pubkey,
+}
+
+impl RaydiumClmmSwapKeys {
+    pub const TICKS_PER_ARRAY: i32 = 60;
+
+    pub fn get_tick_array_start_index(tick_index: i32, tick_spacing: u16) -> i32 {
+        let ticks_in_array = Self::TICKS_PER_ARRAY * tick_spacing as i32;
+        tick_index.div_euclid(ticks_in_array) * ticks_in_array
+    }
+
+    /// Unlike Whirlpool, the start index seed is big-endian bytes, not a decimal string
+    pub fn derive_tick_array_pda(pool_state: &Pubkey, start_tick_index: i32, program_id: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"tick_array", pool_state.as_ref(), &start_tick_index.to_be_bytes()],
+            program_id,
+        ).0
+    }
+
+    /// Tick arrays in the order a swap walks them (price falls when selling token 0)
+    pub fn tick_arrays(&self, zero_for_one: bool) -> [Pubkey; 2] {
+        if zero_for_one {
+            [self.tick_array_current, self.tick_array_lower]
+        } else {
+            [self.tick_array_current, self.tick_array_upper]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_state_layout() {
+        // https://github.com/raydium-io/raydium-clmm/blob/master/programs/amm/src/states/pool.rs
+        let mut data = [0u8; POOL_STATE_LEN];
+        let (config, mint_0, mint_1, vault_0, vault_1, observation) = (
+            Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(),
+            Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(),
+        );
+        data[9..41].copy_from_slice(config.as_ref());
+        data[73..105].copy_from_slice(mint_0.as_ref());
+        data[105..137].copy_from_slice(mint_1.as_ref());
+        data[137..169].copy_from_slice(vault_0.as_ref());
+        data[169..201].copy_from_slice(vault_1.as_ref());
+        data[201..233].copy_from_slice(observation.as_ref());
+        data[235..237].copy_from_slice(&10u16.to_le_bytes());
+        data[237..253].copy_from_slice(&5_000_000_000u128.to_le_bytes());
+        data[253..269].copy_from_slice(&(1u128 << 64).to_le_bytes());
+        data[269..273].copy_from_slice(&(-605i32).to_le_bytes());
+
+        let pool: &PoolState = bytemuck::from_bytes(&data);
+        assert_eq!((pool.amm_config(), pool.observation_key()), (config, observation));
+        assert_eq!((pool.token_mint_0(), pool.token_mint_1()), (mint_0, mint_1));
+        assert_eq!((pool.token_vault_0(), pool.token_vault_1()), (vault_0, vault_1));
+        assert_eq!((pool.tick_spacing(), pool.tick_current()), (10, -605));
+        assert_eq!((pool.liquidity(), pool.sqrt_price_x64()), (5_000_000_000, 1u128 << 64));
+        assert!(!pool.swap_disabled());
+
+        // 60 ticks * spacing 10 = 600 per array
+        assert_eq!(RaydiumClmmSwapKeys::get_tick_array_start_index(-605, 10), -1200);
+        assert_eq!(RaydiumClmmSwapKeys::get_tick_array_start_index(599, 10), 0);
+    }
+}
@@ -1,9 +1,27 @@
-use prometheus::{Counter, CounterVec, Histogram, IntGauge, Registry, TextEncoder, Encoder, HistogramOpts, Opts};
+use std::sync::OnceLock;
+use prometheus::core::Collector;
+use prometheus::proto::MetricFamily;
+use prometheus::{Counter, CounterVec, Gauge, Histogram, HistogramVec, IntGauge, IntGaugeVec, Registry, HistogramOpts, Opts};
 use lazy_static::lazy_static;
 
+/// Upper bounds of the tip buckets used for bundle land-rate tracking
+pub const TIP_BUCKETS_LAMPORTS: [f64; 10] = [
+    1_000.0, 5_000.0, 10_000.0, 25_000.0, 50_000.0, 100_000.0, 250_000.0, 500_000.0, 1_000_000.0, 5_000_000.0,
+];
+
+/// Upper bounds of the compute-unit buckets for simulated bundles
+pub const COMPUTE_UNIT_BUCKETS: [f64; 8] = [
+    50_000.0, 100_000.0, 200_000.0, 300_000.0, 400_000.0, 600_000.0, 1_000_000.0, 1_400_000.0,
+];
+
+/// Strategy id used when `STRATEGY_ID` is unset
+pub const DEFAULT_STRATEGY_ID: &str = "cyclic_arb";
+
+/// Exported registry; every metric name carries the strategy's namespace prefix
+static REGISTRY: OnceLock<Registry> = OnceLock::new();
+static NAMESPACE: OnceLock<String> = OnceLock::new();
+
 lazy_static! {
-    pub static ref REGISTRY: Registry = Registry::new();
-    
     // Opportunity metrics
     pub static ref OPPORTUNITIES_TOTAL: Counter = Counter::new(
         "opportunities_detected_total", 
@@ -69,6 +87,31 @@ lazy_static! {
         "rpc_errors_total",
         "Total RPC errors encountered"
     ).unwrap();
+
+    pub static ref RPC_REQUESTS: CounterVec = CounterVec::new(
+        Opts::new("rpc_requests_total", "RPC calls by pool endpoint index and outcome (ok, error, rate_limited)"),
+        &["endpoint", "outcome"]
+    ).unwrap();
+
+    pub static ref RPC_ENDPOINT_LATENCY_MS: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("rpc_endpoint_latency_ms", "Moving average RPC latency per pool endpoint index"),
+        &["endpoint"]
+    ).unwrap();
+
+    pub static ref WS_ENDPOINT_SLOT: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("ws_endpoint_slot", "Latest slot pushed by each WebSocket endpoint"),
+        &["endpoint"]
+    ).unwrap();
+
+    pub static ref WS_ENDPOINT_STALENESS_MS: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("ws_endpoint_staleness_ms", "Time since each WebSocket endpoint last sent anything (-1 = never)"),
+        &["endpoint"]
+    ).unwrap();
+
+    pub static ref WS_NOTIFICATIONS: CounterVec = CounterVec::new(
+        Opts::new("ws_notifications_total", "Account notifications per WebSocket endpoint: first copy forwarded, or duplicate dropped"),
+        &["endpoint", "result"]
+    ).unwrap();
     
     // Risk management metrics
     pub static ref CIRCUIT_BREAKER_TRIGGERS: Counter = Counter::new(
@@ -76,6 +119,26 @@ lazy_static! {
         "Number of times circuit breaker was triggered"
     ).unwrap();
     
+    pub static ref DRAWDOWN_BREAKER_TRIPS: Counter = Counter::new(
+        "drawdown_breaker_trips_total",
+        "Times realized PnL drawdown over the window halted trading"
+    ).unwrap();
+
+    pub static ref DRAWDOWN_BREAKER_HALTED: IntGauge = IntGauge::new(
+        "drawdown_breaker_halted",
+        "1 while trading is halted by the drawdown breaker"
+    ).unwrap();
+
+    pub static ref WALLET_HIGH_WATER_LAMPORTS: IntGauge = IntGauge::new(
+        "wallet_high_water_lamports",
+        "Highest SOL balance the wallet has shown this session"
+    ).unwrap();
+
+    pub static ref WALLET_WATCHDOG_TRIPS: Counter = Counter::new(
+        "wallet_watchdog_trips_total",
+        "Times the wallet balance fell past the allowed drop below its high-water mark"
+    ).unwrap();
+
     pub static ref DAILY_PNL_LAMPORTS: IntGauge = IntGauge::new(
         "daily_pnl_lamports",
         "Current daily profit/loss in lamports"
@@ -123,17 +186,102 @@ lazy_static! {
         Opts::new("jito_bundle_errors_total", "Total Jito bundle submission errors"),
         &["endpoint_id"]
     ).unwrap();
+    pub static ref STRATEGY_ACTIVE: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("strategy_active", "1 while a strategy (arb, backrun, sweep, reprice) is inside its STRATEGY_SCHEDULE window or forced on"),
+        &["strategy"]
+    ).unwrap();
+
+    pub static ref JITO_ENDPOINT_ACTIVE: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("jito_endpoint_active", "1 while a Jito endpoint is in rotation, 0 while benched for failing submissions"),
+        &["endpoint_id"]
+    ).unwrap();
+
+    pub static ref JITO_CONNECTION_AGE_SECONDS: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("jito_connection_age_seconds", "Seconds since the channel to a Jito endpoint was (re)connected"),
+        &["endpoint_id"]
+    ).unwrap();
+
+    pub static ref JITO_CHANNEL_RECONNECTS: CounterVec = CounterVec::new(
+        Opts::new("jito_channel_reconnects_total", "Jito channels re-established: idle past JITO_MAX_IDLE_SECS or failed warm-up call"),
+        &["endpoint_id", "reason"]
+    ).unwrap();
+
+    pub static ref COMPUTE_UNITS_PER_IX: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("compute_units_per_ix", "Measured compute units per instruction, by program (per-venue CU profile)"),
+        &["program"]
+    ).unwrap();
+
+    pub static ref JITO_BUNDLE_OUTCOMES: CounterVec = CounterVec::new(
+        Opts::new("jito_bundle_outcomes_total", "Submitted Jito bundles by final status (landed, failed, invalid, expired)"),
+        &["endpoint_id", "outcome"]
+    ).unwrap();
+
+    /// Tips of resolved bundles, split by landed/dropped. Per bucket,
+    /// landed / (landed + dropped) is the land probability at that tip.
+    pub static ref JITO_BUNDLE_TIP_LAMPORTS: HistogramVec = HistogramVec::new(
+        HistogramOpts::new(
+            "jito_bundle_tip_lamports",
+            "Tip paid on resolved Jito bundles, by landed/dropped"
+        ).buckets(TIP_BUCKETS_LAMPORTS.to_vec()),
+        &["result"]
+    ).unwrap();
 
     pub static ref SAFETY_FAILURES: CounterVec = CounterVec::new(
         Opts::new("safety_failures_total", "Total safety check failures with reason labels"),
         &["reason"]
     ).unwrap();
 
+    pub static ref HTTP_FAILURES: CounterVec = CounterVec::new(
+        Opts::new("http_failures_total", "Total failed outbound HTTP attempts (including retried ones) by host"),
+        &["host"]
+    ).unwrap();
+
     pub static ref DISCOVERY_ERRORS: CounterVec = CounterVec::new(
         Opts::new("discovery_errors_total", "Total discovery/hydration errors"),
         &["type"]
     ).unwrap();
 
+    pub static ref OPPORTUNITY_OUTCOMES: CounterVec = CounterVec::new(
+        Opts::new("opportunity_outcomes_total", "Pool updates processed, by outcome (executed, execution_failed, no_opportunity or the rejection reason)"),
+        &["outcome"]
+    ).unwrap();
+
+    pub static ref POOLS_DELISTED_TOTAL: CounterVec = CounterVec::new(
+        Opts::new("pools_delisted_total", "Pools removed from the graph after closing, draining or migrating"),
+        &["reason"]
+    ).unwrap();
+    pub static ref GRAPH_PRUNED_TOTAL: CounterVec = CounterVec::new(
+        Opts::new("graph_pruned_total", "Stale pools and orphaned mint nodes removed from the market graph"),
+        &["kind"]
+    ).unwrap();
+    pub static ref CONFIG_INFO: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("bot_config_info", "Always 1; labels identify the effective configuration of this session"),
+        &["config_hash", "mode"]
+    ).unwrap();
+
+    pub static ref FEATURE_FLAG_DECISIONS: CounterVec = CounterVec::new(
+        Opts::new("feature_flag_decisions_total", "Routes evaluated against a rolled-out feature flag, by arm"),
+        &["flag", "arm"]
+    ).unwrap();
+
+    pub static ref FEATURE_FLAG_DISPATCHED_PROFIT: CounterVec = CounterVec::new(
+        Opts::new("feature_flag_dispatched_profit_lamports", "Expected profit of dispatched bundles, by feature flag arm"),
+        &["flag", "arm"]
+    ).unwrap();
+
+    pub static ref HOP_QUOTE_ERROR_BPS: HistogramVec = HistogramVec::new(
+        HistogramOpts::new(
+            "hop_quote_error_bps",
+            "Actual minus quoted hop output on landed routes, in bps of the quote"
+        ).buckets(vec![-500.0, -100.0, -50.0, -20.0, -5.0, 0.0, 5.0, 20.0, 50.0, 100.0, 500.0]),
+        &["venue"]
+    ).unwrap();
+
+    pub static ref ROUTE_HOPS_BY_FEE_TIER: CounterVec = CounterVec::new(
+        Opts::new("route_hops_by_fee_tier_total", "Hops of profitable routes found, by program and pool fee tier (bps)"),
+        &["program", "fee_tier_bps"]
+    ).unwrap();
+
     pub static ref DISCOVERY_CACHE_HITS: Counter = Counter::new(
         "discovery_cache_hits_total",
         "Total signature cache hits in discovery"
@@ -150,33 +298,413 @@ lazy_static! {
             "Distribution of profitable arbitrage route depth (hop count)"
         ).buckets(vec![2.0, 3.0, 4.0, 5.0, 6.0])
     ).unwrap();
+
+    pub static ref ROUTES_BLACKLISTED_TOTAL: Counter = Counter::new(
+        "routes_blacklisted_total",
+        "Total routes put on cooldown after repeated on-chain reverts"
+    ).unwrap();
+
+    pub static ref OPPORTUNITIES_ROUTE_BLACKLISTED: Counter = Counter::new(
+        "opportunities_route_blacklisted_total",
+        "Total opportunities skipped because their route is on cooldown"
+    ).unwrap();
+
+    pub static ref SPLIT_CHUNKS_EXECUTED: Counter = Counter::new(
+        "split_chunks_executed_total",
+        "Total follow-up chunks dispatched for split-execution routes"
+    ).unwrap();
+
+    pub static ref CYCLE_REPRICE_HITS: Counter = Counter::new(
+        "cycle_reprice_hits_total",
+        "Cached cycles found profitable again by the per-slot re-pricing pass"
+    ).unwrap();
+
+    pub static ref CYCLE_SWEEP_HITS: Counter = Counter::new(
+        "cycle_sweep_hits_total",
+        "Profitable cycles found by the full background sweep behind incremental search"
+    ).unwrap();
+
+    pub static ref CYCLE_SEARCH_BUDGET_EXCEEDED: Counter = Counter::new(
+        "cycle_search_budget_exceeded_total",
+        "Cycle searches cut off by the latency budget, returning the best cycle found so far"
+    ).unwrap();
+
+    pub static ref ROUTE_LEGS_SPLIT: Counter = Counter::new(
+        "route_legs_split_total",
+        "Route legs spread over several pools of one pair because the split returned more"
+    ).unwrap();
+
+    pub static ref MODEL_CONFIDENCE_MEAN: Gauge = Gauge::new(
+        "model_confidence_mean",
+        "Mean AI model confidence over the drift monitor's rolling window"
+    ).unwrap();
+    pub static ref MODEL_CONFIDENCE_STDDEV: Gauge = Gauge::new(
+        "model_confidence_stddev",
+        "Standard deviation of AI model confidence over the rolling window"
+    ).unwrap();
+    pub static ref MODEL_DRIFT_STATE: IntGauge = IntGauge::new(
+        "model_drift_state",
+        "AI model output drift: 0 healthy, 1 collapsed to 0/1, 2 shifted from the training baseline"
+    ).unwrap();
+    pub static ref MODEL_RELOADS: CounterVec = CounterVec::new(
+        Opts::new("model_reloads_total", "AI model file reloads by model (champion, challenger) and outcome (ok, error)"),
+        &["model", "outcome"]
+    ).unwrap();
+    pub static ref MODEL_SHADOW_CONFIDENCE: HistogramVec = HistogramVec::new(
+        HistogramOpts::new("model_shadow_confidence", "Confidence of the acting (champion) and shadow (challenger) models on the same opportunities")
+            .buckets(vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0]),
+        &["model"]
+    ).unwrap();
+    pub static ref MODEL_SHADOW_DISAGREEMENTS: CounterVec = CounterVec::new(
+        Opts::new("model_shadow_disagreements_total", "Opportunities only one model would accept at AI_CONFIDENCE_THRESHOLD (champion_only, challenger_only)"),
+        &["accepted_by"]
+    ).unwrap();
+    pub static ref OPPORTUNITY_EXPECTED_VALUE: Histogram = Histogram::with_opts(
+        HistogramOpts::new("opportunity_expected_value_lamports", "Expected value of routes at the EV gate: p(land)·profit − (1−p)·(tip+fees)")
+            .buckets(vec![-100_000.0, -10_000.0, 0.0, 10_000.0, 50_000.0, 100_000.0, 500_000.0, 1_000_000.0, 10_000_000.0])
+    ).unwrap();
+
+    pub static ref CYCLE_CACHE_SIZE: IntGauge = IntGauge::new(
+        "cycle_cache_size",
+        "Cycles held for per-slot re-pricing"
+    ).unwrap();
+
+    pub static ref OPPORTUNITIES_POOL_RATE_LIMITED: Counter = Counter::new(
+        "opportunities_pool_rate_limited_total",
+        "Total opportunities skipped because a pool in the route hit its per-minute dispatch limit"
+    ).unwrap();
+
+    pub static ref OPPORTUNITIES_EXPOSURE_REJECTED: CounterVec = CounterVec::new(
+        Opts::new("opportunities_exposure_rejected_total", "Opportunities refused by a risk exposure limit, by limit type"),
+        &["limit"]
+    ).unwrap();
+
+    pub static ref OPPORTUNITIES_ORACLE_REJECTED: Counter = Counter::new(
+        "opportunities_oracle_rejected_total",
+        "Total opportunities dropped because a hop priced too far above its oracle reference"
+    ).unwrap();
+
+    pub static ref QUOTE_CHECK_HOPS: CounterVec = CounterVec::new(
+        Opts::new("quote_check_hops_total", "Hops re-quoted by the external quote cross-check: matched, mismatch, unrouted, unavailable"),
+        &["outcome"]
+    ).unwrap();
+
+    pub static ref POOLS_BELOW_LIQUIDITY_FLOOR: CounterVec = CounterVec::new(
+        Opts::new("pools_below_liquidity_floor_total", "Pools turned away for SOL-equivalent depth under MIN_LIQUIDITY_LAMPORTS, by where (safety, search, discovery)"),
+        &["stage"]
+    ).unwrap();
+
+    pub static ref OPPORTUNITIES_SLOT_CONFLICT: Counter = Counter::new(
+        "opportunities_slot_conflict_total",
+        "Total opportunities dropped because an overlapping higher-EV route owned the pools this slot"
+    ).unwrap();
+
+    pub static ref OPPORTUNITIES_SLOT_PREEMPTED: Counter = Counter::new(
+        "opportunities_slot_preempted_total",
+        "Total unsent slot claims taken over by a higher-EV overlapping route"
+    ).unwrap();
+
+    pub static ref OPPORTUNITIES_PRIORITY_BOOSTED: Counter = Counter::new(
+        "opportunities_priority_boosted_total",
+        "Total opportunities above the profit percentile given a reserved execution slot"
+    ).unwrap();
+
+    pub static ref TRADE_FLOW_SWAPS_DECODED: Counter = Counter::new(
+        "trade_flow_swaps_decoded_total",
+        "Total Raydium swaps decoded from ray_log events on watched pools"
+    ).unwrap();
+
+    // Data quality
+    pub static ref RESERVE_AUDITS_TOTAL: Counter = Counter::new(
+        "reserve_audits_total",
+        "Total pools re-read from RPC by the reserve reconciliation audit"
+    ).unwrap();
+
+    pub static ref RESERVE_DIVERGENCES_TOTAL: Counter = Counter::new(
+        "reserve_divergences_total",
+        "Total audited pools whose graph state diverged past the threshold and was corrected"
+    ).unwrap();
+
+    // Worker Pool
+    pub static ref GEYSER_SLOT_GAPS_TOTAL: Counter = Counter::new(
+        "geyser_slot_gaps_total",
+        "Total slots skipped by the Geyser stream (missed between consecutive slot updates)"
+    ).unwrap();
+    pub static ref GEYSER_RECONNECTS_TOTAL: Counter = Counter::new(
+        "geyser_reconnects_total",
+        "Total Geyser gRPC stream reconnects"
+    ).unwrap();
+    pub static ref SAFETY_ACCOUNT_BATCHES: Counter = Counter::new(
+        "safety_account_batches_total",
+        "Total getMultipleAccounts calls made by the safety check batcher"
+    ).unwrap();
+    pub static ref SAFETY_ACCOUNT_REQUESTS: Counter = Counter::new(
+        "safety_account_requests_total",
+        "Total account fetch requests from safety checks, before batching"
+    ).unwrap();
+    pub static ref FLASH_LOAN_OPPORTUNITIES: Counter = Counter::new(
+        "flash_loan_opportunities_total",
+        "Total opportunities funded with a flash loan because input exceeded inventory"
+    ).unwrap();
+    pub static ref WORKERS_ACTIVE: IntGauge = IntGauge::new(
+        "workers_active",
+        "Number of workers currently pulling from the work queue"
+    ).unwrap();
+
+    pub static ref WORKER_QUEUE_DEPTH: IntGauge = IntGauge::new(
+        "worker_queue_depth",
+        "Market events waiting in the work queue"
+    ).unwrap();
+
+    pub static ref WORKER_PRIORITY_QUEUE_DEPTH: IntGauge = IntGauge::new(
+        "worker_priority_queue_depth",
+        "Market events for priority quote mints waiting in the work queue"
+    ).unwrap();
+
+    pub static ref WORK_QUEUE_SHED_TOTAL: Counter = Counter::new(
+        "work_queue_shed_total",
+        "Long-tail market events dropped because the bulk lane of the work queue was full"
+    ).unwrap();
+
+    pub static ref BACKRUN_TRIGGERS: Counter = Counter::new(
+        "backrun_triggers_total",
+        "Large observed swaps that queued a priority search on their pool"
+    ).unwrap();
+
+    pub static ref TX_SENDS: CounterVec = CounterVec::new(
+        Opts::new("tx_sender_sends_total", "Fallback transaction sends by sender (rpc, helius, triton, bloxroute, tpu) and outcome (ok, error)"),
+        &["sender", "outcome"]
+    ).unwrap();
+
+    pub static ref BUNDLE_SIMULATIONS: CounterVec = CounterVec::new(
+        Opts::new("bundle_simulations_total", "Pre-send bundle simulations by backend (rpc, jito) and result (ok, failed, error)"),
+        &["backend", "result"]
+    ).unwrap();
+
+    pub static ref BUNDLE_SIMULATION_FAILURES: CounterVec = CounterVec::new(
+        Opts::new("bundle_simulation_failures_total", "Failed bundle simulations by backend and reason (insufficient_funds, account, compute_budget, program_error, instruction_error, other)"),
+        &["backend", "reason"]
+    ).unwrap();
+
+    pub static ref BUNDLE_SIMULATION_UNITS: HistogramVec = HistogramVec::new(
+        HistogramOpts::new(
+            "bundle_simulation_compute_units",
+            "Compute units used by bundles that simulated successfully"
+        ).buckets(COMPUTE_UNIT_BUCKETS.to_vec()),
+        &["backend"]
+    ).unwrap();
+
+    pub static ref DURABLE_NONCE_SIGNS: CounterVec = CounterVec::new(
+        Opts::new("durable_nonce_signs_total", "Fallback transactions by what they were signed against (nonce, busy, stale, unreadable); all but nonce fell back to a recent blockhash"),
+        &["outcome"]
+    ).unwrap();
+
+    pub static ref BUNDLE_RACES: CounterVec = CounterVec::new(
+        Opts::new("bundle_races_total", "Race mode sends by outcome: raced, rpc_rejected, oversized (copy over the legacy size), nonce_busy, nonce_stale, nonce_unreadable"),
+        &["outcome"]
+    ).unwrap();
+
+    pub static ref BUNDLE_RACE_WINNERS: CounterVec = CounterVec::new(
+        Opts::new("bundle_race_winners_total", "Raced trades by the copy that settled on-chain (jito, rpc)"),
+        &["path"]
+    ).unwrap();
+
+    pub static ref WALLET_ROTATIONS: CounterVec = CounterVec::new(
+        Opts::new("wallet_rotations_total", "Signer rotations by reason (signature, locked, rate_limited, manual) and outcome (switched, blocked)"),
+        &["reason", "outcome"]
+    ).unwrap();
+
+    pub static ref WALLET_ACTIVE_SIGNER: IntGauge = IntGauge::new(
+        "wallet_active_signer",
+        "Signer trades go through: 0 primary, 1 standby"
+    ).unwrap();
+
+    pub static ref REALIZED_PNL_DEVIATIONS: Counter = Counter::new(
+        "realized_pnl_deviations_total",
+        "Landed trades whose realized PnL missed the quoted profit by more than PNL_DEVIATION_ALERT_PCT"
+    ).unwrap();
+
+    pub static ref OPPORTUNITIES_PUBLISHED: CounterVec = CounterVec::new(
+        Opts::new("opportunities_published_total", "Opportunities handed to external execution, per sink delivery (delivered, failed)"),
+        &["outcome"]
+    ).unwrap();
+
+    pub static ref RECOVERY_SELLS: CounterVec = CounterVec::new(
+        Opts::new("recovery_sells_total", "Residual intermediate tokens left by trades, by what happened to them (sold, dust, failed, dropped)"),
+        &["outcome"]
+    ).unwrap();
+
+    pub static ref INVENTORY_SWEEPS: CounterVec = CounterVec::new(
+        Opts::new("inventory_sweeps_total", "Wallet holdings swept back to SOL by the inventory manager (sold, failed, unrouted)"),
+        &["outcome"]
+    ).unwrap();
+
+    pub static ref POOL_LAYOUT_CHECKS: CounterVec = CounterVec::new(
+        Opts::new("pool_layout_checks_total", "Reference pool decodes checked against chain and quote data (ok, diverged)"),
+        &["result"]
+    ).unwrap();
+
+    pub static ref POOL_LAYOUT_DIVERGENCES: CounterVec = CounterVec::new(
+        Opts::new("pool_layout_divergences_total", "Reference pool checks that failed, by check (undecodable, mints, vault, liquidity, price, snapshot)"),
+        &["check"]
+    ).unwrap();
+
+    pub static ref POOL_LAYOUT_DRIFTING: IntGauge = IntGauge::new(
+        "pool_layout_drifting",
+        "Reference pools whose decoded account currently fails a layout check"
+    ).unwrap();
+
+    pub static ref INVENTORY_TOKEN_VALUE_LAMPORTS: IntGauge = IntGauge::new(
+        "inventory_token_value_lamports",
+        "Value of the wallet's token holdings at graph spot prices, unpriced mints excluded"
+    ).unwrap();
+
+    pub static ref BROADCAST_LAGGED_TOTAL: Counter = Counter::new(
+        "broadcast_lagged_events_total",
+        "Total market events dropped because the dispatcher lagged the broadcast bus"
+    ).unwrap();
+}
+
+/// Prometheus-safe namespace for a strategy id: lowercase, `[a-z0-9_]`, starting with a letter
+pub fn metric_namespace(strategy_id: &str) -> String {
+    let mut namespace: String = strategy_id.trim().to_ascii_lowercase().chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !namespace.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        namespace.insert_str(0, "s_");
+    }
+    namespace
+}
+
+/// Every exported metric, in registration order. Dashboards are generated from this list.
+pub fn collectors() -> Vec<Box<dyn Collector>> {
+    vec![
+        Box::new(OPPORTUNITIES_TOTAL.clone()),
+        Box::new(OPPORTUNITIES_PROFITABLE.clone()),
+        Box::new(TRADES_EXECUTED.clone()),
+        Box::new(PROFIT_LAMPORTS.clone()),
+        Box::new(LOSS_LAMPORTS.clone()),
+        Box::new(GAS_SPENT_LAMPORTS.clone()),
+        Box::new(ACTUAL_SLIPPAGE_BPS.clone()),
+        Box::new(DETECTION_LATENCY.clone()),
+        Box::new(EXECUTION_LATENCY.clone()),
+        Box::new(WEBSOCKET_STATUS.clone()),
+        Box::new(RPC_ERRORS.clone()),
+        Box::new(RPC_REQUESTS.clone()),
+        Box::new(RPC_ENDPOINT_LATENCY_MS.clone()),
+        Box::new(WS_ENDPOINT_SLOT.clone()),
+        Box::new(WS_ENDPOINT_STALENESS_MS.clone()),
+        Box::new(WS_NOTIFICATIONS.clone()),
+        Box::new(CIRCUIT_BREAKER_TRIGGERS.clone()),
+        Box::new(DRAWDOWN_BREAKER_TRIPS.clone()),
+        Box::new(DRAWDOWN_BREAKER_HALTED.clone()),
+        Box::new(WALLET_HIGH_WATER_LAMPORTS.clone()),
+        Box::new(WALLET_WATCHDOG_TRIPS.clone()),
+        Box::new(DAILY_PNL_LAMPORTS.clone()),
+        Box::new(SAFETY_REJECTIONS.clone()),
+        Box::new(DNA_MATCHES_TOTAL.clone()),
+        Box::new(DNA_ELITE_MATCHES_TOTAL.clone()),
+        Box::new(DISCOVERY_TOKENS_TOTAL.clone()),
+        Box::new(SAFETY_CACHE_HITS.clone()),
+        Box::new(SAFETY_CACHE_MISSES.clone()),
+        Box::new(POOL_DEDUP_SKIPS.clone()),
+        Box::new(JITO_BUNDLE_ERRORS.clone()),
+        Box::new(STRATEGY_ACTIVE.clone()),
+        Box::new(JITO_ENDPOINT_ACTIVE.clone()),
+        Box::new(JITO_CONNECTION_AGE_SECONDS.clone()),
+        Box::new(JITO_CHANNEL_RECONNECTS.clone()),
+        Box::new(JITO_BUNDLE_OUTCOMES.clone()),
+        Box::new(JITO_BUNDLE_TIP_LAMPORTS.clone()),
+        Box::new(COMPUTE_UNITS_PER_IX.clone()),
+        Box::new(SAFETY_FAILURES.clone()),
+        Box::new(DISCOVERY_ERRORS.clone()),
+        Box::new(HOP_QUOTE_ERROR_BPS.clone()),
+        Box::new(FEATURE_FLAG_DECISIONS.clone()),
+        Box::new(POOLS_DELISTED_TOTAL.clone()),
+        Box::new(GRAPH_PRUNED_TOTAL.clone()),
+        Box::new(CONFIG_INFO.clone()),
+        Box::new(OPPORTUNITY_OUTCOMES.clone()),
+        Box::new(FEATURE_FLAG_DISPATCHED_PROFIT.clone()),
+        Box::new(DISCOVERY_CACHE_HITS.clone()),
+        Box::new(OPPORTUNITIES_NON_DNA_TOTAL.clone()),
+        Box::new(ROUTE_DEPTH_HISTOGRAM.clone()),
+        Box::new(ROUTE_HOPS_BY_FEE_TIER.clone()),
+        Box::new(ROUTES_BLACKLISTED_TOTAL.clone()),
+        Box::new(OPPORTUNITIES_ROUTE_BLACKLISTED.clone()),
+        Box::new(SPLIT_CHUNKS_EXECUTED.clone()),
+        Box::new(OPPORTUNITIES_PRIORITY_BOOSTED.clone()),
+        Box::new(OPPORTUNITIES_SLOT_CONFLICT.clone()),
+        Box::new(OPPORTUNITIES_EXPOSURE_REJECTED.clone()),
+        Box::new(OPPORTUNITIES_ORACLE_REJECTED.clone()),
+        Box::new(QUOTE_CHECK_HOPS.clone()),
+        Box::new(POOLS_BELOW_LIQUIDITY_FLOOR.clone()),
+        Box::new(CYCLE_REPRICE_HITS.clone()),
+        Box::new(CYCLE_SWEEP_HITS.clone()),
+        Box::new(CYCLE_SEARCH_BUDGET_EXCEEDED.clone()),
+        Box::new(ROUTE_LEGS_SPLIT.clone()),
+        Box::new(CYCLE_CACHE_SIZE.clone()),
+        Box::new(MODEL_CONFIDENCE_MEAN.clone()),
+        Box::new(MODEL_CONFIDENCE_STDDEV.clone()),
+        Box::new(MODEL_DRIFT_STATE.clone()),
+        Box::new(MODEL_RELOADS.clone()),
+        Box::new(MODEL_SHADOW_CONFIDENCE.clone()),
+        Box::new(MODEL_SHADOW_DISAGREEMENTS.clone()),
+        Box::new(OPPORTUNITY_EXPECTED_VALUE.clone()),
+        Box::new(OPPORTUNITIES_POOL_RATE_LIMITED.clone()),
+        Box::new(OPPORTUNITIES_SLOT_PREEMPTED.clone()),
+        Box::new(TRADE_FLOW_SWAPS_DECODED.clone()),
+        Box::new(RESERVE_AUDITS_TOTAL.clone()),
+        Box::new(RESERVE_DIVERGENCES_TOTAL.clone()),
+        Box::new(GEYSER_SLOT_GAPS_TOTAL.clone()),
+        Box::new(GEYSER_RECONNECTS_TOTAL.clone()),
+        Box::new(SAFETY_ACCOUNT_BATCHES.clone()),
+        Box::new(SAFETY_ACCOUNT_REQUESTS.clone()),
+        Box::new(FLASH_LOAN_OPPORTUNITIES.clone()),
+        Box::new(HTTP_FAILURES.clone()),
+        Box::new(WORKERS_ACTIVE.clone()),
+        Box::new(WORKER_QUEUE_DEPTH.clone()),
+        Box::new(WORKER_PRIORITY_QUEUE_DEPTH.clone()),
+        Box::new(WORK_QUEUE_SHED_TOTAL.clone()),
+        Box::new(BACKRUN_TRIGGERS.clone()),
+        Box::new(TX_SENDS.clone()),
+        Box::new(BUNDLE_SIMULATIONS.clone()),
+        Box::new(BUNDLE_SIMULATION_FAILURES.clone()),
+        Box::new(BUNDLE_SIMULATION_UNITS.clone()),
+        Box::new(DURABLE_NONCE_SIGNS.clone()),
+        Box::new(BUNDLE_RACES.clone()),
+        Box::new(BUNDLE_RACE_WINNERS.clone()),
+        Box::new(WALLET_ROTATIONS.clone()),
+        Box::new(WALLET_ACTIVE_SIGNER.clone()),
+        Box::new(REALIZED_PNL_DEVIATIONS.clone()),
+        Box::new(OPPORTUNITIES_PUBLISHED.clone()),
+        Box::new(RECOVERY_SELLS.clone()),
+        Box::new(INVENTORY_SWEEPS.clone()),
+        Box::new(INVENTORY_TOKEN_VALUE_LAMPORTS.clone()),
+        Box::new(POOL_LAYOUT_CHECKS.clone()),
+        Box::new(POOL_LAYOUT_DIVERGENCES.clone()),
+        Box::new(POOL_LAYOUT_DRIFTING.clone()),
+        Box::new(BROADCAST_LAGGED_TOTAL.clone()),
+    ]
+}
+
+/// Registers every metric under `<namespace>_`, so several strategies can share one Prometheus
+pub fn init_metrics(strategy_id: &str) {
+    let namespace = metric_namespace(strategy_id);
+    let registry = Registry::new_custom(Some(namespace.clone()), None).expect("namespace is non-empty");
+    for collector in collectors() {
+        registry.register(collector).unwrap();
+    }
+    if REGISTRY.set(registry).is_ok() {
+        let _ = NAMESPACE.set(namespace);
+    }
+}
+
+/// Namespace chosen by `init_metrics`, if it ran
+pub fn namespace() -> Option<&'static str> {
+    NAMESPACE.get().map(String::as_str)
 }
 
-pub fn init_metrics() {
-    REGISTRY.register(Box::new(OPPORTUNITIES_TOTAL.clone())).unwrap();
-    REGISTRY.register(Box::new(OPPORTUNITIES_PROFITABLE.clone())).unwrap();
-    REGISTRY.register(Box::new(TRADES_EXECUTED.clone())).unwrap();
-    REGISTRY.register(Box::new(PROFIT_LAMPORTS.clone())).unwrap();
-    REGISTRY.register(Box::new(LOSS_LAMPORTS.clone())).unwrap();
-    REGISTRY.register(Box::new(GAS_SPENT_LAMPORTS.clone())).unwrap();
-    REGISTRY.register(Box::new(ACTUAL_SLIPPAGE_BPS.clone())).unwrap();
-    REGISTRY.register(Box::new(DETECTION_LATENCY.clone())).unwrap();
-    REGISTRY.register(Box::new(EXECUTION_LATENCY.clone())).unwrap();
-    REGISTRY.register(Box::new(WEBSOCKET_STATUS.clone())).unwrap();
-    REGISTRY.register(Box::new(RPC_ERRORS.clone())).unwrap();
-    REGISTRY.register(Box::new(CIRCUIT_BREAKER_TRIGGERS.clone())).unwrap();
-    REGISTRY.register(Box::new(DAILY_PNL_LAMPORTS.clone())).unwrap();
-    REGISTRY.register(Box::new(SAFETY_REJECTIONS.clone())).unwrap();
-    REGISTRY.register(Box::new(DNA_MATCHES_TOTAL.clone())).unwrap();
-    REGISTRY.register(Box::new(DNA_ELITE_MATCHES_TOTAL.clone())).unwrap();
-    REGISTRY.register(Box::new(DISCOVERY_TOKENS_TOTAL.clone())).unwrap();
-    REGISTRY.register(Box::new(SAFETY_CACHE_HITS.clone())).unwrap();
-    REGISTRY.register(Box::new(SAFETY_CACHE_MISSES.clone())).unwrap();
-    REGISTRY.register(Box::new(POOL_DEDUP_SKIPS.clone())).unwrap();
-    REGISTRY.register(Box::new(JITO_BUNDLE_ERRORS.clone())).unwrap();
-    REGISTRY.register(Box::new(SAFETY_FAILURES.clone())).unwrap();
-    REGISTRY.register(Box::new(DISCOVERY_ERRORS.clone())).unwrap();
-    REGISTRY.register(Box::new(DISCOVERY_CACHE_HITS.clone())).unwrap();
-    REGISTRY.register(Box::new(OPPORTUNITIES_NON_DNA_TOTAL.clone())).unwrap();
-    REGISTRY.register(Box::new(ROUTE_DEPTH_HISTOGRAM.clone())).unwrap();
+/// Current values of every registered metric; empty before `init_metrics`
+pub fn gather() -> Vec<MetricFamily> {
+    REGISTRY.get().map(Registry::gather).unwrap_or_default()
 }
@@ -3,16 +3,20 @@ use std::env;
 use std::path::Path;
 
 fn main() {
-    let home = env::var("HOME").expect("HOME not set");
-    let path_str = format!("{}/.config/solana/id.json", home);
-    let path = Path::new(&path_str);
+    // Windows has USERPROFILE instead of HOME
+    let home = env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .expect("Neither HOME nor USERPROFILE is set");
+    let path_buf = Path::new(&home).join(".config").join("solana").join("id.json");
+    let path = path_buf.as_path();
+    let path_str = path.display().to_string();
     
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).expect("Failed to create config dir");
     }
 
     let keypair = Keypair::new();
-    write_keypair_file(&keypair, path_str.clone()).expect("Failed to write keypair");
+    write_keypair_file(&keypair, path).expect("Failed to write keypair");
     
     println!("✅ Generated new keypair at: {}", path_str);
     println!("🔑 Pubkey: {}", keypair.pubkey());
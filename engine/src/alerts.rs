@@ -1,4 +1,4 @@
-use reqwest::Client;
+use executor::http::HttpClient;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use crate::metrics::BotMetrics;
@@ -8,6 +8,10 @@ use tokio::time::{Instant, Duration}; // Use tokio's Instant and Duration for as
 use serde_json::{json, Value}; // Add Value for parsing Telegram responses
 use solana_sdk::pubkey::Pubkey;
 use crate::wallet_manager::WalletManager;
+use crate::drawdown::DrawdownBreaker;
+use crate::schedule::StrategySchedule;
+#[cfg(not(feature = "paper-only"))]
+use crate::wallet_failover::WalletFailover;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AlertSeverity {
@@ -47,8 +51,12 @@ pub struct AlertManager {
     discord_webhook: Option<String>,
     telegram_config: Option<TelegramConfig>,
     ntfy_topic: Option<String>,
-    client: Client,
+    http: Arc<HttpClient>,
     last_alerts: Mutex<HashMap<String, Instant>>,
+    drawdown: Option<Arc<DrawdownBreaker>>,
+    schedule: Option<Arc<StrategySchedule>>,
+    #[cfg(not(feature = "paper-only"))]
+    failover: Option<Arc<WalletFailover>>,
 }
 
 pub struct TelegramConfig {
@@ -57,16 +65,39 @@ pub struct TelegramConfig {
 }
 
 impl AlertManager {
-    pub fn new(discord_webhook: Option<String>, telegram_config: Option<TelegramConfig>, ntfy_topic: Option<String>) -> Self {
+    pub fn new(discord_webhook: Option<String>, telegram_config: Option<TelegramConfig>, ntfy_topic: Option<String>, http: Arc<HttpClient>) -> Self {
         Self {
             discord_webhook,
             telegram_config,
             ntfy_topic,
-            client: Client::new(),
+            http,
             last_alerts: Mutex::new(HashMap::new()),
+            drawdown: None,
+            schedule: None,
+            #[cfg(not(feature = "paper-only"))]
+            failover: None,
         }
     }
+
+    /// Lets /resume lift a drawdown halt and /status report it
+    pub fn with_drawdown_breaker(mut self, breaker: Arc<DrawdownBreaker>) -> Self {
+        self.drawdown = Some(breaker);
+        self
+    }
     
+    /// Lets /schedule show and override the strategy schedule
+    pub fn with_schedule(mut self, schedule: Arc<StrategySchedule>) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Lets /rotate_wallet switch signers, and /status and /balance follow the active one
+    #[cfg(not(feature = "paper-only"))]
+    pub fn with_wallet_failover(mut self, failover: Arc<WalletFailover>) -> Self {
+        self.failover = Some(failover);
+        self
+    }
+
     pub async fn send_alert(&self, severity: AlertSeverity, title: &str, message: &str, fields: Vec<Field>) {
         // Simple Rate Limiting (Prevent spamming the same title/message within 5 minutes)
         let alert_key = format!("{}:{}", title, message);
@@ -99,80 +130,97 @@ impl AlertManager {
     }
     
     async fn dispatch_alert(&self, _severity: AlertSeverity, title: &str, message: &str, fields: Vec<Field>, color: u32) {
-        // Discord webhook
-        if let Some(webhook_url) = &self.discord_webhook {
-            let mut embed = json!({
-                "title": title,
-                "description": message,
-                "color": color,
-                "timestamp": chrono::Utc::now().to_rfc3339(),
-            });
-
-            if !fields.is_empty() {
-                let discord_fields: Vec<_> = fields.iter().map(|f| json!({
-                    "name": &f.name,
-                    "value": &f.value,
-                    "inline": f.inline
-                })).collect();
-                embed["fields"] = json!(discord_fields);
+        for (sink, result) in self.deliver(title, message, &fields, color).await {
+            match result {
+                Ok(()) => tracing::info!("✅ {} alert dispatched successfully.", sink),
+                Err(e) => tracing::error!("Failed to send {} alert: {}", sink, e),
             }
+        }
+    }
 
-            let payload = json!({
-                "embeds": [embed]
-            });
-            
-            if let Err(e) = self.client.post(webhook_url).json(&payload).send().await {
-                tracing::error!("Failed to send Discord alert: {}", e);
-            } else {
-                tracing::info!("✅ Discord alert dispatched successfully.");
-            }
+    /// Sends one message to every configured sink; one result per sink
+    async fn deliver(&self, title: &str, message: &str, fields: &[Field], color: u32) -> Vec<(&'static str, anyhow::Result<()>)> {
+        let mut results = Vec::new();
+        if let Some(webhook_url) = &self.discord_webhook {
+            results.push(("Discord", self.deliver_discord(webhook_url, title, message, fields, color).await));
         }
-        
-        // Telegram
         if let Some(config) = &self.telegram_config {
-            let url = format!(
-                "https://api.telegram.org/bot{}/sendMessage",
-                config.bot_token
-            );
-            
-            let mut text = format!("<b>{}</b>\n\n{}", title, message);
-            if !fields.is_empty() {
-                for field in &fields {
-                    text.push_str(&format!("\n\n<b>{}</b>: {}", field.name, field.value));
-                }
-            }
+            results.push(("Telegram", self.deliver_telegram(config, title, message, fields).await));
+        }
+        if let Some(topic) = &self.ntfy_topic {
+            results.push(("ntfy", self.deliver_ntfy(topic, title, message).await));
+        }
+        results
+    }
 
-            let payload = json!({
-                "chat_id": config.chat_id,
-                "text": text,
-                "parse_mode": "HTML",
-            });
-            
-            match self.client.post(&url).json(&payload).send().await {
-                Ok(resp) => {
-                    let status = resp.status();
-                    if !status.is_success() {
-                        let err_text = resp.text().await.unwrap_or_default();
-                        tracing::error!("Telegram API error ({}): {}", status, err_text);
-                    } else {
-                        tracing::info!("✅ Telegram alert dispatched successfully.");
-                    }
-                }
-                Err(e) => tracing::error!("Failed to send Telegram alert: {}", e),
-            }
+    /// A test message through every configured sink, bypassing the rate limit. Empty if none is configured.
+    pub async fn test_sinks(&self) -> Vec<(&'static str, anyhow::Result<()>)> {
+        let title = format!("{} Self-test", AlertSeverity::Info.to_emoji());
+        self.deliver(&title, "Alert delivery check from `engine --self-test`.", &[], AlertSeverity::Info.to_color()).await
+    }
+
+    async fn deliver_discord(&self, webhook_url: &str, title: &str, message: &str, fields: &[Field], color: u32) -> anyhow::Result<()> {
+        let mut embed = json!({
+            "title": title,
+            "description": message,
+            "color": color,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        });
+
+        if !fields.is_empty() {
+            let discord_fields: Vec<_> = fields.iter().map(|f| json!({
+                "name": &f.name,
+                "value": &f.value,
+                "inline": f.inline
+            })).collect();
+            embed["fields"] = json!(discord_fields);
         }
 
-        // ntfy.sh
-        if let Some(topic) = &self.ntfy_topic {
-            let url = format!("https://ntfy.sh/{}", topic);
-            let payload = format!("{}: {}", title, message);
-            
-            if let Err(e) = self.client.post(&url).body(payload).send().await {
-                tracing::error!("Failed to send ntfy alert: {}", e);
-            } else {
-                tracing::info!("✅ ntfy alert dispatched successfully.");
-            }
+        let payload = json!({
+            "embeds": [embed]
+        });
+        let resp = self.http.send(self.http.post(webhook_url).json(&payload)).await?;
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!("Discord returned {}", resp.status()));
         }
+        Ok(())
+    }
+
+    async fn deliver_telegram(&self, config: &TelegramConfig, title: &str, message: &str, fields: &[Field]) -> anyhow::Result<()> {
+        let url = format!(
+            "https://api.telegram.org/bot{}/sendMessage",
+            config.bot_token
+        );
+
+        let mut text = format!("<b>{}</b>\n\n{}", title, message);
+        for field in fields {
+            text.push_str(&format!("\n\n<b>{}</b>: {}", field.name, field.value));
+        }
+
+        let payload = json!({
+            "chat_id": config.chat_id,
+            "text": text,
+            "parse_mode": "HTML",
+        });
+
+        let resp = self.http.send(self.http.post(&url).json(&payload)).await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let err_text = resp.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Telegram API error ({}): {}", status, err_text));
+        }
+        Ok(())
+    }
+
+    async fn deliver_ntfy(&self, topic: &str, title: &str, message: &str) -> anyhow::Result<()> {
+        let url = format!("https://ntfy.sh/{}", topic);
+        let payload = format!("{}: {}", title, message);
+
+        let resp = self.http.send(self.http.post(&url).body(payload)).await?;
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!("ntfy returned {}", resp.status()));
+        }
+        Ok(())
     }
 
     /// V2: Handle incoming Telegram commands (Poll-based)
@@ -195,7 +243,7 @@ impl AlertManager {
                     config.bot_token, last_update_id + 1
                 );
 
-                match self.client.get(&url).send().await {
+                match self.http.send(self.http.get(&url)).await {
                     Ok(resp) => {
                         if let Ok(json) = resp.json::<Value>().await {
                             if let Some(result) = json.get("result").and_then(|r: &Value| r.as_array()) {
@@ -214,6 +262,8 @@ impl AlertManager {
                                         // Only respond to our configured chat
                                         if chat_id != config.chat_id { continue; }
 
+                                        #[cfg(not(feature = "paper-only"))]
+                                        let payer_pubkey = self.failover.as_ref().map_or(payer_pubkey, |f| f.active_pubkey());
                                         if let Some(text) = message.get("text").and_then(|t: &Value| t.as_str()) {
                                             match text {
                                                 "/status" => {
@@ -225,6 +275,10 @@ impl AlertManager {
                                                     self.send_alert(AlertSeverity::Warning, "Remote Control", "⏸ Trading PAUSED via Telegram.", vec![]).await;
                                                 }
                                                 "/resume" => {
+                                                    if let Some(breaker) = &self.drawdown {
+                                                        breaker.resume(metrics.net_pnl_lamports(), std::time::Instant::now());
+                                                        crate::telemetry::DRAWDOWN_BREAKER_HALTED.set(0);
+                                                    }
                                                     metrics.is_paused.store(false, Ordering::Relaxed);
                                                     self.send_alert(AlertSeverity::Success, "Remote Control", "▶️ Trading RESUMED via Telegram.", vec![]).await;
                                                 }
@@ -234,10 +288,34 @@ impl AlertManager {
                                                         self.send_alert(AlertSeverity::Info, "Balance Request", &format!("Current Wallet Balance: {:.6} SOL", sol), vec![]).await;
                                                     }
                                                 }
+                                                #[cfg(feature = "paper-only")]
+                                                "/rotate_wallet" => self.send_alert(AlertSeverity::Info, "Remote Control", "Paper-only build: there is no live signer to rotate", vec![]).await,
+                                                #[cfg(not(feature = "paper-only"))]
+                                                "/rotate_wallet" => {
+                                                    match &self.failover {
+                                                        Some(failover) => {
+                                                            let outcome = failover.rotate("manual").await;
+                                                            self.send_alert(AlertSeverity::Warning, "Remote Control", &format!("🔁 {}", outcome), vec![]).await;
+                                                        }
+                                                        None => self.send_alert(AlertSeverity::Info, "Remote Control", "No standby keypair configured (STANDBY_KEYPAIR_PATH)", vec![]).await,
+                                                    }
+                                                }
+                                                "/latency" => {
+                                                    let report = metrics.latency.report();
+                                                    self.send_alert(AlertSeverity::Info, "Latency Breakdown", &report, vec![]).await;
+                                                }
                                                 "/help" => {
-                                                    let help_text = "<b>Available Commands:</b>\n/status - Full performance report\n/pause - Stop all trading\n/resume - Start trading again\n/balance - Check SOL balance";
+                                                    let help_text = "<b>Available Commands:</b>\n/status - Full performance report\n/pause - Stop all trading\n/resume - Start trading again\n/balance - Check SOL balance\n/rotate_wallet - Switch to the other signer after a safety check\n/latency - p50/p95/p99 per stage (last hour)\n/schedule [strategy on|off|auto] - Strategy windows and overrides";
                                                     self.send_alert(AlertSeverity::Info, "Bot Menu", help_text, vec![]).await;
                                                 }
+                                                cmd if cmd.starts_with("/schedule") => {
+                                                    if let Some(schedule) = &self.schedule {
+                                                        match schedule.command(&cmd["/schedule".len()..]) {
+                                                            Ok(state) => self.send_alert(AlertSeverity::Info, "Strategy Schedule", &state, vec![]).await,
+                                                            Err(e) => self.send_alert(AlertSeverity::Warning, "Strategy Schedule", &e, vec![]).await,
+                                                        }
+                                                    }
+                                                }
                                                 _ => {}
                                             }
                                         }
@@ -262,7 +340,8 @@ impl AlertManager {
         let _detected = metrics.opportunities_detected.load(Ordering::Relaxed);
         let jito_success = metrics.execution_jito_success.load(Ordering::Relaxed);
         let rpc_success = metrics.execution_rpc_fallback_success.load(Ordering::Relaxed);
-        let total_executions = jito_success + rpc_success;
+        let tpu_success = metrics.execution_tpu_success.load(Ordering::Relaxed);
+        let total_executions = jito_success + rpc_success + tpu_success;
         let exec_attempts = metrics.execution_attempts_total.load(Ordering::Relaxed);
         let rejected_sanity = metrics.opportunities_rejected_profit_sanity.load(Ordering::Relaxed);
         let rejected_safety = metrics.opportunities_rejected_safety.load(Ordering::Relaxed);
@@ -288,11 +367,48 @@ impl AlertManager {
             (uptime.as_secs() % 3600) / 60
         );
 
-        let status_emoji = if metrics.is_paused.load(Ordering::Relaxed) { "⏸ (PAUSED)" } else { "🟢 (ACTIVE)" };
+        let halted = self.drawdown.as_ref().filter(|b| b.is_tripped());
+        let status_emoji = match halted {
+            Some(breaker) => match breaker.resume_in(std::time::Instant::now()) {
+                Some(left) => format!("🧯 (DRAWDOWN HALT, resumes in {}m)", left.as_secs().div_ceil(60)),
+                None => "🧯 (DRAWDOWN HALT, /resume to restart)".to_string(),
+            },
+            None if metrics.is_paused.load(Ordering::Relaxed) => "⏸ (PAUSED)".to_string(),
+            None => "🟢 (ACTIVE)".to_string(),
+        };
+
+        let journal = match &metrics.trade_journal {
+            Some(journal) => match journal.summary(86_400).await {
+                Ok(summary) => format!(
+                    "\n\n🗄️ <b>TRADE JOURNAL (24H)</b>\n\
+                     - Landed / Failed: {} / {}\n\
+                     - Send Failures: {} | Rejected: {}\n\
+                     - Realized P&L: <code>{:.6} SOL</code>{}",
+                    summary.landed, summary.failed, summary.send_failed, summary.rejected,
+                    summary.realized_pnl_lamports as f64 / 1e9,
+                    summary.top_rejections.first()
+                        .map(|(reason, count)| format!("\n- Top Rejection: {} ({})", reason, count))
+                        .unwrap_or_default(),
+                ),
+                Err(e) => {
+                    tracing::warn!("⚠️ Trade journal summary failed: {}", e);
+                    String::new()
+                }
+            },
+            None => String::new(),
+        };
+
+        let funnel: String = crate::funnel::stages(&metrics.funnel.snapshot()).into_iter()
+            .map(|(stage, count, drop)| match drop {
+                Some(drop) => format!("\n- {}: {} (<b>-{:.1}%</b>)", stage.label(), count, drop),
+                None => format!("\n- {}: {}", stage.label(), count),
+            })
+            .collect();
 
         format!(
             "<b>Live Performance Report</b>\n\
              ⏱ <b>Uptime:</b> {} | <b>Mode:</b> {}\n\n\
+             🔻 <b>FUNNEL</b>{}\n\n\
              🛡️ <b>SAFETY ANALYTICS</b>\n\
              - Rejected (Rug Shield): {}\n\
              - Rejected (Slippage): {}\n\
@@ -304,18 +420,19 @@ impl AlertManager {
              💰 <b>ECONOMICS</b>\n\
              - Gas Spent: {:.6} SOL\n\
              - Wallet: {:.4} SOL\n\
-             - 💵 <b>NET P&L:</b> <code>{:.6} SOL</code>",
-            uptime_str, status_emoji, rejected_rug, rejected_slippage, rejected_sanity, rejected_safety,
+             - 💵 <b>NET P&L:</b> <code>{:.6} SOL</code>{}",
+            uptime_str, status_emoji, funnel, rejected_rug, rejected_slippage, rejected_sanity, rejected_safety,
             success_rate, exec_attempts, total_executions, jito_success, rpc_success,
-            gas, current_sol, net_pnl
+            gas, current_sol, net_pnl, journal
         )
     }
 
-    pub async fn send_final_report(&self, metrics: Arc<BotMetrics>, start_time: Instant) {
+    pub async fn send_final_report(&self, metrics: Arc<BotMetrics>, start_time: Instant, config_hash: &str) {
         let detected = metrics.opportunities_detected.load(Ordering::Relaxed);
         let jito_success = metrics.execution_jito_success.load(Ordering::Relaxed);
         let rpc_success = metrics.execution_rpc_fallback_success.load(Ordering::Relaxed);
-        let total_executions = jito_success + rpc_success;
+        let tpu_success = metrics.execution_tpu_success.load(Ordering::Relaxed);
+        let total_executions = jito_success + rpc_success + tpu_success;
         let exec_attempts = metrics.execution_attempts_total.load(Ordering::Relaxed);
         let rejected_sanity = metrics.opportunities_rejected_profit_sanity.load(Ordering::Relaxed);
         let rejected_safety = metrics.opportunities_rejected_safety.load(Ordering::Relaxed);
@@ -341,7 +458,8 @@ impl AlertManager {
 
         let message = format!(
             "<b>Final Session Performance</b>\n\
-             ⏱ <b>Uptime:</b> {}\n\n\
+             ⏱ <b>Uptime:</b> {}\n\
+             🧾 <b>Config:</b> <code>{}</code>\n\n\
              📈 <b>ARBITRAGE STATS</b>\n\
              - Detected: {}\n\
              - Rejected (Sanity): {}\n\
@@ -353,7 +471,7 @@ impl AlertManager {
              💰 <b>FINAL BALANCE</b>\n\
              - Gas Spent: {:.6} SOL\n\
              - 💵 <b>Net P&L:</b> <code>{:.6} SOL</code>",
-            uptime_str, detected, rejected_sanity, rejected_safety, 
+            uptime_str, config_hash, detected, rejected_sanity, rejected_safety, 
             exec_attempts, total_executions, jito_success, rpc_success,
             success_rate, gas, net_pnl
         );
@@ -366,12 +484,13 @@ impl AlertManager {
                 Field { name: "Net PnL".to_string(), value: format!("{:.6} SOL", net_pnl), inline: true },
                 Field { name: "Uptime".to_string(), value: uptime_str, inline: true },
                 Field { name: "Success %".to_string(), value: format!("{:.1}%", success_rate), inline: true },
+                Field { name: "Config".to_string(), value: config_hash.to_string(), inline: true },
             ]
         ).await;
     }
 
     pub async fn send_trade_notification(&self, opportunity: &mev_core::ArbitrageOpportunity, signature: &str) {
-        let profit_sol = opportunity.expected_profit_lamports as f64 / 1e9;
+        let profit_sol = opportunity.profit_in_sol() as f64 / 1e9;
         let title = "🔥 BUNDLE DISPATCHED";
         let message = format!(
             "<b>Profit:</b> <code>{:.6} SOL</code>\n\
@@ -413,7 +532,8 @@ pub async fn monitor_health(
         let detected = metrics.opportunities_detected.load(Ordering::Relaxed);
         let jito_success = metrics.execution_jito_success.load(Ordering::Relaxed);
         let rpc_success = metrics.execution_rpc_fallback_success.load(Ordering::Relaxed);
-        let total_executions = jito_success + rpc_success;
+        let tpu_success = metrics.execution_tpu_success.load(Ordering::Relaxed);
+        let total_executions = jito_success + rpc_success + tpu_success;
         let exec_attempts = metrics.execution_attempts_total.load(Ordering::Relaxed);
 
         // 1. WebSocket Health Check
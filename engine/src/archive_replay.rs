@@ -0,0 +1,260 @@
+/// Archival Replay
+///
+/// `engine replay <start_slot> <end_slot> <pool,pool,...> [dir]` rebuilds the
+/// market stream of a past slot range for the backtester. Pool accounts can't
+/// be read as of an old slot, so their state is taken from transactions: every
+/// successful transaction touching a watched pool in the range is fetched from
+/// `ARCHIVE_RPC_URL` (an archival node; most RPCs only serve recent history),
+/// and the token balances of the pool's vaults around it give the reserves.
+/// The first transaction's pre-balances seed each pool and every transaction's
+/// post-balances are one update. The rows go to `<dir>/market_data.csv` in the
+/// recorder's format, ordered by slot, and `--backtest` is run over them.
+/// Raydium V4 pools only: concentrated-liquidity state isn't in vault balances.
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{EncodedTransaction, UiMessage, UiTransactionEncoding, UiTransactionTokenBalance};
+use tokio::io::AsyncWriteExt;
+use mev_core::constants::RAYDIUM_V4_PROGRAM;
+use mev_core::PoolUpdate;
+use executor::rpc_pool::RpcPool;
+use crate::config::BotConfig;
+use crate::pool_fetcher::PoolKeyFetcher;
+
+const SIGNATURE_PAGE: usize = 1_000;
+/// Stops paging a pool's history past this many transactions in range
+const MAX_TRANSACTIONS_PER_POOL: usize = 50_000;
+const RAYDIUM_V4_FEE_BPS: u16 = 25;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayRequest {
+    pub start_slot: u64,
+    pub end_slot: u64,
+    pub pools: Vec<Pubkey>,
+    pub dir: PathBuf,
+}
+
+impl ReplayRequest {
+    /// `<start_slot> <end_slot> <pool,pool,...> [dir]`, the arguments after `replay`
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        const USAGE: &str = "Usage: engine replay <start_slot> <end_slot> <pool,pool,...> [dir]";
+        let [start, end, pools, rest @ ..] = args else {
+            return Err(USAGE.to_string());
+        };
+        let slot = |s: &str| s.parse::<u64>().map_err(|_| format!("bad slot '{}'. {}", s, USAGE));
+        let (start_slot, end_slot) = (slot(start)?, slot(end)?);
+        if end_slot < start_slot {
+            return Err(format!("end slot {} is before start slot {}", end_slot, start_slot));
+        }
+        let pools = pools.split(',')
+            .map(|p| Pubkey::from_str(p.trim()).map_err(|_| format!("bad pool address '{}'", p.trim())))
+            .collect::<Result<Vec<_>, _>>()?;
+        let dir = rest.first()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| crate::platform::data_dir().join(format!("replay_{}_{}", start_slot, end_slot)));
+        Ok(Self { start_slot, end_slot, pools, dir })
+    }
+}
+
+/// A pool whose reserves are the balances of its two vaults
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WatchedPool {
+    pool: Pubkey,
+    coin_vault: Pubkey,
+    pc_vault: Pubkey,
+}
+
+impl WatchedPool {
+    /// The pool as `balances` (mint and amount by token account) left it
+    fn update(&self, balances: &HashMap<Pubkey, (Pubkey, u64)>, timestamp: u64) -> Option<PoolUpdate> {
+        let (mint_a, reserve_a) = *balances.get(&self.coin_vault)?;
+        let (mint_b, reserve_b) = *balances.get(&self.pc_vault)?;
+        Some(PoolUpdate {
+            pool_address: self.pool,
+            program_id: RAYDIUM_V4_PROGRAM,
+            mint_a,
+            mint_b,
+            reserve_a: reserve_a as u128,
+            reserve_b: reserve_b as u128,
+            price_sqrt: None,
+            liquidity: None,
+            fee_bps: RAYDIUM_V4_FEE_BPS,
+            timestamp,
+        })
+    }
+}
+
+/// Token balances of one side of a transaction, by the token account they belong to
+fn balances_by_account(keys: &[Pubkey], balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>) -> HashMap<Pubkey, (Pubkey, u64)> {
+    let OptionSerializer::Some(balances) = balances else {
+        return HashMap::new();
+    };
+    balances.iter()
+        .filter_map(|b| {
+            let account = *keys.get(b.account_index as usize)?;
+            let mint = Pubkey::from_str(&b.mint).ok()?;
+            Some((account, (mint, b.ui_token_amount.amount.parse().ok()?)))
+        })
+        .collect()
+}
+
+/// Successful transactions touching `pool` within the slots, oldest first
+async fn signatures_in_range(rpc: &RpcPool, pool: &Pubkey, start_slot: u64, end_slot: u64) -> anyhow::Result<Vec<(u64, Signature)>> {
+    let mut found = Vec::new();
+    let mut before: Option<Signature> = None;
+    loop {
+        let page = rpc.call(|c| async move {
+            c.get_signatures_for_address_with_config(pool, GetConfirmedSignaturesForAddress2Config {
+                before,
+                until: None,
+                limit: Some(SIGNATURE_PAGE),
+                commitment: Some(CommitmentConfig::confirmed()),
+            }).await
+        }).await?;
+        let Some(last) = page.last() else { break };
+        let oldest_slot = last.slot;
+        before = Some(Signature::from_str(&last.signature)?);
+        found.extend(page.into_iter()
+            .filter(|s| s.err.is_none() && (start_slot..=end_slot).contains(&s.slot))
+            .filter_map(|s| Some((s.slot, Signature::from_str(&s.signature).ok()?))));
+        if oldest_slot < start_slot {
+            break;
+        }
+        if found.len() >= MAX_TRANSACTIONS_PER_POOL {
+            tracing::warn!("📼 {} has over {} transactions in range; replaying the latest of them only", pool, MAX_TRANSACTIONS_PER_POOL);
+            break;
+        }
+    }
+    // Newest first from the node, within a slot too
+    found.reverse();
+    Ok(found)
+}
+
+/// Rebuilds the pools' updates over the range from the node at `url`, oldest first
+pub async fn reconstruct(url: &str, request: &ReplayRequest) -> anyhow::Result<Vec<PoolUpdate>> {
+    let rpc = RpcPool::single(url);
+    let fetcher = PoolKeyFetcher::new(Arc::new(RpcPool::single(url)));
+    let mut watched = Vec::new();
+    for pool in &request.pools {
+        match fetcher.fetch_raydium_keys(pool).await {
+            Ok(keys) => watched.push(WatchedPool { pool: *pool, coin_vault: keys.amm_coin_vault, pc_vault: keys.amm_pc_vault }),
+            Err(e) => tracing::warn!("📼 Skipping {}: not a Raydium V4 pool ({})", pool, e),
+        }
+    }
+    if watched.is_empty() {
+        return Err(anyhow::anyhow!("None of the pools could be replayed"));
+    }
+
+    // One fetch per transaction, however many watched pools it touches. Within a slot,
+    // each pool's own order is kept; across pools it is only approximate.
+    let mut transactions: Vec<(u64, usize, Signature)> = Vec::new();
+    let mut seen = HashSet::new();
+    for pool in &watched {
+        let signatures = signatures_in_range(&rpc, &pool.pool, request.start_slot, request.end_slot).await?;
+        tracing::info!("📼 {}: {} transactions in slots {}-{}", pool.pool, signatures.len(), request.start_slot, request.end_slot);
+        for (order, (slot, signature)) in signatures.into_iter().enumerate() {
+            if seen.insert(signature) {
+                transactions.push((slot, order, signature));
+            }
+        }
+    }
+    transactions.sort_by_key(|&(slot, order, _)| (slot, order));
+
+    let mut updates = Vec::new();
+    let mut seeded = HashSet::new();
+    for (i, &(_, _, signature)) in transactions.iter().enumerate() {
+        if i > 0 && i % 500 == 0 {
+            tracing::info!("📼 Fetched {}/{} transactions", i, transactions.len());
+        }
+        let tx = rpc.call(|c| async move {
+            c.get_transaction_with_config(&signature, RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            }).await
+        }).await?;
+        let timestamp = tx.block_time.unwrap_or_default().max(0) as u64;
+        let Some(meta) = tx.transaction.meta else { continue };
+
+        // Static keys, then the ones loaded from lookup tables, writable first
+        let mut keys: Vec<Pubkey> = match tx.transaction.transaction {
+            EncodedTransaction::Json(ui_tx) => match ui_tx.message {
+                UiMessage::Raw(message) => message.account_keys.iter().filter_map(|k| Pubkey::from_str(k).ok()).collect(),
+                UiMessage::Parsed(_) => continue,
+            },
+            _ => continue,
+        };
+        if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+            keys.extend(loaded.writable.iter().chain(&loaded.readonly).filter_map(|k| Pubkey::from_str(k).ok()));
+        }
+
+        let pre = balances_by_account(&keys, &meta.pre_token_balances);
+        let post = balances_by_account(&keys, &meta.post_token_balances);
+        for pool in &watched {
+            let Some(after) = pool.update(&post, timestamp) else { continue };
+            if seeded.insert(pool.pool) {
+                updates.extend(pool.update(&pre, timestamp));
+            }
+            updates.push(after);
+        }
+    }
+    Ok(updates)
+}
+
+async fn write_market_data(dir: &Path, updates: &[PoolUpdate]) -> anyhow::Result<PathBuf> {
+    tokio::fs::create_dir_all(dir).await?;
+    let path = dir.join("market_data.csv");
+    let mut file = tokio::io::BufWriter::new(tokio::fs::File::create(&path).await?);
+    file.write_all(crate::recorder::MARKET_DATA_HEADER.as_bytes()).await?;
+    for update in updates {
+        file.write_all(crate::recorder::market_row(update).as_bytes()).await?;
+    }
+    file.flush().await?;
+    Ok(path)
+}
+
+/// Reconstructs the range, writes it where the backtest reads, and backtests it
+pub async fn run(cfg: &BotConfig, request: &ReplayRequest) -> anyhow::Result<crate::backtest::BacktestReport> {
+    let url = cfg.archive_rpc_url.clone().unwrap_or_else(|| cfg.rpc_url.clone());
+    tracing::info!("📼 Archival replay of slots {}-{} for {} pool(s) from {}", request.start_slot, request.end_slot, request.pools.len(), url);
+    let updates = reconstruct(&url, request).await?;
+    let path = write_market_data(&request.dir, &updates).await?;
+    tracing::info!("📼 {} updates written to {}", updates.len(), path.display());
+    crate::backtest::run(cfg, &request.dir).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_balances_become_reserves() {
+        let (coin_mint, pc_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let pool = WatchedPool { pool: Pubkey::new_unique(), coin_vault: Pubkey::new_unique(), pc_vault: Pubkey::new_unique() };
+        let balances = HashMap::from([
+            (pool.coin_vault, (coin_mint, 5_000)),
+            (pool.pc_vault, (pc_mint, 20_000)),
+            (Pubkey::new_unique(), (pc_mint, 1)),
+        ]);
+
+        let update = pool.update(&balances, 1_700_000_000).unwrap();
+        assert_eq!((update.mint_a, update.mint_b), (coin_mint, pc_mint));
+        assert_eq!((update.reserve_a, update.reserve_b, update.timestamp), (5_000, 20_000, 1_700_000_000));
+        // A transaction that didn't touch both vaults says nothing about the pool
+        assert!(pool.update(&HashMap::from([(pool.coin_vault, (coin_mint, 1))]), 0).is_none());
+
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        let request = ReplayRequest::parse(&args(&format!("100 200 {},{} out", pool.pool, pool.coin_vault))).unwrap();
+        assert_eq!((request.start_slot, request.end_slot, request.pools.len()), (100, 200, 2));
+        assert_eq!(request.dir, PathBuf::from("out"));
+        assert!(ReplayRequest::parse(&args(&format!("200 100 {}", pool.pool))).is_err());
+        assert!(ReplayRequest::parse(&args("100 200")).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+/// Back-Run Mode
+///
+/// A large swap knocks its pool off the rest of the market, and the moment
+/// right after it lands is when the corrective arbitrage pays most. With
+/// back-running on, every decoded `ray_log` swap on a watched pool is sized
+/// against the pool's balances; one that moves the input side by at least the
+/// configured share becomes a pool update carrying the post-swap reserves and
+/// goes straight onto the priority lane of the work queue, whatever its quote
+/// mint. The worker's cycle search is anchored on the updated pool, so the
+/// affected pool's edges are searched first. Nothing here looks at pending
+/// transactions: only swaps that already executed trigger a search.
+use std::sync::Arc;
+use mev_core::raydium::{RaySwapLog, SwapDirection};
+use mev_core::{MarketUpdate, PoolUpdate};
+use solana_sdk::pubkey::Pubkey;
+use strategy::StrategyEngine;
+use tokio::sync::mpsc;
+use crate::schedule::{Strategy, StrategySchedule};
+use crate::worker_pool::WorkSender;
+
+/// Triggers waiting for the work queue; more than this and the oldest moves are stale anyway
+const TRIGGER_CAPACITY: usize = 256;
+
+pub struct BackrunTrigger {
+    /// Smallest swap, in bps of the pool's input-side balance, that triggers a search
+    min_impact_bps: u64,
+    engine: Arc<StrategyEngine>,
+    tx: mpsc::Sender<MarketUpdate>,
+}
+
+impl BackrunTrigger {
+    pub fn new(min_impact_bps: u64, engine: Arc<StrategyEngine>) -> (Self, mpsc::Receiver<MarketUpdate>) {
+        let (tx, rx) = mpsc::channel(TRIGGER_CAPACITY);
+        (Self { min_impact_bps, engine, tx }, rx)
+    }
+
+    /// Queues a search if `swap` is large enough. Pools the graph doesn't know yet are skipped.
+    pub fn observe(&self, pool: Pubkey, swap: &RaySwapLog) -> bool {
+        if impact_bps(swap) < self.min_impact_bps {
+            return false;
+        }
+        let Some(state) = self.engine.pool_state(&pool) else {
+            return false;
+        };
+        let update = post_swap_update(&state, swap, chrono::Utc::now().timestamp());
+        if self.tx.try_send(update).is_err() {
+            return false;
+        }
+        crate::telemetry::BACKRUN_TRIGGERS.inc();
+        tracing::debug!("🎯 Back-run: {} bps swap on {}", impact_bps(swap), pool);
+        true
+    }
+}
+
+/// Swap size in bps of the balance it was paid into
+pub fn impact_bps(swap: &RaySwapLog) -> u64 {
+    let reserve_in = match swap.direction {
+        SwapDirection::PcToCoin => swap.pool_pc,
+        SwapDirection::CoinToPc => swap.pool_coin,
+    };
+    if reserve_in == 0 {
+        return 0;
+    }
+    (swap.amount_in as u128 * 10_000 / reserve_in as u128) as u64
+}
+
+/// The pool as the swap left it, mints taken from the graph
+fn post_swap_update(state: &PoolUpdate, swap: &RaySwapLog, timestamp: i64) -> MarketUpdate {
+    let (coin_reserve, pc_reserve) = swap.reserves_after();
+    MarketUpdate {
+        pool_address: state.pool_address,
+        program_id: state.program_id,
+        coin_mint: state.mint_a,
+        pc_mint: state.mint_b,
+        coin_reserve,
+        pc_reserve,
+        price_sqrt: None,
+        liquidity: None,
+        fee_bps: Some(state.fee_bps),
+        timestamp,
+        trigger: mev_core::Trigger::Backrun,
+    }
+}
+
+/// Moves triggers onto the work queue's priority lane until the workers are gone.
+/// Outside the back-run window triggers are dropped; the regular feed still carries the pool.
+pub async fn forward(mut rx: mpsc::Receiver<MarketUpdate>, work_tx: WorkSender, schedule: Arc<StrategySchedule>) {
+    while let Some(update) = rx.recv().await {
+        if !schedule.current(Strategy::Backrun).active {
+            continue;
+        }
+        if work_tx.send_priority(update).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_swap_becomes_post_swap_update() {
+        // 3% of the quote balance swapped in: a 300 bps move
+        let swap = RaySwapLog {
+            direction: SwapDirection::PcToCoin,
+            amount_in: 30_000,
+            amount_out: 2_900,
+            pool_coin: 100_000,
+            pool_pc: 1_000_000,
+        };
+        assert_eq!(impact_bps(&swap), 300);
+        assert_eq!(impact_bps(&RaySwapLog { direction: SwapDirection::CoinToPc, amount_in: 100, ..swap }), 10);
+
+        let state = PoolUpdate {
+            pool_address: Pubkey::new_unique(),
+            program_id: mev_core::constants::RAYDIUM_V4_PROGRAM,
+            mint_a: Pubkey::new_unique(),
+            mint_b: mev_core::constants::SOL_MINT,
+            reserve_a: 100_000,
+            reserve_b: 1_000_000,
+            price_sqrt: None,
+            liquidity: None,
+            fee_bps: 25,
+            timestamp: 0,
+        };
+        let update = post_swap_update(&state, &swap, 42);
+        assert_eq!((update.coin_reserve, update.pc_reserve), (97_100, 1_030_000));
+        assert_eq!((update.coin_mint, update.pc_mint), (state.mint_a, state.mint_b));
+        assert_eq!(update.pool_address, state.pool_address);
+    }
+}
@@ -0,0 +1,254 @@
+/// Offline Backtesting
+///
+/// Replays `market_data.csv` written by the recorder through a `StrategyEngine`
+/// wired to a simulated executor. Every dispatched bundle is held for the
+/// configured latency and settled against the pool state at landing time: a
+/// route that still pays lands with its re-quoted profit, one that no longer
+/// does counts as reverted (Jito drops it, so only the opportunity is lost).
+/// Our own fills are not written back into the replayed pool state. The
+/// engine runs on a manual clock moved to each row's recorded time, so
+/// opportunity timestamps and slot claims match the original session.
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::clock::{Clock, ManualClock};
+use mev_core::{ArbitrageOpportunity, PoolUpdate};
+use strategy::StrategyEngine;
+use crate::config::BotConfig;
+
+/// Collects bundles instead of sending them
+#[derive(Default)]
+pub struct SimulatedExecutor {
+    payer: Pubkey,
+    dispatched: Mutex<Vec<(ArbitrageOpportunity, u64)>>,
+}
+
+impl SimulatedExecutor {
+    fn drain(&self) -> Vec<(ArbitrageOpportunity, u64)> {
+        std::mem::take(&mut *self.dispatched.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+#[async_trait::async_trait]
+impl strategy::ports::ExecutionPort for SimulatedExecutor {
+    async fn build_bundle_instructions(
+        &self,
+        _opportunity: ArbitrageOpportunity,
+        _tip_lamports: u64,
+        _max_slippage_bps: u16,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        Ok(Vec::new())
+    }
+
+    async fn build_and_send_bundle(
+        &self,
+        opportunity: ArbitrageOpportunity,
+        _recent_blockhash: solana_sdk::hash::Hash,
+        tip_lamports: u64,
+        _max_slippage_bps: u16,
+    ) -> anyhow::Result<String> {
+        let mut dispatched = self.dispatched.lock().unwrap_or_else(|e| e.into_inner());
+        dispatched.push((opportunity, tip_lamports));
+        Ok(format!("backtest-{}", dispatched.len()))
+    }
+
+    fn pubkey(&self) -> &Pubkey {
+        &self.payer
+    }
+}
+
+struct PendingFill {
+    opportunity: ArbitrageOpportunity,
+    tip_lamports: u64,
+    lands_at_ms: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct BacktestReport {
+    pub updates_replayed: u64,
+    pub rows_skipped: u64,
+    pub dispatched: u64,
+    pub landed: u64,
+    pub reverted: u64,
+    /// Quoted profit at detection, summed over dispatched bundles
+    pub quoted_profit_lamports: u64,
+    /// Re-quoted profit at landing, summed over landed bundles
+    pub realized_profit_lamports: u64,
+    pub tips_paid_lamports: u64,
+    /// Replayed updates by `EventOutcome` label
+    pub outcomes: BTreeMap<&'static str, u64>,
+    /// Effective configuration the replay ran under
+    pub config_hash: String,
+}
+
+impl BacktestReport {
+    pub fn net_pnl_lamports(&self) -> i64 {
+        self.realized_profit_lamports as i64 - self.tips_paid_lamports as i64
+    }
+
+    pub fn hit_rate(&self) -> f64 {
+        if self.dispatched == 0 {
+            return 0.0;
+        }
+        self.landed as f64 / self.dispatched as f64
+    }
+
+    fn settle(&mut self, engine: &StrategyEngine, fill: PendingFill) {
+        match engine.requote(&fill.opportunity.steps, fill.opportunity.input_amount) {
+            Some(fresh) => {
+                self.landed += 1;
+                // Valued at the rate the dispatched quote was, for cycles from another mint
+                self.realized_profit_lamports += fill.opportunity.start_units_in_sol(fresh.expected_profit_lamports as i64).max(0) as u64;
+                self.tips_paid_lamports += fill.tip_lamports;
+            }
+            None => self.reverted += 1,
+        }
+    }
+}
+
+impl fmt::Display for BacktestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "📼 Config:             {}", self.config_hash)?;
+        writeln!(f, "📼 Updates replayed:   {} ({} rows skipped)", self.updates_replayed, self.rows_skipped)?;
+        writeln!(f, "📼 Bundles dispatched: {}", self.dispatched)?;
+        writeln!(f, "📼 Landed / reverted:  {} / {} (hit rate {:.1}%)", self.landed, self.reverted, self.hit_rate() * 100.0)?;
+        writeln!(f, "📼 Quoted profit:      {} lamports", self.quoted_profit_lamports)?;
+        writeln!(f, "📼 Realized profit:    {} lamports", self.realized_profit_lamports)?;
+        writeln!(f, "📼 Tips paid:          {} lamports", self.tips_paid_lamports)?;
+        if !self.outcomes.is_empty() {
+            writeln!(f, "📼 Outcomes:")?;
+        }
+        for (outcome, count) in &self.outcomes {
+            writeln!(f, "📼   {:<20} {}", outcome, count)?;
+        }
+        write!(f, "📼 Net PnL:            {} lamports ({:.6} SOL)", self.net_pnl_lamports(), self.net_pnl_lamports() as f64 / 1e9)
+    }
+}
+
+/// Parses one recorder row. Rows written before the recorder carried mints and fees return None.
+pub fn parse_market_row(line: &str) -> Option<PoolUpdate> {
+    let cols: Vec<&str> = line.trim_end().split(',').collect();
+    if cols.len() < 11 {
+        return None;
+    }
+    let optional = |col: &str| if col.is_empty() { Ok(None) } else { col.parse().map(Some) };
+
+    Some(PoolUpdate {
+        timestamp: cols[0].parse().ok()?,
+        pool_address: Pubkey::from_str(cols[1]).ok()?,
+        program_id: Pubkey::from_str(cols[2]).ok()?,
+        reserve_a: cols[3].parse().ok()?,
+        reserve_b: cols[4].parse().ok()?,
+        mint_a: Pubkey::from_str(cols[6]).ok()?,
+        mint_b: Pubkey::from_str(cols[7]).ok()?,
+        price_sqrt: optional(cols[8]).ok()?,
+        liquidity: optional(cols[9]).ok()?,
+        fee_bps: cols[10].parse().ok()?,
+    })
+}
+
+/// Replays `<dir>/market_data.csv` in file order with the live strategy settings
+pub async fn run(cfg: &BotConfig, dir: &Path) -> anyhow::Result<BacktestReport> {
+    let path = dir.join("market_data.csv");
+    let file = std::fs::File::open(&path).map_err(|e| anyhow::anyhow!("Cannot open {}: {}", path.display(), e))?;
+
+    let executor = Arc::new(SimulatedExecutor::default());
+    let clock = Arc::new(ManualClock::new(0));
+    let engine = StrategyEngine::new(
+        Some(Arc::clone(&executor) as Arc<dyn strategy::ports::ExecutionPort>),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .with_hop_costs(strategy::hop_costs::HopCostTable::parse(&cfg.hop_cost_table).map_err(|e| anyhow::anyhow!(e))?)
+    .with_cycle_search(strategy::arb::CycleSearchMode::parse(&cfg.cycle_search_mode).map_err(|e| anyhow::anyhow!(e))?)
+    .with_feature_flags(strategy::flags::FeatureFlags::parse(&cfg.feature_flags).map_err(|e| anyhow::anyhow!(e))?)
+    .with_clock(Arc::clone(&clock) as Arc<dyn Clock>)
+    .with_params(cfg.strategy_params());
+    let engine = Arc::new(if cfg.sizing_max_lamports > 0 {
+        engine.with_sizing(strategy::sizing::SizingPolicy::new(cfg.sizing_min_lamports, cfg.sizing_max_lamports))
+    } else {
+        engine
+    });
+
+    let mut report = BacktestReport {
+        config_hash: crate::config_snapshot::ConfigSnapshot::capture(cfg).map_err(|e| anyhow::anyhow!(e))?.hash,
+        ..Default::default()
+    };
+    let mut pending: VecDeque<PendingFill> = VecDeque::new();
+
+    for line in BufReader::new(file).lines().skip(1) {
+        let Some(update) = parse_market_row(&line?) else {
+            report.rows_skipped += 1;
+            continue;
+        };
+        clock.set_unix_ms(update.timestamp.saturating_mul(1000));
+        let now_ms = clock.unix_ms();
+
+        // Bundles land before any update recorded after their landing time
+        while pending.front().is_some_and(|fill| fill.lands_at_ms <= now_ms) {
+            report.settle(&engine, pending.pop_front().unwrap());
+        }
+
+        report.updates_replayed += 1;
+        match engine.process_event(
+            Arc::new(update),
+            mev_core::Trigger::AccountUpdate,
+            cfg.default_trade_size_lamports,
+            cfg.jito_tip_lamports,
+            cfg.jito_tip_percentage,
+            cfg.max_jito_tip_lamports,
+            cfg.max_slippage_bps,
+            cfg.volatility_sensitivity,
+            cfg.max_slippage_ceiling,
+            cfg.ai_confidence_threshold,
+            cfg.max_hops,
+        ).await {
+            Ok(outcome) => *report.outcomes.entry(outcome.label()).or_default() += 1,
+            Err(e) => tracing::debug!("📼 Replay error at t={}: {}", now_ms / 1000, e),
+        }
+
+        for (opportunity, tip_lamports) in executor.drain() {
+            report.dispatched += 1;
+            report.quoted_profit_lamports += opportunity.profit_in_sol();
+            pending.push_back(PendingFill { opportunity, tip_lamports, lands_at_ms: now_ms + cfg.backtest_latency_ms });
+        }
+    }
+
+    // Whatever is still in flight lands against the final state
+    while let Some(fill) = pending.pop_front() {
+        report.settle(&engine, fill);
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_market_row_matches_recorder_columns() {
+        let (pool, program, mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        let cpmm = format!("1700000000,{},{},1000,2000,2,{},{},,,25", pool, program, mint_a, mint_b);
+        let update = parse_market_row(&cpmm).unwrap();
+        assert_eq!((update.timestamp, update.reserve_a, update.reserve_b, update.fee_bps), (1_700_000_000, 1000, 2000, 25));
+        assert_eq!((update.mint_a, update.mint_b, update.price_sqrt), (mint_a, mint_b, None));
+
+        let clmm = format!("1700000001,{},{},0,0,0,{},{},18446744073709551616,5000,4", pool, program, mint_a, mint_b);
+        let update = parse_market_row(&clmm).unwrap();
+        assert_eq!((update.price_sqrt, update.liquidity), (Some(1u128 << 64), Some(5000)));
+
+        // Old recorder format has no mints
+        assert!(parse_market_row(&format!("1700000000,{},{},1000,2000,2", pool, program)).is_none());
+    }
+}
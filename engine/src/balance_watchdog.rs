@@ -0,0 +1,143 @@
+/// Wallet Balance Watchdog
+///
+/// Tracks the session's SOL high-water mark from the wallet itself and alerts
+/// when the live balance sinks more than the configured share below it,
+/// optionally pausing trading. It reads nothing from the bot's own PnL
+/// accounting, so it still fires when that accounting is wrong. It latches
+/// after firing and re-arms once the balance is back above the line; a manual
+/// withdrawal therefore trips it once.
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::time::Duration;
+use solana_sdk::pubkey::Pubkey;
+use crate::alerts::{AlertManager, AlertSeverity, Field};
+use crate::metrics::BotMetrics;
+use crate::wallet_manager::WalletManager;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceDrop {
+    pub high_water_lamports: u64,
+    pub balance_lamports: u64,
+    pub drop_bps: u64,
+}
+
+#[derive(Default)]
+struct State {
+    high_water: u64,
+    tripped: bool,
+}
+
+pub struct BalanceWatchdog {
+    /// Drop below the high-water mark that fires, in bps; 0 disables the watchdog
+    max_drop_bps: u64,
+    auto_pause: bool,
+    state: Mutex<State>,
+}
+
+impl BalanceWatchdog {
+    pub fn new(max_drop_bps: u64, auto_pause: bool) -> Self {
+        Self { max_drop_bps, auto_pause, state: Mutex::new(State::default()) }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_drop_bps > 0
+    }
+
+    pub fn high_water(&self) -> u64 {
+        self.state.lock().unwrap().high_water
+    }
+
+    /// Feeds one balance reading; reports a drop the first time it crosses the line
+    pub fn observe(&self, balance: u64) -> Option<BalanceDrop> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.high_water = state.high_water.max(balance);
+        if state.high_water == 0 {
+            return None;
+        }
+        let drop_bps = ((state.high_water - balance) as u128 * 10_000 / state.high_water as u128) as u64;
+        if drop_bps < self.max_drop_bps {
+            state.tripped = false;
+            return None;
+        }
+        if state.tripped {
+            return None;
+        }
+        state.tripped = true;
+        Some(BalanceDrop { high_water_lamports: state.high_water, balance_lamports: balance, drop_bps })
+    }
+
+    /// Polls the wallet every `poll`, alerting (and pausing, if configured) on a drop
+    pub async fn run(
+        self: Arc<Self>,
+        wallet: Arc<WalletManager>,
+        owner: Pubkey,
+        metrics: Arc<BotMetrics>,
+        alerts: Arc<AlertManager>,
+        poll: Duration,
+    ) {
+        let mut interval = tokio::time::interval(poll);
+        loop {
+            interval.tick().await;
+            let balance = match wallet.get_sol_balance(&owner).await {
+                Ok(balance) => balance,
+                Err(e) => {
+                    tracing::warn!("⚠️ Balance watchdog: wallet read failed: {}", e);
+                    continue;
+                }
+            };
+            let drop = self.observe(balance);
+            crate::telemetry::WALLET_HIGH_WATER_LAMPORTS.set(self.high_water() as i64);
+            let Some(drop) = drop else {
+                continue;
+            };
+
+            crate::telemetry::WALLET_WATCHDOG_TRIPS.inc();
+            if self.auto_pause {
+                metrics.is_paused.store(true, Ordering::Relaxed);
+            }
+            let action = if self.auto_pause { "Trading PAUSED; send /resume once reconciled." } else { "Trading continues (auto-pause off)." };
+            tracing::error!("🐕 BALANCE WATCHDOG: wallet {:.6} SOL is {:.2}% below its {:.6} SOL high-water mark. {}",
+                drop.balance_lamports as f64 / 1e9, drop.drop_bps as f64 / 100.0, drop.high_water_lamports as f64 / 1e9, action);
+            alerts.send_alert(
+                AlertSeverity::Critical,
+                "WALLET BALANCE DROP",
+                &format!("Wallet balance is {:.2}% below this session's high-water mark, whatever the PnL books say. {}",
+                    drop.drop_bps as f64 / 100.0, action),
+                vec![
+                    Field { name: "Balance".to_string(), value: format!("{:.6} SOL", drop.balance_lamports as f64 / 1e9), inline: true },
+                    Field { name: "High-Water".to_string(), value: format!("{:.6} SOL", drop.high_water_lamports as f64 / 1e9), inline: true },
+                    Field { name: "Booked PnL".to_string(), value: format!("{:.6} SOL", metrics.net_pnl_lamports() as f64 / 1e9), inline: true },
+                ],
+            ).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fires_once_per_breach_of_high_water() {
+        let watchdog = BalanceWatchdog::new(1_000, true);
+        assert_eq!(watchdog.observe(2_000_000_000), None);
+        assert_eq!(watchdog.observe(2_500_000_000), None);
+        assert_eq!(watchdog.high_water(), 2_500_000_000);
+
+        // 8% off the peak is inside the 10% band
+        assert_eq!(watchdog.observe(2_300_000_000), None);
+        let drop = watchdog.observe(2_200_000_000).expect("12% below high-water");
+        assert_eq!(drop.drop_bps, 1_200);
+        assert_eq!(drop.high_water_lamports, 2_500_000_000);
+        // Latched until the balance is back above the line
+        assert_eq!(watchdog.observe(2_100_000_000), None);
+        assert_eq!(watchdog.observe(2_400_000_000), None);
+        assert!(watchdog.observe(2_000_000_000).is_some());
+
+        assert_eq!(BalanceWatchdog::new(0, true).observe(0), None);
+    }
+}
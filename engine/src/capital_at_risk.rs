@@ -0,0 +1,196 @@
+/// Capital-at-Risk Snapshot (GET /risk on the metrics port)
+///
+/// One JSON document for external risk dashboards and compliance pollers:
+/// notional committed to unconfirmed bundles, wallet inventory valued in SOL
+/// at graph spot prices, today's PnL, and every limit that currently stops
+/// trading (pause, circuit breaker, route cooldowns).
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::Ordering;
+use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use strategy::StrategyEngine;
+use strategy::analytics::in_flight::InFlightBook;
+use strategy::analytics::route_guard::RouteBlacklist;
+use crate::metrics::BotMetrics;
+use crate::risk::RiskManager;
+use crate::token_metadata::TokenMetadata;
+use crate::wallet_manager::WalletManager;
+
+#[derive(Debug, Serialize)]
+pub struct RiskSnapshot {
+    pub generated_at: String,
+    pub trading_paused: bool,
+    pub circuit_breaker_tripped: bool,
+    pub in_flight: InFlightSummary,
+    /// None when the wallet could not be read; see `errors`
+    pub inventory: Option<InventoryValuation>,
+    pub pnl: PnlSummary,
+    pub cooldowns: Vec<RouteCooldown>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InFlightSummary {
+    pub bundles: usize,
+    pub notional_lamports: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InventoryValuation {
+    pub sol_lamports: u64,
+    pub tokens: Vec<TokenHolding>,
+    /// SOL plus every token with a known spot price
+    pub total_value_lamports: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenHolding {
+    pub mint: String,
+    /// Known symbol, or the mint's first characters
+    pub symbol: String,
+    pub raw_amount: u64,
+    /// `raw_amount` in whole tokens at the mint's decimals; None until they are known
+    pub ui_amount: Option<String>,
+    /// None when no pool in the graph prices this mint against SOL
+    pub value_lamports: Option<u64>,
+}
+
+impl TokenHolding {
+    pub fn new(mint: &Pubkey, raw_amount: u64, value_lamports: Option<u64>, metadata: &TokenMetadata) -> Self {
+        Self {
+            mint: mint.to_string(),
+            symbol: metadata.symbol(mint),
+            raw_amount,
+            ui_amount: metadata.ui_amount(mint, raw_amount),
+            value_lamports,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PnlSummary {
+    /// UTC day, rolled over on the first snapshot after midnight
+    pub day: String,
+    pub daily_pnl_lamports: i64,
+    pub session_profit_lamports: u64,
+    pub session_loss_lamports: u64,
+    pub daily_trades: u32,
+    pub daily_volume_lamports: u64,
+    pub daily_loss_lamports: u64,
+    pub max_daily_loss_lamports: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RouteCooldown {
+    pub route: String,
+    pub remaining_secs: u64,
+}
+
+pub struct CapitalAtRisk {
+    metrics: Arc<BotMetrics>,
+    risk_mgr: Arc<RiskManager>,
+    wallet_mgr: Arc<WalletManager>,
+    engine: Arc<StrategyEngine>,
+    route_blacklist: Arc<RouteBlacklist>,
+    in_flight: Arc<InFlightBook>,
+    owner: Pubkey,
+    token_mints: Vec<Pubkey>,
+    metadata: Arc<TokenMetadata>,
+    // Session PnL at the start of the current UTC day
+    day_start: Mutex<(NaiveDate, i64)>,
+}
+
+impl CapitalAtRisk {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        metrics: Arc<BotMetrics>,
+        risk_mgr: Arc<RiskManager>,
+        wallet_mgr: Arc<WalletManager>,
+        engine: Arc<StrategyEngine>,
+        route_blacklist: Arc<RouteBlacklist>,
+        in_flight: Arc<InFlightBook>,
+        owner: Pubkey,
+        token_mints: Vec<Pubkey>,
+        metadata: Arc<TokenMetadata>,
+    ) -> Self {
+        let day_start = Mutex::new((Utc::now().date_naive(), session_pnl(&metrics)));
+        Self { metrics, risk_mgr, wallet_mgr, engine, route_blacklist, in_flight, owner, token_mints, metadata, day_start }
+    }
+
+    pub async fn snapshot(&self) -> RiskSnapshot {
+        let mut errors = Vec::new();
+        let (bundles, notional_lamports) = self.in_flight.summary();
+
+        let inventory = match self.inventory().await {
+            Ok(inventory) => Some(inventory),
+            Err(e) => {
+                errors.push(format!("inventory: {}", e));
+                None
+            }
+        };
+
+        let cooldowns = self.route_blacklist.active_cooldowns().into_iter()
+            .map(|(route, remaining)| RouteCooldown { route, remaining_secs: remaining.as_secs() })
+            .collect();
+
+        RiskSnapshot {
+            generated_at: Utc::now().to_rfc3339(),
+            trading_paused: self.metrics.is_paused.load(Ordering::Relaxed),
+            circuit_breaker_tripped: self.risk_mgr.circuit_breaker_triggered.load(Ordering::Relaxed),
+            in_flight: InFlightSummary { bundles, notional_lamports },
+            inventory,
+            pnl: self.pnl(),
+            cooldowns,
+            errors,
+        }
+    }
+
+    async fn inventory(&self) -> anyhow::Result<InventoryValuation> {
+        let sol_lamports = self.wallet_mgr.get_sol_balance(&self.owner).await?;
+        let balances = self.wallet_mgr.get_multiple_token_balances(&self.owner, &self.token_mints).await?;
+        if let Err(e) = self.metadata.resolve(&self.token_mints).await {
+            tracing::debug!("🏷️ Token decimals lookup failed: {}", e);
+        }
+
+        let mut tokens: Vec<TokenHolding> = balances.into_iter()
+            .filter(|(_, amount)| *amount > 0)
+            .map(|(mint, raw_amount)| {
+                let value_lamports = self.engine.spot_price(&mint, &mev_core::constants::SOL_MINT)
+                    .map(|rate| (raw_amount as f64 * rate) as u64);
+                TokenHolding::new(&mint, raw_amount, value_lamports, &self.metadata)
+            })
+            .collect();
+        tokens.sort_by(|a, b| a.mint.cmp(&b.mint));
+
+        let total_value_lamports = sol_lamports + tokens.iter().filter_map(|t| t.value_lamports).sum::<u64>();
+        Ok(InventoryValuation { sol_lamports, tokens, total_value_lamports })
+    }
+
+    fn pnl(&self) -> PnlSummary {
+        let session = session_pnl(&self.metrics);
+        let today = Utc::now().date_naive();
+        let baseline = {
+            let mut day_start = self.day_start.lock().unwrap_or_else(|e| e.into_inner());
+            if day_start.0 != today {
+                *day_start = (today, session);
+            }
+            day_start.1
+        };
+
+        PnlSummary {
+            day: today.to_string(),
+            daily_pnl_lamports: session - baseline,
+            session_profit_lamports: self.metrics.total_profit_lamports.load(Ordering::Relaxed),
+            session_loss_lamports: self.metrics.total_loss_lamports.load(Ordering::Relaxed),
+            daily_trades: self.risk_mgr.daily_trades.load(Ordering::Relaxed),
+            daily_volume_lamports: self.risk_mgr.daily_volume.load(Ordering::Relaxed),
+            daily_loss_lamports: self.risk_mgr.daily_loss.load(Ordering::Relaxed),
+            max_daily_loss_lamports: self.risk_mgr.max_daily_loss_lamports,
+        }
+    }
+}
+
+fn session_pnl(metrics: &BotMetrics) -> i64 {
+    metrics.total_profit_lamports.load(Ordering::Relaxed) as i64 - metrics.total_loss_lamports.load(Ordering::Relaxed) as i64
+}
@@ -42,7 +42,7 @@ pub const MONITORED_POOLS: &[PoolConfig] = &[
     PoolConfig { address: pubkey!("HJPjoWUrhoZzkNfRpHuieeFk9WcZWjwy6PBjZ81ngndJ"), token_a: USDC_MINT, token_b: USDT_MINT, dex: DexType::Orca },    // Stable Bridge
 ];
 
-#[derive(Debug, serde::Deserialize, Clone, PartialEq, Default)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Default)]
 pub enum ExecutionMode {
     #[default]
     Simulation,      // 🛡️
@@ -51,18 +51,32 @@ pub enum ExecutionMode {
 
 }
 
-#[derive(Debug, serde::Deserialize, Clone)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 pub struct BotConfig {
     #[serde(default)]
     pub mode: ExecutionMode,
     #[serde(alias = "RPC_URL")]
     pub rpc_url: String,
+    #[serde(alias = "RPC_FALLBACK_URLS", alias = "RPC_URL_BACKUP", default)]
+    pub rpc_fallback_urls: String,
+    #[serde(alias = "RPC_RATE_LIMIT_COOLDOWN_SECS", default = "default_rpc_rate_limit_cooldown_secs")]
+    pub rpc_rate_limit_cooldown_secs: u64,
     #[serde(alias = "WS_URL")]
     pub ws_url: String,
+    #[serde(alias = "WS_FALLBACK_URLS", default)]
+    pub ws_fallback_urls: String,
+    #[serde(alias = "WS_DIVERGENCE_SLOTS", default = "default_ws_divergence_slots")]
+    pub ws_divergence_slots: u64,
     #[serde(alias = "JITO_URL")]
     pub jito_url: String,
     #[serde(alias = "KEYPAIR_PATH")]
     pub keypair_path: String,
+    #[serde(alias = "STANDBY_KEYPAIR_PATH")]
+    pub standby_keypair_path: Option<String>,
+    #[serde(alias = "WALLET_FAILOVER_THRESHOLD", default = "default_wallet_failover_threshold")]
+    pub wallet_failover_threshold: u32,
+    #[serde(alias = "WALLET_FAILOVER_MIN_SOL_LAMPORTS", default = "default_wallet_failover_min_sol_lamports")]
+    pub wallet_failover_min_sol_lamports: u64,
     #[serde(alias = "DEFAULT_TRADE_SIZE_LAMPORTS")]
     pub default_trade_size_lamports: u64,
     #[serde(alias = "JITO_TIP_LAMPORTS")]
@@ -81,28 +95,83 @@ pub struct BotConfig {
     pub monitored_pool_addresses: String,
     #[serde(default)]
     pub max_daily_loss_lamports: u64,
+    #[serde(alias = "DRAWDOWN_MAX_LAMPORTS", default = "default_drawdown_max_lamports")]
+    pub drawdown_max_lamports: u64,
+    #[serde(alias = "DRAWDOWN_WINDOW_SECS", default = "default_drawdown_window_secs")]
+    pub drawdown_window_secs: u64,
+    #[serde(alias = "DRAWDOWN_COOLDOWN_SECS", default = "default_drawdown_cooldown_secs")]
+    pub drawdown_cooldown_secs: u64,
+    #[serde(alias = "WALLET_WATCHDOG_MAX_DROP_BPS", default = "default_wallet_watchdog_max_drop_bps")]
+    pub wallet_watchdog_max_drop_bps: u64,
+    #[serde(alias = "WALLET_WATCHDOG_AUTO_PAUSE", default = "default_wallet_watchdog_auto_pause")]
+    pub wallet_watchdog_auto_pause: bool,
+    #[serde(alias = "WALLET_WATCHDOG_POLL_SECS", default = "default_wallet_watchdog_poll_secs")]
+    pub wallet_watchdog_poll_secs: u64,
     #[serde(alias = "DISCORD_WEBHOOK")]
     pub discord_webhook: Option<String>,
     #[serde(alias = "TELEGRAM_BOT_TOKEN")]
     pub telegram_bot_token: Option<String>,
     #[serde(alias = "TELEGRAM_CHAT_ID")]
     pub telegram_chat_id: Option<String>,
-    #[serde(alias = "MIN_PROFIT_THRESHOLD", default = "default_min_profit")]
-    pub min_profit_threshold_lamports: u64,
+    /// Strategy thresholds below override the mode preset (see `strategy_params`)
+    #[serde(alias = "MIN_PROFIT_THRESHOLD")]
+    pub min_profit_threshold_lamports: Option<u64>,
     #[serde(alias = "AI_CONFIDENCE_THRESHOLD", default = "default_ai_confidence")]
     pub ai_confidence_threshold: f32,
+    #[serde(alias = "AI_DRIFT_WINDOW", default = "default_ai_drift_window")]
+    pub ai_drift_window: usize,
+    #[serde(alias = "AI_DRIFT_BASELINE_MEAN")]
+    pub ai_drift_baseline_mean: Option<f32>,
+    #[serde(alias = "AI_DRIFT_MAX_SHIFT", default = "default_ai_drift_max_shift")]
+    pub ai_drift_max_shift: f32,
+    #[serde(alias = "AI_DRIFT_FALLBACK", default)]
+    pub ai_drift_fallback: bool,
+    /// How often ai_model.onnx (and the challenger) are checked for changes; 0 = never
+    #[serde(alias = "AI_MODEL_RELOAD_SECS", default = "default_ai_model_reload_secs")]
+    pub ai_model_reload_secs: u64,
+    /// Second model scored in shadow next to ai_model.onnx; never acted on
+    #[serde(alias = "AI_CHALLENGER_MODEL_PATH")]
+    pub ai_challenger_model_path: Option<String>,
+    /// Execute on expected value (model probability x land rate) instead of AI_CONFIDENCE_THRESHOLD
+    #[serde(alias = "EV_GATE_ENABLED", default = "default_ev_gate_enabled")]
+    pub ev_gate_enabled: bool,
+    #[serde(alias = "EV_MARGIN_LAMPORTS", default)]
+    pub ev_margin_lamports: i64,
     #[serde(alias = "KELLY_FRACTION", default = "default_kelly_fraction")]
     pub kelly_fraction: f32,
+    #[serde(alias = "KELLY_MIN_TRADES", default = "default_kelly_min_trades")]
+    pub kelly_min_trades: usize,
+    #[serde(alias = "KELLY_MIN_LAMPORTS", default = "default_kelly_min_lamports")]
+    pub kelly_min_lamports: u64,
     #[serde(alias = "DATABASE_URL")]
     pub database_url: Option<String>,
     #[serde(alias = "MIN_LIQUIDITY_LAMPORTS", default = "default_min_liquidity")]
     pub min_liquidity_lamports: u64,
-    #[serde(alias = "SANITY_PROFIT_FACTOR", default = "default_sanity_profit_factor")]
-    pub sanity_profit_factor: u64,
+    #[serde(alias = "MAX_TRANSFER_FEE_BPS", default)]
+    pub max_transfer_fee_bps: u16,
+    #[serde(alias = "SANITY_PROFIT_FACTOR")]
+    pub sanity_profit_factor: Option<f64>,
+    #[serde(alias = "MAX_PRICE_IMPACT_BPS")]
+    pub max_price_impact_bps: Option<u16>,
+    #[serde(alias = "STRATEGY_ID", default = "default_strategy_id")]
+    pub strategy_id: String,
     #[serde(alias = "NTFY_TOPIC")]
     pub ntfy_topic: Option<String>,
     #[serde(alias = "HELIUS_SENDER_URL")]
     pub helius_sender_url: Option<String>,
+    #[serde(alias = "TPU_FALLBACK_ENABLED", default)]
+    pub tpu_fallback_enabled: bool,
+    #[serde(alias = "TPU_FANOUT_SLOTS", default = "default_tpu_fanout_slots")]
+    pub tpu_fanout_slots: u64,
+    #[cfg(not(feature = "paper-only"))]
+    #[serde(alias = "TX_SENDER_ORDER", default = "default_tx_sender_order")]
+    pub tx_sender_order: String,
+    #[serde(alias = "TRITON_SEND_URL")]
+    pub triton_send_url: Option<String>,
+    #[serde(alias = "BLOXROUTE_URL", default = "default_bloxroute_url")]
+    pub bloxroute_url: String,
+    #[serde(alias = "BLOXROUTE_AUTH_HEADER")]
+    pub bloxroute_auth_header: Option<String>,
     #[serde(alias = "FEE_STRATEGY", default)]
     pub fee_strategy: FeeStrategy,
     #[serde(alias = "MAX_HOPS", default = "default_max_hops")]
@@ -111,13 +180,249 @@ pub struct BotConfig {
     pub max_liquidity_usd: u64,
     #[serde(alias = "EXCLUDED_MINTS", default = "default_excluded_mints")]
     pub excluded_mints: Vec<String>,
+    #[serde(alias = "REJECTION_LOG_MAX_PER_SEC", default = "default_rejection_log_max_per_sec")]
+    pub rejection_log_max_per_sec: u32,
+    #[serde(alias = "TRADE_JOURNAL_MAX_REJECTIONS_PER_SEC", default = "default_trade_journal_max_rejections_per_sec")]
+    pub trade_journal_max_rejections_per_sec: u32,
+    #[serde(alias = "PNL_DEVIATION_ALERT_PCT", default = "default_pnl_deviation_alert_pct")]
+    pub pnl_deviation_alert_pct: f64,
+    /// Sell intermediate tokens a landed trade left in the wallet back into the route's start mint
+    #[serde(alias = "RECOVERY_SELL_ENABLED", default = "default_recovery_sell_enabled")]
+    pub recovery_sell_enabled: bool,
+    #[serde(alias = "RECOVERY_MIN_OUTPUT_LAMPORTS", default = "default_recovery_min_output_lamports")]
+    pub recovery_min_output_lamports: u64,
+    /// Poll every token account the wallet holds; 0 turns the inventory manager off
+    #[serde(alias = "INVENTORY_POLL_SECS", default = "default_inventory_poll_secs")]
+    pub inventory_poll_secs: u64,
+    /// Sell holdings outside INVENTORY_KEEP_MINTS back to SOL
+    #[serde(alias = "INVENTORY_SWEEP_ENABLED", default = "default_inventory_sweep_enabled")]
+    pub inventory_sweep_enabled: bool,
+    #[serde(alias = "INVENTORY_SWEEP_MIN_LAMPORTS", default = "default_inventory_sweep_min_lamports")]
+    pub inventory_sweep_min_lamports: u64,
+    /// Comma-separated mints held on purpose, never swept
+    #[serde(alias = "INVENTORY_KEEP_MINTS", default)]
+    pub inventory_keep_mints: String,
+    #[serde(alias = "TRAINING_EXPORT_ENABLED", default)]
+    pub training_export_enabled: bool,
+    /// Empty = `training/` under the data directory
+    #[serde(alias = "TRAINING_EXPORT_DIR", default)]
+    pub training_export_dir: String,
+    #[serde(alias = "TRAINING_EXPORT_FORMAT", default = "default_training_export_format")]
+    pub training_export_format: String,
+    /// Publish opportunities to OPPORTUNITY_WEBHOOK_URL / OPPORTUNITY_NATS_URL instead of executing them
+    #[serde(alias = "EXTERNAL_EXECUTION", default)]
+    pub external_execution: bool,
+    #[serde(alias = "OPPORTUNITY_WEBHOOK_URL")]
+    pub opportunity_webhook_url: Option<String>,
+    #[serde(alias = "OPPORTUNITY_NATS_URL")]
+    pub opportunity_nats_url: Option<String>,
+    #[serde(alias = "OPPORTUNITY_NATS_SUBJECT", default = "default_opportunity_nats_subject")]
+    pub opportunity_nats_subject: String,
+    #[serde(alias = "ROUTE_MAX_REVERTS", default = "default_route_max_reverts")]
+    pub route_max_reverts: u32,
+    #[serde(alias = "ROUTE_REVERT_WINDOW_SECS", default = "default_route_revert_window_secs")]
+    pub route_revert_window_secs: u64,
+    #[serde(alias = "ROUTE_BLACKLIST_COOLDOWN_SECS", default = "default_route_blacklist_cooldown_secs")]
+    pub route_blacklist_cooldown_secs: u64,
+    #[serde(alias = "MIN_WORKERS", default = "default_min_workers")]
+    pub min_workers: usize,
+    #[serde(alias = "MAX_WORKERS", default = "default_max_workers")]
+    pub max_workers: usize,
+    #[serde(alias = "PRIORITY_QUOTE_MINTS", default = "default_priority_quote_mints")]
+    pub priority_quote_mints: String,
+    #[serde(alias = "SPLIT_MAX_CHUNKS", default = "default_split_max_chunks")]
+    pub split_max_chunks: u8,
+    #[serde(alias = "SPLIT_ROUTE_CHUNKS", default)]
+    pub split_route_chunks: String,
+    #[serde(alias = "SPLIT_REQUOTE_DELAY_MS", default = "default_split_requote_delay_ms")]
+    pub split_requote_delay_ms: u64,
+    #[serde(alias = "VERIFY_PROGRAM_IDS", default = "default_verify_program_ids")]
+    pub verify_program_ids: bool,
+    #[serde(alias = "HOP_COST_TABLE", default)]
+    pub hop_cost_table: String,
+    #[serde(alias = "CYCLE_SEARCH_MODE", default)]
+    pub cycle_search_mode: String,
+    #[serde(alias = "PRIVACY_MODE", default)]
+    pub privacy_mode: bool,
+    #[serde(alias = "PRIVACY_MAX_JITTER_MS", default = "default_privacy_max_jitter_ms")]
+    pub privacy_max_jitter_ms: u64,
+    #[serde(alias = "PRIVACY_DECOY_ACCOUNTS", default = "default_privacy_decoy_accounts")]
+    pub privacy_decoy_accounts: u8,
+    #[serde(alias = "TRADE_FLOW_ENABLED", default)]
+    pub trade_flow_enabled: bool,
+    #[serde(alias = "BACKRUN_ENABLED", default)]
+    pub backrun_enabled: bool,
+    #[serde(alias = "BACKRUN_MIN_IMPACT_BPS", default = "default_backrun_min_impact_bps")]
+    pub backrun_min_impact_bps: u64,
+    #[serde(alias = "RESERVE_AUDIT_INTERVAL_SECS", default = "default_reserve_audit_interval_secs")]
+    pub reserve_audit_interval_secs: u64,
+    #[serde(alias = "RESERVE_AUDIT_TOP_POOLS", default = "default_reserve_audit_top_pools")]
+    pub reserve_audit_top_pools: usize,
+    #[serde(alias = "RESERVE_AUDIT_THRESHOLD_BPS", default = "default_reserve_audit_threshold_bps")]
+    pub reserve_audit_threshold_bps: u64,
+    #[serde(alias = "EDGE_TTL_SECS", default = "default_edge_ttl_secs")]
+    pub edge_ttl_secs: u64,
+    #[serde(alias = "GRAPH_PRUNE_AFTER_SECS", default = "default_graph_prune_after_secs")]
+    pub graph_prune_after_secs: u64,
+    #[serde(alias = "GRAPH_PRUNE_INTERVAL_SECS", default = "default_graph_prune_interval_secs")]
+    pub graph_prune_interval_secs: u64,
+    #[serde(alias = "EXECUTION_SLOTS", default = "default_execution_slots")]
+    pub execution_slots: usize,
+    #[serde(alias = "PRIORITY_EXECUTION_SLOTS", default = "default_priority_execution_slots")]
+    pub priority_execution_slots: usize,
+    #[serde(alias = "PRIORITY_PROFIT_PERCENTILE", default = "default_priority_profit_percentile")]
+    pub priority_profit_percentile: f64,
+    #[serde(alias = "PRIORITY_PROFIT_WINDOW", default = "default_priority_profit_window")]
+    pub priority_profit_window: usize,
+    #[serde(alias = "SIZING_MIN_LAMPORTS", default = "default_sizing_min_lamports")]
+    pub sizing_min_lamports: u64,
+    #[serde(alias = "SIZING_MAX_LAMPORTS", default)]
+    pub sizing_max_lamports: u64,
+    #[serde(alias = "TIP_FLOOR_PERCENTILE", default)]
+    pub tip_floor_percentile: String,
+    #[serde(alias = "TIP_FLOOR_ELITE_PERCENTILE", default = "default_tip_floor_elite_percentile")]
+    pub tip_floor_elite_percentile: String,
+    #[serde(alias = "TIP_PROFIT_SHARE", default = "default_tip_profit_share")]
+    pub tip_profit_share: f64,
+    #[serde(alias = "INGEST_BACKEND", default = "default_ingest_backend")]
+    pub ingest_backend: String,
+    #[serde(alias = "GEYSER_ENDPOINT")]
+    pub geyser_endpoint: Option<String>,
+    #[serde(alias = "GEYSER_X_TOKEN")]
+    pub geyser_x_token: Option<String>,
+    #[serde(alias = "LOOKUP_TABLE_ADDRESS")]
+    pub lookup_table_address: Option<String>,
+    #[serde(alias = "LOOKUP_TABLE_AUTO_CREATE", default)]
+    pub lookup_table_auto_create: bool,
+    #[serde(alias = "LOOKUP_TABLE_MIN_USES", default = "default_lookup_table_min_uses")]
+    pub lookup_table_min_uses: u32,
+    #[serde(alias = "AUTO_CREATE_ATAS", default = "default_auto_create_atas")]
+    pub auto_create_atas: bool,
+    #[serde(alias = "WRAP_SOL", default = "default_wrap_sol")]
+    pub wrap_sol: bool,
+    #[serde(alias = "UNWRAP_WSOL", default = "default_unwrap_wsol")]
+    pub unwrap_wsol: bool,
+    #[serde(alias = "DURABLE_NONCE_ACCOUNT", default)]
+    pub durable_nonce_account: String,
+    #[serde(alias = "RACE_MODE", default)]
+    pub race_mode: bool,
+    #[serde(alias = "FLASH_LOAN_RESERVES", default)]
+    pub flash_loan_reserves: String,
+    #[serde(alias = "FEATURE_FLAGS", default)]
+    pub feature_flags: String,
+    #[serde(alias = "ORACLE_FEEDS", default)]
+    pub oracle_feeds: String,
+    #[serde(alias = "ORACLE_MAX_DEVIATION_BPS", default = "default_oracle_max_deviation_bps")]
+    pub oracle_max_deviation_bps: u32,
+    #[serde(alias = "ORACLE_MAX_AGE_SECS", default = "default_oracle_max_age_secs")]
+    pub oracle_max_age_secs: u64,
+    #[serde(alias = "ORACLE_POLL_MS", default = "default_oracle_poll_ms")]
+    pub oracle_poll_ms: u64,
+    #[serde(alias = "ORACLE_MODE", default = "default_oracle_mode")]
+    pub oracle_mode: String,
+    #[serde(alias = "JUPITER_QUOTE_URL", default)]
+    pub jupiter_quote_url: String,
+    #[serde(alias = "JUPITER_TOLERANCE_BPS", default = "default_jupiter_tolerance_bps")]
+    pub jupiter_tolerance_bps: u32,
+    /// Pre-send simulation for the pipeline's `simulation` gate: off, rpc or jito
+    #[serde(alias = "BUNDLE_SIMULATION", default)]
+    pub bundle_simulation: String,
+    /// Jito-enabled RPC answering `simulateBundle`; empty = RPC_URL
+    #[serde(alias = "JITO_SIMULATE_URL", default)]
+    pub jito_simulate_url: String,
+    /// Re-decode one reference pool per venue and check it against chain and quote data; 0 = off
+    #[serde(alias = "LAYOUT_CHECK_INTERVAL_SECS", default = "default_layout_check_interval_secs")]
+    pub layout_check_interval_secs: u64,
+    #[serde(alias = "LAYOUT_CHECK_TOLERANCE_BPS", default = "default_layout_check_tolerance_bps")]
+    pub layout_check_tolerance_bps: u32,
+    #[serde(alias = "LAYOUT_CHECK_AUTO_PAUSE", default = "default_layout_check_auto_pause")]
+    pub layout_check_auto_pause: bool,
+    /// Gates between a found route and its bundle, in run order, `gate(key=value,...)` (see policy.rs); empty = all, default order
+    #[serde(alias = "EXECUTION_POLICY", default)]
+    pub execution_policy: String,
+    #[serde(alias = "CYCLE_REPRICE_TOP_K", default = "default_cycle_reprice_top_k")]
+    pub cycle_reprice_top_k: usize,
+    /// Each update searches only cycles through its own pool; a periodic full sweep covers the rest
+    /// UTC windows per strategy, `strategy:days:hours[:scale];...` (see schedule.rs); empty = always on
+    #[serde(alias = "STRATEGY_SCHEDULE", default)]
+    pub strategy_schedule: String,
+    #[serde(alias = "INCREMENTAL_SEARCH_ENABLED", default = "default_incremental_search_enabled")]
+    pub incremental_search_enabled: bool,
+    #[serde(alias = "CYCLE_SWEEP_INTERVAL_MS", default = "default_cycle_sweep_interval_ms")]
+    pub cycle_sweep_interval_ms: u64,
+    #[serde(alias = "PARALLEL_SEARCH_MIN_HOPS", default = "default_parallel_search_min_hops")]
+    pub parallel_search_min_hops: u8,
+    #[serde(alias = "CYCLE_SEARCH_BUDGET_US", default = "default_cycle_search_budget_us")]
+    pub cycle_search_budget_us: u64,
+    #[serde(alias = "SPLIT_ROUTE_MAX_POOLS", default = "default_split_route_max_pools")]
+    pub split_route_max_pools: u8,
+    #[serde(alias = "POOL_RATE_LIMIT_PER_MIN", default = "default_pool_rate_limit_per_min")]
+    pub pool_rate_limit_per_min: u32,
+    #[serde(alias = "POOL_RATE_LIMIT_OVERRIDES", default)]
+    pub pool_rate_limit_overrides: String,
+    #[serde(alias = "RISK_MAX_MINT_NOTIONAL_LAMPORTS", default = "default_risk_max_mint_notional_lamports")]
+    pub risk_max_mint_notional_lamports: u64,
+    #[serde(alias = "RISK_MAX_POOL_TRADES_PER_MIN", default = "default_risk_max_pool_trades_per_min")]
+    pub risk_max_pool_trades_per_min: u32,
+    #[serde(alias = "RISK_MAX_WALLET_SHARE_BPS", default = "default_risk_max_wallet_share_bps")]
+    pub risk_max_wallet_share_bps: u16,
+    #[serde(alias = "COMPUTE_UNIT_SIMULATE", default = "default_compute_unit_simulate")]
+    pub compute_unit_simulate: bool,
+    #[serde(alias = "COMPUTE_UNIT_MARGIN_BPS", default = "default_compute_unit_margin_bps")]
+    pub compute_unit_margin_bps: u32,
+    #[serde(alias = "PRIORITY_FEE_BUDGET_LAMPORTS", default = "default_priority_fee_budget_lamports")]
+    pub priority_fee_budget_lamports: u64,
+    #[serde(alias = "JITO_ENDPOINT_MIN_SUCCESS_RATE", default = "default_jito_endpoint_min_success_rate")]
+    pub jito_endpoint_min_success_rate: f64,
+    #[serde(alias = "JITO_ENDPOINT_WINDOW_SECS", default = "default_jito_endpoint_window_secs")]
+    pub jito_endpoint_window_secs: u64,
+    #[serde(alias = "JITO_ENDPOINT_MIN_SAMPLES", default = "default_jito_endpoint_min_samples")]
+    pub jito_endpoint_min_samples: usize,
+    #[serde(alias = "JITO_ENDPOINT_PROBE_SECS", default = "default_jito_endpoint_probe_secs")]
+    pub jito_endpoint_probe_secs: u64,
+    #[serde(alias = "JITO_WARMUP_SECS", default = "default_jito_warmup_secs")]
+    pub jito_warmup_secs: u64,
+    #[serde(alias = "JITO_MAX_IDLE_SECS", default = "default_jito_max_idle_secs")]
+    pub jito_max_idle_secs: u64,
+    #[serde(alias = "HTTP_HOST_POLICIES", default)]
+    pub http_host_policies: String,
+    #[serde(alias = "BACKTEST_LATENCY_MS", default = "default_backtest_latency_ms")]
+    pub backtest_latency_ms: u64,
+    /// Node with full transaction history for `engine replay`; RPC_URL when unset
+    #[serde(alias = "ARCHIVE_RPC_URL")]
+    pub archive_rpc_url: Option<String>,
+    #[serde(alias = "PAPER_LANDING_DELAY_MS", default = "default_paper_landing_delay_ms")]
+    pub paper_landing_delay_ms: u64,
+    #[serde(alias = "PAPER_FAILURE_RATE", default = "default_paper_failure_rate")]
+    pub paper_failure_rate: f64,
+    #[serde(alias = "PAPER_SLIPPAGE_BPS", default = "default_paper_slippage_bps")]
+    pub paper_slippage_bps: u16,
+    /// Seeds tip-account, jitter, paper-fill and backoff draws; unset draws from entropy
+    #[serde(alias = "RNG_SEED")]
+    pub rng_seed: Option<u64>,
+    #[serde(alias = "CONTROL_API_TOKEN")]
+    pub control_api_token: Option<String>,
+    #[serde(alias = "CONTROL_API_BIND", default = "default_control_api_bind")]
+    pub control_api_bind: String,
 }
 
-fn default_min_profit() -> u64 { 30_000 } // Lowered to 30k for better hit rate
+fn default_rpc_rate_limit_cooldown_secs() -> u64 { 30 } // Endpoint skipped after a 429
+fn default_ws_divergence_slots() -> u64 { 25 } // ~10s behind the leading feed
 fn default_ai_confidence() -> f32 { 0.7 } // Lowered to 0.7 (was 0.8)
-fn default_kelly_fraction() -> f32 { 0.1 }
-fn default_min_liquidity() -> u64 { 5_000_000_000 } // 5 SOL (was 10 SOL)
-fn default_sanity_profit_factor() -> u64 { 100 } // 100x
+fn default_ai_drift_window() -> usize { 500 } // Model outputs per drift evaluation
+fn default_ai_model_reload_secs() -> u64 { 30 }
+fn default_ai_drift_max_shift() -> f32 { 0.25 } // Window mean vs training mean
+fn default_ev_gate_enabled() -> bool { true }
+fn default_drawdown_max_lamports() -> u64 { 50_000_000 } // 0.05 SOL off the window's peak; 0 = disabled
+fn default_drawdown_window_secs() -> u64 { 1_800 }
+fn default_drawdown_cooldown_secs() -> u64 { 3_600 } // 0 = halt until /resume
+fn default_wallet_watchdog_max_drop_bps() -> u64 { 1_500 } // 15% below the session high; 0 = disabled
+fn default_wallet_watchdog_auto_pause() -> bool { true }
+fn default_wallet_watchdog_poll_secs() -> u64 { 30 }
+fn default_kelly_fraction() -> f32 { 0.1 } // 0 = static DEFAULT_TRADE_SIZE_LAMPORTS
+fn default_kelly_min_trades() -> usize { 20 }
+fn default_kelly_min_lamports() -> u64 { 1_000_000 } // 0.001 SOL
+fn default_min_liquidity() -> u64 { 5_000_000_000 } // 5 SOL-equivalent of pool depth (was 10 SOL)
 
 fn default_tip_percentage() -> f64 { 0.15 }
 fn default_max_tip() -> u64 { 100_000_000 } // 0.1 SOL
@@ -125,6 +430,85 @@ fn default_volatility_sensitivity() -> f64 { 1.0 }
 fn default_max_slippage_ceiling() -> u16 { 200 } // 2%
 fn default_max_hops() -> u8 { 5 }
 fn default_max_liquidity_usd() -> u64 { 200_000 } // Cap filtering at $200k to avoid HFT
+fn default_rejection_log_max_per_sec() -> u32 { 5 } // Per rejection reason, 0 disables
+fn default_trade_journal_max_rejections_per_sec() -> u32 { 20 } // All reasons together, 0 = attempts only
+fn default_pnl_deviation_alert_pct() -> f64 { 20.0 } // Realized vs quoted profit
+fn default_recovery_sell_enabled() -> bool { true }
+fn default_recovery_min_output_lamports() -> u64 { 100_000 } // Below this a sell costs more than it recovers
+fn default_inventory_poll_secs() -> u64 { 60 }
+fn default_inventory_sweep_enabled() -> bool { true }
+fn default_inventory_sweep_min_lamports() -> u64 { 1_000_000 } // 0.001 SOL; smaller holdings are shown but left alone
+fn default_opportunity_nats_subject() -> String { "mev.opportunities".to_string() }
+fn default_training_export_format() -> String { "csv".to_string() }
+fn default_route_max_reverts() -> u32 { 3 }
+fn default_route_revert_window_secs() -> u64 { 600 } // Reverts must cluster within 10 min
+fn default_route_blacklist_cooldown_secs() -> u64 { 1800 } // 30 min bench
+fn default_min_workers() -> usize { 2 }
+fn default_max_workers() -> usize { 8 } // Previous fixed pool size
+fn default_priority_quote_mints() -> String { mev_core::constants::SOL_MINT.to_string() } // Empty = single lane
+fn default_split_max_chunks() -> u8 { 1 } // 1 = no splitting
+fn default_split_requote_delay_ms() -> u64 { 800 } // ~2 slots for our fill to show up
+fn default_verify_program_ids() -> bool { true } // Disable only for local validators without venue programs
+fn default_privacy_max_jitter_ms() -> u64 { 150 } // Capped at one slot (400ms)
+fn default_privacy_decoy_accounts() -> u8 { 2 }
+fn default_execution_slots() -> usize { 4 }
+fn default_priority_execution_slots() -> usize { 1 } // Reserved for top-percentile opportunities
+fn default_priority_profit_percentile() -> f64 { 0.9 }
+fn default_priority_profit_window() -> usize { 500 } // Most recent detected opportunities
+fn default_reserve_audit_interval_secs() -> u64 { 300 } // 0 disables
+fn default_reserve_audit_top_pools() -> usize { 20 }
+fn default_reserve_audit_threshold_bps() -> u64 { 100 } // Vault balances drift a little with unsettled PnL
+fn default_backrun_min_impact_bps() -> u64 { 50 } // Swap size vs the pool's input-side balance
+fn default_edge_ttl_secs() -> u64 { 900 } // 0 disables
+fn default_graph_prune_after_secs() -> u64 { 3600 } // 0 disables
+fn default_graph_prune_interval_secs() -> u64 { 60 }
+fn default_sizing_min_lamports() -> u64 { 1_000_000 } // 0.001 SOL
+fn default_tip_floor_elite_percentile() -> String { "p95".to_string() }
+fn default_tip_profit_share() -> f64 { 0.10 }
+fn default_ingest_backend() -> String { "websocket".to_string() }
+fn default_lookup_table_min_uses() -> u32 { 3 } // Bundles an account must appear in before it joins the table
+fn default_backtest_latency_ms() -> u64 { 400 } // Detection to landing, about one slot
+fn default_paper_landing_delay_ms() -> u64 { 400 } // Same one-slot landing as the backtest
+fn default_paper_failure_rate() -> f64 { 0.2 } // Share of paper bundles that never land
+fn default_paper_slippage_bps() -> u16 { 10 } // Haircut on the live re-quote at landing
+fn default_tpu_fanout_slots() -> u64 { 8 } // Current leader plus the next one or two
+fn default_strategy_id() -> String { mev_core::telemetry::DEFAULT_STRATEGY_ID.to_string() } // Metric namespace
+#[cfg(not(feature = "paper-only"))]
+fn default_tx_sender_order() -> String { executor::tx_sender::DEFAULT_SENDER_ORDER.to_string() }
+fn default_bloxroute_url() -> String { "https://ny.solana.dex.blxrbdn.com".to_string() } // Trader API region
+fn default_oracle_max_deviation_bps() -> u32 { 500 } // Fees and impact make hops worse, never this much better
+fn default_oracle_max_age_secs() -> u64 { 30 }
+fn default_oracle_poll_ms() -> u64 { 2_000 }
+fn default_oracle_mode() -> String { "pull".to_string() } // Stream needs a WS endpoint that allows accountSubscribe on oracle accounts
+fn default_jupiter_tolerance_bps() -> u32 { 100 } // Our hop may beat Jupiter's direct quote by 1% (quote lag) before it's refused
+fn default_layout_check_interval_secs() -> u64 { 600 }
+fn default_layout_check_tolerance_bps() -> u32 { 300 } // Covers the fee tier in the reference quote; a shifted offset is far outside it
+fn default_layout_check_auto_pause() -> bool { true }
+fn default_wallet_failover_threshold() -> u32 { 3 } // One bad send is noise; three in a row with key errors is the key
+fn default_wallet_failover_min_sol_lamports() -> u64 { 100_000_000 } // 0.1 SOL: enough for fees and tips while the operator tops it up
+fn default_cycle_reprice_top_k() -> usize { 32 } // Cycles re-quoted every slot; 0 = update-driven search only
+fn default_incremental_search_enabled() -> bool { true }
+fn default_cycle_sweep_interval_ms() -> u64 { 2_000 } // Full search from SOL; 0 = never
+fn default_parallel_search_min_hops() -> u8 { 4 } // Fan out 4-5 hop searches; 0 = never
+fn default_cycle_search_budget_us() -> u64 { 2_000 } // 0 = unbounded
+fn default_split_route_max_pools() -> u8 { 1 } // One pool per leg; 2-4 spreads a leg over a pair's pools
+fn default_pool_rate_limit_per_min() -> u32 { 6 } // Routes per pool per minute; 0 = unlimited
+fn default_risk_max_mint_notional_lamports() -> u64 { 60_000_000 } // 0.06 SOL in flight through any one mint; 0 = unlimited
+fn default_risk_max_pool_trades_per_min() -> u32 { 20 } // Hard cap, overrides included; 0 = unlimited
+fn default_risk_max_wallet_share_bps() -> u16 { 2_500 } // 25% of the balance per trade; 0 = unlimited
+fn default_compute_unit_simulate() -> bool { true }
+fn default_auto_create_atas() -> bool { true }
+fn default_wrap_sol() -> bool { true }
+fn default_unwrap_wsol() -> bool { true } // Proceeds come back as native SOL, which is what inventory and PnL read
+fn default_compute_unit_margin_bps() -> u32 { 1_000 } // +10% over the simulated units
+fn default_priority_fee_budget_lamports() -> u64 { 100_000 } // 0.0001 SOL per transaction
+fn default_jito_endpoint_min_success_rate() -> f64 { 0.5 } // 0 never benches
+fn default_jito_endpoint_window_secs() -> u64 { 300 }
+fn default_jito_endpoint_min_samples() -> usize { 10 }
+fn default_jito_endpoint_probe_secs() -> u64 { 30 }
+fn default_jito_warmup_secs() -> u64 { 15 } // getTipAccounts on a channel quiet this long
+fn default_jito_max_idle_secs() -> u64 { 120 } // Reconnect a channel with no bundle this long; 0 = never
+fn default_control_api_bind() -> String { "127.0.0.1:8083".to_string() } // Loopback unless deliberately exposed
 fn default_excluded_mints() -> Vec<String> {
     vec![
         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(), // USDC
@@ -155,6 +539,7 @@ impl BotConfig {
         // Safety Limits
         if config.mode == ExecutionMode::LiveMicro {
             config.default_trade_size_lamports = config.default_trade_size_lamports.min(20_000_000); // 0.02 SOL Hard Cap
+            config.sizing_max_lamports = config.sizing_max_lamports.min(20_000_000);
         }
 
         config.max_daily_loss_lamports = 50_000_000; // 0.05 SOL
@@ -163,8 +548,175 @@ impl BotConfig {
         Ok(config)
     }
 
+    /// Mode preset with any configured thresholds laid over it
+    pub fn strategy_params(&self) -> strategy::params::StrategyParams {
+        use strategy::params::{StrategyParams, StrategyPreset};
+        let preset = match self.mode {
+            ExecutionMode::Simulation => StrategyPreset::Simulation,
+            ExecutionMode::LiveMicro => StrategyPreset::LiveMicro,
+            ExecutionMode::LiveProduction => StrategyPreset::LiveProduction,
+        };
+        let base = StrategyParams::preset(preset);
+        StrategyParams {
+            max_trade_size_lamports: base.max_trade_size_lamports,
+            min_profit_threshold_lamports: self.min_profit_threshold_lamports.unwrap_or(base.min_profit_threshold_lamports),
+            sanity_profit_factor: self.sanity_profit_factor.unwrap_or(base.sanity_profit_factor),
+            max_price_impact_bps: self.max_price_impact_bps.unwrap_or(base.max_price_impact_bps),
+        }
+    }
+
+    /// Output drift thresholds for the AI model
+    pub fn drift_policy(&self) -> strategy::analytics::model_drift::DriftPolicy {
+        strategy::analytics::model_drift::DriftPolicy {
+            window: self.ai_drift_window,
+            baseline_mean: self.ai_drift_baseline_mean,
+            max_mean_shift: self.ai_drift_max_shift,
+            fallback: self.ai_drift_fallback,
+            ..Default::default()
+        }
+    }
+
+    /// Jito tip floor policy. Without an explicit TIP_FLOOR_PERCENTILE, paper and micro
+    /// runs tip at the median while production keeps the EMA 75th percentile.
+    pub fn tip_floor_policy(&self) -> Result<executor::tip_policy::TipFloorPolicy, String> {
+        use executor::tip_policy::TipPercentile;
+        let percentile = match (self.tip_floor_percentile.trim(), &self.mode) {
+            ("", ExecutionMode::LiveProduction) => TipPercentile::Ema75,
+            ("", _) => TipPercentile::P50,
+            (p, _) => TipPercentile::parse(p)?,
+        };
+        Ok(executor::tip_policy::TipFloorPolicy {
+            percentile,
+            elite_percentile: TipPercentile::parse(&self.tip_floor_elite_percentile)?,
+            profit_share: self.tip_profit_share,
+            profit_share_cap_lamports: self.max_jito_tip_lamports,
+        })
+    }
+
+    /// RPC endpoints for the failover pool: `RPC_URL` first, then `RPC_FALLBACK_URLS` (comma-separated)
+    pub fn rpc_urls(&self) -> Vec<String> {
+        std::iter::once(self.rpc_url.as_str())
+            .chain(self.rpc_fallback_urls.split(','))
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// WebSocket endpoints watched in parallel: `WS_URL` first, then `WS_FALLBACK_URLS` (comma-separated)
+    pub fn ws_urls(&self) -> Vec<String> {
+        let mut urls: Vec<String> = Vec::new();
+        for url in std::iter::once(self.ws_url.as_str()).chain(self.ws_fallback_urls.split(',')).map(str::trim) {
+            if !url.is_empty() && !urls.iter().any(|u| u == url) {
+                urls.push(url.to_string());
+            }
+        }
+        urls
+    }
+
+    /// `KEYPAIR_PATH`, or the platform default when it is empty
+    pub fn resolved_keypair_path(&self) -> String {
+        if self.keypair_path.is_empty() {
+            crate::platform::default_keypair_path().to_string_lossy().into_owned()
+        } else {
+            self.keypair_path.clone()
+        }
+    }
+
+    /// OPPORTUNITY_WEBHOOK_URL when set and non-blank
+    pub fn opportunity_webhook(&self) -> Option<&str> {
+        self.opportunity_webhook_url.as_deref().map(str::trim).filter(|u| !u.is_empty())
+    }
+
+    /// OPPORTUNITY_NATS_URL when set and non-blank
+    pub fn opportunity_nats(&self) -> Option<&str> {
+        self.opportunity_nats_url.as_deref().map(str::trim).filter(|u| !u.is_empty())
+    }
+
+    pub fn rpc_pool(&self) -> executor::rpc_pool::RpcPool {
+        executor::rpc_pool::RpcPool::new(&self.rpc_urls(), executor::rpc_pool::RpcPoolPolicy {
+            rate_limit_cooldown: std::time::Duration::from_secs(self.rpc_rate_limit_cooldown_secs),
+            ..Default::default()
+        })
+    }
+
+    /// Nonce account fallback transactions are signed against; None signs with recent blockhashes
+    pub fn durable_nonce(&self) -> Result<Option<Pubkey>, String> {
+        let account = self.durable_nonce_account.trim();
+        if account.is_empty() {
+            return Ok(None);
+        }
+        account.parse::<Pubkey>()
+            .map(Some)
+            .map_err(|e| format!("DURABLE_NONCE_ACCOUNT '{}' is not a valid pubkey: {}", account, e))
+    }
+
+    /// `TX_SENDER_ORDER` minus the senders that have no endpoint or credentials configured
+    #[cfg(not(feature = "paper-only"))]
+    pub fn tx_senders(&self) -> Result<Vec<executor::tx_sender::SenderKind>, String> {
+        use executor::tx_sender::SenderKind;
+        let set = |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+        Ok(SenderKind::parse_order(&self.tx_sender_order)?
+            .into_iter()
+            .filter(|kind| match kind {
+                SenderKind::Rpc => true,
+                SenderKind::Helius => set(&self.helius_sender_url),
+                SenderKind::Triton => set(&self.triton_send_url),
+                SenderKind::Bloxroute => set(&self.bloxroute_auth_header),
+                SenderKind::Tpu => self.tpu_fallback_enabled,
+            })
+            .collect())
+    }
+
+    /// Fallback senders in `TX_SENDER_ORDER`, built from their endpoint settings
+    #[cfg(not(feature = "paper-only"))]
+    pub fn tx_sender_chain(
+        &self,
+        rpc: &std::sync::Arc<executor::rpc_pool::RpcPool>,
+        http: &std::sync::Arc<executor::http::HttpClient>,
+    ) -> Result<executor::tx_sender::SenderChain, String> {
+        use std::sync::Arc;
+        use executor::tx_sender::{BloxrouteSender, EndpointSender, RpcSender, SenderKind, TxSender};
+        let senders = self.tx_senders()?.into_iter().map(|kind| -> Arc<dyn TxSender> {
+            match kind {
+                SenderKind::Rpc => Arc::new(RpcSender::new(Arc::clone(rpc))),
+                SenderKind::Helius => Arc::new(EndpointSender::helius(self.helius_sender_url.clone().unwrap_or_default())),
+                SenderKind::Triton => Arc::new(EndpointSender::triton(self.triton_send_url.clone().unwrap_or_default())),
+                SenderKind::Bloxroute => Arc::new(BloxrouteSender::new(
+                    Arc::clone(http),
+                    &self.bloxroute_url,
+                    self.bloxroute_auth_header.clone().unwrap_or_default(),
+                )),
+                SenderKind::Tpu => Arc::new(executor::tpu::TpuExecutor::new(Arc::clone(rpc), self.tpu_fanout_slots)),
+            }
+        }).collect();
+        Ok(executor::tx_sender::SenderChain::new(senders))
+    }
+
+    /// Solend reserves to flash-borrow from, comma-separated (empty = flash loans off)
+    pub fn flash_loan_reserves(&self) -> Result<Vec<Pubkey>, String> {
+        self.flash_loan_reserves.split(',')
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .map(|r| r.parse::<Pubkey>().map_err(|_| format!("invalid reserve address: {}", r)))
+            .collect()
+    }
+
+    /// Quote mints whose pools jump the work queue, comma-separated
+    pub fn priority_quote_mints(&self) -> Result<std::collections::HashSet<Pubkey>, String> {
+        self.priority_quote_mints.split(',')
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .map(|m| m.parse::<Pubkey>().map_err(|_| format!("invalid mint: {}", m)))
+            .collect()
+    }
+
     /// Validates configuration values at startup (Fail Fast)
     pub fn validate(&self) -> Result<(), String> {
+        if cfg!(feature = "paper-only") && self.mode != ExecutionMode::Simulation {
+            return Err(format!("EXECUTION_MODE {:?} needs live execution, which this paper-only build leaves out. Use Simulation.", self.mode));
+        }
+
         // Validate URLs
         if !self.rpc_url.starts_with("http") {
             return Err(format!("Invalid RPC_URL: must start with http/https. Got: {}", self.rpc_url));
@@ -177,9 +729,16 @@ impl BotConfig {
             tracing::warn!("⚠️  USING PUBLIC RPC FOR LIVE TRADING. Rate limits may cause missed opportunities.");
         }
 
+        if let Some(url) = self.rpc_fallback_urls.split(',').map(str::trim).find(|u| !u.is_empty() && !u.starts_with("http")) {
+            return Err(format!("Invalid RPC_FALLBACK_URLS entry: must start with http/https. Got: {}", url));
+        }
+
         if !self.ws_url.starts_with("ws") {
             return Err(format!("Invalid WS_URL: must start with ws/wss. Got: {}", self.ws_url));
         }
+        if let Some(url) = self.ws_fallback_urls.split(',').map(str::trim).find(|u| !u.is_empty() && !u.starts_with("ws")) {
+            return Err(format!("Invalid WS_FALLBACK_URLS entry: must start with ws/wss. Got: {}", url));
+        }
         if !self.jito_url.starts_with("http") {
             return Err(format!("Invalid JITO_URL: must start with http/https. Got: {}", self.jito_url));
         }
@@ -201,6 +760,218 @@ impl BotConfig {
             tracing::warn!("⚠️  JITO_TIP_LAMPORTS (base) is very low ({}). May result in rejected bundles.", self.jito_tip_lamports);
         }
 
+        if self.drawdown_max_lamports > 0 && self.drawdown_window_secs == 0 {
+            return Err("DRAWDOWN_WINDOW_SECS cannot be 0 while DRAWDOWN_MAX_LAMPORTS is set".into());
+        }
+
+        if self.ai_drift_window < 50 {
+            return Err(format!("AI_DRIFT_WINDOW must be at least 50. Got: {}", self.ai_drift_window));
+        }
+        if let Some(mean) = self.ai_drift_baseline_mean.filter(|m| !(0.0..=1.0).contains(m)) {
+            return Err(format!("AI_DRIFT_BASELINE_MEAN must be in [0.0, 1.0]. Got: {}", mean));
+        }
+        if !(0.0..=1.0).contains(&self.ai_drift_max_shift) {
+            return Err(format!("AI_DRIFT_MAX_SHIFT must be in [0.0, 1.0]. Got: {}", self.ai_drift_max_shift));
+        }
+
+        if self.wallet_watchdog_max_drop_bps > 10_000 {
+            return Err(format!("WALLET_WATCHDOG_MAX_DROP_BPS must be at most 10000. Got: {}", self.wallet_watchdog_max_drop_bps));
+        }
+        if self.wallet_watchdog_max_drop_bps > 0 && self.wallet_watchdog_poll_secs == 0 {
+            return Err("WALLET_WATCHDOG_POLL_SECS cannot be 0 while the watchdog is on".into());
+        }
+
+        if !(self.pnl_deviation_alert_pct.is_finite() && self.pnl_deviation_alert_pct > 0.0) {
+            return Err(format!("PNL_DEVIATION_ALERT_PCT must be > 0. Got: {}", self.pnl_deviation_alert_pct));
+        }
+        if let Err(e) = self.training_export_format.parse::<crate::training_export::ExportFormat>() {
+            return Err(format!("TRAINING_EXPORT_FORMAT: {}", e));
+        }
+        if self.external_execution && self.opportunity_webhook().is_none() && self.opportunity_nats().is_none() {
+            return Err("EXTERNAL_EXECUTION needs OPPORTUNITY_WEBHOOK_URL or OPPORTUNITY_NATS_URL".into());
+        }
+        if self.opportunity_nats().is_some() && self.opportunity_nats_subject.trim().is_empty() {
+            return Err("OPPORTUNITY_NATS_SUBJECT cannot be empty".into());
+        }
+
+        if !(0.0..=1.0).contains(&self.kelly_fraction) {
+            return Err(format!("KELLY_FRACTION must be between 0.0 and 1.0. Got: {}", self.kelly_fraction));
+        }
+
+        if let Err(e) = strategy::split::SplitPolicy::new(self.split_max_chunks, std::time::Duration::ZERO)
+            .with_route_overrides(&self.split_route_chunks) {
+            return Err(format!("SPLIT_ROUTE_CHUNKS: {}", e));
+        }
+
+        if let Err(e) = strategy::hop_costs::HopCostTable::parse(&self.hop_cost_table) {
+            return Err(format!("HOP_COST_TABLE: {}", e));
+        }
+
+        if let Err(e) = strategy::arb::CycleSearchMode::parse(&self.cycle_search_mode) {
+            return Err(format!("CYCLE_SEARCH_MODE: {}", e));
+        }
+
+        if self.graph_prune_after_secs > 0 && self.graph_prune_after_secs < self.edge_ttl_secs {
+            return Err(format!("GRAPH_PRUNE_AFTER_SECS ({}) must not be below EDGE_TTL_SECS ({})",
+                self.graph_prune_after_secs, self.edge_ttl_secs));
+        }
+        if self.graph_prune_after_secs > 0 && self.graph_prune_interval_secs == 0 {
+            return Err("GRAPH_PRUNE_INTERVAL_SECS must be > 0 when pruning is enabled".into());
+        }
+
+        if !(0.0..1.0).contains(&self.priority_profit_percentile) {
+            return Err(format!("PRIORITY_PROFIT_PERCENTILE must be in [0.0, 1.0). Got: {}", self.priority_profit_percentile));
+        }
+
+        if !(0.0..=1.0).contains(&self.jito_endpoint_min_success_rate) {
+            return Err(format!("JITO_ENDPOINT_MIN_SUCCESS_RATE must be in [0.0, 1.0]. Got: {}", self.jito_endpoint_min_success_rate));
+        }
+        if self.jito_endpoint_probe_secs == 0 {
+            return Err("JITO_ENDPOINT_PROBE_SECS must be > 0".into());
+        }
+        if self.jito_warmup_secs == 0 {
+            return Err("JITO_WARMUP_SECS must be > 0".into());
+        }
+
+        if !(0.0..=1.0).contains(&self.paper_failure_rate) {
+            return Err(format!("PAPER_FAILURE_RATE must be in [0.0, 1.0]. Got: {}", self.paper_failure_rate));
+        }
+        if self.tpu_fallback_enabled && !(1..=64).contains(&self.tpu_fanout_slots) {
+            return Err(format!("TPU_FANOUT_SLOTS must be in [1, 64]. Got: {}", self.tpu_fanout_slots));
+        }
+        if self.strategy_id.trim().is_empty() {
+            return Err("STRATEGY_ID must not be empty (it namespaces every metric)".into());
+        }
+        #[cfg(not(feature = "paper-only"))]
+        match self.tx_senders() {
+            Ok(senders) if senders.is_empty() => {
+                return Err(format!("TX_SENDER_ORDER '{}' has no configured sender (add rpc, or set the listed senders' URLs/credentials)", self.tx_sender_order));
+            }
+            Ok(_) => {}
+            Err(e) => return Err(format!("TX_SENDER_ORDER: {}", e)),
+        }
+        if let Err(e) = self.strategy_params().validate() {
+            return Err(format!("MIN_PROFIT_THRESHOLD / SANITY_PROFIT_FACTOR / MAX_PRICE_IMPACT_BPS: {}", e));
+        }
+        if self.backrun_enabled && !self.trade_flow_enabled {
+            return Err("BACKRUN_ENABLED requires TRADE_FLOW_ENABLED (swaps are read from the trade-flow log stream)".into());
+        }
+        if self.rng_seed.is_some() && self.mode != ExecutionMode::Simulation {
+            tracing::warn!("⚠️  RNG_SEED is set for live trading. Tip-account picks and privacy jitter become predictable.");
+        }
+
+        if self.sizing_max_lamports > 1_000_000_000 {
+            return Err(format!("SIZING_MAX_LAMPORTS cannot exceed the 1 SOL trade limit. Got: {}", self.sizing_max_lamports));
+        }
+        if self.sizing_max_lamports > 0 && self.sizing_min_lamports > self.sizing_max_lamports {
+            return Err("SIZING_MIN_LAMPORTS must not exceed SIZING_MAX_LAMPORTS".into());
+        }
+
+        if let Err(e) = self.tip_floor_policy() {
+            return Err(format!("TIP_FLOOR_PERCENTILE / TIP_FLOOR_ELITE_PERCENTILE: {}", e));
+        }
+        if !(0.0..=1.0).contains(&self.tip_profit_share) {
+            return Err(format!("TIP_PROFIT_SHARE must be in [0.0, 1.0]. Got: {}", self.tip_profit_share));
+        }
+
+        match self.ingest_backend.as_str() {
+            "websocket" => {}
+            "geyser" if self.geyser_endpoint.as_deref().is_some_and(|e| !e.is_empty()) => {}
+            "geyser" => return Err("INGEST_BACKEND=geyser requires GEYSER_ENDPOINT".into()),
+            other => return Err(format!("INGEST_BACKEND must be websocket or geyser. Got: {}", other)),
+        }
+
+        if let Some(addr) = self.lookup_table_address.as_deref().filter(|a| !a.is_empty()) {
+            if addr.parse::<Pubkey>().is_err() {
+                return Err(format!("LOOKUP_TABLE_ADDRESS is not a valid pubkey: {}", addr));
+            }
+        }
+
+        if let Err(e) = self.flash_loan_reserves() {
+            return Err(format!("FLASH_LOAN_RESERVES: {}", e));
+        }
+
+        if let Err(e) = self.priority_quote_mints() {
+            return Err(format!("PRIORITY_QUOTE_MINTS: {}", e));
+        }
+
+        if let Err(e) = strategy::flags::FeatureFlags::parse(&self.feature_flags) {
+            return Err(format!("FEATURE_FLAGS: {}", e));
+        }
+
+        if let Err(e) = strategy::safety::oracle_guard::OracleFeed::parse_list(&self.oracle_feeds) {
+            return Err(format!("ORACLE_FEEDS: {}", e));
+        }
+        if self.oracle_poll_ms == 0 {
+            return Err("ORACLE_POLL_MS cannot be 0".into());
+        }
+        if let Err(e) = crate::oracle_feed::OracleMode::parse(&self.oracle_mode) {
+            return Err(format!("ORACLE_MODE: {}", e));
+        }
+        if let Err(e) = executor::bundle_sim::SimulationBackend::parse(&self.bundle_simulation) {
+            return Err(format!("BUNDLE_SIMULATION: {}", e));
+        }
+        if !self.jito_simulate_url.is_empty() && !self.jito_simulate_url.starts_with("http") {
+            return Err(format!("JITO_SIMULATE_URL must be an http(s) URL. Got: {}", self.jito_simulate_url));
+        }
+        if !self.jupiter_quote_url.is_empty() && !self.jupiter_quote_url.starts_with("http") {
+            return Err(format!("JUPITER_QUOTE_URL must be an http(s) URL. Got: {}", self.jupiter_quote_url));
+        }
+        if self.layout_check_tolerance_bps == 0 || self.layout_check_tolerance_bps > 10_000 {
+            return Err(format!("LAYOUT_CHECK_TOLERANCE_BPS must be 1 to 10000. Got: {}", self.layout_check_tolerance_bps));
+        }
+        self.durable_nonce()?;
+        if self.race_mode {
+            // The two copies only dedupe through the nonce, and the copy goes to Helius Sender
+            if self.durable_nonce()?.is_none() {
+                return Err("RACE_MODE needs DURABLE_NONCE_ACCOUNT: it keeps the bundle and its RPC copy from both landing".to_string());
+            }
+            if self.helius_sender_url.as_deref().is_none_or(|u| u.trim().is_empty()) {
+                return Err("RACE_MODE needs HELIUS_SENDER_URL for the RPC copy".to_string());
+            }
+        }
+        if let Some(standby) = self.standby_keypair_path.as_deref().filter(|p| !p.trim().is_empty()) {
+            if standby.trim() == self.resolved_keypair_path() {
+                return Err("STANDBY_KEYPAIR_PATH must point at a different keypair than KEYPAIR_PATH".to_string());
+            }
+        }
+        if self.unwrap_wsol && !self.wrap_sol {
+            return Err("UNWRAP_WSOL requires WRAP_SOL (the closed WSOL account would leave later routes unfunded)".to_string());
+        }
+        if let Err(e) = strategy::policy::ExecutionPolicy::parse(&self.execution_policy) {
+            return Err(format!("EXECUTION_POLICY: {}", e));
+        }
+        if let Err(e) = crate::inventory_manager::parse_mints(&self.inventory_keep_mints) {
+            return Err(format!("INVENTORY_KEEP_MINTS: {}", e));
+        }
+
+        crate::schedule::StrategySchedule::parse(&self.strategy_schedule)
+            .map_err(|e| format!("Invalid STRATEGY_SCHEDULE: {}", e))?;
+        if self.incremental_search_enabled && self.cycle_sweep_interval_ms == 0 {
+            return Err("CYCLE_SWEEP_INTERVAL_MS must be > 0 with INCREMENTAL_SEARCH_ENABLED (routes off the updated pool are only found by the sweep)".to_string());
+        }
+        if self.parallel_search_min_hops == 1 {
+            return Err("PARALLEL_SEARCH_MIN_HOPS must be 0 (off) or at least 2 (a cycle takes two hops)".to_string());
+        }
+        if !(1..=4).contains(&self.split_route_max_pools) {
+            return Err(format!("SPLIT_ROUTE_MAX_POOLS must be 1 (off) to 4 (each pool adds a swap instruction). Got: {}", self.split_route_max_pools));
+        }
+        if self.cycle_reprice_top_k > 1_024 {
+            return Err(format!("CYCLE_REPRICE_TOP_K must be at most 1024 (one slot's budget). Got: {}", self.cycle_reprice_top_k));
+        }
+
+        if let Err(e) = strategy::pool_rate_limit::PoolRateLimiter::parse_overrides(&self.pool_rate_limit_overrides) {
+            return Err(format!("POOL_RATE_LIMIT_OVERRIDES: {}", e));
+        }
+
+        if self.risk_max_wallet_share_bps > 10_000 {
+            return Err(format!("RISK_MAX_WALLET_SHARE_BPS must be at most 10000. Got: {}", self.risk_max_wallet_share_bps));
+        }
+
+        if let Err(e) = executor::http::parse_overrides(&self.http_host_policies) {
+            return Err(format!("HTTP_HOST_POLICIES: {}", e));
+        }
+
         // Validate default trade size
         if self.default_trade_size_lamports == 0 {
             return Err("DEFAULT_TRADE_SIZE_LAMPORTS cannot be 0".into());
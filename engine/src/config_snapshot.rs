@@ -0,0 +1,108 @@
+/// Effective Configuration Snapshot
+///
+/// The resolved `BotConfig` (environment plus every default) serialized once at
+/// startup, with credentials blanked, and identified by a short hash. The hash
+/// rides along in session reports, the trade journal and the `bot_config_info`
+/// metric; the full snapshot is kept under `config_snapshots/<hash>.json`, so a
+/// PnL difference between two sessions can be traced to the parameters behind it.
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+use crate::config::BotConfig;
+
+/// Fields that carry credentials or API keys: recorded as set/unset only
+const REDACTED_FIELDS: &[&str] = &[
+    "rpc_url",
+    "rpc_fallback_urls",
+    "archive_rpc_url",
+    "ws_url",
+    "ws_fallback_urls",
+    "discord_webhook",
+    "telegram_bot_token",
+    "telegram_chat_id",
+    "database_url",
+    "ntfy_topic",
+    "helius_sender_url",
+    "triton_send_url",
+    "bloxroute_auth_header",
+    "geyser_endpoint",
+    "geyser_x_token",
+    "control_api_token",
+];
+
+#[derive(Debug, Clone)]
+pub struct ConfigSnapshot {
+    /// 16 hex chars of FNV-1a over the redacted snapshot
+    pub hash: String,
+    pub values: Value,
+}
+
+impl ConfigSnapshot {
+    pub fn capture(config: &BotConfig) -> Result<Self, String> {
+        let values = serde_json::to_value(config).map_err(|e| format!("Config snapshot failed: {}", e))?;
+        Ok(Self::from_values(values))
+    }
+
+    pub fn from_values(mut values: Value) -> Self {
+        if let Value::Object(fields) = &mut values {
+            for name in REDACTED_FIELDS {
+                if let Some(value) = fields.get_mut(*name) {
+                    let set = !value.is_null() && value.as_str() != Some("");
+                    *value = Value::String(if set { "<redacted>" } else { "<unset>" }.into());
+                }
+            }
+        }
+        let hash = format!("{:016x}", fnv1a(values.to_string().as_bytes()));
+        Self { hash, values }
+    }
+
+    /// Writes `<dir>/config_snapshots/<hash>.json`, once per distinct config
+    pub fn persist(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        let dir = dir.join("config_snapshots");
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{}.json", self.hash));
+        if !path.exists() {
+            let pretty = serde_json::to_string_pretty(&self.values).map_err(std::io::Error::other)?;
+            std::fs::write(&path, pretty)?;
+        }
+        Ok(path)
+    }
+}
+
+/// Stable across builds and platforms, unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| (hash ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_snapshot_hash_tracks_parameters_not_secrets() {
+        let base = ConfigSnapshot::from_values(json!({
+            "rpc_url": "https://mainnet.helius-rpc.com/?api-key=secret",
+            "telegram_bot_token": null,
+            "min_profit_threshold_lamports": 100_000,
+        }));
+        assert_eq!(base.hash.len(), 16);
+        assert_eq!(base.values["rpc_url"], "<redacted>");
+        assert_eq!(base.values["telegram_bot_token"], "<unset>");
+        assert!(!base.values.to_string().contains("secret"));
+
+        // Another key for the same endpoint is the same configuration
+        let rotated_key = ConfigSnapshot::from_values(json!({
+            "rpc_url": "https://mainnet.helius-rpc.com/?api-key=other",
+            "telegram_bot_token": null,
+            "min_profit_threshold_lamports": 100_000,
+        }));
+        assert_eq!(base.hash, rotated_key.hash);
+
+        let retuned = ConfigSnapshot::from_values(json!({
+            "rpc_url": "https://mainnet.helius-rpc.com/?api-key=secret",
+            "telegram_bot_token": null,
+            "min_profit_threshold_lamports": 150_000,
+        }));
+        assert_ne!(base.hash, retuned.hash);
+    }
+}
@@ -0,0 +1,411 @@
+/// REST Control API
+///
+/// Authenticated HTTP surface for dashboards and orchestration, alongside the
+/// Telegram commands: pause/resume, a status summary with wallet holdings,
+/// open positions, PnL by trigger from the trade journal, the Prometheus text, get/patch of the strategy values
+/// workers re-read on every event, and the execution policy's gates with their on/off switches.
+/// Every route needs `Authorization: Bearer <CONTROL_API_TOKEN>`;
+/// without a token configured the API is not started.
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use prometheus::{Encoder, TextEncoder};
+use serde::{Deserialize, Serialize};
+use strategy::ports::TelemetryPort;
+use crate::capital_at_risk::{CapitalAtRisk, InventoryValuation};
+use strategy::params::{StrategyParams, StrategyParamsHandle};
+use strategy::policy::{ExecutionPolicy, Gate};
+use crate::config::BotConfig;
+use crate::drawdown::DrawdownBreaker;
+use crate::inventory_manager::InventoryManager;
+use crate::metrics::BotMetrics;
+use crate::risk::RiskManager;
+
+/// Strategy values that take effect on the next event without a restart
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RuntimeParams {
+    pub default_trade_size_lamports: u64,
+    pub min_profit_threshold_lamports: u64,
+    pub sanity_profit_factor: f64,
+    pub max_price_impact_bps: u16,
+    pub jito_tip_lamports: u64,
+    pub jito_tip_percentage: f64,
+    pub max_jito_tip_lamports: u64,
+    pub max_slippage_bps: u16,
+    pub ai_confidence_threshold: f32,
+    pub max_hops: u8,
+}
+
+/// Partial update; omitted fields keep their current value
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeParamsPatch {
+    pub default_trade_size_lamports: Option<u64>,
+    pub min_profit_threshold_lamports: Option<u64>,
+    pub sanity_profit_factor: Option<f64>,
+    pub max_price_impact_bps: Option<u16>,
+    pub jito_tip_lamports: Option<u64>,
+    pub jito_tip_percentage: Option<f64>,
+    pub max_jito_tip_lamports: Option<u64>,
+    pub max_slippage_bps: Option<u16>,
+    pub ai_confidence_threshold: Option<f32>,
+    pub max_hops: Option<u8>,
+}
+
+impl RuntimeParams {
+    pub fn from_config(cfg: &BotConfig) -> Self {
+        let strategy = cfg.strategy_params();
+        Self {
+            default_trade_size_lamports: cfg.default_trade_size_lamports,
+            min_profit_threshold_lamports: strategy.min_profit_threshold_lamports,
+            sanity_profit_factor: strategy.sanity_profit_factor,
+            max_price_impact_bps: strategy.max_price_impact_bps,
+            jito_tip_lamports: cfg.jito_tip_lamports,
+            jito_tip_percentage: cfg.jito_tip_percentage,
+            max_jito_tip_lamports: cfg.max_jito_tip_lamports,
+            max_slippage_bps: cfg.max_slippage_bps,
+            ai_confidence_threshold: cfg.ai_confidence_threshold,
+            max_hops: cfg.max_hops,
+        }
+    }
+
+    /// Merges a patch, rejecting the whole patch if any resulting value is out of range
+    pub fn apply(&self, patch: RuntimeParamsPatch, max_trade_size_lamports: u64) -> Result<Self, String> {
+        let next = Self {
+            default_trade_size_lamports: patch.default_trade_size_lamports.unwrap_or(self.default_trade_size_lamports),
+            min_profit_threshold_lamports: patch.min_profit_threshold_lamports.unwrap_or(self.min_profit_threshold_lamports),
+            sanity_profit_factor: patch.sanity_profit_factor.unwrap_or(self.sanity_profit_factor),
+            max_price_impact_bps: patch.max_price_impact_bps.unwrap_or(self.max_price_impact_bps),
+            jito_tip_lamports: patch.jito_tip_lamports.unwrap_or(self.jito_tip_lamports),
+            jito_tip_percentage: patch.jito_tip_percentage.unwrap_or(self.jito_tip_percentage),
+            max_jito_tip_lamports: patch.max_jito_tip_lamports.unwrap_or(self.max_jito_tip_lamports),
+            max_slippage_bps: patch.max_slippage_bps.unwrap_or(self.max_slippage_bps),
+            ai_confidence_threshold: patch.ai_confidence_threshold.unwrap_or(self.ai_confidence_threshold),
+            max_hops: patch.max_hops.unwrap_or(self.max_hops),
+        };
+
+        // Same bounds as BotConfig::new/validate
+        if next.default_trade_size_lamports == 0 || next.default_trade_size_lamports > max_trade_size_lamports {
+            return Err(format!("default_trade_size_lamports must be in (0, {}]. Got: {}", max_trade_size_lamports, next.default_trade_size_lamports));
+        }
+        if next.jito_tip_percentage <= 0.0 || next.jito_tip_percentage >= 1.0 {
+            return Err(format!("jito_tip_percentage must be between 0.0 and 1.0. Got: {}", next.jito_tip_percentage));
+        }
+        if next.max_slippage_bps == 0 || next.max_slippage_bps > 10_000 {
+            return Err(format!("max_slippage_bps must be in [1, 10000]. Got: {}", next.max_slippage_bps));
+        }
+        if !(0.0..=1.0).contains(&next.ai_confidence_threshold) {
+            return Err(format!("ai_confidence_threshold must be in [0.0, 1.0]. Got: {}", next.ai_confidence_threshold));
+        }
+        if !(2..=8).contains(&next.max_hops) {
+            return Err(format!("max_hops must be in [2, 8]. Got: {}", next.max_hops));
+        }
+        if next.jito_tip_lamports > next.max_jito_tip_lamports {
+            return Err("jito_tip_lamports must not exceed max_jito_tip_lamports".into());
+        }
+        next.strategy_params(max_trade_size_lamports).validate()?;
+        Ok(next)
+    }
+
+    /// The engine-side thresholds, under the mode's trade-size cap
+    pub fn strategy_params(&self, max_trade_size_lamports: u64) -> StrategyParams {
+        StrategyParams {
+            max_trade_size_lamports,
+            min_profit_threshold_lamports: self.min_profit_threshold_lamports,
+            sanity_profit_factor: self.sanity_profit_factor,
+            max_price_impact_bps: self.max_price_impact_bps,
+        }
+    }
+}
+
+/// Shared, hot-swappable `RuntimeParams`. Thresholds the engine reads itself are
+/// pushed into its `StrategyParamsHandle` on every patch.
+pub struct HotConfig {
+    params: RwLock<RuntimeParams>,
+    strategy: Arc<StrategyParamsHandle>,
+    /// LiveMicro keeps its 0.02 SOL hard cap through patches
+    max_trade_size_lamports: u64,
+}
+
+impl HotConfig {
+    pub fn new(cfg: &BotConfig, strategy: Arc<StrategyParamsHandle>) -> Self {
+        let max_trade_size_lamports = strategy.get().max_trade_size_lamports;
+        Self { params: RwLock::new(RuntimeParams::from_config(cfg)), strategy, max_trade_size_lamports }
+    }
+
+    pub fn get(&self) -> RuntimeParams {
+        self.params.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn patch(&self, patch: RuntimeParamsPatch) -> Result<RuntimeParams, String> {
+        let mut params = self.params.write().unwrap_or_else(|e| e.into_inner());
+        let next = params.apply(patch, self.max_trade_size_lamports)?;
+        self.strategy.set(next.strategy_params(self.max_trade_size_lamports))?;
+        *params = next;
+        Ok(params.clone())
+    }
+}
+
+#[derive(Clone)]
+pub struct ControlState {
+    pub token: Arc<str>,
+    pub metrics: Arc<BotMetrics>,
+    pub risk_mgr: Arc<RiskManager>,
+    pub hot_config: Arc<HotConfig>,
+    pub capital_at_risk: Arc<CapitalAtRisk>,
+    pub drawdown: Arc<DrawdownBreaker>,
+    pub policy: Arc<ExecutionPolicy>,
+    /// None with INVENTORY_POLL_SECS=0
+    pub inventory: Option<Arc<InventoryManager>>,
+    pub started: Instant,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusReport {
+    trading_paused: bool,
+    circuit_breaker_tripped: bool,
+    drawdown_halted: bool,
+    /// None while trading or when the halt waits for /resume
+    drawdown_resume_in_secs: Option<u64>,
+    drawdown_lamports: u64,
+    uptime_secs: u64,
+    opportunities_detected: u64,
+    execution_attempts: u64,
+    win_rate: f32,
+    net_pnl_lamports: i64,
+    /// Trade journal totals over the last 24h; None without a database
+    journal_24h: Option<crate::trade_journal::JournalSummary>,
+    /// Wallet holdings as of the inventory manager's last poll
+    holdings: Option<InventoryValuation>,
+}
+
+pub fn router(state: ControlState) -> Router {
+    Router::new()
+        .route("/pause", post(pause))
+        .route("/resume", post(resume))
+        .route("/status", get(status))
+        .route("/config", get(get_config).patch(patch_config))
+        .route("/positions", get(positions))
+        .route("/attribution", get(attribution))
+        .route("/policy", get(get_policy).patch(patch_policy))
+        .route("/metrics", get(metrics_text))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state)
+}
+
+/// Serves the control API until the process exits
+pub async fn serve(bind: String, state: ControlState) {
+    let addr: SocketAddr = match bind.parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            tracing::error!("❌ Invalid CONTROL_API_BIND {}: {}", bind, e);
+            return;
+        }
+    };
+    tracing::info!("🎛️ Control API listening on {}", addr);
+    match tokio::net::TcpListener::bind(addr).await {
+        Ok(listener) => {
+            if let Err(e) = axum::serve(listener, router(state)).await {
+                tracing::error!("❌ Control API error: {}", e);
+            }
+        }
+        Err(e) => tracing::error!("❌ Failed to start control API on {}: {}", addr, e),
+    }
+}
+
+pub(crate) fn authorized(headers: &HeaderMap, token: &str) -> bool {
+    let Some(presented) = headers.get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    // Compare every byte so the response time doesn't leak the matching prefix
+    presented.len() == token.len()
+        && presented.bytes().zip(token.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+async fn require_token(State(state): State<ControlState>, request: Request, next: Next) -> Response {
+    if !authorized(request.headers(), &state.token) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    next.run(request).await
+}
+
+async fn pause(State(state): State<ControlState>) -> Json<StatusReport> {
+    state.metrics.is_paused.store(true, Ordering::Relaxed);
+    tracing::warn!("⏸ Trading paused via control API");
+    status(State(state)).await
+}
+
+async fn resume(State(state): State<ControlState>) -> Json<StatusReport> {
+    state.drawdown.resume(state.metrics.net_pnl_lamports(), Instant::now());
+    crate::telemetry::DRAWDOWN_BREAKER_HALTED.set(0);
+    state.metrics.is_paused.store(false, Ordering::Relaxed);
+    tracing::info!("▶️ Trading resumed via control API");
+    status(State(state)).await
+}
+
+async fn status(State(state): State<ControlState>) -> Json<StatusReport> {
+    let journal_24h = match &state.metrics.trade_journal {
+        Some(journal) => journal.summary(86_400).await
+            .map_err(|e| tracing::warn!("⚠️ Trade journal summary failed: {}", e))
+            .ok(),
+        None => None,
+    };
+    Json(StatusReport {
+        trading_paused: state.metrics.is_paused.load(Ordering::Relaxed),
+        circuit_breaker_tripped: state.risk_mgr.circuit_breaker_triggered.load(Ordering::Relaxed),
+        drawdown_halted: state.drawdown.is_tripped(),
+        drawdown_resume_in_secs: state.drawdown.resume_in(Instant::now()).map(|d| d.as_secs()),
+        drawdown_lamports: state.drawdown.window_drawdown(),
+        uptime_secs: state.started.elapsed().as_secs(),
+        opportunities_detected: state.metrics.opportunities_detected.load(Ordering::Relaxed),
+        execution_attempts: state.metrics.execution_attempts_total.load(Ordering::Relaxed),
+        win_rate: state.metrics.get_win_rate(),
+        net_pnl_lamports: state.metrics.net_pnl_lamports(),
+        journal_24h,
+        holdings: state.inventory.as_ref().and_then(|inventory| inventory.holdings()),
+    })
+}
+
+async fn get_config(State(state): State<ControlState>) -> Json<RuntimeParams> {
+    Json(state.hot_config.get())
+}
+
+async fn patch_config(State(state): State<ControlState>, Json(patch): Json<RuntimeParamsPatch>) -> Response {
+    match state.hot_config.patch(patch) {
+        Ok(params) => {
+            tracing::info!("🎛️ Runtime config updated via control API: {:?}", params);
+            Json(params).into_response()
+        }
+        Err(e) => (StatusCode::UNPROCESSABLE_ENTITY, e).into_response(),
+    }
+}
+
+/// The gates in run order, with their parameters and whether each is switched on
+async fn get_policy(State(state): State<ControlState>) -> Json<serde_json::Value> {
+    Json(policy_json(&state.policy))
+}
+
+/// Switches listed gates on or off, e.g. `{"dna": false, "ai": true}`. Nothing changes unless every gate is valid.
+async fn patch_policy(State(state): State<ControlState>, Json(toggles): Json<BTreeMap<String, bool>>) -> Response {
+    match apply_toggles(&state.policy, &toggles) {
+        Ok(()) => {
+            tracing::info!("🎛️ Execution policy updated via control API: {}", state.policy.describe());
+            Json(policy_json(&state.policy)).into_response()
+        }
+        Err(e) => (StatusCode::UNPROCESSABLE_ENTITY, e).into_response(),
+    }
+}
+
+fn policy_json(policy: &ExecutionPolicy) -> serde_json::Value {
+    let gates: Vec<serde_json::Value> = policy.steps().iter()
+        .map(|step| serde_json::json!({
+            "gate": step.gate.name(),
+            "enabled": step.is_enabled(),
+            "params": step.params().iter().copied().collect::<BTreeMap<_, _>>(),
+        }))
+        .collect();
+    serde_json::json!({ "gates": gates })
+}
+
+fn apply_toggles(policy: &ExecutionPolicy, toggles: &BTreeMap<String, bool>) -> Result<(), String> {
+    let mut resolved = Vec::with_capacity(toggles.len());
+    for (name, enabled) in toggles {
+        let gate = Gate::parse(name).ok_or_else(|| format!("unknown gate '{}'", name))?;
+        if policy.step(gate).is_none() {
+            return Err(format!("gate '{}' is not in EXECUTION_POLICY", name));
+        }
+        resolved.push((gate, *enabled));
+    }
+    for (gate, enabled) in resolved {
+        policy.set_enabled(gate, enabled);
+    }
+    Ok(())
+}
+
+/// Unconfirmed bundles and wallet inventory, from the capital-at-risk snapshot
+async fn positions(State(state): State<ControlState>) -> Json<serde_json::Value> {
+    let snapshot = state.capital_at_risk.snapshot().await;
+    Json(serde_json::json!({
+        "in_flight": snapshot.in_flight,
+        "inventory": snapshot.inventory,
+        "errors": snapshot.errors,
+    }))
+}
+
+/// Trade journal PnL over the last 24h by strategy and trigger
+async fn attribution(State(state): State<ControlState>) -> Response {
+    let Some(journal) = &state.metrics.trade_journal else {
+        return (StatusCode::NOT_FOUND, "trade journal disabled (DATABASE_URL unset)").into_response();
+    };
+    match journal.attribution(86_400).await {
+        Ok(rows) => Json(rows).into_response(),
+        Err(e) => {
+            tracing::warn!("⚠️ Trade journal attribution failed: {}", e);
+            (StatusCode::SERVICE_UNAVAILABLE, e.to_string()).into_response()
+        }
+    }
+}
+
+async fn metrics_text() -> String {
+    let mut buffer = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&crate::telemetry::gather(), &mut buffer) {
+        tracing::error!("❌ Metrics encoding failed: {}", e);
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patch_validates_and_requires_bearer_token() {
+        let params = RuntimeParams {
+            default_trade_size_lamports: 100_000_000,
+            min_profit_threshold_lamports: 30_000,
+            sanity_profit_factor: 0.1,
+            max_price_impact_bps: 100,
+            jito_tip_lamports: 10_000,
+            jito_tip_percentage: 0.15,
+            max_jito_tip_lamports: 100_000_000,
+            max_slippage_bps: 50,
+            ai_confidence_threshold: 0.7,
+            max_hops: 5,
+        };
+
+        let patched = params.apply(RuntimeParamsPatch { min_profit_threshold_lamports: Some(50_000), ..Default::default() }, 1_000_000_000).unwrap();
+        assert_eq!(patched, RuntimeParams { min_profit_threshold_lamports: 50_000, ..params.clone() });
+        assert!(params.apply(RuntimeParamsPatch { jito_tip_percentage: Some(1.5), ..Default::default() }, 1_000_000_000).is_err());
+        assert!(params.apply(RuntimeParamsPatch { max_hops: Some(1), ..Default::default() }, 1_000_000_000).is_err());
+        assert!(params.apply(RuntimeParamsPatch { max_price_impact_bps: Some(0), ..Default::default() }, 1_000_000_000).is_err());
+        // LiveMicro cap
+        assert!(params.apply(RuntimeParamsPatch::default(), 20_000_000).is_err());
+        assert!(serde_json::from_str::<RuntimeParamsPatch>(r#"{"rpc_url":"http://x"}"#).is_err());
+
+        let mut headers = HeaderMap::new();
+        assert!(!authorized(&headers, "s3cret"));
+        headers.insert(header::AUTHORIZATION, "Bearer s3cre7".parse().unwrap());
+        assert!(!authorized(&headers, "s3cret"));
+        headers.insert(header::AUTHORIZATION, "Bearer s3cret".parse().unwrap());
+        assert!(authorized(&headers, "s3cret"));
+
+        let policy = ExecutionPolicy::parse("exposure,dna,safety").unwrap();
+        let toggles = |pairs: &[(&str, bool)]| pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect::<BTreeMap<_, _>>();
+        assert!(apply_toggles(&policy, &toggles(&[("dna", false), ("ai", false)])).is_err());
+        assert!(policy.is_active(Gate::Dna));
+        apply_toggles(&policy, &toggles(&[("dna", false)])).unwrap();
+        assert!(!policy.is_active(Gate::Dna));
+        assert_eq!(policy_json(&policy)["gates"][1]["enabled"], false);
+    }
+}
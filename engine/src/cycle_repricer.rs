@@ -0,0 +1,87 @@
+/// Per-Slot Cycle Re-Pricing
+///
+/// Wakes once per slot (chain time as the shared clock tracks it) and has the
+/// strategy engine re-quote its cached top cycles in one pass. A cycle that
+/// reopened goes through the same gates as a worker's find and is booked the
+/// same way: risk, metrics and a trade notification.
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use strategy::analytics::decisions::EventOutcome;
+use strategy::cycle_cache::CycleCache;
+use crate::AppContext;
+
+/// How often the clock is checked for a new slot
+const SLOT_POLL: Duration = Duration::from_millis(25);
+
+pub async fn run(ctx: Arc<AppContext>, cache: Arc<CycleCache>) {
+    let clock = mev_core::clock::system();
+    let mut last_slot = clock.slot();
+    let mut poll = tokio::time::interval(SLOT_POLL);
+    poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        poll.tick().await;
+        let slot = clock.slot();
+        if slot <= last_slot {
+            continue;
+        }
+        last_slot = slot;
+        crate::telemetry::CYCLE_CACHE_SIZE.set(cache.len() as i64);
+        if cache.is_empty() || ctx.metrics.is_paused.load(Ordering::Relaxed) {
+            continue;
+        }
+
+        let activation = ctx.schedule.current(crate::schedule::Strategy::Reprice);
+        if !activation.active {
+            continue;
+        }
+        let params = ctx.hot_config.get();
+        let trade_size = match &ctx.position_sizer {
+            Some(sizer) => sizer.size(
+                &ctx.metrics.recent_trade_stats(),
+                ctx.inventory.available(&mev_core::constants::SOL_MINT),
+                params.default_trade_size_lamports,
+            ),
+            None => params.default_trade_size_lamports,
+        };
+        let trade_size = activation.scaled(trade_size, ctx.engine.params().max_trade_size_lamports);
+        if ctx.risk_mgr.can_trade(trade_size).is_err() {
+            continue;
+        }
+
+        let outcome = ctx.engine.reprice_cycles(
+            trade_size,
+            params.jito_tip_lamports,
+            params.jito_tip_percentage,
+            params.max_jito_tip_lamports,
+            params.max_slippage_bps,
+            ctx.config.volatility_sensitivity,
+            ctx.config.max_slippage_ceiling,
+            params.ai_confidence_threshold,
+        ).await;
+
+        if let Ok(found) = &outcome {
+            ctx.metrics.funnel.record(found, crate::funnel::FunnelStage::Found);
+        }
+        match outcome {
+            // A quiet slot isn't a processed update; keep it out of the outcome counts
+            Ok(EventOutcome::NoOpportunity) => {}
+            Ok(EventOutcome::Executed(opportunity)) => {
+                crate::telemetry::OPPORTUNITY_OUTCOMES.with_label_values(&["executed"]).inc();
+                crate::telemetry::OPPORTUNITIES_TOTAL.inc();
+                crate::telemetry::OPPORTUNITIES_PROFITABLE.inc();
+                ctx.metrics.log_opportunity(true);
+                ctx.risk_mgr.record_trade(trade_size, opportunity.profit_in_sol() as i64);
+                let alerts = Arc::clone(&ctx.alert_mgr);
+                tokio::spawn(async move {
+                    alerts.send_trade_notification(&opportunity, "Success (Slot Reprice)").await;
+                });
+            }
+            Ok(outcome) => {
+                crate::telemetry::OPPORTUNITY_OUTCOMES.with_label_values(&[outcome.label()]).inc();
+                crate::telemetry::OPPORTUNITIES_TOTAL.inc();
+            }
+            Err(e) => tracing::error!("💥 Slot reprice error: {}", e),
+        }
+    }
+}
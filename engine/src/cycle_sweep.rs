@@ -0,0 +1,79 @@
+/// Background Cycle Sweep
+///
+/// With incremental search an update only looks at cycles through its own
+/// pool, so a route whose pools all went quiet while its price moved
+/// elsewhere is never searched by the workers. Every `CYCLE_SWEEP_INTERVAL_MS`
+/// this task runs the full search from SOL, where trade size is denominated,
+/// and books what it finds like the slot re-pricer does.
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use strategy::analytics::decisions::EventOutcome;
+use crate::AppContext;
+
+pub async fn run(ctx: Arc<AppContext>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        if ctx.metrics.is_paused.load(Ordering::Relaxed) {
+            continue;
+        }
+
+        let activation = ctx.schedule.current(crate::schedule::Strategy::Sweep);
+        if !activation.active {
+            continue;
+        }
+        let params = ctx.hot_config.get();
+        let trade_size = match &ctx.position_sizer {
+            Some(sizer) => sizer.size(
+                &ctx.metrics.recent_trade_stats(),
+                ctx.inventory.available(&mev_core::constants::SOL_MINT),
+                params.default_trade_size_lamports,
+            ),
+            None => params.default_trade_size_lamports,
+        };
+        let trade_size = activation.scaled(trade_size, ctx.engine.params().max_trade_size_lamports);
+        if ctx.risk_mgr.can_trade(trade_size).is_err() {
+            continue;
+        }
+
+        let started = std::time::Instant::now();
+        let outcome = ctx.engine.sweep_cycles(
+            &mev_core::constants::SOL_MINT,
+            trade_size,
+            params.jito_tip_lamports,
+            params.jito_tip_percentage,
+            params.max_jito_tip_lamports,
+            params.max_slippage_bps,
+            ctx.config.volatility_sensitivity,
+            ctx.config.max_slippage_ceiling,
+            params.ai_confidence_threshold,
+            params.max_hops,
+        ).await;
+        tracing::debug!("🧭 Cycle sweep took {}ms", started.elapsed().as_millis());
+
+        if let Ok(found) = &outcome {
+            ctx.metrics.funnel.record(found, crate::funnel::FunnelStage::Found);
+        }
+        match outcome {
+            Ok(EventOutcome::NoOpportunity) => {}
+            Ok(EventOutcome::Executed(opportunity)) => {
+                crate::telemetry::OPPORTUNITY_OUTCOMES.with_label_values(&["executed"]).inc();
+                crate::telemetry::OPPORTUNITIES_TOTAL.inc();
+                crate::telemetry::OPPORTUNITIES_PROFITABLE.inc();
+                ctx.metrics.log_opportunity(true);
+                ctx.risk_mgr.record_trade(trade_size, opportunity.profit_in_sol() as i64);
+                let alerts = Arc::clone(&ctx.alert_mgr);
+                tokio::spawn(async move {
+                    alerts.send_trade_notification(&opportunity, "Success (Cycle Sweep)").await;
+                });
+            }
+            Ok(outcome) => {
+                crate::telemetry::OPPORTUNITY_OUTCOMES.with_label_values(&[outcome.label()]).inc();
+                crate::telemetry::OPPORTUNITIES_TOTAL.inc();
+            }
+            Err(e) => tracing::error!("💥 Cycle sweep error: {}", e),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+/// Pool Delisting
+///
+/// Pools die in three ways: the account is closed (no lamports, no data),
+/// liquidity is pulled until a reserve hits zero, or a Pump.fun curve
+/// completes and migrates (it then reports empty reserves). Dead pools keep
+/// their edges in the graph and their subscriptions open, so every search
+/// walks them for nothing. The delister takes them out of the graph, the
+/// scoring engine and the ingestion subscriptions, once per pool, and sends
+/// an informational alert.
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::mpsc::UnboundedSender;
+use mev_core::MarketUpdate;
+use strategy::StrategyEngine;
+use crate::alerts::{AlertManager, AlertSeverity};
+use crate::scoring::PoolScoringEngine;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DelistReason {
+    Closed,
+    Drained,
+    Migrated,
+}
+
+impl DelistReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DelistReason::Closed => "closed",
+            DelistReason::Drained => "drained",
+            DelistReason::Migrated => "migrated",
+        }
+    }
+}
+
+/// A closed account has been garbage-collected down to nothing
+pub fn is_closed(lamports: u64, data_len: usize) -> bool {
+    lamports == 0 || data_len == 0
+}
+
+/// Why a decoded pool update means the pool is dead, if it does.
+/// Concentrated pools are left alone: zero active liquidity only means the price
+/// left every position's range, which can come back. DLMM accounts carry no reserves,
+/// and an empty order book only means no one is quoting right now.
+pub fn dead_pool(update: &MarketUpdate) -> Option<DelistReason> {
+    if update.price_sqrt.is_some()
+        || update.program_id == mev_core::constants::METEORA_PROGRAM_ID
+        || mev_core::constants::is_clob(&update.program_id)
+    {
+        return None;
+    }
+    if update.coin_reserve > 0 && update.pc_reserve > 0 {
+        return None;
+    }
+    if update.program_id == mev_core::constants::PUMP_FUN_PROGRAM {
+        Some(DelistReason::Migrated)
+    } else {
+        Some(DelistReason::Drained)
+    }
+}
+
+pub struct PoolDelister {
+    engine: Arc<StrategyEngine>,
+    scoring: Arc<PoolScoringEngine>,
+    alert_mgr: Arc<AlertManager>,
+    /// Pools for the ingestion backend to stop streaming
+    unsubscribe_tx: UnboundedSender<Pubkey>,
+    delisted: Mutex<HashSet<Pubkey>>,
+}
+
+impl PoolDelister {
+    pub fn new(
+        engine: Arc<StrategyEngine>,
+        scoring: Arc<PoolScoringEngine>,
+        alert_mgr: Arc<AlertManager>,
+        unsubscribe_tx: UnboundedSender<Pubkey>,
+    ) -> Self {
+        Self { engine, scoring, alert_mgr, unsubscribe_tx, delisted: Mutex::new(HashSet::new()) }
+    }
+
+    pub fn is_delisted(&self, pool: &Pubkey) -> bool {
+        self.delisted.lock().unwrap_or_else(|e| e.into_inner()).contains(pool)
+    }
+
+    /// Removes the pool everywhere. Later calls for the same pool are no-ops.
+    pub fn delist(self: &Arc<Self>, pool: Pubkey, reason: DelistReason) {
+        if !self.delisted.lock().unwrap_or_else(|e| e.into_inner()).insert(pool) {
+            return;
+        }
+        let had_edges = self.engine.remove_pool(&pool);
+        let _ = self.unsubscribe_tx.send(pool);
+        mev_core::telemetry::POOLS_DELISTED_TOTAL.with_label_values(&[reason.as_str()]).inc();
+        tracing::info!("🪦 Pool {} delisted ({}). Removed from graph: {}", pool, reason.as_str(), had_edges);
+
+        let this = Arc::clone(self);
+        tokio::spawn(async move {
+            if let Err(e) = this.scoring.remove(&pool).await {
+                tracing::warn!("⚠️ Failed to drop weight of delisted pool {}: {}", pool, e);
+            }
+            this.alert_mgr.send_alert(
+                AlertSeverity::Info,
+                "POOL DELISTED",
+                &format!("Pool {} {}; removed from graph, subscriptions and scoring.", pool, reason.as_str()),
+                vec![],
+            ).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::constants::{ORCA_WHIRLPOOL_PROGRAM, PUMP_FUN_PROGRAM, RAYDIUM_V4_PROGRAM};
+
+    #[test]
+    fn test_dead_pool_detection() {
+        let update = |program_id, coin_reserve, pc_reserve, price_sqrt| MarketUpdate {
+            pool_address: Pubkey::new_unique(),
+            program_id,
+            coin_mint: Pubkey::new_unique(),
+            pc_mint: Pubkey::new_unique(),
+            coin_reserve,
+            pc_reserve,
+            price_sqrt,
+            liquidity: price_sqrt.map(|_| 0),
+            fee_bps: None,
+            timestamp: 0,
+            trigger: mev_core::Trigger::AccountUpdate,
+        };
+
+        assert_eq!(dead_pool(&update(RAYDIUM_V4_PROGRAM, 1_000, 2_000, None)), None);
+        assert_eq!(dead_pool(&update(RAYDIUM_V4_PROGRAM, 1_000, 0, None)), Some(DelistReason::Drained));
+        assert_eq!(dead_pool(&update(PUMP_FUN_PROGRAM, 0, 0, None)), Some(DelistReason::Migrated));
+        // Out-of-range CLMM price is not a dead pool
+        assert_eq!(dead_pool(&update(ORCA_WHIRLPOOL_PROGRAM, 0, 0, Some(1 << 64))), None);
+
+        assert!(is_closed(0, 0));
+        assert!(is_closed(2_039_280, 0));
+        assert!(!is_closed(2_039_280, 752));
+    }
+}
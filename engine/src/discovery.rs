@@ -6,6 +6,7 @@ use serde_json::{json, Value};
 use solana_sdk::pubkey::Pubkey;
 // use anyhow::{Result, anyhow};
 use crate::config::BotConfig;
+use executor::rpc_pool::RpcPool;
 use mev_core::constants::*;
 use crate::tui::AppState;
 use lru::LruCache;
@@ -23,7 +24,7 @@ pub struct DiscoveryEvent {
 
 pub async fn start_discovery(
     ws_url: String, 
-    rpc_url: String, // Explicit RPC URL
+    rpc: Arc<RpcPool>,
     discovery_tx: Sender<DiscoveryEvent>, 
     market_tx: tokio::sync::broadcast::Sender<mev_core::MarketUpdate>,
     tui_state: Option<Arc<std::sync::Mutex<AppState>>>,
@@ -87,6 +88,17 @@ pub async fn start_discovery(
         ]
     });
 
+    // 5. Subscribe to Launchpad Logs (Raydium LaunchLab, Moonshot)
+    let launchpad_subs = [(5, RAYDIUM_LAUNCHLAB_PROGRAM), (6, MOONSHOT_PROGRAM)].map(|(id, program)| json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "logsSubscribe",
+        "params": [
+            { "mentions": [program.to_string()] },
+            { "commitment": "processed" }
+        ]
+    }));
+
     if let Err(e) = write.send(Message::Text(raydium_sub.to_string().into())).await {
         tracing::error!("❌ Raydium Log Sub Failed: {}", e);
     }
@@ -99,8 +111,13 @@ pub async fn start_discovery(
     if let Err(e) = write.send(Message::Text(meteora_sub.to_string().into())).await {
         tracing::error!("❌ Meteora Log Sub Failed: {}", e);
     }
+    for sub in launchpad_subs {
+        if let Err(e) = write.send(Message::Text(sub.to_string().into())).await {
+            tracing::error!("❌ Launchpad Log Sub Failed: {}", e);
+        }
+    }
 
-    let rpc_client = Arc::new(solana_client::nonblocking::rpc_client::RpcClient::new(rpc_url)); 
+    let rpc_client = rpc;
     
     // 4. Signature Cache (Eliminate redundant hydration)
     let sig_cache = Arc::new(Mutex::new(LruCache::<String, bool>::new(NonZeroUsize::new(1000).unwrap())));
@@ -117,9 +134,15 @@ pub async fn start_discovery(
                                 if let Some(logs) = value.get("logs").and_then(|l| l.as_array()) {
                                     let signature = value.get("signature").and_then(|s| s.as_str()).unwrap_or("unknown");
                                     
+                                    let mut invoked = None;
                                     for log in logs {
                                         let log_str = log.as_str().unwrap_or("");
-                                        if let Some(event) = parse_log_message(log_str, signature) {
+                                        if let Some(program) = invoked_program(log_str) {
+                                            invoked = Some(program);
+                                        }
+                                        let parsed = parse_launchpad_log(log_str, invoked.as_ref())
+                                            .or_else(|| parse_log_message(log_str, signature));
+                                        if let Some(event) = parsed {
                                             // Check Signature Cache first
                                             {
                                                 let mut cache = sig_cache.lock().unwrap();
@@ -214,6 +237,27 @@ pub async fn start_discovery(
                                                         let _ = sub_tx.send(update.pool_address.to_string());
                                                     }
                                                 });
+                                            } else if is_launchpad(&event.program_id) {
+                                                // 🚀 LAUNCHPAD INJECTION (LaunchLab / Moonshot)
+                                                let rpc = Arc::clone(&rpc_client);
+                                                let market_tx = market_tx.clone();
+                                                let sub_tx = sub_tx.clone();
+                                                let discovery_tx = discovery_tx.clone();
+                                                let sig = signature.to_string();
+
+                                                tokio::spawn(async move {
+                                                    match hydrate_launchpad_pool(rpc, sig.clone(), event).await {
+                                                        Ok(update) => {
+                                                            tracing::info!("🚀 Discovery Engine: INJECTING {} Curve {} (Liquidity: {:.2} SOL)",
+                                                                launchpad_name(&update.program_id), update.pool_address, update.pc_reserve as f64 / 1e9);
+                                                            let _ = market_tx.send(update.clone());
+                                                            let _ = sub_tx.send(update.pool_address.to_string());
+                                                            // Hydrated event carries the curve address, so the DNA pipeline can follow it
+                                                            let _ = discovery_tx.send(hydrated_event(&update)).await;
+                                                        }
+                                                        Err(e) => tracing::debug!("🧪 Launchpad hydration skip for {}: {}", sig, e),
+                                                    }
+                                                });
                                             }
                                             }
                                         }
@@ -232,8 +276,25 @@ pub async fn start_discovery(
     }
 }
 
+/// Creation transaction for a pool-init signature, from the healthiest RPC endpoint
+async fn fetch_transaction(
+    rpc: &RpcPool,
+    sig: &solana_sdk::signature::Signature,
+) -> solana_client::client_error::Result<solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta> {
+    rpc.call(|c| async move {
+        c.get_transaction_with_config(
+            sig,
+            solana_client::rpc_config::RpcTransactionConfig {
+                encoding: Some(solana_transaction_status::UiTransactionEncoding::Base64),
+                commitment: Some(solana_sdk::commitment_config::CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        ).await
+    }).await
+}
+
 pub async fn hydrate_raydium_pool(
-    rpc: Arc<solana_client::nonblocking::rpc_client::RpcClient>,
+    rpc: Arc<RpcPool>,
     signature: String, // We might not need signature if we have the pool address from event, but event.pool_address is usually default() from logs
     event: DiscoveryEvent
 ) -> anyhow::Result<mev_core::MarketUpdate> {
@@ -268,14 +329,7 @@ pub async fn hydrate_raydium_pool(
     // 1. Fetch Transaction
     let mut tx_info = None;
     for _ in 0..3 {
-        if let Ok(info) = rpc.get_transaction_with_config(
-            &sig,
-            solana_client::rpc_config::RpcTransactionConfig {
-                encoding: Some(solana_transaction_status::UiTransactionEncoding::Base64),
-                commitment: Some(solana_sdk::commitment_config::CommitmentConfig::confirmed()),
-                max_supported_transaction_version: Some(0),
-            }
-        ).await {
+        if let Ok(info) = fetch_transaction(&rpc, &sig).await {
             tx_info = Some(info);
             break;
         }
@@ -326,12 +380,14 @@ pub async fn hydrate_raydium_pool(
         pc_reserve,
         price_sqrt: None,
         liquidity: None,
+        fee_bps: None,
         timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_secs() as i64,
+        trigger: mev_core::Trigger::Discovery,
     })
 }
 
 pub async fn hydrate_pump_fun_pool(
-    rpc: Arc<solana_client::nonblocking::rpc_client::RpcClient>,
+    rpc: Arc<RpcPool>,
     _signature: String,
     _event: DiscoveryEvent
 ) -> anyhow::Result<mev_core::MarketUpdate> {
@@ -350,14 +406,7 @@ pub async fn hydrate_pump_fun_pool(
     // 1. Fetch Transaction to get accounts
     let mut tx_info = None;
     for attempt in 1..=3 {
-        match rpc.get_transaction_with_config(
-            &sig,
-            solana_client::rpc_config::RpcTransactionConfig {
-                encoding: Some(solana_transaction_status::UiTransactionEncoding::Base64),
-                commitment: Some(solana_sdk::commitment_config::CommitmentConfig::confirmed()),
-                max_supported_transaction_version: Some(0),
-            }
-        ).await {
+        match fetch_transaction(&rpc, &sig).await {
             Ok(info) => {
                 tx_info = Some(info);
                 break;
@@ -386,7 +435,7 @@ pub async fn hydrate_pump_fun_pool(
     for chunk in accounts.chunks(100) {
         let mut retry_count = 0;
         let chunk_accounts = loop {
-            match rpc.get_multiple_accounts(chunk).await {
+            match rpc.call(|c| async move { c.get_multiple_accounts(chunk).await }).await {
                 Ok(accs) => break accs,
                 Err(e) if retry_count < 3 => {
                     retry_count += 1;
@@ -402,7 +451,7 @@ pub async fn hydrate_pump_fun_pool(
     for (i, account_opt) in account_results.into_iter().enumerate() {
         let key = &accounts[i];
         if let Some(account) = account_opt {
-            if account.owner == PUMP_FUN_PROGRAM && (account.data.len() == 49 || account.data.len() == 137) {
+            if account.owner == PUMP_FUN_PROGRAM && mev_core::pump_fun::CURVE_ACCOUNT_LENS.contains(&account.data.len()) {
                 tracing::info!("🎯 Found Pump.fun Bonding Curve at index {}: {} (size: {} bytes)", i, key, account.data.len());
                 
                 if account.data.len() < 8 { continue; }
@@ -418,17 +467,8 @@ pub async fn hydrate_pump_fun_pool(
                             // In Pump.fun Create, Account 0 is always the Mint
                             let token_mint = accounts[0];
                             
-                            return Ok(mev_core::MarketUpdate {
-                                pool_address: *key,
-                                program_id: PUMP_FUN_PROGRAM,
-                                pc_mint: SOL_MINT, 
-                                coin_mint: token_mint,
-                                coin_reserve: curve.virtual_token_reserves,
-                                pc_reserve: curve.virtual_sol_reserves,
-                                price_sqrt: None,
-                                liquidity: None,
-                                timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_secs() as i64,
-                            });
+                            let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_secs() as i64;
+                            return Ok(mev_core::MarketUpdate { trigger: mev_core::Trigger::Discovery, ..curve.to_market_update(*key, token_mint, ts) });
                         }
                     },
                     Err(e) => tracing::warn!("❌ Failed to deserialize curve at {} (size: {} bytes): {}", key, account.data.len(), e),
@@ -442,7 +482,7 @@ pub async fn hydrate_pump_fun_pool(
 }
 
 pub async fn hydrate_meteora_pool(
-    rpc: Arc<solana_client::nonblocking::rpc_client::RpcClient>,
+    rpc: Arc<RpcPool>,
     signature: String,
     _event: DiscoveryEvent
 ) -> anyhow::Result<mev_core::MarketUpdate> {
@@ -452,14 +492,7 @@ pub async fn hydrate_meteora_pool(
     let sig = Signature::from_str(&signature)?;
     
     // Fetch transaction to get accounts
-    let tx_info = rpc.get_transaction_with_config(
-        &sig,
-        solana_client::rpc_config::RpcTransactionConfig {
-            encoding: Some(solana_transaction_status::UiTransactionEncoding::Base64),
-            commitment: Some(solana_sdk::commitment_config::CommitmentConfig::confirmed()),
-            max_supported_transaction_version: Some(0),
-        }
-    ).await?;
+    let tx_info = fetch_transaction(&rpc, &sig).await?;
 
     let message = tx_info.transaction.transaction.decode().ok_or_else(|| anyhow::anyhow!("Failed to decode transaction"))?.message;
     
@@ -478,10 +511,170 @@ pub async fn hydrate_meteora_pool(
         pc_reserve: 0,
         price_sqrt: None,
         liquidity: None,
+        fee_bps: None,
         timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_secs() as i64,
+        trigger: mev_core::Trigger::Discovery,
+    })
+}
+
+pub fn is_launchpad(program_id: &Pubkey) -> bool {
+    *program_id == RAYDIUM_LAUNCHLAB_PROGRAM || *program_id == MOONSHOT_PROGRAM
+}
+
+pub fn launchpad_name(program_id: &Pubkey) -> &'static str {
+    match *program_id {
+        RAYDIUM_LAUNCHLAB_PROGRAM => "LaunchLab",
+        MOONSHOT_PROGRAM => "Moonshot",
+        _ => "Unknown",
+    }
+}
+
+/// Discovery event for a hydrated pool, with its address and mints filled in
+pub fn hydrated_event(update: &mev_core::MarketUpdate) -> DiscoveryEvent {
+    DiscoveryEvent {
+        pool_address: update.pool_address,
+        program_id: update.program_id,
+        token_a: Some(update.coin_mint),
+        token_b: Some(update.pc_mint),
+        timestamp: update.timestamp as u64,
+    }
+}
+
+/// LaunchLab and Moonshot launches: finds the curve account among the transaction's
+/// accounts and reports its virtual reserves as (token, SOL), like Pump.fun
+pub async fn hydrate_launchpad_pool(
+    rpc: Arc<RpcPool>,
+    signature: String,
+    event: DiscoveryEvent
+) -> anyhow::Result<mev_core::MarketUpdate> {
+    use solana_sdk::signature::Signature;
+    use std::str::FromStr;
+
+    let sig = Signature::from_str(&signature)?;
+    let venue = launchpad_name(&event.program_id);
+
+    let mut tx_info = None;
+    for attempt in 1..=3 {
+        match fetch_transaction(&rpc, &sig).await {
+            Ok(info) => {
+                tx_info = Some(info);
+                break;
+            }
+            Err(e) => tracing::warn!("⏳ [Hydration] {} Tx Fetch Attempt {} Failed for {}: {}", venue, attempt, signature, e),
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(500 * attempt)).await;
+    }
+
+    let tx_info = tx_info.ok_or_else(|| {
+        mev_core::telemetry::DISCOVERY_ERRORS.with_label_values(&["hydration_launchpad"]).inc();
+        anyhow::anyhow!("Failed to fetch {} transaction {} after 3 attempts", venue, signature)
+    })?;
+    let message = tx_info.transaction.transaction.decode().ok_or_else(|| anyhow::anyhow!("Failed to decode transaction"))?.message;
+    let accounts = message.static_account_keys();
+
+    let mut account_results = Vec::with_capacity(accounts.len());
+    for chunk in accounts.chunks(100) {
+        account_results.extend(rpc.call(|c| async move { c.get_multiple_accounts(chunk).await }).await?);
+    }
+    let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_secs() as i64;
+
+    for (key, account) in accounts.iter().zip(account_results) {
+        let Some(account) = account else { continue };
+        if account.owner != event.program_id {
+            continue;
+        }
+
+        if event.program_id == RAYDIUM_LAUNCHLAB_PROGRAM {
+            let Ok(pool) = mev_core::launchlab::LaunchLabPool::from_account_data(&account.data) else { continue };
+            if !pool.is_trading() || pool.quote_mint != SOL_MINT || pool.virtual_base == 0 {
+                continue;
+            }
+            let (token_reserve, sol_reserve) = pool.virtual_reserves();
+            tracing::info!("✅ [Unified] Hydrated LaunchLab Pool {}: Tokens={}, SOL={}", key, token_reserve, sol_reserve);
+            return Ok(mev_core::MarketUpdate {
+                pool_address: *key,
+                program_id: RAYDIUM_LAUNCHLAB_PROGRAM,
+                coin_mint: pool.base_mint,
+                pc_mint: SOL_MINT,
+                coin_reserve: token_reserve,
+                pc_reserve: sol_reserve,
+                price_sqrt: None,
+                liquidity: None,
+                fee_bps: None,
+                timestamp,
+                trigger: mev_core::Trigger::Discovery,
+            });
+        }
+
+        if event.program_id == MOONSHOT_PROGRAM {
+            let Ok(curve) = mev_core::moonshot::MoonshotCurve::from_account_data(&account.data) else { continue };
+            if !curve.is_supported() || curve.curve_amount == 0 {
+                continue;
+            }
+            let (token_reserve, sol_reserve) = curve.virtual_reserves();
+            tracing::info!("✅ [Unified] Hydrated Moonshot Curve {}: Tokens={}, SOL={}", key, token_reserve, sol_reserve);
+            return Ok(mev_core::MarketUpdate {
+                pool_address: *key,
+                program_id: MOONSHOT_PROGRAM,
+                coin_mint: curve.mint,
+                pc_mint: SOL_MINT,
+                coin_reserve: token_reserve,
+                pc_reserve: sol_reserve,
+                price_sqrt: None,
+                liquidity: None,
+                fee_bps: None,
+                timestamp,
+                trigger: mev_core::Trigger::Discovery,
+            });
+        }
+    }
+
+    mev_core::telemetry::DISCOVERY_ERRORS.with_label_values(&["not_found_launchpad"]).inc();
+    Err(anyhow::anyhow!("Could not identify {} curve for {}", venue, signature))
+}
+
+/// Program whose invocation a `Program <id> invoke [n]` line opens
+pub fn invoked_program(log: &str) -> Option<Pubkey> {
+    use std::str::FromStr;
+    let rest = log.strip_prefix("Program ")?;
+    let (id, tail) = rest.split_once(' ')?;
+    if !tail.starts_with("invoke [") {
+        return None;
+    }
+    Pubkey::from_str(id).ok()
+}
+
+/// Launchpad creations. Their instruction names are too generic to match on
+/// their own ("Initialize" also appears in Orca logs), so they only count
+/// inside an invocation of the launchpad program.
+pub fn parse_launchpad_log(log: &str, invoked: Option<&Pubkey>) -> Option<DiscoveryEvent> {
+    let program_id = *invoked?;
+    let trigger = match program_id {
+        RAYDIUM_LAUNCHLAB_PROGRAM => LAUNCHLAB_LOG_TRIGGER,
+        MOONSHOT_PROGRAM => MOONSHOT_LOG_TRIGGER,
+        _ => return None,
+    };
+    // Exact instruction name: LaunchLab also logs "Initialize" for e.g. InitializeV2
+    if !log.trim_end().ends_with(trigger) {
+        return None;
+    }
+    Some(DiscoveryEvent {
+        pool_address: Pubkey::default(),
+        program_id,
+        token_a: None,
+        token_b: None,
+        timestamp: 0,
     })
 }
 
+/// Decodes a `Program log: ray_log: <base64>` line into a swap event
+pub fn parse_ray_swap_log(log: &str) -> Option<mev_core::raydium::RaySwapLog> {
+    use base64::{Engine as _, engine::general_purpose};
+    let (_, payload) = log.split_once(mev_core::raydium::RAY_LOG_PREFIX)?;
+    let bytes = general_purpose::STANDARD.decode(payload.trim()).ok()?;
+    mev_core::raydium::RaySwapLog::decode(&bytes)
+}
+
 pub fn parse_log_message(log: &str, _signature: &str) -> Option<DiscoveryEvent> {
     // A. Raydium (Standard or Migration)
     if log.contains(RAYDIUM_AMM_LOG_TRIGGER) {
@@ -552,10 +745,51 @@ mod tests {
         assert_eq!(event_init.program_id, ORCA_WHIRLPOOL_PROGRAM);
     }
 
+    #[test]
+    fn test_parse_launchpad_logs_only_inside_their_program() {
+        let logs = [
+            "Program LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj invoke [1]",
+            "Program log: Instruction: Initialize",
+        ];
+        let mut invoked = None;
+        let mut events = Vec::new();
+        for log in logs {
+            if let Some(program) = invoked_program(log) {
+                invoked = Some(program);
+            }
+            events.extend(parse_launchpad_log(log, invoked.as_ref()));
+        }
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].program_id, RAYDIUM_LAUNCHLAB_PROGRAM);
+
+        let moonshot = invoked_program("Program MoonCVVNZFSYkqNXP6bxHLPL6QQJiMagDL3qcqUQTrG invoke [2]");
+        assert_eq!(moonshot, Some(MOONSHOT_PROGRAM));
+        assert!(parse_launchpad_log("Program log: Instruction: TokenMint", moonshot.as_ref()).is_some());
+        assert!(parse_launchpad_log("Program log: Instruction: Buy", moonshot.as_ref()).is_none());
+        // Same instruction name under another program is not a launch
+        assert!(parse_launchpad_log("Program log: Instruction: Initialize", Some(&ORCA_WHIRLPOOL_PROGRAM)).is_none());
+        assert!(invoked_program("Program log: Instruction: Initialize").is_none());
+    }
+
     #[test]
     fn test_parse_raydium_log() {
         let log = "Program log: ray_log: AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
         let event = parse_log_message(log, "sig123").expect("Should parse Raydium");
         assert_eq!(event.program_id, RAYDIUM_V4_PROGRAM);
     }
+
+    #[test]
+    fn test_parse_ray_swap_log() {
+        use base64::{Engine as _, engine::general_purpose};
+        let mut data = vec![3u8]; // SwapBaseIn
+        for field in [1_000u64, 900, 1, 0, 50_000, 80_000, 620] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        let log = format!("Program log: ray_log: {}", general_purpose::STANDARD.encode(&data));
+
+        let swap = parse_ray_swap_log(&log).expect("Should decode swap");
+        assert!(swap.is_buy());
+        assert_eq!((swap.amount_in, swap.amount_out), (1_000, 620));
+        assert!(parse_ray_swap_log("Program log: Instruction: SwapBaseIn").is_none());
+    }
 }
@@ -0,0 +1,67 @@
+/// Meteora DLMM Bin Refresh
+///
+/// A DLMM pair account only says which bin is active; the liquidity lives in
+/// separate bin array accounts the pool subscription never sees. Every pair
+/// update schedules a re-read of the bin arrays around the active bin, and the
+/// strategy quotes Meteora hops against the latest set it was handed.
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::meteora::{BinArray, DlmmLiquidity, MeteoraDLMM, LB_PAIR_LEN};
+use strategy::StrategyEngine;
+
+pub struct DlmmBinFetcher {
+    rpc: RpcClient,
+    engine: Arc<StrategyEngine>,
+    in_flight: Mutex<HashSet<Pubkey>>,
+}
+
+impl DlmmBinFetcher {
+    pub fn new(rpc_url: &str, engine: Arc<StrategyEngine>) -> Self {
+        Self {
+            rpc: RpcClient::new(rpc_url.to_string()),
+            engine,
+            in_flight: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Schedules a bin refresh for `pool` unless one is already running
+    pub fn request(self: &Arc<Self>, pool: Pubkey) {
+        if !self.in_flight.lock().unwrap_or_else(|e| e.into_inner()).insert(pool) {
+            return;
+        }
+        let fetcher = Arc::clone(self);
+        tokio::spawn(async move {
+            match fetcher.refresh(&pool).await {
+                Ok(bins) => tracing::debug!("🧺 DLMM {} refreshed with {} bin(s)", pool, bins),
+                Err(e) => tracing::debug!("🧺 DLMM bin refresh failed for {}: {}", pool, e),
+            }
+            fetcher.in_flight.lock().unwrap_or_else(|e| e.into_inner()).remove(&pool);
+        });
+    }
+
+    /// Re-reads the pair and its bin arrays and hands them to the strategy.
+    /// Returns how many non-empty bins were loaded.
+    pub async fn refresh(&self, pool: &Pubkey) -> anyhow::Result<usize> {
+        let account = self.rpc.get_account(pool).await?;
+        let bytes = account.data.get(..LB_PAIR_LEN).ok_or_else(|| anyhow::anyhow!("Not a DLMM pair: {} bytes", account.data.len()))?;
+        let pair: MeteoraDLMM = bytemuck::try_pod_read_unaligned(bytes).map_err(|e| anyhow::anyhow!("Bad DLMM layout: {:?}", e))?;
+
+        // Bin arrays are PDAs of whichever program owns the pair
+        let keys: Vec<Pubkey> = pair.bin_array_window()
+            .iter()
+            .map(|&index| BinArray::derive_pda(pool, index, &account.owner))
+            .collect();
+        let arrays: Vec<BinArray> = self.rpc.get_multiple_accounts(&keys).await?
+            .into_iter()
+            .flatten()
+            .filter_map(|account| BinArray::from_account_data(&account.data).ok())
+            .collect();
+
+        let liquidity = DlmmLiquidity::new(&pair, &arrays);
+        let loaded = liquidity.bins.len();
+        self.engine.update_dlmm_liquidity(*pool, liquidity);
+        Ok(loaded)
+    }
+}
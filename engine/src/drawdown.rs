@@ -0,0 +1,190 @@
+/// Drawdown Circuit Breaker
+///
+/// Samples realized net PnL and halts trading once it has fallen more than the
+/// configured amount below its peak inside the sliding window (e.g. 0.05 SOL
+/// in 30 minutes). The halt goes through `is_paused`, like a manual /pause,
+/// and lifts by itself after the cooldown or on /resume. Either way the window
+/// starts over from the current PnL, so the old losses can't re-trip it.
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::alerts::{AlertManager, AlertSeverity, Field};
+use crate::metrics::BotMetrics;
+
+#[derive(Debug, Clone)]
+pub struct DrawdownPolicy {
+    /// Peak-to-current realized loss that halts trading; 0 disables the breaker
+    pub max_drawdown_lamports: u64,
+    pub window: Duration,
+    /// Halt length before trading resumes by itself; zero waits for /resume
+    pub cooldown: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawdownEvent {
+    Tripped { drawdown_lamports: u64 },
+    Resumed,
+}
+
+#[derive(Default)]
+struct State {
+    /// (sampled at, net PnL), oldest first
+    samples: VecDeque<(Instant, i64)>,
+    tripped_at: Option<Instant>,
+}
+
+pub struct DrawdownBreaker {
+    policy: DrawdownPolicy,
+    state: Mutex<State>,
+}
+
+impl DrawdownBreaker {
+    pub fn new(policy: DrawdownPolicy) -> Self {
+        Self { policy, state: Mutex::new(State::default()) }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.policy.max_drawdown_lamports > 0
+    }
+
+    pub fn is_tripped(&self) -> bool {
+        self.state.lock().unwrap().tripped_at.is_some()
+    }
+
+    /// Time left before an automatic resume; None when not halted or waiting for /resume
+    pub fn resume_in(&self, now: Instant) -> Option<Duration> {
+        let tripped_at = self.state.lock().unwrap().tripped_at?;
+        (!self.policy.cooldown.is_zero())
+            .then(|| self.policy.cooldown.saturating_sub(now.duration_since(tripped_at)))
+    }
+
+    /// Fall from the window's peak to the latest sample
+    pub fn window_drawdown(&self) -> u64 {
+        let state = self.state.lock().unwrap();
+        let Some(&(_, latest)) = state.samples.back() else {
+            return 0;
+        };
+        let peak = state.samples.iter().map(|(_, pnl)| *pnl).max().unwrap_or(latest);
+        (peak - latest).max(0) as u64
+    }
+
+    /// Feeds one net PnL sample; reports a trip or an automatic resume
+    pub fn observe(&self, net_pnl: i64, now: Instant) -> Option<DrawdownEvent> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(tripped_at) = state.tripped_at {
+            if self.policy.cooldown.is_zero() || now.duration_since(tripped_at) < self.policy.cooldown {
+                return None;
+            }
+            Self::rebase(&mut state, net_pnl, now);
+            return Some(DrawdownEvent::Resumed);
+        }
+
+        state.samples.push_back((now, net_pnl));
+        while state.samples.front().is_some_and(|(t, _)| now.duration_since(*t) > self.policy.window) {
+            state.samples.pop_front();
+        }
+        let peak = state.samples.iter().map(|(_, pnl)| *pnl).max().unwrap_or(net_pnl);
+        let drawdown = (peak - net_pnl).max(0) as u64;
+        if drawdown >= self.policy.max_drawdown_lamports {
+            state.tripped_at = Some(now);
+            return Some(DrawdownEvent::Tripped { drawdown_lamports: drawdown });
+        }
+        None
+    }
+
+    /// Manual /resume: lifts a halt early. Returns true if one was active.
+    pub fn resume(&self, net_pnl: i64, now: Instant) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let was_tripped = state.tripped_at.is_some();
+        Self::rebase(&mut state, net_pnl, now);
+        was_tripped
+    }
+
+    fn rebase(state: &mut State, net_pnl: i64, now: Instant) {
+        state.tripped_at = None;
+        state.samples.clear();
+        state.samples.push_back((now, net_pnl));
+    }
+
+    /// Samples `metrics` every `poll`, pausing and resuming trading as the breaker moves
+    pub async fn run(self: Arc<Self>, metrics: Arc<BotMetrics>, alerts: Arc<AlertManager>, poll: Duration) {
+        let mut interval = tokio::time::interval(poll);
+        loop {
+            interval.tick().await;
+            match self.observe(metrics.net_pnl_lamports(), Instant::now()) {
+                Some(DrawdownEvent::Tripped { drawdown_lamports }) => {
+                    metrics.is_paused.store(true, Ordering::Relaxed);
+                    crate::telemetry::DRAWDOWN_BREAKER_TRIPS.inc();
+                    crate::telemetry::DRAWDOWN_BREAKER_HALTED.set(1);
+                    let resume = match self.policy.cooldown.as_secs() {
+                        0 => "Send /resume to restart trading.".to_string(),
+                        secs => format!("Trading resumes automatically in {}m, or on /resume.", secs.div_ceil(60)),
+                    };
+                    tracing::error!("🧯 DRAWDOWN BREAKER: realized PnL fell {:.6} SOL within {}m. Trading halted.",
+                        drawdown_lamports as f64 / 1e9, self.policy.window.as_secs() / 60);
+                    alerts.send_alert(
+                        AlertSeverity::Critical,
+                        "DRAWDOWN BREAKER TRIPPED",
+                        &format!("Realized PnL fell {:.6} SOL within {} minutes. {}",
+                            drawdown_lamports as f64 / 1e9, self.policy.window.as_secs() / 60, resume),
+                        vec![
+                            Field { name: "Drawdown".to_string(), value: format!("{:.6} SOL", drawdown_lamports as f64 / 1e9), inline: true },
+                            Field { name: "Limit".to_string(), value: format!("{:.6} SOL", self.policy.max_drawdown_lamports as f64 / 1e9), inline: true },
+                        ],
+                    ).await;
+                }
+                Some(DrawdownEvent::Resumed) => {
+                    metrics.is_paused.store(false, Ordering::Relaxed);
+                    crate::telemetry::DRAWDOWN_BREAKER_HALTED.set(0);
+                    tracing::info!("▶️ Drawdown cooldown over. Trading resumed.");
+                    alerts.send_alert(AlertSeverity::Success, "Drawdown Breaker", "▶️ Cooldown over. Trading RESUMED.", vec![]).await;
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trips_on_window_drawdown_and_resumes() {
+        let breaker = DrawdownBreaker::new(DrawdownPolicy {
+            max_drawdown_lamports: 50_000_000,
+            window: Duration::from_secs(1_800),
+            cooldown: Duration::from_secs(600),
+        });
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        // Up 0.03 SOL, then back to -0.01: 0.04 off the peak
+        assert_eq!(breaker.observe(30_000_000, at(0)), None);
+        assert_eq!(breaker.observe(-10_000_000, at(600)), None);
+        assert_eq!(breaker.window_drawdown(), 40_000_000);
+
+        // The peak has left the window, so a further 0.03 SOL loss isn't enough
+        assert_eq!(breaker.observe(-10_000_000, at(1_900)), None);
+        assert_eq!(breaker.observe(-40_000_000, at(2_000)), None);
+
+        assert_eq!(breaker.observe(-60_000_000, at(2_100)), Some(DrawdownEvent::Tripped { drawdown_lamports: 50_000_000 }));
+        assert!(breaker.is_tripped());
+        assert_eq!(breaker.resume_in(at(2_400)), Some(Duration::from_secs(300)));
+        assert_eq!(breaker.observe(-60_000_000, at(2_400)), None);
+        assert_eq!(breaker.observe(-60_000_000, at(2_700)), Some(DrawdownEvent::Resumed));
+        assert!(!breaker.is_tripped());
+
+        // Losses from before the halt don't count again
+        assert_eq!(breaker.observe(-90_000_000, at(2_800)), None);
+        assert_eq!(breaker.observe(-110_000_000, at(2_900)), Some(DrawdownEvent::Tripped { drawdown_lamports: 50_000_000 }));
+        assert!(breaker.resume(-110_000_000, at(2_950)));
+        assert!(!breaker.resume(-110_000_000, at(2_960)));
+    }
+}
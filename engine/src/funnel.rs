@@ -0,0 +1,175 @@
+/// Rejection Funnel
+///
+/// Counts how far each pool update got: received by a worker → searched →
+/// cycles found → passed sanity → passed AI → passed safety → simulated →
+/// submitted → landed. Every outcome is booked at all the stages it reached,
+/// so each count is the number that made it at least that far and the
+/// drop-off between two neighbours is where the rest were lost. Sanity covers
+/// the cheap gates before the model (SOL pricing, profit sanity, min profit,
+/// blacklists, exposure, oracle), AI covers the model, EV and DNA gates, and safety the
+/// rug, transfer-fee and quote cross-checks. Sweep and slot re-price hits have no update
+/// behind them and enter at "cycles found".
+use std::sync::atomic::{AtomicU64, Ordering};
+use strategy::analytics::decisions::EventOutcome;
+use strategy::analytics::rejections::RejectionReason;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FunnelStage {
+    Received,
+    Searched,
+    Found,
+    Sanity,
+    Ai,
+    Safety,
+    Simulated,
+    Submitted,
+    Landed,
+}
+
+impl FunnelStage {
+    pub const ALL: [FunnelStage; 9] = [
+        FunnelStage::Received,
+        FunnelStage::Searched,
+        FunnelStage::Found,
+        FunnelStage::Sanity,
+        FunnelStage::Ai,
+        FunnelStage::Safety,
+        FunnelStage::Simulated,
+        FunnelStage::Submitted,
+        FunnelStage::Landed,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FunnelStage::Received => "Updates received",
+            FunnelStage::Searched => "Searched",
+            FunnelStage::Found => "Cycles found",
+            FunnelStage::Sanity => "Passed sanity",
+            FunnelStage::Ai => "Passed AI",
+            FunnelStage::Safety => "Passed safety",
+            FunnelStage::Simulated => "Simulated",
+            FunnelStage::Submitted => "Submitted",
+            FunnelStage::Landed => "Landed",
+        }
+    }
+
+    /// Last stage an outcome got through; None when it was turned away before the search
+    pub fn reached_by(outcome: &EventOutcome) -> Option<FunnelStage> {
+        Some(match outcome {
+            EventOutcome::NoOpportunity => FunnelStage::Searched,
+            EventOutcome::Rejected(reason) => match reason {
+                RejectionReason::TradeSizeLimit => return None,
+                RejectionReason::ProfitSanity
+                | RejectionReason::TipExceedsProfit
+                | RejectionReason::MinProfit
+                | RejectionReason::RouteBlacklisted
+                | RejectionReason::SlotConflict
+                | RejectionReason::Unfunded
+                | RejectionReason::FeatureDisabled
+                | RejectionReason::OracleDeviation
+                | RejectionReason::PoolRateLimited
+                | RejectionReason::Exposure
+                | RejectionReason::Unpriced => FunnelStage::Found,
+                RejectionReason::AiConfidence | RejectionReason::ExpectedValue | RejectionReason::Dna => FunnelStage::Sanity,
+                RejectionReason::Safety | RejectionReason::TransferFee | RejectionReason::QuoteMismatch => FunnelStage::Ai,
+                RejectionReason::Simulation => FunnelStage::Safety,
+            },
+            EventOutcome::ExecutionFailed => FunnelStage::Simulated,
+            EventOutcome::Executed(_) => FunnelStage::Submitted,
+        })
+    }
+}
+
+pub struct Funnel {
+    counts: [AtomicU64; 9],
+}
+
+impl Default for Funnel {
+    fn default() -> Self {
+        Self { counts: std::array::from_fn(|_| AtomicU64::new(0)) }
+    }
+}
+
+impl Funnel {
+    fn bump(&self, stage: FunnelStage) {
+        self.counts[stage as usize].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_received(&self) {
+        self.bump(FunnelStage::Received);
+    }
+
+    pub fn record_landed(&self) {
+        self.bump(FunnelStage::Landed);
+    }
+
+    /// Books `outcome` at every stage from `entry` through the last one it reached
+    pub fn record(&self, outcome: &EventOutcome, entry: FunnelStage) {
+        let Some(reached) = FunnelStage::reached_by(outcome) else {
+            return;
+        };
+        for stage in FunnelStage::ALL.into_iter().filter(|s| (entry..=reached).contains(s)) {
+            self.bump(stage);
+        }
+    }
+
+    pub fn snapshot(&self) -> [(FunnelStage, u64); 9] {
+        FunnelStage::ALL.map(|stage| (stage, self.counts[stage as usize].load(Ordering::Relaxed)))
+    }
+}
+
+/// Share of the previous stage lost at this one, in percent
+fn drop_off(previous: u64, count: u64) -> f64 {
+    if previous == 0 {
+        0.0
+    } else {
+        previous.saturating_sub(count) as f64 / previous as f64 * 100.0
+    }
+}
+
+/// Each stage with its drop-off from the one before, first stage bare
+pub fn stages(snapshot: &[(FunnelStage, u64)]) -> Vec<(FunnelStage, u64, Option<f64>)> {
+    snapshot.iter().enumerate()
+        .map(|(i, &(stage, count))| (stage, count, i.checked_sub(1).map(|p| drop_off(snapshot[p].1, count))))
+        .collect()
+}
+
+/// One-line funnel for the periodic log
+pub fn summary_line(snapshot: &[(FunnelStage, u64)]) -> String {
+    stages(snapshot).into_iter()
+        .map(|(stage, count, drop)| match drop {
+            Some(drop) => format!("{} {} (-{:.1}%)", stage.label(), count, drop),
+            None => format!("{} {}", stage.label(), count),
+        })
+        .collect::<Vec<_>>()
+        .join(" → ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outcomes_count_at_every_stage_they_reached() {
+        let funnel = Funnel::default();
+        for _ in 0..10 {
+            funnel.record_received();
+        }
+        for _ in 0..6 {
+            funnel.record(&EventOutcome::NoOpportunity, FunnelStage::Searched);
+        }
+        funnel.record(&EventOutcome::Rejected(RejectionReason::MinProfit), FunnelStage::Searched);
+        funnel.record(&EventOutcome::Rejected(RejectionReason::AiConfidence), FunnelStage::Searched);
+        funnel.record(&EventOutcome::Rejected(RejectionReason::TradeSizeLimit), FunnelStage::Searched);
+        funnel.record(&EventOutcome::ExecutionFailed, FunnelStage::Searched);
+        // A sweep hit has no update behind it
+        funnel.record(&EventOutcome::Rejected(RejectionReason::Simulation), FunnelStage::Found);
+
+        let counts: Vec<u64> = funnel.snapshot().iter().map(|(_, c)| *c).collect();
+        assert_eq!(counts, vec![10, 9, 4, 3, 2, 2, 1, 0, 0]);
+
+        let line = summary_line(&funnel.snapshot());
+        assert!(line.starts_with("Updates received 10 → Searched 9 (-10.0%) → Cycles found 4 (-55.6%)"));
+        assert!(line.ends_with("Landed 0 (-0.0%)"));
+    }
+}
@@ -1,117 +1,219 @@
+/// Yellowstone Geyser Ingestion (INGEST_BACKEND=geyser)
+///
+/// Public WebSocket `accountSubscribe` trails the validator by 200-400ms. This
+/// backend streams the same pool accounts straight from a Geyser plugin over
+/// gRPC and publishes them on the same `broadcast::Sender<MarketUpdate>` as
+/// the WebSocket watcher, so nothing downstream changes. Transactions touching
+/// the pools feed trade flow, slot updates expose gaps in the stream, and a
+/// dropped stream reconnects with backoff.
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use futures_util::{SinkExt, StreamExt};
 use yellowstone_grpc_client::GeyserGrpcClient;
 use yellowstone_grpc_proto::prelude::*;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use solana_sdk::pubkey::Pubkey;
-use std::collections::HashMap;
 use tracing::{info, warn, error};
 use mev_core::MarketUpdate;
+use strategy::analytics::trade_flow::TradeFlowTracker;
+use strategy::order_books::OrderBookCache;
+use crate::scoring::PoolScoringEngine;
+use crate::delisting::{self, DelistReason, PoolDelister};
+use crate::watcher::{decode_pool_account, record_pool_swaps};
+use crate::backrun::BackrunTrigger;
+
+const MAX_BACKOFF_SECS: u64 = 60;
+
+/// Tracks the last slot seen on the stream and reports how many were skipped
+#[derive(Debug, Default)]
+pub struct SlotTracker {
+    last: Option<u64>,
+}
+
+impl SlotTracker {
+    /// Returns the number of slots missed before `slot`. Old or repeated slots are ignored.
+    pub fn observe(&mut self, slot: u64) -> u64 {
+        let missed = match self.last {
+            Some(last) if slot > last + 1 => slot - last - 1,
+            _ => 0,
+        };
+        if self.last.map_or(true, |last| slot > last) {
+            self.last = Some(slot);
+        }
+        missed
+    }
+}
 
-/// Yellowstone gRPC listener for high-speed account updates
 pub struct GeyserListener {
     endpoint: String,
     token: Option<String>,
+    pools: HashSet<Pubkey>,
+    scoring_engine: Arc<PoolScoringEngine>,
+    trade_flow: Option<Arc<TradeFlowTracker>>,
+    order_books: Arc<OrderBookCache>,
+    delister: Arc<PoolDelister>,
+    backrun: Option<Arc<BackrunTrigger>>,
 }
 
 impl GeyserListener {
-    pub fn new(endpoint: String, token: Option<String>) -> Self {
-        Self { endpoint, token }
+    pub fn new(
+        endpoint: String,
+        token: Option<String>,
+        pools: HashSet<Pubkey>,
+        scoring_engine: Arc<PoolScoringEngine>,
+        trade_flow: Option<Arc<TradeFlowTracker>>,
+        order_books: Arc<OrderBookCache>,
+        delister: Arc<PoolDelister>,
+    ) -> Self {
+        Self { endpoint, token, pools, scoring_engine, trade_flow, order_books, delister, backrun: None }
     }
 
-    /// Start listening to account updates via gRPC
-    pub async fn start(
-        &self,
-        pool_addresses: Vec<Pubkey>,
-        tx: mpsc::Sender<MarketUpdate>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        info!("🚀 Starting Yellowstone gRPC listener for {} pools", pool_addresses.len());
-
-        // Connect to gRPC endpoint
-        let mut client = GeyserGrpcClient::connect(
-            self.endpoint.clone(),
-            self.token.clone(),
-            None,
-        )?;
-
-        // Build subscription request
-        let mut accounts_filter = HashMap::new();
-        
-        // Subscribe to all monitored pool addresses
-        for (i, pool_address) in pool_addresses.iter().enumerate() {
-            accounts_filter.insert(
-                format!("pool_{}", i),
-                SubscribeRequestFilterAccounts {
-                    account: vec![pool_address.to_string()],
-                    owner: vec![],
-                    filters: vec![],
-                },
-            );
-        }
+    /// Large swaps seen on the transaction stream queue a back-run search
+    pub fn with_backrun(mut self, backrun: Option<Arc<BackrunTrigger>>) -> Self {
+        self.backrun = backrun;
+        self
+    }
 
-        let request = SubscribeRequest {
-            slots: HashMap::new(),
-            accounts: accounts_filter,
-            transactions: HashMap::new(),
-            blocks: HashMap::new(),
-            blocks_meta: HashMap::new(),
-            entry: HashMap::new(),
-            commitment: Some(CommitmentLevel::Confirmed as i32),
-            accounts_data_slice: vec![],
-            ping: None,
-        };
+    /// Streams until the process exits, reconnecting with exponential backoff.
+    /// The slot tracker survives reconnects, so time spent disconnected shows up as a gap.
+    /// Pools arriving on `unsubscribe_rx` are dropped from the subscription.
+    pub async fn run(mut self, market_tx: broadcast::Sender<MarketUpdate>, mut unsubscribe_rx: mpsc::UnboundedReceiver<Pubkey>) {
+        info!("🚀 Geyser ingestion: {} pools via {}", self.pools.len(), self.endpoint);
+        let mut slots = SlotTracker::default();
+        let mut retry_delay = 1;
+
+        loop {
+            match self.stream(&market_tx, &mut slots, &mut unsubscribe_rx).await {
+                Ok(()) => warn!("⚠️ Geyser stream ended. Reconnecting..."),
+                Err(e) => error!("❌ Geyser stream failed: {}. Retrying in {}s...", e, retry_delay),
+            }
+            mev_core::telemetry::GEYSER_RECONNECTS_TOTAL.inc();
+            tokio::time::sleep(Duration::from_secs(retry_delay)).await;
+            retry_delay = (retry_delay * 2).min(MAX_BACKOFF_SECS);
+        }
+    }
 
-        info!("📡 Subscribing to gRPC account updates...");
+    async fn stream(
+        &mut self,
+        market_tx: &broadcast::Sender<MarketUpdate>,
+        slots: &mut SlotTracker,
+        unsubscribe_rx: &mut mpsc::UnboundedReceiver<Pubkey>,
+    ) -> anyhow::Result<()> {
+        let mut client = GeyserGrpcClient::connect(self.endpoint.clone(), self.token.clone(), None)?;
         let (mut subscribe_tx, mut stream) = client.subscribe().await?;
-        
-        // Send subscription request
-        subscribe_tx.send(request).await?;
-        info!("✅ gRPC subscription established");
-
-        // Process incoming updates
-        while let Some(message) = stream.next().await {
-            match message {
-                Ok(msg) => {
-                    if let Some(update) = msg.update_oneof {
-                        match update {
-                            subscribe_update::UpdateOneof::Account(account_update) => {
-                                self.process_account_update(account_update, &tx).await;
-                            }
-                            subscribe_update::UpdateOneof::Ping(_) => {
-                                // Keep-alive ping, no action needed
-                            }
-                            _ => {
-                                // Ignore other update types (transactions, slots, etc.)
-                            }
-                        }
+        subscribe_tx.send(self.subscribe_request()).await?;
+        info!("✅ Geyser subscription established ({} pools)", self.pools.len());
+
+        loop {
+            let message = tokio::select! {
+                Some(dead_pool) = unsubscribe_rx.recv() => {
+                    // Re-sending the request replaces the stream's filters in place
+                    if self.pools.remove(&dead_pool) {
+                        subscribe_tx.send(self.subscribe_request()).await?;
+                        info!("🔕 Geyser unsubscribed delisted pool {}", dead_pool);
                     }
+                    continue;
                 }
-                Err(e) => {
-                    error!("❌ gRPC stream error: {}", e);
-                    return Err(Box::new(e));
+                message = stream.next() => match message {
+                    Some(message) => message,
+                    None => break,
+                },
+            };
+            let Some(update) = message?.update_oneof else {
+                continue;
+            };
+            match update {
+                subscribe_update::UpdateOneof::Account(account) => self.handle_account(account, market_tx),
+                subscribe_update::UpdateOneof::Transaction(transaction) => self.handle_transaction(transaction),
+                subscribe_update::UpdateOneof::Slot(slot) => {
+                    mev_core::clock::system().observe_slot(slot.slot);
+                    let missed = slots.observe(slot.slot);
+                    if missed > 0 {
+                        warn!("🕳️ Geyser slot gap: {} slot(s) missed before {}", missed, slot.slot);
+                        mev_core::telemetry::GEYSER_SLOT_GAPS_TOTAL.inc_by(missed as f64);
+                    }
                 }
+                _ => {}
             }
         }
-
-        warn!("⚠️ gRPC stream ended");
         Ok(())
     }
 
-    async fn process_account_update(
-        &self,
-        account_update: SubscribeUpdateAccount,
-        tx: &mpsc::Sender<MarketUpdate>,
-    ) {
-        if let Some(account_info) = account_update.account {
-            let pubkey_str = bs58::encode(&account_info.pubkey).into_string();
-            
-            // Parse account data (this will reuse existing Raydium/Orca parsing logic)
-            if let Ok(pubkey) = pubkey_str.parse::<Pubkey>() {
-                // TODO: Parse pool data based on owner (Raydium vs Orca)
-                // For now, log the update
-                info!("📊 gRPC update for pool: {}", pubkey);
-                
-                // This would integrate with existing pool parsing logic
-                // Example: parse AmmInfo, Whirlpool, etc.
-            }
+    fn subscribe_request(&self) -> SubscribeRequest {
+        let pools: Vec<String> = self.pools.iter().map(|p| p.to_string()).collect();
+
+        let mut accounts = HashMap::new();
+        accounts.insert("pools".to_string(), SubscribeRequestFilterAccounts {
+            account: pools.clone(),
+            ..Default::default()
+        });
+
+        let mut transactions = HashMap::new();
+        if self.trade_flow.is_some() {
+            transactions.insert("pool_swaps".to_string(), SubscribeRequestFilterTransactions {
+                vote: Some(false),
+                failed: Some(false),
+                account_include: pools,
+                ..Default::default()
+            });
+        }
+
+        let mut slots = HashMap::new();
+        slots.insert("slots".to_string(), SubscribeRequestFilterSlots::default());
+
+        SubscribeRequest {
+            accounts,
+            transactions,
+            slots,
+            commitment: Some(CommitmentLevel::Processed as i32),
+            ..Default::default()
+        }
+    }
+
+    fn handle_account(&self, update: SubscribeUpdateAccount, market_tx: &broadcast::Sender<MarketUpdate>) {
+        let Some(account) = update.account else {
+            return;
+        };
+        let Ok(pool_pub) = Pubkey::try_from(account.pubkey.as_slice()) else {
+            return;
+        };
+        if delisting::is_closed(account.lamports, account.data.len()) {
+            self.delister.delist(pool_pub, DelistReason::Closed);
+            return;
+        }
+        self.scoring_engine.update_activity(pool_pub);
+
+        let ts = mev_core::clock::system().unix_secs() as i64;
+        if let Some(market_update) = decode_pool_account(pool_pub, &account.data, ts, &self.order_books) {
+            let _ = market_tx.send(market_update);
+        }
+    }
+
+    /// ray_log carries no pool address; attribute only transactions touching exactly one watched pool
+    fn handle_transaction(&self, update: SubscribeUpdateTransaction) {
+        let (Some(trade_flow), Some(info)) = (&self.trade_flow, update.transaction) else {
+            return;
+        };
+        let Some(meta) = info.meta else {
+            return;
+        };
+        let account_keys = info.transaction
+            .and_then(|tx| tx.message)
+            .map(|msg| msg.account_keys)
+            .unwrap_or_default();
+
+        let mut touched = account_keys.iter()
+            .filter_map(|k| Pubkey::try_from(k.as_slice()).ok())
+            .filter(|k| self.pools.contains(k));
+        if let (Some(pool), None) = (touched.next(), touched.next()) {
+            record_pool_swaps(
+                pool,
+                meta.log_messages.iter().map(String::as_str),
+                trade_flow,
+                &self.scoring_engine,
+                self.backrun.as_deref(),
+            );
         }
     }
 }
@@ -119,13 +221,92 @@ impl GeyserListener {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use base64::{Engine as _, engine::general_purpose};
+    use strategy::analytics::volatility::VolatilityTracker;
+    use crate::alerts::AlertManager;
 
-    #[test]
-    fn test_geyser_listener_creation() {
-        let listener = GeyserListener::new(
+    fn geyser(pools: HashSet<Pubkey>, trade_flow: Option<Arc<TradeFlowTracker>>) -> GeyserListener {
+        let scoring = Arc::new(PoolScoringEngine::new(None));
+        let engine = Arc::new(strategy::StrategyEngine::new(None, None, None, None, None, None, None));
+        let alerts = Arc::new(AlertManager::new(None, None, None, Arc::new(executor::http::HttpClient::default())));
+        let delister = Arc::new(PoolDelister::new(engine, Arc::clone(&scoring), alerts, mpsc::unbounded_channel().0));
+        GeyserListener::new(
             "http://localhost:10000".to_string(),
             Some("test-token".to_string()),
-        );
+            pools,
+            scoring,
+            trade_flow,
+            Arc::new(OrderBookCache::new()),
+            delister,
+        )
+    }
+
+    #[test]
+    fn test_geyser_listener_creation() {
+        let pool = Pubkey::new_unique();
+        let listener = geyser(HashSet::from([pool]), None);
         assert_eq!(listener.endpoint, "http://localhost:10000");
+        assert_eq!(listener.token.as_deref(), Some("test-token"));
+
+        // Pool accounts and slots are always streamed; transactions only when trade flow wants them
+        let request = listener.subscribe_request();
+        assert_eq!(request.accounts["pools"].account, vec![pool.to_string()]);
+        assert!(request.slots.contains_key("slots") && request.transactions.is_empty());
+        assert_eq!(request.commitment, Some(CommitmentLevel::Processed as i32));
+
+        let flow = Arc::new(TradeFlowTracker::new(Arc::new(VolatilityTracker::new())));
+        let request = geyser(HashSet::from([pool]), Some(flow)).subscribe_request();
+        assert_eq!(request.transactions["pool_swaps"].account_include, vec![pool.to_string()]);
+    }
+
+    #[test]
+    fn test_swap_logs_route_to_the_one_watched_pool_they_touch() {
+        let (pool, other_pool) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let flow = Arc::new(TradeFlowTracker::new(Arc::new(VolatilityTracker::new())));
+        let listener = geyser(HashSet::from([pool, other_pool]), Some(Arc::clone(&flow)));
+
+        let mut swap = vec![3u8]; // SwapBaseIn
+        for field in [1_000u64, 900, 1, 0, 50_000, 80_000, 620] {
+            swap.extend_from_slice(&field.to_le_bytes());
+        }
+        let logs = vec![
+            "Program log: Instruction: SwapBaseIn".to_string(),
+            format!("Program log: ray_log: {}", general_purpose::STANDARD.encode(&swap)),
+        ];
+        let transaction = |keys: &[Pubkey]| SubscribeUpdateTransaction {
+            transaction: Some(SubscribeUpdateTransactionInfo {
+                transaction: Some(Transaction {
+                    message: Some(Message {
+                        account_keys: keys.iter().map(|k| k.to_bytes().to_vec()).collect(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                meta: Some(TransactionStatusMeta { log_messages: logs.clone(), ..Default::default() }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        listener.handle_transaction(transaction(&[Pubkey::new_unique(), pool]));
+        assert_eq!(flow.snapshot(pool).trades, 1);
+        assert_eq!(flow.snapshot(other_pool).trades, 0);
+
+        // A route through two watched pools can't be attributed; one through none isn't ours
+        listener.handle_transaction(transaction(&[pool, other_pool]));
+        listener.handle_transaction(transaction(&[Pubkey::new_unique()]));
+        assert_eq!((flow.snapshot(pool).trades, flow.snapshot(other_pool).trades), (1, 0));
+    }
+
+    #[test]
+    fn test_slot_tracker_reports_gaps() {
+        let mut slots = SlotTracker::default();
+        assert_eq!(slots.observe(100), 0);
+        assert_eq!(slots.observe(101), 0);
+        assert_eq!(slots.observe(105), 3);
+        // Late or duplicate slots neither count nor move the cursor back
+        assert_eq!(slots.observe(103), 0);
+        assert_eq!(slots.observe(105), 0);
+        assert_eq!(slots.observe(106), 0);
     }
 }
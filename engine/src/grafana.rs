@@ -0,0 +1,144 @@
+/// Grafana Dashboard Generator
+///
+/// `engine grafana [file]` writes a dashboard for the metrics this build
+/// exports, under the configured `STRATEGY_ID` namespace: one panel per metric,
+/// titled with its help text. Counters are graphed as per-second rates,
+/// gauges as values, histograms as p50/p95; labelled metrics get one series
+/// per label combination. Panels use Grafana's default datasource, so the
+/// file works both through the import dialog and dropped into
+/// `monitoring/grafana/dashboards/` for provisioning.
+use prometheus::core::Collector;
+use prometheus::proto::MetricType;
+use serde_json::{json, Value};
+
+const PANEL_WIDTH: u64 = 12;
+const PANEL_HEIGHT: u64 = 8;
+const RATE_WINDOW: &str = "5m";
+
+/// One exported metric, as the dashboard needs it
+struct MetricSpec {
+    name: String,
+    help: String,
+    kind: MetricType,
+    labels: Vec<String>,
+}
+
+fn specs(namespace: &str, collectors: &[Box<dyn Collector>]) -> Vec<MetricSpec> {
+    let mut specs = Vec::new();
+    for collector in collectors {
+        let families = collector.collect();
+        for desc in collector.desc() {
+            let Some(family) = families.iter().find(|f| f.get_name() == desc.fq_name) else {
+                continue;
+            };
+            specs.push(MetricSpec {
+                name: format!("{}_{}", namespace, desc.fq_name),
+                help: desc.help.clone(),
+                kind: family.get_field_type(),
+                labels: desc.variable_labels.clone(),
+            });
+        }
+    }
+    specs
+}
+
+/// `(expr, legend)` pairs graphing `spec`
+fn targets(spec: &MetricSpec) -> Vec<(String, String)> {
+    let by = |extra: &[&str]| {
+        let labels: Vec<&str> = extra.iter().copied().chain(spec.labels.iter().map(String::as_str)).collect();
+        if labels.is_empty() { String::new() } else { format!(" by ({})", labels.join(", ")) }
+    };
+    let legend = |prefix: &str| {
+        let labels: Vec<String> = spec.labels.iter().map(|l| format!("{{{{{}}}}}", l)).collect();
+        match (prefix.is_empty(), labels.is_empty()) {
+            (_, true) => if prefix.is_empty() { spec.name.clone() } else { prefix.to_string() },
+            (true, false) => labels.join(" "),
+            (false, false) => format!("{} {}", prefix, labels.join(" ")),
+        }
+    };
+    match spec.kind {
+        MetricType::COUNTER => vec![(
+            format!("sum{}(rate({}[{}]))", by(&[]), spec.name, RATE_WINDOW),
+            legend(""),
+        )],
+        MetricType::HISTOGRAM => ["0.5", "0.95"].iter().map(|q| (
+            format!("histogram_quantile({}, sum{}(rate({}_bucket[{}])))", q, by(&["le"]), spec.name, RATE_WINDOW),
+            legend(if *q == "0.5" { "p50" } else { "p95" }),
+        )).collect(),
+        _ => vec![(
+            if spec.labels.is_empty() { spec.name.clone() } else { format!("sum{}({})", by(&[]), spec.name) },
+            legend(""),
+        )],
+    }
+}
+
+/// Dashboard JSON for `collectors` exported under `namespace`
+pub fn dashboard(strategy_id: &str, namespace: &str, collectors: &[Box<dyn Collector>]) -> Value {
+    let panels: Vec<Value> = specs(namespace, collectors).iter().enumerate().map(|(i, spec)| {
+        let i = i as u64;
+        let unit = match spec.kind {
+            MetricType::COUNTER => "ops",
+            _ => "short",
+        };
+        json!({
+            "id": i + 1,
+            "title": spec.help,
+            "description": spec.name,
+            "type": "timeseries",
+            "fieldConfig": { "defaults": { "unit": unit }, "overrides": [] },
+            "targets": targets(spec).into_iter().enumerate().map(|(t, (expr, legend))| json!({
+                "refId": ((b'A' + t as u8) as char).to_string(),
+                "expr": expr,
+                "legendFormat": legend,
+            })).collect::<Vec<_>>(),
+            "gridPos": {
+                "h": PANEL_HEIGHT,
+                "w": PANEL_WIDTH,
+                "x": (i % 2) * PANEL_WIDTH,
+                "y": (i / 2) * PANEL_HEIGHT,
+            },
+        })
+    }).collect();
+
+    json!({
+        "uid": format!("mev-{}", namespace.replace('_', "-")),
+        "title": format!("Solana MEV Bot - {}", strategy_id),
+        "tags": ["solana", "mev", "arbitrage", namespace],
+        "timezone": "browser",
+        "schemaVersion": 39,
+        "refresh": "30s",
+        "time": { "from": "now-6h", "to": "now" },
+        "panels": panels,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::{Counter, CounterVec, HistogramOpts, HistogramVec, IntGauge, Opts};
+
+    #[test]
+    fn test_panels_follow_metric_kind_and_namespace() {
+        assert_eq!(mev_core::telemetry::metric_namespace("Cyclic-Arb v2"), "cyclic_arb_v2");
+        assert_eq!(mev_core::telemetry::metric_namespace("2nd"), "s_2nd");
+
+        let collectors: Vec<Box<dyn Collector>> = vec![
+            Box::new(Counter::new("trades_total", "Trades").unwrap()),
+            Box::new(CounterVec::new(Opts::new("sends_total", "Sends"), &["sender", "outcome"]).unwrap()),
+            Box::new(IntGauge::new("queue_depth", "Queue").unwrap()),
+            Box::new(HistogramVec::new(HistogramOpts::new("latency_ms", "Latency"), &["stage"]).unwrap()),
+        ];
+        let dashboard = dashboard("cyclic_arb", "cyclic_arb", &collectors);
+        let panels = dashboard["panels"].as_array().unwrap();
+        assert_eq!(panels.len(), 4);
+
+        let expr = |panel: usize, target: usize| panels[panel]["targets"][target]["expr"].as_str().unwrap().to_string();
+        assert_eq!(expr(0, 0), "sum(rate(cyclic_arb_trades_total[5m]))");
+        // A labelled vec with no children yet still gets its panel
+        assert_eq!(expr(1, 0), "sum by (sender, outcome)(rate(cyclic_arb_sends_total[5m]))");
+        assert_eq!(panels[1]["targets"][0]["legendFormat"], "{{sender}} {{outcome}}");
+        assert_eq!(expr(2, 0), "cyclic_arb_queue_depth");
+        assert_eq!(expr(3, 1), "histogram_quantile(0.95, sum by (le, stage)(rate(cyclic_arb_latency_ms_bucket[5m])))");
+        assert_eq!(panels[3]["gridPos"]["x"], PANEL_WIDTH);
+    }
+}
@@ -0,0 +1,182 @@
+/// Hop-Level Execution Analytics
+///
+/// A landed route only tells us the bundle went through. To see which venue's
+/// math is off we re-read the confirmed transaction, pair each swap instruction
+/// with its hop in order, and take the hop's real output from the SPL token
+/// transfers its CPI made out of the pool (any transfer not signed by us).
+/// Hops settled without a token transfer, like Pump.fun sells paid in raw
+/// lamports, are skipped.
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{EncodedTransaction, UiInstruction, UiMessage, UiParsedInstruction, UiTransactionEncoding};
+use mev_core::{ArbitrageOpportunity, SwapStep};
+use strategy::analytics::quote_errors::QuoteErrorTracker;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TokenTransfer {
+    pub amount: u64,
+    pub authority: Pubkey,
+}
+
+pub struct HopAnalyzer {
+    rpc: RpcClient,
+    payer: Pubkey,
+    tracker: Arc<QuoteErrorTracker>,
+}
+
+impl HopAnalyzer {
+    pub fn new(rpc_url: &str, payer: Pubkey, tracker: Arc<QuoteErrorTracker>) -> Self {
+        Self { rpc: RpcClient::new(rpc_url.to_string()), payer, tracker }
+    }
+
+    /// Compares the landed transaction with the quoted route in the background
+    pub fn analyze(self: &Arc<Self>, opportunity: ArbitrageOpportunity, signature: String) {
+        let analyzer = Arc::clone(self);
+        tokio::spawn(async move {
+            if let Err(e) = analyzer.analyze_landed(&opportunity, &signature).await {
+                tracing::debug!("🔬 Hop analysis skipped for {}: {}", signature, e);
+            }
+        });
+    }
+
+    async fn analyze_landed(&self, opportunity: &ArbitrageOpportunity, signature: &str) -> anyhow::Result<()> {
+        let tx = self.rpc.get_transaction_with_config(
+            &Signature::from_str(signature)?,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::JsonParsed),
+                commitment: Some(solana_sdk::commitment_config::CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        ).await?;
+
+        let EncodedTransaction::Json(ui_tx) = tx.transaction.transaction else {
+            return Err(anyhow::anyhow!("Transaction not JSON-encoded"));
+        };
+        let UiMessage::Parsed(message) = ui_tx.message else {
+            return Err(anyhow::anyhow!("Transaction message not parsed"));
+        };
+        let meta = tx.transaction.meta.ok_or_else(|| anyhow::anyhow!("Transaction has no meta"))?;
+        let OptionSerializer::Some(inner_groups) = meta.inner_instructions else {
+            return Err(anyhow::anyhow!("Transaction has no inner instructions"));
+        };
+
+        let outer: Vec<Option<Pubkey>> = message.instructions.iter().map(program_of).collect();
+        let inner: HashMap<usize, Vec<TokenTransfer>> = inner_groups
+            .iter()
+            .map(|group| (group.index as usize, group.instructions.iter().filter_map(token_transfer).collect()))
+            .collect();
+
+        for (step, actual) in opportunity.steps.iter().zip(hop_outputs(&outer, &inner, &self.payer, &opportunity.steps)) {
+            let Some(actual) = actual else { continue };
+            if let Some(error_bps) = self.tracker.record(step.program_id, step.expected_output, actual) {
+                mev_core::telemetry::HOP_QUOTE_ERROR_BPS
+                    .with_label_values(&[crate::tui::dex_name(&step.program_id)])
+                    .observe(error_bps);
+                tracing::debug!("🔬 {} hop {}: quoted {} got {} ({:+.1} bps)",
+                    crate::tui::dex_name(&step.program_id), step.pool, step.expected_output, actual, error_bps);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Actual output per hop. Swap instructions are matched to steps in order by program id;
+/// a hop's output is what its CPI transferred under any authority but ours.
+pub(crate) fn hop_outputs(
+    outer: &[Option<Pubkey>],
+    inner: &HashMap<usize, Vec<TokenTransfer>>,
+    payer: &Pubkey,
+    steps: &[SwapStep],
+) -> Vec<Option<u64>> {
+    let mut outputs = vec![None; steps.len()];
+    let mut next_step = 0;
+    for (index, program) in outer.iter().enumerate() {
+        let Some(step) = steps.get(next_step) else { break };
+        if *program != Some(step.program_id) {
+            continue;
+        }
+        let received: u64 = inner.get(&index)
+            .map(|transfers| transfers.iter().filter(|t| t.authority != *payer).map(|t| t.amount).sum())
+            .unwrap_or(0);
+        if received > 0 {
+            outputs[next_step] = Some(received);
+        }
+        next_step += 1;
+    }
+    outputs
+}
+
+fn program_of(ix: &UiInstruction) -> Option<Pubkey> {
+    let program_id = match ix {
+        UiInstruction::Parsed(UiParsedInstruction::Parsed(parsed)) => &parsed.program_id,
+        UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(decoded)) => &decoded.program_id,
+        UiInstruction::Compiled(_) => return None,
+    };
+    Pubkey::from_str(program_id).ok()
+}
+
+/// SPL token `transfer` / `transferChecked` as rendered by jsonParsed
+fn token_transfer(ix: &UiInstruction) -> Option<TokenTransfer> {
+    let UiInstruction::Parsed(UiParsedInstruction::Parsed(parsed)) = ix else {
+        return None;
+    };
+    if !parsed.program.starts_with("spl-token") {
+        return None;
+    }
+    parse_transfer(&parsed.parsed)
+}
+
+pub(crate) fn parse_transfer(parsed: &serde_json::Value) -> Option<TokenTransfer> {
+    let info = &parsed["info"];
+    let amount = match parsed["type"].as_str()? {
+        "transfer" => info["amount"].as_str()?,
+        "transferChecked" => info["tokenAmount"]["amount"].as_str()?,
+        _ => return None,
+    };
+    let authority = info["authority"].as_str().or_else(|| info["multisigAuthority"].as_str())?;
+    Some(TokenTransfer {
+        amount: amount.parse().ok()?,
+        authority: Pubkey::from_str(authority).ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_hop_outputs_follow_swap_order() {
+        let payer = Pubkey::new_unique();
+        let (ray, orca, pool_auth) = (mev_core::constants::RAYDIUM_V4_PROGRAM, mev_core::constants::ORCA_WHIRLPOOL_PROGRAM, Pubkey::new_unique());
+        let step = |program_id| SwapStep { pool: Pubkey::new_unique(), program_id, input_mint: Pubkey::new_unique(), output_mint: Pubkey::new_unique(), expected_output: 0 };
+        let steps = [step(ray), step(orca), step(ray)];
+
+        let sent = parse_transfer(&json!({
+            "type": "transfer",
+            "info": { "amount": "1000", "authority": payer.to_string(), "source": "a", "destination": "b" }
+        })).unwrap();
+        let received = |amount: u64| parse_transfer(&json!({
+            "type": "transferChecked",
+            "info": { "tokenAmount": { "amount": amount.to_string() }, "authority": pool_auth.to_string() }
+        })).unwrap();
+
+        // [compute budget, ray swap, orca swap, tip, ray swap]
+        let outer = [None, Some(ray), Some(orca), Some(solana_sdk::system_program::id()), Some(ray)];
+        let inner = HashMap::from([
+            (1, vec![sent.clone(), received(2_000)]),
+            (2, vec![sent.clone(), received(3_000)]),
+            // Last hop paid out without a token transfer
+            (4, vec![sent]),
+        ]);
+
+        assert_eq!(hop_outputs(&outer, &inner, &payer, &steps), vec![Some(2_000), Some(3_000), None]);
+        assert!(parse_transfer(&json!({ "type": "approve", "info": {} })).is_none());
+    }
+}
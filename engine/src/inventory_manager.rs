@@ -0,0 +1,219 @@
+/// Inventory Manager
+///
+/// Partial executions and pump.fun buys leave the wallet holding tokens no
+/// strategy is working with. Every `INVENTORY_POLL_SECS` the manager reads all
+/// of the payer's token accounts, values them in SOL at graph spot prices and
+/// publishes the snapshot for /status and the TUI. With sweeping on, each
+/// holding outside `INVENTORY_KEEP_MINTS` worth at least
+/// `INVENTORY_SWEEP_MIN_LAMPORTS` is sold back to SOL over the cheapest route
+/// in the graph: direct, or through USDC when that pays more. Sweeps wait for
+/// a poll with no bundle in flight and trading not paused, so they never race
+/// a live route for the same tokens. WSOL is left to the unwrap step.
+use std::str::FromStr;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::constants::{SOL_MINT, USDC_MINT};
+use mev_core::{ArbitrageOpportunity, SwapStep};
+use strategy::analytics::in_flight::InFlightBook;
+use strategy::ports::ExecutionPort;
+use strategy::StrategyEngine;
+use crate::capital_at_risk::{InventoryValuation, TokenHolding};
+use crate::metrics::BotMetrics;
+use crate::token_metadata::TokenMetadata;
+use crate::wallet_manager::WalletManager;
+
+#[derive(Debug, Clone)]
+pub struct SweepPolicy {
+    /// Off: holdings are tracked and shown, never sold
+    pub enabled: bool,
+    pub min_value_lamports: u64,
+    pub keep_mints: Vec<Pubkey>,
+    pub tip_lamports: u64,
+    pub max_slippage_bps: u16,
+}
+
+/// Comma-separated mints, e.g. `INVENTORY_KEEP_MINTS`
+pub fn parse_mints(list: &str) -> Result<Vec<Pubkey>, String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(|m| Pubkey::from_str(m).map_err(|e| format!("invalid mint '{}': {}", m, e)))
+        .collect()
+}
+
+/// Holdings worth sweeping, most valuable first. Unpriced holdings have no route to SOL and are left.
+pub fn sweep_candidates(tokens: &[(Pubkey, u64, Option<u64>)], policy: &SweepPolicy) -> Vec<(Pubkey, u64)> {
+    let mut candidates: Vec<(Pubkey, u64, u64)> = tokens.iter()
+        .filter(|(mint, amount, _)| *mint != SOL_MINT && *amount > 0 && !policy.keep_mints.contains(mint))
+        .filter_map(|(mint, amount, value)| value.filter(|v| *v >= policy.min_value_lamports).map(|v| (*mint, *amount, v)))
+        .collect();
+    candidates.sort_by(|a, b| b.2.cmp(&a.2));
+    candidates.into_iter().map(|(mint, amount, _)| (mint, amount)).collect()
+}
+
+pub struct InventoryManager {
+    wallet: Arc<WalletManager>,
+    engine: Arc<StrategyEngine>,
+    executor: Arc<dyn ExecutionPort>,
+    in_flight: Arc<InFlightBook>,
+    metrics: Arc<BotMetrics>,
+    metadata: Arc<TokenMetadata>,
+    policy: SweepPolicy,
+    latest: Mutex<Option<InventoryValuation>>,
+}
+
+impl InventoryManager {
+    pub fn new(
+        wallet: Arc<WalletManager>,
+        engine: Arc<StrategyEngine>,
+        executor: Arc<dyn ExecutionPort>,
+        in_flight: Arc<InFlightBook>,
+        metrics: Arc<BotMetrics>,
+        metadata: Arc<TokenMetadata>,
+        policy: SweepPolicy,
+    ) -> Self {
+        Self { wallet, engine, executor, in_flight, metrics, metadata, policy, latest: Mutex::new(None) }
+    }
+
+    /// Last poll's holdings; None until the first one succeeds
+    pub fn holdings(&self) -> Option<InventoryValuation> {
+        self.latest.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Polls until the process exits
+    pub async fn run(self: Arc<Self>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.poll().await {
+                tracing::warn!("🧺 Inventory poll failed: {}", e);
+            }
+        }
+    }
+
+    async fn poll(&self) -> anyhow::Result<()> {
+        // The executor's signer: follows a wallet rotation
+        let owner = *self.executor.pubkey();
+        let sol_lamports = self.wallet.get_sol_balance(&owner).await?;
+        let holdings = self.wallet.get_token_holdings(&owner).await?;
+        let mints: Vec<Pubkey> = holdings.iter().map(|(mint, _)| *mint).collect();
+        if let Err(e) = self.metadata.resolve(&mints).await {
+            tracing::debug!("🏷️ Token decimals lookup failed: {}", e);
+        }
+        let priced: Vec<(Pubkey, u64, Option<u64>)> = holdings
+            .into_iter()
+            .map(|(mint, amount)| {
+                let value = if mint == SOL_MINT {
+                    Some(amount)
+                } else {
+                    self.engine.spot_price(&mint, &SOL_MINT).map(|rate| (amount as f64 * rate) as u64)
+                };
+                (mint, amount, value)
+            })
+            .collect();
+
+        let token_value: u64 = priced.iter().filter_map(|(_, _, v)| *v).sum();
+        mev_core::telemetry::INVENTORY_TOKEN_VALUE_LAMPORTS.set(token_value.min(i64::MAX as u64) as i64);
+        let tokens = priced.iter()
+            .map(|(mint, raw_amount, value_lamports)| TokenHolding::new(mint, *raw_amount, *value_lamports, &self.metadata))
+            .collect();
+        *self.latest.lock().unwrap_or_else(|e| e.into_inner()) = Some(InventoryValuation {
+            sol_lamports,
+            tokens,
+            total_value_lamports: sol_lamports + token_value,
+        });
+
+        if !self.policy.enabled {
+            return Ok(());
+        }
+        let candidates = sweep_candidates(&priced, &self.policy);
+        if candidates.is_empty() {
+            return Ok(());
+        }
+        let (bundles, _) = self.in_flight.summary();
+        if bundles > 0 || self.metrics.is_paused.load(Ordering::Relaxed) {
+            tracing::debug!("🧺 {} holdings to sweep; waiting for an idle, unpaused poll", candidates.len());
+            return Ok(());
+        }
+        for (mint, amount) in candidates {
+            let outcome = self.sweep(&mint, amount).await;
+            mev_core::telemetry::INVENTORY_SWEEPS.with_label_values(&[outcome]).inc();
+        }
+        Ok(())
+    }
+
+    async fn sweep(&self, mint: &Pubkey, amount: u64) -> &'static str {
+        let Some(steps) = cheapest_route(&self.engine, mint, amount) else {
+            tracing::warn!("🧺 No route sells {} to SOL within the impact cap", self.metadata.format(mint, amount));
+            return "unrouted";
+        };
+        let expected = steps.last().map_or(0, |s| s.expected_output);
+        let opportunity = ArbitrageOpportunity {
+            steps: steps.into_iter().collect(),
+            input_amount: amount,
+            timestamp: mev_core::clock::system().unix_secs(),
+            ..Default::default()
+        };
+        let hops = opportunity.steps.len();
+        match self.executor.build_and_send_bundle(opportunity, solana_sdk::hash::Hash::default(), self.policy.tip_lamports, self.policy.max_slippage_bps).await {
+            Ok(signature) => {
+                tracing::info!("🧺 Swept {} for ~{} over {} hop(s): {}",
+                    self.metadata.format(mint, amount), self.metadata.format(&SOL_MINT, expected), hops, signature);
+                "sold"
+            }
+            Err(e) => {
+                tracing::warn!("🧺 Sweep of {} failed: {}", self.metadata.format(mint, amount), e);
+                "failed"
+            }
+        }
+    }
+}
+
+/// Direct to SOL or through USDC, whichever pays more SOL
+fn cheapest_route(engine: &StrategyEngine, mint: &Pubkey, amount: u64) -> Option<Vec<SwapStep>> {
+    let direct = engine.best_hop(mint, &SOL_MINT, amount).map(|hop| vec![hop]);
+    let via_usdc = (*mint != USDC_MINT)
+        .then(|| engine.best_hop(mint, &USDC_MINT, amount))
+        .flatten()
+        .and_then(|first| {
+            let second = engine.best_hop(&USDC_MINT, &SOL_MINT, first.expected_output)?;
+            Some(vec![first, second])
+        });
+    let output = |route: &Vec<SwapStep>| route.last().map_or(0, |s| s.expected_output);
+    match (direct, via_usdc) {
+        (Some(d), Some(u)) => Some(if output(&u) > output(&d) { u } else { d }),
+        (d, u) => d.or(u),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweeps_priced_holdings_outside_the_keep_list() {
+        let (bonk, wif, kept, unpriced) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let policy = SweepPolicy {
+            enabled: true,
+            min_value_lamports: 1_000_000,
+            keep_mints: vec![kept],
+            tip_lamports: 10_000,
+            max_slippage_bps: 100,
+        };
+        let tokens = [
+            (bonk, 500, Some(2_000_000)),
+            (wif, 10, Some(9_000_000)),
+            (kept, 1, Some(50_000_000)),
+            (unpriced, 1_000, None),
+            (Pubkey::new_unique(), 3, Some(999_999)),
+            (SOL_MINT, 5_000_000, Some(5_000_000)),
+        ];
+        assert_eq!(sweep_candidates(&tokens, &policy), vec![(wif, 10), (bonk, 500)]);
+
+        assert_eq!(parse_mints(&format!(" {}, ,{}", bonk, wif)).unwrap(), vec![bonk, wif]);
+        assert!(parse_mints("not-a-mint").is_err());
+    }
+}
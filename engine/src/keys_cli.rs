@@ -0,0 +1,220 @@
+/// `engine keys <pool>`: swap-key resolution for one pool
+///
+/// Runs the same `PoolKeyFetcher` path the executors use and prints every
+/// account it derives, each checked against the program that should own it.
+/// A wrong vault, a tick array that was never initialized or a market owned by
+/// the wrong program shows up here instead of as a failed bundle. User-side
+/// accounts (owner, ATAs) are filled in at execution time and are not listed.
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::constants::*;
+use crate::pool_fetcher::PoolKeyFetcher;
+
+/// What a derived account should look like on chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    OwnedBy(Pubkey),
+    /// Mint or token account under either token program
+    Token,
+    /// A deployed program
+    Executable,
+    /// PDA signer or optional account; any state is fine
+    Any,
+}
+
+pub struct ResolvedAccount {
+    pub name: &'static str,
+    pub address: Pubkey,
+    pub expected: Expected,
+}
+
+fn acc(name: &'static str, address: Pubkey, expected: Expected) -> ResolvedAccount {
+    ResolvedAccount { name, address, expected }
+}
+
+/// Checks one fetched account against what the swap needs from it
+pub fn check(expected: Expected, account: Option<&Account>) -> Result<(), String> {
+    let owner_ok = |account: &Account, owners: &[Pubkey]| if owners.contains(&account.owner) {
+        Ok(())
+    } else {
+        Err(format!("owned by {}, expected {}", account.owner, owners.iter().map(|o| o.to_string()).collect::<Vec<_>>().join(" or ")))
+    };
+    match (expected, account) {
+        (Expected::Any, _) => Ok(()),
+        (_, None) => Err("account does not exist".into()),
+        (Expected::OwnedBy(program), Some(account)) => owner_ok(account, &[program]),
+        (Expected::Token, Some(account)) => owner_ok(account, &[TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID]),
+        (Expected::Executable, Some(account)) if account.executable => Ok(()),
+        (Expected::Executable, Some(_)) => Err("not an executable program".into()),
+    }
+}
+
+/// Venue name and derived accounts for `pool`, dispatched on the pool account's owner
+async fn resolve(fetcher: &PoolKeyFetcher, pool: &Pubkey, owner: &Pubkey) -> anyhow::Result<(&'static str, Vec<ResolvedAccount>)> {
+    use Expected::*;
+    let err = |e: Box<dyn std::error::Error>| anyhow::anyhow!("{}", e);
+
+    if *owner == RAYDIUM_V4_PROGRAM {
+        let k = fetcher.fetch_raydium_keys(pool).await.map_err(err)?;
+        Ok(("Raydium V4", vec![
+            acc("amm_id", k.amm_id, OwnedBy(RAYDIUM_V4_PROGRAM)),
+            acc("amm_authority", k.amm_authority, Any),
+            acc("amm_open_orders", k.amm_open_orders, OwnedBy(k.serum_program_id)),
+            acc("amm_target_orders", k.amm_target_orders, OwnedBy(RAYDIUM_V4_PROGRAM)),
+            acc("amm_coin_vault", k.amm_coin_vault, Token),
+            acc("amm_pc_vault", k.amm_pc_vault, Token),
+            acc("serum_program_id", k.serum_program_id, Executable),
+            acc("serum_market", k.serum_market, OwnedBy(k.serum_program_id)),
+            acc("serum_bids", k.serum_bids, OwnedBy(k.serum_program_id)),
+            acc("serum_asks", k.serum_asks, OwnedBy(k.serum_program_id)),
+            acc("serum_event_queue", k.serum_event_queue, OwnedBy(k.serum_program_id)),
+            acc("serum_coin_vault", k.serum_coin_vault, Token),
+            acc("serum_pc_vault", k.serum_pc_vault, Token),
+            acc("serum_vault_signer", k.serum_vault_signer, Any),
+            acc("token_program", k.token_program, Executable),
+        ]))
+    } else if *owner == ORCA_WHIRLPOOL_PROGRAM {
+        let k = fetcher.fetch_orca_keys(pool).await.map_err(err)?;
+        Ok(("Orca Whirlpool", vec![
+            acc("whirlpool", k.whirlpool, OwnedBy(ORCA_WHIRLPOOL_PROGRAM)),
+            acc("mint_a", k.mint_a, Token),
+            acc("mint_b", k.mint_b, Token),
+            acc("token_vault_a", k.token_vault_a, Token),
+            acc("token_vault_b", k.token_vault_b, Token),
+            acc("tick_array_0", k.tick_array_0, OwnedBy(ORCA_WHIRLPOOL_PROGRAM)),
+            acc("tick_array_1", k.tick_array_1, OwnedBy(ORCA_WHIRLPOOL_PROGRAM)),
+            acc("tick_array_2", k.tick_array_2, OwnedBy(ORCA_WHIRLPOOL_PROGRAM)),
+            acc("oracle", k.oracle, Any),
+        ]))
+    } else if *owner == METEORA_PROGRAM_ID {
+        let k = fetcher.fetch_meteora_keys(pool).await.map_err(err)?;
+        let mut accounts = vec![
+            acc("dlmm_pool", k.dlmm_pool, OwnedBy(METEORA_PROGRAM_ID)),
+            acc("reserve_x", k.reserve_x, Token),
+            acc("reserve_y", k.reserve_y, Token),
+            acc("token_x_mint", k.token_x_mint, Token),
+            acc("token_y_mint", k.token_y_mint, Token),
+            acc("oracle", k.oracle, OwnedBy(METEORA_PROGRAM_ID)),
+        ];
+        if let Some(ext) = k.bin_array_bitmap_extension {
+            accounts.push(acc("bin_array_bitmap_extension", ext, OwnedBy(METEORA_PROGRAM_ID)));
+        }
+        Ok(("Meteora DLMM", accounts))
+    } else if *owner == RAYDIUM_CLMM_PROGRAM {
+        let k = fetcher.fetch_raydium_clmm_keys(pool).await.map_err(err)?;
+        Ok(("Raydium CLMM", vec![
+            acc("pool_state", k.pool_state, OwnedBy(RAYDIUM_CLMM_PROGRAM)),
+            acc("amm_config", k.amm_config, OwnedBy(RAYDIUM_CLMM_PROGRAM)),
+            acc("observation_state", k.observation_state, OwnedBy(RAYDIUM_CLMM_PROGRAM)),
+            acc("mint_0", k.mint_0, Token),
+            acc("mint_1", k.mint_1, Token),
+            acc("token_vault_0", k.token_vault_0, Token),
+            acc("token_vault_1", k.token_vault_1, Token),
+            acc("tick_array_current", k.tick_array_current, OwnedBy(RAYDIUM_CLMM_PROGRAM)),
+            acc("tick_array_lower", k.tick_array_lower, OwnedBy(RAYDIUM_CLMM_PROGRAM)),
+            acc("tick_array_upper", k.tick_array_upper, OwnedBy(RAYDIUM_CLMM_PROGRAM)),
+        ]))
+    } else if *owner == PHOENIX_PROGRAM {
+        let k = fetcher.fetch_phoenix_keys(pool).await.map_err(err)?;
+        Ok(("Phoenix", vec![
+            acc("market", k.market, OwnedBy(PHOENIX_PROGRAM)),
+            acc("log_authority", mev_core::phoenix::PhoenixSwapKeys::log_authority(), Any),
+            acc("base_mint", k.base_mint, Token),
+            acc("quote_mint", k.quote_mint, Token),
+            acc("base_vault", k.base_vault, Token),
+            acc("quote_vault", k.quote_vault, Token),
+        ]))
+    } else if *owner == OPENBOOK_V2_PROGRAM {
+        let k = fetcher.fetch_openbook_keys(pool).await.map_err(err)?;
+        let mut accounts = vec![
+            acc("market", k.market, OwnedBy(OPENBOOK_V2_PROGRAM)),
+            acc("market_authority", k.market_authority, Any),
+            acc("bids", k.bids, OwnedBy(OPENBOOK_V2_PROGRAM)),
+            acc("asks", k.asks, OwnedBy(OPENBOOK_V2_PROGRAM)),
+            acc("event_heap", k.event_heap, OwnedBy(OPENBOOK_V2_PROGRAM)),
+            acc("base_vault", k.base_vault, Token),
+            acc("quote_vault", k.quote_vault, Token),
+            acc("base_mint", k.base_mint, Token),
+            acc("quote_mint", k.quote_mint, Token),
+        ];
+        accounts.extend(k.oracle_a.map(|o| acc("oracle_a", o, Any)));
+        accounts.extend(k.oracle_b.map(|o| acc("oracle_b", o, Any)));
+        Ok(("OpenBook v2", accounts))
+    } else {
+        Err(anyhow::anyhow!("Pool {} is owned by {}, which has no key resolver", pool, owner))
+    }
+}
+
+/// One pool's derived accounts, each with the result of its check
+pub struct PoolCheck {
+    pub venue: &'static str,
+    pub accounts: Vec<(ResolvedAccount, Result<(), String>)>,
+}
+
+impl PoolCheck {
+    pub fn failures(&self) -> usize {
+        self.accounts.iter().filter(|(_, result)| result.is_err()).count()
+    }
+}
+
+/// Resolves `pool` the way the executors do and checks every derived account on chain
+pub async fn check_pool(rpc_url: &str, pool: &Pubkey) -> anyhow::Result<PoolCheck> {
+    let rpc = RpcClient::new(rpc_url.to_string());
+    let pool_account = rpc.get_account(pool)
+        .map_err(|e| anyhow::anyhow!("Failed to fetch pool {}: {}", pool, e))?;
+
+    let fetcher = PoolKeyFetcher::new(std::sync::Arc::new(executor::rpc_pool::RpcPool::single(rpc_url)));
+    let (venue, accounts) = resolve(&fetcher, pool, &pool_account.owner).await?;
+
+    let addresses: Vec<Pubkey> = accounts.iter().map(|a| a.address).collect();
+    let fetched = addresses.chunks(100)
+        .map(|chunk| rpc.get_multiple_accounts(chunk))
+        .collect::<Result<Vec<_>, _>>()?
+        .concat();
+
+    let accounts = accounts.into_iter().zip(fetched.iter())
+        .map(|(resolved, account)| {
+            let result = check(resolved.expected, account.as_ref());
+            (resolved, result)
+        })
+        .collect();
+    Ok(PoolCheck { venue, accounts })
+}
+
+/// Prints the resolved accounts; errors if any of them fails its check
+pub async fn run(rpc_url: &str, pool: &Pubkey) -> anyhow::Result<()> {
+    let pool_check = check_pool(rpc_url, pool).await?;
+
+    println!("🔑 {} pool {}", pool_check.venue, pool);
+    for (resolved, result) in &pool_check.accounts {
+        match result {
+            Ok(()) => println!("   ├─ {:<28} {:<44} ✅", resolved.name, resolved.address),
+            Err(e) => println!("   ├─ {:<28} {:<44} ❌ {}", resolved.name, resolved.address, e),
+        }
+    }
+
+    let failures = pool_check.failures();
+    if failures > 0 {
+        return Err(anyhow::anyhow!("{} of {} accounts failed validation", failures, pool_check.accounts.len()));
+    }
+    println!("   └─ all {} accounts valid", pool_check.accounts.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_checks() {
+        let owned_by = |owner, executable| Account { lamports: 1, data: vec![], owner, executable, rent_epoch: 0 };
+
+        assert!(check(Expected::OwnedBy(RAYDIUM_V4_PROGRAM), Some(&owned_by(RAYDIUM_V4_PROGRAM, false))).is_ok());
+        assert!(check(Expected::OwnedBy(RAYDIUM_V4_PROGRAM), Some(&owned_by(ORCA_WHIRLPOOL_PROGRAM, false))).is_err());
+        assert!(check(Expected::Token, Some(&owned_by(TOKEN_2022_PROGRAM_ID, false))).is_ok());
+        assert!(check(Expected::Token, None).unwrap_err().contains("does not exist"));
+        assert!(check(Expected::Executable, Some(&owned_by(Pubkey::default(), false))).is_err());
+        assert!(check(Expected::Any, None).is_ok());
+    }
+}
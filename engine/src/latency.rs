@@ -0,0 +1,162 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How far back `/latency` looks
+pub const LATENCY_WINDOW: Duration = Duration::from_secs(3600);
+
+// Hard cap per stage so a hot detection loop can't grow the window unbounded
+const MAX_SAMPLES_PER_STAGE: usize = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatencyStage {
+    Detection,
+    Build,
+    Submit,
+}
+
+impl LatencyStage {
+    pub const ALL: [LatencyStage; 3] = [LatencyStage::Detection, LatencyStage::Build, LatencyStage::Submit];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LatencyStage::Detection => "Detection",
+            LatencyStage::Build => "Build",
+            LatencyStage::Submit => "Submit",
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+/// Rolling per-stage latency samples (ms) over the last hour.
+/// Prometheus histograms are cumulative since boot; this answers "how slow are we right now".
+pub struct LatencyTracker {
+    stages: [Mutex<VecDeque<(Instant, f64)>>; 3],
+}
+
+impl LatencyTracker {
+    pub fn new() -> Self {
+        Self {
+            stages: [Mutex::new(VecDeque::new()), Mutex::new(VecDeque::new()), Mutex::new(VecDeque::new())],
+        }
+    }
+
+    pub fn record(&self, stage: LatencyStage, ms: f64) {
+        self.record_at(stage, ms, Instant::now());
+    }
+
+    fn record_at(&self, stage: LatencyStage, ms: f64, at: Instant) {
+        if let Ok(mut samples) = self.stages[stage.index()].lock() {
+            prune(&mut samples, at);
+            if samples.len() >= MAX_SAMPLES_PER_STAGE {
+                samples.pop_front();
+            }
+            samples.push_back((at, ms));
+        }
+    }
+
+    pub fn summary(&self, stage: LatencyStage) -> LatencySummary {
+        self.summary_at(stage, Instant::now())
+    }
+
+    fn summary_at(&self, stage: LatencyStage, now: Instant) -> LatencySummary {
+        let mut values: Vec<f64> = match self.stages[stage.index()].lock() {
+            Ok(mut samples) => {
+                prune(&mut samples, now);
+                samples.iter().map(|(_, ms)| *ms).collect()
+            }
+            Err(_) => return LatencySummary::default(),
+        };
+        if values.is_empty() {
+            return LatencySummary::default();
+        }
+        values.sort_by(|a, b| a.total_cmp(b));
+        LatencySummary {
+            count: values.len(),
+            p50: percentile(&values, 0.50),
+            p95: percentile(&values, 0.95),
+            p99: percentile(&values, 0.99),
+        }
+    }
+
+    /// HTML body for the Telegram `/latency` reply
+    pub fn report(&self) -> String {
+        let mut out = format!("<b>Latency (last {}m)</b>\n", LATENCY_WINDOW.as_secs() / 60);
+        for stage in LatencyStage::ALL {
+            let s = self.summary(stage);
+            if s.count == 0 {
+                out.push_str(&format!("<b>{}:</b> no samples\n", stage.label()));
+            } else {
+                out.push_str(&format!(
+                    "<b>{}:</b> p50 {:.1}ms | p95 {:.1}ms | p99 {:.1}ms (n={})\n",
+                    stage.label(), s.p50, s.p95, s.p99, s.count
+                ));
+            }
+        }
+        out
+    }
+}
+
+impl Default for LatencyTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn prune(samples: &mut VecDeque<(Instant, f64)>, now: Instant) {
+    while let Some((at, _)) = samples.front() {
+        if now.duration_since(*at) <= LATENCY_WINDOW {
+            break;
+        }
+        samples.pop_front();
+    }
+}
+
+/// Nearest-rank percentile over an already sorted slice
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let rank = (q * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_nearest_rank() {
+        let tracker = LatencyTracker::new();
+        for ms in 1..=100 {
+            tracker.record(LatencyStage::Build, ms as f64);
+        }
+        let s = tracker.summary(LatencyStage::Build);
+        assert_eq!(s.count, 100);
+        assert_eq!(s.p50, 50.0);
+        assert_eq!(s.p95, 95.0);
+        assert_eq!(s.p99, 99.0);
+        assert_eq!(tracker.summary(LatencyStage::Submit).count, 0);
+    }
+
+    #[test]
+    fn test_old_samples_fall_out_of_window() {
+        let tracker = LatencyTracker::new();
+        let start = Instant::now();
+        tracker.record_at(LatencyStage::Detection, 500.0, start);
+        tracker.record_at(LatencyStage::Detection, 5.0, start + Duration::from_secs(1800));
+
+        let later = start + LATENCY_WINDOW + Duration::from_secs(1);
+        let s = tracker.summary_at(LatencyStage::Detection, later);
+        assert_eq!(s.count, 1);
+        assert_eq!(s.p99, 5.0);
+    }
+}
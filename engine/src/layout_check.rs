@@ -0,0 +1,402 @@
+/// Pool Layout Check
+///
+/// DEX program upgrades can shift an account layout under our offset-based
+/// decoders, and the graph then prices pools off garbage without any error.
+/// Every `LAYOUT_CHECK_INTERVAL_SECS` (and once at startup) one reference pool
+/// per venue is read and decoded the way the stream decodes it, then checked:
+/// - the decoded mints are the pair the pool is configured with
+/// - both decoded vaults are token accounts of those mints holding liquidity,
+///   and a concentrated pool reports nonzero liquidity
+/// - the decoded spot price is within `LAYOUT_CHECK_TOLERANCE_BPS` of what the
+///   quote source (Jupiter, on the same venue and pair) pays for a small swap
+/// - fields that never change for a pool (size, mints, vaults) match the
+///   previous poll's snapshot
+///
+/// A pool that starts failing raises one critical alert and, with
+/// `LAYOUT_CHECK_AUTO_PAUSE`, pauses trading until someone looks.
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use executor::rpc_pool::RpcPool;
+use mev_core::SwapStep;
+use mev_core::constants::{
+    ORCA_WHIRLPOOL_PROGRAM, RAYDIUM_CLMM_PROGRAM, RAYDIUM_V4_PROGRAM, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+use strategy::ports::QuoteSourcePort;
+use crate::alerts::{AlertManager, AlertSeverity, Field};
+use crate::config::PoolConfig;
+use crate::metrics::BotMetrics;
+
+const RAYDIUM_V4_LEN: usize = 752;
+const WHIRLPOOL_LEN: usize = 653;
+/// Venues with a decoder to check
+const CHECKED_PROGRAMS: [Pubkey; 3] = [RAYDIUM_V4_PROGRAM, ORCA_WHIRLPOOL_PROGRAM, RAYDIUM_CLMM_PROGRAM];
+/// Reference swap size: this share of the input-side vault, in bps, small enough to move no price
+const QUOTE_SIZE_BPS: u64 = 1;
+
+/// A pool whose pair we know independently of its account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReferencePool {
+    pub address: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+}
+
+/// The first configured pool of each address; a pool listed twice keeps its first pair
+pub fn reference_pools(pools: &[PoolConfig]) -> Vec<ReferencePool> {
+    let mut seen: Vec<ReferencePool> = Vec::new();
+    for pool in pools {
+        if !seen.iter().any(|r| r.address == pool.address) {
+            seen.push(ReferencePool { address: pool.address, mint_a: pool.token_a, mint_b: pool.token_b });
+        }
+    }
+    seen
+}
+
+/// What our decoders read out of a pool account
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolLayout {
+    pub program_id: Pubkey,
+    pub data_len: usize,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    /// Q64.64 sqrt of raw B per raw A; None for constant-product pools
+    pub sqrt_price: Option<u128>,
+    pub liquidity: Option<u128>,
+}
+
+impl PoolLayout {
+    pub fn decode(program_id: &Pubkey, data: &[u8]) -> Result<Self, String> {
+        let short = || format!("{} bytes is too short for the decoder", data.len());
+        if *program_id == RAYDIUM_V4_PROGRAM {
+            let amm: &mev_core::raydium::AmmInfo = bytemuck::try_from_bytes(data.get(..RAYDIUM_V4_LEN).ok_or_else(short)?)
+                .map_err(|_| "bad AmmInfo layout".to_string())?;
+            return Ok(Self {
+                program_id: *program_id,
+                data_len: data.len(),
+                mint_a: amm.base_mint(),
+                mint_b: amm.quote_mint(),
+                vault_a: amm.base_vault(),
+                vault_b: amm.quote_vault(),
+                sqrt_price: None,
+                liquidity: None,
+            });
+        }
+        if *program_id == ORCA_WHIRLPOOL_PROGRAM {
+            let pool: &mev_core::orca::Whirlpool = bytemuck::try_from_bytes(data.get(..WHIRLPOOL_LEN).ok_or_else(short)?)
+                .map_err(|_| "bad Whirlpool layout".to_string())?;
+            return Ok(Self {
+                program_id: *program_id,
+                data_len: data.len(),
+                mint_a: pool.token_mint_a(),
+                mint_b: pool.token_mint_b(),
+                vault_a: pool.token_vault_a(),
+                vault_b: pool.token_vault_b(),
+                sqrt_price: Some(pool.sqrt_price()),
+                liquidity: Some(pool.liquidity()),
+            });
+        }
+        if *program_id == RAYDIUM_CLMM_PROGRAM {
+            let bytes = data.get(..mev_core::raydium_clmm::POOL_STATE_LEN).ok_or_else(short)?;
+            let pool = bytemuck::try_pod_read_unaligned::<mev_core::raydium_clmm::PoolState>(bytes)
+                .map_err(|_| "bad PoolState layout".to_string())?;
+            return Ok(Self {
+                program_id: *program_id,
+                data_len: data.len(),
+                mint_a: pool.token_mint_0(),
+                mint_b: pool.token_mint_1(),
+                vault_a: pool.token_vault_0(),
+                vault_b: pool.token_vault_1(),
+                sqrt_price: Some(pool.sqrt_price_x64()),
+                liquidity: Some(pool.liquidity()),
+            });
+        }
+        Err(format!("no layout check for program {}", program_id))
+    }
+
+    /// Raw B per raw A: the decoded sqrt price, or the vault balances for a constant-product pool
+    pub fn spot_price(&self, vault_a: u64, vault_b: u64) -> Option<f64> {
+        match self.sqrt_price {
+            Some(sqrt) => {
+                let sqrt = sqrt as f64 / (1u128 << 64) as f64;
+                Some(sqrt * sqrt)
+            }
+            None => (vault_a > 0).then(|| vault_b as f64 / vault_a as f64),
+        }
+    }
+
+    /// Fields that differ from an earlier snapshot of the same pool; none of them ever change legitimately
+    pub fn diff(&self, previous: &PoolLayout) -> Vec<&'static str> {
+        let mut changed = Vec::new();
+        if self.program_id != previous.program_id {
+            changed.push("owner");
+        }
+        if self.data_len != previous.data_len {
+            changed.push("size");
+        }
+        if (self.mint_a, self.mint_b) != (previous.mint_a, previous.mint_b) {
+            changed.push("mints");
+        }
+        if (self.vault_a, self.vault_b) != (previous.vault_a, previous.vault_b) {
+            changed.push("vaults");
+        }
+        changed
+    }
+}
+
+/// SPL token account (either token program): its mint and balance
+pub fn token_account(account: Option<&Account>) -> Option<(Pubkey, u64)> {
+    let account = account.filter(|a| a.owner == TOKEN_PROGRAM_ID || a.owner == TOKEN_2022_PROGRAM_ID)?;
+    let mint = Pubkey::new_from_array(account.data.get(0..32)?.try_into().ok()?);
+    let amount = u64::from_le_bytes(account.data.get(64..72)?.try_into().ok()?);
+    Some((mint, amount))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Divergence {
+    Undecodable(String),
+    Mints { decoded: (Pubkey, Pubkey) },
+    Vault { mint: Pubkey, detail: String },
+    NoLiquidity,
+    Price { ours: f64, reference: f64, deviation_bps: f64 },
+    Changed(Vec<&'static str>),
+}
+
+impl Divergence {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Divergence::Undecodable(_) => "undecodable",
+            Divergence::Mints { .. } => "mints",
+            Divergence::Vault { .. } => "vault",
+            Divergence::NoLiquidity => "liquidity",
+            Divergence::Price { .. } => "price",
+            Divergence::Changed(_) => "snapshot",
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Divergence::Undecodable(e) => format!("account no longer decodes: {}", e),
+            Divergence::Mints { decoded } => format!("decoded mints {} / {} are not the pool's pair", decoded.0, decoded.1),
+            Divergence::Vault { mint, detail } => format!("vault for {}: {}", mint, detail),
+            Divergence::NoLiquidity => "decoded liquidity is zero".to_string(),
+            Divergence::Price { ours, reference, deviation_bps } =>
+                format!("decoded price {:.6e} is {:.0} bps off the reference {:.6e}", ours, deviation_bps, reference),
+            Divergence::Changed(fields) => format!("{} changed since the last snapshot", fields.join(", ")),
+        }
+    }
+}
+
+/// Everything checkable without a reference quote
+pub fn check_structure(reference: &ReferencePool, layout: &PoolLayout, vaults: [Option<(Pubkey, u64)>; 2], previous: Option<&PoolLayout>) -> Vec<Divergence> {
+    let mut found = Vec::new();
+    let pair = [reference.mint_a, reference.mint_b];
+    if !(pair.contains(&layout.mint_a) && pair.contains(&layout.mint_b) && layout.mint_a != layout.mint_b) {
+        found.push(Divergence::Mints { decoded: (layout.mint_a, layout.mint_b) });
+    }
+    for (mint, vault) in [(layout.mint_a, vaults[0]), (layout.mint_b, vaults[1])] {
+        let detail = match vault {
+            None => Some("not a token account".to_string()),
+            Some((held, _)) if held != mint => Some(format!("holds {}", held)),
+            Some((_, 0)) => Some("empty".to_string()),
+            Some(_) => None,
+        };
+        if let Some(detail) = detail {
+            found.push(Divergence::Vault { mint, detail });
+        }
+    }
+    if layout.liquidity == Some(0) {
+        found.push(Divergence::NoLiquidity);
+    }
+    if let Some(previous) = previous {
+        let changed = layout.diff(previous);
+        if !changed.is_empty() {
+            found.push(Divergence::Changed(changed));
+        }
+    }
+    found
+}
+
+pub struct LayoutChecker {
+    rpc: Arc<RpcPool>,
+    quotes: Option<Arc<dyn QuoteSourcePort>>,
+    pools: Vec<ReferencePool>,
+    tolerance_bps: u32,
+    auto_pause: bool,
+    snapshots: Mutex<HashMap<Pubkey, PoolLayout>>,
+    /// Pools currently failing, with the checks they fail; alerts go out when this changes
+    failing: Mutex<HashMap<Pubkey, Vec<&'static str>>>,
+}
+
+impl LayoutChecker {
+    pub fn new(rpc: Arc<RpcPool>, quotes: Option<Arc<dyn QuoteSourcePort>>, pools: Vec<ReferencePool>, tolerance_bps: u32, auto_pause: bool) -> Self {
+        Self { rpc, quotes, pools, tolerance_bps, auto_pause, snapshots: Mutex::default(), failing: Mutex::default() }
+    }
+
+    pub async fn run(self: Arc<Self>, metrics: Arc<BotMetrics>, alerts: Arc<AlertManager>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.check_once(&metrics, &alerts).await {
+                tracing::warn!("🧬 Layout check skipped: {}", e);
+            }
+        }
+    }
+
+    /// One reference pool per venue, the first configured
+    fn per_venue(&self, accounts: &[Option<Account>]) -> Vec<(ReferencePool, Account)> {
+        let mut picked: Vec<(ReferencePool, Account)> = Vec::new();
+        for (reference, account) in self.pools.iter().zip(accounts) {
+            if let Some(account) = account.as_ref().filter(|a| CHECKED_PROGRAMS.contains(&a.owner)) {
+                if !picked.iter().any(|(_, a)| a.owner == account.owner) {
+                    picked.push((*reference, account.clone()));
+                }
+            }
+        }
+        picked
+    }
+
+    async fn check_once(&self, metrics: &BotMetrics, alerts: &AlertManager) -> anyhow::Result<()> {
+        let addresses: Vec<Pubkey> = self.pools.iter().map(|p| p.address).collect();
+        let accounts = self.rpc.call(|c| async move { c.get_multiple_accounts(&addresses).await }).await?;
+
+        for (reference, account) in self.per_venue(&accounts) {
+            let found = self.inspect(&reference, &account).await?;
+            mev_core::telemetry::POOL_LAYOUT_CHECKS.with_label_values(&[if found.is_empty() { "ok" } else { "diverged" }]).inc();
+            for divergence in &found {
+                mev_core::telemetry::POOL_LAYOUT_DIVERGENCES.with_label_values(&[divergence.label()]).inc();
+            }
+
+            let labels: Vec<&'static str> = found.iter().map(Divergence::label).collect();
+            let changed = {
+                let mut failing = self.failing.lock().unwrap();
+                let before = failing.get(&reference.address).cloned().unwrap_or_default();
+                if labels.is_empty() {
+                    failing.remove(&reference.address);
+                } else {
+                    failing.insert(reference.address, labels.clone());
+                }
+                mev_core::telemetry::POOL_LAYOUT_DRIFTING.set(failing.len() as i64);
+                before != labels
+            };
+            if !changed {
+                continue;
+            }
+            let venue = crate::tui::dex_name(&account.owner);
+            if found.is_empty() {
+                tracing::info!("🧬 {} reference pool {} decodes cleanly again", venue, reference.address);
+                continue;
+            }
+            self.report(venue, &reference, &found, metrics, alerts).await;
+        }
+        Ok(())
+    }
+
+    async fn inspect(&self, reference: &ReferencePool, account: &Account) -> anyhow::Result<Vec<Divergence>> {
+        let layout = match PoolLayout::decode(&account.owner, &account.data) {
+            Ok(layout) => layout,
+            Err(e) => return Ok(vec![Divergence::Undecodable(e)]),
+        };
+        let vault_keys = [layout.vault_a, layout.vault_b];
+        let vault_accounts = self.rpc.call(|c| async move { c.get_multiple_accounts(&vault_keys).await }).await?;
+        let vaults = [token_account(vault_accounts[0].as_ref()), token_account(vault_accounts[1].as_ref())];
+
+        let previous = self.snapshots.lock().unwrap().insert(reference.address, layout.clone());
+        let mut found = check_structure(reference, &layout, vaults, previous.as_ref());
+        if !found.is_empty() {
+            return Ok(found);
+        }
+
+        // Structure holds: the decoded price must agree with a venue-restricted quote
+        let (Some(quotes), Some((_, reserve_a)), Some((_, reserve_b))) = (&self.quotes, vaults[0], vaults[1]) else {
+            return Ok(found);
+        };
+        let Some(ours) = layout.spot_price(reserve_a, reserve_b) else {
+            return Ok(found);
+        };
+        let amount_in = (reserve_a / 10_000 * QUOTE_SIZE_BPS).max(1);
+        let step = SwapStep {
+            pool: reference.address,
+            program_id: layout.program_id,
+            input_mint: layout.mint_a,
+            output_mint: layout.mint_b,
+            expected_output: 0,
+        };
+        match quotes.quote_hop(&step, amount_in).await {
+            Ok(Some(out)) if out > 0 => {
+                let reference_price = out as f64 / amount_in as f64;
+                let deviation_bps = ((ours / reference_price) - 1.0).abs() * 10_000.0;
+                if deviation_bps > self.tolerance_bps as f64 {
+                    found.push(Divergence::Price { ours, reference: reference_price, deviation_bps });
+                }
+            }
+            Ok(_) => tracing::debug!("🧬 {} has no reference quote for {}", quotes.name(), reference.address),
+            Err(e) => tracing::debug!("🧬 {} quote for {} unavailable: {}", quotes.name(), reference.address, e),
+        }
+        Ok(found)
+    }
+
+    async fn report(&self, venue: &str, reference: &ReferencePool, found: &[Divergence], metrics: &BotMetrics, alerts: &AlertManager) {
+        if self.auto_pause {
+            metrics.is_paused.store(true, Ordering::Relaxed);
+        }
+        let action = if self.auto_pause { "Trading PAUSED; check the program for an upgrade, then /resume." } else { "Trading continues (auto-pause off)." };
+        let details: Vec<String> = found.iter().map(Divergence::describe).collect();
+        tracing::error!("🧬 LAYOUT DRIFT on {} pool {}: {}. {}", venue, reference.address, details.join("; "), action);
+        alerts.send_alert(
+            AlertSeverity::Critical,
+            "POOL LAYOUT DRIFT",
+            &format!("The {} decoder no longer reads its reference pool correctly; a program upgrade may have moved the account layout. {}", venue, action),
+            vec![
+                Field { name: "Pool".to_string(), value: reference.address.to_string(), inline: false },
+                Field { name: "Findings".to_string(), value: details.join("\n"), inline: false },
+            ],
+        ).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_structure_flags_shifted_fields() {
+        let (sol, usdc) = (mev_core::constants::SOL_MINT, mev_core::constants::USDC_MINT);
+        let reference = ReferencePool { address: Pubkey::new_unique(), mint_a: sol, mint_b: usdc };
+        let layout = PoolLayout {
+            program_id: ORCA_WHIRLPOOL_PROGRAM,
+            data_len: 653,
+            mint_a: sol,
+            mint_b: usdc,
+            vault_a: Pubkey::new_unique(),
+            vault_b: Pubkey::new_unique(),
+            sqrt_price: Some(12u128 << 64),
+            liquidity: Some(1_000_000),
+        };
+        let healthy = [Some((sol, 5_000)), Some((usdc, 720_000))];
+        assert!(check_structure(&reference, &layout, healthy, Some(&layout)).is_empty());
+        assert_eq!(layout.spot_price(1, 1), Some(144.0));
+
+        // Offsets moved by a field: the "mints" read as other keys and the vaults as garbage
+        let shifted = PoolLayout { mint_a: Pubkey::new_unique(), vault_b: Pubkey::new_unique(), data_len: 661, liquidity: Some(0), ..layout.clone() };
+        let found = check_structure(&reference, &shifted, [Some((sol, 5_000)), None], Some(&layout));
+        let labels: Vec<&str> = found.iter().map(Divergence::label).collect();
+        assert_eq!(labels, vec!["mints", "vault", "liquidity", "snapshot"]);
+        assert_eq!(found[3], Divergence::Changed(vec!["size", "mints", "vaults"]));
+
+        // A vault holding the wrong mint, or nothing
+        let found = check_structure(&reference, &layout, [Some((usdc, 5_000)), Some((usdc, 0))], None);
+        assert_eq!(found.len(), 2);
+
+        // Constant product: price from the vaults
+        let cpmm = PoolLayout { program_id: RAYDIUM_V4_PROGRAM, sqrt_price: None, liquidity: None, ..layout };
+        assert_eq!(cpmm.spot_price(2_000, 300_000), Some(150.0));
+        assert_eq!(cpmm.spot_price(0, 300_000), None);
+    }
+}
@@ -134,10 +134,11 @@ pub async fn start_listener(
                                                     use base64::{Engine as _, engine::general_purpose};
                                                     if let Ok(bytes) = general_purpose::STANDARD.decode(update_str) {
                                                         let pool_addr = Pubkey::from_str(pool_addr_str).unwrap_or_default();
-                                                        let ts = std::time::SystemTime::now()
-                                                            .duration_since(std::time::UNIX_EPOCH)
-                                                            .unwrap()
-                                                            .as_secs() as i64;
+                                                        let clock = mev_core::clock::system();
+                                                        if let Some(slot) = result.get("context").and_then(|c| c.get("slot")).and_then(|s| s.as_u64()) {
+                                                            clock.observe_slot(slot);
+                                                        }
+                                                        let ts = clock.unix_secs() as i64;
 
                                                         // 1. Identify DEX by data length or owner
                                                         if bytes.len() == 653 { // Orca Whirlpool
@@ -153,7 +154,9 @@ pub async fn start_listener(
                                                                 pc_reserve: 0,
                                                                 price_sqrt: Some(whirlpool.sqrt_price()),
                                                                 liquidity: Some(whirlpool.liquidity()),
+                                                                fee_bps: Some(whirlpool.fee_bps()),
                                                                 timestamp: ts,
+                                                                trigger: mev_core::Trigger::AccountUpdate,
                                                             };
                                                             if tx.send(update).is_err() { break; }
                                                         } else if bytes.len() == 752 { // Raydium V4 CPMM
@@ -169,11 +172,51 @@ pub async fn start_listener(
                                                                 pc_reserve: amm_info.quote_reserve(),
                                                                 price_sqrt: None,
                                                                 liquidity: None,
+                                                                fee_bps: None,
                                                                 timestamp: ts,
+                                                                trigger: mev_core::Trigger::AccountUpdate,
                                                             };
                                                             if tx.send(update).is_err() { break; }
-                                                        } else if bytes.len() == 1544 { 
-                                                            tracing::debug!("Detected Raydium CLMM update (1544 bytes) for pool {}", pool_addr);
+                                                        } else if bytes.len() == mev_core::raydium_clmm::POOL_STATE_LEN { // Raydium CLMM
+                                                            if let Ok(pool_state) = bytemuck::try_pod_read_unaligned::<mev_core::raydium_clmm::PoolState>(&bytes) {
+                                                                let update = MarketUpdate {
+                                                                    pool_address: pool_addr,
+                                                                    program_id: mev_core::constants::RAYDIUM_CLMM_PROGRAM,
+                                                                    coin_mint: pool_state.token_mint_0(),
+                                                                    pc_mint: pool_state.token_mint_1(),
+                                                                    coin_reserve: 0,
+                                                                    pc_reserve: 0,
+                                                                    price_sqrt: Some(pool_state.sqrt_price_x64()),
+                                                                    liquidity: Some(pool_state.liquidity()),
+                                                                    fee_bps: None,
+                                                                    timestamp: ts,
+                                                                    trigger: mev_core::Trigger::AccountUpdate,
+                                                                };
+                                                                if tx.send(update).is_err() { break; }
+                                                            }
+                                                        } else if mev_core::pump_fun::CURVE_ACCOUNT_LENS.contains(&bytes.len()) { // Pump.fun curve
+                                                            if let Ok(curve) = mev_core::pump_fun::PumpFunBondingCurve::from_account_data(&bytes[8..]) {
+                                                                // Mint unknown here; the worker resolves it from the graph
+                                                                if tx.send(curve.to_market_update(pool_addr, Pubkey::default(), ts)).is_err() { break; }
+                                                            }
+                                                        } else if bytes.len() == mev_core::meteora::LB_PAIR_LEN { // Meteora DLMM
+                                                            if let Ok(pair) = bytemuck::try_pod_read_unaligned::<mev_core::meteora::MeteoraDLMM>(&bytes) {
+                                                                // Bins live in separate accounts; the strategy quotes from the bin cache
+                                                                let update = MarketUpdate {
+                                                                    pool_address: pool_addr,
+                                                                    program_id: mev_core::constants::METEORA_PROGRAM_ID,
+                                                                    coin_mint: pair.token_x_mint(),
+                                                                    pc_mint: pair.token_y_mint(),
+                                                                    coin_reserve: 0,
+                                                                    pc_reserve: 0,
+                                                                    price_sqrt: None,
+                                                                    liquidity: None,
+                                                                    fee_bps: None,
+                                                                    timestamp: ts,
+                                                                    trigger: mev_core::Trigger::AccountUpdate,
+                                                                };
+                                                                if tx.send(update).is_err() { break; }
+                                                            }
                                                         } else {
                                                             tracing::trace!("Ignoring unknown account size: {} bytes for pool {}", bytes.len(), pool_addr);
                                                         }
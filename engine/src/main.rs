@@ -11,6 +11,8 @@ use tracing::{info, error, warn, debug};
 
 // Internal Crates
 use strategy::StrategyEngine;
+use strategy::analytics::decisions::EventOutcome;
+use strategy::ports::{PriceOraclePort, TelemetryPort};
 // Removed unused JitoExecutor and LegacyExecutor
 
 mod config;
@@ -29,6 +31,44 @@ mod discovery;
 mod birth_watcher;
 mod watcher;
 mod scoring;
+mod worker_pool;
+mod program_check;
+mod latency;
+mod reconciler;
+mod oracle_feed;
+mod geyser_listener;
+mod capital_at_risk;
+mod backtest;
+mod archive_replay;
+mod keys_cli;
+mod dlmm_bins;
+mod whirlpool_ticks;
+mod openbook_books;
+mod hop_analytics;
+mod control_api;
+mod platform;
+mod delisting;
+mod config_snapshot;
+mod drawdown;
+mod ws_feed;
+mod balance_watchdog;
+mod model_drift;
+mod backrun;
+mod cycle_repricer;
+mod cycle_sweep;
+mod trade_journal;
+mod pnl_reconciler;
+mod training_export;
+mod grafana;
+mod funnel;
+mod self_test;
+mod recovery;
+mod inventory_manager;
+mod token_metadata;
+mod layout_check;
+#[cfg(not(feature = "paper-only"))]
+mod wallet_failover;
+mod schedule;
 
 use crate::intelligence::MarketIntelligence;
 use crate::wallet_manager::WalletManager;
@@ -44,6 +84,16 @@ pub struct AppContext {
     pub metrics: Arc<metrics::BotMetrics>,
     pub risk_mgr: Arc<risk::RiskManager>,
     pub alert_mgr: Arc<alerts::AlertManager>,
+    /// Strategy values the control API can change at runtime
+    pub hot_config: Arc<control_api::HotConfig>,
+    /// Takes closed, drained and migrated pools out of the graph and subscriptions
+    pub delister: Arc<delisting::PoolDelister>,
+    /// Spendable balances, refreshed from the wallet
+    pub inventory: Arc<strategy::flash_loan::Inventory>,
+    /// Sizes each trade from the recent record; None = static trade size
+    pub position_sizer: Option<strategy::position_sizing::KellySizer>,
+    /// UTC windows and Telegram overrides deciding which strategies run
+    pub schedule: Arc<schedule::StrategySchedule>,
 }
 
 #[tokio::main]
@@ -70,17 +120,78 @@ async fn main() -> anyhow::Result<()> {
         }
     };
     
+    // 4.0 Key Inspection: `engine keys <pool>` resolves one pool's swap accounts and exits
+    let cli_args: Vec<String> = env::args().collect();
+    if cli_args.get(1).map(String::as_str) == Some("keys") {
+        let pool = cli_args.get(2)
+            .and_then(|p| Pubkey::from_str(p).ok())
+            .ok_or_else(|| anyhow::anyhow!("Usage: engine keys <pool_address>"))?;
+        return keys_cli::run(&bot_cfg.rpc_url, &pool).await;
+    }
+    // `engine grafana [file]` writes a dashboard for the exported metrics and exits
+    if cli_args.get(1).map(String::as_str) == Some("grafana") {
+        let namespace = mev_core::telemetry::metric_namespace(&bot_cfg.strategy_id);
+        let dashboard = grafana::dashboard(&bot_cfg.strategy_id, &namespace, &mev_core::telemetry::collectors());
+        let json = serde_json::to_string_pretty(&dashboard)?;
+        match cli_args.get(2) {
+            Some(path) => {
+                std::fs::write(path, json)?;
+                info!("📊 Grafana dashboard for {}_* written to {}", namespace, path);
+            }
+            None => println!("{}", json),
+        }
+        return Ok(());
+    }
+
+    // `engine --self-test` checks config, connectivity, key resolution, a bundle build and alert delivery, then exits
+    if cli_args.iter().any(|a| a == "--self-test") {
+        info!("🩺 Self-test: exercising the pipeline without trading...");
+        let report = self_test::run(&bot_cfg).await;
+        println!("\n{}", report);
+        if !report.passed() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // 4. Startup Validation (Fail Fast)
     if let Err(e) = bot_cfg.validate() {
         error!("❌ Configuration Validation Failed: {}", e);
         std::process::exit(1);
     }
+
+    // Effective config snapshot: its hash tags reports, the trade journal and metrics
+    let config_snapshot = config_snapshot::ConfigSnapshot::capture(&bot_cfg).map_err(|e| anyhow::anyhow!(e))?;
+    match config_snapshot.persist(&platform::data_dir()) {
+        Ok(path) => info!("🧾 Config {} ({})", config_snapshot.hash, path.display()),
+        Err(e) => warn!("⚠️ Config {} snapshot not saved: {}", config_snapshot.hash, e),
+    }
+    mev_core::telemetry::CONFIG_INFO
+        .with_label_values(&[&config_snapshot.hash, &format!("{:?}", bot_cfg.mode)])
+        .set(1);
     
+    // 4.0.1 Backtest Mode: replay recorded market data offline and exit
+    if let Some(i) = cli_args.iter().position(|a| a == "--backtest") {
+        let dir = cli_args.get(i + 1).map(std::path::PathBuf::from).unwrap_or_else(platform::data_dir);
+        info!("📼 Backtest Mode: replaying {} ({}ms simulated latency)", dir.join("market_data.csv").display(), bot_cfg.backtest_latency_ms);
+        let report = backtest::run(&bot_cfg, &dir).await?;
+        println!("\n{}", report);
+        return Ok(());
+    }
+
+    // 4.0.2 Archival Replay: rebuild a past slot range from transaction history, then backtest it
+    if cli_args.get(1).map(String::as_str) == Some("replay") {
+        let request = archive_replay::ReplayRequest::parse(&cli_args[2..]).map_err(|e| anyhow::anyhow!(e))?;
+        let report = archive_replay::run(&bot_cfg, &request).await?;
+        println!("\n{}", report);
+        return Ok(());
+    }
+
     // 4.1 Initialize Data Recorder (Ops Layer)
     let recording_enabled = env::var("DATA_RECORDING_ENABLED").unwrap_or_else(|_| "false".to_string()) == "true";
     let recorder = if recording_enabled {
         info!("💾 Data Recording ENABLED. Initializing recorder...");
-        match recorder::AsyncCsvWriter::new("data").await {
+        match recorder::AsyncCsvWriter::new(&platform::data_dir(), &config_snapshot.hash).await {
             Ok(r) => Some(Arc::new(r)),
             Err(e) => {
                 error!("❌ Failed to initialize Data Recorder: {}", e);
@@ -94,11 +205,7 @@ async fn main() -> anyhow::Result<()> {
     
     info!("✅ Config Loaded & Validated: RPC={}, Jito={}", bot_cfg.rpc_url, bot_cfg.jito_url);
     
-    let key_path = if bot_cfg.keypair_path.is_empty() {
-        format!("{}/.config/solana/id.json", env::var("HOME").unwrap_or_else(|_| ".".to_string()))
-    } else {
-        bot_cfg.keypair_path.clone()
-    };
+    let key_path = bot_cfg.resolved_keypair_path();
     
     let payer = match read_keypair_file(&key_path) {
         Ok(k) => k,
@@ -108,6 +215,31 @@ async fn main() -> anyhow::Result<()> {
         }
     };
     info!("🔑 Identity: {}", payer.pubkey());
+    #[cfg(not(feature = "paper-only"))]
+    let standby = match bot_cfg.standby_keypair_path.as_deref().filter(|p| !p.trim().is_empty()) {
+        Some(path) => match read_keypair_file(path.trim()) {
+            Ok(k) => {
+                info!("🔑 Standby identity: {} (failover after {} consecutive key failures, or /rotate_wallet)",
+                    k.pubkey(), bot_cfg.wallet_failover_threshold);
+                Some(k)
+            }
+            Err(e) => {
+                error!("❌ CRITICAL: Failed to read standby keypair at {}: {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    // 4.2 Venue Program Verification (Fail Fast)
+    if bot_cfg.verify_program_ids {
+        info!("🔎 Verifying venue program IDs against {}...", bot_cfg.rpc_url);
+        if let Err(e) = program_check::verify_venue_programs(&bot_cfg.rpc_url).await {
+            error!("❌ CRITICAL: Venue program verification failed: {}", e);
+            std::process::exit(1);
+        }
+        info!("✅ All venue programs verified.");
+    }
 
     // --- COMPOSITION ROOT SETUP ---
     
@@ -153,71 +285,604 @@ async fn main() -> anyhow::Result<()> {
 
     // 2. Initialize Telemetry & Metrics (with Intelligence reference)
     info!("🔌 Connecting to RPC: {}...", bot_cfg.rpc_url);
-    let metrics = Arc::new(metrics::BotMetrics::new(Some(Arc::clone(&intel_port))));
-    let pool_fetcher = Arc::new(pool_fetcher::PoolKeyFetcher::new(&bot_cfg.rpc_url));
-    let risk_mgr = Arc::new(risk::RiskManager::new());
+    let (route_alert_tx, mut route_alert_rx) = mpsc::unbounded_channel::<String>();
+    let route_blacklist = Arc::new(strategy::analytics::route_guard::RouteBlacklist::new(
+        bot_cfg.route_max_reverts,
+        std::time::Duration::from_secs(bot_cfg.route_revert_window_secs),
+        std::time::Duration::from_secs(bot_cfg.route_blacklist_cooldown_secs),
+        Some(route_alert_tx),
+    ));
+    let in_flight = Arc::new(strategy::analytics::in_flight::InFlightBook::default());
+    let quote_errors = Arc::new(strategy::analytics::quote_errors::QuoteErrorTracker::default());
+    let hop_analyzer = Arc::new(hop_analytics::HopAnalyzer::new(&bot_cfg.rpc_url, payer.pubkey(), Arc::clone(&quote_errors)));
+    let rpc_pool = Arc::new(bot_cfg.rpc_pool());
+    info!("🛰️ RPC pool: {} endpoint(s), 429 cooldown {}s", rpc_pool.len(), bot_cfg.rpc_rate_limit_cooldown_secs);
+    let trade_journal = match &db_pool {
+        Some(pool) => match trade_journal::TradeJournal::new(pool.clone(), bot_cfg.trade_journal_max_rejections_per_sec).await {
+            Ok(journal) => {
+                info!("🗄️ Trade journal ACTIVE (rejections capped at {}/s)", bot_cfg.trade_journal_max_rejections_per_sec);
+                Some(journal)
+            }
+            Err(e) => {
+                error!("❌ Failed to initialize trade journal: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+    let training_export = bot_cfg.training_export_enabled.then(|| {
+        let dir = if bot_cfg.training_export_dir.trim().is_empty() {
+            platform::data_dir().join("training")
+        } else {
+            std::path::PathBuf::from(bot_cfg.training_export_dir.trim())
+        };
+        let format = bot_cfg.training_export_format.parse().unwrap_or(training_export::ExportFormat::Csv);
+        info!("🧪 Training export ACTIVE: {:?}, rotated daily in {}", format, dir.display());
+        Arc::new(training_export::TrainingExporter::new(dir, format))
+    });
+    let pnl_reconciler = Arc::new(pnl_reconciler::PnlReconciler::new(
+        Arc::clone(&rpc_pool),
+        payer.pubkey(),
+        bot_cfg.pnl_deviation_alert_pct,
+        trade_journal.clone(),
+        training_export.clone(),
+    ));
+    let metrics = Arc::new(metrics::BotMetrics::new(
+        Some(Arc::clone(&intel_port)),
+        Some(Arc::clone(&route_blacklist)),
+        Some(Arc::clone(&in_flight)),
+        Some(hop_analyzer),
+        trade_journal.clone(),
+        Some(Arc::clone(&pnl_reconciler)),
+        training_export.clone(),
+    ));
+    pnl_reconciler.attach(Arc::downgrade(&metrics) as std::sync::Weak<dyn strategy::ports::TelemetryPort>);
+    info!("💰 Landed trades booked at realized PnL (quote deviation flagged above {}%)", bot_cfg.pnl_deviation_alert_pct);
+    let rng = Arc::new(executor::rng::SharedRng::new(bot_cfg.rng_seed));
+    if let Some(seed) = rng.seed() {
+        info!("🎲 RNG seeded with {}: tip accounts, jitter and paper fills are reproducible", seed);
+    }
+    let pool_fetcher = Arc::new(pool_fetcher::PoolKeyFetcher::new(Arc::clone(&rpc_pool)));
+    let inventory = Arc::new(strategy::flash_loan::Inventory::new());
+    let exposure_limits = risk::ExposureLimits {
+        max_mint_notional_lamports: bot_cfg.risk_max_mint_notional_lamports,
+        max_pool_trades_per_min: bot_cfg.risk_max_pool_trades_per_min,
+        max_wallet_share_bps: bot_cfg.risk_max_wallet_share_bps,
+    };
+    info!("🧯 Exposure limits: {} lamports per mint in flight, {} trades/pool/min, {} bps of wallet per trade (0 = off)",
+        exposure_limits.max_mint_notional_lamports, exposure_limits.max_pool_trades_per_min, exposure_limits.max_wallet_share_bps);
+    let risk_mgr = Arc::new(risk::RiskManager::new()
+        .with_exposure_limits(exposure_limits, Arc::clone(&in_flight), Arc::clone(&inventory)));
 
     // 4.3 Initialize Performance & Safety
     info!("📊 Initializing Performance Tracker...");
-    let performance_tracker = Arc::new(strategy::analytics::performance::PerformanceTracker::new("logs/performance.log").await);
+    let performance_tracker = Arc::new(strategy::analytics::performance::PerformanceTracker::new(&platform::log_path("performance.log")).await);
+    // 4.3.1 Oracle references: the route sanity guard, and the prices non-SOL pool sides are valued at
+    let oracle_feeds = strategy::safety::oracle_guard::OracleFeed::parse_list(&bot_cfg.oracle_feeds).map_err(|e| anyhow::anyhow!(e))?;
+    let (price_oracle, stream_oracle): (Option<Arc<dyn PriceOraclePort>>, _) = if oracle_feeds.is_empty() {
+        (None, None)
+    } else {
+        let mode = oracle_feed::OracleMode::parse(&bot_cfg.oracle_mode).map_err(|e| anyhow::anyhow!(e))?;
+        let feeds = oracle_feeds.len();
+        let pyth = Arc::new(oracle_feed::PythOracle::new(Arc::clone(&rpc_pool), oracle_feeds, bot_cfg.oracle_max_age_secs));
+        info!("🔮 Price oracle {} ACTIVE ({:?}): {} feed(s), band {} bps, max age {}s",
+            pyth.name(), mode, feeds, bot_cfg.oracle_max_deviation_bps, bot_cfg.oracle_max_age_secs);
+        tokio::spawn(Arc::clone(&pyth).run(mode, std::time::Duration::from_millis(bot_cfg.oracle_poll_ms)));
+        // Stream mode follows the price accounts on the market watchers' WebSocket feed
+        let stream_oracle = (mode == oracle_feed::OracleMode::Stream).then(|| Arc::clone(&pyth));
+        (Some(pyth as Arc<dyn PriceOraclePort>), stream_oracle)
+    };
+    let oracle_guard = price_oracle.clone()
+        .map(|oracle| Arc::new(strategy::safety::oracle_guard::OracleGuard::new(oracle, bot_cfg.oracle_max_deviation_bps)));
+    let liquidity_floor = Arc::new(strategy::safety::liquidity_floor::LiquidityFloor::new(bot_cfg.min_liquidity_lamports, price_oracle.clone()));
+    info!("🏜️ Liquidity floor: {:.2} SOL-equivalent per pool ({})", bot_cfg.min_liquidity_lamports as f64 / 1e9,
+        if price_oracle.is_some() { "non-SOL sides at oracle prices" } else { "SOL-paired pools only, no ORACLE_FEEDS" });
+
     info!("🛡️ Initializing Safety Checker...");
-    let safety_checker = Arc::new(strategy::safety::token_validator::TokenSafetyChecker::new(&bot_cfg.rpc_url, bot_cfg.min_liquidity_lamports));
+    let safety_checker = Arc::new(strategy::safety::token_validator::TokenSafetyChecker::new(&bot_cfg.rpc_url, bot_cfg.min_liquidity_lamports)
+        .with_max_transfer_fee_bps(bot_cfg.max_transfer_fee_bps)
+        .with_liquidity_floor(Arc::clone(&liquidity_floor)));
+
+    // 4.3.4 Shared outbound HTTP client (per-host timeouts, retries and failure metrics)
+    let http = Arc::new(executor::http::HttpClient::default()
+        .with_overrides(&bot_cfg.http_host_policies)
+        .map_err(|e| anyhow::anyhow!(e))?);
+
+    // 4.3.5 Flash-Loan Liquidity (optional; only the Jito executor can bracket bundles with it)
+    #[cfg(not(feature = "paper-only"))]
+    let mut flash_loans: Option<Arc<executor::flash_loan::SolendFlashLoanProvider>> = None;
+    #[cfg(not(feature = "paper-only"))]
+    let flash_reserves = bot_cfg.flash_loan_reserves().map_err(|e| anyhow::anyhow!(e))?;
+    #[cfg(not(feature = "paper-only"))]
+    if !flash_reserves.is_empty() {
+        let rpc_url = bot_cfg.rpc_url.clone();
+        let loaded = tokio::task::spawn_blocking(move || executor::flash_loan::SolendFlashLoanProvider::load(
+            &solana_client::rpc_client::RpcClient::new(rpc_url),
+            executor::flash_loan::SOLEND_PROGRAM_ID,
+            &flash_reserves,
+        )).await?;
+        match loaded {
+            Ok(provider) => {
+                for reserve in provider.reserves() {
+                    info!("🏦 Flash-loan reserve {} ({}), fee {}bps", reserve.address, reserve.liquidity_mint, reserve.flash_loan_fee_bps);
+                }
+                flash_loans = Some(Arc::new(provider));
+            }
+            Err(e) => warn!("❌ Flash-loan reserves unavailable: {}. Continuing without flash loans.", e),
+        }
+    }
 
     // 4.4 Initialize Execution Engine (Abstracted)
     info!("⚡ Initializing Execution Port (Jito preference)...");
-    let execution_port: Arc<dyn strategy::ports::ExecutionPort> = if bot_cfg.jito_url.is_empty() {
-        info!("⚠️ Jito URL empty. Falling back to Legacy RPC Executor.");
-        Arc::new(executor::legacy::LegacyExecutor::new(
-            &bot_cfg.rpc_url,
-            solana_sdk::signature::Keypair::from_bytes(&payer.to_bytes()).map_err(|e| anyhow::anyhow!("Keypair clone failed: {}", e))?,
-            Some(Arc::clone(&pool_fetcher) as Arc<dyn strategy::ports::PoolKeyProvider>),
-        ))
-    } else {
-        match executor::jito::JitoExecutor::new(
-            &bot_cfg.jito_url,
-            &payer,
-            &bot_cfg.rpc_url,
-            bot_cfg.helius_sender_url.clone(),
-            bot_cfg.fee_strategy.clone(),
-            Some(Arc::clone(&pool_fetcher) as Arc<dyn strategy::ports::PoolKeyProvider>),
-            Some(Arc::clone(&metrics) as Arc<dyn strategy::ports::TelemetryPort>),
-        ).await {
-            Ok(jito) => Arc::new(jito),
-            Err(e) => {
-                warn!("❌ Jito initialization failed: {}. Falling back to Legacy.", e);
-                Arc::new(executor::legacy::LegacyExecutor::new(
-                    &bot_cfg.rpc_url,
-                    solana_sdk::signature::Keypair::from_bytes(&payer.to_bytes()).map_err(|e| anyhow::anyhow!("Keypair clone failed: {}", e))?,
-                    Some(Arc::clone(&pool_fetcher) as Arc<dyn strategy::ports::PoolKeyProvider>),
-                ))
+    let mut paper_executor: Option<Arc<executor::paper::PaperExecutor>> = None;
+    // Filled by the wallet manager, read by the executors when they bracket a bundle's legs
+    let ata_cache: Arc<executor::token_accounts::AtaCache> = Arc::default();
+    let token_accounts = executor::token_accounts::TokenAccountPolicy {
+        create_atas: bot_cfg.auto_create_atas,
+        wrap_sol: bot_cfg.wrap_sol,
+        unwrap_sol: bot_cfg.unwrap_wsol,
+    };
+    info!("📦 Token accounts: create ATAs {}, wrap SOL {}, unwrap WSOL {}",
+        token_accounts.create_atas, token_accounts.wrap_sol, token_accounts.unwrap_sol);
+    let wallet_mgr = Arc::new(WalletManager::new(Arc::clone(&rpc_pool)).with_ata_cache(Arc::clone(&ata_cache)));
+    // Set when a standby signer is configured; /rotate_wallet and key failures switch between the two
+    #[cfg(not(feature = "paper-only"))]
+    let mut signer_failover: Option<Arc<wallet_failover::WalletFailover>> = None;
+    #[cfg(not(feature = "paper-only"))]
+    let (failover_alert_tx, mut failover_alert_rx) = mpsc::unbounded_channel::<(alerts::AlertSeverity, String)>();
+    #[cfg(not(feature = "paper-only"))]
+    let durable_nonce = match bot_cfg.durable_nonce().map_err(|e| anyhow::anyhow!(e))? {
+        Some(account) => {
+            let nonce = Arc::new(executor::nonce::DurableNonce::new(account, payer.pubkey()));
+            match nonce.hash(&rpc_pool).await {
+                Ok(_) => {
+                    info!("🔐 Durable nonce {} ready for fallback transactions", account);
+                    Some(nonce)
+                }
+                Err(e) => {
+                    warn!("❌ Durable nonce unusable: {}. Fallback transactions use recent blockhashes.", e);
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+    let execution_port: Arc<dyn strategy::ports::ExecutionPort> = match bot_cfg.mode {
+        _ if bot_cfg.external_execution => {
+            info!("📡 External execution: publishing opportunities (webhook: {}, NATS: {}) instead of trading",
+                bot_cfg.opportunity_webhook().is_some(), bot_cfg.opportunity_nats().map(|_| bot_cfg.opportunity_nats_subject.as_str()).unwrap_or("off"));
+            #[cfg(not(feature = "paper-only"))]
+            {
+                flash_loans = None;
+            }
+            let mut publisher = executor::publisher::OpportunityPublisher::new(
+                payer.pubkey(),
+                Arc::clone(&pool_fetcher) as Arc<dyn strategy::ports::PoolKeyProvider>,
+                Arc::clone(&http),
+                bot_cfg.opportunity_webhook().map(str::to_string),
+            );
+            if let Some(url) = bot_cfg.opportunity_nats() {
+                publisher = publisher.connect_nats(url, bot_cfg.opportunity_nats_subject.trim().to_string()).await?;
+            }
+            Arc::new(publisher)
+        }
+        config::ExecutionMode::Simulation => {
+            info!("🧻 Simulation mode: paper trading (landing {}ms, failure rate {:.0}%, slippage {}bps)",
+                bot_cfg.paper_landing_delay_ms, bot_cfg.paper_failure_rate * 100.0, bot_cfg.paper_slippage_bps);
+            #[cfg(not(feature = "paper-only"))]
+            {
+                flash_loans = None;
+            }
+            let paper = Arc::new(executor::paper::PaperExecutor::new(
+                payer.pubkey(),
+                Some(Arc::clone(&metrics) as Arc<dyn strategy::ports::TelemetryPort>),
+                std::time::Duration::from_millis(bot_cfg.paper_landing_delay_ms),
+                bot_cfg.paper_failure_rate,
+                bot_cfg.paper_slippage_bps,
+            ).with_rng(Arc::clone(&rng)));
+            paper_executor = Some(Arc::clone(&paper));
+            paper
+        }
+        // Config validation already turned live modes away; the executors below are not in this build
+        #[cfg(feature = "paper-only")]
+        _ => anyhow::bail!("{:?} needs live execution, which this paper-only build leaves out", bot_cfg.mode),
+        #[cfg(not(feature = "paper-only"))]
+        _ => {
+            // One executor per signer: the primary keypair, then the standby when STANDBY_KEYPAIR_PATH is set
+            let mut live: Vec<Arc<dyn strategy::ports::ExecutionPort>> = Vec::new();
+            for signer in std::iter::once(&payer).chain(standby.as_ref()) {
+                let is_primary = live.is_empty();
+                let port: Arc<dyn strategy::ports::ExecutionPort> = if bot_cfg.jito_url.is_empty() {
+                    info!("⚠️ Jito URL empty. Falling back to Legacy RPC Executor.");
+                    flash_loans = None;
+                    let mut legacy = executor::legacy::LegacyExecutor::new(
+                        Arc::clone(&rpc_pool),
+                        solana_sdk::signature::Keypair::from_bytes(&signer.to_bytes()).map_err(|e| anyhow::anyhow!("Keypair clone failed: {}", e))?,
+                        Some(Arc::clone(&pool_fetcher) as Arc<dyn strategy::ports::PoolKeyProvider>),
+                    ).with_token_accounts(token_accounts, Arc::clone(&ata_cache));
+                    if let Some(nonce) = durable_nonce.as_ref().filter(|_| is_primary) {
+                        legacy = legacy.with_durable_nonce(Arc::clone(nonce));
+                    }
+                    Arc::new(legacy)
+                } else {
+                    match executor::jito::JitoExecutor::new(
+                        &bot_cfg.jito_url,
+                        signer,
+                        Arc::clone(&rpc_pool),
+                        bot_cfg.fee_strategy.clone(),
+                        Some(Arc::clone(&pool_fetcher) as Arc<dyn strategy::ports::PoolKeyProvider>),
+                        Some(Arc::clone(&metrics) as Arc<dyn strategy::ports::TelemetryPort>),
+                    ).await {
+                        Ok(mut jito) => {
+                            jito.set_rng(Arc::clone(&rng));
+                            jito.set_privacy(executor::privacy::PrivacyConfig::new(
+                                bot_cfg.privacy_mode,
+                                bot_cfg.privacy_max_jitter_ms,
+                                bot_cfg.privacy_decoy_accounts,
+                            ));
+                            jito.set_tip_policy(bot_cfg.tip_floor_policy().map_err(|e| anyhow::anyhow!(e))?);
+                            jito.set_http_client(Arc::clone(&http));
+                            if let Some(url) = bot_cfg.helius_sender_url.clone().filter(|u| !u.trim().is_empty()) {
+                                jito.set_priority_fee_url(url);
+                            }
+                            jito.set_tx_senders(bot_cfg.tx_sender_chain(&rpc_pool, &http).map_err(|e| anyhow::anyhow!(e))?);
+                            jito.set_compute_budget(executor::compute_budget::ComputeBudgetPolicy {
+                                simulate: bot_cfg.compute_unit_simulate,
+                                margin_bps: bot_cfg.compute_unit_margin_bps,
+                                max_priority_fee_lamports: bot_cfg.priority_fee_budget_lamports,
+                                ..Default::default()
+                            });
+                            jito.set_endpoint_health(executor::endpoint_health::EndpointHealthPolicy {
+                                window: std::time::Duration::from_secs(bot_cfg.jito_endpoint_window_secs),
+                                min_samples: bot_cfg.jito_endpoint_min_samples,
+                                min_success_rate: bot_cfg.jito_endpoint_min_success_rate,
+                                probe_interval: std::time::Duration::from_secs(bot_cfg.jito_endpoint_probe_secs),
+                            });
+                            jito.start_endpoint_probes();
+                            jito.set_channel_warmup(executor::channel_warmer::WarmupPolicy {
+                                warmup_interval: std::time::Duration::from_secs(bot_cfg.jito_warmup_secs),
+                                max_idle: std::time::Duration::from_secs(bot_cfg.jito_max_idle_secs),
+                            });
+                            jito.start_channel_warmer();
+                            jito.set_bundle_tracker(Arc::new(executor::bundle_tracker::BundleTracker::new(
+                                Arc::clone(&http),
+                                Some(Arc::clone(&metrics) as Arc<dyn strategy::ports::TelemetryPort>),
+                            )));
+
+                            let lookup_table = bot_cfg.lookup_table_address.as_deref()
+                                .filter(|a| !a.is_empty())
+                                .and_then(|a| a.parse::<Pubkey>().ok());
+                            // The table's authority is the primary signer; the standby sends without one
+                            if is_primary && (lookup_table.is_some() || bot_cfg.lookup_table_auto_create) {
+                                let tables = Arc::new(executor::lookup_table::LookupTableManager::new(
+                                    rpc_pool.blocking_client(),
+                                    Arc::new(solana_sdk::signature::Keypair::from_bytes(&signer.to_bytes()).map_err(|e| anyhow::anyhow!("Keypair clone failed: {}", e))?),
+                                    lookup_table,
+                                    bot_cfg.lookup_table_min_uses,
+                                ));
+                                jito.set_lookup_tables(Arc::clone(&tables));
+                                tokio::spawn(async move {
+                                    if tables.address().is_none() {
+                                        let creator = Arc::clone(&tables);
+                                        match tokio::task::spawn_blocking(move || creator.create()).await {
+                                            Ok(Ok(address)) => info!("📇 Set LOOKUP_TABLE_ADDRESS={} to reuse this table", address),
+                                            Ok(Err(e)) => warn!("📇 Lookup table creation failed: {}", e),
+                                            Err(e) => warn!("📇 Lookup table creation panicked: {}", e),
+                                        }
+                                    }
+                                    tables.run(std::time::Duration::from_secs(30)).await;
+                                });
+                            }
+                            jito.set_token_accounts(token_accounts, Arc::clone(&ata_cache));
+                            if let Some(nonce) = durable_nonce.as_ref().filter(|_| is_primary) {
+                                jito.set_durable_nonce(Arc::clone(nonce));
+                            }
+                            if bot_cfg.race_mode && is_primary {
+                                match (&durable_nonce, bot_cfg.helius_sender_url.clone()) {
+                                    (Some(_), Some(url)) => jito.set_race_sender(Arc::new(executor::tx_sender::EndpointSender::helius(url))),
+                                    _ => warn!("🏁 RACE_MODE off: the durable nonce failed its startup check"),
+                                }
+                            }
+                            if let Some(provider) = &flash_loans {
+                                jito.set_flash_loans(Arc::clone(provider) as Arc<dyn strategy::ports::FlashLoanProvider>);
+                            }
+                            Arc::new(jito)
+                        }
+                        Err(e) => {
+                            warn!("❌ Jito initialization failed: {}. Falling back to Legacy.", e);
+                            flash_loans = None;
+                            let mut legacy = executor::legacy::LegacyExecutor::new(
+                                Arc::clone(&rpc_pool),
+                                solana_sdk::signature::Keypair::from_bytes(&signer.to_bytes()).map_err(|e| anyhow::anyhow!("Keypair clone failed: {}", e))?,
+                                Some(Arc::clone(&pool_fetcher) as Arc<dyn strategy::ports::PoolKeyProvider>),
+                            ).with_token_accounts(token_accounts, Arc::clone(&ata_cache));
+                            if let Some(nonce) = durable_nonce.as_ref().filter(|_| is_primary) {
+                                legacy = legacy.with_durable_nonce(Arc::clone(nonce));
+                            }
+                            Arc::new(legacy)
+                        }
+                    }
+                };
+                live.push(port);
+            }
+            let primary = live.remove(0);
+            match live.pop() {
+                Some(standby_port) => {
+                    let failover = Arc::new(wallet_failover::WalletFailover::new(
+                        primary,
+                        standby_port,
+                        wallet_failover::FailoverPolicy {
+                            threshold: bot_cfg.wallet_failover_threshold,
+                            min_sol_lamports: bot_cfg.wallet_failover_min_sol_lamports,
+                        },
+                        Arc::clone(&wallet_mgr),
+                        Arc::clone(&metrics),
+                        failover_alert_tx.clone(),
+                    ));
+                    signer_failover = Some(Arc::clone(&failover));
+                    failover
+                }
+                None => primary,
             }
         }
     };
     
 
     // 4.5 Initialize Strategy Engine (The Brain)
-    let ai_model = match strategy::adapters::ONNXModelAdapter::from_file("ai_model.onnx") {
+    let champion = match strategy::adapters::ONNXModelAdapter::from_file("ai_model.onnx") {
         Ok(model) => {
-            info!("🧠 AI Model loaded successfully (ai_model.onnx)");
-            Some(Arc::new(model) as Arc<dyn strategy::ports::AIModelPort>)
+            info!("🧠 AI Model loaded successfully (ai_model.onnx, feature schema v{})", model.schema().version);
+            Some(Arc::new(model))
         }
         Err(e) => {
             warn!("⚠️ Failed to load AI model: {}. Running in heuristic mode.", e);
             None
         }
     };
+    let challenger = match bot_cfg.ai_challenger_model_path.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(_) if champion.is_none() => {
+            warn!("⚠️ AI_CHALLENGER_MODEL_PATH ignored: shadow evaluation needs ai_model.onnx as the champion");
+            None
+        }
+        Some(path) => match strategy::adapters::ONNXModelAdapter::from_file(path) {
+            Ok(model) => {
+                info!("🥊 Challenger model {} scored in shadow (feature schema v{}), never acted on", path, model.schema().version);
+                Some(Arc::new(model))
+            }
+            Err(e) => {
+                warn!("⚠️ Failed to load challenger model {}: {}. No shadow evaluation.", path, e);
+                None
+            }
+        },
+        None => None,
+    };
+    let ai_model = champion.as_ref().map(|champion| match &challenger {
+        Some(challenger) => Arc::new(strategy::adapters::ShadowModel::new(
+            Arc::clone(champion) as Arc<dyn strategy::ports::AIModelPort>,
+            Arc::clone(challenger) as Arc<dyn strategy::ports::AIModelPort>,
+            bot_cfg.ai_confidence_threshold,
+        )) as Arc<dyn strategy::ports::AIModelPort>,
+        None => Arc::clone(champion) as Arc<dyn strategy::ports::AIModelPort>,
+    });
 
-    let engine = Arc::new(StrategyEngine::new(
-        Some(execution_port),
-        None, // No simulation in prod
+    let model_drift = ai_model.as_ref().map(|_| Arc::new(strategy::analytics::model_drift::ModelDriftMonitor::new(bot_cfg.drift_policy())));
+    if bot_cfg.ai_model_reload_secs > 0 {
+        let interval = std::time::Duration::from_secs(bot_cfg.ai_model_reload_secs);
+        if let Some(champion) = &champion {
+            // A new champion gets a fresh drift baseline
+            let drift = model_drift.clone();
+            tokio::spawn(Arc::clone(champion).watch("champion", interval, move |_| {
+                if let Some(drift) = &drift {
+                    drift.reset();
+                }
+            }));
+        }
+        if let Some(challenger) = &challenger {
+            tokio::spawn(Arc::clone(challenger).watch("challenger", interval, |_| {}));
+        }
+    }
+    let simulator = executor::bundle_sim::SimulationBackend::parse(&bot_cfg.bundle_simulation)
+        .map_err(|e| anyhow::anyhow!(e))?
+        .map(|backend| {
+            let jito_url = if bot_cfg.jito_simulate_url.is_empty() { &bot_cfg.rpc_url } else { &bot_cfg.jito_simulate_url };
+            info!("🧪 Bundle simulation via {}: bundles that fail it are dropped", backend.name());
+            Arc::new(executor::bundle_sim::ChainSimulator::new(backend, Arc::clone(&rpc_pool), Arc::clone(&http), jito_url))
+                as Arc<dyn strategy::ports::BundleSimulator>
+        });
+    let engine = StrategyEngine::new(
+        Some(Arc::clone(&execution_port)),
+        simulator,
         ai_model,
         Some(Arc::clone(&performance_tracker)),
         Some(Arc::clone(&safety_checker)),
         Some(Arc::clone(&metrics) as Arc<dyn strategy::ports::TelemetryPort>),
         Some(intel_port),
+    );
+    let rejection_journal = strategy::analytics::rejections::RejectionJournal::new(
+        &platform::log_path("rejections.csv"),
+        bot_cfg.rejection_log_max_per_sec,
+    ).await;
+    let split_policy = strategy::split::SplitPolicy::new(
+        bot_cfg.split_max_chunks,
+        std::time::Duration::from_millis(bot_cfg.split_requote_delay_ms),
+    ).with_route_overrides(&bot_cfg.split_route_chunks).map_err(|e| anyhow::anyhow!(e))?;
+    if split_policy.is_enabled() {
+        info!("✂️ Split execution ENABLED (default chunks: {}, route overrides: {})", split_policy.default_chunks, split_policy.route_chunks.len());
+    }
+    let execution_lane = Arc::new(strategy::priority::ExecutionLane::new(
+        bot_cfg.execution_slots,
+        bot_cfg.priority_execution_slots,
+        bot_cfg.priority_profit_percentile,
+        bot_cfg.priority_profit_window,
     ));
+    let hop_costs = strategy::hop_costs::HopCostTable::parse(&bot_cfg.hop_cost_table).map_err(|e| anyhow::anyhow!(e))?;
+    if !hop_costs.is_empty() {
+        info!("🪜 Hop cost table ACTIVE: {}", bot_cfg.hop_cost_table);
+    }
+    let cycle_search = strategy::arb::CycleSearchMode::parse(&bot_cfg.cycle_search_mode).map_err(|e| anyhow::anyhow!(e))?;
+    if cycle_search == strategy::arb::CycleSearchMode::BellmanFord {
+        info!("🧭 Cycle search: Bellman-Ford negative-cycle mode");
+    }
+    let engine = if bot_cfg.sizing_max_lamports > 0 {
+        info!("📐 Optimal sizing ACTIVE: {}-{} lamports", bot_cfg.sizing_min_lamports, bot_cfg.sizing_max_lamports);
+        engine.with_sizing(strategy::sizing::SizingPolicy::new(bot_cfg.sizing_min_lamports, bot_cfg.sizing_max_lamports))
+    } else {
+        engine
+    };
+    #[cfg(not(feature = "paper-only"))]
+    let engine = match &flash_loans {
+        Some(provider) => engine.with_flash_loans(strategy::flash_loan::FlashLoanPolicy::new(
+            Arc::clone(provider) as Arc<dyn strategy::ports::FlashLoanProvider>,
+            Arc::clone(&inventory),
+        )),
+        None => engine,
+    };
+    let feature_flags = strategy::flags::FeatureFlags::parse(&bot_cfg.feature_flags).map_err(|e| anyhow::anyhow!(e))?;
+    for flag in strategy::flags::Flag::ALL {
+        if let Some(percent) = feature_flags.rollout(flag) {
+            info!("🚩 Feature flag {}: {}% of routes", flag.name(), percent);
+        }
+    }
+    let engine = engine.with_feature_flags(feature_flags);
+    let engine = match (&oracle_guard, &price_oracle) {
+        (Some(guard), Some(oracle)) => engine.with_oracle_guard(Arc::clone(guard)).with_price_oracle(Arc::clone(oracle)),
+        _ => engine,
+    };
+    let engine = if bot_cfg.jupiter_quote_url.is_empty() {
+        engine
+    } else {
+        info!("🪐 Jupiter quote cross-check ACTIVE: tolerance {} bps", bot_cfg.jupiter_tolerance_bps);
+        let jupiter = Arc::new(executor::jupiter::JupiterQuotes::new(Arc::clone(&http), &bot_cfg.jupiter_quote_url));
+        engine.with_quote_check(Arc::new(strategy::safety::quote_check::QuoteCrossCheck::new(jupiter, bot_cfg.jupiter_tolerance_bps)))
+    };
+    let execution_policy = Arc::new(strategy::policy::ExecutionPolicy::parse(&bot_cfg.execution_policy).map_err(|e| anyhow::anyhow!(e))?);
+    info!("🧾 Execution policy: {}", execution_policy.describe());
+    if !execution_policy.is_active(strategy::policy::Gate::SizeCap) {
+        warn!("⚠️ EXECUTION_POLICY leaves out size_cap: MAX_TRADE_SIZE_LAMPORTS is not enforced");
+    }
+    let engine = engine.with_execution_policy(Arc::clone(&execution_policy));
+    let pool_rate_limit = strategy::pool_rate_limit::PoolRateLimiter::parse_overrides(&bot_cfg.pool_rate_limit_overrides)
+        .map_err(|e| anyhow::anyhow!(e))?
+        .into_iter()
+        .fold(strategy::pool_rate_limit::PoolRateLimiter::new(bot_cfg.pool_rate_limit_per_min),
+            |limiter, (a, b, limit)| limiter.with_pair_override(a, b, limit));
+    info!("⏳ Pool rate limit: {} routes/min per pool ({} pair override(s))",
+        bot_cfg.pool_rate_limit_per_min, bot_cfg.pool_rate_limit_overrides.split(',').filter(|e| !e.trim().is_empty()).count());
+    let engine = engine.with_pool_rate_limit(Arc::new(pool_rate_limit))
+        .with_exposure_guard(Arc::clone(&risk_mgr) as Arc<dyn strategy::ports::ExposurePort>)
+        .with_pause_flag(Arc::clone(&metrics.is_paused));
+    // Pre-trade pool state of every dispatched route, archived alongside market data
+    let engine = match &recorder {
+        Some(rec) => {
+            let (archive, mut snapshots) = strategy::analytics::depth_snapshot::DepthArchive::new(256);
+            let rec = Arc::clone(rec);
+            tokio::spawn(async move {
+                while let Some(snapshot) = snapshots.recv().await {
+                    rec.record_depth_snapshot(&snapshot).await;
+                }
+            });
+            engine.with_depth_archive(Arc::new(archive))
+        }
+        None => engine,
+    };
+    // Only keep per-hop decision detail when someone is looking at it
+    let no_tui = env::args().any(|a| a == "--no-tui");
+    let decision_feed = Arc::new(strategy::analytics::decisions::DecisionFeed::default());
+    let engine = if no_tui { engine } else { engine.with_decision_feed(Arc::clone(&decision_feed)) };
+    let cycle_cache = (bot_cfg.cycle_reprice_top_k > 0)
+        .then(|| Arc::new(strategy::cycle_cache::CycleCache::new(bot_cfg.cycle_reprice_top_k)));
+    let engine = match &cycle_cache {
+        Some(cache) => engine.with_cycle_cache(Arc::clone(cache)),
+        None => engine,
+    };
+    let engine = match &model_drift {
+        Some(monitor) => engine.with_model_drift(Arc::clone(monitor)),
+        None => engine,
+    };
+    let engine = if bot_cfg.ev_gate_enabled {
+        const BASE_FEE_LAMPORTS: u64 = 5_000; // One signature
+        let policy = strategy::ev_gate::EvPolicy {
+            margin_lamports: bot_cfg.ev_margin_lamports,
+            failure_fee_lamports: BASE_FEE_LAMPORTS + bot_cfg.priority_fee_budget_lamports,
+        };
+        info!("🎲 EV gate ACTIVE: margin {} lamports, {} lamports fees at risk per trade", policy.margin_lamports, policy.failure_fee_lamports);
+        engine.with_ev_gate(policy)
+    } else {
+        engine
+    };
+    let engine = match &trade_journal {
+        Some(journal) => engine.with_trade_journal(Arc::clone(journal) as Arc<dyn strategy::ports::TradeJournalPort>),
+        None => engine,
+    };
+    let engine = match &training_export {
+        Some(export) => engine.with_training_export(Arc::clone(export) as Arc<dyn strategy::ports::TrainingExportPort>),
+        None => engine,
+    };
+    let engine = Arc::new(
+        engine
+            .with_rejection_journal(Arc::new(rejection_journal))
+            .with_route_blacklist(Arc::clone(&route_blacklist))
+            .with_in_flight(Arc::clone(&in_flight))
+            .with_split_policy(split_policy)
+            .with_hop_costs(hop_costs)
+            .with_cycle_search(cycle_search)
+            .with_edge_ttl(bot_cfg.edge_ttl_secs)
+            .with_incremental_search(bot_cfg.incremental_search_enabled)
+            .with_parallel_search(
+                bot_cfg.parallel_search_min_hops,
+                (bot_cfg.cycle_search_budget_us > 0).then(|| std::time::Duration::from_micros(bot_cfg.cycle_search_budget_us)),
+            )
+            .with_split_routing(bot_cfg.split_route_max_pools)
+            .with_liquidity_floor(Arc::clone(&liquidity_floor))
+            .with_execution_lane(execution_lane)
+            .with_slot_locks(Arc::new(strategy::slot_lock::SlotLockService::new()))
+            .with_params(bot_cfg.strategy_params())
+    );
+    let strategy_params = engine.params();
+    info!("🎚️ Strategy thresholds: max trade {} lamports, min profit {} lamports, sanity cap {}x input, impact cap {} bps",
+        strategy_params.max_trade_size_lamports, strategy_params.min_profit_threshold_lamports,
+        strategy_params.sanity_profit_factor, strategy_params.max_price_impact_bps);
+    if let Some(paper) = &paper_executor {
+        paper.attach(&engine);
+    }
+
+    let token_metadata = Arc::new(token_metadata::TokenMetadata::new(Arc::clone(&rpc_pool)));
 
-    let wallet_mgr = Arc::new(WalletManager::new(&bot_cfg.rpc_url));
+    // 4.5.1 Inventory snapshot: sizes trades, and routes sized past it are flash-loan funded
+    {
+        #[cfg(not(feature = "paper-only"))]
+        let token_mints: Vec<Pubkey> = flash_loans.iter()
+            .flat_map(|provider| provider.reserves().map(|r| r.liquidity_mint))
+            .filter(|m| *m != mev_core::constants::SOL_MINT)
+            .collect();
+        #[cfg(feature = "paper-only")]
+        let token_mints: Vec<Pubkey> = Vec::new();
+        let (wallet, inventory, signer) = (Arc::clone(&wallet_mgr), Arc::clone(&inventory), Arc::clone(&execution_port));
+        tokio::spawn(async move {
+            const FEE_RESERVE_LAMPORTS: u64 = 50_000_000; // Never trade the SOL needed for fees and tips
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(30));
+            loop {
+                ticker.tick().await;
+                // The wallet trades are signed for; changes when the signer is rotated
+                let owner = *signer.pubkey();
+                match wallet.get_sol_balance(&owner).await {
+                    Ok(lamports) => inventory.set(mev_core::constants::SOL_MINT, lamports.saturating_sub(FEE_RESERVE_LAMPORTS)),
+                    Err(e) => warn!("⚠️ Inventory refresh failed: {}", e),
+                }
+                for mint in &token_mints {
+                    if let Ok(amount) = wallet.get_token_balance(&owner, mint).await {
+                        inventory.set(*mint, amount);
+                    }
+                }
+                // ATAs our bundles created stop being re-created once they show up on chain
+                match wallet.refresh_ata_cache(&owner).await {
+                    Ok(0) => {}
+                    Ok(landed) => debug!("📦 {} new ATA(s) confirmed", landed),
+                    Err(e) => debug!("📦 ATA refresh failed: {}", e),
+                }
+            }
+        });
+    }
     
     // 4.6 Initialize Alerting
     let telegram_config = if let (Some(token), Some(chat_id)) = (&bot_cfg.telegram_bot_token, &bot_cfg.telegram_chat_id) {
@@ -230,19 +895,99 @@ async fn main() -> anyhow::Result<()> {
     } else {
         None
     };
-    let alert_mgr = Arc::new(alerts::AlertManager::new(
+    let drawdown_breaker = Arc::new(drawdown::DrawdownBreaker::new(drawdown::DrawdownPolicy {
+        max_drawdown_lamports: bot_cfg.drawdown_max_lamports,
+        window: std::time::Duration::from_secs(bot_cfg.drawdown_window_secs),
+        cooldown: std::time::Duration::from_secs(bot_cfg.drawdown_cooldown_secs),
+    }));
+    let schedule = Arc::new(schedule::StrategySchedule::parse(&bot_cfg.strategy_schedule).map_err(|e| anyhow::anyhow!(e))?);
+    let alert_mgr = alerts::AlertManager::new(
         bot_cfg.discord_webhook.clone(), 
         telegram_config,
         bot_cfg.ntfy_topic.clone(),
-    ));
+        Arc::clone(&http),
+    ).with_drawdown_breaker(Arc::clone(&drawdown_breaker))
+     .with_schedule(Arc::clone(&schedule));
+    #[cfg(not(feature = "paper-only"))]
+    let alert_mgr = match &signer_failover {
+        Some(failover) => alert_mgr.with_wallet_failover(Arc::clone(failover)),
+        None => alert_mgr,
+    };
+    let alert_mgr = Arc::new(alert_mgr);
     tracing::info!("🔔 Alerting configured: Discord={}, Telegram={}", 
         bot_cfg.discord_webhook.is_some(),
         bot_cfg.telegram_bot_token.is_some() && bot_cfg.telegram_chat_id.is_some()
     );
 
     // 4.3.6 Initialize Telemetry
-    mev_core::telemetry::init_metrics();
-    tokio::spawn(telemetry::serve_metrics());
+    mev_core::telemetry::init_metrics(&bot_cfg.strategy_id);
+    info!("📊 Metrics namespace: {}_*", mev_core::telemetry::metric_namespace(&bot_cfg.strategy_id));
+    let monitored_mints: std::collections::BTreeSet<Pubkey> = config::MONITORED_POOLS.iter()
+        .flat_map(|p| [p.token_a, p.token_b])
+        .filter(|m| *m != mev_core::constants::SOL_MINT)
+        .collect();
+    let capital_at_risk = Arc::new(capital_at_risk::CapitalAtRisk::new(
+        Arc::clone(&metrics),
+        Arc::clone(&risk_mgr),
+        Arc::clone(&wallet_mgr),
+        Arc::clone(&engine),
+        Arc::clone(&route_blacklist),
+        Arc::clone(&in_flight),
+        payer.pubkey(),
+        monitored_mints.into_iter().collect(),
+        Arc::clone(&token_metadata),
+    ));
+    tokio::spawn(telemetry::serve_metrics(Some(Arc::clone(&capital_at_risk)), Some(quote_errors)));
+
+    // Inventory manager: values every token the wallet holds and sweeps strays back to SOL
+    let keep_mints = inventory_manager::parse_mints(&bot_cfg.inventory_keep_mints).map_err(|e| anyhow::anyhow!(e))?;
+    let inventory_mgr = (bot_cfg.inventory_poll_secs > 0).then(|| {
+        Arc::new(inventory_manager::InventoryManager::new(
+            Arc::clone(&wallet_mgr),
+            Arc::clone(&engine),
+            Arc::clone(&execution_port),
+            Arc::clone(&in_flight),
+            Arc::clone(&metrics),
+            Arc::clone(&token_metadata),
+            inventory_manager::SweepPolicy {
+                // Nothing executes under external execution
+                enabled: bot_cfg.inventory_sweep_enabled && !bot_cfg.external_execution,
+                min_value_lamports: bot_cfg.inventory_sweep_min_lamports,
+                keep_mints,
+                tip_lamports: bot_cfg.jito_tip_lamports,
+                max_slippage_bps: bot_cfg.max_slippage_ceiling,
+            },
+        ))
+    });
+    match &inventory_mgr {
+        Some(manager) => {
+            info!("🧺 Inventory manager: poll every {}s, sweep to SOL {} (min {} lamports)",
+                bot_cfg.inventory_poll_secs,
+                if bot_cfg.inventory_sweep_enabled && !bot_cfg.external_execution { "ON" } else { "OFF" },
+                bot_cfg.inventory_sweep_min_lamports);
+            tokio::spawn(Arc::clone(manager).run(std::time::Duration::from_secs(bot_cfg.inventory_poll_secs)));
+        }
+        None => info!("🧺 Inventory manager disabled (INVENTORY_POLL_SECS=0)"),
+    }
+
+    // 4.3.7 REST Control API (only with a token configured)
+    let hot_config = Arc::new(control_api::HotConfig::new(&bot_cfg, engine.params_handle()));
+    match bot_cfg.control_api_token.as_deref().filter(|t| !t.is_empty()) {
+        Some(token) => {
+            tokio::spawn(control_api::serve(bot_cfg.control_api_bind.clone(), control_api::ControlState {
+                token: Arc::from(token),
+                metrics: Arc::clone(&metrics),
+                risk_mgr: Arc::clone(&risk_mgr),
+                hot_config: Arc::clone(&hot_config),
+                capital_at_risk,
+                drawdown: Arc::clone(&drawdown_breaker),
+                policy: Arc::clone(&execution_policy),
+                inventory: inventory_mgr.clone(),
+                started: std::time::Instant::now(),
+            }));
+        }
+        None => info!("🎛️ Control API disabled (set CONTROL_API_TOKEN to enable)"),
+    }
     
     // Start health monitor (status checks every 5 minutes + hourly summary)
     tokio::spawn(alerts::monitor_health(
@@ -253,6 +998,63 @@ async fn main() -> anyhow::Result<()> {
         bot_start_time
     ));
 
+    // Drawdown breaker: halts through is_paused, resumes after the cooldown
+    if drawdown_breaker.is_enabled() {
+        info!("🧯 Drawdown breaker: halt at -{:.4} SOL within {}s, cooldown {}s (0 = manual /resume)",
+            bot_cfg.drawdown_max_lamports as f64 / 1e9, bot_cfg.drawdown_window_secs, bot_cfg.drawdown_cooldown_secs);
+        tokio::spawn(Arc::clone(&drawdown_breaker).run(
+            Arc::clone(&metrics),
+            Arc::clone(&alert_mgr),
+            std::time::Duration::from_secs(5),
+        ));
+    } else {
+        info!("🧯 Drawdown breaker disabled (DRAWDOWN_MAX_LAMPORTS=0)");
+    }
+
+    if let Some(monitor) = &model_drift {
+        info!("🧠 Model drift monitor: window {}, fallback {}", bot_cfg.ai_drift_window, bot_cfg.ai_drift_fallback);
+        tokio::spawn(model_drift::run(Arc::clone(monitor), Arc::clone(&alert_mgr), std::time::Duration::from_secs(30)));
+    }
+
+    // Wallet watchdog: backstop on the chain balance itself, independent of PnL accounting
+    let balance_watchdog = Arc::new(balance_watchdog::BalanceWatchdog::new(
+        bot_cfg.wallet_watchdog_max_drop_bps,
+        bot_cfg.wallet_watchdog_auto_pause,
+    ));
+    if balance_watchdog.is_enabled() {
+        info!("🐕 Wallet watchdog: alert at {:.2}% below high-water (auto-pause: {})",
+            bot_cfg.wallet_watchdog_max_drop_bps as f64 / 100.0, bot_cfg.wallet_watchdog_auto_pause);
+        tokio::spawn(balance_watchdog.run(
+            Arc::clone(&wallet_mgr),
+            payer.pubkey(),
+            Arc::clone(&metrics),
+            Arc::clone(&alert_mgr),
+            std::time::Duration::from_secs(bot_cfg.wallet_watchdog_poll_secs),
+        ));
+    }
+
+    // Layout drift check: catches a program upgrade that moved the offsets our decoders read
+    if bot_cfg.layout_check_interval_secs > 0 {
+        let quotes: Option<Arc<dyn strategy::ports::QuoteSourcePort>> = (!bot_cfg.jupiter_quote_url.is_empty())
+            .then(|| Arc::new(executor::jupiter::JupiterQuotes::new(Arc::clone(&http), &bot_cfg.jupiter_quote_url)) as Arc<dyn strategy::ports::QuoteSourcePort>);
+        info!("🧬 Layout check every {}s: structure{} (auto-pause: {})",
+            bot_cfg.layout_check_interval_secs,
+            if quotes.is_some() { " and price vs Jupiter" } else { " only (no JUPITER_QUOTE_URL)" },
+            bot_cfg.layout_check_auto_pause);
+        let checker = Arc::new(layout_check::LayoutChecker::new(
+            Arc::clone(&rpc_pool),
+            quotes,
+            layout_check::reference_pools(config::MONITORED_POOLS),
+            bot_cfg.layout_check_tolerance_bps,
+            bot_cfg.layout_check_auto_pause,
+        ));
+        tokio::spawn(checker.run(
+            Arc::clone(&metrics),
+            Arc::clone(&alert_mgr),
+            std::time::Duration::from_secs(bot_cfg.layout_check_interval_secs),
+        ));
+    }
+
     // Start Telegram Command Listener (V2)
     tokio::spawn(Arc::clone(&alert_mgr).handle_telegram_commands(
         Arc::clone(&metrics),
@@ -261,6 +1063,44 @@ async fn main() -> anyhow::Result<()> {
         bot_start_time
     ));
 
+    // Forward route blacklist events to alerting
+    let alert_mgr_routes = Arc::clone(&alert_mgr);
+    tokio::spawn(async move {
+        while let Some(msg) = route_alert_rx.recv().await {
+            alert_mgr_routes.send_alert(alerts::AlertSeverity::Warning, "ROUTE BLACKLISTED", &msg, vec![]).await;
+        }
+    });
+
+    // Forward signer rotations to alerting
+    #[cfg(not(feature = "paper-only"))]
+    {
+        let alert_mgr_failover = Arc::clone(&alert_mgr);
+        tokio::spawn(async move {
+            while let Some((severity, msg)) = failover_alert_rx.recv().await {
+                alert_mgr_failover.send_alert(severity, "WALLET ROTATION", &msg, vec![]).await;
+            }
+        });
+    }
+
+    // Recovery sells: intermediate tokens a landed trade left in the wallet go back to its start mint
+    let recovery_seller = (bot_cfg.recovery_sell_enabled && !bot_cfg.external_execution).then(|| {
+        Arc::new(recovery::RecoverySeller::new(
+            Arc::clone(&engine),
+            Arc::clone(&execution_port),
+            Arc::clone(&alert_mgr),
+            Arc::clone(&token_metadata),
+            recovery::RecoveryPolicy {
+                min_output_lamports: bot_cfg.recovery_min_output_lamports,
+                tip_lamports: bot_cfg.jito_tip_lamports,
+                max_slippage_bps: bot_cfg.max_slippage_ceiling,
+            },
+        ))
+    });
+    if let Some(seller) = &recovery_seller {
+        pnl_reconciler.attach_recovery(Arc::downgrade(seller));
+        info!("🧹 Recovery seller ACTIVE: residual tokens worth over {} lamports are sold back", bot_cfg.recovery_min_output_lamports);
+    }
+
     // Start 5-minute periodic weight sync (PostgreSQL)
     let scoring_engine_sync = Arc::clone(&scoring_engine);
     tokio::spawn(async move {
@@ -283,6 +1123,27 @@ async fn main() -> anyhow::Result<()> {
         }
     });
 
+    // 4.3.8 Pool Delisting (unsubscribes go to whichever backend streams pool accounts)
+    let (unsubscribe_tx, unsubscribe_rx) = mpsc::unbounded_channel();
+    let delister = Arc::new(delisting::PoolDelister::new(
+        Arc::clone(&engine),
+        Arc::clone(&scoring_engine),
+        Arc::clone(&alert_mgr),
+        unsubscribe_tx,
+    ));
+
+    // Kelly sizing off the recent record, capped at the risk position limit
+    let position_sizer = (bot_cfg.kelly_fraction > 0.0).then(|| {
+        info!("🎯 Kelly sizing ACTIVE: {:.2}x Kelly after {} trades, {}-{} lamports",
+            bot_cfg.kelly_fraction, bot_cfg.kelly_min_trades, bot_cfg.kelly_min_lamports, risk_mgr.max_position_size_lamports);
+        strategy::position_sizing::KellySizer::new(
+            bot_cfg.kelly_fraction as f64,
+            bot_cfg.kelly_min_trades,
+            bot_cfg.kelly_min_lamports,
+            risk_mgr.max_position_size_lamports,
+        )
+    });
+
     // 4.4 Assemble Context (Composition Root)
     let context = Arc::new(AppContext {
         config: bot_cfg.clone(),
@@ -293,7 +1154,65 @@ async fn main() -> anyhow::Result<()> {
         metrics,
         risk_mgr,
         alert_mgr: Arc::clone(&alert_mgr),
+        hot_config,
+        delister,
+        inventory,
+        position_sizer,
+        schedule: Arc::clone(&schedule),
     });
+    tokio::spawn(schedule::run(Arc::clone(&schedule), Arc::clone(&alert_mgr)));
+
+    // 4.4.0 Slot re-pricing: the cached top cycles are re-quoted together once per slot
+    if let Some(cache) = cycle_cache {
+        info!("🔁 Slot re-pricing ENABLED (top {} cycles per slot)", bot_cfg.cycle_reprice_top_k);
+        tokio::spawn(cycle_repricer::run(Arc::clone(&context), cache));
+    }
+
+    // 4.4.0.1 Full sweep: the cycles incremental search doesn't look at
+    if bot_cfg.cycle_sweep_interval_ms > 0 {
+        info!("🧭 Cycle search: {}, full sweep from SOL every {}ms",
+            if bot_cfg.incremental_search_enabled { "incremental (updated pool only)" } else { "full per update" }, bot_cfg.cycle_sweep_interval_ms);
+        tokio::spawn(cycle_sweep::run(Arc::clone(&context), std::time::Duration::from_millis(bot_cfg.cycle_sweep_interval_ms)));
+    }
+
+    // 4.4.1 Reserve Reconciliation Audit
+    if bot_cfg.reserve_audit_interval_secs > 0 {
+        let reconciler = Arc::new(reconciler::ReserveReconciler::new(
+            &bot_cfg.rpc_url,
+            Arc::clone(&context.engine),
+            Arc::clone(&scoring_engine),
+            bot_cfg.reserve_audit_top_pools,
+            bot_cfg.reserve_audit_threshold_bps,
+        ));
+        info!("🧾 Reserve audit ENABLED (top {} pools every {}s, threshold {} bps)",
+            bot_cfg.reserve_audit_top_pools, bot_cfg.reserve_audit_interval_secs, bot_cfg.reserve_audit_threshold_bps);
+        tokio::spawn(reconciler.run(std::time::Duration::from_secs(bot_cfg.reserve_audit_interval_secs)));
+    }
+
+    // Stale pool pruning keeps search latency bounded as the graph churns
+    if bot_cfg.graph_prune_after_secs > 0 {
+        let engine_prune = Arc::clone(&context.engine);
+        let max_age = bot_cfg.graph_prune_after_secs;
+        info!("🧹 Graph pruning ENABLED (pools silent for {}s, checked every {}s; search TTL {}s)",
+            max_age, bot_cfg.graph_prune_interval_secs, bot_cfg.edge_ttl_secs);
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(bot_cfg.graph_prune_interval_secs));
+        tokio::spawn(async move {
+            loop {
+                interval.tick().await;
+                let pruned = engine_prune.prune_graph(max_age);
+                if pruned.pools > 0 || pruned.nodes > 0 {
+                    info!("🧹 Pruned {} stale pool(s) and {} orphaned mint(s) from the graph", pruned.pools, pruned.nodes);
+                    telemetry::GRAPH_PRUNED_TOTAL.with_label_values(&["pool"]).inc_by(pruned.pools as f64);
+                    telemetry::GRAPH_PRUNED_TOTAL.with_label_values(&["node"]).inc_by(pruned.nodes as f64);
+                }
+            }
+        });
+    }
+
+    // 4.4.2 Meteora DLMM bins, Whirlpool tick arrays and OpenBook book sides, re-read whenever their market updates
+    let dlmm_bins = Arc::new(dlmm_bins::DlmmBinFetcher::new(&bot_cfg.rpc_url, Arc::clone(&context.engine)));
+    let whirlpool_ticks = Arc::new(whirlpool_ticks::WhirlpoolTickFetcher::new(&bot_cfg.rpc_url, Arc::clone(&context.engine)));
+    let openbook_books = Arc::new(openbook_books::OpenBookFetcher::new(&bot_cfg.rpc_url, Arc::clone(&context.engine)));
 
     // 4.5 Pre-flight Wallet Verification
     info!("🧪 Cooling down for RPC stability (3s)...");
@@ -317,7 +1236,7 @@ async fn main() -> anyhow::Result<()> {
                 }
             }
             if !missing_atas.is_empty() {
-                info!("📦 Found {} missing ATAs. Preparing for lazy creation...", missing_atas.len());
+                info!("📦 Found {} missing ATAs. Bundles create them on first use.", missing_atas.len());
             } else {
                 info!("✅ All required ATAs exist.");
             }
@@ -345,20 +1264,13 @@ async fn main() -> anyhow::Result<()> {
 
     match context.wallet_mgr.get_multiple_token_balances(&context.payer.pubkey(), &inventory_mints).await {
         Ok(balances) => {
-            let mut inventory = std::collections::HashMap::new();
-            for (mint, balance) in balances {
-                let symbol = match mint {
-                    mev_core::constants::USDC_MINT => "USDC",
-                    mev_core::constants::JUP_MINT => "JUP ",
-                    mev_core::constants::RAY_MINT => "RAY ",
-                    mev_core::constants::BONK_MINT => "BONK",
-                    mev_core::constants::WIF_MINT => "WIF ",
-                    _ => "UNKN",
-                };
-                info!("   ├─ {}: {:.6} (raw: {})", symbol, balance as f64 / 1e6, balance);
-                inventory.insert(symbol, balance);
+            if let Err(e) = token_metadata.resolve(&inventory_mints).await {
+                warn!("⚠️ Token decimals lookup failed: {}. Unresolved balances are shown raw.", e);
+            }
+            for (mint, balance) in &balances {
+                info!("   ├─ {} (raw: {})", token_metadata.format(mint, *balance), balance);
             }
-            info!("   └─ Total: {} tokens tracked", inventory.len());
+            info!("   └─ Total: {} tokens tracked", balances.len());
         },
         Err(e) => error!("❌ Failed to batch fetch token balances: {}", e),
     }
@@ -368,16 +1280,18 @@ async fn main() -> anyhow::Result<()> {
     let (shutdown_tx, _shutdown_rx) = mpsc::channel::<()>(1);
     
     // 6.5. TUI Dashboard (Real-time Monitoring) - MOVED UP
-    let no_tui = env::args().any(|a| a == "--no-tui");
     let tui_state = Arc::new(std::sync::Mutex::new(tui::AppState::new()));
     if !no_tui {
+        tui_state.lock().unwrap().decision_feed = Some(Arc::clone(&decision_feed));
+        tui_state.lock().unwrap().inventory = inventory_mgr.clone();
+        tui_state.lock().unwrap().metadata = Arc::clone(&token_metadata);
         let tui_state_clone = Arc::clone(&tui_state);
         std::thread::spawn(move || {
             if let Err(e) = tui::TuiApp::new(tui_state_clone).run() {
                 error!("TUI error: {}", e);
             }
         });
-        info!("📊 TUI Dashboard ACTIVE (press 'q' to quit)");
+        info!("📊 TUI Dashboard ACTIVE (press 'q' to quit, ↑/↓ + Enter to inspect a route)");
     }
     
     let mut pools_to_watch = HashMap::new();
@@ -409,26 +1323,74 @@ async fn main() -> anyhow::Result<()> {
         || bot_cfg.mode != config::ExecutionMode::Simulation;
     let analyze_mode = args.contains(&"--analyze".to_string());
 
-    let ws_url = bot_cfg.ws_url.clone();
-    let rpc_url = bot_cfg.rpc_url.clone();
-    let market_tx_watcher = tx.clone();
-    let discovery_tx_watcher = discovery_tx.clone();
-    let tui_watcher = Arc::clone(&tui_state);
-    let monitored_pools = pools_to_watch.clone();
+    let mut monitored_pools = pools_to_watch.clone();
 
     let scoring_engine_watcher = Arc::clone(&scoring_engine);
-    tokio::spawn(async move {
-        watcher::start_market_watcher(
+    let trade_flow = if bot_cfg.trade_flow_enabled {
+        info!("🌊 Trade-flow decoding ENABLED for watched pools");
+        Some(context.engine.trade_flow())
+    } else {
+        None
+    };
+    let (backrun, backrun_rx) = if bot_cfg.backrun_enabled {
+        info!("🎯 Back-run mode ENABLED: swaps moving a pool by >= {} bps trigger a priority search", bot_cfg.backrun_min_impact_bps);
+        let (trigger, rx) = backrun::BackrunTrigger::new(bot_cfg.backrun_min_impact_bps, Arc::clone(&context.engine));
+        (Some(Arc::new(trigger)), Some(rx))
+    } else {
+        (None, None)
+    };
+
+    // 5.5 Geyser ingestion takes over pool accounts (and trade flow); the WebSocket watcher keeps discovery
+    let delister_watcher = Arc::clone(&context.delister);
+    let order_books_watcher = context.engine.order_books();
+    let (trade_flow, watcher_unsubscribe_rx) = if bot_cfg.ingest_backend == "geyser" {
+        let pools = monitored_pools.keys().filter_map(|p| p.parse().ok()).collect();
+        let listener = geyser_listener::GeyserListener::new(
+            bot_cfg.geyser_endpoint.clone().unwrap_or_default(),
+            bot_cfg.geyser_x_token.clone(),
+            pools,
+            Arc::clone(&scoring_engine),
+            trade_flow,
+            context.engine.order_books(),
+            Arc::clone(&context.delister),
+        ).with_backrun(backrun.clone());
+        tokio::spawn(listener.run(tx.clone(), unsubscribe_rx));
+        monitored_pools.clear();
+        (None, mpsc::unbounded_channel().1)
+    } else {
+        (trade_flow, unsubscribe_rx)
+    };
+    // One watcher per WebSocket endpoint; the feed monitor forwards each notification once
+    let ws_urls = bot_cfg.ws_urls();
+    let feed = Arc::new(ws_feed::FeedMonitor::new(ws_urls.len(), bot_cfg.ws_divergence_slots));
+    if ws_urls.len() > 1 {
+        info!("📡 Redundant WebSocket feeds: {} endpoints, divergence alert at {} slots", ws_urls.len(), bot_cfg.ws_divergence_slots);
+        tokio::spawn(Arc::clone(&feed).run(Arc::clone(&alert_mgr), ws_urls.clone(), std::time::Duration::from_secs(5)));
+    }
+    let sub_rxs = ws_feed::fan_out(sub_rx, ws_urls.len());
+    let unsubscribe_rxs = ws_feed::fan_out(watcher_unsubscribe_rx, ws_urls.len());
+    for (endpoint, ((ws_url, sub_rx), unsubscribe_rx)) in ws_urls.into_iter().zip(sub_rxs).zip(unsubscribe_rxs).enumerate() {
+        tokio::spawn(watcher::start_market_watcher(
+            endpoint,
+            Arc::clone(&feed),
             ws_url,
-            rpc_url,
-            discovery_tx_watcher,
-            market_tx_watcher,
-            Some(tui_watcher),
-            monitored_pools,
+            Arc::clone(&rpc_pool),
+            Arc::clone(&rng),
+            discovery_tx.clone(),
+            tx.clone(),
+            Some(Arc::clone(&tui_state)),
+            monitored_pools.clone(),
             sub_rx,
-            scoring_engine_watcher,
-        ).await;
-    });
+            unsubscribe_rx,
+            Arc::clone(&scoring_engine_watcher),
+            trade_flow.clone(),
+            Arc::clone(&order_books_watcher),
+            Arc::clone(&delister_watcher),
+            backrun.clone(),
+            Arc::clone(&liquidity_floor),
+            stream_oracle.clone(),
+        ));
+    }
 
     // 6. Birth Watcher (New Pool Logic)
     if discovery_enabled {
@@ -447,8 +1409,8 @@ async fn main() -> anyhow::Result<()> {
     // 6.1 Shutdown Watcher
     let shutdown_tx_signal = shutdown_tx.clone();
     tokio::spawn(async move {
-        tokio::signal::ctrl_c().await.expect("Failed to listen for ctrl-c");
-        info!("🛑 Shutdown signal received (Ctrl+C). Cleaning up...");
+        let signal = platform::shutdown_signal().await;
+        info!("🛑 Shutdown signal received ({}). Cleaning up...", signal);
         let _ = shutdown_tx_signal.send(()).await;
     });
 
@@ -468,6 +1430,45 @@ async fn main() -> anyhow::Result<()> {
             },
             Err(e) => error!("❌ Failed to generate analysis: {}", e),
         }
+        if let Some(journal) = &trade_journal {
+            match journal.summary(86_400).await {
+                Ok(summary) => {
+                    println!("🗄️ ==========================================");
+                    println!("🗄️   TRADE JOURNAL (LAST 24H)                ");
+                    println!("🗄️ ==========================================");
+                    println!("🗄️ Rejected:           {}", summary.rejected);
+                    println!("🗄️ Send Failed:        {}", summary.send_failed);
+                    println!("🗄️ Pending:            {}", summary.dispatched);
+                    println!("🗄️ Landed / Failed:    {} / {}", summary.landed, summary.failed);
+                    if let Some(rate) = summary.land_rate() {
+                        println!("🗄️ Land Rate:          {:.1}%", rate * 100.0);
+                    }
+                    println!("🗄️ Realized PnL:       {:.6} SOL", summary.realized_pnl_lamports as f64 / 1e9);
+                    for (reason, count) in &summary.top_rejections {
+                        println!("🗄️   {:<18} {}", reason, count);
+                    }
+                    println!("🗄️ ==========================================\n");
+                }
+                Err(e) => error!("❌ Failed to summarize trade journal: {}", e),
+            }
+            match journal.attribution(86_400).await {
+                Ok(rows) if !rows.is_empty() => {
+                    println!("🗄️ PnL BY TRIGGER (LAST 24H)");
+                    println!("🗄️ {:<8} {:<15} {:>5} {:>7} {:>14} {:>6} {:>6}", "Strategy", "Trigger", "Sent", "Landed", "PnL (SOL)", "AI", "DNA");
+                    for row in &rows {
+                        println!("🗄️ {:<8} {:<15} {:>5} {:>6.0}% {:>14.6} {:>6} {:>6}",
+                            row.strategy, row.trigger, row.dispatched,
+                            row.land_rate().unwrap_or(0.0) * 100.0,
+                            row.realized_pnl_lamports as f64 / 1e9,
+                            row.avg_ai_confidence.map_or("-".to_string(), |c| format!("{:.2}", c)),
+                            row.avg_dna_score.map_or("-".to_string(), |s| format!("{:.0}", s)));
+                    }
+                    println!("🗄️ ==========================================\n");
+                }
+                Ok(_) => {}
+                Err(e) => error!("❌ Failed to attribute trade journal PnL: {}", e),
+            }
+        }
     }
 
     // 6.5. TUI Dashboard (Real-time Monitoring) - MOVED TO STEP 6.1
@@ -486,34 +1487,90 @@ async fn main() -> anyhow::Result<()> {
     ).await;
     
     // 7. Worker Pool Ignition (HFT Optimization)
-    let num_workers = 8;
-    for i in 0..num_workers {
-        let mut worker_rx = tx.subscribe();
+    // Broadcast -> two-tier work queue (priority quote mints first) -> autoscaled workers (parked when idle)
+    let priority_mints = bot_cfg.priority_quote_mints().unwrap_or_default();
+    info!("🚦 Priority lane: {} quote mint(s)", priority_mints.len());
+    let (work_tx, work_rx) = worker_pool::work_queue(priority_mints);
+    let work_rx = Arc::new(tokio::sync::Mutex::new(work_rx));
+    let scaler = Arc::new(worker_pool::WorkerScaler::new(bot_cfg.min_workers, bot_cfg.max_workers));
+    tokio::spawn(worker_pool::dispatch(tx.subscribe(), work_tx.clone(), Arc::clone(&scaler)));
+    if let Some(backrun_rx) = backrun_rx {
+        tokio::spawn(backrun::forward(backrun_rx, work_tx.clone(), Arc::clone(&schedule)));
+    }
+    tokio::spawn(Arc::clone(&scaler).run(work_tx));
+
+    for i in 0..scaler.max_workers() {
+        let worker_rx = Arc::clone(&work_rx);
+        let worker_scaler = Arc::clone(&scaler);
         let ctx = Arc::clone(&context);
         let rec_inner = recorder.clone();
         let tui_worker_clone = Arc::clone(&tui_state);
+        let worker_dlmm_bins = Arc::clone(&dlmm_bins);
+        let worker_whirlpool_ticks = Arc::clone(&whirlpool_ticks);
+        let worker_openbook_books = Arc::clone(&openbook_books);
         
         tokio::spawn(async move {
             info!("👷 Worker {} started.", i);
-            while let Ok(event) = worker_rx.recv().await {
+            loop {
+                worker_scaler.wait_until_active(i).await;
+                let event = match worker_rx.lock().await.recv().await {
+                    Some(event) => event,
+                    None => break,
+                };
                 // Update WebSocket status in telemetry
                 telemetry::WEBSOCKET_STATUS.set(1);
+                ctx.metrics.funnel.record_received();
 
                 // 🛡️ Remote Control Check
                 if ctx.metrics.is_paused.load(std::sync::atomic::Ordering::Relaxed) {
                     continue;
                 }
 
+                // 🪦 Dead pools: drop late updates, delist newly drained or migrated ones
+                if ctx.delister.is_delisted(&event.pool_address) {
+                    continue;
+                }
+                if let Some(reason) = delisting::dead_pool(&event) {
+                    ctx.delister.delist(event.pool_address, reason);
+                    continue;
+                }
+
+                if event.program_id == mev_core::constants::METEORA_PROGRAM_ID {
+                    worker_dlmm_bins.request(event.pool_address);
+                } else if event.program_id == mev_core::constants::ORCA_WHIRLPOOL_PROGRAM {
+                    worker_whirlpool_ticks.request(event.pool_address);
+                } else if event.program_id == mev_core::constants::OPENBOOK_V2_PROGRAM {
+                    worker_openbook_books.request(event.pool_address);
+                }
+
+                // Pump.fun curve accounts don't carry their mint: take it from the hydrated graph state
+                let coin_mint = if event.program_id == mev_core::constants::PUMP_FUN_PROGRAM && event.coin_mint == Pubkey::default() {
+                    match ctx.engine.pool_state(&event.pool_address) {
+                        Some(pool) => pool.mint_a,
+                        None => continue,
+                    }
+                } else {
+                    event.coin_mint
+                };
+
                 let domain_update = Arc::new(mev_core::PoolUpdate {
                     pool_address: event.pool_address,
                     program_id: event.program_id,
-                    mint_a: event.coin_mint,
+                    mint_a: coin_mint,
                     mint_b: event.pc_mint,
                     reserve_a: event.coin_reserve as u128,
                     reserve_b: event.pc_reserve as u128,
                     price_sqrt: event.price_sqrt,
                     liquidity: event.liquidity,
-                    fee_bps: 25, // Raydium V4 standard fee (0.25%) 
+                    fee_bps: if let Some(fee_bps) = event.fee_bps {
+                        fee_bps // Read off the pool account (Whirlpool fee tiers)
+                    } else if event.program_id == mev_core::constants::PUMP_FUN_PROGRAM {
+                        mev_core::pump_fun::TRADE_FEE_BPS
+                    } else if let Some(book) = ctx.engine.order_books().get(&event.pool_address) {
+                        book.taker_fee_bps
+                    } else {
+                        25 // Raydium V4 standard fee (0.25%)
+                    },
                     timestamp: event.timestamp as u64,
                 });
                 
@@ -530,8 +1587,25 @@ async fn main() -> anyhow::Result<()> {
                     });
                 }
 
+                // 🕐 Outside the arb window the graph stays current but nothing is searched
+                let activation = ctx.schedule.current(schedule::Strategy::Arb);
+                if !activation.active {
+                    ctx.engine.correct_pool_state((*domain_update).clone());
+                    continue;
+                }
+
                 // 🛡️ Risk Check
-                if let Err(_e) = ctx.risk_mgr.can_trade(ctx.config.default_trade_size_lamports) {
+                let params = ctx.hot_config.get();
+                let trade_size = match &ctx.position_sizer {
+                    Some(sizer) => sizer.size(
+                        &ctx.metrics.recent_trade_stats(),
+                        ctx.inventory.available(&mev_core::constants::SOL_MINT),
+                        params.default_trade_size_lamports,
+                    ),
+                    None => params.default_trade_size_lamports,
+                };
+                let trade_size = activation.scaled(trade_size, ctx.engine.params().max_trade_size_lamports);
+                if let Err(_e) = ctx.risk_mgr.can_trade(trade_size) {
                     continue; // Skip silently in hot path
                 }
 
@@ -539,25 +1613,32 @@ async fn main() -> anyhow::Result<()> {
                 debug!("⏱️ START process_event at {:?}", start_time);
                 let processing_result = ctx.engine.process_event(
                     domain_update, 
-                    ctx.config.default_trade_size_lamports,
-                    ctx.config.jito_tip_lamports,
-                    ctx.config.jito_tip_percentage,
-                    ctx.config.max_jito_tip_lamports,
-                    ctx.config.max_slippage_bps,
+                    event.trigger,
+                    trade_size,
+                    params.jito_tip_lamports,
+                    params.jito_tip_percentage,
+                    params.max_jito_tip_lamports,
+                    params.max_slippage_bps,
                     ctx.config.volatility_sensitivity,
                     ctx.config.max_slippage_ceiling,
-                    ctx.config.min_profit_threshold_lamports,
-                    ctx.config.ai_confidence_threshold,
-                    ctx.config.sanity_profit_factor,
-                    ctx.config.max_hops
+                    params.ai_confidence_threshold,
+                    params.max_hops
                 ).await;
                 
                 let duration = start_time.elapsed().as_millis() as f64;
                 debug!("⏱️ END process_event. Duration: {}ms", duration);
                 telemetry::DETECTION_LATENCY.observe(duration);
+                ctx.metrics.latency.record(latency::LatencyStage::Detection, duration);
+                if let Ok(outcome) = &processing_result {
+                    telemetry::OPPORTUNITY_OUTCOMES.with_label_values(&[outcome.label()]).inc();
+                    ctx.metrics.funnel.record(outcome, funnel::FunnelStage::Searched);
+                    if let Ok(mut state) = tui_worker_clone.lock() {
+                        *state.outcome_counts.entry(outcome.label()).or_default() += 1;
+                    }
+                }
 
                 match processing_result {
-                    Ok(Some(opportunity)) => {
+                    Ok(EventOutcome::Executed(opportunity)) => {
                         telemetry::OPPORTUNITIES_TOTAL.inc();
                         telemetry::OPPORTUNITIES_PROFITABLE.inc();
                         
@@ -583,18 +1664,16 @@ async fn main() -> anyhow::Result<()> {
                             if let Ok(mut state) = tui_worker_clone.lock() {
                                 state.recent_opportunities.push(opportunity.clone());
                                 state.current_latency_ms = duration;
-                                if opportunity.expected_profit_lamports > 0 {
-                                    state.total_simulated_pnl += opportunity.expected_profit_lamports;
-                                }
+                                state.total_simulated_pnl = ctx.metrics.net_pnl_lamports();
                             }
                         }
 
-                        ctx.risk_mgr.record_trade(ctx.config.default_trade_size_lamports, opportunity.expected_profit_lamports as i64);
+                        ctx.risk_mgr.record_trade(trade_size, opportunity.profit_in_sol() as i64);
                         if let Some(r) = &rec_inner {
                             let _ = r.record_arbitrage(opportunity).await;
                         }
                     }
-                    Ok(None) => {
+                    Ok(_) => {
                         telemetry::OPPORTUNITIES_TOTAL.inc();
                     }
                     Err(e) => {
@@ -608,28 +1687,13 @@ async fn main() -> anyhow::Result<()> {
     }
 
     // --- GRACEFUL SHUTDOWN HANDLER ---
-    tokio::select! {
-        _ = tokio::signal::ctrl_c() => {
-            info!("🛑 Received SIGINT (Ctrl+C). Initiating graceful shutdown...");
-        }
-        _ = async {
-            #[cfg(unix)]
-            {
-                let mut term = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()).unwrap();
-                term.recv().await;
-                info!("🛑 Received SIGTERM. Initiating graceful shutdown...");
-            }
-            #[cfg(not(unix))]
-            {
-                std::future::pending::<()>().await;
-            }
-        } => {}
-    }
+    let signal = platform::shutdown_signal().await;
+    info!("🛑 Received {}. Initiating graceful shutdown...", signal);
 
     info!("👋 Engine shutting down gracefully...");
     let _ = scoring_engine.sync_to_db().await;
     context.metrics.print_summary();
-    context.alert_mgr.send_final_report(Arc::clone(&context.metrics), bot_start_time).await;
+    context.alert_mgr.send_final_report(Arc::clone(&context.metrics), bot_start_time, &config_snapshot.hash).await;
     info!("Goodbye!");
     
     Ok(())
@@ -1,6 +1,35 @@
-use std::sync::Arc;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicU64, AtomicU32, Ordering};
 use tracing::info;
+use crate::latency::{LatencyStage, LatencyTracker};
+
+/// Resolved Jito bundles for one endpoint or tip bucket
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LandRate {
+    pub landed: u64,
+    pub dropped: u64,
+}
+
+impl LandRate {
+    pub fn rate(&self) -> f64 {
+        let total = self.landed + self.dropped;
+        if total > 0 { self.landed as f64 / total as f64 } else { 0.0 }
+    }
+}
+
+/// Upper bound (lamports) of the tip bucket `tip` falls in; `u64::MAX` above the last bucket
+pub fn tip_bucket(tip: u64) -> u64 {
+    crate::telemetry::TIP_BUCKETS_LAMPORTS.iter()
+        .map(|&bound| bound as u64)
+        .find(|&bound| tip <= bound)
+        .unwrap_or(u64::MAX)
+}
+
+/// Landed results kept for position sizing
+const RECENT_TRADES_WINDOW: usize = 200;
+/// Resolved bundles needed before the land rate feeds the EV gate
+const MIN_LAND_RATE_BUNDLES: u64 = 20;
 
 /// Enhanced bot metrics with execution tracking
 pub struct BotMetrics {
@@ -18,6 +47,8 @@ pub struct BotMetrics {
     pub execution_jito_failed: AtomicU64,
     pub execution_rpc_fallback_success: AtomicU64,
     pub execution_rpc_fallback_failed: AtomicU64,
+    pub execution_tpu_success: AtomicU64,
+    pub execution_tpu_failed: AtomicU64,
     
     // Retry tracking - NEW SECTION
     pub retry_attempt_1_success: AtomicU64,  // First retry succeeded
@@ -31,25 +62,53 @@ pub struct BotMetrics {
     pub endpoint_1_successes: AtomicU64,
     pub endpoint_2_attempts: AtomicU64,
     pub endpoint_2_successes: AtomicU64,
+
+    // Bundle land rate, from block-engine bundle statuses
+    pub bundles_by_endpoint: Mutex<BTreeMap<usize, LandRate>>,
+    pub bundles_by_tip: Mutex<BTreeMap<u64, LandRate>>,
     
     // Performance tracking
     pub total_profit_lamports: AtomicU64,
     pub total_loss_lamports: AtomicU64,
     pub total_gas_spent: AtomicU64,
+    /// Realized PnL of the last trades (lamports), oldest first
+    pub recent_trades: Mutex<VecDeque<i64>>,
     
     // Latency tracking
     pub avg_detection_latency_ms: AtomicU32,
     pub avg_execution_latency_ms: AtomicU32,
+    pub latency: LatencyTracker, // Rolling 1h window for /latency
     
     // Health tracking
     pub websocket_reconnects: AtomicU32,
     pub rpc_errors: AtomicU32,
     
     // Remote Control State - NEW: V2
-    pub is_paused: std::sync::atomic::AtomicBool, 
+    pub is_paused: Arc<std::sync::atomic::AtomicBool>, // Shared with the strategy engine's split chunks
     
     // Success Library Integration (Phase 3 Hardening)
     pub intel: Option<Arc<dyn strategy::ports::MarketIntelligencePort>>,
+
+    // Route revert tracking (fed from landed-trade reports)
+    pub route_blacklist: Option<Arc<strategy::analytics::route_guard::RouteBlacklist>>,
+
+    // Dispatched-but-unconfirmed notional (closed on landing reports)
+    pub in_flight: Option<Arc<strategy::analytics::in_flight::InFlightBook>>,
+
+    // Per-hop quote error from landed transactions
+    pub hop_analyzer: Option<Arc<crate::hop_analytics::HopAnalyzer>>,
+
+    // Postgres trade journal (settled from landed-trade reports)
+    pub trade_journal: Option<Arc<crate::trade_journal::TradeJournal>>,
+
+    // Books on-chain trades at their realized PnL (and settles their journal rows)
+    pub pnl_reconciler: Option<Arc<crate::pnl_reconciler::PnlReconciler>>,
+
+    // Model training rows waiting on their outcome (on-chain ones settle through the reconciler)
+    pub training_export: Option<Arc<crate::training_export::TrainingExporter>>,
+
+    // How far each update got, for the periodic report and the hourly alert
+    pub funnel: crate::funnel::Funnel,
 }
 
 impl strategy::ports::TelemetryPort for BotMetrics {
@@ -89,6 +148,12 @@ impl strategy::ports::TelemetryPort for BotMetrics {
     fn log_rpc_fallback_failed(&self) {
         self.log_rpc_fallback_failed();
     }
+    fn log_tpu_fallback_success(&self) {
+        self.log_tpu_success();
+    }
+    fn log_tpu_fallback_failed(&self) {
+        self.log_tpu_failed();
+    }
     fn log_retry_success(&self, retry_number: usize) {
         self.log_retry_success(retry_number);
     }
@@ -98,7 +163,12 @@ impl strategy::ports::TelemetryPort for BotMetrics {
     fn log_endpoint_success(&self, endpoint_index: usize) {
         self.log_endpoint_success(endpoint_index);
     }
+    fn log_bundle_outcome(&self, endpoint_index: usize, tip_lamports: u64, landed: bool) {
+        self.log_bundle_outcome(endpoint_index, tip_lamports, landed);
+    }
     fn log_realized_pnl(&self, lamports: i64) {
+        // Reconciled on-chain trades, and paper fills under water (they land as zero-profit wins)
+        self.record_trade_result(lamports);
         if lamports > 0 {
             self.total_profit_lamports.fetch_add(lamports as u64, Ordering::SeqCst);
         } else if lamports < 0 {
@@ -106,13 +176,57 @@ impl strategy::ports::TelemetryPort for BotMetrics {
         }
     }
 
-    fn log_trade_landed(&self, opportunity: mev_core::ArbitrageOpportunity, _signature: String, success: bool) {
-        let lamports = opportunity.expected_profit_lamports;
+    fn log_build_latency(&self, ms: f64) {
+        self.latency.record(LatencyStage::Build, ms);
+    }
+    fn log_submit_latency(&self, ms: f64) {
+        self.latency.record(LatencyStage::Submit, ms);
+    }
+
+    fn log_trade_landed(&self, opportunity: mev_core::ArbitrageOpportunity, signature: String, success: bool) {
+        let lamports = opportunity.profit_in_sol();
+        if let Some(book) = &self.in_flight {
+            book.close(&opportunity);
+        }
+        if let Some(blacklist) = &self.route_blacklist {
+            if success {
+                blacklist.record_success(&opportunity);
+            } else {
+                blacklist.record_revert(&opportunity);
+            }
+        }
+        // Paper fills carry realized PnL but have no transaction behind them
+        let on_chain = !signature.starts_with(executor::paper::SIGNATURE_PREFIX);
+        // On-chain trades are booked once the reconciler has read what they actually made
+        let reconciler = self.pnl_reconciler.as_ref().filter(|_| on_chain);
+        match reconciler {
+            Some(reconciler) => reconciler.reconcile(opportunity.clone(), signature.clone(), success),
+            None => {
+                let paper_pnl = (!on_chain).then(|| if success { lamports as i64 } else { 0 });
+                if let Some(journal) = &self.trade_journal {
+                    journal.record_settlement(signature.clone(), success, paper_pnl, None);
+                }
+                if let Some(export) = &self.training_export {
+                    export.record_settlement(signature.clone(), success, paper_pnl);
+                }
+                if !success {
+                    self.record_trade_result(-(lamports as i64));
+                } else if lamports > 0 {
+                    self.record_trade_result(lamports as i64);
+                }
+            }
+        }
         if success {
-            self.total_profit_lamports.fetch_add(lamports, Ordering::SeqCst);
+            self.funnel.record_landed();
+            if reconciler.is_none() {
+                self.total_profit_lamports.fetch_add(lamports, Ordering::SeqCst);
+            }
+            if let Some(analyzer) = self.hop_analyzer.as_ref().filter(|_| on_chain) {
+                analyzer.analyze(opportunity.clone(), signature);
+            }
             
             // 🚀 Save Success Story (Async bridge)
-            if let Some(intel) = &self.intel {
+            if let Some(intel) = self.intel.as_ref().filter(|_| on_chain) {
                 let intel_clone = Arc::clone(intel);
                 let story = mev_core::SuccessStory {
                     strategy_id: "momentum_sniper_v1".to_string(),
@@ -139,28 +253,64 @@ impl strategy::ports::TelemetryPort for BotMetrics {
                     }
                 });
             }
-        } else {
+        } else if reconciler.is_none() {
             self.total_loss_lamports.fetch_add(lamports, Ordering::SeqCst);
         }
     }
 
+    fn log_race_winner(&self, dispatched: &str, settled: &str) {
+        if let Some(journal) = &self.trade_journal {
+            journal.record_alias(dispatched, settled);
+        }
+        if let Some(export) = &self.training_export {
+            export.record_alias(dispatched, settled);
+        }
+    }
+
     fn get_total_loss(&self) -> u64 {
         self.total_loss_lamports.load(Ordering::SeqCst)
     }
 
     fn get_win_rate(&self) -> f32 {
         let attempts = self.execution_attempts_total.load(Ordering::Relaxed) as f32;
-        let success = (self.execution_jito_success.load(Ordering::Relaxed) + self.execution_rpc_fallback_success.load(Ordering::Relaxed)) as f32;
+        let success = (self.execution_jito_success.load(Ordering::Relaxed)
+            + self.execution_rpc_fallback_success.load(Ordering::Relaxed)
+            + self.execution_tpu_success.load(Ordering::Relaxed)) as f32;
         if attempts > 0.0 {
             success / attempts
         } else {
             1.0 // Assume 100% win rate if no trades made yet to avoid aggressive scaling down
         }
     }
+
+    fn recent_trade_stats(&self) -> strategy::ports::TradeStats {
+        let recent = self.recent_trades.lock().unwrap();
+        let (wins, losses): (Vec<i64>, Vec<i64>) = recent.iter().partition(|&&pnl| pnl > 0);
+        let mean = |v: &[i64]| if v.is_empty() { 0.0 } else { v.iter().map(|&x| x.unsigned_abs() as f64).sum::<f64>() / v.len() as f64 };
+        strategy::ports::TradeStats {
+            trades: recent.len(),
+            win_rate: if recent.is_empty() { 0.0 } else { wins.len() as f64 / recent.len() as f64 },
+            avg_win: mean(&wins),
+            avg_loss: mean(&losses),
+        }
+    }
+
+    fn bundle_land_rate(&self) -> Option<f64> {
+        let bundles = self.bundle_land_rate();
+        (bundles.landed + bundles.dropped >= MIN_LAND_RATE_BUNDLES).then(|| bundles.rate())
+    }
 }
 
 impl BotMetrics {
-    pub fn new(intel: Option<Arc<dyn strategy::ports::MarketIntelligencePort>>) -> Self {
+    pub fn new(
+        intel: Option<Arc<dyn strategy::ports::MarketIntelligencePort>>,
+        route_blacklist: Option<Arc<strategy::analytics::route_guard::RouteBlacklist>>,
+        in_flight: Option<Arc<strategy::analytics::in_flight::InFlightBook>>,
+        hop_analyzer: Option<Arc<crate::hop_analytics::HopAnalyzer>>,
+        trade_journal: Option<Arc<crate::trade_journal::TradeJournal>>,
+        pnl_reconciler: Option<Arc<crate::pnl_reconciler::PnlReconciler>>,
+        training_export: Option<Arc<crate::training_export::TrainingExporter>>,
+    ) -> Self {
         Self {
             // Opportunity tracking
             opportunities_detected: AtomicU64::new(0),
@@ -176,6 +326,8 @@ impl BotMetrics {
             execution_jito_failed: AtomicU64::new(0),
             execution_rpc_fallback_success: AtomicU64::new(0),
             execution_rpc_fallback_failed: AtomicU64::new(0),
+            execution_tpu_success: AtomicU64::new(0),
+            execution_tpu_failed: AtomicU64::new(0),
             
             // Retry tracking
             retry_attempt_1_success: AtomicU64::new(0),
@@ -189,24 +341,43 @@ impl BotMetrics {
             endpoint_1_successes: AtomicU64::new(0),
             endpoint_2_attempts: AtomicU64::new(0),
             endpoint_2_successes: AtomicU64::new(0),
+            bundles_by_endpoint: Mutex::new(BTreeMap::new()),
+            bundles_by_tip: Mutex::new(BTreeMap::new()),
             
             // Performance tracking
             total_profit_lamports: AtomicU64::new(0),
             total_loss_lamports: AtomicU64::new(0),
             total_gas_spent: AtomicU64::new(0),
+            recent_trades: Mutex::new(VecDeque::with_capacity(RECENT_TRADES_WINDOW)),
             
             // Latency tracking
             avg_detection_latency_ms: AtomicU32::new(0),
             avg_execution_latency_ms: AtomicU32::new(0),
+            latency: LatencyTracker::new(),
             
             // Health tracking
             websocket_reconnects: AtomicU32::new(0),
             rpc_errors: AtomicU32::new(0),
             
             // Remote Control
-            is_paused: std::sync::atomic::AtomicBool::new(false),
+            is_paused: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             intel,
+            route_blacklist,
+            in_flight,
+            hop_analyzer,
+            trade_journal,
+            pnl_reconciler,
+            training_export,
+            funnel: crate::funnel::Funnel::default(),
+        }
+    }
+
+    fn record_trade_result(&self, lamports: i64) {
+        let mut recent = self.recent_trades.lock().unwrap();
+        if recent.len() == RECENT_TRADES_WINDOW {
+            recent.pop_front();
         }
+        recent.push_back(lamports);
     }
 
     pub fn log_opportunity(&self, profitable: bool) {
@@ -253,6 +424,14 @@ impl BotMetrics {
         self.execution_rpc_fallback_failed.fetch_add(1, Ordering::Relaxed);
     }
     
+    pub fn log_tpu_success(&self) {
+        self.execution_tpu_success.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    pub fn log_tpu_failed(&self) {
+        self.execution_tpu_failed.fetch_add(1, Ordering::Relaxed);
+    }
+    
     pub fn log_retry_success(&self, retry_number: usize) {
         match retry_number {
             0 => { self.retry_attempt_1_success.fetch_add(1, Ordering::Relaxed); },
@@ -280,6 +459,18 @@ impl BotMetrics {
         }
     }
     
+    pub fn log_bundle_outcome(&self, endpoint_index: usize, tip_lamports: u64, landed: bool) {
+        let bump = |rate: &mut LandRate| if landed { rate.landed += 1 } else { rate.dropped += 1 };
+        bump(self.bundles_by_endpoint.lock().unwrap_or_else(|e| e.into_inner()).entry(endpoint_index).or_default());
+        bump(self.bundles_by_tip.lock().unwrap_or_else(|e| e.into_inner()).entry(tip_bucket(tip_lamports)).or_default());
+    }
+
+    /// Landed share of resolved bundles, across all endpoints
+    pub fn bundle_land_rate(&self) -> LandRate {
+        self.bundles_by_endpoint.lock().unwrap_or_else(|e| e.into_inner()).values()
+            .fold(LandRate::default(), |acc, r| LandRate { landed: acc.landed + r.landed, dropped: acc.dropped + r.dropped })
+    }
+    
     pub fn print_summary(&self) {
         let detected = self.opportunities_detected.load(Ordering::Relaxed);
         let profitable = self.opportunities_profitable.load(Ordering::Relaxed);
@@ -291,6 +482,8 @@ impl BotMetrics {
         let jito_fail = self.execution_jito_failed.load(Ordering::Relaxed);
         let rpc_ok = self.execution_rpc_fallback_success.load(Ordering::Relaxed);
         let rpc_fail = self.execution_rpc_fallback_failed.load(Ordering::Relaxed);
+        let tpu_ok = self.execution_tpu_success.load(Ordering::Relaxed);
+        let tpu_fail = self.execution_tpu_failed.load(Ordering::Relaxed);
         
         println!("
 ╔════════════════════════════════════════════════════╗
@@ -308,6 +501,8 @@ impl BotMetrics {
 ║   Jito Failed:        {:>14} ({:>5.1}%)          ║
 ║   RPC Fallback OK:    {:>14} ({:>5.1}%)          ║
 ║   RPC Fallback Fail:  {:>14} ({:>5.1}%)          ║
+║   TPU Direct OK:      {:>14} ({:>5.1}%)          ║
+║   TPU Direct Fail:    {:>14} ({:>5.1}%)          ║
 ╠════════════════════════════════════════════════════╣
 ║ PROFIT/LOSS                                        ║
 ║   Total Profit: {:>24.4} SOL              ║
@@ -324,6 +519,8 @@ impl BotMetrics {
             jito_fail, if exec_total > 0 { (jito_fail as f64 / exec_total as f64) * 100.0 } else { 0.0 },
             rpc_ok, if exec_total > 0 { (rpc_ok as f64 / exec_total as f64) * 100.0 } else { 0.0 },
             rpc_fail, if exec_total > 0 { (rpc_fail as f64 / exec_total as f64) * 100.0 } else { 0.0 },
+            tpu_ok, if exec_total > 0 { (tpu_ok as f64 / exec_total as f64) * 100.0 } else { 0.0 },
+            tpu_fail, if exec_total > 0 { (tpu_fail as f64 / exec_total as f64) * 100.0 } else { 0.0 },
             self.total_profit_lamports.load(Ordering::Relaxed) as f64 / 1e9,
             self.total_loss_lamports.load(Ordering::Relaxed) as f64 / 1e9,
             (self.total_profit_lamports.load(Ordering::Relaxed) as i64 
@@ -331,18 +528,25 @@ impl BotMetrics {
         );
     }
 
+    /// Realized profit minus realized loss
+    pub fn net_pnl_lamports(&self) -> i64 {
+        self.total_profit_lamports.load(Ordering::Relaxed) as i64 - self.total_loss_lamports.load(Ordering::Relaxed) as i64
+    }
+
     pub fn print_periodic_update(&self) {
         let detected = self.opportunities_detected.load(Ordering::Relaxed);
         let profitable = self.opportunities_profitable.load(Ordering::Relaxed);
         let exec_total = self.execution_attempts_total.load(Ordering::Relaxed);
         let jito_ok = self.execution_jito_success.load(Ordering::Relaxed);
         let rpc_ok = self.execution_rpc_fallback_success.load(Ordering::Relaxed);
-        let net = (self.total_profit_lamports.load(Ordering::Relaxed) as i64 
-                  - self.total_loss_lamports.load(Ordering::Relaxed) as i64) as f64 / 1e9;
+        let tpu_ok = self.execution_tpu_success.load(Ordering::Relaxed);
+        let net = self.net_pnl_lamports() as f64 / 1e9;
+        let bundles = self.bundle_land_rate();
 
-        info!("📈 [PERIODIC] Opps: {}/{} | Exec: {} ({} Jito ✅, {} RPC ✅) | PnL: {:.4} SOL",
-            profitable, detected, exec_total, jito_ok, rpc_ok, net
+        info!("📈 [PERIODIC] Opps: {}/{} | Exec: {} ({} Jito ✅, {} RPC ✅, {} TPU ✅) | Bundles landed: {}/{} | PnL: {:.4} SOL",
+            profitable, detected, exec_total, jito_ok, rpc_ok, tpu_ok, bundles.landed, bundles.landed + bundles.dropped, net
         );
+        info!("🔻 [FUNNEL] {}", crate::funnel::summary_line(&self.funnel.snapshot()));
     }
     
     /// NEW: Print detailed execution stats
@@ -389,5 +593,14 @@ impl BotMetrics {
             ep2_attempts, ep2_success,
             if ep2_attempts > 0 { (ep2_success as f64 / ep2_attempts as f64) * 100.0 } else { 0.0 },
         );
+
+        println!("BUNDLE LAND RATE");
+        for (endpoint, rate) in self.bundles_by_endpoint.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+            println!("  Endpoint {}: {:>6} landed / {:>6} dropped ({:>5.1}%)", endpoint, rate.landed, rate.dropped, rate.rate() * 100.0);
+        }
+        for (bucket, rate) in self.bundles_by_tip.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+            let label = if *bucket == u64::MAX { "above".to_string() } else { format!("≤{}", bucket) };
+            println!("  Tip {:>10}: {:>6} landed / {:>6} dropped ({:>5.1}%)", label, rate.landed, rate.dropped, rate.rate() * 100.0);
+        }
     }
 }
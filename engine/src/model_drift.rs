@@ -0,0 +1,58 @@
+/// AI Model Drift Alerts
+///
+/// Polls the strategy engine's `ModelDriftMonitor` and tells the operator when
+/// the model's confidence outputs collapse or shift away from the training
+/// baseline, and again when they look healthy. Detection and the heuristic
+/// fallback live in the strategy crate; this side only reports.
+use std::sync::Arc;
+use std::time::Duration;
+use strategy::analytics::model_drift::{DriftState, ModelDriftMonitor};
+use crate::alerts::{AlertManager, AlertSeverity, Field};
+
+pub async fn run(monitor: Arc<ModelDriftMonitor>, alerts: Arc<AlertManager>, poll: Duration) {
+    let mut interval = tokio::time::interval(poll);
+    let mut last = DriftState::Healthy;
+    loop {
+        interval.tick().await;
+        let state = monitor.state();
+        if state.is_drifted() == last.is_drifted() {
+            last = state;
+            continue;
+        }
+        last = state;
+
+        let fallback = monitor.policy().fallback;
+        let (summary, fields) = match state {
+            DriftState::Healthy => {
+                tracing::info!("🧠 AI model outputs back within their normal range{}", if fallback { "; model re-enabled" } else { "" });
+                continue;
+            }
+            DriftState::Collapsed { mean, stddev, saturated_share } => (
+                format!("Model confidence has collapsed: {:.0}% of recent outputs are pinned at 0 or 1.", saturated_share * 100.0),
+                vec![
+                    Field { name: "Mean".to_string(), value: format!("{:.3}", mean), inline: true },
+                    Field { name: "Std Dev".to_string(), value: format!("{:.3}", stddev), inline: true },
+                ],
+            ),
+            DriftState::Shifted { mean, baseline } => (
+                format!("Model confidence mean has moved from {:.3} to {:.3}.", baseline, mean),
+                vec![
+                    Field { name: "Mean".to_string(), value: format!("{:.3}", mean), inline: true },
+                    Field { name: "Baseline".to_string(), value: format!("{:.3}", baseline), inline: true },
+                ],
+            ),
+        };
+        let action = if fallback {
+            "Falling back to heuristic mode until it recovers."
+        } else {
+            "The model still gates trades (AI_DRIFT_FALLBACK=false)."
+        };
+        tracing::warn!("🧠 AI MODEL DRIFT: {} {}", summary, action);
+        alerts.send_alert(
+            AlertSeverity::Warning,
+            "AI Model Drift",
+            &format!("{} Check the feature pipeline against the training data. {}", summary, action),
+            fields,
+        ).await;
+    }
+}
@@ -0,0 +1,60 @@
+/// OpenBook v2 Book Refresh
+///
+/// An OpenBook market account holds parameters only; resting orders live in
+/// its two book side accounts. Placing or filling an order always writes the
+/// market (sequence number, fee totals), so each market update schedules a
+/// re-read of both sides, which is swapped into the market's graph edges.
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::openbook::OpenBookMarket;
+use strategy::StrategyEngine;
+
+pub struct OpenBookFetcher {
+    rpc: RpcClient,
+    engine: Arc<StrategyEngine>,
+    in_flight: Mutex<HashSet<Pubkey>>,
+}
+
+impl OpenBookFetcher {
+    pub fn new(rpc_url: &str, engine: Arc<StrategyEngine>) -> Self {
+        Self {
+            rpc: RpcClient::new(rpc_url.to_string()),
+            engine,
+            in_flight: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Schedules a book refresh for `market` unless one is already running
+    pub fn request(self: &Arc<Self>, market: Pubkey) {
+        if !self.in_flight.lock().unwrap_or_else(|e| e.into_inner()).insert(market) {
+            return;
+        }
+        let fetcher = Arc::clone(self);
+        tokio::spawn(async move {
+            match fetcher.refresh(&market).await {
+                Ok(levels) => tracing::debug!("📖 OpenBook {} refreshed with {} level(s)", market, levels),
+                Err(e) => tracing::debug!("📖 OpenBook refresh failed for {}: {}", market, e),
+            }
+            fetcher.in_flight.lock().unwrap_or_else(|e| e.into_inner()).remove(&market);
+        });
+    }
+
+    /// Re-reads the market and both book sides into the graph.
+    /// Returns how many price levels were loaded.
+    pub async fn refresh(&self, market: &Pubkey) -> anyhow::Result<usize> {
+        let account = self.rpc.get_account(market).await?;
+        let parsed = OpenBookMarket::from_account_data(&account.data).map_err(|e| anyhow::anyhow!(e))?;
+        let mut sides = self.rpc.get_multiple_accounts(&[parsed.bids, parsed.asks]).await?.into_iter();
+        let (Some(Some(bids)), Some(Some(asks))) = (sides.next(), sides.next()) else {
+            return Err(anyhow::anyhow!("Book side accounts missing"));
+        };
+
+        let now = mev_core::clock::system().unix_secs();
+        let book = parsed.order_book(&bids.data, &asks.data, now).map_err(|e| anyhow::anyhow!(e))?;
+        let levels = book.bids.len() + book.asks.len();
+        self.engine.update_order_book(*market, book);
+        Ok(levels)
+    }
+}
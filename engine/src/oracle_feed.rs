@@ -0,0 +1,197 @@
+/// Pyth Price Oracle
+///
+/// The `PriceOraclePort` adapter for Pyth. It keeps a price cache current
+/// for the configured price accounts, either by polling them all in one
+/// getMultipleAccounts call per tick through the RPC pool (`pull`) or by
+/// following their writes (`stream`). Streamed price accounts ride the
+/// market watchers' WebSocket subscriptions, so they fail over and dedupe
+/// with the shared feed; each watcher (re)connect triggers one poll, so
+/// prices don't wait for the next write. A feed that fails to decode
+/// (halted, wrong account) is left out and ages past the cache's limit, so
+/// its hops simply stop being judged and its mint stops being priced.
+use std::sync::Arc;
+use std::time::Duration;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use executor::rpc_pool::RpcPool;
+use mev_core::pyth::PythPrice;
+use strategy::ports::PriceOraclePort;
+use strategy::safety::oracle_guard::{OracleFeed, PriceCache, ReferencePrice};
+
+/// How the price accounts are kept current
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OracleMode {
+    /// getMultipleAccounts every `ORACLE_POLL_MS`
+    Pull,
+    /// accountSubscribe on every price account, through the market watchers
+    Stream,
+}
+
+impl OracleMode {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "pull" => Ok(OracleMode::Pull),
+            "stream" => Ok(OracleMode::Stream),
+            other => Err(format!("unknown oracle mode '{}' (pull, stream)", other)),
+        }
+    }
+}
+
+pub struct PythOracle {
+    rpc: Arc<RpcPool>,
+    feeds: Vec<OracleFeed>,
+    prices: PriceCache,
+}
+
+impl PythOracle {
+    pub fn new(rpc: Arc<RpcPool>, feeds: Vec<OracleFeed>, max_age_secs: u64) -> Self {
+        Self { rpc, feeds, prices: PriceCache::new(max_age_secs) }
+    }
+
+    /// Price accounts the watchers subscribe to in stream mode
+    pub fn price_accounts(&self) -> impl Iterator<Item = Pubkey> + '_ {
+        self.feeds.iter().map(|f| f.price_account)
+    }
+
+    /// Pull polls every `poll_interval`. Stream fills the cache once; writes then come in through `accept_write`.
+    pub async fn run(self: Arc<Self>, mode: OracleMode, poll_interval: Duration) {
+        match mode {
+            OracleMode::Pull => self.poll(poll_interval).await,
+            OracleMode::Stream => self.refresh().await,
+        }
+    }
+
+    async fn poll(&self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            self.refresh().await;
+        }
+    }
+
+    /// `poll_once`, logging instead of failing
+    pub async fn refresh(&self) {
+        if let Err(e) = self.poll_once().await {
+            tracing::debug!("🔮 Oracle poll failed: {}", e);
+        }
+    }
+
+    /// Refreshes every feed once. Returns how many were updated.
+    pub async fn poll_once(&self) -> anyhow::Result<usize> {
+        let accounts: Vec<Pubkey> = self.price_accounts().collect();
+        let accounts = &accounts;
+        let fetched = self.rpc.call(|c| async move { c.get_multiple_accounts(accounts).await }).await?;
+        Ok(self.apply_fetched(&fetched))
+    }
+
+    /// Accounts in feed order, as getMultipleAccounts returns them
+    fn apply_fetched(&self, fetched: &[Option<Account>]) -> usize {
+        self.feeds.iter().zip(fetched)
+            .filter(|(feed, account)| account.as_ref().is_some_and(|account| self.apply(feed, &account.data)))
+            .count()
+    }
+
+    /// A write notified on the shared WebSocket feed (base64 data). None when `account` is not a
+    /// price account, so the caller handles it as a pool; otherwise whether the price was taken.
+    pub fn accept_write(&self, account: &str, data_base64: &str) -> Option<bool> {
+        use base64::{Engine as _, engine::general_purpose};
+        let account: Pubkey = account.parse().ok()?;
+        let feed = self.feeds.iter().find(|f| f.price_account == account)?;
+        Some(general_purpose::STANDARD.decode(data_base64).is_ok_and(|bytes| self.apply(feed, &bytes)))
+    }
+
+    /// Decodes one price account into the cache
+    fn apply(&self, feed: &OracleFeed, data: &[u8]) -> bool {
+        match PythPrice::from_account_data(data) {
+            Ok(price) => {
+                self.prices.update(feed.mint, ReferencePrice {
+                    price: price.price_f64(),
+                    conf: price.conf_f64(),
+                    decimals: feed.decimals,
+                    publish_time: price.publish_time.max(0) as u64,
+                });
+                true
+            }
+            Err(e) => {
+                tracing::debug!("🔮 Oracle {} for {} skipped: {}", feed.price_account, feed.mint, e);
+                false
+            }
+        }
+    }
+}
+
+impl PriceOraclePort for PythOracle {
+    fn name(&self) -> &'static str {
+        "pyth"
+    }
+
+    fn reference(&self, mint: &Pubkey, now_secs: u64) -> Option<ReferencePrice> {
+        self.prices.reference(mint, now_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{Engine as _, engine::general_purpose};
+
+    /// v2 price account with the aggregate at `price * 10^-8`
+    fn price_account(price: i64, publish_time: i64, trading: bool) -> Vec<u8> {
+        let mut data = vec![0u8; 3312];
+        data[0..4].copy_from_slice(&0xa1b2c3d4u32.to_le_bytes());
+        data[8..12].copy_from_slice(&3u32.to_le_bytes());
+        data[20..24].copy_from_slice(&(-8i32).to_le_bytes());
+        data[96..104].copy_from_slice(&publish_time.to_le_bytes());
+        data[208..216].copy_from_slice(&price.to_le_bytes());
+        data[216..224].copy_from_slice(&1_000_000u64.to_le_bytes());
+        data[224..228].copy_from_slice(&(trading as u32).to_le_bytes());
+        data
+    }
+
+    fn oracle() -> (PythOracle, OracleFeed) {
+        let feed = OracleFeed { mint: Pubkey::new_unique(), price_account: Pubkey::new_unique(), decimals: 9 };
+        // Nothing listens on port 1; polls fail fast
+        (PythOracle::new(Arc::new(RpcPool::single("http://127.0.0.1:1")), vec![feed], 60), feed)
+    }
+
+    #[test]
+    fn test_streamed_writes_update_the_cache_until_they_go_stale() {
+        let (oracle, feed) = oracle();
+        let account = feed.price_account.to_string();
+        let write = |price, publish_time, trading| general_purpose::STANDARD.encode(price_account(price, publish_time, trading));
+
+        assert_eq!(oracle.accept_write(&account, &write(15_000_000_000, 1_000, true)), Some(true));
+        let price = oracle.reference(&feed.mint, 1_030).expect("fresh price");
+        assert!((price.price - 150.0).abs() < 1e-9 && (price.conf - 0.01).abs() < 1e-9);
+        assert_eq!((price.decimals, price.publish_time), (9, 1_000));
+
+        // A halted feed or an undecodable write is consumed but leaves the last price in place
+        assert_eq!(oracle.accept_write(&account, &write(1, 1_010, false)), Some(false));
+        assert_eq!(oracle.accept_write(&account, "not base64!"), Some(false));
+        assert_eq!(oracle.reference(&feed.mint, 1_030).map(|p| p.publish_time), Some(1_000));
+
+        // Past the max age the mint is no longer priced, until the next write
+        assert!(oracle.reference(&feed.mint, 1_061).is_none());
+        assert_eq!(oracle.accept_write(&account, &write(15_100_000_000, 1_060, true)), Some(true));
+        assert!(oracle.reference(&feed.mint, 1_061).is_some());
+
+        // Pool accounts on the same subscriptions are not the oracle's
+        assert_eq!(oracle.accept_write(&Pubkey::new_unique().to_string(), &write(1, 1_060, true)), None);
+        assert_eq!(oracle.accept_write("not a pubkey", &write(1, 1_060, true)), None);
+    }
+
+    #[tokio::test]
+    async fn test_poll_once_applies_fetched_accounts_and_surfaces_rpc_errors() {
+        let (oracle, feed) = oracle();
+        assert!(oracle.poll_once().await.is_err());
+        assert!(oracle.reference(&feed.mint, 1_000).is_none());
+
+        let account = |data| Account { lamports: 1, data, owner: Pubkey::new_unique(), executable: false, rent_epoch: 0 };
+        assert_eq!(oracle.apply_fetched(&[None]), 0);
+        assert_eq!(oracle.apply_fetched(&[Some(account(price_account(15_000_000_000, 1_000, false)))]), 0);
+        assert!(oracle.reference(&feed.mint, 1_000).is_none());
+        assert_eq!(oracle.apply_fetched(&[Some(account(price_account(15_000_000_000, 1_000, true)))]), 1);
+        assert_eq!(oracle.reference(&feed.mint, 1_000).map(|p| p.publish_time), Some(1_000));
+    }
+}
@@ -0,0 +1,102 @@
+/// Platform Compatibility Layer
+///
+/// Keeps the OS differences the engine runs into in one place: where the
+/// Solana CLI keypair lives (`HOME` is not set on Windows), where data and
+/// logs go, and which signals mean "shut down" (SIGINT/SIGTERM on unix,
+/// Ctrl+C/Ctrl+Break/console close on Windows).
+use std::env;
+use std::path::PathBuf;
+
+/// Home directory from `HOME`, falling back to the Windows profile variables
+pub fn home_dir() -> Option<PathBuf> {
+    home_from(|key| env::var(key).ok())
+}
+
+fn home_from(var: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    let non_empty = |key: &str| var(key).filter(|v| !v.is_empty());
+    non_empty("HOME")
+        .or_else(|| non_empty("USERPROFILE"))
+        .or_else(|| Some(format!("{}{}", non_empty("HOMEDRIVE")?, non_empty("HOMEPATH")?)))
+        .map(PathBuf::from)
+}
+
+/// Where `solana-keygen` writes by default on every OS: `~/.config/solana/id.json`
+pub fn default_keypair_path() -> PathBuf {
+    home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".config")
+        .join("solana")
+        .join("id.json")
+}
+
+/// Recorder output and backtest input (`DATA_DIR`, default `data`)
+pub fn data_dir() -> PathBuf {
+    env::var("DATA_DIR").ok().filter(|d| !d.is_empty()).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("data"))
+}
+
+/// A file under the log directory (`LOG_DIR`, default `logs`), created if missing
+pub fn log_path(file: &str) -> String {
+    let dir = env::var("LOG_DIR").ok().filter(|d| !d.is_empty()).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("logs"));
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        tracing::warn!("⚠️ Cannot create log directory {}: {}", dir.display(), e);
+    }
+    dir.join(file).to_string_lossy().into_owned()
+}
+
+/// Resolves once the OS asks the process to stop
+pub async fn shutdown_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut term) => tokio::select! {
+                _ = tokio::signal::ctrl_c() => "SIGINT (Ctrl+C)",
+                _ = term.recv() => "SIGTERM",
+            },
+            Err(e) => {
+                tracing::warn!("⚠️ SIGTERM handler unavailable: {}. Listening for Ctrl+C only.", e);
+                let _ = tokio::signal::ctrl_c().await;
+                "SIGINT (Ctrl+C)"
+            }
+        }
+    }
+    #[cfg(windows)]
+    {
+        use tokio::signal::windows::{ctrl_break, ctrl_close};
+        match (ctrl_break(), ctrl_close()) {
+            (Ok(mut brk), Ok(mut close)) => tokio::select! {
+                _ = tokio::signal::ctrl_c() => "Ctrl+C",
+                _ = brk.recv() => "Ctrl+Break",
+                _ = close.recv() => "console close",
+            },
+            _ => {
+                let _ = tokio::signal::ctrl_c().await;
+                "Ctrl+C"
+            }
+        }
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        "Ctrl+C"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_home_dir_falls_back_to_windows_profile() {
+        let env = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            home_from(move |key| vars.get(key).cloned())
+        };
+
+        assert_eq!(env(&[("HOME", "/home/bot"), ("USERPROFILE", r"C:\Users\bot")]), Some(PathBuf::from("/home/bot")));
+        assert_eq!(env(&[("HOME", ""), ("USERPROFILE", r"C:\Users\bot")]), Some(PathBuf::from(r"C:\Users\bot")));
+        assert_eq!(env(&[("HOMEDRIVE", "D:"), ("HOMEPATH", r"\bot")]), Some(PathBuf::from(r"D:\bot")));
+        assert_eq!(env(&[("HOMEDRIVE", "D:")]), None);
+    }
+}
@@ -0,0 +1,242 @@
+/// Realized PnL Reconciliation
+///
+/// A landing report only says the bundle landed; the profit it carries is the
+/// quote. For every on-chain trade that lands, the reconciler re-reads the
+/// transaction and books what the payer's balances actually moved instead:
+/// SOL (fee and tip included) plus wrapped SOL. A successful trade whose
+/// realized PnL misses its quote by more than `PNL_DEVIATION_ALERT_PCT` is
+/// flagged. The same read settles the trade journal row, CU price included,
+/// and hands any intermediate token the trade left in the wallet to the
+/// recovery seller.
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock, Weak};
+use std::time::Duration;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{EncodedTransaction, UiMessage, UiTransactionEncoding, UiTransactionTokenBalance};
+use mev_core::ArbitrageOpportunity;
+use executor::rpc_pool::RpcPool;
+use strategy::ports::TelemetryPort;
+use crate::recovery::RecoverySeller;
+use crate::trade_journal::TradeJournal;
+use crate::training_export::TrainingExporter;
+
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+/// A just-confirmed transaction can take a moment to be served by `getTransaction`
+const FETCH_ATTEMPTS: u32 = 3;
+const FETCH_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// What a landed transaction did to the payer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settlement {
+    /// Payer's SOL plus WSOL change; fees and the tip are already in it
+    pub realized_pnl: i64,
+    pub fee_lamports: u64,
+    pub cu_price_micro_lamports: Option<u64>,
+}
+
+pub struct PnlReconciler {
+    rpc: Arc<RpcPool>,
+    payer: Pubkey,
+    deviation_alert_pct: f64,
+    journal: Option<Arc<TradeJournal>>,
+    training_export: Option<Arc<TrainingExporter>>,
+    telemetry: OnceLock<Weak<dyn TelemetryPort>>,
+    recovery: OnceLock<Weak<RecoverySeller>>,
+}
+
+impl PnlReconciler {
+    pub fn new(
+        rpc: Arc<RpcPool>,
+        payer: Pubkey,
+        deviation_alert_pct: f64,
+        journal: Option<Arc<TradeJournal>>,
+        training_export: Option<Arc<TrainingExporter>>,
+    ) -> Self {
+        Self {
+            rpc,
+            payer,
+            deviation_alert_pct,
+            journal,
+            training_export,
+            telemetry: OnceLock::new(),
+            recovery: OnceLock::new(),
+        }
+    }
+
+    /// Points realized PnL at the metrics that own this reconciler; only a weak handle is kept
+    pub fn attach(&self, telemetry: Weak<dyn TelemetryPort>) {
+        let _ = self.telemetry.set(telemetry);
+    }
+
+    /// Sells what landed trades leave behind; built after the executor, hence attached late
+    pub fn attach_recovery(&self, recovery: Weak<RecoverySeller>) {
+        let _ = self.recovery.set(recovery);
+    }
+
+    /// Books the realized PnL of a landing report in the background. When the transaction
+    /// can't be read, the quote is booked instead, as it was before reconciliation.
+    pub fn reconcile(self: &Arc<Self>, opportunity: ArbitrageOpportunity, signature: String, success: bool) {
+        let reconciler = Arc::clone(self);
+        tokio::spawn(async move {
+            let expected = opportunity.profit_in_sol() as i64;
+            let (settlement, token_deltas) = match reconciler.fetch_with_retry(&signature).await {
+                Ok((settlement, token_deltas)) => (Some(settlement), token_deltas),
+                Err(e) => {
+                    tracing::warn!("⚠️ Realized PnL unavailable for {}, booking the quote: {}", signature, e);
+                    (None, HashMap::new())
+                }
+            };
+            let realized = settlement.map(|s| s.realized_pnl).unwrap_or(if success { expected } else { -expected });
+
+            if let Some(tel) = reconciler.telemetry.get().and_then(Weak::upgrade) {
+                tel.log_realized_pnl(realized);
+            }
+            if realized >= 0 {
+                mev_core::telemetry::PROFIT_LAMPORTS.inc_by(realized as f64);
+            } else {
+                mev_core::telemetry::LOSS_LAMPORTS.inc_by(realized.unsigned_abs() as f64);
+            }
+
+            if let Some(settlement) = settlement {
+                tracing::info!("💰 Realized {:+} lamports on {} (quoted +{}, fee {})",
+                    settlement.realized_pnl, signature, expected, settlement.fee_lamports);
+                if success {
+                    if let Some(deviation) = deviation_pct(expected, settlement.realized_pnl).filter(|d| *d > reconciler.deviation_alert_pct) {
+                        crate::telemetry::REALIZED_PNL_DEVIATIONS.inc();
+                        tracing::warn!("🚩 Realized PnL off quote by {:.0}% on {}: {:+} vs +{} lamports (route {})",
+                            deviation, signature, settlement.realized_pnl, expected,
+                            strategy::analytics::rejections::route_signature(&opportunity));
+                    }
+                }
+            }
+
+            if let Some(recovery) = reconciler.recovery.get().and_then(Weak::upgrade) {
+                for residual in crate::recovery::residuals(&opportunity, &token_deltas, &signature) {
+                    recovery.queue(residual);
+                }
+            }
+            if let Some(journal) = &reconciler.journal {
+                journal.record_settlement(signature.clone(), success, settlement.map(|s| s.realized_pnl), settlement.and_then(|s| s.cu_price_micro_lamports));
+            }
+            if let Some(export) = &reconciler.training_export {
+                export.record_settlement(signature, success, settlement.map(|s| s.realized_pnl));
+            }
+        });
+    }
+
+    async fn fetch_with_retry(&self, signature: &str) -> anyhow::Result<(Settlement, HashMap<Pubkey, i128>)> {
+        let mut attempt = 1;
+        loop {
+            match self.fetch(signature).await {
+                Ok(settlement) => return Ok(settlement),
+                Err(e) if attempt >= FETCH_ATTEMPTS => return Err(e),
+                Err(_) => {
+                    attempt += 1;
+                    tokio::time::sleep(FETCH_RETRY_DELAY).await;
+                }
+            }
+        }
+    }
+
+    /// The settlement, plus the payer's net change in every token other than WSOL
+    async fn fetch(&self, signature: &str) -> anyhow::Result<(Settlement, HashMap<Pubkey, i128>)> {
+        let signature = Signature::from_str(signature)?;
+        let tx = self.rpc.call(|c| async move {
+            c.get_transaction_with_config(&signature, RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(solana_sdk::commitment_config::CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            }).await
+        }).await?;
+        let meta = tx.transaction.meta.ok_or_else(|| anyhow::anyhow!("Transaction has no meta"))?;
+
+        let payer = self.payer.to_string();
+        let wsol = mev_core::constants::SOL_MINT.to_string();
+        let wsol_balance = |balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>| -> u64 {
+            match balances {
+                OptionSerializer::Some(balances) => balances.iter()
+                    .filter(|b| b.mint == wsol && matches!(&b.owner, OptionSerializer::Some(owner) if *owner == payer))
+                    .filter_map(|b| b.ui_token_amount.amount.parse::<u64>().ok())
+                    .sum(),
+                _ => 0,
+            }
+        };
+        // The payer is account 0 of its own transaction
+        let (Some(pre), Some(post)) = (meta.pre_balances.first(), meta.post_balances.first()) else {
+            return Err(anyhow::anyhow!("Transaction meta has no balances"));
+        };
+        let realized = realized_pnl(*pre, *post, wsol_balance(&meta.pre_token_balances), wsol_balance(&meta.post_token_balances));
+
+        let mut token_deltas: HashMap<Pubkey, i128> = HashMap::new();
+        for (balances, sign) in [(&meta.pre_token_balances, -1i128), (&meta.post_token_balances, 1)] {
+            let OptionSerializer::Some(balances) = balances else { continue };
+            for balance in balances {
+                if balance.mint == wsol || !matches!(&balance.owner, OptionSerializer::Some(owner) if *owner == payer) {
+                    continue;
+                }
+                if let (Ok(mint), Ok(amount)) = (Pubkey::from_str(&balance.mint), balance.ui_token_amount.amount.parse::<u64>()) {
+                    *token_deltas.entry(mint).or_default() += sign * amount as i128;
+                }
+            }
+        }
+
+        let cu_price = match tx.transaction.transaction {
+            EncodedTransaction::Json(ui_tx) => match ui_tx.message {
+                UiMessage::Raw(message) => {
+                    let budget_program = solana_sdk::compute_budget::id().to_string();
+                    message.instructions.iter()
+                        .filter(|ix| message.account_keys.get(ix.program_id_index as usize) == Some(&budget_program))
+                        .filter_map(|ix| bs58::decode(&ix.data).into_vec().ok())
+                        .find_map(|data| compute_unit_price(&data))
+                }
+                UiMessage::Parsed(_) => None,
+            },
+            _ => None,
+        };
+        Ok((Settlement { realized_pnl: realized, fee_lamports: meta.fee, cu_price_micro_lamports: cu_price }, token_deltas))
+    }
+}
+
+/// Payer's SOL change across the transaction, wrapped SOL included
+fn realized_pnl(pre_lamports: u64, post_lamports: u64, pre_wsol: u64, post_wsol: u64) -> i64 {
+    (post_lamports as i64 - pre_lamports as i64) + (post_wsol as i64 - pre_wsol as i64)
+}
+
+/// How far realized missed the quote, in percent of the quote. None for a zero quote.
+fn deviation_pct(expected: i64, realized: i64) -> Option<f64> {
+    (expected > 0).then(|| (realized - expected).unsigned_abs() as f64 * 100.0 / expected as f64)
+}
+
+/// Micro-lamports per CU from a `SetComputeUnitPrice` instruction
+fn compute_unit_price(data: &[u8]) -> Option<u64> {
+    match data {
+        [SET_COMPUTE_UNIT_PRICE, price @ ..] if price.len() >= 8 => Some(u64::from_le_bytes(price[..8].try_into().ok()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settlement_decoding_and_deviation() {
+        // Paid 5k in fees and tip, got 120k more WSOL back than went in
+        assert_eq!(realized_pnl(2_000_000, 1_995_000, 100_000_000, 100_120_000), 115_000);
+        assert_eq!(realized_pnl(2_000_000, 1_990_000, 0, 0), -10_000);
+
+        let mut data = vec![SET_COMPUTE_UNIT_PRICE];
+        data.extend_from_slice(&25_000u64.to_le_bytes());
+        assert_eq!(compute_unit_price(&data), Some(25_000));
+        // SetComputeUnitLimit is a different instruction
+        assert_eq!(compute_unit_price(&[2, 0x40, 0x0d, 0x03, 0x00]), None);
+
+        assert_eq!(deviation_pct(100_000, 115_000), Some(15.0));
+        assert_eq!(deviation_pct(100_000, -10_000), Some(110.0));
+        assert_eq!(deviation_pct(0, -5_000), None);
+    }
+}
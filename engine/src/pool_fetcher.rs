@@ -1,4 +1,4 @@
-use solana_client::rpc_client::RpcClient;
+use executor::rpc_pool::RpcPool;
 use solana_sdk::pubkey::Pubkey;
 use std::error::Error;
 // use std::str::FromStr;
@@ -9,7 +9,7 @@ use mev_core::raydium::{AmmInfo, RaydiumSwapKeys};
 use std::sync::Arc;
 
 pub struct PoolKeyFetcher {
-    rpc: Arc<RpcClient>,
+    rpc: Arc<RpcPool>,
 }
 
 #[async_trait::async_trait]
@@ -31,21 +31,37 @@ impl strategy::ports::PoolKeyProvider for PoolKeyFetcher {
             .map_err(|e| anyhow::anyhow!("Meteora key fetch error: {}", e))?;
         Ok(keys)
     }
+
+    async fn get_raydium_clmm_keys(&self, pool_id: &Pubkey) -> Result<mev_core::raydium_clmm::RaydiumClmmSwapKeys, anyhow::Error> {
+        let keys = self.fetch_raydium_clmm_keys(pool_id).await
+            .map_err(|e| anyhow::anyhow!("Raydium CLMM key fetch error: {}", e))?;
+        Ok(keys)
+    }
+
+    async fn get_phoenix_keys(&self, market: &Pubkey) -> Result<mev_core::phoenix::PhoenixSwapKeys, anyhow::Error> {
+        let keys = self.fetch_phoenix_keys(market).await
+            .map_err(|e| anyhow::anyhow!("Phoenix key fetch error: {}", e))?;
+        Ok(keys)
+    }
+
+    async fn get_openbook_keys(&self, market: &Pubkey) -> Result<mev_core::openbook::OpenBookSwapKeys, anyhow::Error> {
+        let keys = self.fetch_openbook_keys(market).await
+            .map_err(|e| anyhow::anyhow!("OpenBook key fetch error: {}", e))?;
+        Ok(keys)
+    }
 }
 
 use mev_core::orca::{Whirlpool, OrcaSwapKeys};
 
 impl PoolKeyFetcher {
-    pub fn new(rpc_url: &str) -> Self {
-        Self {
-            rpc: Arc::new(RpcClient::new(rpc_url.to_string())),
-        }
+    pub fn new(rpc: Arc<RpcPool>) -> Self {
+        Self { rpc }
     }
 
     pub async fn fetch_raydium_keys(&self, pool_id: &Pubkey) -> Result<RaydiumSwapKeys, Box<dyn Error>> {
         tracing::debug!("🔍 Fetching Raydium keys for Pool: {}", pool_id);
         // ... (rest of the existing fetch_keys logic)
-        let account = self.rpc.get_account(pool_id)?;
+        let account = self.rpc.call_blocking(|c| c.get_account(pool_id))?;
         if account.data.len() < 752 {
             return Err("Account data too small for Raydium V4 (expected 752)".into());
         }
@@ -57,7 +73,7 @@ impl PoolKeyFetcher {
 
         // Fetch Serum Market account to get Bids, Asks, Event Queue, and Vaults
         let market_id = amm_info.market_id();
-        let market_account = self.rpc.get_account(&market_id)?;
+        let market_account = self.rpc.call_blocking(|c| c.get_account(&market_id))?;
         if market_account.data.len() < 388 {
             return Err("Serum market account data too small".into());
         }
@@ -97,7 +113,7 @@ impl PoolKeyFetcher {
 
     pub async fn fetch_orca_keys(&self, pool_id: &Pubkey) -> Result<OrcaSwapKeys, Box<dyn Error>> {
         tracing::debug!("🔍 Fetching Orca keys for Pool: {}", pool_id);
-        let account = self.rpc.get_account(pool_id)?;
+        let account = self.rpc.call_blocking(|c| c.get_account(pool_id))?;
         
         if account.data.len() < 653 {
             return Err("Account data too small for Whirlpool (expected 653)".into());
@@ -140,26 +156,102 @@ impl PoolKeyFetcher {
         })
     }
 
+    pub async fn fetch_raydium_clmm_keys(&self, pool_id: &Pubkey) -> Result<mev_core::raydium_clmm::RaydiumClmmSwapKeys, Box<dyn Error>> {
+        use mev_core::raydium_clmm::{PoolState, RaydiumClmmSwapKeys, POOL_STATE_LEN};
+        tracing::debug!("🔍 Fetching Raydium CLMM keys for Pool: {}", pool_id);
+        let account = self.rpc.call_blocking(|c| c.get_account(pool_id))?;
+
+        if account.data.len() < POOL_STATE_LEN {
+            return Err("Account data too small for Raydium CLMM (expected 1544)".into());
+        }
+
+        let pool: &PoolState = bytemuck::try_from_bytes(&account.data[..POOL_STATE_LEN])
+            .map_err(|_| "Failed to cast Raydium CLMM data layout")?;
+        if pool.swap_disabled() {
+            return Err("Raydium CLMM pool has swaps disabled".into());
+        }
+
+        let program_id = mev_core::constants::RAYDIUM_CLMM_PROGRAM;
+        let start_index = RaydiumClmmSwapKeys::get_tick_array_start_index(pool.tick_current(), pool.tick_spacing());
+        let ticks_in_array = RaydiumClmmSwapKeys::TICKS_PER_ARRAY * pool.tick_spacing() as i32;
+
+        Ok(RaydiumClmmSwapKeys {
+            pool_state: *pool_id,
+            amm_config: pool.amm_config(),
+            observation_state: pool.observation_key(),
+            mint_0: pool.token_mint_0(),
+            mint_1: pool.token_mint_1(),
+            token_vault_0: pool.token_vault_0(),
+            token_vault_1: pool.token_vault_1(),
+            user_owner: Pubkey::default(),   // Will be set by executor to payer
+            user_token_0: Pubkey::default(), // Will be set by executor
+            user_token_1: Pubkey::default(), // Will be set by executor
+            tick_array_current: RaydiumClmmSwapKeys::derive_tick_array_pda(pool_id, start_index, &program_id),
+            tick_array_lower: RaydiumClmmSwapKeys::derive_tick_array_pda(pool_id, start_index - ticks_in_array, &program_id),
+            tick_array_upper: RaydiumClmmSwapKeys::derive_tick_array_pda(pool_id, start_index + ticks_in_array, &program_id),
+        })
+    }
+
+    pub async fn fetch_phoenix_keys(&self, market: &Pubkey) -> Result<mev_core::phoenix::PhoenixSwapKeys, Box<dyn Error>> {
+        tracing::debug!("🔍 Fetching Phoenix keys for Market: {}", market);
+        let account = self.rpc.call_blocking(|c| c.get_account(market))?;
+        let parsed = mev_core::phoenix::PhoenixMarket::from_account_data(&account.data)?;
+
+        Ok(mev_core::phoenix::PhoenixSwapKeys {
+            market: *market,
+            base_mint: parsed.base_mint,
+            quote_mint: parsed.quote_mint,
+            base_vault: parsed.base_vault,
+            quote_vault: parsed.quote_vault,
+            base_lot_size: parsed.base_lot_size,
+            quote_lot_size: parsed.quote_lot_size,
+            trader: Pubkey::default(),               // Will be set by executor to payer
+            trader_base_account: Pubkey::default(),  // Will be set by executor
+            trader_quote_account: Pubkey::default(), // Will be set by executor
+        })
+    }
+
+    pub async fn fetch_openbook_keys(&self, market: &Pubkey) -> Result<mev_core::openbook::OpenBookSwapKeys, Box<dyn Error>> {
+        tracing::debug!("🔍 Fetching OpenBook keys for Market: {}", market);
+        let account = self.rpc.call_blocking(|c| c.get_account(market))?;
+        let parsed = mev_core::openbook::OpenBookMarket::from_account_data(&account.data)?;
+
+        Ok(mev_core::openbook::OpenBookSwapKeys {
+            market: *market,
+            market_authority: parsed.market_authority,
+            bids: parsed.bids,
+            asks: parsed.asks,
+            event_heap: parsed.event_heap,
+            base_vault: parsed.base_vault,
+            quote_vault: parsed.quote_vault,
+            base_mint: parsed.base_mint,
+            quote_mint: parsed.quote_mint,
+            oracle_a: parsed.oracle_a,
+            oracle_b: parsed.oracle_b,
+            base_lot_size: parsed.base_lot_size,
+            quote_lot_size: parsed.quote_lot_size,
+            signer: Pubkey::default(),             // Will be set by executor to payer
+            user_base_account: Pubkey::default(),  // Will be set by executor
+            user_quote_account: Pubkey::default(), // Will be set by executor
+        })
+    }
+
     pub async fn fetch_meteora_keys(&self, pool_id: &Pubkey) -> Result<mev_core::meteora::MeteoraSwapKeys, Box<dyn Error>> {
         tracing::debug!("🔍 Fetching Meteora keys for Pool: {}", pool_id);
-        let account = self.rpc.get_account(pool_id)?;
+        let account = self.rpc.call_blocking(|c| c.get_account(pool_id))?;
         
-        let dlmm: &mev_core::meteora::MeteoraDLMM = bytemuck::try_from_bytes(&account.data[..1024])
-            .map_err(|_| "Failed to cast Meteora data layout")?;
+        let dlmm: mev_core::meteora::MeteoraDLMM = bytemuck::try_pod_read_unaligned(
+            account.data.get(..mev_core::meteora::LB_PAIR_LEN).ok_or("Meteora account too small")?
+        ).map_err(|_| "Failed to cast Meteora data layout")?;
 
-        // Meteora DLMM usually has reserves derived or stored. 
-        // For instruction building, we need specific vault addresses.
-        // We can derive them if we know they are ATAs of the pool or use fixed offsets.
-        // For now, providing a structured guess based on common DLMM layouts.
-        
         Ok(mev_core::meteora::MeteoraSwapKeys {
             dlmm_pool: *pool_id,
             bin_array_bitmap_extension: None, // Optional
-            reserve_x: Pubkey::default(), // To be resolved if needed
-            reserve_y: Pubkey::default(), 
+            reserve_x: dlmm.reserve_x(),
+            reserve_y: dlmm.reserve_y(),
             token_x_mint: dlmm.token_x_mint(),
             token_y_mint: dlmm.token_y_mint(),
-            oracle: Pubkey::default(),
+            oracle: dlmm.oracle(),
             user_token_x: Pubkey::default(),
             user_token_y: Pubkey::default(),
             user_owner: Pubkey::default(),
@@ -0,0 +1,94 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_sdk::pubkey::Pubkey;
+use mev_core::constants::*;
+use tracing::info;
+
+/// Every venue (and the flash-loan lender) we build instructions for. A typo here silently breaks a whole builder.
+pub const VENUE_PROGRAMS: &[(&str, Pubkey)] = &[
+    ("Raydium V4", RAYDIUM_V4_PROGRAM),
+    ("Orca Whirlpool", ORCA_WHIRLPOOL_PROGRAM),
+    ("Raydium CLMM", RAYDIUM_CLMM_PROGRAM),
+    ("Meteora DLMM", METEORA_PROGRAM_ID),
+    ("Pump.fun", PUMP_FUN_PROGRAM),
+    ("Phoenix", PHOENIX_PROGRAM),
+    ("OpenBook v2", OPENBOOK_V2_PROGRAM),
+    ("Raydium LaunchLab", RAYDIUM_LAUNCHLAB_PROGRAM),
+    ("Moonshot", MOONSHOT_PROGRAM),
+    #[cfg(not(feature = "paper-only"))]
+    ("Solend", executor::flash_loan::SOLEND_PROGRAM_ID),
+];
+
+// UpgradeableLoaderState::Program discriminant (bincode u32 LE)
+const PROGRAM_STATE_TAG: [u8; 4] = [2, 0, 0, 0];
+
+/// Checks a fetched account really is a deployed upgradeable program
+pub fn check_program_account(name: &str, program_id: &Pubkey, account: Option<&Account>) -> Result<(), String> {
+    let account = account.ok_or_else(|| format!("{} program {} does not exist on this cluster", name, program_id))?;
+
+    if !account.executable {
+        return Err(format!("{} program {} is not executable", name, program_id));
+    }
+    if account.owner != bpf_loader_upgradeable::id() {
+        return Err(format!("{} program {} owned by {} (expected upgradeable loader)", name, program_id, account.owner));
+    }
+    if account.data.len() != UpgradeableLoaderState::size_of_program() || account.data[..4] != PROGRAM_STATE_TAG {
+        return Err(format!("{} program {} has unexpected account layout ({} bytes)", name, program_id, account.data.len()));
+    }
+    Ok(())
+}
+
+/// Fetches all venue program accounts in one call and fails on the first mismatch
+pub async fn verify_venue_programs(rpc_url: &str) -> Result<(), String> {
+    let rpc = RpcClient::new(rpc_url.to_string());
+    let ids: Vec<Pubkey> = VENUE_PROGRAMS.iter().map(|(_, id)| *id).collect();
+    let accounts = rpc.get_multiple_accounts(&ids).await
+        .map_err(|e| format!("Failed to fetch program accounts: {}", e))?;
+
+    for ((name, id), account) in VENUE_PROGRAMS.iter().zip(accounts.iter()) {
+        check_program_account(name, id, account.as_ref())?;
+        info!("   ├─ {} ✅ {}", name, id);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program_account() -> Account {
+        let mut data = PROGRAM_STATE_TAG.to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        Account {
+            lamports: 1_141_440,
+            data,
+            owner: bpf_loader_upgradeable::id(),
+            executable: true,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_valid_program_passes() {
+        assert!(check_program_account("Test", &Pubkey::new_unique(), Some(&program_account())).is_ok());
+    }
+
+    #[test]
+    fn test_missing_or_non_program_fails() {
+        let id = Pubkey::new_unique();
+        assert!(check_program_account("Test", &id, None).is_err());
+
+        let mut not_exec = program_account();
+        not_exec.executable = false;
+        assert!(check_program_account("Test", &id, Some(&not_exec)).is_err());
+
+        let mut wrong_owner = program_account();
+        wrong_owner.owner = Pubkey::new_unique();
+        assert!(check_program_account("Test", &id, Some(&wrong_owner)).is_err());
+
+        let mut wrong_layout = program_account();
+        wrong_layout.data[0] = 3;
+        assert!(check_program_account("Test", &id, Some(&wrong_layout)).is_err());
+    }
+}
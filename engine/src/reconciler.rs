@@ -0,0 +1,203 @@
+/// Pool Reserve Reconciliation
+///
+/// The graph only knows what the account stream told it. A dropped
+/// notification, an out-of-order update or a bad offset in a decoder leaves a
+/// pool quietly wrong, and every route through it is priced off fiction. This
+/// task periodically re-reads the highest-weighted pools straight from RPC,
+/// compares them with the graph and overwrites any that drifted too far.
+use std::sync::Arc;
+use std::time::Duration;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::PoolUpdate;
+use mev_core::constants::{ORCA_WHIRLPOOL_PROGRAM, RAYDIUM_CLMM_PROGRAM, RAYDIUM_V4_PROGRAM};
+use strategy::StrategyEngine;
+use crate::scoring::PoolScoringEngine;
+
+// SPL token account: mint (32) + owner (32) + amount (8)
+const TOKEN_AMOUNT_OFFSET: usize = 64;
+
+pub struct ReserveReconciler {
+    rpc: RpcClient,
+    engine: Arc<StrategyEngine>,
+    scoring: Arc<PoolScoringEngine>,
+    top_pools: usize,
+    threshold_bps: u64,
+}
+
+impl ReserveReconciler {
+    pub fn new(rpc_url: &str, engine: Arc<StrategyEngine>, scoring: Arc<PoolScoringEngine>, top_pools: usize, threshold_bps: u64) -> Self {
+        Self {
+            rpc: RpcClient::new(rpc_url.to_string()),
+            engine,
+            scoring,
+            top_pools,
+            threshold_bps,
+        }
+    }
+
+    pub async fn run(self: Arc<Self>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await; // Let the graph fill before the first audit
+        loop {
+            ticker.tick().await;
+            let corrected = self.audit_once().await;
+            if corrected > 0 {
+                tracing::warn!("🧾 Reserve audit corrected {} pool(s)", corrected);
+            }
+        }
+    }
+
+    /// Audits the top pools once. Returns how many were corrected.
+    pub async fn audit_once(&self) -> usize {
+        let mut corrected = 0;
+        for weight in self.scoring.get_top_pools(self.top_pools) {
+            let Some(graph_state) = self.engine.pool_state(&weight.pool_address) else {
+                continue;
+            };
+            let fresh = match self.fetch_fresh(&graph_state).await {
+                Ok(Some(fresh)) => fresh,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::debug!("🧾 Reserve audit fetch failed for {}: {}", graph_state.pool_address, e);
+                    continue;
+                }
+            };
+
+            mev_core::telemetry::RESERVE_AUDITS_TOTAL.inc();
+            let drift = divergence_bps(&graph_state, &fresh);
+            if drift > self.threshold_bps {
+                tracing::warn!(
+                    "🧾 AUDIT: pool {} diverged {} bps | graph: ({}, {}, {:?}, {:?}) | chain: ({}, {}, {:?}, {:?})",
+                    graph_state.pool_address, drift,
+                    graph_state.reserve_a, graph_state.reserve_b, graph_state.price_sqrt, graph_state.liquidity,
+                    fresh.reserve_a, fresh.reserve_b, fresh.price_sqrt, fresh.liquidity,
+                );
+                mev_core::telemetry::RESERVE_DIVERGENCES_TOTAL.inc();
+                self.engine.correct_pool_state(fresh);
+                corrected += 1;
+            }
+        }
+        corrected
+    }
+
+    /// Rebuilds the pool's state from chain, keeping the graph's orientation and fee
+    async fn fetch_fresh(&self, graph_state: &PoolUpdate) -> anyhow::Result<Option<PoolUpdate>> {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_secs();
+
+        if graph_state.program_id == ORCA_WHIRLPOOL_PROGRAM {
+            let account = self.rpc.get_account(&graph_state.pool_address).await?;
+            if account.data.len() < 653 {
+                return Ok(None);
+            }
+            let whirlpool: &mev_core::orca::Whirlpool = bytemuck::try_from_bytes(&account.data[..653])
+                .map_err(|_| anyhow::anyhow!("bad Whirlpool layout"))?;
+            return Ok(Some(PoolUpdate {
+                price_sqrt: Some(whirlpool.sqrt_price()),
+                liquidity: Some(whirlpool.liquidity()),
+                timestamp: now,
+                ..graph_state.clone()
+            }));
+        }
+
+        if graph_state.program_id == RAYDIUM_CLMM_PROGRAM {
+            let account = self.rpc.get_account(&graph_state.pool_address).await?;
+            let Ok(pool) = bytemuck::try_pod_read_unaligned::<mev_core::raydium_clmm::PoolState>(&account.data) else {
+                return Ok(None);
+            };
+            return Ok(Some(PoolUpdate {
+                price_sqrt: Some(pool.sqrt_price_x64()),
+                liquidity: Some(pool.liquidity()),
+                timestamp: now,
+                ..graph_state.clone()
+            }));
+        }
+
+        if graph_state.program_id == RAYDIUM_V4_PROGRAM {
+            // Vault balances are independent of the AmmInfo decoder the stream uses
+            let account = self.rpc.get_account(&graph_state.pool_address).await?;
+            if account.data.len() < 752 {
+                return Ok(None);
+            }
+            let amm: &mev_core::raydium::AmmInfo = bytemuck::try_from_bytes(&account.data[..752])
+                .map_err(|_| anyhow::anyhow!("bad AmmInfo layout"))?;
+            let vaults = self.rpc.get_multiple_accounts(&[amm.base_vault(), amm.quote_vault()]).await?;
+            let (Some(base), Some(quote)) = (token_amount(&vaults[0]), token_amount(&vaults[1])) else {
+                return Ok(None);
+            };
+            let (reserve_a, reserve_b) = if graph_state.mint_a == amm.base_mint() { (base, quote) } else { (quote, base) };
+            return Ok(Some(PoolUpdate {
+                reserve_a: reserve_a as u128,
+                reserve_b: reserve_b as u128,
+                timestamp: now,
+                ..graph_state.clone()
+            }));
+        }
+
+        Ok(None)
+    }
+}
+
+fn token_amount(account: &Option<solana_sdk::account::Account>) -> Option<u64> {
+    let data = &account.as_ref()?.data;
+    Some(u64::from_le_bytes(data.get(TOKEN_AMOUNT_OFFSET..TOKEN_AMOUNT_OFFSET + 8)?.try_into().ok()?))
+}
+
+fn relative_bps(ours: u128, theirs: u128) -> u64 {
+    if ours == theirs {
+        return 0;
+    }
+    let base = ours.max(theirs).max(1);
+    (ours.abs_diff(theirs).saturating_mul(10_000) / base).min(u64::MAX as u128) as u64
+}
+
+/// Largest relative gap between two views of the same pool, in bps
+pub fn divergence_bps(graph: &PoolUpdate, fresh: &PoolUpdate) -> u64 {
+    [
+        relative_bps(graph.reserve_a, fresh.reserve_a),
+        relative_bps(graph.reserve_b, fresh.reserve_b),
+        relative_bps(graph.price_sqrt.unwrap_or(0), fresh.price_sqrt.unwrap_or(0)),
+        relative_bps(graph.liquidity.unwrap_or(0), fresh.liquidity.unwrap_or(0)),
+    ]
+    .into_iter()
+    .max()
+    .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cpmm(reserve_a: u128, reserve_b: u128) -> PoolUpdate {
+        PoolUpdate {
+            pool_address: Pubkey::new_unique(),
+            program_id: RAYDIUM_V4_PROGRAM,
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            reserve_a,
+            reserve_b,
+            price_sqrt: None,
+            liquidity: None,
+            fee_bps: 25,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_divergence_takes_worst_field() {
+        let graph = cpmm(1_000_000, 2_000_000);
+        assert_eq!(divergence_bps(&graph, &graph.clone()), 0);
+        assert_eq!(divergence_bps(&graph, &PoolUpdate { reserve_b: 1_900_000, ..graph.clone() }), 500);
+        assert_eq!(divergence_bps(&graph, &PoolUpdate { reserve_a: 1_001_000, ..graph.clone() }), 9);
+    }
+
+    #[test]
+    fn test_divergence_on_clmm_and_swapped_sides() {
+        let orca = PoolUpdate { price_sqrt: Some(1 << 64), liquidity: Some(5_000), ..cpmm(0, 0) };
+        assert_eq!(divergence_bps(&orca, &PoolUpdate { liquidity: Some(0), ..orca.clone() }), 10_000);
+
+        // Reserves stored the wrong way round show up as a huge gap
+        let graph = cpmm(1_000, 50_000);
+        assert!(divergence_bps(&graph, &cpmm(50_000, 1_000)) > 9_000);
+    }
+}
@@ -1,8 +1,10 @@
 use mev_core::{PoolUpdate, ArbitrageOpportunity};
+use strategy::analytics::depth_snapshot::{DepthSnapshot, PoolDepth};
+use serde_json::{json, Value};
 use tokio::fs::{OpenOptions, create_dir_all, File};
 use tokio::io::{AsyncWriteExt, BufWriter};
 use std::path::Path;
-use tracing::{info, error};
+use tracing::{info, warn, error};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -10,20 +12,46 @@ use tokio::sync::Mutex;
 pub struct AsyncCsvWriter {
     pool_writer: Arc<Mutex<BufWriter<File>>>,
     arbitrage_writer: Arc<Mutex<BufWriter<File>>>,
+    /// One JSON object per dispatched trade: route plus the pre-trade state of its pools
+    depth_writer: Arc<Mutex<BufWriter<File>>>,
+    /// Effective configuration the rows were produced under
+    config_hash: Arc<str>,
 }
 
+/// Columns of `market_data.csv`, which `--backtest` replays
+pub const MARKET_DATA_HEADER: &str = "timestamp,pool_address,program_id,reserve_a,reserve_b,price_ratio,mint_a,mint_b,price_sqrt,liquidity,fee_bps\n";
+
+/// One `market_data.csv` row. Columns after price_ratio carry the rest of the PoolUpdate so `--backtest` can replay it
+pub fn market_row(update: &PoolUpdate) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{}\n",
+        update.timestamp,
+        update.pool_address,
+        update.program_id,
+        update.reserve_a,
+        update.reserve_b,
+        if update.reserve_a > 0 { (update.reserve_b as f64 / update.reserve_a as f64).to_string() } else { "0".to_string() },
+        update.mint_a,
+        update.mint_b,
+        update.price_sqrt.map(|p| p.to_string()).unwrap_or_default(),
+        update.liquidity.map(|l| l.to_string()).unwrap_or_default(),
+        update.fee_bps,
+    )
+}
+
+const ARBITRAGE_HEADER: &str = "timestamp,num_hops,profit_lamports,input_amount,total_fees_bps,max_price_impact_bps,min_liquidity,route,config_hash\n";
+
 impl AsyncCsvWriter {
-    pub async fn new(output_dir: &str) -> Result<Self, std::io::Error> {
-        let path = Path::new(output_dir);
-        if !path.exists() {
-            create_dir_all(path).await?;
+    pub async fn new(output_dir: &Path, config_hash: &str) -> Result<Self, std::io::Error> {
+        if !output_dir.exists() {
+            create_dir_all(output_dir).await?;
         }
         
-        let pool_data_path = format!("{}/market_data.csv", output_dir);
-        let arbitrage_data_path = format!("{}/arbitrage_data.csv", output_dir);
+        let pool_data_path = output_dir.join("market_data.csv");
+        let arbitrage_data_path = output_dir.join("arbitrage_data.csv");
         
         // 1. Prepare Pool Data Writer
-        let pool_exists = Path::new(&pool_data_path).exists();
+        let pool_exists = pool_data_path.exists();
         let pool_file = OpenOptions::new()
             .create(true)
             .append(true)
@@ -32,13 +60,22 @@ impl AsyncCsvWriter {
         let mut pool_writer = BufWriter::new(pool_file);
         
         if !pool_exists {
-            let header = "timestamp,pool_address,program_id,reserve_a,reserve_b,price_ratio\n";
-            pool_writer.write_all(header.as_bytes()).await?;
+            pool_writer.write_all(MARKET_DATA_HEADER.as_bytes()).await?;
             pool_writer.flush().await?;
         }
 
-        // 2. Prepare Arbitrage Data Writer
-        let arb_exists = Path::new(&arbitrage_data_path).exists();
+        // 2. Prepare Arbitrage Data Writer (a file with older columns is set aside, not appended to)
+        if let Ok(existing) = std::fs::File::open(&arbitrage_data_path) {
+            use std::io::BufRead;
+            let header = std::io::BufReader::new(existing).lines().next().and_then(Result::ok);
+            if header.is_some_and(|header| header != ARBITRAGE_HEADER.trim_end()) {
+                let stamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+                let legacy = output_dir.join(format!("arbitrage_data.{}.csv", stamp));
+                warn!("⚠️ arbitrage_data.csv has an older header, moved to {}", legacy.display());
+                tokio::fs::rename(&arbitrage_data_path, &legacy).await?;
+            }
+        }
+        let arb_exists = arbitrage_data_path.exists();
         let arb_file = OpenOptions::new()
             .create(true)
             .append(true)
@@ -47,29 +84,29 @@ impl AsyncCsvWriter {
         let mut arb_writer = BufWriter::new(arb_file);
 
         if !arb_exists {
-            let header = "timestamp,num_hops,profit_lamports,input_amount,total_fees_bps,max_price_impact_bps,min_liquidity,route\n";
-            arb_writer.write_all(header.as_bytes()).await?;
+            arb_writer.write_all(ARBITRAGE_HEADER.as_bytes()).await?;
             arb_writer.flush().await?;
         }
 
-        info!("✅ Data Recorder initialized at {}", output_dir);
+        // 3. Depth snapshots (JSON lines, no header)
+        let depth_file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(output_dir.join("depth_snapshots.jsonl"))
+            .await?;
+
+        info!("✅ Data Recorder initialized at {}", output_dir.display());
 
         Ok(Self { 
             pool_writer: Arc::new(Mutex::new(pool_writer)),
             arbitrage_writer: Arc::new(Mutex::new(arb_writer)),
+            depth_writer: Arc::new(Mutex::new(BufWriter::new(depth_file))),
+            config_hash: Arc::from(config_hash),
         })
     }
 
     pub async fn record(&self, update: PoolUpdate) {
-        let line = format!(
-            "{},{},{},{},{},{}\n",
-            update.timestamp,
-            update.pool_address,
-            update.program_id,
-            update.reserve_a,
-            update.reserve_b,
-            if update.reserve_a > 0 { (update.reserve_b as f64 / update.reserve_a as f64).to_string() } else { "0".to_string() }
-        );
+        let line = market_row(&update);
 
         let mut writer = self.pool_writer.lock().await;
         if let Err(e) = writer.write_all(line.as_bytes()).await {
@@ -92,7 +129,7 @@ impl AsyncCsvWriter {
             .join("->");
         
         let line = format!(
-            "{},{},{},{},{},{},{},\"{}\"\n",
+            "{},{},{},{},{},{},{},\"{}\",{}\n",
             opp.timestamp,
             opp.steps.len(),
             opp.expected_profit_lamports,
@@ -100,7 +137,8 @@ impl AsyncCsvWriter {
             opp.total_fees_bps,
             opp.max_price_impact_bps,
             opp.min_liquidity,
-            route
+            route,
+            self.config_hash
         );
 
         let mut writer = self.arbitrage_writer.lock().await;
@@ -111,4 +149,106 @@ impl AsyncCsvWriter {
             error!("Failed to flush arbitrage data CSV: {}", e);
         }
     }
+
+    pub async fn record_depth_snapshot(&self, snapshot: &DepthSnapshot) {
+        let mut record = depth_snapshot_json(snapshot);
+        record["config_hash"] = Value::from(&*self.config_hash);
+        let line = format!("{}\n", record);
+        let mut writer = self.depth_writer.lock().await;
+        if let Err(e) = writer.write_all(line.as_bytes()).await {
+            error!("Failed to write depth snapshot: {}", e);
+        }
+        if let Err(e) = writer.flush().await {
+            error!("Failed to flush depth snapshots: {}", e);
+        }
+    }
+}
+
+/// Pubkeys as base58 and u128s as strings, so the file reads the same in any JSON tool
+pub fn depth_snapshot_json(snapshot: &DepthSnapshot) -> Value {
+    let opp = &snapshot.opportunity;
+    json!({
+        "captured_ms": snapshot.captured_ms,
+        "bundle_id": snapshot.bundle_id,
+        "input_amount": opp.input_amount,
+        "expected_profit_lamports": opp.expected_profit_lamports,
+        "route": opp.steps.iter().map(|s| json!({
+            "pool": s.pool.to_string(),
+            "program_id": s.program_id.to_string(),
+            "input_mint": s.input_mint.to_string(),
+            "output_mint": s.output_mint.to_string(),
+            "expected_output": s.expected_output,
+        })).collect::<Vec<_>>(),
+        "pools": snapshot.pools.iter().map(pool_depth_json).collect::<Vec<_>>(),
+    })
+}
+
+fn pool_depth_json(depth: &PoolDepth) -> Value {
+    let state = depth.state.as_ref().map(|u| json!({
+        "program_id": u.program_id.to_string(),
+        "mint_a": u.mint_a.to_string(),
+        "mint_b": u.mint_b.to_string(),
+        "reserve_a": u.reserve_a.to_string(),
+        "reserve_b": u.reserve_b.to_string(),
+        "price_sqrt": u.price_sqrt.map(|p| p.to_string()),
+        "liquidity": u.liquidity.map(|l| l.to_string()),
+        "fee_bps": u.fee_bps,
+        "timestamp": u.timestamp,
+    }));
+    let dlmm = depth.dlmm.as_ref().map(|d| json!({
+        "active_id": d.active_id,
+        "fee_bps": d.fee_bps,
+        "bins": d.bins.iter().map(|(id, bin)| json!([id, bin.amount_x, bin.amount_y, bin.price.to_string()])).collect::<Vec<_>>(),
+    }));
+    let book = depth.book.as_ref().map(|b| json!({
+        "base_mint": b.base_mint.to_string(),
+        "quote_mint": b.quote_mint.to_string(),
+        "base_lot_size": b.base_lot_size,
+        "taker_fee_bps": b.taker_fee_bps,
+        "bids": b.bids.iter().map(|l| [l.price, l.lots]).collect::<Vec<_>>(),
+        "asks": b.asks.iter().map(|l| [l.price, l.lots]).collect::<Vec<_>>(),
+    }));
+    json!({ "pool": depth.pool.to_string(), "state": state, "dlmm": dlmm, "book": book })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::clob::{Level, OrderBook};
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_depth_snapshot_json_keeps_full_precision() {
+        let pool = Pubkey::new_unique();
+        let snapshot = DepthSnapshot {
+            captured_ms: 1_700_000_000_000,
+            bundle_id: "abc".into(),
+            opportunity: ArbitrageOpportunity::default(),
+            pools: vec![PoolDepth {
+                pool,
+                state: Some(PoolUpdate {
+                    pool_address: pool,
+                    program_id: Pubkey::new_unique(),
+                    mint_a: Pubkey::new_unique(),
+                    mint_b: Pubkey::new_unique(),
+                    reserve_a: u128::MAX,
+                    reserve_b: 1,
+                    price_sqrt: None,
+                    liquidity: Some(42),
+                    fee_bps: 25,
+                    timestamp: 9,
+                }),
+                dlmm: None,
+                book: Some(Arc::new(OrderBook { bids: vec![Level { price: 100, lots: 5 }], ..Default::default() })),
+            }],
+        };
+
+        let value = depth_snapshot_json(&snapshot);
+        let depth = &value["pools"][0];
+        assert_eq!(depth["pool"], pool.to_string());
+        assert_eq!(depth["state"]["reserve_a"], u128::MAX.to_string());
+        assert_eq!(depth["state"]["price_sqrt"], Value::Null);
+        assert_eq!(depth["book"]["bids"][0], json!([100, 5]));
+        assert!(depth["dlmm"].is_null());
+    }
 }
@@ -0,0 +1,180 @@
+/// Recovery Seller
+///
+/// A route that stops partway leaves the wallet holding one of its
+/// intermediate tokens: a fallback transaction sent outside a bundle, or a hop
+/// that paid more than quoted, so the next hop swapped less than arrived. The
+/// reconciler reads every landed transaction's token balances, and any
+/// intermediate mint the payer gained is queued here. Each residual is sold
+/// back into the route's start mint over the best venue in the graph, retried
+/// a few times, and reported to the operator either way. Residuals worth less
+/// than `RECOVERY_MIN_OUTPUT_LAMPORTS` are reported but left in the wallet.
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::mpsc;
+use mev_core::constants::SOL_MINT;
+use mev_core::ArbitrageOpportunity;
+use strategy::ports::ExecutionPort;
+use strategy::StrategyEngine;
+use crate::alerts::{AlertManager, AlertSeverity};
+use crate::token_metadata::TokenMetadata;
+
+const QUEUE_CAPACITY: usize = 64;
+const SELL_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(15);
+
+/// Tokens a settled trade left behind
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Residual {
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Where the route started; the residual is sold back into it
+    pub into: Pubkey,
+    /// Signature of the trade that left it
+    pub origin: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RecoveryPolicy {
+    pub min_output_lamports: u64,
+    pub tip_lamports: u64,
+    pub max_slippage_bps: u16,
+}
+
+pub struct RecoverySeller {
+    sender: mpsc::Sender<Residual>,
+    metadata: Arc<TokenMetadata>,
+}
+
+impl RecoverySeller {
+    /// Starts the sell task
+    pub fn new(engine: Arc<StrategyEngine>, executor: Arc<dyn ExecutionPort>, alerts: Arc<AlertManager>, metadata: Arc<TokenMetadata>, policy: RecoveryPolicy) -> Self {
+        let (sender, rx) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(sell_loop(engine, executor, alerts, Arc::clone(&metadata), policy, rx));
+        Self { sender, metadata }
+    }
+
+    pub fn queue(&self, residual: Residual) {
+        tracing::warn!("🧹 {} left {} in the wallet; queueing a recovery sell", residual.origin, self.metadata.format(&residual.mint, residual.amount));
+        if let Err(e) = self.sender.try_send(residual) {
+            mev_core::telemetry::RECOVERY_SELLS.with_label_values(&["dropped"]).inc();
+            tracing::error!("🧹 Recovery queue full; {} needs a manual sell", e.into_inner().mint);
+        }
+    }
+}
+
+/// Intermediate mints of `opportunity` the payer ended up holding more of, with the amounts.
+/// The start mint is settled by the PnL itself; SOL is in the PnL too.
+pub fn residuals(opportunity: &ArbitrageOpportunity, token_deltas: &HashMap<Pubkey, i128>, origin: &str) -> Vec<Residual> {
+    let Some(into) = opportunity.steps.first().map(|s| s.input_mint) else {
+        return Vec::new();
+    };
+    let mut found: Vec<Residual> = Vec::new();
+    for step in &opportunity.steps {
+        let mint = step.output_mint;
+        if mint == into || mint == SOL_MINT || found.iter().any(|r| r.mint == mint) {
+            continue;
+        }
+        if let Some(&delta) = token_deltas.get(&mint).filter(|d| **d > 0) {
+            found.push(Residual { mint, amount: delta.min(u64::MAX as i128) as u64, into, origin: origin.to_string() });
+        }
+    }
+    found
+}
+
+async fn sell_loop(
+    engine: Arc<StrategyEngine>,
+    executor: Arc<dyn ExecutionPort>,
+    alerts: Arc<AlertManager>,
+    metadata: Arc<TokenMetadata>,
+    policy: RecoveryPolicy,
+    mut rx: mpsc::Receiver<Residual>,
+) {
+    while let Some(residual) = rx.recv().await {
+        if let Err(e) = metadata.resolve(&[residual.mint, residual.into]).await {
+            tracing::debug!("🏷️ Token decimals lookup failed: {}", e);
+        }
+        let (outcome, detail) = sell(&engine, executor.as_ref(), &metadata, &policy, &residual).await;
+        mev_core::telemetry::RECOVERY_SELLS.with_label_values(&[outcome]).inc();
+        let severity = if outcome == "sold" { AlertSeverity::Warning } else { AlertSeverity::Critical };
+        let message = format!("{} ({}) left by {}: {}", metadata.format(&residual.mint, residual.amount), residual.mint, residual.origin, detail);
+        tracing::warn!("🧹 Recovery {}: {}", outcome, message);
+        alerts.send_alert(severity, "RESIDUAL TOKENS", &message, vec![]).await;
+    }
+}
+
+/// One residual, re-quoted on every attempt. Returns the outcome label and what happened.
+async fn sell(engine: &StrategyEngine, executor: &dyn ExecutionPort, metadata: &TokenMetadata, policy: &RecoveryPolicy, residual: &Residual) -> (&'static str, String) {
+    let mut last_error = String::new();
+    for attempt in 1..=SELL_ATTEMPTS {
+        if attempt > 1 {
+            tokio::time::sleep(RETRY_DELAY).await;
+        }
+        let Some(step) = engine.best_hop(&residual.mint, &residual.into, residual.amount) else {
+            last_error = format!("no venue sells it into {} within the impact cap", metadata.symbol(&residual.into));
+            continue;
+        };
+        let value_lamports = if residual.into == SOL_MINT {
+            step.expected_output
+        } else {
+            engine.spot_price(&residual.into, &SOL_MINT).map_or(u64::MAX, |rate| (step.expected_output as f64 * rate) as u64)
+        };
+        if value_lamports < policy.min_output_lamports {
+            return ("dust", format!("worth ~{}, below RECOVERY_MIN_OUTPUT_LAMPORTS; left in the wallet", metadata.format(&SOL_MINT, value_lamports)));
+        }
+
+        let expected_output = step.expected_output;
+        let opportunity = ArbitrageOpportunity {
+            steps: [step].into_iter().collect(),
+            input_amount: residual.amount,
+            timestamp: mev_core::clock::system().unix_secs(),
+            ..Default::default()
+        };
+        match executor.build_and_send_bundle(opportunity, solana_sdk::hash::Hash::default(), policy.tip_lamports, policy.max_slippage_bps).await {
+            Ok(signature) => return ("sold", format!("sold for ~{} in {}", metadata.format(&residual.into, expected_output), signature)),
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+    ("failed", format!("sell failed after {} attempts, sell it manually: {}", SELL_ATTEMPTS, last_error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::SwapStep;
+
+    #[test]
+    fn test_residuals_are_gained_intermediate_mints() {
+        let (usdc, bonk) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let hop = |input_mint, output_mint| SwapStep {
+            pool: Pubkey::new_unique(),
+            program_id: mev_core::constants::RAYDIUM_V4_PROGRAM,
+            input_mint,
+            output_mint,
+            expected_output: 1_000,
+        };
+        let opp = ArbitrageOpportunity {
+            steps: [hop(SOL_MINT, usdc), hop(usdc, bonk), hop(bonk, SOL_MINT)].into_iter().collect(),
+            input_amount: 1_000,
+            ..Default::default()
+        };
+
+        // USDC was swapped away in full; BONK came in and only part of it went back out
+        let deltas = HashMap::from([(usdc, 0), (bonk, 250), (SOL_MINT, 5_000)]);
+        let found = residuals(&opp, &deltas, "sig");
+        assert_eq!(found, vec![Residual { mint: bonk, amount: 250, into: SOL_MINT, origin: "sig".to_string() }]);
+
+        // A balance that went down (pre-existing inventory spent) is not a residual
+        assert!(residuals(&opp, &HashMap::from([(bonk, -10)]), "sig").is_empty());
+
+        // An unwind floors its output at the quote, not at the input
+        let unwind = ArbitrageOpportunity {
+            steps: [SwapStep { expected_output: 500, ..hop(bonk, SOL_MINT) }].into_iter().collect(),
+            input_amount: 250,
+            ..Default::default()
+        };
+        assert_eq!(unwind.min_amount_out(100), 495);
+        assert_eq!(ArbitrageOpportunity { input_amount: 10_000, ..opp }.min_amount_out(100), 9_900);
+    }
+}
@@ -1,4 +1,21 @@
+use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, AtomicU32, Ordering};
+use mev_core::ArbitrageOpportunity;
+use strategy::analytics::in_flight::{exposed_mints, InFlightBook};
+use strategy::flash_loan::Inventory;
+use strategy::pool_rate_limit::PoolRateLimiter;
+use strategy::ports::{ExposureLimit, ExposurePort};
+
+/// Per-trade exposure limits; 0 disables each
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExposureLimits {
+    /// Input notional of in-flight bundles routed through any one intermediate mint
+    pub max_mint_notional_lamports: u64,
+    /// Trades through any one pool per rolling minute, whatever the pair
+    pub max_pool_trades_per_min: u32,
+    /// Own (non-borrowed) input of one trade, in bps of the start mint balance
+    pub max_wallet_share_bps: u16,
+}
 
 pub struct RiskManager {
     // Daily limits
@@ -18,6 +35,12 @@ pub struct RiskManager {
     // Circuit breaker
     pub consecutive_losses: AtomicU32,
     pub circuit_breaker_triggered: std::sync::atomic::AtomicBool,
+
+    // Exposure limits
+    pub exposure: ExposureLimits,
+    in_flight: Option<Arc<InFlightBook>>,
+    inventory: Option<Arc<Inventory>>,
+    pool_trades: PoolRateLimiter,
 }
 
 impl RiskManager {
@@ -34,8 +57,22 @@ impl RiskManager {
             daily_loss: AtomicU64::new(0),
             consecutive_losses: AtomicU32::new(0),
             circuit_breaker_triggered: std::sync::atomic::AtomicBool::new(false),
+
+            exposure: ExposureLimits::default(),
+            in_flight: None,
+            inventory: None,
+            pool_trades: PoolRateLimiter::new(0),
         }
     }
+
+    /// Enables the exposure checks; mint notional is read from `in_flight`, balances from `inventory`
+    pub fn with_exposure_limits(mut self, limits: ExposureLimits, in_flight: Arc<InFlightBook>, inventory: Arc<Inventory>) -> Self {
+        self.exposure = limits;
+        self.in_flight = Some(in_flight);
+        self.inventory = Some(inventory);
+        self.pool_trades = PoolRateLimiter::new(limits.max_pool_trades_per_min);
+        self
+    }
     
     pub fn can_trade(&self, amount: u64) -> Result<(), RiskError> {
         // Check circuit breaker
@@ -95,6 +132,40 @@ impl RiskManager {
     }
 }
 
+impl ExposurePort for RiskManager {
+    fn check_exposure(&self, opp: &ArbitrageOpportunity, now_ms: u64) -> Option<ExposureLimit> {
+        let limits = &self.exposure;
+
+        // The book holds SOL lamports; a cycle from another mint is valued at the rate its profit was
+        if let (Some(book), max) = (&self.in_flight, limits.max_mint_notional_lamports) {
+            let notional = opp.start_units_in_sol(opp.input_amount as i64).max(0) as u64;
+            if max > 0 && exposed_mints(opp).iter().any(|mint| book.mint_notional(mint) + notional > max) {
+                return Some(ExposureLimit::MintNotional);
+            }
+        }
+
+        if self.pool_trades.saturated_pool(opp, now_ms).is_some() {
+            return Some(ExposureLimit::PoolTrades);
+        }
+
+        if let (Some(inventory), Some(step)) = (&self.inventory, opp.steps.first()) {
+            let balance = inventory.available(&step.input_mint);
+            let own = opp.input_amount.saturating_sub(opp.flash_loan_amount);
+            // An unknown (zero) balance is left to the funding check
+            if limits.max_wallet_share_bps > 0 && balance > 0
+                && own as u128 * 10_000 > balance as u128 * limits.max_wallet_share_bps as u128 {
+                return Some(ExposureLimit::WalletShare);
+            }
+        }
+
+        None
+    }
+
+    fn record_dispatch(&self, opp: &ArbitrageOpportunity, now_ms: u64) {
+        self.pool_trades.record(opp, now_ms);
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum RiskError {
     #[error("Circuit breaker tripped")]
@@ -108,3 +179,65 @@ pub enum RiskError {
     #[error("Position size too large")]
     PositionSizeTooLarge,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::SwapStep;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn route(pool: Pubkey, sol: Pubkey, token: Pubkey, input_amount: u64) -> ArbitrageOpportunity {
+        let step = |input_mint, output_mint| SwapStep {
+            pool,
+            program_id: Pubkey::default(),
+            input_mint,
+            output_mint,
+            expected_output: 0,
+        };
+        ArbitrageOpportunity {
+            steps: vec![step(sol, token), step(token, sol)].into(),
+            input_amount,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_exposure_limits_by_type() {
+        let (sol, token) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let in_flight = Arc::new(InFlightBook::default());
+        let inventory = Arc::new(Inventory::new());
+        inventory.set(sol, 100_000_000);
+        let limits = ExposureLimits { max_mint_notional_lamports: 30_000_000, max_pool_trades_per_min: 2, max_wallet_share_bps: 2_500 };
+        let risk = RiskManager::new().with_exposure_limits(limits, Arc::clone(&in_flight), inventory);
+
+        let now = 1_000_000;
+        let trade = route(Pubkey::new_unique(), sol, token, 20_000_000);
+        assert_eq!(risk.check_exposure(&trade, now), None);
+
+        // A second 0.02 SOL bundle through the same token would hold 0.04 SOL of it
+        in_flight.open(&trade);
+        let other_pool = route(Pubkey::new_unique(), sol, token, 20_000_000);
+        assert_eq!(risk.check_exposure(&other_pool, now), Some(ExposureLimit::MintNotional));
+
+        // 2B raw units of a mint worth 0.001 lamports each is 0.002 SOL, not 2 SOL
+        let mut priced = route(Pubkey::new_unique(), Pubkey::new_unique(), token, 2_000_000_000);
+        priced.expected_profit_lamports = 1_000;
+        priced.profit_sol_lamports = Some(1);
+        assert_eq!(risk.check_exposure(&priced, now), None);
+        priced.profit_sol_lamports = Some(10);
+        assert_eq!(risk.check_exposure(&priced, now), Some(ExposureLimit::MintNotional));
+
+        // 30% of the wallet is too much, unless most of it is borrowed
+        let mut large = route(Pubkey::new_unique(), sol, Pubkey::new_unique(), 30_000_000);
+        assert_eq!(risk.check_exposure(&large, now), Some(ExposureLimit::WalletShare));
+        large.flash_loan_amount = 10_000_000;
+        assert_eq!(risk.check_exposure(&large, now), None);
+
+        // The pool window runs on the caller's clock, not the wall clock
+        let busy = route(Pubkey::new_unique(), sol, Pubkey::new_unique(), 1_000_000);
+        risk.record_dispatch(&busy, now);
+        risk.record_dispatch(&busy, now);
+        assert_eq!(risk.check_exposure(&busy, now), Some(ExposureLimit::PoolTrades));
+        assert_eq!(risk.check_exposure(&busy, now + 60_000), None);
+    }
+}
@@ -0,0 +1,292 @@
+/// Strategy Schedule
+///
+/// Turns strategies on and off by UTC weekday and hour, and scales their
+/// trade size inside a window, so a strategy that only pays in some hours of
+/// the hour-of-day PnL (e.g. back-runs during 13:00-21:00 UTC) isn't run in
+/// the others. `STRATEGY_SCHEDULE` lists windows as
+/// `strategy:days:hours[:scale]`, e.g. `backrun:*:13-21;arb:sat-sun:0-24:0.5`.
+/// A strategy with no windows is always on at full size; one with windows is
+/// on only inside them. Telegram `/schedule` shows the current state and can
+/// force a strategy on or off until `/schedule <strategy> auto`.
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::alerts::{AlertManager, AlertSeverity};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const DAY_NAMES: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+const MAX_SCALE: f64 = 4.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    /// Update-driven cycle search in the workers
+    Arb,
+    /// Priority searches after large observed swaps
+    Backrun,
+    /// Periodic full cycle search from SOL
+    Sweep,
+    /// Per-slot re-pricing of cached cycles
+    Reprice,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 4] = [Strategy::Arb, Strategy::Backrun, Strategy::Sweep, Strategy::Reprice];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::Arb => "arb",
+            Strategy::Backrun => "backrun",
+            Strategy::Sweep => "sweep",
+            Strategy::Reprice => "reprice",
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self, String> {
+        Self::ALL.into_iter()
+            .find(|strategy| strategy.name() == s.trim().to_ascii_lowercase())
+            .ok_or_else(|| format!("unknown strategy '{}' (arb, backrun, sweep, reprice)", s.trim()))
+    }
+}
+
+/// Hours `[start, end)` UTC on the days in `days` (bit 0 = Monday). `end < start` wraps past midnight.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Window {
+    strategy: Strategy,
+    days: u8,
+    start_hour: u8,
+    end_hour: u8,
+    scale: f64,
+}
+
+impl Window {
+    fn covers(&self, weekday: usize, hour: u8) -> bool {
+        let in_hours = if self.start_hour < self.end_hour {
+            (self.start_hour..self.end_hour).contains(&hour)
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        };
+        in_hours && self.days & (1 << weekday) != 0
+    }
+}
+
+fn parse_day(s: &str) -> Result<usize, String> {
+    DAY_NAMES.iter().position(|d| *d == s.trim().to_ascii_lowercase())
+        .ok_or_else(|| format!("unknown day '{}' (mon..sun)", s.trim()))
+}
+
+/// `*`, `mon-fri`, `sat,sun` or a single day, as a weekday bitmask
+fn parse_days(s: &str) -> Result<u8, String> {
+    if s.trim() == "*" {
+        return Ok(0x7f);
+    }
+    let mut days = 0u8;
+    for part in s.split(',') {
+        match part.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (parse_day(from)?, parse_day(to)?);
+                let mut day = from;
+                loop {
+                    days |= 1 << day;
+                    if day == to {
+                        break;
+                    }
+                    day = (day + 1) % 7;
+                }
+            }
+            None => days |= 1 << parse_day(part)?,
+        }
+    }
+    Ok(days)
+}
+
+fn parse_hours(s: &str) -> Result<(u8, u8), String> {
+    let (start, end) = s.split_once('-').ok_or_else(|| format!("hours '{}' must be start-end, e.g. 13-21", s))?;
+    let hour = |h: &str| h.trim().parse::<u8>().map_err(|_| format!("bad hour '{}'", h.trim()));
+    let (start, end) = (hour(start)?, hour(end)?);
+    if start > 23 || end > 24 || start == end {
+        return Err(format!("hours '{}' must be start 0-23, end 1-24, not equal", s));
+    }
+    Ok((start, end))
+}
+
+/// Whether a strategy may run right now, and at what share of the trade size
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Activation {
+    pub active: bool,
+    pub scale: f64,
+    /// Set by a Telegram override rather than the schedule
+    pub forced: bool,
+}
+
+impl Activation {
+    /// `trade_size` scaled by the window, never above `max`
+    pub fn scaled(&self, trade_size: u64, max: u64) -> u64 {
+        ((trade_size as f64 * self.scale) as u64).clamp(1, max.max(1))
+    }
+}
+
+pub struct StrategySchedule {
+    windows: Vec<Window>,
+    overrides: Mutex<HashMap<Strategy, bool>>,
+}
+
+impl StrategySchedule {
+    /// Parses `strategy:days:hours[:scale]` windows separated by `;`. Empty = everything always on.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut windows = Vec::new();
+        for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let parts: Vec<&str> = entry.split(':').collect();
+            if !(3..=4).contains(&parts.len()) {
+                return Err(format!("schedule entry '{}' must be strategy:days:hours[:scale]", entry));
+            }
+            let (start_hour, end_hour) = parse_hours(parts[2])?;
+            let scale = match parts.get(3) {
+                Some(s) => s.trim().parse::<f64>().map_err(|_| format!("bad scale '{}'", s.trim()))?,
+                None => 1.0,
+            };
+            if !(scale > 0.0 && scale <= MAX_SCALE) {
+                return Err(format!("scale in '{}' must be in (0, {}]", entry, MAX_SCALE));
+            }
+            windows.push(Window {
+                strategy: Strategy::parse(parts[0])?,
+                days: parse_days(parts[1])?,
+                start_hour,
+                end_hour,
+                scale,
+            });
+        }
+        Ok(Self { windows, overrides: Mutex::new(HashMap::new()) })
+    }
+
+    /// State at `unix_secs`; the first window covering the hour decides the scale
+    pub fn activation_at(&self, strategy: Strategy, unix_secs: u64) -> Activation {
+        let weekday = ((unix_secs / 86_400 + 3) % 7) as usize; // 1970-01-01 was a Thursday
+        let hour = ((unix_secs % 86_400) / 3_600) as u8;
+        let scheduled = {
+            let mut own = self.windows.iter().filter(|w| w.strategy == strategy).peekable();
+            if own.peek().is_none() {
+                Activation { active: true, scale: 1.0, forced: false }
+            } else {
+                match own.find(|w| w.covers(weekday, hour)) {
+                    Some(window) => Activation { active: true, scale: window.scale, forced: false },
+                    None => Activation { active: false, scale: 1.0, forced: false },
+                }
+            }
+        };
+        let forced = self.overrides.lock().unwrap_or_else(|e| e.into_inner()).get(&strategy).copied();
+        match forced {
+            Some(active) => Activation { active, scale: if active { scheduled.scale } else { 1.0 }, forced: true },
+            None => scheduled,
+        }
+    }
+
+    pub fn current(&self, strategy: Strategy) -> Activation {
+        self.activation_at(strategy, mev_core::clock::system().unix_secs())
+    }
+
+    /// Forces a strategy on or off; None hands it back to the schedule
+    pub fn set_override(&self, strategy: Strategy, active: Option<bool>) {
+        let mut overrides = self.overrides.lock().unwrap_or_else(|e| e.into_inner());
+        match active {
+            Some(active) => overrides.insert(strategy, active),
+            None => overrides.remove(&strategy),
+        };
+    }
+
+    /// One line per strategy, for Telegram
+    pub fn describe(&self) -> String {
+        Strategy::ALL.iter()
+            .map(|&strategy| {
+                let now = self.current(strategy);
+                let windows: Vec<String> = self.windows.iter()
+                    .filter(|w| w.strategy == strategy)
+                    .map(|w| format!("{:02}-{:02}h x{}", w.start_hour, w.end_hour, w.scale))
+                    .collect();
+                format!(
+                    "{} {}: {}{}{}",
+                    if now.active { "🟢" } else { "⚪" },
+                    strategy.name(),
+                    if now.active { format!("on x{}", now.scale) } else { "off".to_string() },
+                    if now.forced { " (override)" } else { "" },
+                    if windows.is_empty() { " | always".to_string() } else { format!(" | {}", windows.join(", ")) },
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Handles `/schedule [strategy on|off|auto]`; returns the reply
+    pub fn command(&self, args: &str) -> Result<String, String> {
+        let mut words = args.split_whitespace();
+        let (Some(name), Some(action)) = (words.next(), words.next()) else {
+            return Ok(self.describe());
+        };
+        let strategy = Strategy::parse(name)?;
+        let active = match action.to_ascii_lowercase().as_str() {
+            "on" => Some(true),
+            "off" => Some(false),
+            "auto" => None,
+            other => return Err(format!("unknown action '{}' (on, off, auto)", other)),
+        };
+        self.set_override(strategy, active);
+        crate::telemetry::STRATEGY_ACTIVE.with_label_values(&[strategy.name()]).set(self.current(strategy).active as i64);
+        Ok(self.describe())
+    }
+}
+
+/// Publishes each strategy's state and reports when the schedule turns one on or off
+pub async fn run(schedule: Arc<StrategySchedule>, alerts: Arc<AlertManager>) {
+    let mut last: HashMap<Strategy, bool> = HashMap::new();
+    let mut ticker = tokio::time::interval(CHECK_INTERVAL);
+    loop {
+        ticker.tick().await;
+        for strategy in Strategy::ALL {
+            let now = schedule.current(strategy);
+            crate::telemetry::STRATEGY_ACTIVE.with_label_values(&[strategy.name()]).set(now.active as i64);
+            let previous = last.insert(strategy, now.active);
+            if previous.is_some_and(|was| was != now.active) {
+                let message = format!("{} is now {} (x{} size)", strategy.name(), if now.active { "ON" } else { "OFF" }, now.scale);
+                tracing::info!("🕐 Schedule: {}", message);
+                alerts.send_alert(AlertSeverity::Info, "STRATEGY SCHEDULE", &message, vec![]).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_by_day_and_hour() {
+        let schedule = StrategySchedule::parse("backrun:*:13-21; arb:sat-sun:22-6:0.5; arb:mon-fri:0-24").unwrap();
+        // 1970-01-05 was a Monday
+        let at = |day: u64, hour: u64| (4 + day) * 86_400 + hour * 3_600;
+
+        assert!(!schedule.activation_at(Strategy::Backrun, at(0, 12)).active);
+        assert!(schedule.activation_at(Strategy::Backrun, at(0, 13)).active);
+        assert!(!schedule.activation_at(Strategy::Backrun, at(6, 21)).active);
+
+        // Weekend nights only, at half size, wrapping midnight
+        let saturday_night = schedule.activation_at(Strategy::Arb, at(5, 23));
+        assert_eq!((saturday_night.active, saturday_night.scale), (true, 0.5));
+        assert!(schedule.activation_at(Strategy::Arb, at(6, 3)).active);
+        assert!(!schedule.activation_at(Strategy::Arb, at(6, 12)).active);
+        assert_eq!(schedule.activation_at(Strategy::Arb, at(2, 12)).scale, 1.0);
+
+        // No windows: always on
+        assert!(schedule.activation_at(Strategy::Sweep, at(3, 3)).active);
+
+        // Overrides win until handed back
+        schedule.set_override(Strategy::Backrun, Some(true));
+        assert!(schedule.activation_at(Strategy::Backrun, at(0, 2)).forced);
+        schedule.set_override(Strategy::Backrun, None);
+        assert!(!schedule.activation_at(Strategy::Backrun, at(0, 2)).active);
+
+        assert_eq!(saturday_night.scaled(10_000_000, 1_000_000), 1_000_000);
+        assert_eq!(parse_days("fri-mon").unwrap(), 0b111_0001);
+        assert!(StrategySchedule::parse("snipe:*:13-21").is_err());
+        assert!(StrategySchedule::parse("arb:*:13-13").is_err());
+        assert!(StrategySchedule::parse("arb:*:0-24:5").is_err());
+    }
+}
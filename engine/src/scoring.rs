@@ -1,11 +1,13 @@
 use dashmap::DashMap;
 use solana_sdk::pubkey::Pubkey;
 use mev_core::pool_weight::{PoolWeight, weight_constants::*};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use mev_core::clock::Clock;
 
 pub struct PoolScoringEngine {
     weights: DashMap<Pubkey, PoolWeight>,
     pool: Option<deadpool_postgres::Pool>,
+    clock: Arc<dyn Clock>,
 }
 
 use std::str::FromStr;
@@ -15,9 +17,16 @@ impl PoolScoringEngine {
         Self {
             weights: DashMap::new(),
             pool,
+            clock: mev_core::clock::system(),
         }
     }
 
+    /// Time source for update stamps and decay (a manual clock in tests)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     pub async fn init_db(&self) -> anyhow::Result<()> {
         if let Some(pool) = &self.pool {
             let client = pool.get().await?;
@@ -86,7 +95,7 @@ impl PoolScoringEngine {
     }
 
     pub fn update_activity(&self, pool_address: Pubkey) {
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let now = self.clock.unix_secs();
         
         let mut entry = self.weights.entry(pool_address).or_insert_with(|| PoolWeight::new(pool_address));
         
@@ -97,7 +106,7 @@ impl PoolScoringEngine {
     }
 
     pub fn update_dna_score(&self, pool_address: Pubkey, dna_score: u64) {
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let now = self.clock.unix_secs();
         let mut entry = self.weights.entry(pool_address).or_insert_with(|| PoolWeight::new(pool_address));
         entry.dna_score = dna_score;
         entry.last_update_ts = now;
@@ -107,8 +116,16 @@ impl PoolScoringEngine {
         entry.weight = (entry.weight + dna_bonus).min(MAX_WEIGHT);
     }
 
+    /// One-sided flow moves the price and opens arbs; balanced churn doesn't
+    pub fn update_trade_flow(&self, pool_address: Pubkey, pressure: f64) {
+        let now = self.clock.unix_secs();
+        let mut entry = self.weights.entry(pool_address).or_insert_with(|| PoolWeight::new(pool_address));
+        entry.weight = (entry.weight + pressure.abs() * FLOW_BONUS_MULTIPLIER).min(MAX_WEIGHT);
+        entry.last_update_ts = now;
+    }
+
     pub fn decay_weights(&self) {
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let now = self.clock.unix_secs();
         
         self.weights.retain(|_pk, weight| {
             let elapsed = now.saturating_sub(weight.last_update_ts);
@@ -123,6 +140,16 @@ impl PoolScoringEngine {
         });
     }
 
+    /// Forgets a delisted pool so it is neither ranked nor reloaded on the next start
+    pub async fn remove(&self, pool_address: &Pubkey) -> anyhow::Result<()> {
+        self.weights.remove(pool_address);
+        if let Some(pool) = &self.pool {
+            let client = pool.get().await?;
+            client.execute("DELETE FROM pool_weights WHERE pool_address = $1", &[&pool_address.to_string()]).await?;
+        }
+        Ok(())
+    }
+
     pub fn get_weight(&self, pool_address: &Pubkey) -> f64 {
         self.weights.get(pool_address).map(|w| w.weight).unwrap_or(BASE_WEIGHT)
     }
@@ -133,3 +160,30 @@ impl PoolScoringEngine {
         all_weights.into_iter().take(limit).collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::clock::ManualClock;
+
+    #[test]
+    fn test_weights_decay_on_the_injected_clock() {
+        let clock = Arc::new(ManualClock::new(1_000_000));
+        let scoring = PoolScoringEngine::new(None).with_clock(Arc::clone(&clock) as Arc<dyn Clock>);
+        let (quiet, busy) = (Pubkey::new_unique(), Pubkey::new_unique());
+        scoring.update_activity(quiet);
+        scoring.update_activity(busy);
+        let fresh = scoring.get_weight(&quiet);
+        assert_eq!(scoring.get_top_pools(2)[0].last_update_ts, 1_000);
+
+        // Nothing decays until the clock moves
+        scoring.decay_weights();
+        assert_eq!(scoring.get_weight(&quiet), fresh);
+
+        clock.advance(std::time::Duration::from_secs(60));
+        scoring.update_activity(busy);
+        scoring.decay_weights();
+        assert!(scoring.get_weight(&quiet) < fresh);
+        assert!(scoring.get_weight(&busy) > scoring.get_weight(&quiet));
+    }
+}
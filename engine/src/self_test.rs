@@ -0,0 +1,311 @@
+/// Startup Self-Test
+///
+/// `engine --self-test` runs the pipeline once without trading: config
+/// validation and the keypair, every configured RPC, WebSocket and Jito
+/// endpoint, key resolution for one monitored pool per venue, a bundle built
+/// over one of those pools and simulated, and a test message through each
+/// alert sink. It prints a pass/fail report and exits non-zero when a check
+/// fails, so it can gate the switch from paper to live mode. Endpoint URLs are
+/// left out of the report; some carry API keys.
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use mev_core::constants::*;
+use mev_core::{ArbitrageOpportunity, SwapStep};
+use executor::http::HttpClient;
+use strategy::ports::PoolKeyProvider;
+use crate::alerts::{AlertManager, TelegramConfig};
+use crate::config::{BotConfig, PoolConfig, MONITORED_POOLS};
+use crate::pool_fetcher::PoolKeyFetcher;
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Input of the test bundle: 0.001 SOL
+const TEST_BUNDLE_LAMPORTS: u64 = 1_000_000;
+
+/// Venues with a key resolver, checked in this order
+const VENUES: &[(&str, Pubkey)] = &[
+    ("Raydium V4", RAYDIUM_V4_PROGRAM),
+    ("Orca Whirlpool", ORCA_WHIRLPOOL_PROGRAM),
+    ("Raydium CLMM", RAYDIUM_CLMM_PROGRAM),
+    ("Meteora DLMM", METEORA_PROGRAM_ID),
+    ("Phoenix", PHOENIX_PROGRAM),
+    ("OpenBook v2", OPENBOOK_V2_PROGRAM),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Worth a look, but not a reason to stay out of live mode
+    Warn,
+    Fail,
+    /// Nothing configured to check
+    Skip,
+}
+
+impl CheckStatus {
+    fn icon(&self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "✅",
+            CheckStatus::Warn => "⚠️",
+            CheckStatus::Fail => "❌",
+            CheckStatus::Skip => "⏭️",
+        }
+    }
+}
+
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+#[derive(Default)]
+pub struct SelfTestReport {
+    pub checks: Vec<Check>,
+}
+
+impl SelfTestReport {
+    fn record(&mut self, name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) {
+        self.checks.push(Check { name: name.into(), status, detail: detail.into() });
+    }
+
+    /// Pass with the detail on Ok, Fail with the error otherwise
+    fn outcome(&mut self, name: impl Into<String>, result: anyhow::Result<String>) {
+        match result {
+            Ok(detail) => self.record(name, CheckStatus::Pass, detail),
+            Err(e) => self.record(name, CheckStatus::Fail, e.to_string()),
+        }
+    }
+
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    pub fn passed(&self) -> bool {
+        self.count(CheckStatus::Fail) == 0
+    }
+}
+
+impl fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "🩺 SELF-TEST REPORT")?;
+        for check in &self.checks {
+            writeln!(f, "   {} {:<24} {}", check.status.icon(), check.name, check.detail)?;
+        }
+        writeln!(f, "   {} passed, {} warnings, {} failed, {} skipped",
+            self.count(CheckStatus::Pass), self.count(CheckStatus::Warn), self.count(CheckStatus::Fail), self.count(CheckStatus::Skip))?;
+        if self.passed() {
+            write!(f, "   ✅ READY for live mode")
+        } else {
+            write!(f, "   ❌ NOT READY: fix the failed checks before going live")
+        }
+    }
+}
+
+pub async fn run(cfg: &BotConfig) -> SelfTestReport {
+    let mut report = SelfTestReport::default();
+
+    report.outcome("config", cfg.validate().map(|_| format!("{:?} mode", cfg.mode)).map_err(anyhow::Error::msg));
+    let payer = match read_keypair_file(cfg.resolved_keypair_path()) {
+        Ok(payer) => {
+            report.record("keypair", CheckStatus::Pass, payer.pubkey().to_string());
+            Some(payer)
+        }
+        Err(e) => {
+            report.record("keypair", CheckStatus::Fail, format!("{}: {}", cfg.resolved_keypair_path(), e));
+            None
+        }
+    };
+
+    for (i, url) in cfg.rpc_urls().iter().enumerate() {
+        report.outcome(format!("rpc #{}", i + 1), check_rpc(url).await);
+    }
+    for (i, url) in cfg.ws_urls().iter().enumerate() {
+        report.outcome(format!("ws #{}", i + 1), check_ws(url).await);
+    }
+    let jito_urls: Vec<&str> = cfg.jito_url.split(',').map(str::trim).filter(|u| !u.is_empty()).collect();
+    if jito_urls.is_empty() {
+        report.record("jito", CheckStatus::Skip, "JITO_URL empty: trades go through the legacy RPC executor");
+    }
+    for (i, url) in jito_urls.iter().enumerate() {
+        let result = executor::channel_warmer::ping_block_engine(url).await
+            .map(|accounts| format!("{} tip accounts advertised", accounts));
+        report.outcome(format!("jito #{}", i + 1), result);
+    }
+
+    let resolved = check_venue_keys(cfg, &mut report).await;
+    match (&payer, resolved.first()) {
+        (Some(payer), Some((pool, program))) => check_bundle(cfg, payer, pool, *program, &mut report).await,
+        (None, _) => report.record("bundle", CheckStatus::Skip, "no keypair"),
+        (_, None) => report.record("bundle", CheckStatus::Skip, "no pool resolved cleanly"),
+    }
+
+    check_alerts(cfg, &mut report).await;
+    report
+}
+
+async fn check_rpc(url: &str) -> anyhow::Result<String> {
+    let rpc = RpcClient::new_with_timeout(url.to_string(), CHECK_TIMEOUT);
+    let started = Instant::now();
+    let slot = rpc.get_slot().await?;
+    Ok(format!("slot {} in {}ms", slot, started.elapsed().as_millis()))
+}
+
+/// Subscribes to slots and waits for the first notification
+async fn check_ws(url: &str) -> anyhow::Result<String> {
+    let started = Instant::now();
+    let (mut ws, _) = tokio::time::timeout(CHECK_TIMEOUT, connect_async(url)).await
+        .map_err(|_| anyhow::anyhow!("connect timed out"))??;
+    ws.send(Message::Text(json!({ "jsonrpc": "2.0", "id": 1, "method": "slotSubscribe" }).to_string().into())).await?;
+    let slot = tokio::time::timeout(CHECK_TIMEOUT, async {
+        while let Some(message) = ws.next().await {
+            if let Message::Text(text) = message? {
+                let value: Value = serde_json::from_str(&text)?;
+                if let Some(slot) = value.pointer("/params/result/slot").and_then(Value::as_u64) {
+                    return Ok(slot);
+                }
+            }
+        }
+        Err::<u64, anyhow::Error>(anyhow::anyhow!("stream closed before a slot notification"))
+    }).await.map_err(|_| anyhow::anyhow!("no slot notification within {}s", CHECK_TIMEOUT.as_secs()))??;
+    let _ = ws.close(None).await;
+    Ok(format!("slot {} after {}ms", slot, started.elapsed().as_millis()))
+}
+
+/// Resolves the first monitored pool of each venue; returns the pools whose accounts all checked out
+async fn check_venue_keys(cfg: &BotConfig, report: &mut SelfTestReport) -> Vec<(&'static PoolConfig, Pubkey)> {
+    let rpc = RpcClient::new_with_timeout(cfg.rpc_url.clone(), CHECK_TIMEOUT);
+    let addresses: Vec<Pubkey> = MONITORED_POOLS.iter().map(|p| p.address).collect();
+    let accounts = match rpc.get_multiple_accounts(&addresses).await {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            report.record("keys", CheckStatus::Fail, format!("monitored pools unreadable: {}", e));
+            return Vec::new();
+        }
+    };
+
+    let mut resolved = Vec::new();
+    for (venue, program) in VENUES {
+        let name = format!("keys: {}", venue);
+        let Some((pool, _)) = MONITORED_POOLS.iter().zip(&accounts)
+            .find(|(_, account)| matches!(account, Some(a) if a.owner == *program)) else {
+            report.record(name, CheckStatus::Skip, "no monitored pool on this venue");
+            continue;
+        };
+        match crate::keys_cli::check_pool(&cfg.rpc_url, &pool.address).await {
+            Ok(check) if check.failures() == 0 => {
+                report.record(name, CheckStatus::Pass, format!("{} ({} accounts)", pool.address, check.accounts.len()));
+                resolved.push((pool, *program));
+            }
+            Ok(check) => {
+                let failed: Vec<String> = check.accounts.iter()
+                    .filter_map(|(account, result)| result.as_ref().err().map(|e| format!("{} {}", account.name, e)))
+                    .collect();
+                report.record(name, CheckStatus::Fail, format!("{}: {}", pool.address, failed.join("; ")));
+            }
+            Err(e) => report.record(name, CheckStatus::Fail, format!("{}: {}", pool.address, e)),
+        }
+    }
+    resolved
+}
+
+/// Builds a there-and-back route through `pool` with the executor's builders and simulates it.
+/// The build has to work; the simulation only warns, since an unfunded test wallet can't fill it.
+async fn check_bundle(cfg: &BotConfig, payer: &Keypair, pool: &PoolConfig, program: Pubkey, report: &mut SelfTestReport) {
+    let rpc_pool = Arc::new(cfg.rpc_pool());
+    let signer = match Keypair::from_bytes(&payer.to_bytes()) {
+        Ok(signer) => signer,
+        Err(e) => return report.record("bundle build", CheckStatus::Fail, e.to_string()),
+    };
+    let fetcher = Arc::new(PoolKeyFetcher::new(Arc::clone(&rpc_pool)));
+    let builder = executor::legacy::LegacyExecutor::new(Arc::clone(&rpc_pool), signer, Some(fetcher as Arc<dyn PoolKeyProvider>));
+
+    let step = |input_mint, output_mint| SwapStep {
+        pool: pool.address,
+        program_id: program,
+        input_mint,
+        output_mint,
+        expected_output: TEST_BUNDLE_LAMPORTS,
+    };
+    let opportunity = ArbitrageOpportunity {
+        steps: [step(pool.token_a, pool.token_b), step(pool.token_b, pool.token_a)].into_iter().collect(),
+        input_amount: TEST_BUNDLE_LAMPORTS,
+        ..Default::default()
+    };
+    let ixs = match builder.build_instructions(opportunity, cfg.max_slippage_bps).await {
+        Ok(ixs) => ixs,
+        Err(e) => return report.record("bundle build", CheckStatus::Fail, format!("{}: {}", pool.address, e)),
+    };
+    let blockhash = match rpc_pool.call(|c| async move { c.get_latest_blockhash().await }).await {
+        Ok(blockhash) => blockhash,
+        Err(e) => return report.record("bundle build", CheckStatus::Fail, format!("no blockhash: {}", e)),
+    };
+    let tx = Transaction::new_signed_with_payer(&ixs, Some(&payer.pubkey()), &[payer], blockhash);
+    let size = bincode::serialize(&tx).map(|b| b.len()).unwrap_or(0);
+    report.record("bundle build", CheckStatus::Pass, format!("{} instructions over {}, {} bytes", ixs.len(), pool.address, size));
+
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(CommitmentConfig::confirmed()),
+        ..Default::default()
+    };
+    let tx = &tx;
+    match rpc_pool.call(|c| { let config = config.clone(); async move { c.simulate_transaction_with_config(tx, config).await } }).await {
+        Ok(response) => match response.value.err {
+            None => report.record("bundle simulation", CheckStatus::Pass, format!("{} CU", response.value.units_consumed.unwrap_or(0))),
+            Some(e) => report.record("bundle simulation", CheckStatus::Warn, format!("{} (expected without funded token accounts)", e)),
+        },
+        Err(e) => report.record("bundle simulation", CheckStatus::Fail, e.to_string()),
+    }
+}
+
+async fn check_alerts(cfg: &BotConfig, report: &mut SelfTestReport) {
+    let http = match HttpClient::default().with_overrides(&cfg.http_host_policies) {
+        Ok(http) => Arc::new(http),
+        Err(e) => return report.record("alerts", CheckStatus::Fail, e),
+    };
+    let telegram = match (&cfg.telegram_bot_token, &cfg.telegram_chat_id) {
+        (Some(token), Some(chat_id)) => Some(TelegramConfig { bot_token: token.clone(), chat_id: chat_id.clone() }),
+        _ => None,
+    };
+    let alerts = AlertManager::new(cfg.discord_webhook.clone(), telegram, cfg.ntfy_topic.clone(), http);
+    let results = alerts.test_sinks().await;
+    if results.is_empty() {
+        report.record("alerts", CheckStatus::Warn, "no sink configured: nobody hears about a halt");
+    }
+    for (sink, result) in results {
+        report.outcome(format!("alerts: {}", sink), result.map(|_| "test message delivered".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_fails_only_on_failed_checks() {
+        let mut report = SelfTestReport::default();
+        report.outcome("rpc #1", Ok("slot 1 in 20ms".to_string()));
+        report.record("jito", CheckStatus::Skip, "JITO_URL empty");
+        report.record("bundle simulation", CheckStatus::Warn, "insufficient funds");
+        assert!(report.passed());
+        assert!(report.to_string().contains("READY for live mode"));
+
+        report.outcome("ws #1", Err(anyhow::anyhow!("connect timed out")));
+        assert!(!report.passed());
+        let text = report.to_string();
+        assert!(text.contains("1 passed, 1 warnings, 1 failed, 1 skipped"));
+        assert!(text.contains("❌ ws #1"));
+        assert!(text.contains("NOT READY"));
+    }
+}
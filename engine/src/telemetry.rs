@@ -1,10 +1,13 @@
 pub use mev_core::telemetry::*;
-use axum::{routing::get, Router};
+use axum::{routing::get, Json, Router};
 use std::net::SocketAddr;
+use std::sync::Arc;
+use crate::capital_at_risk::CapitalAtRisk;
+use strategy::analytics::quote_errors::QuoteErrorTracker;
 use prometheus::{TextEncoder, Encoder};
 
-/// Start metrics HTTP server
-pub async fn serve_metrics() {
+/// Start metrics HTTP server (`/metrics`, plus `/risk` and `/quote-errors` when their sources are given)
+pub async fn serve_metrics(capital_at_risk: Option<Arc<CapitalAtRisk>>, quote_errors: Option<Arc<QuoteErrorTracker>>) {
     let port = std::env::var("METRICS_PORT")
         .unwrap_or_else(|_| "8082".to_string())
         .parse::<u16>()
@@ -14,12 +17,28 @@ pub async fn serve_metrics() {
 
     let app = Router::new().route("/metrics", get(move || async {
         let encoder = TextEncoder::new();
-        let metric_families = REGISTRY.gather();
+        let metric_families = gather();
         let mut buffer = Vec::new();
         encoder.encode(&metric_families, &mut buffer).unwrap();
         String::from_utf8(buffer).unwrap()
     }));
 
+    let app = match capital_at_risk {
+        Some(car) => app.route("/risk", get(move || {
+            let car = Arc::clone(&car);
+            async move { Json(car.snapshot().await) }
+        })),
+        None => app,
+    };
+
+    let app = match quote_errors {
+        Some(tracker) => app.route("/quote-errors", get(move || {
+            let tracker = Arc::clone(&tracker);
+            async move { Json(quote_error_report(&tracker)) }
+        })),
+        None => app,
+    };
+
     tokio::spawn(async move {
         let addr = SocketAddr::from(([0, 0, 0, 0], port));
         match tokio::net::TcpListener::bind(addr).await {
@@ -34,3 +53,19 @@ pub async fn serve_metrics() {
         }
     });
 }
+
+/// Per-venue quoting error on landed routes, worst first
+fn quote_error_report(tracker: &QuoteErrorTracker) -> serde_json::Value {
+    let venues: Vec<serde_json::Value> = tracker.snapshot()
+        .into_iter()
+        .map(|(program_id, e)| serde_json::json!({
+            "venue": crate::tui::dex_name(&program_id),
+            "program_id": program_id.to_string(),
+            "samples": e.samples,
+            "mean_error_bps": e.mean_error_bps,
+            "mean_abs_error_bps": e.mean_abs_error_bps,
+            "worst_error_bps": e.worst_error_bps,
+        }))
+        .collect();
+    serde_json::json!({ "venues": venues })
+}
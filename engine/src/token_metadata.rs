@@ -0,0 +1,152 @@
+/// Token Metadata
+///
+/// Symbol and decimals per mint, for every amount a person reads: logs,
+/// alerts, the TUI and the JSON reports. Well-known mints are built in; any
+/// other mint's decimals are read from its mint account on first sight and
+/// cached (the offset is the same for Token and Token-2022). A mint not
+/// resolved yet is shown as a raw amount, never scaled by a guessed decimals.
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use solana_sdk::pubkey::Pubkey;
+use executor::rpc_pool::RpcPool;
+use mev_core::constants::{
+    BODEN_MINT, BONK_MINT, DRIFT_MINT, JTO_MINT, JUP_MINT, PENGU_MINT, POPCAT_MINT, RAY_MINT,
+    SOL_MINT, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, USDC_MINT, USDT_MINT, WIF_MINT,
+};
+
+/// `decimals` in the SPL mint layout
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MintInfo {
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+pub struct TokenMetadata {
+    rpc: Option<Arc<RpcPool>>,
+    mints: RwLock<HashMap<Pubkey, MintInfo>>,
+}
+
+impl Default for TokenMetadata {
+    /// Well-known mints only, nothing fetched
+    fn default() -> Self {
+        let known = [
+            (SOL_MINT, "SOL", 9),
+            (USDC_MINT, "USDC", 6),
+            (USDT_MINT, "USDT", 6),
+            (JUP_MINT, "JUP", 6),
+            (RAY_MINT, "RAY", 6),
+            (BONK_MINT, "BONK", 5),
+            (WIF_MINT, "WIF", 6),
+            (POPCAT_MINT, "POPCAT", 9),
+            (JTO_MINT, "JTO", 9),
+            (PENGU_MINT, "PENGU", 6),
+            (DRIFT_MINT, "DRIFT", 6),
+            (BODEN_MINT, "BODEN", 9),
+        ];
+        let mints = known.into_iter()
+            .map(|(mint, symbol, decimals)| (mint, MintInfo { symbol: symbol.to_string(), decimals }))
+            .collect();
+        Self { rpc: None, mints: RwLock::new(mints) }
+    }
+}
+
+impl TokenMetadata {
+    /// Resolves unknown mints over `rpc`
+    pub fn new(rpc: Arc<RpcPool>) -> Self {
+        Self { rpc: Some(rpc), ..Self::default() }
+    }
+
+    pub fn get(&self, mint: &Pubkey) -> Option<MintInfo> {
+        self.mints.read().unwrap().get(mint).cloned()
+    }
+
+    /// Known symbol, or the mint's first characters
+    pub fn symbol(&self, mint: &Pubkey) -> String {
+        self.get(mint).map_or_else(|| short_mint(mint), |info| info.symbol)
+    }
+
+    /// `raw` in whole tokens, e.g. `1.5 BONK`; `150000 raw 7xKXtg..` until the decimals are known
+    pub fn format(&self, mint: &Pubkey, raw: u64) -> String {
+        match self.get(mint) {
+            Some(info) => format!("{} {}", format_units(raw, info.decimals), info.symbol),
+            None => format!("{} raw {}", raw, short_mint(mint)),
+        }
+    }
+
+    /// `raw` in whole tokens without the symbol, exact; None until the decimals are known
+    pub fn ui_amount(&self, mint: &Pubkey, raw: u64) -> Option<String> {
+        self.get(mint).map(|info| format_units(raw, info.decimals))
+    }
+
+    /// Reads decimals for the mints not cached yet. Returns how many were added.
+    pub async fn resolve(&self, mints: &[Pubkey]) -> anyhow::Result<usize> {
+        let Some(rpc) = &self.rpc else {
+            return Ok(0);
+        };
+        let unknown: Vec<Pubkey> = {
+            let cached = self.mints.read().unwrap();
+            let mut unknown: Vec<Pubkey> = mints.iter().filter(|m| !cached.contains_key(m)).copied().collect();
+            unknown.sort();
+            unknown.dedup();
+            unknown
+        };
+        let mut added = 0;
+        for chunk in unknown.chunks(100) {
+            let accounts = rpc.call(|c| async move { c.get_multiple_accounts(chunk).await }).await?;
+            let mut cached = self.mints.write().unwrap();
+            for (mint, account) in chunk.iter().zip(accounts) {
+                let Some(account) = account.filter(|a| a.owner == TOKEN_PROGRAM_ID || a.owner == TOKEN_2022_PROGRAM_ID) else {
+                    continue;
+                };
+                if let Some(&decimals) = account.data.get(MINT_DECIMALS_OFFSET) {
+                    cached.insert(*mint, MintInfo { symbol: short_mint(mint), decimals });
+                    added += 1;
+                }
+            }
+        }
+        Ok(added)
+    }
+}
+
+/// `raw` scaled down by `decimals`, exact, trailing zeros dropped: (1_500_000, 6) → `1.5`
+pub fn format_units(raw: u64, decimals: u8) -> String {
+    let Some(scale) = 10u128.checked_pow(decimals as u32) else {
+        return raw.to_string();
+    };
+    let (whole, frac) = (raw as u128 / scale, raw as u128 % scale);
+    if frac == 0 {
+        return whole.to_string();
+    }
+    let frac = format!("{:0width$}", frac, width = decimals as usize);
+    format!("{}.{}", whole, frac.trim_end_matches('0'))
+}
+
+fn short_mint(mint: &Pubkey) -> String {
+    let s = mint.to_string();
+    format!("{}..", &s[..6.min(s.len())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amounts_use_each_mints_decimals() {
+        assert_eq!(format_units(1_500_000, 6), "1.5");
+        assert_eq!(format_units(42, 0), "42");
+        assert_eq!(format_units(5, 9), "0.000000005");
+        assert_eq!(format_units(u64::MAX, 19), "1.8446744073709551615");
+
+        let metadata = TokenMetadata::default();
+        // The same raw amount is 10 BONK (5 decimals) but 1 USDC (6) and 0.001 SOL (9)
+        assert_eq!(metadata.format(&BONK_MINT, 1_000_000), "10 BONK");
+        assert_eq!(metadata.format(&USDC_MINT, 1_000_000), "1 USDC");
+        assert_eq!(metadata.format(&SOL_MINT, 1_000_000), "0.001 SOL");
+
+        let unknown = Pubkey::new_unique();
+        assert_eq!(metadata.ui_amount(&unknown, 7), None);
+        assert_eq!(metadata.format(&unknown, 7), format!("7 raw {}", short_mint(&unknown)));
+    }
+}
@@ -0,0 +1,377 @@
+/// Trade Journal (Postgres)
+///
+/// `PerformanceTracker` appends one log line per dispatched route. The journal
+/// keeps the whole lifecycle in a `trades` table instead. A row is written for
+/// every route that reaches the gates, with its opportunity snapshot and either
+/// the gate that refused it or the tip and bundle it was sent with. The row is
+/// updated when the transaction settles: landed or failed, the compute-unit
+/// price it paid, and the realized PnL `PnlReconciler` read from the payer's
+/// balance change. Writes
+/// go through a bounded channel to one background task, so the hot path never
+/// waits on the database. Rejections are capped per second.
+///
+/// Each row is tagged with the strategy and trigger that found the route
+/// (account update, discovery, back-run, slot re-price, sweep) and the AI and
+/// DNA scores it passed the gates with. `attribution` groups settled trades by
+/// strategy and trigger, so the signal sources that actually earn can be told
+/// apart from the ones that only spend tips.
+use std::sync::{Arc, Mutex};
+use deadpool_postgres::Pool;
+use tokio::sync::mpsc;
+use mev_core::ArbitrageOpportunity;
+use strategy::analytics::rejections::route_signature;
+use strategy::ports::TradeJournalPort;
+
+const WRITE_QUEUE_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeStatus {
+    Rejected,
+    Dispatched,
+    SendFailed,
+    Landed,
+    Failed,
+}
+
+impl TradeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeStatus::Rejected => "rejected",
+            TradeStatus::Dispatched => "dispatched",
+            TradeStatus::SendFailed => "send_failed",
+            TradeStatus::Landed => "landed",
+            TradeStatus::Failed => "failed",
+        }
+    }
+}
+
+enum JournalWrite {
+    Attempt {
+        status: TradeStatus,
+        opportunity: ArbitrageOpportunity,
+        rejection_reason: Option<&'static str>,
+        tip_lamports: Option<u64>,
+        bundle_id: Option<String>,
+        error: Option<String>,
+    },
+    Settlement {
+        bundle_id: String,
+        status: TradeStatus,
+        realized_pnl: Option<i64>,
+        cu_price_micro_lamports: Option<u64>,
+    },
+    /// A raced trade settled under its other copy's signature
+    Alias {
+        bundle_id: String,
+        settled: String,
+    },
+}
+
+/// Counts by status over a time window, for /status and the analyze report
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct JournalSummary {
+    pub rejected: u64,
+    pub dispatched: u64,
+    pub send_failed: u64,
+    pub landed: u64,
+    pub failed: u64,
+    /// Sum over settled trades that have a realized PnL
+    pub realized_pnl_lamports: i64,
+    /// Most frequent rejection reasons, most frequent first
+    pub top_rejections: Vec<(String, u64)>,
+}
+
+impl JournalSummary {
+    /// Landed share of settled trades
+    pub fn land_rate(&self) -> Option<f64> {
+        let settled = self.landed + self.failed;
+        (settled > 0).then(|| self.landed as f64 / settled as f64)
+    }
+}
+
+/// Settled trades from one strategy and trigger over a time window
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct TriggerAttribution {
+    pub strategy: String,
+    pub trigger: String,
+    /// Routes handed to the executor, settled or not
+    pub dispatched: u64,
+    pub landed: u64,
+    pub failed: u64,
+    pub realized_pnl_lamports: i64,
+    pub tips_lamports: i64,
+    /// Mean over the trades that were scored
+    pub avg_ai_confidence: Option<f64>,
+    pub avg_dna_score: Option<f64>,
+}
+
+impl TriggerAttribution {
+    /// Landed share of settled trades
+    pub fn land_rate(&self) -> Option<f64> {
+        let settled = self.landed + self.failed;
+        (settled > 0).then(|| self.landed as f64 / settled as f64)
+    }
+
+    /// Realized PnL per settled trade
+    pub fn pnl_per_trade(&self) -> Option<i64> {
+        let settled = (self.landed + self.failed) as i64;
+        (settled > 0).then(|| self.realized_pnl_lamports / settled)
+    }
+}
+
+pub struct TradeJournal {
+    pool: Pool,
+    sender: mpsc::Sender<JournalWrite>,
+    max_rejections_per_sec: u32,
+    /// (unix second, rejections journaled in it)
+    rejection_window: Mutex<(i64, u32)>,
+}
+
+impl TradeJournal {
+    /// Creates the `trades` table if needed and starts the writer task
+    pub async fn new(pool: Pool, max_rejections_per_sec: u32) -> anyhow::Result<Arc<Self>> {
+        pool.get().await?.batch_execute("
+            CREATE TABLE IF NOT EXISTS trades (
+                id BIGSERIAL PRIMARY KEY,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                status TEXT NOT NULL,
+                rejection_reason TEXT,
+                route TEXT NOT NULL,
+                hops SMALLINT NOT NULL,
+                input_amount BIGINT NOT NULL,
+                expected_profit BIGINT NOT NULL,
+                opportunity JSONB NOT NULL,
+                tip_lamports BIGINT,
+                bundle_id TEXT,
+                error TEXT,
+                cu_price_micro_lamports BIGINT,
+                realized_pnl BIGINT,
+                settled_at TIMESTAMPTZ
+            );
+            ALTER TABLE trades ADD COLUMN IF NOT EXISTS strategy TEXT;
+            ALTER TABLE trades ADD COLUMN IF NOT EXISTS trigger TEXT;
+            ALTER TABLE trades ADD COLUMN IF NOT EXISTS ai_confidence REAL;
+            ALTER TABLE trades ADD COLUMN IF NOT EXISTS dna_score BIGINT;
+            CREATE INDEX IF NOT EXISTS idx_trades_created ON trades (created_at DESC);
+            CREATE INDEX IF NOT EXISTS idx_trades_bundle ON trades (bundle_id) WHERE bundle_id IS NOT NULL;
+            CREATE INDEX IF NOT EXISTS idx_trades_trigger ON trades (trigger, created_at DESC);
+        ").await?;
+        tracing::info!("🗄️ Trades table verified/created.");
+
+        let (sender, rx) = mpsc::channel(WRITE_QUEUE_CAPACITY);
+        tokio::spawn(write_loop(pool.clone(), rx));
+        Ok(Arc::new(Self {
+            pool,
+            sender,
+            max_rejections_per_sec,
+            rejection_window: Mutex::new((0, 0)),
+        }))
+    }
+
+    fn enqueue(&self, write: JournalWrite) {
+        if self.sender.try_send(write).is_err() {
+            tracing::debug!("🗄️ Trade journal queue full; dropping a write");
+        }
+    }
+
+    fn admit_rejection(&self) -> bool {
+        if self.max_rejections_per_sec == 0 {
+            return false;
+        }
+        let now = chrono::Utc::now().timestamp();
+        let mut window = self.rejection_window.lock().unwrap_or_else(|e| e.into_inner());
+        if window.0 != now {
+            *window = (now, 0);
+        }
+        window.1 += 1;
+        window.1 <= self.max_rejections_per_sec
+    }
+
+    /// Settles a dispatched trade. Realized PnL and CU price are None when the transaction couldn't be read.
+    pub fn record_settlement(&self, signature: String, landed: bool, realized_pnl: Option<i64>, cu_price_micro_lamports: Option<u64>) {
+        let status = if landed { TradeStatus::Landed } else { TradeStatus::Failed };
+        self.enqueue(JournalWrite::Settlement { bundle_id: signature, status, realized_pnl, cu_price_micro_lamports });
+    }
+
+    /// Re-keys a dispatched trade whose raced RPC copy settled instead of its bundle.
+    /// Queued ahead of that settlement, so the settlement finds the row.
+    pub fn record_alias(&self, bundle_id: &str, settled: &str) {
+        self.enqueue(JournalWrite::Alias { bundle_id: bundle_id.to_string(), settled: settled.to_string() });
+    }
+
+    /// Status counts, realized PnL and top rejection reasons over the last `window_secs`
+    pub async fn summary(&self, window_secs: i64) -> anyhow::Result<JournalSummary> {
+        let client = self.pool.get().await?;
+        let since = (chrono::Utc::now().timestamp() - window_secs) as f64;
+        let mut summary = JournalSummary::default();
+        for row in client.query(
+            "SELECT status, COUNT(*), COALESCE(SUM(realized_pnl), 0)::BIGINT FROM trades
+             WHERE created_at > to_timestamp($1) GROUP BY status",
+            &[&since],
+        ).await? {
+            let count = row.get::<_, i64>(1) as u64;
+            summary.realized_pnl_lamports += row.get::<_, i64>(2);
+            match row.get::<_, &str>(0) {
+                "rejected" => summary.rejected = count,
+                "dispatched" => summary.dispatched = count,
+                "send_failed" => summary.send_failed = count,
+                "landed" => summary.landed = count,
+                "failed" => summary.failed = count,
+                _ => {}
+            }
+        }
+        summary.top_rejections = client.query(
+            "SELECT rejection_reason, COUNT(*) FROM trades
+             WHERE status = 'rejected' AND created_at > to_timestamp($1)
+             GROUP BY rejection_reason ORDER BY 2 DESC LIMIT 5",
+            &[&since],
+        ).await?
+            .iter()
+            .map(|row| (row.get::<_, Option<String>>(0).unwrap_or_default(), row.get::<_, i64>(1) as u64))
+            .collect();
+        Ok(summary)
+    }
+
+    /// Dispatched trades over the last `window_secs` by strategy and trigger, highest realized PnL first
+    pub async fn attribution(&self, window_secs: i64) -> anyhow::Result<Vec<TriggerAttribution>> {
+        let client = self.pool.get().await?;
+        let since = (chrono::Utc::now().timestamp() - window_secs) as f64;
+        let rows = client.query(
+            "SELECT COALESCE(strategy, 'arb'), COALESCE(trigger, 'account_update'),
+                    COUNT(*),
+                    COUNT(*) FILTER (WHERE status = 'landed'),
+                    COUNT(*) FILTER (WHERE status = 'failed'),
+                    COALESCE(SUM(realized_pnl), 0)::BIGINT,
+                    COALESCE(SUM(tip_lamports), 0)::BIGINT,
+                    AVG(ai_confidence)::DOUBLE PRECISION,
+                    AVG(dna_score)::DOUBLE PRECISION
+             FROM trades
+             WHERE status IN ('dispatched', 'landed', 'failed') AND created_at > to_timestamp($1)
+             GROUP BY 1, 2 ORDER BY 6 DESC",
+            &[&since],
+        ).await?;
+        Ok(rows.iter().map(|row| TriggerAttribution {
+            strategy: row.get(0),
+            trigger: row.get(1),
+            dispatched: row.get::<_, i64>(2) as u64,
+            landed: row.get::<_, i64>(3) as u64,
+            failed: row.get::<_, i64>(4) as u64,
+            realized_pnl_lamports: row.get(5),
+            tips_lamports: row.get(6),
+            avg_ai_confidence: row.get(7),
+            avg_dna_score: row.get(8),
+        }).collect())
+    }
+}
+
+impl TradeJournalPort for TradeJournal {
+    fn record_rejection(&self, opportunity: &ArbitrageOpportunity, reason: &'static str) {
+        if !self.admit_rejection() {
+            return;
+        }
+        self.enqueue(JournalWrite::Attempt {
+            status: TradeStatus::Rejected,
+            opportunity: opportunity.clone(),
+            rejection_reason: Some(reason),
+            tip_lamports: None,
+            bundle_id: None,
+            error: None,
+        });
+    }
+
+    fn record_dispatch(&self, opportunity: &ArbitrageOpportunity, tip_lamports: u64, bundle_id: &str) {
+        self.enqueue(JournalWrite::Attempt {
+            status: TradeStatus::Dispatched,
+            opportunity: opportunity.clone(),
+            rejection_reason: None,
+            tip_lamports: Some(tip_lamports),
+            bundle_id: Some(bundle_id.to_string()),
+            error: None,
+        });
+    }
+
+    fn record_send_failure(&self, opportunity: &ArbitrageOpportunity, tip_lamports: u64, error: &str) {
+        self.enqueue(JournalWrite::Attempt {
+            status: TradeStatus::SendFailed,
+            opportunity: opportunity.clone(),
+            rejection_reason: None,
+            tip_lamports: Some(tip_lamports),
+            bundle_id: None,
+            error: Some(error.to_string()),
+        });
+    }
+}
+
+async fn write_loop(pool: Pool, mut rx: mpsc::Receiver<JournalWrite>) {
+    while let Some(write) = rx.recv().await {
+        if let Err(e) = apply(&pool, write).await {
+            tracing::warn!("⚠️ Trade journal write failed: {}", e);
+        }
+    }
+}
+
+async fn apply(pool: &Pool, write: JournalWrite) -> anyhow::Result<()> {
+    let client = pool.get().await?;
+    match write {
+        JournalWrite::Attempt { status, opportunity, rejection_reason, tip_lamports, bundle_id, error } => {
+            client.execute(
+                "INSERT INTO trades (status, rejection_reason, route, hops, input_amount, expected_profit, opportunity, tip_lamports, bundle_id, error,
+                                     strategy, trigger, ai_confidence, dna_score)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7::text::jsonb, $8, $9, $10, $11, $12, $13, $14)",
+                &[
+                    &status.as_str(),
+                    &rejection_reason,
+                    &route_signature(&opportunity),
+                    &(opportunity.steps.len() as i16),
+                    &(opportunity.input_amount as i64),
+                    &(opportunity.profit_in_sol() as i64),
+                    &serde_json::to_string(&opportunity)?,
+                    &tip_lamports.map(|t| t as i64),
+                    &bundle_id,
+                    &error,
+                    &opportunity.trigger.strategy(),
+                    &opportunity.trigger.as_str(),
+                    &opportunity.ai_confidence,
+                    &opportunity.dna_score.map(|s| s.min(i64::MAX as u64) as i64),
+                ],
+            ).await?;
+        }
+        JournalWrite::Settlement { bundle_id, status, realized_pnl, cu_price_micro_lamports } => {
+            client.execute(
+                "UPDATE trades SET status = $2, realized_pnl = $3, cu_price_micro_lamports = $4, settled_at = now()
+                 WHERE bundle_id = $1",
+                &[&bundle_id, &status.as_str(), &realized_pnl, &cu_price_micro_lamports.map(|p| p as i64)],
+            ).await?;
+        }
+        JournalWrite::Alias { bundle_id, settled } => {
+            client.execute("UPDATE trades SET bundle_id = $2 WHERE bundle_id = $1", &[&bundle_id, &settled]).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_land_rate_and_trigger_attribution() {
+        let summary = JournalSummary { landed: 3, failed: 1, ..Default::default() };
+        assert_eq!(summary.land_rate(), Some(0.75));
+        assert_eq!(JournalSummary::default().land_rate(), None);
+
+        let backrun = TriggerAttribution { landed: 2, failed: 2, realized_pnl_lamports: 90_000, ..Default::default() };
+        assert_eq!(backrun.land_rate(), Some(0.5));
+        assert_eq!(backrun.pnl_per_trade(), Some(22_500));
+        assert_eq!(TriggerAttribution { dispatched: 3, ..Default::default() }.pnl_per_trade(), None);
+
+        // Journal rows written before tagging read back as update-driven arb
+        let legacy: ArbitrageOpportunity = serde_json::from_str(
+            r#"{"steps":[],"expected_profit_lamports":0,"input_amount":0,"total_fees_bps":0,"max_price_impact_bps":0,
+                "min_liquidity":0,"timestamp":0,"is_dna_match":false,"is_elite_match":false,
+                "initial_liquidity_lamports":null,"launch_hour_utc":null}"#,
+        ).unwrap();
+        assert_eq!((legacy.trigger.strategy(), legacy.trigger.as_str()), ("arb", "account_update"));
+        assert_eq!(mev_core::Trigger::Backrun.strategy(), "backrun");
+    }
+}
@@ -0,0 +1,327 @@
+/// Training Data Exporter
+///
+/// With `TRAINING_EXPORT_ENABLED`, every opportunity the engine decides on is
+/// written as one row: route, sizes, the decision, the simulation result, and
+/// the model features at the latest schema under their schema names, so
+/// `train_model.py` reads live data the same way it reads backtests. A
+/// dispatched row is held back until its settlement arrives (landed and
+/// realized PnL), or written as not landed after `SETTLEMENT_TIMEOUT`, which
+/// is how dropped bundles end. Files rotate at UTC midnight; with
+/// `TRAINING_EXPORT_FORMAT=parquet` the finished day's CSV is converted and
+/// removed. Rows go through a bounded channel, like the trade journal's.
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::mpsc;
+use strategy::adapters::features::FeatureSchema;
+use strategy::analytics::rejections::route_signature;
+use strategy::ports::{TrainingEvaluation, TrainingExportPort};
+
+const QUEUE_CAPACITY: usize = 4096;
+/// Jito never reports a dropped bundle; after this long it counts as not landed
+const SETTLEMENT_TIMEOUT: Duration = Duration::from_secs(120);
+const SWEEP_INTERVAL: Duration = Duration::from_secs(10);
+const LEADING_COLUMNS: &str = "timestamp_ms,schema_version,route,hops,input_amount,expected_profit,tip_lamports,decision,simulated_units,simulation_error,bundle_id,landed,realized_pnl";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Parquet,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "parquet" => Ok(ExportFormat::Parquet),
+            other => Err(format!("unknown format '{}' (csv, parquet)", other)),
+        }
+    }
+}
+
+enum ExportEvent {
+    Evaluation { at_ms: u64, evaluation: TrainingEvaluation },
+    Settlement { bundle_id: String, landed: bool, realized_pnl: Option<i64> },
+    /// A raced trade settles under its RPC copy's signature
+    Alias { bundle_id: String, settled: String },
+}
+
+struct Row {
+    at_ms: u64,
+    evaluation: TrainingEvaluation,
+    landed: Option<bool>,
+    realized_pnl: Option<i64>,
+}
+
+pub struct TrainingExporter {
+    sender: mpsc::Sender<ExportEvent>,
+}
+
+impl TrainingExporter {
+    /// Starts the writer task; files go to `dir`
+    pub fn new(dir: PathBuf, format: ExportFormat) -> Self {
+        let (sender, rx) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(write_loop(dir, format, rx));
+        Self { sender }
+    }
+
+    fn enqueue(&self, event: ExportEvent) {
+        if self.sender.try_send(event).is_err() {
+            tracing::debug!("🧪 Training export queue full; dropping a row");
+        }
+    }
+
+    /// Completes the row of a dispatched route
+    pub fn record_settlement(&self, bundle_id: String, landed: bool, realized_pnl: Option<i64>) {
+        self.enqueue(ExportEvent::Settlement { bundle_id, landed, realized_pnl });
+    }
+
+    /// The row of a raced route waits for its settlement under `settled` instead
+    pub fn record_alias(&self, bundle_id: &str, settled: &str) {
+        self.enqueue(ExportEvent::Alias { bundle_id: bundle_id.to_string(), settled: settled.to_string() });
+    }
+}
+
+impl TrainingExportPort for TrainingExporter {
+    fn record_evaluation(&self, evaluation: TrainingEvaluation) {
+        let at_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        self.enqueue(ExportEvent::Evaluation { at_ms, evaluation });
+    }
+}
+
+fn header() -> String {
+    format!("{},{}\n", LEADING_COLUMNS, FeatureSchema::LATEST.names().join(","))
+}
+
+/// Quotes a text field when it would break the row
+fn csv_text(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn format_row(row: &Row) -> String {
+    let e = &row.evaluation;
+    let opt = |v: Option<String>| v.unwrap_or_default();
+    let mut line = [
+        row.at_ms.to_string(),
+        FeatureSchema::LATEST.version.to_string(),
+        csv_text(&route_signature(&e.opportunity)),
+        e.opportunity.steps.len().to_string(),
+        e.opportunity.input_amount.to_string(),
+        e.opportunity.expected_profit_lamports.to_string(),
+        e.tip_lamports.to_string(),
+        e.decision.to_string(),
+        opt(e.simulation.as_ref().and_then(|s| s.as_ref().ok()).map(u64::to_string)),
+        opt(e.simulation.as_ref().and_then(|s| s.as_ref().err()).map(|err| csv_text(err))),
+        opt(e.bundle_id.as_deref().map(csv_text)),
+        opt(row.landed.map(|l| l.to_string())),
+        opt(row.realized_pnl.map(|p| p.to_string())),
+    ]
+    .join(",");
+    // Always the full schema width, so every row lines up with the header
+    for i in 0..FeatureSchema::LATEST.width() {
+        line.push(',');
+        line.push_str(&e.features.get(i).copied().unwrap_or(0.0).to_string());
+    }
+    line.push('\n');
+    line
+}
+
+fn day_of(at_ms: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(at_ms as i64)
+        .unwrap_or_default()
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn csv_path(dir: &Path, day: &str) -> PathBuf {
+    dir.join(format!("training_v{}_{}.csv", FeatureSchema::LATEST.version, day))
+}
+
+/// One day's file, appended to across restarts
+struct DayFile {
+    day: String,
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl DayFile {
+    async fn open(dir: &Path, day: String) -> std::io::Result<Self> {
+        tokio::fs::create_dir_all(dir).await?;
+        let path = csv_path(dir, &day);
+        let exists = path.exists();
+        let file = OpenOptions::new().create(true).append(true).open(&path).await?;
+        let mut writer = BufWriter::new(file);
+        if !exists {
+            writer.write_all(header().as_bytes()).await?;
+        }
+        Ok(Self { day, path, writer })
+    }
+}
+
+async fn write_loop(dir: PathBuf, format: ExportFormat, mut rx: mpsc::Receiver<ExportEvent>) {
+    let mut file: Option<DayFile> = None;
+    let mut pending: HashMap<String, (Instant, Row)> = HashMap::new();
+    let mut sweep = tokio::time::interval(SWEEP_INTERVAL);
+
+    loop {
+        let mut ready = Vec::new();
+        tokio::select! {
+            event = rx.recv() => match event {
+                Some(ExportEvent::Evaluation { at_ms, evaluation }) => {
+                    let row = Row { at_ms, evaluation, landed: None, realized_pnl: None };
+                    match row.evaluation.bundle_id.clone() {
+                        Some(bundle_id) => { pending.insert(bundle_id, (Instant::now(), row)); }
+                        None => ready.push(row),
+                    }
+                }
+                Some(ExportEvent::Settlement { bundle_id, landed, realized_pnl }) => {
+                    if let Some((_, mut row)) = pending.remove(&bundle_id) {
+                        row.landed = Some(landed);
+                        row.realized_pnl = realized_pnl;
+                        ready.push(row);
+                    }
+                }
+                Some(ExportEvent::Alias { bundle_id, settled }) => {
+                    if let Some(entry) = pending.remove(&bundle_id) {
+                        pending.insert(settled, entry);
+                    }
+                }
+                None => {
+                    // Shutting down: whatever hasn't settled is written as unknown
+                    ready.extend(pending.drain().map(|(_, (_, row))| row));
+                    write_rows(&dir, format, &mut file, ready).await;
+                    if let Some(open) = file.as_mut() {
+                        let _ = open.writer.flush().await;
+                    }
+                    return;
+                }
+            },
+            _ = sweep.tick() => {
+                let expired: Vec<String> = pending.iter()
+                    .filter(|(_, (since, _))| since.elapsed() >= SETTLEMENT_TIMEOUT)
+                    .map(|(id, _)| id.clone())
+                    .collect();
+                for id in expired {
+                    if let Some((_, mut row)) = pending.remove(&id) {
+                        row.landed = Some(false);
+                        ready.push(row);
+                    }
+                }
+                if let Some(open) = file.as_mut() {
+                    if let Err(e) = open.writer.flush().await {
+                        tracing::warn!("🧪 Training export flush failed: {}", e);
+                    }
+                }
+            }
+        }
+        write_rows(&dir, format, &mut file, ready).await;
+    }
+}
+
+async fn write_rows(dir: &Path, format: ExportFormat, file: &mut Option<DayFile>, rows: Vec<Row>) {
+    for row in rows {
+        let day = day_of(row.at_ms);
+        // A settlement can finish yesterday's row after midnight; it goes in today's file
+        if file.as_ref().is_some_and(|open| open.day < day) {
+            if let Some(closed) = file.take() {
+                rotate(closed, format).await;
+            }
+        }
+        if file.is_none() {
+            match DayFile::open(dir, day).await {
+                Ok(open) => *file = Some(open),
+                Err(e) => {
+                    tracing::warn!("🧪 Training export file unavailable: {}", e);
+                    continue;
+                }
+            }
+        }
+        if let Some(open) = file.as_mut() {
+            if let Err(e) = open.writer.write_all(format_row(&row).as_bytes()).await {
+                tracing::warn!("🧪 Training export write failed: {}", e);
+            }
+        }
+    }
+}
+
+/// Closes a finished day, converting it to Parquet when asked
+async fn rotate(mut closed: DayFile, format: ExportFormat) {
+    if let Err(e) = closed.writer.flush().await {
+        tracing::warn!("🧪 Training export flush failed: {}", e);
+    }
+    drop(closed.writer);
+    if format == ExportFormat::Csv {
+        tracing::info!("🧪 Training export for {} closed: {}", closed.day, closed.path.display());
+        return;
+    }
+    let csv = closed.path.clone();
+    match tokio::task::spawn_blocking(move || to_parquet(&csv)).await {
+        Ok(Ok(parquet)) => tracing::info!("🧪 Training export for {} written to {}", closed.day, parquet.display()),
+        Ok(Err(e)) => tracing::warn!("🧪 Parquet conversion of {} failed, keeping the CSV: {}", closed.path.display(), e),
+        Err(e) => tracing::warn!("🧪 Parquet conversion of {} panicked: {}", closed.path.display(), e),
+    }
+}
+
+fn to_parquet(csv: &Path) -> anyhow::Result<PathBuf> {
+    use polars::prelude::*;
+    let mut frame = CsvReader::from_path(csv)?.has_header(true).finish()?;
+    let parquet = csv.with_extension("parquet");
+    ParquetWriter::new(std::fs::File::create(&parquet)?).finish(&mut frame)?;
+    std::fs::remove_file(csv)?;
+    Ok(parquet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::{ArbitrageOpportunity, SwapStep};
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_rows_line_up_with_the_header() {
+        let opp = ArbitrageOpportunity {
+            steps: (0..2).map(|_| SwapStep {
+                pool: Pubkey::new_unique(),
+                program_id: mev_core::constants::RAYDIUM_V4_PROGRAM,
+                input_mint: Pubkey::new_unique(),
+                output_mint: Pubkey::new_unique(),
+                expected_output: 0,
+            }).collect(),
+            input_amount: 1_000_000,
+            expected_profit_lamports: 5_000,
+            ..Default::default()
+        };
+        let row = Row {
+            at_ms: 86_400_000,
+            evaluation: TrainingEvaluation {
+                opportunity: opp,
+                features: vec![2.0, 50.0],
+                decision: "simulation",
+                simulation: Some(Err("custom program error: 0x1, slippage".to_string())),
+                tip_lamports: 1_000,
+                bundle_id: None,
+            },
+            landed: None,
+            realized_pnl: None,
+        };
+        let line = format_row(&row);
+        assert!(line.contains(",simulation,,\"custom program error: 0x1, slippage\","));
+        let columns = |s: &str| s.trim_end().split(',').count();
+        // The quoted error holds one extra comma
+        assert_eq!(columns(&line) - 1, columns(&header()));
+        assert!(line.trim_end().ends_with(",2,50,0,0,0,0,0,0,0,0,0,0"));
+
+        assert_eq!(day_of(86_400_000), "1970-01-02");
+        assert_eq!(csv_text("a\"b"), "\"a\"\"b\"");
+        assert_eq!("Parquet".parse::<ExportFormat>(), Ok(ExportFormat::Parquet));
+        assert!("json".parse::<ExportFormat>().is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use crossterm::{
@@ -10,15 +11,24 @@ use ratatui::{
     layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Span, Line},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, List, ListItem},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, List, ListItem, Wrap},
     Terminal,
 };
 use mev_core::ArbitrageOpportunity;
+use solana_sdk::pubkey::Pubkey;
+use strategy::analytics::decisions::{Decision, DecisionFeed, RouteDecision};
+use strategy::policy::trace_summary;
+use crate::capital_at_risk::{InventoryValuation, TokenHolding};
 use crate::discovery::DiscoveryEvent;
+use crate::inventory_manager::InventoryManager;
+use crate::token_metadata::TokenMetadata;
+
+const MAX_FEED_ROWS: usize = 30;
 
 // Shared State Structure
 pub struct AppState {
-    pub total_simulated_pnl: u64,
+    /// Realized net PnL as reported to `BotMetrics` (paper fills in Simulation mode)
+    pub total_simulated_pnl: i64,
     pub recent_opportunities: Vec<ArbitrageOpportunity>,
     pub recent_discoveries: Vec<DiscoveryEvent>,
     pub recent_logs: Vec<String>,
@@ -26,6 +36,14 @@ pub struct AppState {
     pub start_time: std::time::Instant,
     pub pool_count: usize,
     pub current_latency_ms: f64,
+    /// Executed and rejected routes with per-hop detail (route inspector)
+    pub decision_feed: Option<Arc<DecisionFeed>>,
+    /// Processed updates by `EventOutcome` label
+    pub outcome_counts: BTreeMap<&'static str, u64>,
+    /// Wallet holdings; None with the inventory manager off
+    pub inventory: Option<Arc<InventoryManager>>,
+    /// Symbols and decimals for token amounts; well-known mints until the shared one is attached
+    pub metadata: Arc<TokenMetadata>,
 }
 
 impl AppState {
@@ -39,17 +57,24 @@ impl AppState {
             start_time: std::time::Instant::now(),
             pool_count: 0,
             current_latency_ms: 0.0,
+            decision_feed: None,
+            outcome_counts: BTreeMap::new(),
+            inventory: None,
+            metadata: Arc::default(),
         }
     }
 }
 
 pub struct TuiApp {
     state: Arc<Mutex<AppState>>,
+    /// Row in the route feed, counted from the newest entry
+    selected: usize,
+    inspecting: bool,
 }
 
 impl TuiApp {
     pub fn new(state: Arc<Mutex<AppState>>) -> Self {
-        Self { state }
+        Self { state, selected: 0, inspecting: false }
     }
 
     pub fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
@@ -100,10 +125,17 @@ impl TuiApp {
             
             if crossterm::event::poll(timeout)? {
                 if let Event::Key(key) = event::read()? {
-                    if let KeyCode::Char('q') = key.code {
-                        let mut state = self.state.lock().unwrap();
-                        state.is_running = false;
-                        return Ok(());
+                    match key.code {
+                        KeyCode::Char('q') => {
+                            let mut state = self.state.lock().unwrap();
+                            state.is_running = false;
+                            return Ok(());
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+                        KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(MAX_FEED_ROWS - 1),
+                        KeyCode::Enter => self.inspecting = true,
+                        KeyCode::Esc => self.inspecting = false,
+                        _ => {}
                     }
                 }
             }
@@ -114,20 +146,26 @@ impl TuiApp {
         }
     }
 
-    fn ui(&self, f: &mut ratatui::Frame) {
+    fn ui(&mut self, f: &mut ratatui::Frame) {
         // 3. UI Layout - Prioritize Opportunity Table
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
             .constraints([
-                Constraint::Length(4),       // Header
+                Constraint::Length(6),       // Header
                 Constraint::Percentage(45),  // Arbitrage Feed
-                Constraint::Percentage(25),  // Discovery Feed (Mojito)
+                Constraint::Percentage(25),  // Discovery Feed (Mojito) / Route Inspector
                 Constraint::Percentage(30),  // Logs
             ].as_ref())
             .split(f.size());
 
         let state = self.state.lock().unwrap();
+        let decisions: Vec<RouteDecision> = state.decision_feed.as_ref()
+            .map(|feed| feed.recent().into_iter().rev().take(MAX_FEED_ROWS).collect())
+            .unwrap_or_default();
+        if !decisions.is_empty() {
+            self.selected = self.selected.min(decisions.len() - 1);
+        }
 
         // [Header Rendering Code - Unchanged] 
         let pnl_sol = state.total_simulated_pnl as f64 / 1_000_000_000.0;
@@ -159,6 +197,17 @@ impl TuiApp {
                 Span::raw(" | Latency: "),
                 Span::styled(format!("{:.2}ms", state.current_latency_ms), Style::default().fg(Color::Cyan)),
             ]),
+            Line::from(vec![
+                Span::raw("Outcomes: "),
+                Span::styled(outcome_summary(&state.outcome_counts), Style::default().fg(Color::Gray)),
+            ]),
+            Line::from(vec![
+                Span::raw("Holdings: "),
+                Span::styled(
+                    state.inventory.as_ref().map_or("off".to_string(), |inventory| holdings_summary(inventory.holdings().as_ref())),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ]),
         ];
         
         let header = Paragraph::new(header_text)
@@ -177,11 +226,7 @@ impl TuiApp {
             
             // Full Route Visualization
             let route_str = opp.steps.iter()
-                .map(|s| {
-                     // Truncate mints for display: "So11...1112" -> "So11.."
-                     let m = s.input_mint.to_string();
-                     format!("{}..", &m[0..4])
-                })
+                .map(|s| state.metadata.symbol(&s.input_mint))
                 .collect::<Vec<_>>()
                 .join(" -> ");
             
@@ -193,17 +238,30 @@ impl TuiApp {
             ])
         });
 
-        let t = Table::new(rows, [
-                Constraint::Percentage(15), // Timestamp
-                Constraint::Percentage(5),  // Hops
-                Constraint::Percentage(15), // Profit
-                Constraint::Percentage(65), // Route
-            ])
-            .header(header_row)
-            .block(Block::default().borders(Borders::ALL).title("Recent Arbitrage Opportunities (Live Feed)"))
-            .column_spacing(2);
-        
-        f.render_widget(t, chunks[1]);
+        if state.decision_feed.is_some() {
+            render_decision_feed(f, chunks[1], &decisions, self.selected, &state.metadata);
+        } else {
+            let t = Table::new(rows, [
+                    Constraint::Percentage(15), // Timestamp
+                    Constraint::Percentage(5),  // Hops
+                    Constraint::Percentage(15), // Profit
+                    Constraint::Percentage(65), // Route
+                ])
+                .header(header_row)
+                .block(Block::default().borders(Borders::ALL).title("Recent Arbitrage Opportunities (Live Feed)"))
+                .column_spacing(2);
+
+            f.render_widget(t, chunks[1]);
+        }
+
+        // 2.2 Route Inspector replaces the discovery feed while open
+        if self.inspecting {
+            if let Some(selected) = decisions.get(self.selected) {
+                render_route_inspector(f, chunks[2], selected, &state.metadata);
+                render_logs(f, chunks[3], &state.recent_logs);
+                return;
+            }
+        }
         
         // 2.5 Discovery Feed (Mojito)
         let discovery_items: Vec<ListItem> = state.recent_discoveries.iter().rev().take(15).map(|ev| {
@@ -221,12 +279,170 @@ impl TuiApp {
         f.render_widget(discovery_list, chunks[2]);
 
         // 3. logs
-        let logs: Vec<ListItem> = state.recent_logs.iter().rev().take(20)
-            .map(|l| ListItem::new(Line::from(vec![Span::raw(l)])))
-            .collect();
-        
-        let log_list = List::new(logs)
-            .block(Block::default().borders(Borders::ALL).title("System Console"));
-        f.render_widget(log_list, chunks[3]);
+        render_logs(f, chunks[3], &state.recent_logs);
+    }
+}
+
+/// Most frequent outcomes first, e.g. "no_opportunity 812 | min_profit 40 | executed 3"
+fn outcome_summary(counts: &BTreeMap<&'static str, u64>) -> String {
+    let mut sorted: Vec<_> = counts.iter().collect();
+    sorted.sort_by(|a, b| b.1.cmp(a.1));
+    sorted.iter().take(6).map(|(label, n)| format!("{} {}", label, n)).collect::<Vec<_>>().join(" | ")
+}
+
+fn holdings_summary(holdings: Option<&InventoryValuation>) -> String {
+    let Some(holdings) = holdings else {
+        return "waiting for the first poll".to_string();
+    };
+    let token_value: u64 = holdings.tokens.iter().filter_map(|t| t.value_lamports).sum();
+    let unpriced = holdings.tokens.iter().filter(|t| t.value_lamports.is_none()).count();
+    let mut summary = format!("{:.4} SOL + {} tokens ≈ {:.4} SOL", holdings.sol_lamports as f64 / 1e9, holdings.tokens.len(), token_value as f64 / 1e9);
+    if unpriced > 0 {
+        summary.push_str(&format!(" ({} unpriced)", unpriced));
+    }
+    let mut largest: Vec<&TokenHolding> = holdings.tokens.iter().collect();
+    largest.sort_by(|a, b| b.value_lamports.cmp(&a.value_lamports));
+    let top: Vec<String> = largest.iter().take(3)
+        .map(|t| format!("{} {}", t.ui_amount.clone().unwrap_or_else(|| format!("{} raw", t.raw_amount)), t.symbol))
+        .collect();
+    if !top.is_empty() {
+        summary.push_str(&format!(" | {}", top.join(", ")));
+    }
+    summary
+}
+
+fn render_logs(f: &mut ratatui::Frame, area: ratatui::layout::Rect, recent_logs: &[String]) {
+    let logs: Vec<ListItem> = recent_logs.iter().rev().take(20)
+        .map(|l| ListItem::new(Line::from(vec![Span::raw(l)])))
+        .collect();
+
+    let log_list = List::new(logs)
+        .block(Block::default().borders(Borders::ALL).title("System Console"));
+    f.render_widget(log_list, area);
+}
+
+pub(crate) fn dex_name(program_id: &Pubkey) -> &'static str {
+    match *program_id {
+        mev_core::constants::RAYDIUM_V4_PROGRAM => "Raydium",
+        mev_core::constants::ORCA_WHIRLPOOL_PROGRAM => "Orca",
+        mev_core::constants::RAYDIUM_CLMM_PROGRAM => "Raydium CLMM",
+        mev_core::constants::PUMP_FUN_PROGRAM => "Pump.fun",
+        mev_core::constants::METEORA_PROGRAM_ID => "Meteora",
+        mev_core::constants::RAYDIUM_LAUNCHLAB_PROGRAM => "LaunchLab",
+        mev_core::constants::MOONSHOT_PROGRAM => "Moonshot",
+        _ => "Unknown",
+    }
+}
+
+fn decision_style(decision: &Decision) -> Style {
+    match decision {
+        Decision::Executed => Style::default().fg(Color::Green),
+        Decision::Rejected(_) => Style::default().fg(Color::Yellow),
+        Decision::ExecutionFailed => Style::default().fg(Color::Red),
+    }
+}
+
+/// Executed and rejected routes, newest first, with the selected row highlighted
+fn render_decision_feed(f: &mut ratatui::Frame, area: ratatui::layout::Rect, decisions: &[RouteDecision], selected: usize, metadata: &TokenMetadata) {
+    let header_cells = ["Time", "Hops", "Profit (Lamports)", "EV", "Decision", "Route"]
+        .iter().map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    let header_row = Row::new(header_cells).height(1).bottom_margin(1);
+
+    let rows = decisions.iter().map(|d| {
+        let route_str = d.opportunity.steps.iter()
+            .map(|s| metadata.symbol(&s.input_mint))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        Row::new(vec![
+            Cell::from(d.at.format("%H:%M:%S").to_string()),
+            Cell::from(d.opportunity.steps.len().to_string()),
+            Cell::from(d.opportunity.expected_profit_lamports.to_string()).style(Style::default().fg(Color::Green)),
+            expected_value_cell(d.opportunity.expected_value_lamports),
+            Cell::from(d.decision.label()).style(decision_style(&d.decision)),
+            Cell::from(route_str),
+        ])
+    });
+
+    let table = Table::new(rows, [
+            Constraint::Percentage(10), // Time
+            Constraint::Percentage(5),  // Hops
+            Constraint::Percentage(15), // Profit
+            Constraint::Percentage(10), // EV
+            Constraint::Percentage(20), // Decision
+            Constraint::Percentage(40), // Route
+        ])
+        .header(header_row)
+        .block(Block::default().borders(Borders::ALL).title("Recent Routes (↑/↓ select, Enter inspect, Esc close)"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .column_spacing(2);
+
+    let mut table_state = TableState::default();
+    if !decisions.is_empty() {
+        table_state.select(Some(selected));
+    }
+    f.render_stateful_widget(table, area, &mut table_state);
+}
+
+/// EV at the gate; blank for routes rejected before it
+fn expected_value_cell(ev: Option<i64>) -> Cell<'static> {
+    match ev {
+        Some(ev) => Cell::from(ev.to_string()).style(Style::default().fg(if ev > 0 { Color::Green } else { Color::Red })),
+        None => Cell::from("-"),
     }
 }
+
+/// Full route of one decision: per-hop pool, DEX, amounts, fee and impact
+fn render_route_inspector(f: &mut ratatui::Frame, area: ratatui::layout::Rect, decision: &RouteDecision, metadata: &TokenMetadata) {
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Length(4), Constraint::Min(3)].as_ref())
+        .split(area);
+
+    let opp = &decision.opportunity;
+    let summary = Paragraph::new(Line::from(vec![
+        Span::styled(decision.decision.label(), decision_style(&decision.decision).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(
+            " | In: {} | Profit: {} | EV: {} | Fees: {} bps | Max impact: {} bps",
+            opp.steps.first().map_or(opp.input_amount.to_string(), |s| metadata.format(&s.input_mint, opp.input_amount)),
+            opp.expected_profit_lamports,
+            opp.expected_value_lamports.map_or("-".to_string(), |ev| ev.to_string()),
+            opp.total_fees_bps, opp.max_price_impact_bps
+        )),
+    ]))
+    .block(Block::default().borders(Borders::ALL).title(format!("Route Inspector @ {}", decision.at.format("%H:%M:%S%.3f"))));
+    f.render_widget(summary, parts[0]);
+
+    let gates = Paragraph::new(trace_summary(&decision.trace))
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Gates"));
+    f.render_widget(gates, parts[1]);
+
+    let header_cells = ["#", "DEX", "Pool", "Pair", "Amount In", "Amount Out", "Fee", "Impact"]
+        .iter().map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    let rows = decision.hops.iter().enumerate().map(|(i, hop)| {
+        Row::new(vec![
+            Cell::from((i + 1).to_string()),
+            Cell::from(dex_name(&hop.program_id)),
+            Cell::from(hop.pool.to_string()),
+            Cell::from(format!("{} -> {}", metadata.symbol(&hop.input_mint), metadata.symbol(&hop.output_mint))),
+            Cell::from(metadata.ui_amount(&hop.input_mint, hop.amount_in).unwrap_or_else(|| hop.amount_in.to_string())),
+            Cell::from(metadata.ui_amount(&hop.output_mint, hop.amount_out).unwrap_or_else(|| hop.amount_out.to_string())),
+            Cell::from(format!("{} bps", hop.fee_bps)),
+            Cell::from(format!("{} bps", hop.impact_bps)),
+        ])
+    });
+    let hops = Table::new(rows, [
+            Constraint::Length(2),
+            Constraint::Length(8),
+            Constraint::Percentage(30),
+            Constraint::Percentage(18),
+            Constraint::Percentage(14),
+            Constraint::Percentage(14),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ])
+        .header(Row::new(header_cells).height(1))
+        .block(Block::default().borders(Borders::ALL).title("Hops"))
+        .column_spacing(1);
+    f.render_widget(hops, parts[2]);
+}
@@ -0,0 +1,266 @@
+/// Wallet Failover
+///
+/// With `STANDBY_KEYPAIR_PATH` set, an executor is built for each signer and
+/// trades go through whichever is active. Errors that point at the signing
+/// key rather than the route (signature verification failures, a frozen or
+/// locked account, the key being rate-limited) are counted; after
+/// `WALLET_FAILOVER_THRESHOLD` in a row, or on a Telegram `/rotate_wallet`,
+/// the engine rotates to the other signer.
+///
+/// A rotation pauses trading, re-derives the ATA cache for the new owner and
+/// runs a safety check on it: the wallet must hold at least
+/// `WALLET_FAILOVER_MIN_SOL_LAMPORTS` and its token accounts must be readable.
+/// Only then does the switch happen and trading resume (unless it was paused
+/// before). A failed check keeps the old signer and trading paused.
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::mpsc::UnboundedSender;
+use mev_core::ArbitrageOpportunity;
+use strategy::ports::ExecutionPort;
+use crate::alerts::AlertSeverity;
+use crate::metrics::BotMetrics;
+use crate::wallet_manager::WalletManager;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignerFault {
+    SignatureRejected,
+    AccountLocked,
+    RateLimited,
+}
+
+impl SignerFault {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SignerFault::SignatureRejected => "signature",
+            SignerFault::AccountLocked => "locked",
+            SignerFault::RateLimited => "rate_limited",
+        }
+    }
+}
+
+/// Whether a send error is the signer's fault rather than the route's
+pub fn classify(error: &str) -> Option<SignerFault> {
+    let error = error.to_lowercase();
+    let any = |needles: &[&str]| needles.iter().any(|n| error.contains(n));
+    if any(&["signature verification", "signaturefailure", "invalid signature"]) {
+        Some(SignerFault::SignatureRejected)
+    } else if any(&["account is frozen", "accountinuse", "account in use", "account locked"]) {
+        Some(SignerFault::AccountLocked)
+    } else if any(&["rate limit", "resource exhausted", "resourceexhausted"]) {
+        // Block-engine limits (gRPC); an RPC's HTTP 429 is the endpoint's, and the pool rotates away from it
+        Some(SignerFault::RateLimited)
+    } else {
+        None
+    }
+}
+
+/// Consecutive signer faults; a successful send clears the run, other errors leave it
+pub struct FaultTracker {
+    threshold: u32,
+    consecutive: AtomicU32,
+}
+
+impl FaultTracker {
+    /// `threshold` 0 never trips
+    pub fn new(threshold: u32) -> Self {
+        Self { threshold, consecutive: AtomicU32::new(0) }
+    }
+
+    /// The fault that completed a run of `threshold`, if this one did
+    pub fn record(&self, outcome: Result<(), &str>) -> Option<SignerFault> {
+        let fault = match outcome {
+            Ok(()) => {
+                self.consecutive.store(0, Ordering::Relaxed);
+                return None;
+            }
+            Err(e) => classify(e)?,
+        };
+        let run = self.consecutive.fetch_add(1, Ordering::Relaxed) + 1;
+        (self.threshold > 0 && run >= self.threshold).then(|| {
+            self.consecutive.store(0, Ordering::Relaxed);
+            fault
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FailoverPolicy {
+    pub threshold: u32,
+    pub min_sol_lamports: u64,
+}
+
+pub struct WalletFailover {
+    /// Primary first, then the standby
+    signers: [Arc<dyn ExecutionPort>; 2],
+    active: AtomicUsize,
+    rotating: AtomicBool,
+    faults: FaultTracker,
+    policy: FailoverPolicy,
+    wallet: Arc<WalletManager>,
+    metrics: Arc<BotMetrics>,
+    alerts: UnboundedSender<(AlertSeverity, String)>,
+}
+
+impl WalletFailover {
+    pub fn new(
+        primary: Arc<dyn ExecutionPort>,
+        standby: Arc<dyn ExecutionPort>,
+        policy: FailoverPolicy,
+        wallet: Arc<WalletManager>,
+        metrics: Arc<BotMetrics>,
+        alerts: UnboundedSender<(AlertSeverity, String)>,
+    ) -> Self {
+        mev_core::telemetry::WALLET_ACTIVE_SIGNER.set(0);
+        Self {
+            signers: [primary, standby],
+            active: AtomicUsize::new(0),
+            rotating: AtomicBool::new(false),
+            faults: FaultTracker::new(policy.threshold),
+            policy,
+            wallet,
+            metrics,
+            alerts,
+        }
+    }
+
+    fn active(&self) -> &Arc<dyn ExecutionPort> {
+        &self.signers[self.active.load(Ordering::Acquire)]
+    }
+
+    /// The wallet trades are currently signed for
+    pub fn active_pubkey(&self) -> Pubkey {
+        *self.active().pubkey()
+    }
+
+    /// `primary` or `standby`
+    pub fn active_label(&self) -> &'static str {
+        if self.active.load(Ordering::Acquire) == 0 { "primary" } else { "standby" }
+    }
+
+    /// Switches to the other signer once it passes the safety check. Returns what happened, for the operator.
+    pub async fn rotate(&self, reason: &str) -> String {
+        if self.rotating.swap(true, Ordering::AcqRel) {
+            return "A wallet rotation is already running".to_string();
+        }
+        let was_paused = self.metrics.is_paused.swap(true, Ordering::Relaxed);
+        let from = self.active.load(Ordering::Acquire);
+        let to = 1 - from;
+        let owner = *self.signers[to].pubkey();
+        tracing::warn!("🔁 Rotating signer ({}): {} → {}", reason, self.signers[from].pubkey(), owner);
+
+        let outcome = match self.confirm(&owner).await {
+            Ok(lamports) => {
+                self.active.store(to, Ordering::Release);
+                mev_core::telemetry::WALLET_ACTIVE_SIGNER.set(to as i64);
+                if !was_paused {
+                    self.metrics.is_paused.store(false, Ordering::Relaxed);
+                }
+                let message = format!("Signer rotated ({}) to the {} wallet {} holding {:.4} SOL; trading {}",
+                    reason, self.active_label(), owner, lamports as f64 / 1e9, if was_paused { "stays paused" } else { "resumed" });
+                let _ = self.alerts.send((AlertSeverity::Warning, message.clone()));
+                mev_core::telemetry::WALLET_ROTATIONS.with_label_values(&[reason, "switched"]).inc();
+                message
+            }
+            Err(e) => {
+                let message = format!("Signer rotation ({}) to {} blocked: {}. Trading stays PAUSED on the current signer.", reason, owner, e);
+                let _ = self.alerts.send((AlertSeverity::Critical, message.clone()));
+                mev_core::telemetry::WALLET_ROTATIONS.with_label_values(&[reason, "blocked"]).inc();
+                message
+            }
+        };
+        tracing::warn!("🔁 {}", outcome);
+        self.rotating.store(false, Ordering::Release);
+        outcome
+    }
+
+    /// Safety check on the incoming wallet: funded, and its ATAs re-derived. Returns its SOL balance.
+    async fn confirm(&self, owner: &Pubkey) -> anyhow::Result<u64> {
+        let lamports = self.wallet.get_sol_balance(owner).await?;
+        if lamports < self.policy.min_sol_lamports {
+            return Err(anyhow::anyhow!("it holds {} lamports, below the {} minimum", lamports, self.policy.min_sol_lamports));
+        }
+        // Every cached ATA belongs to the outgoing owner; keep only the mints the new one has an account for
+        let cache = self.wallet.ata_cache();
+        let mut mints = cache.known();
+        mints.extend(cache.pending());
+        let found: Vec<Pubkey> = self.wallet.check_atas_exist(owner, &mints).await?
+            .into_iter()
+            .filter_map(|(mint, exists)| exists.then_some(mint))
+            .collect();
+        cache.clear();
+        cache.mark_existing(found.iter().copied());
+        tracing::info!("📦 Re-derived ATAs for {}: {} of {} mint(s) have an account", owner, found.len(), mints.len());
+        Ok(lamports)
+    }
+
+    async fn observe(&self, result: &anyhow::Result<String>) {
+        let error = result.as_ref().err().map(|e| e.to_string());
+        let outcome = match &error {
+            Some(e) => Err(e.as_str()),
+            None => Ok(()),
+        };
+        if let Some(fault) = self.faults.record(outcome) {
+            tracing::error!("🔑 {} consecutive {} failures on the {} signer", self.policy.threshold, fault.label(), self.active_label());
+            self.rotate(fault.label()).await;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ExecutionPort for WalletFailover {
+    async fn build_bundle_instructions(
+        &self,
+        opportunity: ArbitrageOpportunity,
+        tip_lamports: u64,
+        max_slippage_bps: u16,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        self.active().build_bundle_instructions(opportunity, tip_lamports, max_slippage_bps).await
+    }
+
+    async fn build_and_send_bundle(
+        &self,
+        opportunity: ArbitrageOpportunity,
+        recent_blockhash: Hash,
+        tip_lamports: u64,
+        max_slippage_bps: u16,
+    ) -> anyhow::Result<String> {
+        let result = self.active().build_and_send_bundle(opportunity, recent_blockhash, tip_lamports, max_slippage_bps).await;
+        self.observe(&result).await;
+        result
+    }
+
+    fn pubkey(&self) -> &Pubkey {
+        self.active().pubkey()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotates_after_consecutive_signer_faults_only() {
+        assert_eq!(classify("Transaction did not pass signature verification"), Some(SignerFault::SignatureRejected));
+        assert_eq!(classify("Error processing Instruction 2: Account is frozen"), Some(SignerFault::AccountLocked));
+        assert_eq!(classify("status: ResourceExhausted, message: \"rate limit exceeded\""), Some(SignerFault::RateLimited));
+        assert_eq!(classify("Pre-flight simulation failed. Trade aborted safely."), None);
+
+        let tracker = FaultTracker::new(3);
+        let rejected = Err("SignatureFailure");
+        assert_eq!(tracker.record(rejected), None);
+        assert_eq!(tracker.record(rejected), None);
+        // A route failure neither counts nor clears the run; a landed send does
+        assert_eq!(tracker.record(Err("slippage exceeded")), None);
+        assert_eq!(tracker.record(rejected), Some(SignerFault::SignatureRejected));
+        assert_eq!(tracker.record(rejected), None);
+        assert_eq!(tracker.record(Ok(())), None);
+        assert_eq!(tracker.record(rejected), None);
+        assert_eq!(tracker.record(rejected), None);
+
+        let manual_only = FaultTracker::new(0);
+        assert!((0..10).all(|_| manual_only.record(rejected).is_none()));
+    }
+}
@@ -7,18 +7,45 @@ use solana_sdk::{
 };
 use spl_associated_token_account::instruction::create_associated_token_account;
 use spl_associated_token_account::get_associated_token_address;
-use solana_client::nonblocking::rpc_client::RpcClient;
+use executor::rpc_pool::RpcPool;
+use executor::token_accounts::AtaCache;
 use anyhow::Result;
+use std::sync::Arc;
 
 pub struct WalletManager {
-    rpc: RpcClient,
+    rpc: Arc<RpcPool>,
+    /// Mints with a known payer ATA; executors skip creating those in their bundles
+    ata_cache: Arc<AtaCache>,
 }
 
 impl WalletManager {
-    pub fn new(rpc_url: &str) -> Self {
-        Self {
-            rpc: RpcClient::new(rpc_url.to_string()),
+    pub fn new(rpc: Arc<RpcPool>) -> Self {
+        Self { rpc, ata_cache: Arc::default() }
+    }
+
+    /// Shares an ATA cache the executors were built with
+    pub fn with_ata_cache(mut self, cache: Arc<AtaCache>) -> Self {
+        self.ata_cache = cache;
+        self
+    }
+
+    /// The cache a signer rotation re-derives for the incoming owner
+    #[cfg(not(feature = "paper-only"))]
+    pub fn ata_cache(&self) -> Arc<AtaCache> {
+        Arc::clone(&self.ata_cache)
+    }
+
+    /// Re-checks the mints bundles created ATAs for. Returns how many are now known to exist.
+    pub async fn refresh_ata_cache(&self, owner: &Pubkey) -> Result<usize> {
+        let pending = self.ata_cache.pending();
+        if pending.is_empty() {
+            return Ok(0);
         }
+        let landed = self.check_atas_exist(owner, &pending).await?
+            .into_iter()
+            .filter(|(_, exists)| *exists)
+            .count();
+        Ok(landed)
     }
 
     /// Ensure an ATA exists for the given mint. 
@@ -26,8 +53,11 @@ impl WalletManager {
     pub async fn ensure_ata_exists(&self, payer: &Pubkey, token_mint: &Pubkey) -> Option<Instruction> {
         let ata = get_associated_token_address(payer, token_mint);
         
-        match self.rpc.get_account(&ata).await {
-            Ok(_) => None, // Account exists
+        match self.rpc.call(|c| async move { c.get_account(&ata).await }).await {
+            Ok(_) => {
+                self.ata_cache.mark_existing([*token_mint]);
+                None // Account exists
+            }
             Err(_) => {
                 println!("📦 Creating ATA for mint: {}", token_mint);
                 Some(create_associated_token_account(
@@ -95,7 +125,7 @@ impl WalletManager {
 
         // RPC get_multiple_accounts limit is typically 100
         for chunk in atas.chunks(100) {
-            let accounts = self.rpc.get_multiple_accounts(chunk).await?;
+            let accounts = self.rpc.call(|c| async move { c.get_multiple_accounts(chunk).await }).await?;
             for (_i, account_opt) in accounts.into_iter().enumerate() {
                 let mint = mints[results.len()];
                 let balance = if let Some(account) = account_opt {
@@ -117,24 +147,53 @@ impl WalletManager {
     /// Check which ATAs exist for a list of mints
     pub async fn check_atas_exist(&self, owner: &Pubkey, mints: &[Pubkey]) -> Result<Vec<(Pubkey, bool)>> {
         let atas: Vec<Pubkey> = mints.iter().map(|m| get_associated_token_address(owner, m)).collect();
-        let accounts = self.rpc.get_multiple_accounts(&atas).await?;
+        let atas = atas.as_slice();
+        let accounts = self.rpc.call(|c| async move { c.get_multiple_accounts(atas).await }).await?;
         
         let mut results = Vec::new();
         for (i, acc) in accounts.into_iter().enumerate() {
             results.push((mints[i], acc.is_some()));
         }
+        self.ata_cache.mark_existing(results.iter().filter(|(_, exists)| *exists).map(|(mint, _)| *mint));
         Ok(results)
     }
 
+    /// Every mint the owner holds a nonzero balance of, summed over its token accounts
+    pub async fn get_token_holdings(&self, owner: &Pubkey) -> Result<Vec<(Pubkey, u64)>> {
+        use solana_account_decoder::UiAccountData;
+        use solana_client::rpc_request::TokenAccountsFilter;
+        use std::str::FromStr;
+
+        let accounts = self.rpc.call(|c| async move {
+            c.get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(spl_token::id())).await
+        }).await?;
+
+        let mut totals: HashMap<Pubkey, u64> = HashMap::new();
+        for keyed in accounts {
+            let UiAccountData::Json(parsed) = keyed.account.data else {
+                continue;
+            };
+            let info = &parsed.parsed["info"];
+            let mint = info["mint"].as_str().and_then(|m| Pubkey::from_str(m).ok());
+            let amount = info["tokenAmount"]["amount"].as_str().and_then(|a| a.parse::<u64>().ok());
+            if let (Some(mint), Some(amount)) = (mint, amount.filter(|a| *a > 0)) {
+                *totals.entry(mint).or_default() += amount;
+            }
+        }
+        let mut holdings: Vec<(Pubkey, u64)> = totals.into_iter().collect();
+        holdings.sort_by_key(|(mint, _)| mint.to_string());
+        Ok(holdings)
+    }
+
     /// Get native SOL balance
     pub async fn get_sol_balance(&self, address: &Pubkey) -> Result<u64> {
-        Ok(self.rpc.get_balance(address).await?)
+        Ok(self.rpc.call(|c| async move { c.get_balance(address).await }).await?)
     }
 
     /// Get token balance for a given mint
     pub async fn get_token_balance(&self, owner: &Pubkey, mint: &Pubkey) -> Result<u64> {
         let ata = get_associated_token_address(owner, mint);
-        match self.rpc.get_token_account_balance(&ata).await {
+        match self.rpc.call(|c| async move { c.get_token_account_balance(&ata).await }).await {
             Ok(balance) => Ok(balance.amount.parse::<u64>().unwrap_or(0)),
             Err(_) => Ok(0), // Account likely doesn't exist
         }
@@ -150,7 +209,7 @@ mod tests {
     #[test]
     fn test_unwrap_wsol_instruction() {
         let payer = Pubkey::new_unique();
-        let wallet_mgr = WalletManager::new("http://localhost:8899");
+        let wallet_mgr = WalletManager::new(Arc::new(RpcPool::single("http://localhost:8899")));
         
         let ix_result = wallet_mgr.unwrap_wsol(&payer);
         assert!(ix_result.is_ok());
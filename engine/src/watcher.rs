@@ -7,24 +7,43 @@ use serde_json::{json, Value};
 use crate::tui::AppState;
 use mev_core::constants::*;
 use mev_core::MarketUpdate;
-use crate::discovery::{DiscoveryEvent, parse_log_message};
+use crate::discovery::{DiscoveryEvent, invoked_program, parse_launchpad_log, parse_log_message, parse_ray_swap_log};
+use strategy::analytics::trade_flow::TradeFlowTracker;
+use strategy::order_books::OrderBookCache;
 // use mev_core::telemetry::*;
 use crate::scoring::PoolScoringEngine;
+use crate::delisting::{self, DelistReason, PoolDelister};
+use executor::rpc_pool::RpcPool;
+use executor::rng::SharedRng;
+use rand::Rng;
+use crate::ws_feed::FeedMonitor;
+use crate::backrun::BackrunTrigger;
+use strategy::safety::liquidity_floor::LiquidityFloor;
+use crate::oracle_feed::PythOracle;
 pub async fn start_market_watcher(
+    endpoint: usize,
+    feed: Arc<FeedMonitor>,
     ws_url: String,
-    rpc_url: String,
+    rpc: Arc<RpcPool>,
+    rng: Arc<SharedRng>,
     discovery_tx: mpsc::Sender<DiscoveryEvent>,
     market_tx: broadcast::Sender<MarketUpdate>,
     tui_state: Option<Arc<std::sync::Mutex<AppState>>>,
-    monitored_pools: HashMap<String, (String, String)>,
+    mut monitored_pools: HashMap<String, (String, String)>,
     mut subscription_rx: mpsc::UnboundedReceiver<String>,
+    mut unsubscribe_rx: mpsc::UnboundedReceiver<solana_sdk::pubkey::Pubkey>,
     scoring_engine: Arc<PoolScoringEngine>,
+    trade_flow: Option<Arc<TradeFlowTracker>>,
+    order_books: Arc<OrderBookCache>,
+    delister: Arc<PoolDelister>,
+    backrun: Option<Arc<BackrunTrigger>>,
+    liquidity_floor: Arc<LiquidityFloor>,
+    oracle: Option<Arc<PythOracle>>,
 ) {
-    tracing::info!("📡 Starting Unified MarketWatcher: {}", ws_url);
+    tracing::info!("📡 Starting Unified MarketWatcher #{}: {}", endpoint, ws_url);
     let hydration_limit = Arc::new(tokio::sync::Semaphore::new(3)); // Max 3 concurrent GET_TRANSACTION calls
 
     let mut retry_delay = 2; // Start with 2s
-    let mut seen_signatures = std::collections::HashSet::new();
     let mut seen_pools: std::collections::HashMap<String, std::time::Instant> = std::collections::HashMap::new();
     let mut last_cleanup = std::time::Instant::now();
     let mut last_decay = std::time::Instant::now();
@@ -32,7 +51,6 @@ pub async fn start_market_watcher(
     loop {
         // Periodic cleanup of seen signatures and pools (every 5 minutes)
         if last_cleanup.elapsed() > std::time::Duration::from_secs(300) {
-            seen_signatures.clear();
             seen_pools.clear();
             last_cleanup = std::time::Instant::now();
         }
@@ -49,7 +67,7 @@ pub async fn start_market_watcher(
                 s
             },
             Err(e) => {
-                let jitter = rand::random::<u64>() % 1000;
+                let jitter = rng.with(|r| r.gen_range(0..1000u64));
                 tracing::error!("❌ Watcher WebSocket Failed: {}. Retrying in {}s...", e, retry_delay);
                 tokio::time::sleep(tokio::time::Duration::from_millis(retry_delay * 1000 + jitter)).await;
                 retry_delay = (retry_delay * 2).min(60); // Max 60s
@@ -58,7 +76,7 @@ pub async fn start_market_watcher(
         };
 
         let (mut write, mut read) = ws_stream.split();
-        let rpc_client = Arc::new(solana_client::nonblocking::rpc_client::RpcClient::new(rpc_url.clone()));
+        let rpc_client = Arc::clone(&rpc);
 
         // 1. Initial Subscriptions
         let sub_messages = vec![
@@ -81,6 +99,14 @@ pub async fn start_market_watcher(
             json!({
                 "jsonrpc": "2.0", "id": 5, "method": "slotSubscribe"
             }),
+            json!({
+                "jsonrpc": "2.0", "id": 6, "method": "logsSubscribe",
+                "params": [{ "mentions": [RAYDIUM_LAUNCHLAB_PROGRAM.to_string()] }, { "commitment": "processed" }]
+            }),
+            json!({
+                "jsonrpc": "2.0", "id": 7, "method": "logsSubscribe",
+                "params": [{ "mentions": [MOONSHOT_PROGRAM.to_string()] }, { "commitment": "processed" }]
+            }),
         ];
 
         for sub in sub_messages {
@@ -89,6 +115,8 @@ pub async fn start_market_watcher(
 
         let mut sub_to_pool = HashMap::new();
         let mut pending_subs = HashMap::new(); // Request ID -> Pool Addr
+        let mut log_sub_to_pool = HashMap::new();
+        let mut pending_log_subs = HashMap::new(); // Request ID -> Pool Addr (ray_log trade flow)
         let mut req_id = 100;
 
         for pool_addr in monitored_pools.keys() {
@@ -99,12 +127,51 @@ pub async fn start_market_watcher(
                 "params": [pool_addr, { "encoding": "base64", "commitment": "processed" }]
             });
             let _ = write.send(Message::Text(sub_msg.to_string().into())).await;
+
+            if trade_flow.is_some() {
+                let mid = req_id; req_id += 1;
+                pending_log_subs.insert(mid, pool_addr.clone());
+                let _ = write.send(Message::Text(pool_logs_subscription(mid, pool_addr).to_string().into())).await;
+            }
+        }
+
+        // Streamed oracle price accounts share the subscriptions; a poll covers writes missed while disconnected
+        if let Some(oracle) = &oracle {
+            for account in oracle.price_accounts() {
+                let mid = req_id; req_id += 1;
+                pending_subs.insert(mid, account.to_string());
+                let sub_msg = json!({
+                    "jsonrpc": "2.0", "id": mid, "method": "accountSubscribe",
+                    "params": [account.to_string(), { "encoding": "base64", "commitment": "confirmed" }]
+                });
+                let _ = write.send(Message::Text(sub_msg.to_string().into())).await;
+            }
+            let oracle = Arc::clone(oracle);
+            tokio::spawn(async move { oracle.refresh().await });
         }
 
-        tracing::info!("👂 Unified Watcher ONLINE. Monitoring {} pools + New Discovery.", monitored_pools.len());
+        tracing::info!("👂 Unified Watcher #{} ONLINE. Monitoring {} pools + New Discovery.", endpoint, monitored_pools.len());
 
         loop {
             tokio::select! {
+                Some(dead_pool) = unsubscribe_rx.recv() => {
+                    let dead_pool = dead_pool.to_string();
+                    monitored_pools.remove(&dead_pool);
+                    feed.forget_pool(&dead_pool);
+                    let unsubscribe = |method: &str, sub_id: u64| json!({
+                        "jsonrpc": "2.0", "id": 0, "method": method, "params": [sub_id]
+                    });
+                    for (sub_id, method) in sub_to_pool.iter().filter(|(_, p)| **p == dead_pool).map(|(id, _)| (*id, "accountUnsubscribe"))
+                        .chain(log_sub_to_pool.iter().filter(|(_, p)| **p == dead_pool).map(|(id, _)| (*id, "logsUnsubscribe")))
+                        .collect::<Vec<_>>()
+                    {
+                        let _ = write.send(Message::Text(unsubscribe(method, sub_id).to_string().into())).await;
+                    }
+                    sub_to_pool.retain(|_, p| *p != dead_pool);
+                    log_sub_to_pool.retain(|_, p| *p != dead_pool);
+                    tracing::info!("🔕 [Unified] Unsubscribed delisted pool {}", dead_pool);
+                }
+
                 Some(new_pool) = subscription_rx.recv() => {
                     let mid = req_id; req_id += 1;
                     pending_subs.insert(mid, new_pool.clone());
@@ -115,11 +182,17 @@ pub async fn start_market_watcher(
                     if let Err(e) = write.send(Message::Text(sub_msg.to_string().into())).await {
                         tracing::error!("❌ Failed dynamic sub send for {}: {}", new_pool, e);
                     }
+                    if trade_flow.is_some() {
+                        let mid = req_id; req_id += 1;
+                        let _ = write.send(Message::Text(pool_logs_subscription(mid, &new_pool).to_string().into())).await;
+                        pending_log_subs.insert(mid, new_pool);
+                    }
                 }
 
                 msg = read.next() => {
                     match msg {
                         Some(Ok(Message::Text(text))) => {
+                            feed.touch(endpoint);
                             if let Ok(json) = serde_json::from_str::<Value>(&text) {
                                 if let Some(id_val) = json.get("id").and_then(|v| v.as_u64()) {
                                     if let Some(pool_addr) = pending_subs.get(&(id_val as i32)) {
@@ -127,6 +200,10 @@ pub async fn start_market_watcher(
                                             sub_to_pool.insert(sub_id, pool_addr.clone());
                                             tracing::info!("✅ [Unified] Subscribed: {} (ID: {})", pool_addr, sub_id);
                                         }
+                                    } else if let Some(pool_addr) = pending_log_subs.get(&(id_val as i32)) {
+                                        if let Some(sub_id) = json.get("result").and_then(|v| v.as_u64()) {
+                                            log_sub_to_pool.insert(sub_id, pool_addr.clone());
+                                        }
                                     }
                                     continue;
                                 }
@@ -136,15 +213,32 @@ pub async fn start_market_watcher(
                                     let sub_id = params.get("subscription").and_then(|v| v.as_u64()).unwrap_or(0);
 
                                     match method {
+                                        "logsNotification" if log_sub_to_pool.contains_key(&sub_id) => {
+                                            let pool_addr = &log_sub_to_pool[&sub_id];
+                                            let signature = params.pointer("/result/value/signature").and_then(|s| s.as_str()).unwrap_or("unknown");
+                                            // Every endpoint delivers the same swap; count it once
+                                            if !feed.accept_signature(&format!("{}:{}", pool_addr, signature)) {
+                                                continue;
+                                            }
+                                            if let (Some(flow), Some(logs)) = (&trade_flow, params.pointer("/result/value/logs").and_then(|l| l.as_array())) {
+                                                handle_pool_logs(pool_addr, logs, flow, &scoring_engine, backrun.as_deref());
+                                            }
+                                        },
                                         "logsNotification" => {
                                              if let Some(result) = params.get("result") {
                                                 if let Some(value) = result.get("value") {
                                                     if let Some(logs) = value.get("logs").and_then(|l| l.as_array()) {
                                                         let signature = value.get("signature").and_then(|s| s.as_str()).unwrap_or("unknown");
+                                                        let mut invoked = None;
                                                         for log in logs {
                                                             let log_str = log.as_str().unwrap_or("");
-                                                            if let Some(event) = parse_log_message(log_str, signature) {
-                                                                if seen_signatures.insert(signature.to_string()) {
+                                                            if let Some(program) = invoked_program(log_str) {
+                                                                invoked = Some(program);
+                                                            }
+                                                            let parsed = parse_launchpad_log(log_str, invoked.as_ref())
+                                                                .or_else(|| parse_log_message(log_str, signature));
+                                                            if let Some(event) = parsed {
+                                                                if feed.accept_signature(signature) {
                                                                     let pool_key = event.pool_address.to_string();
                                                                     let should_process = if let Some(last_seen) = seen_pools.get(&pool_key) {
                                                                         if last_seen.elapsed() < std::time::Duration::from_secs(300) {
@@ -160,7 +254,7 @@ pub async fn start_market_watcher(
                                                                     
                                                                     if should_process {
                                                                         seen_pools.insert(pool_key, std::time::Instant::now());
-                                                                        handle_discovery_event(event, signature, &rpc_client, &market_tx, &discovery_tx, &tui_state, hydration_limit.clone(), Arc::clone(&scoring_engine)).await;
+                                                                        handle_discovery_event(event, signature, &rpc_client, &market_tx, &discovery_tx, &tui_state, hydration_limit.clone(), Arc::clone(&scoring_engine), Arc::clone(&liquidity_floor)).await;
                                                                     }
                                                                 }
                                                             }
@@ -172,17 +266,39 @@ pub async fn start_market_watcher(
                                         "accountNotification" => {
                                             if let Some(pool_addr_str) = sub_to_pool.get(&sub_id) {
                                                 if let Some(result) = params.get("result") {
+                                                    let slot = result.get("context").and_then(|c| c.get("slot")).and_then(|s| s.as_u64());
+                                                    if let Some(slot) = slot {
+                                                        mev_core::clock::system().observe_slot(slot);
+                                                    }
                                                     if let Some(value) = result.get("value") {
                                                         if let Some(data_arr) = value.get("data").and_then(|d| d.as_array()) {
                                                             if let Some(update_str) = data_arr.first().and_then(|v| v.as_str()) {
-                                                                handle_account_update(pool_addr_str, update_str, &market_tx, Arc::clone(&scoring_engine)).await;
+                                                                // The same write arrives from every endpoint; only the first copy goes on
+                                                                if !feed.accept_account(endpoint, pool_addr_str, slot.unwrap_or(0), update_str) {
+                                                                    continue;
+                                                                }
+                                                                if oracle.as_ref().and_then(|o| o.accept_write(pool_addr_str, update_str)).is_some() {
+                                                                    continue;
+                                                                }
+                                                                let lamports = value.get("lamports").and_then(|l| l.as_u64()).unwrap_or(0);
+                                                                if delisting::is_closed(lamports, update_str.len()) {
+                                                                    if let Ok(pool) = pool_addr_str.parse() {
+                                                                        delister.delist(pool, DelistReason::Closed);
+                                                                    }
+                                                                    continue;
+                                                                }
+                                                                handle_account_update(pool_addr_str, update_str, &market_tx, Arc::clone(&scoring_engine), &order_books).await;
                                                             }
                                                         }
                                                     }
                                                 }
                                             }
                                         },
-                                        "slotNotification" => {},
+                                        "slotNotification" => {
+                                            if let Some(slot) = params.pointer("/result/slot").and_then(|s| s.as_u64()) {
+                                                feed.observe_slot(endpoint, slot);
+                                            }
+                                        },
                                         _ => {}
                                     }
                                 }
@@ -201,15 +317,62 @@ pub async fn start_market_watcher(
     }
 }
 
+fn pool_logs_subscription(id: i32, pool_addr: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0", "id": id, "method": "logsSubscribe",
+        "params": [{ "mentions": [pool_addr] }, { "commitment": "processed" }]
+    })
+}
+
+/// Turns the ray_log swap events of one watched pool into trade flow
+fn handle_pool_logs(
+    pool_addr: &str,
+    logs: &[Value],
+    trade_flow: &TradeFlowTracker,
+    scoring_engine: &PoolScoringEngine,
+    backrun: Option<&BackrunTrigger>,
+) {
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+
+    let pool_pub = Pubkey::from_str(pool_addr).unwrap_or_default();
+    record_pool_swaps(pool_pub, logs.iter().filter_map(|l| l.as_str()), trade_flow, scoring_engine, backrun);
+}
+
+/// Shared with the Geyser backend, which gets the same logs from its transaction stream
+pub(crate) fn record_pool_swaps<'a>(
+    pool_pub: solana_sdk::pubkey::Pubkey,
+    logs: impl IntoIterator<Item = &'a str>,
+    trade_flow: &TradeFlowTracker,
+    scoring_engine: &PoolScoringEngine,
+    backrun: Option<&BackrunTrigger>,
+) {
+    let mut decoded = 0;
+    for log in logs {
+        if let Some(swap) = parse_ray_swap_log(log) {
+            trade_flow.record(pool_pub, &swap);
+            if let Some(backrun) = backrun {
+                backrun.observe(pool_pub, &swap);
+            }
+            decoded += 1;
+        }
+    }
+    if decoded > 0 {
+        mev_core::telemetry::TRADE_FLOW_SWAPS_DECODED.inc_by(decoded as f64);
+        scoring_engine.update_trade_flow(pool_pub, trade_flow.snapshot(pool_pub).pressure());
+    }
+}
+
 async fn handle_discovery_event(
     event: DiscoveryEvent,
     signature: &str,
-    rpc: &Arc<solana_client::nonblocking::rpc_client::RpcClient>,
+    rpc: &Arc<RpcPool>,
     market_tx: &broadcast::Sender<MarketUpdate>,
     discovery_tx: &mpsc::Sender<DiscoveryEvent>,
     tui: &Option<Arc<std::sync::Mutex<AppState>>>,
     semaphore: Arc<tokio::sync::Semaphore>,
     scoring_engine: Arc<PoolScoringEngine>,
+    liquidity_floor: Arc<LiquidityFloor>,
 ) {
     tracing::info!("✨ [{:?}] New Pool Detected! Sig: {}", event.program_id, signature);
     
@@ -226,6 +389,7 @@ async fn handle_discovery_event(
 
     let rpc_clone = Arc::clone(rpc);
     let market_tx_clone = market_tx.clone();
+    let discovery_tx_clone = discovery_tx.clone();
     let sig = signature.to_string();
     let ev = event.clone();
     let sem = semaphore.clone();
@@ -235,6 +399,18 @@ async fn handle_discovery_event(
             let _permit = _permit;
             if ev.program_id == RAYDIUM_V4_PROGRAM {
                 if let Ok(update) = crate::discovery::hydrate_raydium_pool(rpc_clone, sig.clone(), ev).await {
+                    // Bonding curves launch thin by design; a new AMM pool has to hold real depth
+                    let depth = liquidity_floor.depth_lamports(
+                        (update.coin_mint, update.coin_reserve as u128),
+                        (update.pc_mint, update.pc_reserve as u128),
+                        mev_core::clock::system().unix_secs(),
+                    );
+                    if !liquidity_floor.admits(depth) {
+                        mev_core::telemetry::POOLS_BELOW_LIQUIDITY_FLOOR.with_label_values(&["discovery"]).inc();
+                        tracing::debug!("🚫 Discovery Filter: Dropping Raydium {} (~{} SOL-equivalent lamports, floor {})",
+                            update.pool_address, depth.unwrap_or(0), liquidity_floor.min_lamports());
+                        return;
+                    }
                     tracing::info!("🔥 [Unified] INJECTING Raydium {} for Snipe", update.pool_address);
                     let _ = market_tx_clone.send(update);
                 }
@@ -248,6 +424,12 @@ async fn handle_discovery_event(
                     tracing::info!("☄️ [Unified] INJECTING Meteora {} for Snipe", update.pool_address);
                     let _ = market_tx_clone.send(update);
                 }
+            } else if crate::discovery::is_launchpad(&ev.program_id) {
+                if let Ok(update) = crate::discovery::hydrate_launchpad_pool(rpc_clone, sig.clone(), ev).await {
+                    tracing::info!("🚀 [Unified] INJECTING {} {} for Snipe", crate::discovery::launchpad_name(&update.program_id), update.pool_address);
+                    let _ = discovery_tx_clone.send(crate::discovery::hydrated_event(&update)).await;
+                    let _ = market_tx_clone.send(update);
+                }
             }
         });
     } else {
@@ -255,7 +437,13 @@ async fn handle_discovery_event(
     }
 }
 
-async fn handle_account_update(pool_addr: &str, data_base64: &str, tx: &broadcast::Sender<MarketUpdate>, scoring_engine: Arc<PoolScoringEngine>) {
+async fn handle_account_update(
+    pool_addr: &str,
+    data_base64: &str,
+    tx: &broadcast::Sender<MarketUpdate>,
+    scoring_engine: Arc<PoolScoringEngine>,
+    order_books: &OrderBookCache,
+) {
     use base64::{Engine as _, engine::general_purpose};
     use solana_sdk::pubkey::Pubkey;
     use std::str::FromStr;
@@ -266,24 +454,80 @@ async fn handle_account_update(pool_addr: &str, data_base64: &str, tx: &broadcas
         // Update pool weight (Activity Bonus)
         scoring_engine.update_activity(pool_pub);
 
-        let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64;
-        
-        if bytes.len() == 653 { // Orca
-            let whirlpool: &mev_core::orca::Whirlpool = unsafe { &*(bytes.as_ptr() as *const mev_core::orca::Whirlpool) };
-            let _ = tx.send(MarketUpdate {
-                pool_address: pool_pub, program_id: ORCA_WHIRLPOOL_PROGRAM,
-                coin_mint: whirlpool.token_mint_a(), pc_mint: whirlpool.token_mint_b(),
-                coin_reserve: 0, pc_reserve: 0, price_sqrt: Some(whirlpool.sqrt_price()), liquidity: Some(whirlpool.liquidity()),
-                timestamp: ts,
-            });
-        } else if bytes.len() == 752 { // Raydium
-            let amm: &mev_core::raydium::AmmInfo = unsafe { &*(bytes.as_ptr() as *const mev_core::raydium::AmmInfo) };
-            let _ = tx.send(MarketUpdate {
-                pool_address: pool_pub, program_id: RAYDIUM_V4_PROGRAM,
-                coin_mint: amm.base_mint(), pc_mint: amm.quote_mint(),
-                coin_reserve: amm.base_reserve(), pc_reserve: amm.quote_reserve(),
-                price_sqrt: None, liquidity: None, timestamp: ts,
-            });
+        let ts = mev_core::clock::system().unix_secs() as i64;
+        if let Some(update) = decode_pool_account(pool_pub, &bytes, ts, order_books) {
+            let _ = tx.send(update);
         }
     }
 }
+
+/// Raw pool account -> MarketUpdate, told apart by account size (shared with the Geyser backend).
+/// CLOB markets also refresh their book in `order_books`, which is what their hops quote from.
+pub(crate) fn decode_pool_account(
+    pool_pub: solana_sdk::pubkey::Pubkey,
+    bytes: &[u8],
+    ts: i64,
+    order_books: &OrderBookCache,
+) -> Option<MarketUpdate> {
+    if bytes.len() == 653 { // Orca
+        let whirlpool: mev_core::orca::Whirlpool = bytemuck::try_pod_read_unaligned(bytes).ok()?;
+        Some(MarketUpdate {
+            pool_address: pool_pub, program_id: ORCA_WHIRLPOOL_PROGRAM,
+            coin_mint: whirlpool.token_mint_a(), pc_mint: whirlpool.token_mint_b(),
+            coin_reserve: 0, pc_reserve: 0, price_sqrt: Some(whirlpool.sqrt_price()), liquidity: Some(whirlpool.liquidity()),
+            fee_bps: Some(whirlpool.fee_bps()), timestamp: ts, trigger: mev_core::Trigger::AccountUpdate,
+        })
+    } else if bytes.len() == 752 { // Raydium
+        let amm: mev_core::raydium::AmmInfo = bytemuck::try_pod_read_unaligned(bytes).ok()?;
+        Some(MarketUpdate {
+            pool_address: pool_pub, program_id: RAYDIUM_V4_PROGRAM,
+            coin_mint: amm.base_mint(), pc_mint: amm.quote_mint(),
+            coin_reserve: amm.base_reserve(), pc_reserve: amm.quote_reserve(),
+            price_sqrt: None, liquidity: None, fee_bps: None, timestamp: ts, trigger: mev_core::Trigger::AccountUpdate,
+        })
+    } else if bytes.len() == mev_core::raydium_clmm::POOL_STATE_LEN { // Raydium CLMM
+        let pool: mev_core::raydium_clmm::PoolState = bytemuck::try_pod_read_unaligned(bytes).ok()?;
+        Some(MarketUpdate {
+            pool_address: pool_pub, program_id: mev_core::constants::RAYDIUM_CLMM_PROGRAM,
+            coin_mint: pool.token_mint_0(), pc_mint: pool.token_mint_1(),
+            coin_reserve: 0, pc_reserve: 0, price_sqrt: Some(pool.sqrt_price_x64()), liquidity: Some(pool.liquidity()),
+            fee_bps: None, timestamp: ts, trigger: mev_core::Trigger::AccountUpdate,
+        })
+    } else if mev_core::pump_fun::CURVE_ACCOUNT_LENS.contains(&bytes.len()) { // Pump.fun curve
+        let curve = mev_core::pump_fun::PumpFunBondingCurve::from_account_data(&bytes[8..]).ok()?;
+        // The curve doesn't store its mint; the worker fills it in from the hydrated graph state
+        Some(curve.to_market_update(pool_pub, solana_sdk::pubkey::Pubkey::default(), ts))
+    } else if bytes.len() == mev_core::meteora::LB_PAIR_LEN { // Meteora DLMM
+        let pair: mev_core::meteora::MeteoraDLMM = bytemuck::try_pod_read_unaligned(bytes).ok()?;
+        // Bins live in separate accounts; the strategy quotes from the bin cache
+        Some(MarketUpdate {
+            pool_address: pool_pub, program_id: METEORA_PROGRAM_ID,
+            coin_mint: pair.token_x_mint(), pc_mint: pair.token_y_mint(),
+            coin_reserve: 0, pc_reserve: 0, price_sqrt: None, liquidity: None,
+            fee_bps: None, timestamp: ts, trigger: mev_core::Trigger::AccountUpdate,
+        })
+    } else if let Ok(market) = mev_core::phoenix::PhoenixMarket::from_account_data(bytes) { // Phoenix (size from its header)
+        let book = market.order_book(mev_core::clock::system().slot(), ts.max(0) as u64);
+        // Reserves are the book at its mid price, for spot rates; hops quote the book itself
+        let (coin_reserve, pc_reserve) = book.virtual_reserves();
+        order_books.update(pool_pub, book);
+        Some(MarketUpdate {
+            pool_address: pool_pub, program_id: PHOENIX_PROGRAM,
+            coin_mint: market.base_mint, pc_mint: market.quote_mint,
+            coin_reserve, pc_reserve, price_sqrt: None, liquidity: None,
+            fee_bps: None, timestamp: ts, trigger: mev_core::Trigger::AccountUpdate,
+        })
+    } else if bytes.len() == mev_core::openbook::MARKET_LEN { // OpenBook v2
+        let market = mev_core::openbook::OpenBookMarket::from_account_data(bytes).ok()?;
+        // Orders live in the book side accounts, refreshed by the worker; price off the last book read
+        let (coin_reserve, pc_reserve) = order_books.get(&pool_pub).map_or((0, 0), |book| book.virtual_reserves());
+        Some(MarketUpdate {
+            pool_address: pool_pub, program_id: OPENBOOK_V2_PROGRAM,
+            coin_mint: market.base_mint, pc_mint: market.quote_mint,
+            coin_reserve, pc_reserve, price_sqrt: None, liquidity: None,
+            fee_bps: None, timestamp: ts, trigger: mev_core::Trigger::AccountUpdate,
+        })
+    } else {
+        None
+    }
+}
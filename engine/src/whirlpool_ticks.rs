@@ -0,0 +1,78 @@
+/// Orca Whirlpool Tick Refresh
+///
+/// A Whirlpool account carries the price and the liquidity of the current tick
+/// only; where that liquidity ends lives in tick array accounts the pool
+/// subscription never sees. Every pool update schedules a re-read of the three
+/// arrays a swap instruction passes (current, previous, next), and the strategy
+/// walks the latest set when quoting the pool.
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::clmm::{TickArray, TickRange};
+use mev_core::orca::{OrcaSwapKeys, Whirlpool};
+use strategy::StrategyEngine;
+
+const WHIRLPOOL_LEN: usize = 653;
+
+pub struct WhirlpoolTickFetcher {
+    rpc: RpcClient,
+    engine: Arc<StrategyEngine>,
+    in_flight: Mutex<HashSet<Pubkey>>,
+}
+
+impl WhirlpoolTickFetcher {
+    pub fn new(rpc_url: &str, engine: Arc<StrategyEngine>) -> Self {
+        Self {
+            rpc: RpcClient::new(rpc_url.to_string()),
+            engine,
+            in_flight: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Schedules a tick refresh for `pool` unless one is already running
+    pub fn request(self: &Arc<Self>, pool: Pubkey) {
+        if !self.in_flight.lock().unwrap_or_else(|e| e.into_inner()).insert(pool) {
+            return;
+        }
+        let fetcher = Arc::clone(self);
+        tokio::spawn(async move {
+            match fetcher.refresh(&pool).await {
+                Ok(ticks) => tracing::debug!("🌀 Whirlpool {} refreshed with {} initialized tick(s)", pool, ticks),
+                Err(e) => tracing::debug!("🌀 Whirlpool tick refresh failed for {}: {}", pool, e),
+            }
+            fetcher.in_flight.lock().unwrap_or_else(|e| e.into_inner()).remove(&pool);
+        });
+    }
+
+    /// Re-reads the pool and its tick arrays and hands them to the strategy.
+    /// Returns how many initialized ticks were loaded.
+    pub async fn refresh(&self, pool: &Pubkey) -> anyhow::Result<usize> {
+        let account = self.rpc.get_account(pool).await?;
+        let bytes = account.data.get(..WHIRLPOOL_LEN).ok_or_else(|| anyhow::anyhow!("Not a Whirlpool: {} bytes", account.data.len()))?;
+        let whirlpool: Whirlpool = bytemuck::try_pod_read_unaligned(bytes).map_err(|e| anyhow::anyhow!("Bad Whirlpool layout: {:?}", e))?;
+
+        let spacing = whirlpool.tick_spacing();
+        let span = OrcaSwapKeys::TICKS_PER_ARRAY * spacing as i32;
+        let start = OrcaSwapKeys::get_tick_array_start_index(whirlpool.tick_current_index(), spacing);
+        let keys: Vec<Pubkey> = [start - span, start, start + span]
+            .iter()
+            .map(|&index| OrcaSwapKeys::derive_tick_array_pda(pool, index, &account.owner))
+            .collect();
+        let arrays: Vec<Option<TickArray>> = self.rpc.get_multiple_accounts(&keys).await?
+            .into_iter()
+            .map(|account| account.and_then(|account| TickArray::decode(&account.data)))
+            .collect();
+
+        // The loaded range has to be contiguous and hold the price: neighbours are optional, the current array is not
+        let [previous, Some(current), next]: [Option<TickArray>; 3] = arrays.try_into().map_err(|_| anyhow::anyhow!("RPC returned the wrong number of tick arrays"))? else {
+            anyhow::bail!("Current tick array {} is not initialized", start);
+        };
+        let loaded: Vec<TickArray> = previous.into_iter().chain(std::iter::once(current)).chain(next).collect();
+
+        let ticks = TickRange::from_arrays(&loaded, spacing);
+        let initialized = ticks.ticks.len();
+        self.engine.update_whirlpool_ticks(*pool, ticks);
+        Ok(initialized)
+    }
+}
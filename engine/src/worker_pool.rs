@@ -0,0 +1,261 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio::time::Duration;
+use tracing::{info, warn};
+use mev_core::MarketUpdate;
+use solana_sdk::pubkey::Pubkey;
+
+/// Bounded hand-off between the broadcast fan-out and the worker pool (per lane)
+pub const WORK_QUEUE_CAPACITY: usize = 1024;
+
+/// Producer side of the two-tier work queue. Updates for pools quoted in a
+/// priority mint (SOL majors anchor most cycles) go to the priority lane;
+/// everything else goes to the bulk lane, which sheds when full rather than
+/// holding the priority lane up behind it.
+#[derive(Clone)]
+pub struct WorkSender {
+    priority: mpsc::Sender<MarketUpdate>,
+    bulk: mpsc::Sender<MarketUpdate>,
+    priority_mints: Arc<HashSet<Pubkey>>,
+}
+
+/// Consumer side: workers always drain the priority lane first
+pub struct WorkQueue {
+    priority: mpsc::Receiver<MarketUpdate>,
+    bulk: mpsc::Receiver<MarketUpdate>,
+}
+
+pub fn work_queue(priority_mints: HashSet<Pubkey>) -> (WorkSender, WorkQueue) {
+    let (priority_tx, priority_rx) = mpsc::channel(WORK_QUEUE_CAPACITY);
+    let (bulk_tx, bulk_rx) = mpsc::channel(WORK_QUEUE_CAPACITY);
+    (
+        WorkSender { priority: priority_tx, bulk: bulk_tx, priority_mints: Arc::new(priority_mints) },
+        WorkQueue { priority: priority_rx, bulk: bulk_rx },
+    )
+}
+
+impl WorkSender {
+    pub fn is_priority(&self, update: &MarketUpdate) -> bool {
+        self.priority_mints.contains(&update.coin_mint) || self.priority_mints.contains(&update.pc_mint)
+    }
+
+    /// Queues `update` on its lane. Errs once the workers are gone.
+    pub async fn send(&self, update: MarketUpdate) -> Result<(), ()> {
+        if self.is_priority(&update) {
+            return self.priority.send(update).await.map_err(|_| ());
+        }
+        match self.bulk.try_send(update) {
+            Ok(()) => Ok(()),
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                crate::telemetry::WORK_QUEUE_SHED_TOTAL.inc();
+                Ok(())
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => Err(()),
+        }
+    }
+
+    /// Queues `update` on the priority lane regardless of its mints (back-run triggers)
+    pub async fn send_priority(&self, update: MarketUpdate) -> Result<(), ()> {
+        self.priority.send(update).await.map_err(|_| ())
+    }
+
+    /// Events waiting in the (priority, bulk) lanes
+    pub fn depth(&self) -> (usize, usize) {
+        (
+            self.priority.max_capacity() - self.priority.capacity(),
+            self.bulk.max_capacity() - self.bulk.capacity(),
+        )
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.priority.is_closed() && self.bulk.is_closed()
+    }
+}
+
+impl WorkQueue {
+    /// Next event, priority lane first; None once both lanes are closed and drained
+    pub async fn recv(&mut self) -> Option<MarketUpdate> {
+        tokio::select! {
+            biased;
+            Some(update) = self.priority.recv() => Some(update),
+            Some(update) = self.bulk.recv() => Some(update),
+            else => None,
+        }
+    }
+}
+
+const SCALE_UP_STEP: usize = 2;
+const QUIET_TICKS_BEFORE_PARK: u32 = 5; // ~5s of empty queue before parking a worker
+
+/// Decides how many workers should be pulling from the work queue.
+/// Workers above the target park on a watch channel instead of exiting,
+/// so scaling back up is instant.
+pub struct WorkerScaler {
+    min_workers: usize,
+    max_workers: usize,
+    target_tx: watch::Sender<usize>,
+    lagged: AtomicU64,
+}
+
+impl WorkerScaler {
+    pub fn new(min_workers: usize, max_workers: usize) -> Self {
+        let min_workers = min_workers.max(1);
+        let max_workers = max_workers.max(min_workers);
+        let (target_tx, _) = watch::channel(min_workers);
+        Self { min_workers, max_workers, target_tx, lagged: AtomicU64::new(0) }
+    }
+
+    pub fn max_workers(&self) -> usize {
+        self.max_workers
+    }
+
+    pub fn target(&self) -> usize {
+        *self.target_tx.borrow()
+    }
+
+    /// Broadcast receivers report dropped events here
+    pub fn record_lag(&self, skipped: u64) {
+        self.lagged.fetch_add(skipped, Ordering::Relaxed);
+        crate::telemetry::BROADCAST_LAGGED_TOTAL.inc_by(skipped as f64);
+    }
+
+    /// Parks worker `id` until the target includes it
+    pub async fn wait_until_active(&self, id: usize) {
+        let mut rx = self.target_tx.subscribe();
+        while id >= *rx.borrow_and_update() {
+            if rx.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Pure scaling rule: grow fast under pressure, shrink slowly when idle
+    pub fn next_target(&self, current: usize, queue_depth: usize, lagged: u64, quiet_ticks: u32) -> usize {
+        let under_pressure = lagged > 0 || queue_depth * 2 >= WORK_QUEUE_CAPACITY || queue_depth > current * 4;
+        if under_pressure {
+            (current + SCALE_UP_STEP).min(self.max_workers)
+        } else if queue_depth == 0 && quiet_ticks >= QUIET_TICKS_BEFORE_PARK {
+            current.saturating_sub(1).max(self.min_workers)
+        } else {
+            current
+        }
+    }
+
+    /// Background loop sampling queue depth and broadcast lag once per second
+    pub async fn run(self: Arc<Self>, work_tx: WorkSender) {
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+        let mut quiet_ticks: u32 = 0;
+        info!("⚖️ Worker autoscaler ONLINE ({}..={} workers)", self.min_workers, self.max_workers);
+
+        loop {
+            interval.tick().await;
+            if work_tx.is_closed() {
+                return;
+            }
+
+            let (priority_depth, bulk_depth) = work_tx.depth();
+            let depth = priority_depth + bulk_depth;
+            let lagged = self.lagged.swap(0, Ordering::Relaxed);
+            quiet_ticks = if depth == 0 && lagged == 0 { quiet_ticks.saturating_add(1) } else { 0 };
+
+            crate::telemetry::WORKER_QUEUE_DEPTH.set(depth as i64);
+            crate::telemetry::WORKER_PRIORITY_QUEUE_DEPTH.set(priority_depth as i64);
+
+            let current = self.target();
+            let next = self.next_target(current, depth, lagged, quiet_ticks);
+            if next != current {
+                if next > current {
+                    info!("⚖️ Scaling workers {} -> {} (queue: {}, lagged: {})", current, next, depth, lagged);
+                } else {
+                    info!("⚖️ Parking worker {} -> {} (queue idle)", current, next);
+                    quiet_ticks = 0;
+                }
+                self.target_tx.send_replace(next);
+            }
+            crate::telemetry::WORKERS_ACTIVE.set(next as i64);
+        }
+    }
+}
+
+/// Moves events from the broadcast bus into the shared work queue
+pub async fn dispatch(
+    mut market_rx: broadcast::Receiver<MarketUpdate>,
+    work_tx: WorkSender,
+    scaler: Arc<WorkerScaler>,
+) {
+    loop {
+        match market_rx.recv().await {
+            Ok(event) => {
+                if work_tx.send(event).await.is_err() {
+                    return;
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("🐢 Dispatcher lagged, {} market events dropped", skipped);
+                scaler.record_lag(skipped);
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scales_up_on_lag_and_caps_at_max() {
+        let scaler = WorkerScaler::new(2, 8);
+        assert_eq!(scaler.next_target(2, 0, 10, 0), 4);
+        assert_eq!(scaler.next_target(7, 0, 1, 0), 8);
+        assert_eq!(scaler.next_target(8, WORK_QUEUE_CAPACITY, 0, 0), 8);
+    }
+
+    #[test]
+    fn test_scales_up_on_deep_queue() {
+        let scaler = WorkerScaler::new(2, 8);
+        assert_eq!(scaler.next_target(2, 9, 0, 0), 4);
+        assert_eq!(scaler.next_target(2, 3, 0, 0), 2);
+    }
+
+    #[test]
+    fn test_parks_only_after_sustained_quiet() {
+        let scaler = WorkerScaler::new(2, 8);
+        assert_eq!(scaler.next_target(6, 0, 0, 1), 6);
+        assert_eq!(scaler.next_target(6, 0, 0, QUIET_TICKS_BEFORE_PARK), 5);
+        assert_eq!(scaler.next_target(2, 0, 0, QUIET_TICKS_BEFORE_PARK), 2);
+    }
+
+    #[tokio::test]
+    async fn test_priority_lane_preempts_and_bulk_sheds() {
+        let sol = mev_core::constants::SOL_MINT;
+        let update = |coin_mint, pc_mint| MarketUpdate {
+            pool_address: Pubkey::new_unique(),
+            program_id: Pubkey::default(),
+            coin_mint,
+            pc_mint,
+            coin_reserve: 0,
+            pc_reserve: 0,
+            price_sqrt: None,
+            liquidity: None,
+            fee_bps: None,
+            timestamp: 0,
+            trigger: mev_core::Trigger::AccountUpdate,
+        };
+        let (tx, mut rx) = work_queue(HashSet::from([sol]));
+
+        // Fill the bulk lane; one more long-tail update is dropped, not waited on
+        for _ in 0..=WORK_QUEUE_CAPACITY {
+            tx.send(update(Pubkey::new_unique(), Pubkey::new_unique())).await.unwrap();
+        }
+        assert_eq!(tx.depth(), (0, WORK_QUEUE_CAPACITY));
+
+        let major = update(Pubkey::new_unique(), sol);
+        tx.send(major.clone()).await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().pool_address, major.pool_address);
+        assert_eq!(tx.depth(), (0, WORK_QUEUE_CAPACITY));
+        assert!(!tx.is_priority(&rx.recv().await.unwrap()));
+    }
+}
@@ -0,0 +1,211 @@
+/// Redundant WebSocket Feeds
+///
+/// One MarketWatcher runs per WebSocket endpoint, all subscribed to the same
+/// pools and programs, so a provider that silently stops pushing doesn't
+/// blind the bot. Every watcher reports through the shared `FeedMonitor`:
+/// an account notification goes on only if it is the first copy of that pool
+/// write (same slot and same data from another endpoint is a duplicate), and
+/// log notifications only on a signature's first sighting. Each endpoint's
+/// last slot is tracked from its `slotSubscribe` stream; when one falls
+/// behind the others past the divergence limit, an alert goes out.
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use dashmap::DashMap;
+use lru::LruCache;
+use tokio::sync::mpsc;
+use crate::alerts::{AlertManager, AlertSeverity, Field};
+
+/// Signatures remembered for log deduplication
+const SEEN_SIGNATURES: usize = 20_000;
+
+/// Latest accepted write of one pool
+struct PoolWrite {
+    slot: u64,
+    /// Data hashes already forwarded for `slot` (a pool can be written more than once per slot)
+    hashes: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EndpointState {
+    slot: u64,
+    last_message: Option<Instant>,
+}
+
+pub struct FeedMonitor {
+    endpoints: usize,
+    pool_writes: DashMap<String, PoolWrite>,
+    signatures: Mutex<LruCache<String, ()>>,
+    state: Mutex<Vec<EndpointState>>,
+    /// Slots an endpoint may trail the leader before it counts as diverged
+    max_lag_slots: u64,
+}
+
+/// An endpoint trailing the leading one past the limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divergence {
+    pub endpoint: usize,
+    pub lag_slots: u64,
+}
+
+impl FeedMonitor {
+    pub fn new(endpoints: usize, max_lag_slots: u64) -> Self {
+        Self {
+            endpoints,
+            pool_writes: DashMap::new(),
+            signatures: Mutex::new(LruCache::new(NonZeroUsize::new(SEEN_SIGNATURES).unwrap())),
+            state: Mutex::new(vec![EndpointState { slot: 0, last_message: None }; endpoints]),
+            max_lag_slots,
+        }
+    }
+
+    pub fn endpoints(&self) -> usize {
+        self.endpoints
+    }
+
+    /// Any message from the endpoint; feeds the staleness gauge
+    pub fn touch(&self, endpoint: usize) {
+        if let Some(state) = self.state.lock().unwrap().get_mut(endpoint) {
+            state.last_message = Some(Instant::now());
+        }
+    }
+
+    /// Slot from the endpoint's `slotNotification` stream
+    pub fn observe_slot(&self, endpoint: usize, slot: u64) {
+        if let Some(state) = self.state.lock().unwrap().get_mut(endpoint) {
+            state.slot = state.slot.max(slot);
+        }
+        crate::telemetry::WS_ENDPOINT_SLOT.with_label_values(&[&endpoint.to_string()]).set(slot as i64);
+    }
+
+    /// True for the first copy of a pool write across all endpoints. Writes from an older
+    /// slot than one already forwarded are stale and dropped too.
+    pub fn accept_account(&self, endpoint: usize, pool: &str, slot: u64, data: &str) -> bool {
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let mut write = self.pool_writes.entry(pool.to_string()).or_insert(PoolWrite { slot, hashes: Vec::new() });
+        let accepted = if slot < write.slot || (slot == write.slot && write.hashes.contains(&hash)) {
+            false
+        } else {
+            if slot > write.slot {
+                *write = PoolWrite { slot, hashes: Vec::new() };
+            }
+            write.hashes.push(hash);
+            true
+        };
+        drop(write);
+        let result = if accepted { "first" } else { "duplicate" };
+        crate::telemetry::WS_NOTIFICATIONS.with_label_values(&[&endpoint.to_string(), result]).inc();
+        accepted
+    }
+
+    /// True the first time any endpoint delivers `key` (a signature, optionally scoped to a pool)
+    pub fn accept_signature(&self, key: &str) -> bool {
+        self.signatures.lock().unwrap().put(key.to_string(), ()).is_none()
+    }
+
+    pub fn forget_pool(&self, pool: &str) {
+        self.pool_writes.remove(pool);
+    }
+
+    /// Endpoints trailing the leader by more than the limit. A single endpoint can't diverge.
+    pub fn divergences(&self) -> Vec<Divergence> {
+        let state = self.state.lock().unwrap();
+        let leader = state.iter().map(|s| s.slot).max().unwrap_or(0);
+        if state.len() < 2 || leader == 0 {
+            return Vec::new();
+        }
+        state.iter().enumerate()
+            .map(|(endpoint, s)| Divergence { endpoint, lag_slots: leader - s.slot })
+            .filter(|d| d.lag_slots > self.max_lag_slots)
+            .collect()
+    }
+
+    /// Publishes staleness and alerts when endpoints drift apart or come back together
+    pub async fn run(self: Arc<Self>, alerts: Arc<AlertManager>, urls: Vec<String>, poll: Duration) {
+        let mut interval = tokio::time::interval(poll);
+        let mut diverged: HashMap<usize, u64> = HashMap::new();
+        loop {
+            interval.tick().await;
+            let now = Instant::now();
+            for (endpoint, state) in self.state.lock().unwrap().iter().enumerate() {
+                let stale_ms = state.last_message.map_or(-1, |t| now.duration_since(t).as_millis() as i64);
+                crate::telemetry::WS_ENDPOINT_STALENESS_MS.with_label_values(&[&endpoint.to_string()]).set(stale_ms);
+            }
+
+            let current: HashMap<usize, u64> = self.divergences().into_iter().map(|d| (d.endpoint, d.lag_slots)).collect();
+            for (&endpoint, &lag) in current.iter().filter(|(e, _)| !diverged.contains_key(e)) {
+                let host = urls.get(endpoint).map(|u| redact(u)).unwrap_or_default();
+                tracing::warn!("📡 WS endpoint {} ({}) is {} slots behind the leading feed", endpoint, host, lag);
+                alerts.send_alert(
+                    AlertSeverity::Warning,
+                    "WebSocket Feeds Diverged",
+                    &format!("Endpoint {} trails the leading feed by {} slots; its updates are being outrun by the others.", endpoint, lag),
+                    vec![
+                        Field { name: "Endpoint".to_string(), value: host, inline: true },
+                        Field { name: "Lag".to_string(), value: format!("{} slots", lag), inline: true },
+                    ],
+                ).await;
+            }
+            for endpoint in diverged.keys().filter(|e| !current.contains_key(e)) {
+                tracing::info!("📡 WS endpoint {} caught up with the other feeds", endpoint);
+            }
+            diverged = current;
+        }
+    }
+}
+
+/// Host only; provider URLs often carry an API key
+fn redact(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, r)| r);
+    let host = rest.split(['/', '?']).next().unwrap_or(rest);
+    host.to_string()
+}
+
+/// Copies every message from `rx` to `n` receivers, one per watcher
+pub fn fan_out<T: Clone + Send + 'static>(mut rx: mpsc::UnboundedReceiver<T>, n: usize) -> Vec<mpsc::UnboundedReceiver<T>> {
+    let (senders, receivers): (Vec<_>, Vec<_>) = (0..n).map(|_| mpsc::unbounded_channel()).unzip();
+    tokio::spawn(async move {
+        while let Some(item) = rx.recv().await {
+            for tx in &senders {
+                let _ = tx.send(item.clone());
+            }
+        }
+    });
+    receivers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedups_across_endpoints_and_flags_lagging_feed() {
+        let feed = FeedMonitor::new(2, 10);
+        let pool = "58oQChGsNrtmhaJSRph38tB3BwpL66F42FMa86Fv3Gry";
+
+        // The same write from both providers goes on once
+        assert!(feed.accept_account(0, pool, 100, "AAAA"));
+        assert!(!feed.accept_account(1, pool, 100, "AAAA"));
+        // A second write in the same slot is new data, an older slot is stale
+        assert!(feed.accept_account(1, pool, 100, "BBBB"));
+        assert!(!feed.accept_account(0, pool, 99, "CCCC"));
+        assert!(feed.accept_account(0, pool, 101, "AAAA"));
+
+        assert!(feed.accept_signature("5sig"));
+        assert!(!feed.accept_signature("5sig"));
+
+        feed.observe_slot(0, 1_000);
+        feed.observe_slot(1, 995);
+        assert!(feed.divergences().is_empty());
+        feed.observe_slot(0, 1_020);
+        assert_eq!(feed.divergences(), vec![Divergence { endpoint: 1, lag_slots: 25 }]);
+        assert_eq!(redact("wss://mainnet.helius-rpc.com/?api-key=secret"), "mainnet.helius-rpc.com");
+    }
+}
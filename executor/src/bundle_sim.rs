@@ -0,0 +1,212 @@
+/// Bundle Simulation
+///
+/// `BundleSimulator` adapters for the pipeline's `simulation` gate. `rpc`
+/// runs the bundle's transaction through `simulateTransaction` on the RPC
+/// pool; `jito` posts it to a Jito-enabled RPC's `simulateBundle`, which
+/// executes it the way the block engine would. Both skip signature checks
+/// and replace the blockhash, so nothing is signed here. Every run records
+/// the compute units it used and, when it fails, a coarse reason.
+use std::sync::Arc;
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::{v0, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use strategy::ports::BundleSimulator;
+use crate::http::HttpClient;
+use crate::rpc_pool::RpcPool;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulationBackend {
+    Rpc,
+    Jito,
+}
+
+impl SimulationBackend {
+    /// `BUNDLE_SIMULATION`: `off` (or empty), `rpc` or `jito`
+    pub fn parse(name: &str) -> Result<Option<Self>, String> {
+        match name.trim().to_lowercase().as_str() {
+            "" | "off" => Ok(None),
+            "rpc" => Ok(Some(SimulationBackend::Rpc)),
+            "jito" => Ok(Some(SimulationBackend::Jito)),
+            other => Err(format!("unknown simulation backend '{}' (off, rpc, jito)", other)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SimulationBackend::Rpc => "rpc",
+            SimulationBackend::Jito => "jito",
+        }
+    }
+}
+
+/// Coarse label for a failed simulation, for the failure-reason metric
+pub fn failure_reason(error: &str) -> &'static str {
+    let any = |needles: &[&str]| needles.iter().any(|n| error.contains(n));
+    if any(&["InsufficientFunds", "insufficient funds", "insufficient lamports"]) {
+        "insufficient_funds"
+    } else if any(&["AccountNotFound", "could not find account", "InvalidAccountData"]) {
+        "account"
+    } else if any(&["ComputationalBudgetExceeded", "exceeded CUs", "ProgramFailedToComplete"]) {
+        "compute_budget"
+    } else if any(&["Custom(", "custom program error"]) {
+        "program_error"
+    } else if error.contains("InstructionError") {
+        "instruction_error"
+    } else {
+        "other"
+    }
+}
+
+/// The bundle as one unsigned v0 transaction; signature slots are zeroed
+fn unsigned_tx(instructions: &[Instruction], payer: &Pubkey) -> anyhow::Result<VersionedTransaction> {
+    let message = v0::Message::try_compile(payer, instructions, &[], Hash::default())?;
+    let signatures = vec![Signature::default(); message.header.num_required_signatures as usize];
+    Ok(VersionedTransaction { signatures, message: VersionedMessage::V0(message) })
+}
+
+/// What the simulation said: units consumed, or the bundle's error. Failing to ask at all is the `Err` around it.
+enum Outcome {
+    Succeeded(u64),
+    Failed(String),
+}
+
+pub struct ChainSimulator {
+    backend: SimulationBackend,
+    rpc: Arc<RpcPool>,
+    http: Arc<HttpClient>,
+    jito_url: String,
+}
+
+impl ChainSimulator {
+    /// `jito_url` answers `simulateBundle`; only read by the `jito` backend
+    pub fn new(backend: SimulationBackend, rpc: Arc<RpcPool>, http: Arc<HttpClient>, jito_url: &str) -> Self {
+        Self { backend, rpc, http, jito_url: jito_url.to_string() }
+    }
+
+    async fn simulate_rpc(&self, tx: &VersionedTransaction) -> anyhow::Result<Outcome> {
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(CommitmentConfig::processed()),
+            ..Default::default()
+        };
+        let response = self.rpc.call(|c| {
+            let config = config.clone();
+            async move { c.simulate_transaction_with_config(tx, config).await }
+        }).await?;
+        Ok(match response.value.err {
+            None => Outcome::Succeeded(response.value.units_consumed.unwrap_or(0)),
+            Some(err) => Outcome::Failed(format!("{:?}", err)),
+        })
+    }
+
+    async fn simulate_jito(&self, tx: &VersionedTransaction) -> anyhow::Result<Outcome> {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(bincode::serialize(tx)?);
+        let request = self.http.post(&self.jito_url).json(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "simulateBundle",
+            "params": [
+                { "encodedTransactions": [encoded] },
+                {
+                    "transactionEncoding": "base64",
+                    "skipSigVerify": true,
+                    "replaceRecentBlockhash": true,
+                    "preExecutionAccountsConfigs": [null],
+                    "postExecutionAccountsConfigs": [null],
+                },
+            ],
+        }));
+        let body: Value = self.http.send(request).await?.error_for_status()?.json().await?;
+        simulate_bundle_outcome(&body)
+    }
+}
+
+/// Reads a `simulateBundle` response: `summary` is `"succeeded"` or `{"failed": {...}}`
+fn simulate_bundle_outcome(body: &Value) -> anyhow::Result<Outcome> {
+    if let Some(error) = body.get("error") {
+        return Err(anyhow::anyhow!("simulateBundle error: {}", error));
+    }
+    let value = body.pointer("/result/value")
+        .ok_or_else(|| anyhow::anyhow!("simulateBundle response has no result"))?;
+    let units: u64 = value.get("transactionResults")
+        .and_then(Value::as_array)
+        .map(|results| results.iter().filter_map(|r| r.get("unitsConsumed").and_then(Value::as_u64)).sum())
+        .unwrap_or(0);
+    match value.get("summary") {
+        Some(Value::String(s)) if s == "succeeded" => Ok(Outcome::Succeeded(units)),
+        Some(summary) => {
+            let error = summary.pointer("/failed/error").unwrap_or(summary);
+            Ok(Outcome::Failed(error.to_string()))
+        }
+        None => Err(anyhow::anyhow!("simulateBundle response has no summary")),
+    }
+}
+
+#[async_trait::async_trait]
+impl BundleSimulator for ChainSimulator {
+    async fn simulate_bundle(&self, instructions: &[Instruction], payer: &Pubkey) -> Result<u64, String> {
+        let backend = self.backend.name();
+        let outcome = match unsigned_tx(instructions, payer) {
+            Ok(tx) => match self.backend {
+                SimulationBackend::Rpc => self.simulate_rpc(&tx).await,
+                SimulationBackend::Jito => self.simulate_jito(&tx).await,
+            },
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(Outcome::Succeeded(units)) => {
+                mev_core::telemetry::BUNDLE_SIMULATIONS.with_label_values(&[backend, "ok"]).inc();
+                mev_core::telemetry::BUNDLE_SIMULATION_UNITS.with_label_values(&[backend]).observe(units as f64);
+                Ok(units)
+            }
+            Ok(Outcome::Failed(error)) => {
+                mev_core::telemetry::BUNDLE_SIMULATIONS.with_label_values(&[backend, "failed"]).inc();
+                mev_core::telemetry::BUNDLE_SIMULATION_FAILURES.with_label_values(&[backend, failure_reason(&error)]).inc();
+                Err(error)
+            }
+            Err(e) => {
+                mev_core::telemetry::BUNDLE_SIMULATIONS.with_label_values(&[backend, "error"]).inc();
+                Err(format!("{} simulation unavailable: {}", backend, e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_simulate_bundle_outcomes() {
+        let ok = json!({"result": {"value": {"summary": "succeeded", "transactionResults": [{"err": null, "unitsConsumed": 84_000}]}}});
+        assert!(matches!(simulate_bundle_outcome(&ok), Ok(Outcome::Succeeded(84_000))));
+
+        let failed = json!({"result": {"value": {
+            "summary": {"failed": {"error": {"TransactionFailure": [[1, 2], "InstructionError(2, Custom(6001))"]}, "tx_signature": "x"}},
+            "transactionResults": [],
+        }}});
+        match simulate_bundle_outcome(&failed) {
+            Ok(Outcome::Failed(error)) => assert_eq!(failure_reason(&error), "program_error"),
+            _ => panic!("expected a failed simulation"),
+        }
+        assert!(simulate_bundle_outcome(&json!({"error": {"code": -32601, "message": "Method not found"}})).is_err());
+
+        assert_eq!(failure_reason("InstructionError(0, InsufficientFunds)"), "insufficient_funds");
+        assert_eq!(SimulationBackend::parse(" Jito ").unwrap(), Some(SimulationBackend::Jito));
+        assert_eq!(SimulationBackend::parse("").unwrap(), None);
+        assert!(SimulationBackend::parse("anvil").is_err());
+
+        // Unsigned: one zeroed signature slot for the payer
+        let payer = Pubkey::new_unique();
+        let tx = unsigned_tx(&[solana_sdk::system_instruction::transfer(&payer, &Pubkey::new_unique(), 1)], &payer).unwrap();
+        assert_eq!(tx.signatures, vec![Signature::default()]);
+    }
+}
@@ -0,0 +1,210 @@
+/// Jito bundle status tracking
+///
+/// `send_bundle_no_wait` only hands back a bundle id. The tracker follows each
+/// id through the block engine's JSON-RPC (`getInflightBundleStatuses`, then
+/// `getBundleStatuses` once it leaves the in-flight window) until it lands or is
+/// dropped, and reports the outcome with the endpoint and tip it was sent with.
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use serde_json::Value;
+use strategy::ports::TelemetryPort;
+use crate::http::HttpClient;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Bundles still pending after this long are counted as expired
+const RESOLVE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Status reported by `getInflightBundleStatuses` (last 5 minutes only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InflightStatus {
+    Pending,
+    Landed { slot: u64 },
+    Failed,
+    /// Unknown to the block engine: rejected on receipt, or outside the look-back window
+    Invalid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleOutcome {
+    Landed { slot: u64 },
+    Failed,
+    Invalid,
+    Expired,
+}
+
+impl BundleOutcome {
+    pub fn landed(&self) -> bool {
+        matches!(self, BundleOutcome::Landed { .. })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BundleOutcome::Landed { .. } => "landed",
+            BundleOutcome::Failed => "failed",
+            BundleOutcome::Invalid => "invalid",
+            BundleOutcome::Expired => "expired",
+        }
+    }
+}
+
+/// A submitted bundle awaiting its final status
+#[derive(Debug, Clone)]
+pub struct TrackedBundle {
+    pub bundle_id: String,
+    pub endpoint_index: usize,
+    /// Block engine base URL the bundle was sent to
+    pub endpoint_url: String,
+    pub tip_lamports: u64,
+}
+
+/// Status of `bundle_id` in a `getInflightBundleStatuses` response
+pub fn parse_inflight_status(response: &Value, bundle_id: &str) -> Option<InflightStatus> {
+    let entry = find_entry(response, bundle_id)?;
+    match entry.get("status")?.as_str()? {
+        "Pending" => Some(InflightStatus::Pending),
+        "Failed" => Some(InflightStatus::Failed),
+        "Invalid" => Some(InflightStatus::Invalid),
+        "Landed" => Some(InflightStatus::Landed {
+            slot: entry.get("landed_slot").and_then(Value::as_u64).unwrap_or(0),
+        }),
+        _ => None,
+    }
+}
+
+/// Landed slot of `bundle_id` in a `getBundleStatuses` response, if it landed without error
+pub fn parse_landed_slot(response: &Value, bundle_id: &str) -> Option<u64> {
+    let entry = find_entry(response, bundle_id)?;
+    let errored = entry.get("err")
+        .and_then(|e| e.as_object())
+        .is_some_and(|e| !e.contains_key("Ok"));
+    if errored {
+        return None;
+    }
+    entry.get("slot").and_then(Value::as_u64)
+}
+
+fn find_entry<'a>(response: &'a Value, bundle_id: &str) -> Option<&'a Value> {
+    response.get("result")?
+        .get("value")?
+        .as_array()?
+        .iter()
+        .find(|entry| entry.get("bundle_id").and_then(Value::as_str) == Some(bundle_id))
+}
+
+pub struct BundleTracker {
+    http: Arc<HttpClient>,
+    telemetry: Option<Arc<dyn TelemetryPort>>,
+}
+
+impl BundleTracker {
+    pub fn new(http: Arc<HttpClient>, telemetry: Option<Arc<dyn TelemetryPort>>) -> Self {
+        Self { http, telemetry }
+    }
+
+    /// Follows `bundle` in the background and records its outcome
+    pub fn track(self: &Arc<Self>, bundle: TrackedBundle) {
+        let tracker = Arc::clone(self);
+        tokio::spawn(async move {
+            let outcome = tracker.resolve(&bundle).await;
+            tracker.record(&bundle, outcome);
+        });
+    }
+
+    async fn resolve(&self, bundle: &TrackedBundle) -> BundleOutcome {
+        let started = Instant::now();
+        while started.elapsed() < RESOLVE_TIMEOUT {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            match self.rpc(bundle, "getInflightBundleStatuses").await {
+                Ok(response) => match parse_inflight_status(&response, &bundle.bundle_id) {
+                    Some(InflightStatus::Landed { slot }) => return BundleOutcome::Landed { slot },
+                    // Invalid also covers ids the engine hasn't indexed yet; give it a few polls
+                    Some(InflightStatus::Invalid) if started.elapsed() < POLL_INTERVAL * 3 => continue,
+                    Some(InflightStatus::Failed) => return self.confirm(bundle, BundleOutcome::Failed).await,
+                    Some(InflightStatus::Invalid) => return self.confirm(bundle, BundleOutcome::Invalid).await,
+                    Some(InflightStatus::Pending) | None => continue,
+                },
+                Err(e) => tracing::debug!("⚠️ Bundle status poll failed for {}: {}", bundle.bundle_id, e),
+            }
+        }
+        self.confirm(bundle, BundleOutcome::Expired).await
+    }
+
+    /// The in-flight view only spans a few minutes; `getBundleStatuses` is the record of what landed
+    async fn confirm(&self, bundle: &TrackedBundle, dropped: BundleOutcome) -> BundleOutcome {
+        match self.rpc(bundle, "getBundleStatuses").await {
+            Ok(response) => parse_landed_slot(&response, &bundle.bundle_id)
+                .map(|slot| BundleOutcome::Landed { slot })
+                .unwrap_or(dropped),
+            Err(_) => dropped,
+        }
+    }
+
+    async fn rpc(&self, bundle: &TrackedBundle, method: &str) -> anyhow::Result<Value> {
+        let url = format!("{}/api/v1/{}", bundle.endpoint_url.trim_end_matches('/'), method);
+        let payload = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": [[bundle.bundle_id]],
+        });
+        Ok(self.http.send(self.http.post(&url).json(&payload)).await?.json::<Value>().await?)
+    }
+
+    fn record(&self, bundle: &TrackedBundle, outcome: BundleOutcome) {
+        match outcome {
+            BundleOutcome::Landed { slot } => tracing::info!("📦 Bundle {} landed in slot {} (endpoint {}, tip {} lamports)",
+                bundle.bundle_id, slot, bundle.endpoint_index + 1, bundle.tip_lamports),
+            _ => tracing::warn!("📦 Bundle {} dropped: {} (endpoint {}, tip {} lamports)",
+                bundle.bundle_id, outcome.as_str(), bundle.endpoint_index + 1, bundle.tip_lamports),
+        }
+        mev_core::telemetry::JITO_BUNDLE_OUTCOMES
+            .with_label_values(&[&bundle.endpoint_index.to_string(), outcome.as_str()])
+            .inc();
+        mev_core::telemetry::JITO_BUNDLE_TIP_LAMPORTS
+            .with_label_values(&[if outcome.landed() { "landed" } else { "dropped" }])
+            .observe(bundle.tip_lamports as f64);
+        if let Some(tel) = &self.telemetry {
+            tel.log_bundle_outcome(bundle.endpoint_index, bundle.tip_lamports, outcome.landed());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_status_parsing() {
+        let inflight = serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": {
+                "context": { "slot": 280_999_028u64 },
+                "value": [
+                    { "bundle_id": "a", "status": "Pending", "landed_slot": null },
+                    { "bundle_id": "b", "status": "Landed", "landed_slot": 280_999_000u64 },
+                    { "bundle_id": "c", "status": "Invalid", "landed_slot": null },
+                ]
+            },
+            "id": 1
+        });
+        assert_eq!(parse_inflight_status(&inflight, "a"), Some(InflightStatus::Pending));
+        assert_eq!(parse_inflight_status(&inflight, "b"), Some(InflightStatus::Landed { slot: 280_999_000 }));
+        assert_eq!(parse_inflight_status(&inflight, "c"), Some(InflightStatus::Invalid));
+        assert_eq!(parse_inflight_status(&inflight, "d"), None);
+
+        let statuses = serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": {
+                "context": { "slot": 242_806_119u64 },
+                "value": [
+                    { "bundle_id": "b", "transactions": ["sig"], "slot": 242_804_011u64,
+                      "confirmation_status": "finalized", "err": { "Ok": null } },
+                    null,
+                ]
+            },
+            "id": 1
+        });
+        assert_eq!(parse_landed_slot(&statuses, "b"), Some(242_804_011));
+        assert_eq!(parse_landed_slot(&statuses, "a"), None);
+    }
+}
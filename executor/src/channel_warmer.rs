@@ -0,0 +1,155 @@
+/// Jito channel warm-up
+///
+/// A block engine channel left idle between opportunities goes cold: a load
+/// balancer drops it and the next bundle pays a fresh TCP, TLS and HTTP/2
+/// handshake. Channels are opened with HTTP/2 keepalive pings. An endpoint
+/// with no traffic for `warmup_interval` gets a cheap getTipAccounts call to
+/// keep the path hot. One that has carried no bundle for `max_idle` is
+/// reconnected in the background, as is one whose warm-up call fails, so a
+/// bundle doesn't find a stale channel. Connection age is exported per endpoint.
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use jito_protos::searcher::searcher_service_client::SearcherServiceClient;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+
+const HTTP2_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(10);
+const HTTP2_KEEPALIVE_TIMEOUT: Duration = Duration::from_secs(5);
+const TCP_KEEPALIVE: Duration = Duration::from_secs(30);
+
+/// Opens a searcher channel that keeps itself alive with HTTP/2 pings, even with no call in flight
+pub async fn connect(url: &str) -> anyhow::Result<SearcherServiceClient<Channel>> {
+    let mut endpoint = Endpoint::from_shared(url.to_string())?
+        .http2_keep_alive_interval(HTTP2_KEEPALIVE_INTERVAL)
+        .keep_alive_timeout(HTTP2_KEEPALIVE_TIMEOUT)
+        .keep_alive_while_idle(true)
+        .tcp_keepalive(Some(TCP_KEEPALIVE))
+        .tcp_nodelay(true);
+    if url.starts_with("https") {
+        endpoint = endpoint.tls_config(ClientTlsConfig::new())?;
+    }
+    Ok(SearcherServiceClient::new(endpoint.connect().await?))
+}
+
+/// Connects to one block engine and asks for its tip accounts; returns how many it advertised
+pub async fn ping_block_engine(url: &str) -> anyhow::Result<usize> {
+    let mut client = connect(url).await
+        .map_err(|e| anyhow::anyhow!("connect failed: {}", e))?;
+    let probe = client.get_tip_accounts(jito_protos::searcher::GetTipAccountsRequest {});
+    let response = tokio::time::timeout(Duration::from_secs(5), probe).await
+        .map_err(|_| anyhow::anyhow!("getTipAccounts timed out"))?
+        .map_err(|e| anyhow::anyhow!("getTipAccounts failed: {}", e))?;
+    Ok(response.into_inner().accounts.len())
+}
+
+#[derive(Debug, Clone)]
+pub struct WarmupPolicy {
+    /// No-op call on an endpoint with no traffic for this long
+    pub warmup_interval: Duration,
+    /// Reconnect an endpoint that carried no bundle for this long; zero never does
+    pub max_idle: Duration,
+}
+
+impl Default for WarmupPolicy {
+    fn default() -> Self {
+        Self {
+            warmup_interval: Duration::from_secs(15),
+            max_idle: Duration::from_secs(120),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarmupAction {
+    Ping,
+    Reconnect,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Connection {
+    connected_at: Instant,
+    last_bundle: Instant,
+    /// Last warm-up call or reconnect attempt
+    last_attempt: Instant,
+}
+
+pub struct ChannelWarmer {
+    policy: WarmupPolicy,
+    connections: Mutex<Vec<Connection>>,
+}
+
+impl ChannelWarmer {
+    pub fn new(endpoint_count: usize, policy: WarmupPolicy, now: Instant) -> Self {
+        let fresh = Connection { connected_at: now, last_bundle: now, last_attempt: now };
+        Self { policy, connections: Mutex::new(vec![fresh; endpoint_count]) }
+    }
+
+    /// A bundle went out on the endpoint
+    pub fn touch(&self, index: usize, now: Instant) {
+        if let Some(conn) = self.connections.lock().unwrap().get_mut(index) {
+            conn.last_bundle = now;
+        }
+    }
+
+    /// A warm-up call or reconnect was tried; the next waits a full warm-up interval
+    pub fn attempted(&self, index: usize, now: Instant) {
+        if let Some(conn) = self.connections.lock().unwrap().get_mut(index) {
+            conn.last_attempt = now;
+        }
+    }
+
+    pub fn reconnected(&self, index: usize, now: Instant) {
+        if let Some(conn) = self.connections.lock().unwrap().get_mut(index) {
+            *conn = Connection { connected_at: now, last_bundle: now, last_attempt: now };
+        }
+    }
+
+    pub fn age(&self, index: usize, now: Instant) -> Duration {
+        self.connections.lock().unwrap().get(index).map_or(Duration::ZERO, |conn| now.duration_since(conn.connected_at))
+    }
+
+    /// What the endpoint needs now, if anything
+    pub fn action(&self, index: usize, now: Instant) -> Option<WarmupAction> {
+        let connections = self.connections.lock().unwrap();
+        let conn = connections.get(index)?;
+        let quiet = now.duration_since(conn.last_bundle.max(conn.last_attempt));
+        if quiet < self.policy.warmup_interval {
+            return None;
+        }
+        let max_idle = self.policy.max_idle;
+        let stale = !max_idle.is_zero()
+            && now.duration_since(conn.last_bundle) >= max_idle
+            && now.duration_since(conn.connected_at) >= max_idle;
+        Some(if stale { WarmupAction::Reconnect } else { WarmupAction::Ping })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_channels_are_pinged_then_recycled() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let warmer = ChannelWarmer::new(2, WarmupPolicy { warmup_interval: Duration::from_secs(15), max_idle: Duration::from_secs(60) }, start);
+
+        assert_eq!(warmer.action(0, at(10)), None);
+        assert_eq!(warmer.action(0, at(15)), Some(WarmupAction::Ping));
+
+        // A bundle resets the clock; a warm-up call waits out its own interval
+        warmer.touch(1, at(14));
+        assert_eq!(warmer.action(1, at(20)), None);
+        warmer.attempted(0, at(15));
+        assert_eq!(warmer.action(0, at(29)), None);
+
+        // No bundle for a minute: reconnect, then start over
+        assert_eq!(warmer.action(0, at(60)), Some(WarmupAction::Reconnect));
+        warmer.reconnected(0, at(61));
+        assert_eq!(warmer.age(0, at(70)), Duration::from_secs(9));
+        assert_eq!(warmer.action(0, at(80)), Some(WarmupAction::Ping));
+
+        let never = ChannelWarmer::new(1, WarmupPolicy { max_idle: Duration::ZERO, ..Default::default() }, start);
+        assert_eq!(never.action(0, at(3_600)), Some(WarmupAction::Ping));
+        assert_eq!(warmer.action(5, at(100)), None);
+    }
+}
@@ -0,0 +1,102 @@
+/// Compute budget sizing for bundle transactions
+///
+/// The limit comes from simulating the finished transaction plus a safety
+/// margin; when simulation is off or fails, the measured per-venue profiles
+/// (see `cu_profile`) stand in. The
+/// CU price is the network estimate, lowered if needed so that limit × price
+/// stays inside the per-transaction priority fee budget. A tighter limit
+/// therefore buys a higher price for the same fee.
+
+/// Runtime ceiling for one transaction
+pub const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+
+#[derive(Debug, Clone)]
+pub struct ComputeBudgetPolicy {
+    /// Simulate each bundle transaction to measure its CU use
+    pub simulate: bool,
+    /// Headroom on top of the measured units
+    pub margin_bps: u32,
+    /// Fixed cost of compute budget, tip and ATA instructions
+    pub base_units: u32,
+    /// Estimate per instruction for a venue with no measurements yet
+    pub units_per_hop: u32,
+    /// Most the priority fee may cost per transaction (lamports)
+    pub max_priority_fee_lamports: u64,
+}
+
+impl Default for ComputeBudgetPolicy {
+    fn default() -> Self {
+        Self {
+            simulate: true,
+            margin_bps: 1_000,
+            base_units: 20_000,
+            units_per_hop: 80_000,
+            max_priority_fee_lamports: 100_000,
+        }
+    }
+}
+
+/// Limit and price to put in the transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComputeBudget {
+    pub unit_limit: u32,
+    /// Micro-lamports per CU
+    pub unit_price: u64,
+}
+
+impl ComputeBudget {
+    pub fn priority_fee_lamports(&self) -> u64 {
+        (self.unit_limit as u128 * self.unit_price as u128 / 1_000_000) as u64
+    }
+}
+
+impl ComputeBudgetPolicy {
+    /// Flat estimate for `hops` swaps on venues with no profile
+    pub fn hop_estimate(&self, hops: usize) -> u64 {
+        self.base_units as u64 + self.units_per_hop as u64 * hops as u64
+    }
+
+    /// Limit for a transaction that consumed `simulated_units`, or `estimated_units` when unsimulated
+    pub fn unit_limit(&self, simulated_units: Option<u64>, estimated_units: u64) -> u32 {
+        let units = match simulated_units {
+            Some(units) if units > 0 => units,
+            _ => estimated_units,
+        };
+        let with_margin = units * (10_000 + self.margin_bps as u64) / 10_000;
+        with_margin.min(MAX_COMPUTE_UNITS as u64) as u32
+    }
+
+    /// Estimated price, capped so the whole limit fits the fee budget
+    pub fn plan(&self, simulated_units: Option<u64>, estimated_units: u64, estimated_price: u64) -> ComputeBudget {
+        let unit_limit = self.unit_limit(simulated_units, estimated_units);
+        let affordable = (self.max_priority_fee_lamports as u128 * 1_000_000 / unit_limit.max(1) as u128) as u64;
+        ComputeBudget {
+            unit_limit,
+            unit_price: estimated_price.min(affordable),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_tracks_simulation_and_caps_fee() {
+        let policy = ComputeBudgetPolicy::default();
+
+        // Measured: 180k + 10% margin
+        let budget = policy.plan(Some(180_000), policy.hop_estimate(2), 1_000);
+        assert_eq!(budget.unit_limit, 198_000);
+        assert_eq!(budget.unit_price, 1_000);
+
+        // Unmeasured 5-hop route gets more than the old flat 250k
+        assert_eq!(policy.unit_limit(None, policy.hop_estimate(5)), 462_000);
+        assert_eq!(policy.unit_limit(Some(2_000_000), policy.hop_estimate(2)), MAX_COMPUTE_UNITS);
+
+        // A hot estimate is cut back to the 100k lamport budget
+        let budget = policy.plan(Some(180_000), policy.hop_estimate(2), 10_000_000);
+        assert_eq!(budget.unit_price, 505_050);
+        assert!(budget.priority_fee_lamports() <= policy.max_priority_fee_lamports);
+    }
+}
@@ -0,0 +1,118 @@
+/// Per-Venue Compute-Unit Profiles
+///
+/// A Phoenix take order and a Raydium V4 swap differ several-fold in CU, so
+/// one flat per-hop figure either starves heavy routes or overpays for light
+/// ones. Every measurement (a budget simulation or a landed transaction's
+/// `computeUnitsConsumed`) updates a moving average per program; a bundle's
+/// estimate is the base cost plus the profile of each instruction it carries.
+/// A measurement covers the whole transaction, so the part above the base
+/// cost is split across its instructions in proportion to their current
+/// estimates. Single-venue routes, the common case, are attributed exactly.
+use std::collections::HashMap;
+use std::sync::RwLock;
+use solana_sdk::pubkey::Pubkey;
+
+/// Weight of a new measurement once a profile has settled
+const EMA_ALPHA: f64 = 0.2;
+
+#[derive(Debug, Clone, Copy)]
+struct Profile {
+    units: f64,
+    samples: u64,
+}
+
+pub struct CuProfiles {
+    /// Compute budget, tip and other fixed instructions
+    base_units: u32,
+    /// Estimate for a program never measured
+    default_units: u32,
+    profiles: RwLock<HashMap<Pubkey, Profile>>,
+}
+
+impl CuProfiles {
+    pub fn new(base_units: u32, default_units: u32) -> Self {
+        Self { base_units, default_units, profiles: RwLock::new(HashMap::new()) }
+    }
+
+    /// Expected CU of one instruction of `program`
+    pub fn units(&self, program: &Pubkey) -> u32 {
+        self.profiles.read().unwrap().get(program).map_or(self.default_units, |p| p.units.round() as u32)
+    }
+
+    /// Expected CU of a transaction carrying one instruction per entry of `programs`
+    pub fn estimate(&self, programs: &[Pubkey]) -> u64 {
+        let profiles = self.profiles.read().unwrap();
+        self.base_units as u64 + programs.iter()
+            .map(|p| profiles.get(p).map_or(self.default_units as f64, |p| p.units).round() as u64)
+            .sum::<u64>()
+    }
+
+    /// Folds in a transaction that consumed `units_consumed` with one instruction per entry of `programs`
+    pub fn observe(&self, programs: &[Pubkey], units_consumed: u64) {
+        if programs.is_empty() {
+            return;
+        }
+        let mut profiles = self.profiles.write().unwrap();
+        let current: Vec<f64> = programs.iter()
+            .map(|p| profiles.get(p).map_or(self.default_units as f64, |p| p.units))
+            .collect();
+        let total: f64 = current.iter().sum();
+        let variable = units_consumed.saturating_sub(self.base_units as u64) as f64;
+
+        // Each instruction's share of the measured units, merged per program
+        let mut shares: HashMap<Pubkey, (f64, u32)> = HashMap::new();
+        for (program, estimate) in programs.iter().zip(&current) {
+            let share = if total > 0.0 { variable * estimate / total } else { variable / programs.len() as f64 };
+            let entry = shares.entry(*program).or_insert((0.0, 0));
+            entry.0 += share;
+            entry.1 += 1;
+        }
+        for (program, (units, count)) in shares {
+            let sample = units / count as f64;
+            let profile = profiles.entry(program).or_insert(Profile { units: sample, samples: 0 });
+            // Plain mean while the profile is young, so the default doesn't linger
+            let alpha = (1.0 / (profile.samples + 1) as f64).max(EMA_ALPHA);
+            profile.units += alpha * (sample - profile.units);
+            profile.samples += 1;
+            mev_core::telemetry::COMPUTE_UNITS_PER_IX
+                .with_label_values(&[&program.to_string()])
+                .set(profile.units.round() as i64);
+        }
+    }
+
+    /// (program, CU per instruction, samples) for every measured program
+    pub fn snapshot(&self) -> Vec<(Pubkey, u32, u64)> {
+        let mut rows: Vec<_> = self.profiles.read().unwrap().iter()
+            .map(|(program, p)| (*program, p.units.round() as u32, p.samples))
+            .collect();
+        rows.sort_by_key(|(_, _, samples)| std::cmp::Reverse(*samples));
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_learn_per_venue_costs() {
+        let profiles = CuProfiles::new(20_000, 80_000);
+        let (raydium, phoenix) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_eq!(profiles.estimate(&[raydium, raydium]), 180_000);
+
+        // Single-venue routes are attributed exactly
+        profiles.observe(&[raydium, raydium], 20_000 + 2 * 30_000);
+        assert_eq!(profiles.units(&raydium), 30_000);
+        profiles.observe(&[phoenix], 20_000 + 120_000);
+        assert_eq!(profiles.units(&phoenix), 120_000);
+
+        // A mixed route now budgets for what each leg actually costs
+        assert_eq!(profiles.estimate(&[raydium, phoenix]), 170_000);
+
+        // Mixed measurements split in proportion to the current estimates
+        profiles.observe(&[raydium, phoenix], 20_000 + 300_000);
+        assert!(profiles.units(&phoenix) > 120_000);
+        assert!(profiles.units(&raydium) > 30_000);
+        assert_eq!(profiles.snapshot().len(), 2);
+    }
+}
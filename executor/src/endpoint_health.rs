@@ -0,0 +1,142 @@
+/// Jito endpoint health
+///
+/// Every submission attempt is scored against the endpoint it went to. Once an
+/// endpoint's success rate over the window drops below the floor it is benched:
+/// the round-robin skips it and a background probe pings it until it answers,
+/// instead of each bundle burning its retries on a dead block engine first.
+/// The last endpoint still in rotation is never benched.
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct EndpointHealthPolicy {
+    /// How far back submission outcomes count
+    pub window: Duration,
+    /// Attempts needed in the window before an endpoint can be judged
+    pub min_samples: usize,
+    /// Bench below this success rate; 0 disables benching
+    pub min_success_rate: f64,
+    /// How often benched endpoints are pinged
+    pub probe_interval: Duration,
+}
+
+impl Default for EndpointHealthPolicy {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(300),
+            min_samples: 10,
+            min_success_rate: 0.5,
+            probe_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Endpoint {
+    benched: bool,
+    /// (attempt time, succeeded), oldest first
+    outcomes: VecDeque<(Instant, bool)>,
+}
+
+pub struct EndpointHealth {
+    policy: EndpointHealthPolicy,
+    endpoints: Mutex<Vec<Endpoint>>,
+}
+
+impl EndpointHealth {
+    pub fn new(endpoint_count: usize, policy: EndpointHealthPolicy) -> Self {
+        Self {
+            policy,
+            endpoints: Mutex::new((0..endpoint_count).map(|_| Endpoint::default()).collect()),
+        }
+    }
+
+    pub fn policy(&self) -> &EndpointHealthPolicy {
+        &self.policy
+    }
+
+    pub fn is_active(&self, index: usize) -> bool {
+        self.endpoints.lock().unwrap().get(index).is_some_and(|e| !e.benched)
+    }
+
+    pub fn benched(&self) -> Vec<usize> {
+        self.endpoints.lock().unwrap().iter().enumerate()
+            .filter(|(_, e)| e.benched)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Scores one submission attempt. Returns true if it got the endpoint benched.
+    pub fn record(&self, index: usize, success: bool, now: Instant) -> bool {
+        let mut endpoints = self.endpoints.lock().unwrap();
+        let active = endpoints.iter().filter(|e| !e.benched).count();
+        let Some(endpoint) = endpoints.get_mut(index) else {
+            return false;
+        };
+        endpoint.outcomes.push_back((now, success));
+        while endpoint.outcomes.front().is_some_and(|(t, _)| now.duration_since(*t) > self.policy.window) {
+            endpoint.outcomes.pop_front();
+        }
+
+        if endpoint.benched || active <= 1 || self.policy.min_success_rate <= 0.0 {
+            return false;
+        }
+        if endpoint.outcomes.len() < self.policy.min_samples {
+            return false;
+        }
+        let successes = endpoint.outcomes.iter().filter(|(_, ok)| *ok).count();
+        if (successes as f64 / endpoint.outcomes.len() as f64) < self.policy.min_success_rate {
+            endpoint.benched = true;
+            return true;
+        }
+        false
+    }
+
+    /// A probe got through: back into rotation with a clean window
+    pub fn reinstate(&self, index: usize) {
+        if let Some(endpoint) = self.endpoints.lock().unwrap().get_mut(index) {
+            endpoint.benched = false;
+            endpoint.outcomes.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failing_endpoint_benched_until_reinstated() {
+        let policy = EndpointHealthPolicy { min_samples: 4, ..Default::default() };
+        let health = EndpointHealth::new(2, policy);
+        let start = Instant::now();
+
+        // Too few samples to judge
+        for _ in 0..3 {
+            assert!(!health.record(0, false, start));
+        }
+        assert!(health.record(0, false, start));
+        assert!(!health.is_active(0));
+        assert_eq!(health.benched(), vec![0]);
+
+        // The remaining endpoint stays in rotation however badly it does
+        for _ in 0..10 {
+            assert!(!health.record(1, false, start));
+        }
+        assert!(health.is_active(1));
+
+        health.reinstate(0);
+        assert!(health.is_active(0));
+
+        // Failures that age out of the window no longer count
+        for _ in 0..3 {
+            health.record(0, false, start);
+        }
+        for _ in 0..3 {
+            health.record(0, true, start + Duration::from_secs(301));
+        }
+        assert!(!health.record(0, false, start + Duration::from_secs(302)));
+        assert!(health.is_active(0));
+    }
+}
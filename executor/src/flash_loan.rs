@@ -1,164 +1,174 @@
+use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
+    pubkey,
     pubkey::Pubkey,
-    transaction::Transaction,
+    sysvar,
 };
-use std::error::Error;
+use std::collections::HashMap;
+use strategy::ports::FlashLoanProvider;
+
+pub const SOLEND_PROGRAM_ID: Pubkey = pubkey!("So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo");
+
+const FLASH_BORROW_RESERVE_LIQUIDITY: u8 = 19;
+const FLASH_REPAY_RESERVE_LIQUIDITY: u8 = 20;
+
+// Offsets into the Solend `Reserve` account
+const LENDING_MARKET_OFFSET: usize = 10;
+const LIQUIDITY_MINT_OFFSET: usize = 42;
+const LIQUIDITY_SUPPLY_OFFSET: usize = 75;
+const FLASH_LOAN_FEE_WAD_OFFSET: usize = 314;
+const FEE_RECEIVER_OFFSET: usize = 339;
+const RESERVE_MIN_LEN: usize = FEE_RECEIVER_OFFSET + 32;
+
+/// 1 WAD = 100%
+const WAD_PER_BPS: u64 = 100_000_000_000_000;
+
+/// The accounts of one Solend reserve needed to borrow and repay its liquidity
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolendReserve {
+    pub address: Pubkey,
+    pub lending_market: Pubkey,
+    pub liquidity_mint: Pubkey,
+    pub liquidity_supply: Pubkey,
+    pub fee_receiver: Pubkey,
+    pub flash_loan_fee_bps: u16,
+}
+
+impl SolendReserve {
+    pub fn from_account_data(address: Pubkey, data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < RESERVE_MIN_LEN {
+            return Err(anyhow::anyhow!("Reserve {} too short ({} bytes)", address, data.len()));
+        }
+        let key = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).expect("32-byte slice");
+        let fee_wad = u64::from_le_bytes(data[FLASH_LOAN_FEE_WAD_OFFSET..FLASH_LOAN_FEE_WAD_OFFSET + 8].try_into()?);
+        Ok(Self {
+            address,
+            lending_market: key(LENDING_MARKET_OFFSET),
+            liquidity_mint: key(LIQUIDITY_MINT_OFFSET),
+            liquidity_supply: key(LIQUIDITY_SUPPLY_OFFSET),
+            fee_receiver: key(FEE_RECEIVER_OFFSET),
+            flash_loan_fee_bps: fee_wad.div_ceil(WAD_PER_BPS).min(u16::MAX as u64) as u16,
+        })
+    }
+}
 
-/// Flash loan executor for Solend protocol
+/// Flash loans from Solend reserves
 /// Enables capital-free arbitrage by borrowing and repaying within same transaction
-pub struct FlashLoanExecutor {
-    solend_program_id: Pubkey,
-    lending_market: Pubkey,
+pub struct SolendFlashLoanProvider {
+    program_id: Pubkey,
+    reserves: HashMap<Pubkey, SolendReserve>,  // By liquidity mint
 }
 
-impl FlashLoanExecutor {
-    pub fn new(solend_program_id: Pubkey, lending_market: Pubkey) -> Self {
+impl SolendFlashLoanProvider {
+    pub fn new(program_id: Pubkey, reserves: Vec<SolendReserve>) -> Self {
         Self {
-            solend_program_id,
-            lending_market,
+            program_id,
+            reserves: reserves.into_iter().map(|r| (r.liquidity_mint, r)).collect(),
         }
     }
 
-    /// Build a flash loan transaction with arbitrage instructions
-    /// Transaction structure:
-    /// 1. Flash borrow X tokens
-    /// 2. Execute arbitrage swaps
-    /// 3. Flash repay X tokens + fee
-    pub fn build_flash_loan_transaction(
-        &self,
-        borrow_amount: u64,
-        token_mint: &Pubkey,
-        reserve: &Pubkey,
-        user_token_account: &Pubkey,
-        arb_instructions: Vec<Instruction>,
-    ) -> Result<Vec<Instruction>, Box<dyn Error>> {
-        let mut instructions = Vec::new();
-
-        // 1. Flash borrow instruction
-        let borrow_ix = self.build_flash_borrow_ix(
-            borrow_amount,
-            token_mint,
-            reserve,
-            user_token_account,
-        )?;
-        instructions.push(borrow_ix);
-
-        // 2. Arbitrage instructions (swaps across DEXs)
-        instructions.extend(arb_instructions);
-
-        // 3. Flash repay instruction
-        let repay_ix = self.build_flash_repay_ix(
-            borrow_amount,
-            token_mint,
-            reserve,
-            user_token_account,
-        )?;
-        instructions.push(repay_ix);
+    /// Reads the given reserve accounts from chain
+    pub fn load(rpc: &RpcClient, program_id: Pubkey, reserve_addresses: &[Pubkey]) -> anyhow::Result<Self> {
+        let accounts = rpc.get_multiple_accounts(reserve_addresses)?;
+        let mut reserves = Vec::with_capacity(reserve_addresses.len());
+        for (address, account) in reserve_addresses.iter().zip(accounts) {
+            let account = account.ok_or_else(|| anyhow::anyhow!("Reserve {} not found", address))?;
+            if account.owner != program_id {
+                return Err(anyhow::anyhow!("Reserve {} is not owned by {}", address, program_id));
+            }
+            reserves.push(SolendReserve::from_account_data(*address, &account.data)?);
+        }
+        Ok(Self::new(program_id, reserves))
+    }
 
-        Ok(instructions)
+    pub fn reserves(&self) -> impl Iterator<Item = &SolendReserve> {
+        self.reserves.values()
     }
 
-    fn build_flash_borrow_ix(
-        &self,
-        amount: u64,
-        token_mint: &Pubkey,
-        reserve: &Pubkey,
-        destination: &Pubkey,
-    ) -> Result<Instruction, Box<dyn Error>> {
-        // Solend flash borrow instruction
-        // This is a simplified structure - real implementation needs proper account ordering
-        
-        let accounts = vec![
-            AccountMeta::new(*reserve, false),
-            AccountMeta::new(*destination, false),
-            AccountMeta::new_readonly(*token_mint, false),
-            AccountMeta::new(self.lending_market, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ];
-
-        // Instruction data: [instruction_type, amount]
-        // Flash borrow is usually instruction discriminator 13 for Solend
-        let mut data = vec![13u8]; // Flash borrow discriminator
+    fn lending_market_authority(&self, lending_market: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[lending_market.as_ref()], &self.program_id).0
+    }
+
+    fn build_flash_borrow_ix(&self, reserve: &SolendReserve, amount: u64, destination: &Pubkey) -> Instruction {
+        let mut data = vec![FLASH_BORROW_RESERVE_LIQUIDITY];
         data.extend_from_slice(&amount.to_le_bytes());
 
-        Ok(Instruction {
-            program_id: self.solend_program_id,
-            accounts,
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(reserve.liquidity_supply, false),
+                AccountMeta::new(*destination, false),
+                AccountMeta::new(reserve.address, false),
+                AccountMeta::new_readonly(reserve.lending_market, false),
+                AccountMeta::new_readonly(self.lending_market_authority(&reserve.lending_market), false),
+                AccountMeta::new_readonly(sysvar::instructions::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
             data,
-        })
+        }
     }
 
-    fn build_flash_repay_ix(
-        &self,
-        amount: u64,
-        token_mint: &Pubkey,
-        reserve: &Pubkey,
-        source: &Pubkey,
-    ) -> Result<Instruction, Box<dyn Error>> {
-        // Solend flash repay instruction
-        // Must happen in same transaction as borrow
-        
-        let accounts = vec![
-            AccountMeta::new(*reserve, false),
-            AccountMeta::new(*source, false),
-            AccountMeta::new_readonly(*token_mint, false),
-            AccountMeta::new(self.lending_market, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ];
-
-        // Calculate repay amount (borrow + 0.3% flash loan fee)
-        let fee = amount * 3 / 1000;
-        let repay_amount = amount + fee;
-
-        // Instruction data: [instruction_type, repay_amount]
-        // Flash repay is usually instruction discriminator 14 for Solend
-        let mut data = vec![14u8]; // Flash repay discriminator
-        data.extend_from_slice(&repay_amount.to_le_bytes());
-
-        Ok(Instruction {
-            program_id: self.solend_program_id,
-            accounts,
+    /// Repays `amount`; the program adds the fee itself and checks the borrow at `borrow_index`
+    fn build_flash_repay_ix(&self, reserve: &SolendReserve, amount: u64, source: &Pubkey, authority: &Pubkey, borrow_index: u8) -> Instruction {
+        let mut data = vec![FLASH_REPAY_RESERVE_LIQUIDITY];
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.push(borrow_index);
+
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(*source, false),
+                AccountMeta::new(reserve.liquidity_supply, false),
+                AccountMeta::new(reserve.fee_receiver, false),
+                AccountMeta::new(*source, false), // No host: host fee goes back to us
+                AccountMeta::new(reserve.address, false),
+                AccountMeta::new_readonly(reserve.lending_market, false),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new_readonly(sysvar::instructions::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
             data,
-        })
-    }
-
-    /// Calculate optimal borrow amount based on pool depths
-    pub fn calculate_optimal_borrow(
-        &self,
-        pool_liquidity: u64,
-        min_profit_threshold: u64,
-    ) -> u64 {
-        // Use 90% of available liquidity to avoid slippage
-        let max_safe_borrow = (pool_liquidity as f64 * 0.9) as u64;
-        
-        // Factor in flash loan fee (0.3%)
-        let fee_adjusted = (max_safe_borrow as f64 * 0.997) as u64;
-        
-        // Ensure we can make minimum profit after fees
-        if fee_adjusted > min_profit_threshold {
-            fee_adjusted
-        } else {
-            0 // Not profitable
         }
     }
 }
 
-/// Flash loan opportunity detection
-pub struct FlashLoanOpportunity {
-    pub borrow_amount: u64,
-    pub token_mint: Pubkey,
-    pub expected_profit: u64,
-    pub path: Vec<Pubkey>, // DEX pools in arbitrage path
-}
+impl FlashLoanProvider for SolendFlashLoanProvider {
+    fn name(&self) -> &'static str {
+        "solend"
+    }
 
-impl FlashLoanOpportunity {
-    pub fn is_profitable(&self, min_profit: u64) -> bool {
-        self.expected_profit > min_profit
+    fn supports(&self, mint: &Pubkey) -> bool {
+        self.reserves.contains_key(mint)
     }
 
-    pub fn profit_percentage(&self) -> f64 {
-        (self.expected_profit as f64 / self.borrow_amount as f64) * 100.0
+    fn fee_bps(&self, mint: &Pubkey) -> u16 {
+        self.reserves.get(mint).map(|r| r.flash_loan_fee_bps).unwrap_or(u16::MAX)
+    }
+
+    /// Transaction structure:
+    /// 1. Flash borrow X tokens into the borrower's ATA
+    /// 2. Execute arbitrage swaps
+    /// 3. Flash repay X tokens + fee
+    fn wrap(
+        &self,
+        borrower: &Pubkey,
+        mint: &Pubkey,
+        amount: u64,
+        trade_ixs: Vec<Instruction>,
+        borrow_index: usize,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        let reserve = self.reserves.get(mint)
+            .ok_or_else(|| anyhow::anyhow!("No Solend reserve for mint {}", mint))?;
+        let borrow_index = u8::try_from(borrow_index)
+            .map_err(|_| anyhow::anyhow!("Borrow instruction index {} out of range", borrow_index))?;
+        let user_ata = spl_associated_token_account::get_associated_token_address(borrower, mint);
+
+        let mut instructions = Vec::with_capacity(trade_ixs.len() + 2);
+        instructions.push(self.build_flash_borrow_ix(reserve, amount, &user_ata));
+        instructions.extend(trade_ixs);
+        instructions.push(self.build_flash_repay_ix(reserve, amount, &user_ata, borrower, borrow_index));
+        Ok(instructions)
     }
 }
 
@@ -166,18 +176,33 @@ impl FlashLoanOpportunity {
 mod tests {
     use super::*;
 
+    fn reserve_data(market: Pubkey, mint: Pubkey, supply: Pubkey, fee_receiver: Pubkey, fee_wad: u64) -> Vec<u8> {
+        let mut data = vec![0u8; 619];
+        data[LENDING_MARKET_OFFSET..LENDING_MARKET_OFFSET + 32].copy_from_slice(market.as_ref());
+        data[LIQUIDITY_MINT_OFFSET..LIQUIDITY_MINT_OFFSET + 32].copy_from_slice(mint.as_ref());
+        data[LIQUIDITY_SUPPLY_OFFSET..LIQUIDITY_SUPPLY_OFFSET + 32].copy_from_slice(supply.as_ref());
+        data[FLASH_LOAN_FEE_WAD_OFFSET..FLASH_LOAN_FEE_WAD_OFFSET + 8].copy_from_slice(&fee_wad.to_le_bytes());
+        data[FEE_RECEIVER_OFFSET..FEE_RECEIVER_OFFSET + 32].copy_from_slice(fee_receiver.as_ref());
+        data
+    }
+
     #[test]
-    fn test_optimal_borrow_calculation() {
-        let executor = FlashLoanExecutor::new(
-            Pubkey::new_unique(),
-            Pubkey::new_unique(),
-        );
-
-        let pool_liquidity = 1_000_000_000u64; // 1 SOL
-        let optimal = executor.calculate_optimal_borrow(pool_liquidity, 1_000_000);
-        
-        // Should be ~90% of liquidity minus fee
-        assert!(optimal < pool_liquidity);
-        assert!(optimal > 0);
+    fn test_wrap_brackets_trade_legs() {
+        let (market, mint, supply, fee_receiver) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let address = Pubkey::new_unique();
+        // 0.3% flash loan fee
+        let reserve = SolendReserve::from_account_data(address, &reserve_data(market, mint, supply, fee_receiver, 3_000_000_000_000_000)).unwrap();
+        assert_eq!((reserve.lending_market, reserve.liquidity_supply, reserve.flash_loan_fee_bps), (market, supply, 30));
+
+        let provider = SolendFlashLoanProvider::new(SOLEND_PROGRAM_ID, vec![reserve]);
+        assert!(provider.supports(&mint) && !provider.supports(&Pubkey::new_unique()));
+
+        let borrower = Pubkey::new_unique();
+        let leg = solana_sdk::system_instruction::transfer(&borrower, &borrower, 1);
+        let ixs = provider.wrap(&borrower, &mint, 1_000, vec![leg.clone(), leg], 3).unwrap();
+        assert_eq!(ixs.len(), 4);
+        assert_eq!((ixs[0].data[0], ixs[3].data[0]), (FLASH_BORROW_RESERVE_LIQUIDITY, FLASH_REPAY_RESERVE_LIQUIDITY));
+        assert_eq!(ixs[3].data[9], 3);
+        assert!(ixs[3].accounts.iter().any(|a| a.pubkey == borrower && a.is_signer));
     }
 }
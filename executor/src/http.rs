@@ -0,0 +1,200 @@
+/// Outbound HTTP
+///
+/// Every reqwest call (tip floor, Helius fee estimates, bloXroute submits, Jupiter quotes, Discord, Telegram, ntfy)
+/// goes through one shared client. Each host gets a timeout and retry policy:
+/// calls on the bundle path fail fast and fall back to their defaults, alerting
+/// can afford to wait and retry. Failures are counted per host.
+use std::collections::HashMap;
+use std::time::Duration;
+use reqwest::{RequestBuilder, Response, StatusCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpPolicy {
+    pub timeout: Duration,
+    /// Attempts after the first one
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled after each one
+    pub backoff: Duration,
+}
+
+impl HttpPolicy {
+    pub const fn new(timeout_ms: u64, max_retries: u32, backoff_ms: u64) -> Self {
+        Self {
+            timeout: Duration::from_millis(timeout_ms),
+            max_retries,
+            backoff: Duration::from_millis(backoff_ms),
+        }
+    }
+
+    fn delay(&self, retry: u32) -> Duration {
+        self.backoff * 2u32.saturating_pow(retry.min(6))
+    }
+}
+
+impl Default for HttpPolicy {
+    fn default() -> Self {
+        Self::new(5_000, 2, 250)
+    }
+}
+
+/// Hosts we talk to out of the box. A policy also covers the host's subdomains.
+fn builtin_policies() -> HashMap<String, HttpPolicy> {
+    [
+        ("mainnet.block-engine.jito.wtf", HttpPolicy::new(800, 0, 0)), // Tip floor, on the bundle path
+        ("helius-rpc.com", HttpPolicy::new(500, 0, 0)),                // Fee estimate, on the bundle path
+        ("blxrbdn.com", HttpPolicy::new(1_000, 0, 0)),                  // bloXroute submit, the next sender is waiting
+        ("jup.ag", HttpPolicy::new(800, 0, 0)),                         // Quote cross-check, on the bundle path
+        ("discord.com", HttpPolicy::new(5_000, 3, 500)),
+        ("api.telegram.org", HttpPolicy::new(10_000, 2, 500)),          // getUpdates long-polls for 2s
+        ("ntfy.sh", HttpPolicy::new(5_000, 2, 500)),
+    ]
+    .into_iter()
+    .map(|(host, policy)| (host.to_string(), policy))
+    .collect()
+}
+
+pub struct HttpClient {
+    client: reqwest::Client,
+    default_policy: HttpPolicy,
+    policies: HashMap<String, HttpPolicy>,
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new(HttpPolicy::default())
+    }
+}
+
+impl HttpClient {
+    pub fn new(default_policy: HttpPolicy) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(3))
+            .pool_idle_timeout(Duration::from_secs(90))
+            .build()
+            .unwrap_or_default();
+        Self { client, default_policy, policies: builtin_policies() }
+    }
+
+    /// Overrides the policy for `host` and its subdomains
+    pub fn with_host_policy(mut self, host: &str, policy: HttpPolicy) -> Self {
+        self.policies.insert(host.to_ascii_lowercase(), policy);
+        self
+    }
+
+    /// Applies overrides in the form `host=timeout_ms:retries:backoff_ms;...`
+    pub fn with_overrides(mut self, spec: &str) -> Result<Self, String> {
+        for (host, policy) in parse_overrides(spec)? {
+            self = self.with_host_policy(&host, policy);
+        }
+        Ok(self)
+    }
+
+    /// Most specific policy for `host`: exact match, then parent domains, then the default
+    pub fn policy_for(&self, host: &str) -> HttpPolicy {
+        let host = host.to_ascii_lowercase();
+        let mut candidate = host.as_str();
+        loop {
+            if let Some(policy) = self.policies.get(candidate) {
+                return *policy;
+            }
+            match candidate.split_once('.') {
+                Some((_, parent)) if parent.contains('.') => candidate = parent,
+                _ => return self.default_policy,
+            }
+        }
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    /// Sends with the host's timeout, retrying connection errors, timeouts, 429 and 5xx with backoff.
+    /// Errors never include the URL: some carry credentials (Telegram bot tokens).
+    pub async fn send(&self, request: RequestBuilder) -> anyhow::Result<Response> {
+        let host = request.try_clone()
+            .and_then(|r| r.build().ok())
+            .and_then(|r| r.url().host_str().map(str::to_string))
+            .unwrap_or_default();
+        let policy = self.policy_for(&host);
+
+        let mut pending = Some(request);
+        let mut retry = 0;
+        loop {
+            // Streaming bodies can't be cloned; those get a single attempt
+            let attempt = match pending.as_ref().and_then(RequestBuilder::try_clone) {
+                Some(attempt) => attempt,
+                None => pending.take().expect("request kept until its last attempt"),
+            };
+            let retriable = pending.is_some();
+            let error = match attempt.timeout(policy.timeout).send().await {
+                Ok(resp) if !is_retriable_status(resp.status()) => return Ok(resp),
+                Ok(resp) => format!("HTTP {}", resp.status()),
+                Err(e) if e.is_timeout() || e.is_connect() || e.is_request() => e.without_url().to_string(),
+                Err(e) => {
+                    mev_core::telemetry::HTTP_FAILURES.with_label_values(&[&host]).inc();
+                    return Err(anyhow::anyhow!("{} request failed: {}", host, e.without_url()));
+                }
+            };
+            mev_core::telemetry::HTTP_FAILURES.with_label_values(&[&host]).inc();
+
+            if !retriable || retry >= policy.max_retries {
+                return Err(anyhow::anyhow!("{} request failed after {} attempt(s): {}", host, retry + 1, error));
+            }
+            tracing::debug!("🌐 {} request failed ({}), retry {}/{}", host, error, retry + 1, policy.max_retries);
+            tokio::time::sleep(policy.delay(retry)).await;
+            retry += 1;
+        }
+    }
+}
+
+fn is_retriable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Parses `host=timeout_ms:retries:backoff_ms;...` (empty = no overrides)
+pub fn parse_overrides(spec: &str) -> Result<Vec<(String, HttpPolicy)>, String> {
+    spec.split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (host, policy) = entry.split_once('=')
+                .ok_or_else(|| format!("expected host=timeout_ms:retries:backoff_ms, got '{}'", entry))?;
+            let parts: Vec<&str> = policy.split(':').map(str::trim).collect();
+            let [timeout, retries, backoff] = parts.as_slice() else {
+                return Err(format!("expected timeout_ms:retries:backoff_ms for '{}'", host.trim()));
+            };
+            let number = |s: &str| s.parse::<u64>().map_err(|_| format!("invalid number '{}' in '{}'", s, entry));
+            let timeout = number(timeout)?;
+            if timeout == 0 {
+                return Err(format!("timeout for '{}' must be > 0", host.trim()));
+            }
+            Ok((host.trim().to_ascii_lowercase(), HttpPolicy::new(timeout, number(retries)? as u32, number(backoff)?)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_lookup_and_overrides() {
+        let client = HttpClient::default()
+            .with_overrides("discord.com=2000:1:100; rpc.example.org=300:0:0")
+            .unwrap();
+
+        assert_eq!(client.policy_for("discord.com"), HttpPolicy::new(2_000, 1, 100));
+        // Subdomains inherit, but a bare TLD never matches
+        assert_eq!(client.policy_for("Mainnet.Helius-RPC.com"), HttpPolicy::new(500, 0, 0));
+        assert_eq!(client.policy_for("rpc.example.org"), HttpPolicy::new(300, 0, 0));
+        assert_eq!(client.policy_for("example.org"), HttpPolicy::default());
+
+        assert_eq!(HttpPolicy::new(1_000, 3, 100).delay(2), Duration::from_millis(400));
+        assert!(parse_overrides("discord.com=0:1:1").is_err());
+        assert!(parse_overrides("discord.com=100:1").is_err());
+    }
+}
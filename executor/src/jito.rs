@@ -3,11 +3,16 @@ use solana_sdk::{
     pubkey::Pubkey,
     transaction::{Transaction, VersionedTransaction},
 };
-use solana_client::rpc_client::RpcClient;
+use crate::rpc_pool::RpcPool;
+use solana_client::rpc_config::{RpcSimulateTransactionConfig, RpcTransactionConfig};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
 use jito_protos::searcher::{
     searcher_service_client::SearcherServiceClient, 
 };
-use jito_searcher_client::{get_searcher_client_no_auth, send_bundle_no_wait};
+use jito_searcher_client::send_bundle_no_wait;
 use tonic::transport::Channel;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -17,7 +22,21 @@ use rand::seq::SliceRandom;
 use serde::Deserialize;
 
 use mev_core::{ArbitrageOpportunity, FeeStrategy};
-use strategy::ports::{ExecutionPort, PoolKeyProvider, TelemetryPort};
+use strategy::ports::{ExecutionPort, FlashLoanProvider, PoolKeyProvider, TelemetryPort};
+use crate::privacy::PrivacyConfig;
+use crate::rng::SharedRng;
+use crate::tx_sender::{RpcSender, SenderChain, SenderKind, TxSender};
+use crate::tip_policy::{TipDecision, TipFloorPolicy, TipFloorResponse};
+use crate::lookup_table::{fits_legacy, LookupTableManager};
+use crate::http::HttpClient;
+use crate::bundle_tracker::{BundleTracker, TrackedBundle};
+use crate::compute_budget::{ComputeBudgetPolicy, MAX_COMPUTE_UNITS};
+use crate::cu_profile::CuProfiles;
+use crate::endpoint_health::{EndpointHealth, EndpointHealthPolicy};
+use crate::channel_warmer::{self, ChannelWarmer, WarmupAction, WarmupPolicy};
+use crate::token_accounts::{AtaCache, TokenAccountPolicy};
+use crate::nonce::{DurableNonce, OwnedNonceLease};
+use crate::race::{self, RaceTicket};
 
 #[derive(Deserialize, Debug)]
 struct PriorityFeeLevels {
@@ -42,36 +61,42 @@ struct HeliusRpcResponse<T> {
 
 pub struct JitoExecutor {
     clients: Vec<Arc<Mutex<SearcherServiceClient<Channel>>>>,  // Multiple endpoints
+    endpoint_urls: Vec<String>,  // Same order as clients
     current_endpoint_index: Arc<Mutex<usize>>,  // Round-robin tracker
+    endpoint_health: Arc<EndpointHealth>,  // Benches endpoints that keep failing
+    channel_warmer: Arc<ChannelWarmer>,  // Keeps idle channels warm, recycles stale ones
     auth_keypair: Arc<Keypair>,
     payer_pubkey: Pubkey,
-    rpc_client: Arc<RpcClient>,
+    rpc: Arc<RpcPool>,
     tip_accounts: Vec<Pubkey>,
+    endpoint_tip_accounts: Vec<Vec<Pubkey>>,  // Tip accounts advertised by each endpoint (same order as clients)
+    last_tip_account: std::sync::Mutex<Option<Pubkey>>,
+    privacy: PrivacyConfig,
+    rng: Arc<SharedRng>,  // Tip-account and jitter draws; seeded for reproducible runs
+    tip_policy: TipFloorPolicy,
+    lookup_tables: Option<Arc<LookupTableManager>>,
+    flash_loans: Option<Arc<dyn FlashLoanProvider>>,
+    token_accounts: Option<(TokenAccountPolicy, Arc<AtaCache>)>,  // ATA creation and WSOL wrap around the legs
     key_provider: Option<Arc<dyn PoolKeyProvider>>,
     telemetry: Option<Arc<dyn TelemetryPort>>,
+    bundle_tracker: Option<Arc<BundleTracker>>,
     max_retries: u32,
     tip_floor_url: String,
-    helius_sender_client: Option<Arc<RpcClient>>,
+    http: Arc<HttpClient>,
+    priority_fee_url: Option<String>,  // Helius endpoint for getPriorityFeeEstimate; the RPC pool otherwise
+    senders: SenderChain,  // Fallback path once every block engine failed
     fee_strategy: FeeStrategy,
-}
-
-#[derive(Deserialize, Debug, Default)]
-struct TipFloorResponse {
-    pub landed_tips_25th_percentile: f64,
-    pub landed_tips_50th_percentile: f64,
-    pub landed_tips_75th_percentile: f64,
-    pub landed_tips_95th_percentile: f64,
-    pub landed_tips_99th_percentile: f64,
-    pub ema_landed_tips_50th_percentile: f64,
-    pub ema_landed_tips_75th_percentile: f64,
+    compute_budget: ComputeBudgetPolicy,
+    cu_profiles: Arc<CuProfiles>,  // Measured CU per venue, for budgets without a simulation
+    durable_nonce: Option<Arc<DurableNonce>>,  // Signs the fallback transaction when set
+    race_sender: Option<Arc<dyn TxSender>>,  // Gets an untipped copy of each bundle (RACE_MODE)
 }
 
 impl JitoExecutor {
     pub async fn new(
         block_engine_url: &str,  // Can be comma-separated for multiple endpoints
         auth_keypair: &Keypair, 
-        rpc_url: &str,
-        helius_sender_url: Option<String>,
+        rpc: Arc<RpcPool>,
         fee_strategy: FeeStrategy,
         key_provider: Option<Arc<dyn PoolKeyProvider>>,
         telemetry: Option<Arc<dyn TelemetryPort>>,
@@ -92,15 +117,25 @@ impl JitoExecutor {
         
         // Connect to all endpoints
         let mut clients = Vec::new();
+        let mut endpoint_urls = Vec::new();
+        let mut endpoint_tip_accounts = Vec::new();
         for (i, url) in urls.iter().enumerate() {
-            match get_searcher_client_no_auth(url).await {
+            match channel_warmer::connect(url).await {
                 Ok(mut client) => {
                     // Verify connectivity
-                    match client.get_tip_accounts(jito_protos::searcher::GetTipAccountsRequest {}).await {
-                        Ok(_) => tracing::info!("✅ Jito endpoint {} connected: {}", i+1, url),
-                        Err(e) => tracing::warn!("⚠️ Jito endpoint {} ping failed ({}): {}", i+1, url, e),
-                    }
+                    let advertised = match client.get_tip_accounts(jito_protos::searcher::GetTipAccountsRequest {}).await {
+                        Ok(resp) => {
+                            tracing::info!("✅ Jito endpoint {} connected: {}", i+1, url);
+                            resp.into_inner().accounts.iter().filter_map(|a| Pubkey::from_str(a).ok()).collect()
+                        }
+                        Err(e) => {
+                            tracing::warn!("⚠️ Jito endpoint {} ping failed ({}): {}", i+1, url, e);
+                            Vec::new()
+                        }
+                    };
                     clients.push(Arc::new(Mutex::new(client)));
+                    endpoint_urls.push(url.clone());
+                    endpoint_tip_accounts.push(advertised);
                 }
                 Err(e) => {
                     tracing::error!("❌ Failed to connect to Jito endpoint {}: {}", url, e);
@@ -115,9 +150,6 @@ impl JitoExecutor {
         
         tracing::info!("✅ Jito executor initialized with {} endpoint(s)", clients.len());
         
-        let rpc = Arc::new(RpcClient::new(rpc_url.to_string()));
-        let helius_sender = helius_sender_url.map(|url| Arc::new(RpcClient::new(url)));
-
         let tip_accounts = vec![
             Pubkey::from_str("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5").unwrap(),
             Pubkey::from_str("HFqU5x63VTqvQss8hp11i4wVV8bD44PuyAC8eF6S7yBz").unwrap(),
@@ -126,18 +158,39 @@ impl JitoExecutor {
         ];
 
         Ok(Self {
+            endpoint_health: Arc::new(EndpointHealth::new(clients.len(), EndpointHealthPolicy::default())),
+            channel_warmer: Arc::new(ChannelWarmer::new(clients.len(), WarmupPolicy::default(), std::time::Instant::now())),
             clients,
+            endpoint_urls,
             current_endpoint_index: Arc::new(Mutex::new(0)),
             auth_keypair: auth_arc,
             payer_pubkey,
-            rpc_client: rpc,
+            rpc,
             tip_accounts,
+            endpoint_tip_accounts,
+            last_tip_account: std::sync::Mutex::new(None),
+            privacy: PrivacyConfig::default(),
+            rng: Arc::default(),
+            tip_policy: TipFloorPolicy::default(),
+            lookup_tables: None,
+            flash_loans: None,
+            token_accounts: None,
             key_provider,
             telemetry,
+            bundle_tracker: None,
             max_retries: 3,  // 3 attempts per endpoint
             tip_floor_url: "https://mainnet.block-engine.jito.wtf/api/v1/bundles/tip_floor".to_string(),
-            helius_sender_client: helius_sender,
+            http: Arc::new(HttpClient::default()),
+            priority_fee_url: None,
+            senders: SenderChain::new(vec![Arc::new(RpcSender::new(Arc::clone(&rpc)))]),
             fee_strategy,
+            compute_budget: ComputeBudgetPolicy::default(),
+            cu_profiles: Arc::new(CuProfiles::new(
+                ComputeBudgetPolicy::default().base_units,
+                ComputeBudgetPolicy::default().units_per_hop,
+            )),
+            durable_nonce: None,
+            race_sender: None,
         })
     }
     
@@ -145,24 +198,224 @@ impl JitoExecutor {
         self.fee_strategy = strategy;
     }
 
-    /// Fetches the current tip floor from Jito HTTP API
+    pub fn set_privacy(&mut self, privacy: PrivacyConfig) {
+        if privacy.enabled {
+            tracing::info!("🕶️ Order-flow privacy ON (jitter ≤{}ms, {} decoy accounts)", privacy.max_jitter_ms, privacy.decoy_accounts);
+        }
+        self.privacy = privacy.with_rng(Arc::clone(&self.rng));
+    }
+
+    /// Senders the fallback transaction is tried on, in order. Defaults to the RPC pool alone.
+    pub fn set_tx_senders(&mut self, senders: SenderChain) {
+        tracing::info!("🛰️ Fallback senders: {}", senders.describe());
+        self.senders = senders;
+    }
+
+    /// Endpoint that answers Helius `getPriorityFeeEstimate`
+    pub fn set_priority_fee_url(&mut self, url: String) {
+        self.priority_fee_url = Some(url);
+    }
+
+    /// Draws tip accounts and privacy jitter from `rng` instead of a fresh entropy source
+    pub fn set_rng(&mut self, rng: Arc<SharedRng>) {
+        self.privacy.rng = Arc::clone(&rng);
+        self.rng = rng;
+    }
+
+    pub fn set_tip_policy(&mut self, policy: TipFloorPolicy) {
+        tracing::info!("⚖️ Tip floor: {} ({} for elite), profit share {:.0}% capped at {} lamports",
+            policy.percentile.as_str(), policy.elite_percentile.as_str(), policy.profit_share * 100.0, policy.profit_share_cap_lamports);
+        self.tip_policy = policy;
+    }
+
+    /// Bundles too large for a legacy transaction are compiled as v0 against this table
+    pub fn set_lookup_tables(&mut self, manager: Arc<LookupTableManager>) {
+        self.lookup_tables = Some(manager);
+    }
+
+    /// Shares the process-wide HTTP client (and its per-host policies)
+    pub fn set_http_client(&mut self, http: Arc<HttpClient>) {
+        self.http = http;
+    }
+
+    pub fn set_compute_budget(&mut self, policy: ComputeBudgetPolicy) {
+        tracing::info!("⛽ Compute budget: {} (+{} bps margin), priority fee capped at {} lamports",
+            if policy.simulate { "simulated" } else { "per-venue profile" }, policy.margin_bps, policy.max_priority_fee_lamports);
+        self.cu_profiles = Arc::new(CuProfiles::new(policy.base_units, policy.units_per_hop));
+        self.compute_budget = policy;
+    }
+
+    /// Follows every submitted bundle to landed/dropped for land-rate analytics
+    pub fn set_endpoint_health(&mut self, policy: EndpointHealthPolicy) {
+        self.endpoint_health = Arc::new(EndpointHealth::new(self.clients.len(), policy));
+    }
+
+    /// Pings benched endpoints every probe interval and puts the ones that answer back in rotation
+    pub fn start_endpoint_probes(&self) {
+        let health = Arc::clone(&self.endpoint_health);
+        let clients = self.clients.clone();
+        let urls = self.endpoint_urls.clone();
+        for index in 0..clients.len() {
+            let active = health.is_active(index) as i64;
+            mev_core::telemetry::JITO_ENDPOINT_ACTIVE.with_label_values(&[&index.to_string()]).set(active);
+        }
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(health.policy().probe_interval);
+            loop {
+                interval.tick().await;
+                for index in health.benched() {
+                    let mut client = clients[index].lock().await;
+                    let probe = client.get_tip_accounts(jito_protos::searcher::GetTipAccountsRequest {});
+                    match tokio::time::timeout(std::time::Duration::from_secs(5), probe).await {
+                        Ok(Ok(_)) => {
+                            health.reinstate(index);
+                            mev_core::telemetry::JITO_ENDPOINT_ACTIVE.with_label_values(&[&index.to_string()]).set(1);
+                            tracing::info!("✅ Jito endpoint {} answered probe, back in rotation: {}", index + 1, urls[index]);
+                        }
+                        Ok(Err(e)) => tracing::debug!("Jito endpoint {} probe failed: {}", index + 1, e),
+                        Err(_) => tracing::debug!("Jito endpoint {} probe timed out", index + 1),
+                    }
+                }
+            }
+        });
+    }
+
+    pub fn set_channel_warmup(&mut self, policy: WarmupPolicy) {
+        self.channel_warmer = Arc::new(ChannelWarmer::new(self.clients.len(), policy, std::time::Instant::now()));
+    }
+
+    /// Warm-up calls on idle endpoints and reconnects for stale ones, checked every second
+    pub fn start_channel_warmer(&self) {
+        let warmer = Arc::clone(&self.channel_warmer);
+        let clients = self.clients.clone();
+        let urls = self.endpoint_urls.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+            loop {
+                interval.tick().await;
+                for index in 0..clients.len() {
+                    let endpoint_id = index.to_string();
+                    let now = std::time::Instant::now();
+                    mev_core::telemetry::JITO_CONNECTION_AGE_SECONDS
+                        .with_label_values(&[&endpoint_id])
+                        .set(warmer.age(index, now).as_secs() as i64);
+                    let reason = match warmer.action(index, now) {
+                        None => continue,
+                        Some(WarmupAction::Ping) => {
+                            warmer.attempted(index, now);
+                            let mut client = clients[index].lock().await;
+                            let probe = client.get_tip_accounts(jito_protos::searcher::GetTipAccountsRequest {});
+                            match tokio::time::timeout(std::time::Duration::from_secs(5), probe).await {
+                                Ok(Ok(_)) => continue,
+                                Ok(Err(e)) => tracing::debug!("Jito endpoint {} warm-up failed: {}", index + 1, e),
+                                Err(_) => tracing::debug!("Jito endpoint {} warm-up timed out", index + 1),
+                            }
+                            "warmup_failed"
+                        }
+                        Some(WarmupAction::Reconnect) => {
+                            warmer.attempted(index, now);
+                            "idle"
+                        }
+                    };
+                    match channel_warmer::connect(&urls[index]).await {
+                        Ok(fresh) => {
+                            *clients[index].lock().await = fresh;
+                            warmer.reconnected(index, std::time::Instant::now());
+                            mev_core::telemetry::JITO_CHANNEL_RECONNECTS.with_label_values(&[&endpoint_id, reason]).inc();
+                            tracing::info!("🔌 Jito endpoint {} reconnected ({}): {}", index + 1, reason, urls[index]);
+                        }
+                        Err(e) => tracing::warn!("⚠️ Jito endpoint {} reconnect failed ({}): {}", index + 1, reason, e),
+                    }
+                }
+            }
+        });
+    }
+
+    pub fn set_bundle_tracker(&mut self, tracker: Arc<BundleTracker>) {
+        self.bundle_tracker = Some(tracker);
+    }
+
+    /// Provider for opportunities marked as flash-loan funded
+    pub fn set_flash_loans(&mut self, provider: Arc<dyn FlashLoanProvider>) {
+        tracing::info!("🏦 Flash loans enabled via {}", provider.name());
+        self.flash_loans = Some(provider);
+    }
+
+    /// Bracket every bundle's legs with the ATA creation and WSOL wrap/unwrap they need
+    pub fn set_token_accounts(&mut self, policy: TokenAccountPolicy, cache: Arc<AtaCache>) {
+        self.token_accounts = Some((policy, cache));
+    }
+
+    /// Signs the fallback transaction against this nonce, so resends stay valid past blockhash expiry.
+    /// Only used while the payer is the nonce's authority.
+    pub fn set_durable_nonce(&mut self, nonce: Arc<DurableNonce>) {
+        tracing::info!("🔐 Fallback transactions signed against durable nonce {}", nonce.account());
+        self.durable_nonce = Some(nonce);
+    }
+
+    /// Races each bundle against an untipped copy sent through `sender`. The durable nonce
+    /// keeps the two from both landing, so without one (or while it is busy) bundles go alone.
+    pub fn set_race_sender(&mut self, sender: Arc<dyn TxSender>) {
+        tracing::info!("🏁 Race mode: bundles raced against an untipped {} copy", sender.kind().name());
+        self.race_sender = Some(sender);
+    }
+
+    /// Leases the nonce for a race; None when racing is off or the nonce is busy or unreadable
+    async fn race_ticket(&self) -> Option<RaceTicket> {
+        self.race_sender.as_ref()?;
+        let nonce = self.durable_nonce.as_ref().filter(|n| *n.authority() == self.payer_pubkey)?;
+        let Some(lease) = nonce.lease_owned() else {
+            mev_core::telemetry::BUNDLE_RACES.with_label_values(&["nonce_busy"]).inc();
+            return None;
+        };
+        match nonce.hash(&self.rpc).await {
+            Ok(hash) if nonce.claim(hash) => Some(RaceTicket::new(lease, hash)),
+            Ok(_) => {
+                mev_core::telemetry::BUNDLE_RACES.with_label_values(&["nonce_stale"]).inc();
+                None
+            }
+            Err(e) => {
+                tracing::warn!("⚠️ Durable nonce unreadable, sending the bundle without a race: {}", e);
+                mev_core::telemetry::BUNDLE_RACES.with_label_values(&["nonce_unreadable"]).inc();
+                None
+            }
+        }
+    }
+
+    /// `legs` with the token account setup around them, if configured
+    fn with_account_setup(&self, opportunity: &ArbitrageOpportunity, legs: Vec<Instruction>) -> Vec<Instruction> {
+        match &self.token_accounts {
+            Some((policy, cache)) => crate::token_accounts::setup(&self.payer_pubkey, opportunity, cache, *policy).around(legs),
+            None => legs,
+        }
+    }
+
+    /// Tip account for a bundle on `endpoint_index`. Privacy mode draws from that endpoint's own list.
+    fn select_tip_account(&self, endpoint_index: usize) -> Pubkey {
+        let candidates = match self.endpoint_tip_accounts.get(endpoint_index) {
+            Some(advertised) if self.privacy.enabled && !advertised.is_empty() => advertised.as_slice(),
+            _ => self.tip_accounts.as_slice(),
+        };
+        let mut last = self.last_tip_account.lock().unwrap_or_else(|e| e.into_inner());
+        let tip_account = self.privacy.pick_tip_account(candidates, *last).unwrap_or(self.tip_accounts[0]);
+        *last = Some(tip_account);
+        tip_account
+    }
+
+    /// Fetches the current tip floor from Jito HTTP API at the policy's base percentile
     pub async fn get_tip_floor(&self) -> anyhow::Result<u64> {
-        let resp = reqwest::get(&self.tip_floor_url)
+        Ok(self.get_tip_decision(0, false).await?.floor_lamports)
+    }
+
+    /// Fetches the tip floor and applies the tip policy for one bundle
+    pub async fn get_tip_decision(&self, expected_profit_lamports: u64, is_elite: bool) -> anyhow::Result<TipDecision> {
+        let resp = self.http.send(self.http.get(&self.tip_floor_url))
             .await?
             .json::<Vec<TipFloorResponse>>()
             .await?;
-            
+
         if let Some(floor) = resp.first() {
-            // Use 75th percentile as the minimum base for competitive HFT
-            // Fallback to 50th if 75th is missing or zero
-            let base_sol = if floor.ema_landed_tips_75th_percentile > 0.0 {
-                floor.ema_landed_tips_75th_percentile
-            } else {
-                floor.ema_landed_tips_50th_percentile
-            };
-            
-            let lamports = (base_sol * 1e9) as u64;
-            return Ok(lamports);
+            return Ok(self.tip_policy.decide(floor, expected_profit_lamports, is_elite));
         }
         
         tracing::debug!("⚠️ No Jito tip floor data available from API");
@@ -171,8 +424,7 @@ impl JitoExecutor {
 
     /// Fetches the current priority fee estimate from Helius API
     pub async fn get_priority_fee_estimate(&self, account_keys: Vec<String>) -> u64 {
-        let client = self.helius_sender_client.as_ref().unwrap_or(&self.rpc_client);
-        let url = client.url();
+        let url = self.priority_fee_url.clone().unwrap_or_else(|| self.rpc.url());
 
         let payload = serde_json::json!({
             "jsonrpc": "2.0",
@@ -188,12 +440,7 @@ impl JitoExecutor {
             ]
         });
 
-        match reqwest::Client::new()
-            .post(&url)
-            .json(&payload)
-            .send()
-            .await 
-        {
+        match self.http.send(self.http.post(&url).json(&payload)).await {
             Ok(resp) => {
                 if let Ok(data) = resp.json::<HeliusRpcResponse<PriorityFeeEstimate>>().await {
                     if let Some(levels) = data.result.priority_fee_levels {
@@ -221,7 +468,25 @@ impl JitoExecutor {
         trade_ixs: Vec<solana_sdk::instruction::Instruction>,
         tip_amount_lamports: u64,
         expected_profit_lamports: u64,
+        is_elite: bool,
+        flash_loan: Option<(Pubkey, u64)>,
+        race: Option<&RaceTicket>,
     ) -> anyhow::Result<String> {
+        // 🛡️ Dynamic Tipping logic (Phase 3 Hardening): policy floor + competitive profit share
+        let mut final_tip = tip_amount_lamports;
+        if let Ok(decision) = self.get_tip_decision(expected_profit_lamports, is_elite).await {
+            let competitive_tip = decision.competitive_tip();
+            tracing::info!("⚖️ Tip floor {}: {} lamports | share {:.0}%: {} lamports | planned {} (Profit: {})",
+                decision.percentile.as_str(), decision.floor_lamports, decision.profit_share * 100.0,
+                decision.share_lamports, tip_amount_lamports, expected_profit_lamports);
+
+            // Only upgrade if competitive tip is higher than our planned tip
+            if competitive_tip > final_tip {
+                tracing::info!("⚖️ Jito Tip Upgrade: raising tip to {} lamports", competitive_tip);
+                final_tip = competitive_tip;
+            }
+        }
+
         // Try each endpoint with retries
         for endpoint_attempt in 0..self.clients.len() {
             // Get next endpoint (round-robin)
@@ -232,37 +497,24 @@ impl JitoExecutor {
                 current
             };
             
+            if !self.endpoint_health.is_active(client_index) {
+                continue; // Benched; the prober brings it back
+            }
+            
             tracing::debug!("Attempting Jito endpoint {} (attempt {} of {})", 
                 client_index + 1, endpoint_attempt + 1, self.clients.len());
             
-            // 🛡️ Dynamic Tipping logic (Phase 3 Hardening)
-            let mut final_tip = tip_amount_lamports;
-            if let Ok(floor) = self.get_tip_floor().await {
-                // Heuristic: floor + competitive profit share
-                // We share 10% of profit with Jito to stay ahead of competitors, capped at 0.1 SOL
-                let profit_share = (expected_profit_lamports as f64 * 0.10) as u64;
-                let profit_share_capped = profit_share.min(100_000_000); // 0.1 SOL cap
-                
-                let competitive_tip = floor.max(profit_share_capped);
-                
-                // Only upgrade if competitive tip is higher than our planned tip
-                if competitive_tip > final_tip {
-                    tracing::info!("⚖️ Jito Tip Upgrade: Floor/Share is {}, raising tip to {} lamports (Profit: {})", 
-                        competitive_tip, competitive_tip, expected_profit_lamports);
-                    final_tip = competitive_tip;
-                }
-            }
-
             // Try with exponential backoff
             for retry in 0..self.max_retries {
                 if let Some(ref tel) = self.telemetry {
                     tel.log_endpoint_attempt(client_index);
                 }
 
-                match self.send_bundle_to_endpoint(client_index, trade_ixs.clone(), final_tip).await {
+                match self.send_bundle_to_endpoint(client_index, trade_ixs.clone(), final_tip, flash_loan, race).await {
                     Ok(sig) => {
                         tracing::info!("✅ Bundle submitted via endpoint {} on attempt {}", 
                             client_index + 1, retry + 1);
+                        self.endpoint_health.record(client_index, true, std::time::Instant::now());
                         
                         if let Some(ref tel) = self.telemetry {
                             tel.log_endpoint_success(client_index);
@@ -274,6 +526,16 @@ impl JitoExecutor {
                         let error_msg = e.to_string();
                         let _is_rate_limit = error_msg.contains("ResourceExhausted") 
                             || error_msg.contains("rate limit");
+
+                        if self.endpoint_health.record(client_index, false, std::time::Instant::now()) {
+                            let policy = self.endpoint_health.policy();
+                            tracing::error!("🚫 Jito endpoint {} benched: success rate below {:.0}% over {}s ({}). Probing every {}s.",
+                                client_index + 1, policy.min_success_rate * 100.0, policy.window.as_secs(),
+                                self.endpoint_urls[client_index], policy.probe_interval.as_secs());
+                            mev_core::telemetry::JITO_ENDPOINT_ACTIVE.with_label_values(&[&client_index.to_string()]).set(0);
+                            mev_core::telemetry::JITO_BUNDLE_ERRORS.with_label_values(&[&client_index.to_string()]).inc();
+                            break;
+                        }
                         
                         if retry < self.max_retries - 1 {
                             let backoff_ms = 2_u64.pow(retry as u32) * 1000;  // 1s, 2s, 4s
@@ -293,28 +555,75 @@ impl JitoExecutor {
         Err(anyhow::anyhow!("All Jito endpoints exhausted"))
     }
     
+    /// Signs `ixs` as one transaction. Multi-hop routes can overflow the legacy
+    /// size limit; those are compiled as v0 against our lookup table.
+    fn compile_bundle_tx(&self, ixs: &[Instruction], blockhash: Hash) -> anyhow::Result<VersionedTransaction> {
+        if let Some(tables) = &self.lookup_tables {
+            if !fits_legacy(ixs, &self.payer_pubkey) {
+                tracing::debug!("📇 Bundle exceeds legacy size, compiling v0 message");
+                return tables.compile_v0(ixs, &self.auth_keypair, blockhash);
+            }
+        }
+        Ok(VersionedTransaction::from(Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.payer_pubkey),
+            &[&*self.auth_keypair],
+            blockhash,
+        )))
+    }
+
+    /// Compute units `tx` consumed in simulation; None if it failed or the RPC didn't say
+    fn simulate_units(&self, tx: &VersionedTransaction) -> Option<u64> {
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(CommitmentConfig::processed()),
+            ..Default::default()
+        };
+        match self.rpc.call_blocking(|c| c.simulate_transaction_with_config(tx, config.clone())) {
+            Ok(response) if response.value.err.is_none() => response.value.units_consumed,
+            Ok(response) => {
+                tracing::debug!("⛽ Budget simulation failed ({:?}), using venue profiles", response.value.err);
+                None
+            }
+            Err(e) => {
+                tracing::debug!("⛽ Budget simulation unavailable ({}), using venue profiles", e);
+                None
+            }
+        }
+    }
+
     /// Send bundle to specific endpoint
     async fn send_bundle_to_endpoint(
         &self,
         endpoint_index: usize,
         trade_ixs: Vec<solana_sdk::instruction::Instruction>,
         tip_amount_lamports: u64,
+        flash_loan: Option<(Pubkey, u64)>,
+        race: Option<&RaceTicket>,
     ) -> anyhow::Result<String> {
+        // Privacy: land at a random point in the slot (before taking the endpoint lock)
+        let jitter = self.privacy.jitter();
+        if !jitter.is_zero() {
+            tokio::time::sleep(jitter).await;
+        }
+
         let mut client = self.clients[endpoint_index].lock().await;
         
-        let blockhash = self.rpc_client.get_latest_blockhash()?;
+        // A raced bundle is signed against the nonce, every retry against the same value
+        let blockhash = match race {
+            Some(ticket) => ticket.hash(),
+            None => self.rpc.call_blocking(|c| c.get_latest_blockhash())?,
+        };
 
         // Pick a Random Tip Account
-        let tip_account = {
-            let mut rng = rand::thread_rng();
-            *self.tip_accounts.choose(&mut rng).unwrap()
-        };
+        let tip_account = self.select_tip_account(endpoint_index);
         
-        let tip_ix = solana_sdk::system_instruction::transfer(
+        let tip_ix = self.privacy.decorate_tip(solana_sdk::system_instruction::transfer(
             &self.payer_pubkey,
             &tip_account,
             tip_amount_lamports
-        );
+        ));
 
         // 🛡️ Dynamic Priority Fee (Phase 7)
         let mut account_keys = vec![self.payer_pubkey.to_string(), tip_account.to_string()];
@@ -325,31 +634,165 @@ impl JitoExecutor {
         }
         let priority_fee = self.get_priority_fee_estimate(account_keys).await;
 
-        let mut bundle_ixs = vec![
-            solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(250_000), // Standard safe limit for 3-hop swap
-            solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(priority_fee),    // Dynamic priority
-        ];
-        bundle_ixs.extend(trade_ixs);
-        bundle_ixs.push(tip_ix);
+        // Placeholders (runtime ceiling, no priority fee so the simulation can't fail on the fee);
+        // the real budget is set once the finished transaction has been simulated
+        let limit_marker = ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNITS);
+        let price_marker = ComputeBudgetInstruction::set_compute_unit_price(0);
+        let compute_budget = vec![limit_marker.clone(), price_marker.clone()];
+        let trade_len = trade_ixs.len();
+        let tip_marker = tip_ix.clone();
+        let mut bundle_ixs = self.privacy.arrange(compute_budget, trade_ixs, tip_ix);
 
-        let tx = Transaction::new_signed_with_payer(
-            &bundle_ixs,
-            Some(&self.payer_pubkey),
-            &[&*self.auth_keypair],
-            blockhash,
-        );
-        
-        let signature = tx.signatures[0];
+        // Flash loan: the repay names the borrow by its index, so every final layout
+        // (with the nonce advance in front, without the tip) is bracketed on its own
+        let flash_loan = match flash_loan {
+            Some((mint, amount)) => {
+                let provider = self.flash_loans.as_deref()
+                    .ok_or_else(|| anyhow::anyhow!("Opportunity needs a flash loan but no provider is configured"))?;
+                Some((provider, mint, amount))
+            }
+            None => None,
+        };
+        let bracket = |ixs: Vec<Instruction>| match flash_loan {
+            Some((provider, mint, amount)) => bracket_flash_loan(provider, &self.payer_pubkey, &mint, amount, ixs, trade_len, &tip_marker),
+            None => Ok(ixs),
+        };
+        let sim_ixs = bracket(bundle_ixs.clone())?;
+
+        if let Some(tables) = &self.lookup_tables {
+            tables.record_usage(&sim_ixs);
+        }
+
+        // Size the compute budget from a simulation of the finished transaction; every
+        // measurement also refines the venue profiles used when there isn't one
+        let programs = cu_programs(&sim_ixs, &tip_marker);
+        let simulated_units = if self.compute_budget.simulate {
+            self.simulate_units(&self.compile_bundle_tx(&sim_ixs, blockhash)?)
+        } else {
+            None
+        };
+        if let Some(units) = simulated_units {
+            self.cu_profiles.observe(&programs, units);
+        }
+        let budget = self.compute_budget.plan(simulated_units, self.cu_profiles.estimate(&programs), priority_fee);
+        tracing::debug!("⛽ Compute budget: {} CU ({}) at {} µlamports/CU, fee {} lamports",
+            budget.unit_limit,
+            if simulated_units.is_some() { "simulated" } else { "venue profile" },
+            budget.unit_price,
+            budget.priority_fee_lamports());
+        for ix in bundle_ixs.iter_mut() {
+            if *ix == limit_marker {
+                *ix = ComputeBudgetInstruction::set_compute_unit_limit(budget.unit_limit);
+            } else if *ix == price_marker {
+                *ix = ComputeBudgetInstruction::set_compute_unit_price(budget.unit_price);
+            }
+        }
+
+        if let Some(ticket) = race {
+            bundle_ixs = ticket.wrap(&bundle_ixs);
+        }
+        let versioned_tx = self.compile_bundle_tx(&bracket(bundle_ixs.clone())?, blockhash)?;
 
-        let versioned_tx = VersionedTransaction::from(tx);
+        let signature = versioned_tx.signatures[0];
         let bundles = vec![versioned_tx];
 
-        let _response = send_bundle_no_wait(&bundles, &mut client).await?;
+        // Race: the untipped copy goes out alongside the first attempt
+        let rpc_copy = match (race, &self.race_sender) {
+            (Some(ticket), Some(sender)) if ticket.start() => {
+                let copy = bracket(race::untipped(&bundle_ixs, &tip_marker))?;
+                if fits_legacy(&copy, &self.payer_pubkey) {
+                    let tx = Transaction::new_signed_with_payer(&copy, Some(&self.payer_pubkey), &[&*self.auth_keypair], blockhash);
+                    Some((ticket, Arc::clone(sender), tx))
+                } else {
+                    mev_core::telemetry::BUNDLE_RACES.with_label_values(&["oversized"]).inc();
+                    None
+                }
+            }
+            _ => None,
+        };
+        let (response, _) = tokio::join!(
+            send_bundle_no_wait(&bundles, &mut client),
+            async {
+                let Some((ticket, sender, tx)) = rpc_copy else { return };
+                match sender.send(&tx).await {
+                    Ok(rpc_signature) => {
+                        tracing::info!("🏁 Untipped copy raced via {}: {}", sender.kind().name(), rpc_signature);
+                        mev_core::telemetry::BUNDLE_RACES.with_label_values(&["raced"]).inc();
+                        ticket.record_rpc(rpc_signature);
+                    }
+                    Err(e) => {
+                        tracing::warn!("⚠️ {} rejected the raced copy: {}", sender.kind().name(), e);
+                        mev_core::telemetry::BUNDLE_RACES.with_label_values(&["rpc_rejected"]).inc();
+                    }
+                }
+            },
+        );
+        let response = response?;
+        self.channel_warmer.touch(endpoint_index, std::time::Instant::now());
+        if let Some(tracker) = &self.bundle_tracker {
+            tracker.track(TrackedBundle {
+                bundle_id: response.into_inner().uuid,
+                endpoint_index,
+                endpoint_url: self.endpoint_urls[endpoint_index].clone(),
+                tip_lamports: tip_amount_lamports,
+            });
+        }
         
         Ok(signature.to_string())
     }
 }
 
+/// `ixs` with the `trade_len` trade legs bracketed by the flash loan's borrow and repay. The legs
+/// follow the compute budget, the nonce advance and (when privacy puts it first) the tip; the
+/// repay checks the borrow at the position it ends up in, so this runs on the finished layout.
+fn bracket_flash_loan(
+    provider: &dyn FlashLoanProvider,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    mut ixs: Vec<Instruction>,
+    trade_len: usize,
+    tip_ix: &Instruction,
+) -> anyhow::Result<Vec<Instruction>> {
+    let start = ixs.iter()
+        .position(|ix| ix.program_id != solana_sdk::compute_budget::id() && ix != tip_ix && !crate::nonce::is_advance(ix))
+        .unwrap_or(ixs.len());
+    let end = start + trade_len;
+    if end > ixs.len() {
+        return Err(anyhow::anyhow!("Bundle has {} instruction(s) after the overhead, expected {} trade leg(s)", ixs.len() - start, trade_len));
+    }
+    let legs: Vec<_> = ixs.splice(start..end, std::iter::empty()).collect();
+    let wrapped = provider.wrap(payer, mint, amount, legs, start)?;
+    ixs.splice(start..start, wrapped);
+    Ok(ixs)
+}
+
+/// Program of every instruction that costs CU beyond the fixed compute-budget and tip overhead
+fn cu_programs(ixs: &[Instruction], tip_ix: &Instruction) -> Vec<Pubkey> {
+    ixs.iter()
+        .filter(|ix| ix.program_id != solana_sdk::compute_budget::id() && *ix != tip_ix)
+        .map(|ix| ix.program_id)
+        .collect()
+}
+
+/// Compute units a landed transaction consumed, from its status meta
+fn landed_units(rpc: &RpcPool, signature: &solana_sdk::signature::Signature) -> Option<u64> {
+    let tx = rpc.call_blocking(|c| c.get_transaction_with_config(signature, RpcTransactionConfig {
+        encoding: None,
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    })).ok()?;
+    tx.transaction.meta.and_then(|meta| meta.compute_units_consumed.into())
+}
+
+/// Mint and amount to borrow for `opp`, if it is flash-loan funded
+fn flash_loan_leg(opp: &ArbitrageOpportunity) -> Option<(Pubkey, u64)> {
+    match (opp.flash_loan_amount, opp.steps.first()) {
+        (0, _) | (_, None) => None,
+        (amount, Some(step)) => Some((step.input_mint, amount)),
+    }
+}
+
 #[async_trait::async_trait]
 impl ExecutionPort for JitoExecutor {
     async fn build_bundle_instructions(
@@ -360,9 +803,9 @@ impl ExecutionPort for JitoExecutor {
     ) -> anyhow::Result<Vec<solana_sdk::instruction::Instruction>> {
         let mut instructions = Vec::new();
 
-        // Slippage Calculation: min_amount_out = input * (1 - slippage)
+        // Slippage Calculation: min_amount_out = input (the quote, for a one-way route) * (1 - slippage)
         // bps = 1/10000. So 1% = 100 bps.
-        let min_amount_out = (opportunity.input_amount as u128 * (10000 - max_slippage_bps) as u128 / 10000) as u64;
+        let min_amount_out = opportunity.min_amount_out(max_slippage_bps);
 
 
         let mut current_amount_in = opportunity.input_amount;
@@ -375,6 +818,10 @@ impl ExecutionPort for JitoExecutor {
                 // Only enforce slippage on the final leg to ensure atomic execution succeeds
                 // Intermediate legs use 0 as min_out (swap everything received)
                 let step_min_out = if is_last_step { min_amount_out } else { 0 };
+                // A leg split across pools gives each part its own share of the leg's input
+                if let Some(&amount) = opportunity.step_inputs.get(i) {
+                    current_amount_in = amount;
+                }
 
                 // Raydium Path
                 if step.program_id == mev_core::constants::RAYDIUM_V4_PROGRAM {
@@ -414,6 +861,31 @@ impl ExecutionPort for JitoExecutor {
                         a_to_b,
                     ));
                 }
+                // Raydium CLMM Path
+                else if step.program_id == mev_core::constants::RAYDIUM_CLMM_PROGRAM {
+                    let keys = self.raydium_clmm_keys(provider.as_ref(), &step.pool).await?;
+                    let zero_for_one = step.input_mint == keys.mint_0;
+                    instructions.push(crate::raydium_clmm_builder::swap(&keys, current_amount_in, step_min_out, 0, zero_for_one));
+                }
+                // Phoenix order book Path
+                else if step.program_id == mev_core::constants::PHOENIX_PROGRAM {
+                    let keys = self.phoenix_keys(provider.as_ref(), &step.pool).await?;
+                    let base_in = step.input_mint == keys.base_mint;
+                    instructions.push(crate::phoenix_builder::swap(&keys, current_amount_in, step_min_out, base_in));
+                }
+                // OpenBook v2 order book Path
+                else if step.program_id == mev_core::constants::OPENBOOK_V2_PROGRAM {
+                    let keys = self.openbook_keys(provider.as_ref(), &step.pool).await?;
+                    let base_in = step.input_mint == keys.base_mint;
+                    instructions.push(crate::openbook_builder::place_take_order(&keys, current_amount_in, step_min_out, base_in));
+                }
+                // Pump.fun bonding curve Path
+                else if step.program_id == mev_core::constants::PUMP_FUN_PROGRAM {
+                    let (leg, received) = crate::pump_fun_builder::swap_leg(self.payer_pubkey, step, current_amount_in, step_min_out, max_slippage_bps);
+                    instructions.extend(leg);
+                    current_amount_in = received;
+                    continue;
+                }
                 
                 // Track amount for multi-hop
                 // The output of this step becomes the input of the next
@@ -430,12 +902,10 @@ impl ExecutionPort for JitoExecutor {
         } else {
             return Err(anyhow::anyhow!("PoolKeyProvider missing. Cannot build instructions."));
         }
+        let mut instructions = self.with_account_setup(&opportunity, instructions);
 
         // 2. Add Tip
-        let tip_account = {
-            let mut rng = rand::thread_rng();
-            *self.tip_accounts.choose(&mut rng).unwrap()
-        };
+        let tip_account = self.rng.with(|rng| *self.tip_accounts.choose(rng).unwrap());
         instructions.push(solana_sdk::system_instruction::transfer(
             &self.payer_pubkey,
             &tip_account,
@@ -453,8 +923,9 @@ impl ExecutionPort for JitoExecutor {
         max_slippage_bps: u16,
     ) -> anyhow::Result<String> {
         // Build instructions (without tip - will be added in send methods)
+        let build_start = std::time::Instant::now();
         let mut ixs = Vec::new();
-        let min_amount_out = (opportunity.input_amount as u128 * (10000 - max_slippage_bps) as u128 / 10000) as u64;
+        let min_amount_out = opportunity.min_amount_out(max_slippage_bps);
         let mut current_amount_in = opportunity.input_amount;
         let num_steps = opportunity.steps.len();
 
@@ -462,6 +933,10 @@ impl ExecutionPort for JitoExecutor {
             for (i, step) in opportunity.steps.iter().enumerate() {
                 let is_last_step = i == num_steps - 1;
                 let step_min_out = if is_last_step { min_amount_out } else { 0 };
+                // A leg split across pools gives each part its own share of the leg's input
+                if let Some(&amount) = opportunity.step_inputs.get(i) {
+                    current_amount_in = amount;
+                }
 
                 if step.program_id == mev_core::constants::RAYDIUM_V4_PROGRAM {
                     let keys = provider.get_swap_keys(&step.pool).await?;
@@ -475,48 +950,10 @@ impl ExecutionPort for JitoExecutor {
                     ));
                 } 
                 else if step.program_id == mev_core::constants::PUMP_FUN_PROGRAM {
-                    let bonding_curve = step.pool;
-                    let token_mint = if step.input_mint == mev_core::constants::SOL_MINT { step.output_mint } else { step.input_mint };
-                    let associated_bonding_curve = spl_associated_token_account::get_associated_token_address(
-                        &bonding_curve,
-                        &token_mint
-                    );
-                    let user_ata = spl_associated_token_account::get_associated_token_address(
-                        &self.payer_pubkey,
-                        &token_mint
-                    );
-
-                    let is_buy = step.input_mint == mev_core::constants::SOL_MINT;
-                    
-                    // Add CreateATA for the user if it's a buy (new token)
-                    if is_buy {
-                        ixs.push(spl_associated_token_account::instruction::create_associated_token_account_idempotent(
-                            &self.payer_pubkey,
-                            &self.payer_pubkey,
-                            &token_mint,
-                            &spl_token::id()
-                        ));
-                        
-                        ixs.push(crate::pump_fun_builder::buy(
-                            self.payer_pubkey,
-                            token_mint,
-                            bonding_curve,
-                            associated_bonding_curve,
-                            user_ata,
-                            step.expected_output,
-                            current_amount_in, // max_sol_cost
-                        ));
-                    } else {
-                        ixs.push(crate::pump_fun_builder::sell(
-                            self.payer_pubkey,
-                            token_mint,
-                            bonding_curve,
-                            associated_bonding_curve,
-                            user_ata,
-                            current_amount_in, // amount of tokens
-                            step_min_out,      // min_sol_output
-                        ));
-                    }
+                    let (leg, received) = crate::pump_fun_builder::swap_leg(self.payer_pubkey, step, current_amount_in, step_min_out, max_slippage_bps);
+                    ixs.extend(leg);
+                    current_amount_in = received;
+                    continue;
                 } else if step.program_id == crate::meteora_builder::METEORA_PROGRAM_ID {
                     let keys = provider.get_meteora_keys(&step.pool).await?;
                     let mut final_keys = keys;
@@ -552,6 +989,21 @@ impl ExecutionPort for JitoExecutor {
                         a_to_b,
                     ));
                 }
+                else if step.program_id == mev_core::constants::RAYDIUM_CLMM_PROGRAM {
+                    let keys = self.raydium_clmm_keys(provider.as_ref(), &step.pool).await?;
+                    let zero_for_one = step.input_mint == keys.mint_0;
+                    ixs.push(crate::raydium_clmm_builder::swap(&keys, current_amount_in, step_min_out, 0, zero_for_one));
+                }
+                else if step.program_id == mev_core::constants::PHOENIX_PROGRAM {
+                    let keys = self.phoenix_keys(provider.as_ref(), &step.pool).await?;
+                    let base_in = step.input_mint == keys.base_mint;
+                    ixs.push(crate::phoenix_builder::swap(&keys, current_amount_in, step_min_out, base_in));
+                }
+                else if step.program_id == mev_core::constants::OPENBOOK_V2_PROGRAM {
+                    let keys = self.openbook_keys(provider.as_ref(), &step.pool).await?;
+                    let base_in = step.input_mint == keys.base_mint;
+                    ixs.push(crate::openbook_builder::place_take_order(&keys, current_amount_in, step_min_out, base_in));
+                }
                 
                 current_amount_in = step.expected_output;
             }
@@ -564,77 +1016,100 @@ impl ExecutionPort for JitoExecutor {
         } else {
             return Err(anyhow::anyhow!("PoolKeyProvider missing. Cannot build instructions."));
         }
+        let ixs = self.with_account_setup(&opportunity, ixs);
         
         // Try Jito first with retry logic
         if let Some(ref tel) = self.telemetry {
+            tel.log_build_latency(build_start.elapsed().as_secs_f64() * 1000.0);
             tel.log_execution_attempt();
         }
 
-        let jito_result = self.send_bundle_with_retry(ixs.clone(), tip_lamports, opportunity.expected_profit_lamports).await;
+        let submit_start = std::time::Instant::now();
+        let race = self.race_ticket().await;
+        let jito_result = self.send_bundle_with_retry(
+            ixs.clone(),
+            tip_lamports,
+            opportunity.profit_in_sol(),
+            opportunity.is_elite_match,
+            flash_loan_leg(&opportunity),
+            race.as_ref(),
+        ).await;
+        if let Some(ref tel) = self.telemetry {
+            tel.log_submit_latency(submit_start.elapsed().as_secs_f64() * 1000.0);
+        }
+        let rpc_copy = race.as_ref().and_then(|ticket| ticket.rpc_signature()).map(|sig| sig.to_string());
+        // Held until the trade settles, so no other trade signs against the nonce the two copies share
+        let race_lease = race.map(RaceTicket::into_lease);
         
-        match jito_result {
-            Ok(sig) => {
+        match (jito_result, rpc_copy) {
+            (Ok(sig), rpc_copy) => {
                 tracing::info!("✅ Jito bundle submitted: {}", sig);
                 if let Some(ref tel) = self.telemetry {
                     tel.log_jito_success();
-                    
-                    // Spawn background poller for PnL tracking
-                    let rpc = Arc::clone(&self.rpc_client);
-                    let telemetry = Arc::clone(tel);
-                    let profit = opportunity.expected_profit_lamports;
-                    let signature = sig.clone();
-                    
-                    tokio::spawn(async move {
-                        // Poll for confirmation (max 60s)
-                        for _ in 0..20 {
-                            if let Ok(confirmed) = rpc.get_signature_status(&signature.parse().unwrap()) {
-                                if let Some(Ok(_)) = confirmed {
-                                    tracing::info!("💰 Trade Confirmed! Reporting +{} lamports", profit);
-                                    telemetry.log_trade_landed(opportunity.clone(), signature.clone(), true);
-                                    return;
-                                } else if let Some(Err(e)) = confirmed {
-                                    tracing::warn!("💸 Trade Failed on-chain: {}. Reporting loss.", e);
-                                    telemetry.log_trade_landed(opportunity.clone(), signature.clone(), false);
-                                    return;
-                                }
-                            }
-                            tokio::time::sleep(tokio::time::Duration::from_millis(3000)).await;
-                        }
-                        tracing::error!("⌛ Confirmation timeout for signature {}. PnL estimate uncertain.", signature);
-                    });
                 }
+                // The landed transaction is the trade legs plus fixed overhead; a flash-loan
+                // bundle also carries the loan legs, which only its simulation attributes
+                let landed_programs: Option<Vec<Pubkey>> = (opportunity.flash_loan_amount == 0)
+                    .then(|| ixs.iter().map(|ix| ix.program_id).collect());
+                let candidates = std::iter::once(sig.clone()).chain(rpc_copy).collect();
+                self.track_landing(opportunity, candidates, landed_programs, race_lease);
                 Ok(sig)
             }
-            Err(e) => {
+            (Err(e), Some(sig)) => {
+                // Only the raced copy went out; another fallback transaction could land the trade twice
+                tracing::warn!("🏁 All Jito endpoints failed ({}); the raced copy {} carries the trade", e, sig);
+                if let Some(ref tel) = self.telemetry {
+                    tel.log_jito_failed();
+                }
+                let landed_programs: Option<Vec<Pubkey>> = (opportunity.flash_loan_amount == 0)
+                    .then(|| ixs.iter().map(|ix| ix.program_id).collect());
+                self.track_landing(opportunity, vec![sig.clone()], landed_programs, race_lease);
+                Ok(sig)
+            }
+            (Err(e), None) => {
                 let jito_error = e.to_string();
                 drop(e);  // Explicitly drop to ensure Send
+                // Nothing was accepted: free the nonce. The bundles were still signed against its
+                // value, so until that advances the fallback goes out with a recent blockhash
+                drop(race_lease);
                 
                 if let Some(ref tel) = self.telemetry {
                     tel.log_jito_failed();
                 }
 
-                tracing::error!("❌ All Jito endpoints failed: {}. Attempting RPC fallback...", jito_error);
-                
-                // 🛡️ Helius Rescue: Use specialized Sender API if available (0 credits)
-                let sender = self.helius_sender_client.as_ref().unwrap_or(&self.rpc_client);
-                match self.send_as_standard_transaction_with_client(ixs, sender).await {
-                    Ok(sig) => {
-                        tracing::info!("✅ Fallback transaction succeeded via {}: {}", 
-                            if self.helius_sender_client.is_some() { "Helius Sender" } else { "Standard RPC" }, 
-                            sig
-                        );
+                // The fallback sends the bare legs; without the loan they would trade inventory we don't have
+                if opportunity.flash_loan_amount > 0 {
+                    return Err(anyhow::anyhow!("All Jito endpoints failed for flash-loan bundle: {}", jito_error));
+                }
+
+                tracing::error!("❌ All Jito endpoints failed: {}. Attempting fallback via {}...", jito_error, self.senders.describe());
+
+                let fallback_programs: Vec<Pubkey> = ixs.iter().map(|ix| ix.program_id).collect();
+                match self.send_fallback(ixs).await {
+                    Ok((kind, sig, nonce_lease)) => {
+                        tracing::info!("✅ Fallback transaction sent via {}: {}", kind.name(), sig);
                         if let Some(ref tel) = self.telemetry {
-                            tel.log_rpc_fallback_success();
+                            if kind == SenderKind::Tpu {
+                                tel.log_tpu_fallback_success();
+                            } else {
+                                tel.log_rpc_fallback_success();
+                            }
                         }
+                        // Settled like a bundle, so what it left in the wallet is reconciled too; the
+                        // nonce stays leased until then so the next fallback can't sign the same value
+                        self.track_landing(opportunity, vec![sig.clone()], Some(fallback_programs), nonce_lease);
                         Ok(sig)
                     }
-                    Err(rpc_err) => {
+                    Err(send_err) => {
                         if let Some(ref tel) = self.telemetry {
+                            if self.senders.contains(SenderKind::Tpu) {
+                                tel.log_tpu_fallback_failed();
+                            }
                             tel.log_rpc_fallback_failed();
                         }
                         Err(anyhow::anyhow!(
-                            "Both Jito and RPC execution failed. Jito: {}, RPC: {}", 
-                            jito_error, rpc_err
+                            "Both Jito and fallback execution failed. Jito: {}, Fallback: {}",
+                            jito_error, send_err
                         ))
                     }
                 }
@@ -648,32 +1123,170 @@ impl ExecutionPort for JitoExecutor {
 }
 
 impl JitoExecutor {
-    async fn send_as_standard_transaction(&self, ixs: Vec<solana_sdk::instruction::Instruction>) -> anyhow::Result<String> {
-        self.send_as_standard_transaction_with_client(ixs, &self.rpc_client).await
+    /// Pool keys with the payer's ATAs filled in
+    async fn raydium_clmm_keys(&self, provider: &dyn PoolKeyProvider, pool: &Pubkey) -> anyhow::Result<mev_core::raydium_clmm::RaydiumClmmSwapKeys> {
+        let mut keys = provider.get_raydium_clmm_keys(pool).await?;
+        keys.user_owner = self.payer_pubkey;
+        keys.user_token_0 = spl_associated_token_account::get_associated_token_address(&self.payer_pubkey, &keys.mint_0);
+        keys.user_token_1 = spl_associated_token_account::get_associated_token_address(&self.payer_pubkey, &keys.mint_1);
+        Ok(keys)
     }
 
-    async fn send_as_standard_transaction_with_client(
-        &self, 
-        ixs: Vec<solana_sdk::instruction::Instruction>,
-        client: &Arc<RpcClient>
-    ) -> anyhow::Result<String> {
-        let blockhash = client.get_latest_blockhash()?;
+    /// Market keys with the payer as trader and its ATAs filled in
+    async fn phoenix_keys(&self, provider: &dyn PoolKeyProvider, market: &Pubkey) -> anyhow::Result<mev_core::phoenix::PhoenixSwapKeys> {
+        let mut keys = provider.get_phoenix_keys(market).await?;
+        keys.trader = self.payer_pubkey;
+        keys.trader_base_account = spl_associated_token_account::get_associated_token_address(&self.payer_pubkey, &keys.base_mint);
+        keys.trader_quote_account = spl_associated_token_account::get_associated_token_address(&self.payer_pubkey, &keys.quote_mint);
+        Ok(keys)
+    }
+
+    /// Market keys with the payer as signer and its ATAs filled in
+    async fn openbook_keys(&self, provider: &dyn PoolKeyProvider, market: &Pubkey) -> anyhow::Result<mev_core::openbook::OpenBookSwapKeys> {
+        let mut keys = provider.get_openbook_keys(market).await?;
+        keys.signer = self.payer_pubkey;
+        keys.user_base_account = spl_associated_token_account::get_associated_token_address(&self.payer_pubkey, &keys.base_mint);
+        keys.user_quote_account = spl_associated_token_account::get_associated_token_address(&self.payer_pubkey, &keys.quote_mint);
+        Ok(keys)
+    }
+
+    /// Polls a sent trade until it confirms or fails (60s at most) and reports it for PnL tracking.
+    /// A raced trade has two `signatures`, the bundle's first; at most one of them can land.
+    /// `nonce_lease`, when the trade was signed against the nonce, is held until then.
+    fn track_landing(&self, opportunity: ArbitrageOpportunity, signatures: Vec<String>, landed_programs: Option<Vec<Pubkey>>, nonce_lease: Option<OwnedNonceLease>) {
+        let telemetry = self.telemetry.clone();
+        if telemetry.is_none() && nonce_lease.is_none() {
+            return;
+        }
+        let rpc = Arc::clone(&self.rpc);
+        let cu_profiles = Arc::clone(&self.cu_profiles);
+        let profit = opportunity.profit_in_sol();
+        let raced = signatures.len() > 1;
+        tokio::spawn(async move {
+            let _nonce_lease = nonce_lease;
+            for _ in 0..20 {
+                for (i, signature) in signatures.iter().enumerate() {
+                    let Ok(confirmed) = rpc.call_blocking(|c| c.get_signature_status(&signature.parse().unwrap())) else { continue };
+                    let landed = match confirmed {
+                        Some(Ok(_)) => true,
+                        Some(Err(e)) => {
+                            tracing::warn!("💸 Trade Failed on-chain: {}. Reporting loss.", e);
+                            false
+                        }
+                        None => continue,
+                    };
+                    if raced {
+                        let path = if i == 0 { "jito" } else { "rpc" };
+                        mev_core::telemetry::BUNDLE_RACE_WINNERS.with_label_values(&[path]).inc();
+                        if i > 0 {
+                            tracing::info!("🏁 The raced RPC copy landed ahead of bundle {}", signatures[0]);
+                            if let Some(ref telemetry) = telemetry {
+                                telemetry.log_race_winner(&signatures[0], signature);
+                            }
+                        }
+                    }
+                    if landed {
+                        tracing::info!("💰 Trade Confirmed! Reporting +{} lamports", profit);
+                        if let (Some(programs), Some(units)) = (&landed_programs, landed_units(&rpc, &signature.parse().unwrap())) {
+                            cu_profiles.observe(programs, units);
+                        }
+                    }
+                    if let Some(telemetry) = telemetry {
+                        telemetry.log_trade_landed(opportunity, signature.clone(), landed);
+                    }
+                    return;
+                }
+                tokio::time::sleep(tokio::time::Duration::from_millis(3000)).await;
+            }
+            tracing::error!("⌛ Confirmation timeout for signature {}. PnL estimate uncertain.", signatures.join(" / "));
+        });
+    }
+
+    /// Signs the bare trade legs as one transaction and hands it to the sender chain.
+    /// Signed against the nonce, the lease comes back with the signature: keep it until the trade settles.
+    async fn send_fallback(&self, ixs: Vec<solana_sdk::instruction::Instruction>) -> anyhow::Result<(SenderKind, String, Option<OwnedNonceLease>)> {
+        let mut nonce_lease = None;
+        let mut signed_with_nonce = None;
+        if let Some(nonce) = self.durable_nonce.as_ref().filter(|n| *n.authority() == self.payer_pubkey) {
+            match nonce.lease_owned() {
+                Some(lease) => match nonce.hash(&self.rpc).await {
+                    Ok(hash) if nonce.claim(hash) => {
+                        mev_core::telemetry::DURABLE_NONCE_SIGNS.with_label_values(&["nonce"]).inc();
+                        signed_with_nonce = Some((nonce.wrap(&ixs), hash));
+                        nonce_lease = Some(lease);
+                    }
+                    Ok(_) => {
+                        tracing::warn!("⚠️ Durable nonce not advanced since the last send, signing fallback with a recent blockhash");
+                        mev_core::telemetry::DURABLE_NONCE_SIGNS.with_label_values(&["stale"]).inc();
+                    }
+                    Err(e) => {
+                        tracing::warn!("⚠️ Durable nonce unreadable, signing fallback with a recent blockhash: {}", e);
+                        mev_core::telemetry::DURABLE_NONCE_SIGNS.with_label_values(&["unreadable"]).inc();
+                    }
+                },
+                None => mev_core::telemetry::DURABLE_NONCE_SIGNS.with_label_values(&["busy"]).inc(),
+            }
+        }
+        let (ixs, blockhash) = match signed_with_nonce {
+            Some(signed) => signed,
+            None => (ixs, self.rpc.call(|c| async move { c.get_latest_blockhash().await }).await?),
+        };
         let tx = Transaction::new_signed_with_payer(
             &ixs,
             Some(&self.payer_pubkey),
             &[self.auth_keypair.as_ref()],
             blockhash,
         );
-        match client.send_transaction(&tx) {
-            Ok(sig) => Ok(sig.to_string()),
-            Err(e) => Err(anyhow::anyhow!("RPC execution failed: {}", e)),
-        }
+        let (kind, signature) = self.senders.send(&tx).await?;
+        Ok((kind, signature.to_string(), nonce_lease))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use solana_sdk::system_instruction;
+    use crate::flash_loan::{SolendFlashLoanProvider, SolendReserve, SOLEND_PROGRAM_ID};
+
+    #[test]
+    fn test_flash_loan_repay_points_at_the_borrow_in_both_race_copies() {
+        let (payer, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let provider = SolendFlashLoanProvider::new(SOLEND_PROGRAM_ID, vec![SolendReserve {
+            address: Pubkey::new_unique(),
+            lending_market: Pubkey::new_unique(),
+            liquidity_mint: mint,
+            liquidity_supply: Pubkey::new_unique(),
+            fee_receiver: Pubkey::new_unique(),
+            flash_loan_fee_bps: 30,
+        }]);
+        let nonce = Arc::new(DurableNonce::new(Pubkey::new_unique(), payer));
+        let ticket = RaceTicket::new(nonce.lease_owned().unwrap(), Hash::new_unique());
+
+        let legs = vec![
+            system_instruction::transfer(&payer, &Pubkey::new_unique(), 1),
+            system_instruction::transfer(&payer, &Pubkey::new_unique(), 2),
+        ];
+        let tip = system_instruction::transfer(&payer, &Pubkey::new_unique(), 10_000);
+        let budget = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(400_000),
+            ComputeBudgetInstruction::set_compute_unit_price(5_000),
+        ];
+        let tip_last = [budget.clone(), legs.clone(), vec![tip.clone()]].concat();
+        let tip_first = [budget, vec![tip.clone()], legs.clone()].concat();
+
+        // Privacy may put the tip in front of the legs; the RPC copy drops it again
+        for arranged in [tip_last, tip_first] {
+            let bundle = ticket.wrap(&arranged);
+            for layout in [bundle.clone(), race::untipped(&bundle, &tip)] {
+                let ixs = bracket_flash_loan(&provider, &payer, &mint, 1_000, layout, legs.len(), &tip).unwrap();
+                let borrow = ixs.iter().position(|ix| ix.program_id == SOLEND_PROGRAM_ID).unwrap();
+                let repay = ixs.iter().rposition(|ix| ix.program_id == SOLEND_PROGRAM_ID).unwrap();
+                assert_eq!(ixs[repay].data.last(), Some(&(borrow as u8)));
+                assert_eq!(&ixs[borrow + 1..repay], legs.as_slice());
+                assert!(crate::nonce::is_advance(&ixs[0]));
+            }
+        }
+    }
 
     #[tokio::test]
     async fn test_jito_tip_floor_query() {
@@ -682,7 +1295,7 @@ mod tests {
         // For local verification, we can try a real query if possible.
         let auth = Keypair::new();
         let rpc = "https://api.mainnet-beta.solana.com";
-        let jito = match JitoExecutor::new("mainnet-beta.jito.wtf", &auth, rpc, None, None, None).await {
+        let jito = match JitoExecutor::new("mainnet-beta.jito.wtf", &auth, Arc::new(RpcPool::single(rpc)), FeeStrategy::Medium, None, None).await {
             Ok(j) => j,
             Err(_) => return, // Skip if no connection
         };
@@ -3,42 +3,109 @@
 /// This module sends transactions to the public mempool (RPC) instead of the 
 /// Jito Block Engine. This is your "Testing Mode" executor for development
 /// and non-MEV-sensitive operations.
-use solana_client::rpc_client::RpcClient;
+///
+/// Built with `paper-only`, the executor keeps its instruction builder and
+/// loses everything that signs or sends: it no longer implements
+/// `ExecutionPort`, so nothing can route a trade through it.
 use solana_sdk::{
     instruction::Instruction,
     signature::{Keypair, Signer},
-    transaction::Transaction,
     commitment_config::CommitmentConfig,
 };
-use std::error::Error;
+use std::sync::Arc;
+use crate::rpc_pool::RpcPool;
+use crate::token_accounts::{AtaCache, TokenAccountPolicy};
+#[cfg(not(feature = "paper-only"))]
+use {
+    solana_client::rpc_config::RpcSimulateTransactionConfig,
+    solana_sdk::{hash::Hash, transaction::Transaction},
+    std::error::Error,
+    crate::nonce::{DurableNonce, NonceLease},
+};
 
 /// Legacy executor using standard Solana RPC
 pub struct LegacyExecutor {
-    client: RpcClient,
+    rpc: Arc<RpcPool>,
+    commitment: CommitmentConfig,
+    #[cfg_attr(feature = "paper-only", allow(dead_code))]
     payer: solana_sdk::signature::Keypair,
     payer_pubkey: solana_sdk::pubkey::Pubkey,
     key_provider: Option<std::sync::Arc<dyn strategy::ports::PoolKeyProvider>>,
+    token_accounts: Option<(TokenAccountPolicy, Arc<AtaCache>)>,
+    #[cfg(not(feature = "paper-only"))]
+    durable_nonce: Option<Arc<DurableNonce>>,
 }
 
 impl LegacyExecutor {
     /// Create a new legacy executor
     ///
     /// # Arguments
-    /// * `rpc_url` - Solana RPC endpoint (e.g., "https://api.mainnet-beta.solana.com")
+    /// * `rpc` - Solana RPC endpoints (e.g., "https://api.mainnet-beta.solana.com")
     ///
     /// # Returns
     /// Configured executor with confirmed commitment level
     pub fn new(
-        rpc_url: &str,
+        rpc: Arc<RpcPool>,
         payer: solana_sdk::signature::Keypair,
         key_provider: Option<std::sync::Arc<dyn strategy::ports::PoolKeyProvider>>,
     ) -> Self {
-        let client = RpcClient::new_with_commitment(
-            rpc_url.to_string(),
-            CommitmentConfig::confirmed(),
-        );
         let payer_pubkey = payer.pubkey();
-        Self { client, payer, payer_pubkey, key_provider }
+        Self {
+            rpc,
+            commitment: CommitmentConfig::confirmed(),
+            payer,
+            payer_pubkey,
+            key_provider,
+            token_accounts: None,
+            #[cfg(not(feature = "paper-only"))]
+            durable_nonce: None,
+        }
+    }
+
+    /// Bracket every transaction's legs with the ATA creation and WSOL wrap/unwrap they need
+    pub fn with_token_accounts(mut self, policy: TokenAccountPolicy, cache: Arc<AtaCache>) -> Self {
+        self.token_accounts = Some((policy, cache));
+        self
+    }
+}
+
+#[cfg(not(feature = "paper-only"))]
+impl LegacyExecutor {
+    /// Sign against this nonce instead of a recent blockhash whenever the payer is its authority
+    pub fn with_durable_nonce(mut self, nonce: Arc<DurableNonce>) -> Self {
+        self.durable_nonce = Some(nonce);
+        self
+    }
+
+    /// What `ixs` are signed with: behind a nonce advance when the payer holds a free, readable
+    /// nonce, otherwise as-is against the latest blockhash. Keep the lease until the send is done.
+    fn signing_inputs(
+        &self,
+        payer: &Keypair,
+        ixs: &[Instruction],
+        commitment: CommitmentConfig,
+    ) -> Result<(Vec<Instruction>, Hash, Option<NonceLease<'_>>), Box<dyn Error>> {
+        if let Some(nonce) = self.durable_nonce.as_deref().filter(|n| *n.authority() == payer.pubkey()) {
+            match nonce.lease() {
+                Some(lease) => match nonce.hash_blocking(&self.rpc) {
+                    Ok(hash) if nonce.claim(hash) => {
+                        mev_core::telemetry::DURABLE_NONCE_SIGNS.with_label_values(&["nonce"]).inc();
+                        return Ok((nonce.wrap(ixs), hash, Some(lease)));
+                    }
+                    Ok(_) => {
+                        tracing::warn!("⚠️ Durable nonce not advanced since the last send, signing with a recent blockhash");
+                        mev_core::telemetry::DURABLE_NONCE_SIGNS.with_label_values(&["stale"]).inc();
+                    }
+                    Err(e) => {
+                        tracing::warn!("⚠️ Durable nonce unreadable, signing with a recent blockhash: {}", e);
+                        mev_core::telemetry::DURABLE_NONCE_SIGNS.with_label_values(&["unreadable"]).inc();
+                    }
+                },
+                None => mev_core::telemetry::DURABLE_NONCE_SIGNS.with_label_values(&["busy"]).inc(),
+            }
+        }
+        let recent_blockhash = self.rpc.call_blocking(|c| c.get_latest_blockhash_with_commitment(commitment))?.0;
+        Ok((ixs.to_vec(), recent_blockhash, None))
     }
 
     /// Execute a standard transaction via RPC
@@ -65,12 +132,12 @@ impl LegacyExecutor {
         payer: &Keypair,
         ixs: &[Instruction],
     ) -> Result<String, Box<dyn Error>> {
-        // 1. Get latest blockhash (recent check required for all transactions)
-        let recent_blockhash = self.client.get_latest_blockhash()?;
+        // 1. Get latest blockhash, or the durable nonce (recent check required for all transactions)
+        let (ixs, recent_blockhash, _nonce_lease) = self.signing_inputs(payer, ixs, self.commitment)?;
 
         // 2. Build Transaction
         let tx = Transaction::new_signed_with_payer(
-            ixs,
+            &ixs,
             Some(&payer.pubkey()),
             &[payer], // Signers
             recent_blockhash,
@@ -79,7 +146,10 @@ impl LegacyExecutor {
         // 🛡️ SAFETY ADDITION: PRE-FLIGHT SIMULATION
         // Ask the node: "If I ran this, would it work?"
         tracing::debug!("🕵️ Simulating transaction...");
-        let simulation = self.client.simulate_transaction(&tx)?;
+        let simulation = self.rpc.call_blocking(|c| c.simulate_transaction_with_config(&tx, RpcSimulateTransactionConfig {
+            commitment: Some(self.commitment),
+            ..Default::default()
+        }))?;
         
         if let Some(err) = simulation.value.err {
             // If simulation fails, WE ABORT. We do not send it.
@@ -93,7 +163,7 @@ impl LegacyExecutor {
         // 3. Send and Confirm
         // We use send_and_confirm for testing reliability. 
         // In production, use send_transaction with a custom confirmation loop.
-        let signature = self.client.send_and_confirm_transaction(&tx)?;
+        let signature = self.rpc.call_blocking(|c| c.send_and_confirm_transaction_with_spinner_and_commitment(&tx, self.commitment))?;
 
         Ok(signature.to_string())
     }
@@ -106,16 +176,16 @@ impl LegacyExecutor {
         payer: &Keypair,
         ixs: &[Instruction],
     ) -> Result<String, Box<dyn Error>> {
-        let recent_blockhash = self.client.get_latest_blockhash()?;
+        let (ixs, recent_blockhash, _nonce_lease) = self.signing_inputs(payer, ixs, self.commitment)?;
 
         let tx = Transaction::new_signed_with_payer(
-            ixs,
+            &ixs,
             Some(&payer.pubkey()),
             &[payer],
             recent_blockhash,
         );
 
-        let signature = self.client.send_transaction(&tx)?;
+        let signature = self.rpc.call_blocking(|c| c.send_transaction(&tx))?;
 
         Ok(signature.to_string())
     }
@@ -127,28 +197,33 @@ impl LegacyExecutor {
         ixs: &[Instruction],
         commitment: CommitmentConfig,
     ) -> Result<String, Box<dyn Error>> {
-        let recent_blockhash = self.client
-            .get_latest_blockhash_with_commitment(commitment)?
-            .0;
+        let (ixs, recent_blockhash, _nonce_lease) = self.signing_inputs(payer, ixs, commitment)?;
 
         let tx = Transaction::new_signed_with_payer(
-            ixs,
+            &ixs,
             Some(&payer.pubkey()),
             &[payer],
             recent_blockhash,
         );
 
-        let signature = self.client.send_and_confirm_transaction_with_spinner_and_commitment(
+        let signature = self.rpc.call_blocking(|c| c.send_and_confirm_transaction_with_spinner_and_commitment(
             &tx,
             commitment,
-        )?;
+        ))?;
 
         Ok(signature.to_string())
     }
+}
+
+impl LegacyExecutor {
+    /// Get the RPC pool for advanced usage
+    pub fn rpc(&self) -> &Arc<RpcPool> {
+        &self.rpc
+    }
 
-    /// Get reference to underlying RPC client for advanced usage
-    pub fn client(&self) -> &RpcClient {
-        &self.client
+    /// Commitment used for blockhashes, simulation and confirmation
+    pub fn commitment(&self) -> CommitmentConfig {
+        self.commitment
     }
 }
 
@@ -177,25 +252,52 @@ impl strategy::ports::PoolKeyProvider for LegacyExecutor {
             Err(anyhow::anyhow!("No PoolKeyProvider configured for LegacyExecutor"))
         }
     }
+
+    async fn get_raydium_clmm_keys(&self, pool_address: &solana_sdk::pubkey::Pubkey) -> anyhow::Result<mev_core::raydium_clmm::RaydiumClmmSwapKeys> {
+        if let Some(provider) = &self.key_provider {
+            provider.get_raydium_clmm_keys(pool_address).await
+        } else {
+            Err(anyhow::anyhow!("No PoolKeyProvider configured for LegacyExecutor"))
+        }
+    }
+
+    async fn get_phoenix_keys(&self, market: &solana_sdk::pubkey::Pubkey) -> anyhow::Result<mev_core::phoenix::PhoenixSwapKeys> {
+        if let Some(provider) = &self.key_provider {
+            provider.get_phoenix_keys(market).await
+        } else {
+            Err(anyhow::anyhow!("No PoolKeyProvider configured for LegacyExecutor"))
+        }
+    }
+
+    async fn get_openbook_keys(&self, market: &solana_sdk::pubkey::Pubkey) -> anyhow::Result<mev_core::openbook::OpenBookSwapKeys> {
+        if let Some(provider) = &self.key_provider {
+            provider.get_openbook_keys(market).await
+        } else {
+            Err(anyhow::anyhow!("No PoolKeyProvider configured for LegacyExecutor"))
+        }
+    }
 }
 
-#[async_trait::async_trait]
-impl strategy::ports::ExecutionPort for LegacyExecutor {
-    async fn build_bundle_instructions(
+impl LegacyExecutor {
+    /// The route's swap legs (and token-account setup), unsigned; no tip, since nothing here reaches a block engine
+    pub async fn build_instructions(
         &self,
         opportunity: mev_core::ArbitrageOpportunity,
-        _tip_lamports: u64,
         max_slippage_bps: u16,
     ) -> anyhow::Result<Vec<Instruction>> {
         let mut ixs = Vec::new();
         let mut current_amount_in = opportunity.input_amount;
-        let min_amount_out = (opportunity.input_amount as u128 * (10000 - max_slippage_bps) as u128 / 10000) as u64;
+        let min_amount_out = opportunity.min_amount_out(max_slippage_bps);
 
         let num_steps = opportunity.steps.len();
 
         for (i, step) in opportunity.steps.iter().enumerate() {
             let is_last_step = i == num_steps - 1;
             let step_min_out = if is_last_step { min_amount_out } else { 0 };
+            // A leg split across pools gives each part its own share of the leg's input
+            if let Some(&amount) = opportunity.step_inputs.get(i) {
+                current_amount_in = amount;
+            }
 
             if step.program_id == mev_core::constants::RAYDIUM_V4_PROGRAM {
                 let keys = strategy::ports::PoolKeyProvider::get_swap_keys(self, &step.pool).await?;
@@ -215,13 +317,55 @@ impl strategy::ports::ExecutionPort for LegacyExecutor {
                     true,
                     a_to_b,
                 ));
+            } else if step.program_id == mev_core::constants::RAYDIUM_CLMM_PROGRAM {
+                let mut keys = strategy::ports::PoolKeyProvider::get_raydium_clmm_keys(self, &step.pool).await?;
+                keys.user_owner = self.payer_pubkey;
+                keys.user_token_0 = spl_associated_token_account::get_associated_token_address(&self.payer_pubkey, &keys.mint_0);
+                keys.user_token_1 = spl_associated_token_account::get_associated_token_address(&self.payer_pubkey, &keys.mint_1);
+                let zero_for_one = step.input_mint == keys.mint_0;
+                ixs.push(crate::raydium_clmm_builder::swap(&keys, current_amount_in, step_min_out, 0, zero_for_one));
+            } else if step.program_id == mev_core::constants::PHOENIX_PROGRAM {
+                let mut keys = strategy::ports::PoolKeyProvider::get_phoenix_keys(self, &step.pool).await?;
+                keys.trader = self.payer_pubkey;
+                keys.trader_base_account = spl_associated_token_account::get_associated_token_address(&self.payer_pubkey, &keys.base_mint);
+                keys.trader_quote_account = spl_associated_token_account::get_associated_token_address(&self.payer_pubkey, &keys.quote_mint);
+                let base_in = step.input_mint == keys.base_mint;
+                ixs.push(crate::phoenix_builder::swap(&keys, current_amount_in, step_min_out, base_in));
+            } else if step.program_id == mev_core::constants::OPENBOOK_V2_PROGRAM {
+                let mut keys = strategy::ports::PoolKeyProvider::get_openbook_keys(self, &step.pool).await?;
+                keys.signer = self.payer_pubkey;
+                keys.user_base_account = spl_associated_token_account::get_associated_token_address(&self.payer_pubkey, &keys.base_mint);
+                keys.user_quote_account = spl_associated_token_account::get_associated_token_address(&self.payer_pubkey, &keys.quote_mint);
+                let base_in = step.input_mint == keys.base_mint;
+                ixs.push(crate::openbook_builder::place_take_order(&keys, current_amount_in, step_min_out, base_in));
+            } else if step.program_id == mev_core::constants::PUMP_FUN_PROGRAM {
+                let (leg, received) = crate::pump_fun_builder::swap_leg(self.payer_pubkey, step, current_amount_in, step_min_out, max_slippage_bps);
+                ixs.extend(leg);
+                current_amount_in = received;
+                continue;
             }
             
             // Track amount for multi-hop
             current_amount_in = step.expected_output;
         }
 
-        Ok(ixs)
+        Ok(match &self.token_accounts {
+            Some((policy, cache)) => crate::token_accounts::setup(&self.payer_pubkey, &opportunity, cache, *policy).around(ixs),
+            None => ixs,
+        })
+    }
+}
+
+#[cfg(not(feature = "paper-only"))]
+#[async_trait::async_trait]
+impl strategy::ports::ExecutionPort for LegacyExecutor {
+    async fn build_bundle_instructions(
+        &self,
+        opportunity: mev_core::ArbitrageOpportunity,
+        _tip_lamports: u64,
+        max_slippage_bps: u16,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        self.build_instructions(opportunity, max_slippage_bps).await
     }
 
     async fn build_and_send_bundle(
@@ -231,6 +375,9 @@ impl strategy::ports::ExecutionPort for LegacyExecutor {
         tip_lamports: u64,
         max_slippage_bps: u16,
     ) -> anyhow::Result<String> {
+        if opportunity.flash_loan_amount > 0 {
+            return Err(anyhow::anyhow!("Flash-loan funded opportunities require the Jito executor"));
+        }
         let ixs = self.build_bundle_instructions(opportunity, tip_lamports, max_slippage_bps).await?;
         
         match self.execute_standard_tx(&self.payer, &ixs) {
@@ -247,23 +394,25 @@ impl strategy::ports::ExecutionPort for LegacyExecutor {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use solana_sdk::{system_instruction, pubkey::Pubkey};
 
     #[test]
     fn test_executor_creation() {
-        let executor = LegacyExecutor::new("https://api.mainnet-beta.solana.com", Keypair::new(), None);
+        let executor = LegacyExecutor::new(Arc::new(RpcPool::single("https://api.mainnet-beta.solana.com")), Keypair::new(), None);
         // Should create without errors
-        assert!(executor.client().commitment() == CommitmentConfig::confirmed());
+        assert!(executor.commitment() == CommitmentConfig::confirmed());
     }
 
     #[test]
     #[ignore] // Requires live RPC connection
+    #[cfg(not(feature = "paper-only"))]
     fn test_execute_transfer() {
+        use solana_sdk::{system_instruction, pubkey::Pubkey};
+
         // This test requires a live RPC connection and funded account
         // Run with: cargo test --package executor -- --ignored
 
         let payer = Keypair::new();
-        let executor = LegacyExecutor::new("https://api.mainnet-beta.solana.com", Keypair::from_bytes(&payer.to_bytes()).unwrap(), None);
+        let executor = LegacyExecutor::new(Arc::new(RpcPool::single("https://api.mainnet-beta.solana.com")), Keypair::from_bytes(&payer.to_bytes()).unwrap(), None);
         
         let instruction = system_instruction::transfer(
             &payer.pubkey(),
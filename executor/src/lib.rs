@@ -1,11 +1,42 @@
 pub mod raydium_builder;  // ✅ Raydium V4 swap factory
 pub mod orca_builder;     // ✅ Orca Whirlpool swap
+pub mod raydium_clmm_builder; // ✅ Raydium CLMM swap
 pub mod pump_fun_builder;  // ✅ Pump.fun bonding curve swap
 pub mod meteora_builder;   // ✅ Meteora DLMM swap
-pub mod legacy;           // ✅ Standard RPC executor
+pub mod phoenix_builder;   // ✅ Phoenix IOC swap
+pub mod openbook_builder;  // ✅ OpenBook v2 take order
+pub mod legacy;           // ✅ Standard RPC executor (instruction builder only under paper-only)
+#[cfg(not(feature = "paper-only"))]
 pub mod jito;             // ✅ Jito bundle executor
+pub mod bundle_tracker;   // ✅ Jito bundle status / land-rate tracking
+pub mod endpoint_health;  // ✅ Benching and re-probing of failing Jito endpoints
+pub mod channel_warmer;   // ✅ HTTP/2 keepalive, warm-up calls and idle reconnects for Jito channels
+pub mod privacy;          // ✅ Order-flow privacy mode
+pub mod tip_policy;       // ✅ Tip floor percentile selection
+pub mod jupiter;          // ✅ Jupiter quote API source for the quote cross-check
+#[cfg(not(feature = "paper-only"))]
+pub mod lookup_table;     // ✅ Address lookup tables for v0 bundles
+pub mod compute_budget;   // ✅ Simulated CU limit and fee-budgeted CU price
+pub mod cu_profile;       // ✅ Measured CU per venue for unsimulated budgets
+#[cfg(not(feature = "paper-only"))]
+pub mod flash_loan;       // ✅ Solend flash-loan legs
+pub mod token_accounts;   // ✅ ATA creation and WSOL wrap/unwrap around bundle legs
+pub mod http;             // ✅ Shared outbound HTTP client with per-host policies
+pub mod rpc_pool;         // ✅ Health-scored multi-endpoint RPC failover
+#[cfg(not(feature = "paper-only"))]
+pub mod tpu;              // ✅ Direct QUIC send to upcoming leaders
+pub mod bundle_sim;       // ✅ simulateTransaction / Jito simulateBundle adapters for the simulation gate
+#[cfg(not(feature = "paper-only"))]
+pub mod nonce;            // ✅ Durable-nonce signing for fallback transactions
+#[cfg(not(feature = "paper-only"))]
+pub mod race;             // ✅ Jito bundle raced against an untipped Helius Sender copy
+#[cfg(not(feature = "paper-only"))]
+pub mod tx_sender;        // ✅ Pluggable fallback senders (RPC, Helius, Triton, bloXroute, TPU)
+pub mod paper;            // ✅ Paper-trading executor for Simulation mode
+pub mod publisher;        // ✅ Webhook/NATS publishing for external execution
+pub mod rng;              // ✅ Seedable shared RNG for reproducible simulation runs
 
-#[cfg(test)]
+#[cfg(all(test, not(feature = "paper-only")))]
 mod jito_resilience_tests;
 #[cfg(test)]
 mod orca_tests;
@@ -0,0 +1,219 @@
+/// Address Lookup Tables
+///
+/// A 4-5 leg Raydium+Orca route references more accounts than fit in a legacy
+/// transaction (1232 bytes, 32 per key). When a bundle would overflow, it is
+/// compiled as a v0 message against our lookup table, which replaces most keys
+/// with one-byte indexes. The table is kept warm from usage: accounts that keep
+/// showing up in our bundles are appended to it in the background.
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    address_lookup_table::{
+        instruction::{create_lookup_table, extend_lookup_table},
+        state::AddressLookupTable,
+        AddressLookupTableAccount,
+    },
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, Message, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, VersionedTransaction},
+};
+
+/// Hard cap on addresses in one lookup table
+const MAX_TABLE_ADDRESSES: usize = 256;
+/// Addresses appended per extend transaction (keeps it under the packet limit)
+const EXTEND_BATCH: usize = 20;
+
+/// Serialized size of `ixs` as a legacy transaction signed by `payer` alone
+pub fn legacy_transaction_size(ixs: &[Instruction], payer: &Pubkey) -> usize {
+    let message = Message::new(ixs, Some(payer));
+    let signatures = message.header.num_required_signatures as usize;
+    // shortvec signature count + signatures + message
+    1 + signatures * 64 + message.serialize().len()
+}
+
+pub fn fits_legacy(ixs: &[Instruction], payer: &Pubkey) -> bool {
+    legacy_transaction_size(ixs, payer) <= PACKET_DATA_SIZE
+}
+
+pub struct LookupTableManager {
+    rpc: Arc<RpcClient>,
+    authority: Arc<Keypair>,
+    address: RwLock<Option<Pubkey>>,
+    table: RwLock<Option<AddressLookupTableAccount>>,
+    usage: Mutex<HashMap<Pubkey, u32>>,
+    /// Bundles an account must appear in before it is added to the table
+    min_uses: u32,
+}
+
+impl LookupTableManager {
+    pub fn new(rpc: Arc<RpcClient>, authority: Arc<Keypair>, address: Option<Pubkey>, min_uses: u32) -> Self {
+        Self {
+            rpc,
+            authority,
+            address: RwLock::new(address),
+            table: RwLock::new(None),
+            usage: Mutex::new(HashMap::new()),
+            min_uses: min_uses.max(1),
+        }
+    }
+
+    pub fn address(&self) -> Option<Pubkey> {
+        *self.address.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Creates a new table owned by the payer. Costs rent; only called when auto-create is on.
+    pub fn create(&self) -> anyhow::Result<Pubkey> {
+        let payer = self.authority.pubkey();
+        let recent_slot = self.rpc.get_slot()?;
+        let (ix, address) = create_lookup_table(payer, payer, recent_slot);
+        self.send(&[ix])?;
+        tracing::info!("📇 Created address lookup table {}", address);
+        *self.address.write().unwrap_or_else(|e| e.into_inner()) = Some(address);
+        Ok(address)
+    }
+
+    /// Re-reads the table from chain so newly appended addresses become usable
+    pub fn refresh(&self) -> anyhow::Result<usize> {
+        let Some(address) = self.address() else {
+            return Ok(0);
+        };
+        let account = self.rpc.get_account(&address)?;
+        let table = AddressLookupTable::deserialize(&account.data)
+            .map_err(|e| anyhow::anyhow!("Bad lookup table {}: {}", address, e))?;
+        let addresses = table.addresses.to_vec();
+        let len = addresses.len();
+        *self.table.write().unwrap_or_else(|e| e.into_inner()) = Some(AddressLookupTableAccount { key: address, addresses });
+        Ok(len)
+    }
+
+    /// Counts the accounts of a bundle towards table membership. Signers and programs are skipped:
+    /// signers can't be looked up and programs are cheap to keep inline.
+    pub fn record_usage(&self, ixs: &[Instruction]) {
+        let keys: HashSet<Pubkey> = ixs.iter()
+            .flat_map(|ix| ix.accounts.iter().filter(|a| !a.is_signer).map(|a| a.pubkey))
+            .collect();
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        for key in keys {
+            *usage.entry(key).or_insert(0) += 1;
+        }
+    }
+
+    /// Accounts used often enough that are not in the table yet, most used first
+    fn hot_accounts(&self) -> Vec<Pubkey> {
+        let known: HashSet<Pubkey> = self.table.read().unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|t| t.addresses.iter().copied().collect())
+            .unwrap_or_default();
+        let room = MAX_TABLE_ADDRESSES.saturating_sub(known.len());
+
+        let usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        let mut hot: Vec<(Pubkey, u32)> = usage.iter()
+            .filter(|(k, &n)| n >= self.min_uses && !known.contains(*k))
+            .map(|(k, &n)| (*k, n))
+            .collect();
+        hot.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+        hot.into_iter().take(room.min(EXTEND_BATCH)).map(|(k, _)| k).collect()
+    }
+
+    /// Appends one batch of hot accounts. Returns how many were added.
+    pub fn extend_hot(&self) -> anyhow::Result<usize> {
+        let Some(address) = self.address() else {
+            return Ok(0);
+        };
+        let hot = self.hot_accounts();
+        if hot.is_empty() {
+            return Ok(0);
+        }
+        let payer = self.authority.pubkey();
+        self.send(&[extend_lookup_table(address, payer, Some(payer), hot.clone())])?;
+        tracing::info!("📇 Extended lookup table {} with {} accounts", address, hot.len());
+        Ok(hot.len())
+    }
+
+    /// Refresh + extend loop; RPC calls are blocking so each round runs off the async workers
+    pub async fn run(self: Arc<Self>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let this = Arc::clone(&self);
+            let round = tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+                this.refresh()?;
+                this.extend_hot()?;
+                Ok(())
+            }).await;
+            match round {
+                Ok(Err(e)) => tracing::warn!("📇 Lookup table maintenance failed: {}", e),
+                Err(e) => tracing::warn!("📇 Lookup table maintenance panicked: {}", e),
+                Ok(Ok(())) => {}
+            }
+        }
+    }
+
+    /// Compiles `ixs` as a signed v0 transaction against the cached table
+    pub fn compile_v0(&self, ixs: &[Instruction], signer: &Keypair, blockhash: Hash) -> anyhow::Result<VersionedTransaction> {
+        let tables: Vec<AddressLookupTableAccount> = self.table.read().unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect();
+        let message = v0::Message::try_compile(&signer.pubkey(), ixs, &tables, blockhash)?;
+        Ok(VersionedTransaction::try_new(VersionedMessage::V0(message), &[signer])?)
+    }
+
+    fn send(&self, ixs: &[Instruction]) -> anyhow::Result<()> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&self.authority.pubkey()), &[self.authority.as_ref()], blockhash);
+        self.rpc.send_and_confirm_transaction(&tx)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+
+    fn wide_ix(accounts: usize) -> Instruction {
+        Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[0u8; 17],
+            (0..accounts).map(|_| AccountMeta::new(Pubkey::new_unique(), false)).collect(),
+        )
+    }
+
+    #[test]
+    fn test_five_leg_route_overflows_legacy() {
+        let payer = Pubkey::new_unique();
+        assert!(fits_legacy(&[wide_ix(18), wide_ix(18)], &payer));
+
+        // Raydium swaps carry ~18 accounts each
+        let five_legs: Vec<Instruction> = (0..5).map(|_| wide_ix(18)).collect();
+        assert!(!fits_legacy(&five_legs, &payer));
+    }
+
+    #[test]
+    fn test_v0_with_table_fits_where_legacy_does_not() {
+        let signer = Keypair::new();
+        let rpc = Arc::new(RpcClient::new("http://localhost:8899".to_string()));
+        let manager = LookupTableManager::new(rpc, Arc::new(Keypair::from_bytes(&signer.to_bytes()).unwrap()), Some(Pubkey::new_unique()), 1);
+
+        let five_legs: Vec<Instruction> = (0..5).map(|_| wide_ix(18)).collect();
+        manager.record_usage(&five_legs);
+        let hot = manager.hot_accounts();
+        assert_eq!(hot.len(), EXTEND_BATCH);
+
+        // Pretend every account already made it into the table
+        let addresses = five_legs.iter().flat_map(|ix| ix.accounts.iter().map(|a| a.pubkey)).collect();
+        *manager.table.write().unwrap() = Some(AddressLookupTableAccount { key: manager.address().unwrap(), addresses });
+        assert!(manager.hot_accounts().is_empty());
+
+        let tx = manager.compile_v0(&five_legs, &signer, Hash::default()).unwrap();
+        let size = tx.signatures.len() * 64 + tx.message.serialize().len() + 1;
+        assert!(size <= PACKET_DATA_SIZE, "v0 size {}", size);
+    }
+}
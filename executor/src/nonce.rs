@@ -0,0 +1,188 @@
+/// Durable Nonce
+///
+/// A fallback transaction signed against a recent blockhash is dead ~150
+/// slots later, which in a congested stretch can be before any retry lands.
+/// Signed against a durable nonce instead it stays valid until the nonce
+/// account advances. Each transaction starts with `AdvanceNonceAccount`, so
+/// the nonce moves on as soon as one lands (or fails on-chain) and the next
+/// send reads the fresh value; nothing has to advance it by hand.
+///
+/// One nonce signs one transaction at a time: a send that starts while
+/// another is in progress would reuse the same value and at most one of the
+/// two could land, so it takes the latest blockhash instead. A sent trade
+/// keeps the lease until it settles, and a value that was already signed is
+/// never signed again: until the chain advances it, the next send takes the
+/// latest blockhash too. The account is created beforehand
+/// (`solana create-nonce-account`) with the payer as its authority.
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::nonce::state::{State, Versions};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction::{self, SystemInstruction};
+use crate::rpc_pool::RpcPool;
+
+pub struct DurableNonce {
+    account: Pubkey,
+    authority: Pubkey,
+    in_use: AtomicBool,
+    /// Value the last transaction was signed against
+    last_signed: Mutex<Option<Hash>>,
+}
+
+/// Exclusive use of the nonce until dropped
+pub struct NonceLease<'a> {
+    nonce: &'a DurableNonce,
+}
+
+impl Drop for NonceLease<'_> {
+    fn drop(&mut self) {
+        self.nonce.in_use.store(false, Ordering::Release);
+    }
+}
+
+/// A lease that can outlive the send, e.g. held until a raced trade settles
+pub struct OwnedNonceLease {
+    nonce: Arc<DurableNonce>,
+}
+
+impl OwnedNonceLease {
+    pub fn nonce(&self) -> &DurableNonce {
+        &self.nonce
+    }
+}
+
+impl Drop for OwnedNonceLease {
+    fn drop(&mut self) {
+        self.nonce.in_use.store(false, Ordering::Release);
+    }
+}
+
+impl DurableNonce {
+    pub fn new(account: Pubkey, authority: Pubkey) -> Self {
+        Self { account, authority, in_use: AtomicBool::new(false), last_signed: Mutex::new(None) }
+    }
+
+    pub fn account(&self) -> &Pubkey {
+        &self.account
+    }
+
+    pub fn authority(&self) -> &Pubkey {
+        &self.authority
+    }
+
+    /// None while another transaction is signed against the nonce
+    pub fn lease(&self) -> Option<NonceLease<'_>> {
+        self.in_use
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| NonceLease { nonce: self })
+    }
+
+    /// Same as `lease`, without borrowing the nonce
+    pub fn lease_owned(self: &Arc<Self>) -> Option<OwnedNonceLease> {
+        self.in_use
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| OwnedNonceLease { nonce: Arc::clone(self) })
+    }
+
+    /// Records `hash` as signed against. False when the last transaction already used it:
+    /// that one has not advanced the nonce yet and may still land, so `hash` is spent.
+    pub fn claim(&self, hash: Hash) -> bool {
+        let mut last = self.last_signed.lock().unwrap_or_else(|e| e.into_inner());
+        if *last == Some(hash) {
+            return false;
+        }
+        *last = Some(hash);
+        true
+    }
+
+    /// `ixs` behind the advance instruction, which must come first
+    pub fn wrap(&self, ixs: &[Instruction]) -> Vec<Instruction> {
+        let mut wrapped = Vec::with_capacity(ixs.len() + 1);
+        wrapped.push(system_instruction::advance_nonce_account(&self.account, &self.authority));
+        wrapped.extend_from_slice(ixs);
+        wrapped
+    }
+
+    /// Current nonce value, to sign with in place of a recent blockhash
+    pub async fn hash(&self, rpc: &RpcPool) -> anyhow::Result<Hash> {
+        let account = self.account;
+        let data = rpc.call(|c| async move { c.get_account_data(&account).await }).await?;
+        self.parse(&data)
+    }
+
+    pub fn hash_blocking(&self, rpc: &RpcPool) -> anyhow::Result<Hash> {
+        let data = rpc.call_blocking(|c| c.get_account_data(&self.account))?;
+        self.parse(&data)
+    }
+
+    fn parse(&self, data: &[u8]) -> anyhow::Result<Hash> {
+        let (authority, hash) = nonce_state(data)
+            .ok_or_else(|| anyhow::anyhow!("{} is not an initialized nonce account", self.account))?;
+        if authority != self.authority {
+            return Err(anyhow::anyhow!("nonce {} is controlled by {}, not the payer {}", self.account, authority, self.authority));
+        }
+        Ok(hash)
+    }
+}
+
+/// True for an `AdvanceNonceAccount` instruction, whichever nonce it advances
+pub fn is_advance(ix: &Instruction) -> bool {
+    ix.program_id == solana_sdk::system_program::id()
+        && matches!(bincode::deserialize::<SystemInstruction>(&ix.data), Ok(SystemInstruction::AdvanceNonceAccount))
+}
+
+/// Authority and stored nonce of an initialized nonce account
+pub fn nonce_state(data: &[u8]) -> Option<(Pubkey, Hash)> {
+    let versions: Versions = bincode::deserialize(data).ok()?;
+    match versions.state() {
+        State::Initialized(data) => Some((data.authority, data.blockhash())),
+        State::Uninitialized => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::nonce::state::{Data, DurableNonce as StoredNonce};
+
+    #[test]
+    fn test_signs_against_the_stored_nonce_after_advancing_it() {
+        let (account, payer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let stored = Data::new(payer, StoredNonce::from_blockhash(&Hash::new_unique()), 5_000);
+        let data = bincode::serialize(&Versions::new(State::Initialized(stored.clone()))).unwrap();
+        assert_eq!(nonce_state(&data), Some((payer, stored.blockhash())));
+
+        let nonce = DurableNonce::new(account, payer);
+        assert_eq!(nonce.parse(&data).unwrap(), stored.blockhash());
+        assert!(DurableNonce::new(account, Pubkey::new_unique()).parse(&data).is_err());
+        let empty = bincode::serialize(&Versions::new(State::Uninitialized)).unwrap();
+        assert!(nonce.parse(&empty).is_err());
+
+        let leg = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let wrapped = nonce.wrap(&[leg.clone()]);
+        assert_eq!(wrapped[0], system_instruction::advance_nonce_account(&account, &payer));
+        assert_eq!(wrapped[1], leg);
+        assert!(is_advance(&wrapped[0]) && !is_advance(&leg));
+
+        // One transaction at a time; the nonce frees up when the lease drops
+        let lease = nonce.lease();
+        assert!(lease.is_some() && nonce.lease().is_none());
+        drop(lease);
+        assert!(nonce.lease().is_some());
+        let shared = Arc::new(nonce);
+        let owned = shared.lease_owned();
+        assert!(owned.is_some() && shared.lease().is_none());
+        drop(owned);
+        assert!(shared.lease_owned().is_some());
+
+        // A value is signed once; the next one is free again after the chain advances it
+        let (first, advanced) = (Hash::new_unique(), Hash::new_unique());
+        assert!(shared.claim(first));
+        assert!(!shared.claim(first));
+        assert!(shared.claim(advanced));
+    }
+}
@@ -0,0 +1,112 @@
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use mev_core::openbook::OpenBookSwapKeys;
+
+/// Anchor Discriminator for OpenBook v2 "place_take_order"
+/// Calculated as sha256("global:place_take_order")[..8]
+const PLACE_TAKE_ORDER_DISCRIMINATOR: [u8; 8] = [3, 44, 71, 3, 26, 199, 203, 85];
+const SIDE_BID: u8 = 0;
+const SIDE_ASK: u8 = 1;
+/// `PlaceOrderType::ImmediateOrCancel`
+const IMMEDIATE_OR_CANCEL: u8 = 1;
+/// Book levels a single take may cross
+const MATCH_LIMIT: u8 = 50;
+
+/// Exact-in taker swap. `base_in` sells base into the bids, otherwise quote buys
+/// base from the asks. OpenBook has no minimum-output argument, so `min_amount_out`
+/// becomes the worst price the order may cross: filling at or better than the
+/// average price that yields the minimum can't pay out less (before the taker fee).
+pub fn place_take_order(keys: &OpenBookSwapKeys, amount_in: u64, min_amount_out: u64, base_in: bool) -> Instruction {
+    let (side, price_lots, max_base_lots, max_quote_lots) = if base_in {
+        let base_lots = amount_in / keys.base_lot_size;
+        // Quote lots per base lot the fill must average at least
+        let floor = (min_amount_out / keys.quote_lot_size).div_ceil(base_lots.max(1)).max(1);
+        (SIDE_ASK, floor as i64, base_lots as i64, i64::MAX)
+    } else {
+        let quote_lots = amount_in / keys.quote_lot_size;
+        let min_base_lots = min_amount_out.div_ceil(keys.base_lot_size);
+        let ceiling = if min_base_lots == 0 { i64::MAX } else { (quote_lots / min_base_lots) as i64 };
+        (SIDE_BID, ceiling, i64::MAX, quote_lots as i64)
+    };
+
+    let mut data = Vec::with_capacity(35);
+    data.extend_from_slice(&PLACE_TAKE_ORDER_DISCRIMINATOR);
+    data.push(side);
+    data.extend_from_slice(&price_lots.to_le_bytes());
+    data.extend_from_slice(&max_base_lots.to_le_bytes());
+    data.extend_from_slice(&max_quote_lots.to_le_bytes());
+    data.push(IMMEDIATE_OR_CANCEL);
+    data.push(MATCH_LIMIT);
+
+    let program_id = mev_core::constants::OPENBOOK_V2_PROGRAM;
+    // Anchor passes the program id for optional accounts that are absent
+    let optional = |key: Option<solana_sdk::pubkey::Pubkey>| AccountMeta::new_readonly(key.unwrap_or(program_id), false);
+    let accounts = vec![
+        AccountMeta::new(keys.signer, true),
+        AccountMeta::new(keys.signer, true), // penalty payer
+        AccountMeta::new(keys.market, false),
+        AccountMeta::new_readonly(keys.market_authority, false),
+        AccountMeta::new(keys.bids, false),
+        AccountMeta::new(keys.asks, false),
+        AccountMeta::new(keys.base_vault, false),
+        AccountMeta::new(keys.quote_vault, false),
+        AccountMeta::new(keys.event_heap, false),
+        AccountMeta::new(keys.user_base_account, false),
+        AccountMeta::new(keys.user_quote_account, false),
+        optional(keys.oracle_a),
+        optional(keys.oracle_b),
+        AccountMeta::new_readonly(mev_core::constants::TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        optional(None), // open orders admin
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_place_take_order_bounds_price_by_min_out() {
+        let keys = OpenBookSwapKeys {
+            market: Pubkey::new_unique(),
+            market_authority: Pubkey::new_unique(),
+            bids: Pubkey::new_unique(),
+            asks: Pubkey::new_unique(),
+            event_heap: Pubkey::new_unique(),
+            base_vault: Pubkey::new_unique(),
+            quote_vault: Pubkey::new_unique(),
+            base_mint: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            oracle_a: Some(Pubkey::new_unique()),
+            oracle_b: None,
+            base_lot_size: 1_000,
+            quote_lot_size: 10,
+            signer: Pubkey::new_unique(),
+            user_base_account: Pubkey::new_unique(),
+            user_quote_account: Pubkey::new_unique(),
+        };
+        let i64_at = |data: &[u8], at: usize| i64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+
+        // Sell 20 lots for at least 10_000 quote atoms = 1_000 quote lots: 50 per lot or better
+        let sell = place_take_order(&keys, 20_500, 10_000, true);
+        assert_eq!(sell.data.len(), 35);
+        assert_eq!(&sell.data[..8], &PLACE_TAKE_ORDER_DISCRIMINATOR);
+        assert_eq!(sell.data[8], SIDE_ASK);
+        assert_eq!((i64_at(&sell.data, 9), i64_at(&sell.data, 17)), (50, 20));
+        assert_eq!(sell.accounts[11].pubkey, keys.oracle_a.unwrap());
+        assert_eq!(sell.accounts[12].pubkey, mev_core::constants::OPENBOOK_V2_PROGRAM);
+
+        // Intermediate hop: buy with 1_000 quote lots at any price
+        let buy = place_take_order(&keys, 10_000, 0, false);
+        assert_eq!(buy.data[8], SIDE_BID);
+        assert_eq!((i64_at(&buy.data, 9), i64_at(&buy.data, 25)), (i64::MAX, 1_000));
+        // Needing 4 base lots caps the price at 250 quote lots per lot
+        assert_eq!(i64_at(&place_take_order(&keys, 10_000, 4_000, false).data, 9), 250);
+    }
+}
@@ -0,0 +1,223 @@
+/// Paper-Trading Executor
+///
+/// Stands in for the Jito executor in Simulation mode. Nothing is signed or
+/// sent: each bundle "lands" after a modeled delay against whatever the live
+/// graph says the route pays at that moment, less a slippage haircut. A share
+/// of bundles never land at all, and a fill below the bundle's minimum output
+/// reverts like a real Jito bundle would (atomically, tip not paid). Fills are
+/// reported through `TelemetryPort` with their realized PnL in place of the
+/// quoted profit, so `BotMetrics` tracks an equity curve rather than a sum of
+/// `expected_profit_lamports`.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, Weak};
+use std::time::Duration;
+use rand::Rng;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::ArbitrageOpportunity;
+use strategy::ports::{ExecutionPort, TelemetryPort};
+use strategy::StrategyEngine;
+use crate::rng::SharedRng;
+
+/// Prefix of the signatures handed out for paper bundles; they never resolve on chain
+pub const SIGNATURE_PREFIX: &str = "paper-";
+
+/// Base signature fee a landed transaction pays on top of the tip
+const BASE_FEE_LAMPORTS: u64 = 5_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaperFill {
+    /// Never made it into a block; costs nothing
+    Dropped,
+    /// Output fell below the bundle's minimum; reverts atomically, costs nothing
+    Reverted,
+    /// Landed; net of tip and base fee
+    Filled { pnl_lamports: i64 },
+}
+
+/// Settles one bundle. `live_output` is the route's output at landing time
+/// (None once a pool on it is gone or empty).
+pub fn settle(
+    opportunity: &ArbitrageOpportunity,
+    live_output: Option<u64>,
+    landed: bool,
+    tip_lamports: u64,
+    slippage_bps: u16,
+    max_slippage_bps: u16,
+) -> PaperFill {
+    if !landed {
+        return PaperFill::Dropped;
+    }
+    let Some(live_output) = live_output else {
+        return PaperFill::Reverted;
+    };
+    let output = haircut(live_output, slippage_bps);
+    let quoted_output = opportunity.input_amount.saturating_add(opportunity.expected_profit_lamports);
+    if output < haircut(quoted_output, max_slippage_bps) {
+        return PaperFill::Reverted;
+    }
+    // Output and input are in the start mint's units, the tip and fee in lamports
+    let gross = opportunity.start_units_in_sol(output as i64 - opportunity.input_amount as i64);
+    PaperFill::Filled {
+        pnl_lamports: gross - tip_lamports as i64 - BASE_FEE_LAMPORTS as i64,
+    }
+}
+
+fn haircut(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * 10_000u128.saturating_sub(bps as u128) / 10_000) as u64
+}
+
+pub struct PaperExecutor {
+    payer: Pubkey,
+    engine: OnceLock<Weak<StrategyEngine>>,
+    telemetry: Option<Arc<dyn TelemetryPort>>,
+    landing_delay: Duration,
+    failure_rate: f64,
+    slippage_bps: u16,
+    dispatched: AtomicU64,
+    rng: Arc<SharedRng>,
+}
+
+impl PaperExecutor {
+    pub fn new(
+        payer: Pubkey,
+        telemetry: Option<Arc<dyn TelemetryPort>>,
+        landing_delay: Duration,
+        failure_rate: f64,
+        slippage_bps: u16,
+    ) -> Self {
+        Self {
+            payer,
+            engine: OnceLock::new(),
+            telemetry,
+            landing_delay,
+            failure_rate: failure_rate.clamp(0.0, 1.0),
+            slippage_bps,
+            dispatched: AtomicU64::new(0),
+            rng: Arc::default(),
+        }
+    }
+
+    /// Drop draws come from `rng`, so a seeded run drops the same bundles every time
+    pub fn with_rng(mut self, rng: Arc<SharedRng>) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// Points fills at the engine whose graph they settle against. The engine owns
+    /// this executor, so only a weak handle is kept.
+    pub fn attach(&self, engine: &Arc<StrategyEngine>) {
+        let _ = self.engine.set(Arc::downgrade(engine));
+    }
+}
+
+fn report(tel: &dyn TelemetryPort, opportunity: ArbitrageOpportunity, signature: String, fill: PaperFill) {
+    match fill {
+        // Like a dropped Jito bundle, nothing ever reports back
+        PaperFill::Dropped => {
+            tracing::info!("🧻 Paper bundle {} dropped", signature);
+        }
+        PaperFill::Reverted => {
+            tracing::info!("🧻 Paper bundle {} reverted (min output not met)", signature);
+            tel.log_trade_landed(ArbitrageOpportunity { expected_profit_lamports: 0, profit_sol_lamports: None, ..opportunity }, signature, false);
+        }
+        PaperFill::Filled { pnl_lamports } => {
+            tracing::info!("🧻 Paper bundle {} filled: {:+} lamports (quoted +{})", signature, pnl_lamports, opportunity.profit_in_sol());
+            tel.log_trade_landed(
+                ArbitrageOpportunity { expected_profit_lamports: pnl_lamports.max(0) as u64, profit_sol_lamports: None, ..opportunity },
+                signature,
+                true,
+            );
+            if pnl_lamports < 0 {
+                tel.log_realized_pnl(pnl_lamports);
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ExecutionPort for PaperExecutor {
+    async fn build_bundle_instructions(
+        &self,
+        _opportunity: ArbitrageOpportunity,
+        _tip_lamports: u64,
+        _max_slippage_bps: u16,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        Ok(Vec::new())
+    }
+
+    async fn build_and_send_bundle(
+        &self,
+        opportunity: ArbitrageOpportunity,
+        _recent_blockhash: solana_sdk::hash::Hash,
+        tip_lamports: u64,
+        max_slippage_bps: u16,
+    ) -> anyhow::Result<String> {
+        let engine = self.engine.get()
+            .and_then(Weak::upgrade)
+            .ok_or_else(|| anyhow::anyhow!("Paper executor is not attached to a strategy engine"))?;
+        let signature = format!("{}{}", SIGNATURE_PREFIX, self.dispatched.fetch_add(1, Ordering::Relaxed) + 1);
+        let Some(tel) = self.telemetry.clone() else {
+            return Ok(signature);
+        };
+        tel.log_execution_attempt();
+        tel.log_jito_success();
+
+        let landed = !self.rng.with(|rng| rng.gen_bool(self.failure_rate));
+        let (delay, slippage_bps, sig) = (self.landing_delay, self.slippage_bps, signature.clone());
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let live_output = engine.route_output(&opportunity.steps, opportunity.input_amount);
+            let fill = settle(&opportunity, live_output, landed, tip_lamports, slippage_bps, max_slippage_bps);
+            report(tel.as_ref(), opportunity, sig, fill);
+        });
+        Ok(signature)
+    }
+
+    fn pubkey(&self) -> &Pubkey {
+        &self.payer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settle_marks_against_live_output() {
+        let opportunity = ArbitrageOpportunity {
+            input_amount: 1_000_000_000,
+            expected_profit_lamports: 10_000_000,
+            ..Default::default()
+        };
+        let tip = 1_000_000;
+
+        assert_eq!(settle(&opportunity, Some(1_010_000_000), false, tip, 10, 50), PaperFill::Dropped);
+        assert_eq!(settle(&opportunity, None, true, tip, 10, 50), PaperFill::Reverted);
+
+        // 10 bps haircut: 1.009 SOL out, less input, tip and base fee
+        assert_eq!(
+            settle(&opportunity, Some(1_010_000_000), true, tip, 10, 50),
+            PaperFill::Filled { pnl_lamports: 7_985_000 }
+        );
+
+        // Route decayed but stayed inside the bundle's tolerance
+        assert_eq!(
+            settle(&opportunity, Some(1_005_000_000), true, tip, 0, 50),
+            PaperFill::Filled { pnl_lamports: 3_995_000 }
+        );
+        // Wide enough tolerance to land at a loss
+        assert_eq!(
+            settle(&opportunity, Some(1_000_000_000), true, tip, 0, 100),
+            PaperFill::Filled { pnl_lamports: -1_005_000 }
+        );
+        assert_eq!(settle(&opportunity, Some(1_000_000_000), true, tip, 0, 50), PaperFill::Reverted);
+
+        // A cycle from another mint: its gain is valued at the profit's SOL rate before the tip comes off
+        let bonk = ArbitrageOpportunity { profit_sol_lamports: Some(5_000_000), ..opportunity };
+        assert_eq!(
+            settle(&bonk, Some(1_010_000_000), true, tip, 0, 50),
+            PaperFill::Filled { pnl_lamports: 3_995_000 }
+        );
+    }
+}
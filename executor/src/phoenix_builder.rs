@@ -0,0 +1,95 @@
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use mev_core::phoenix::PhoenixSwapKeys;
+
+/// `PhoenixInstruction::Swap`
+const SWAP_TAG: u8 = 0;
+/// `OrderPacket::ImmediateOrCancel`
+const IMMEDIATE_OR_CANCEL: u8 = 2;
+const SIDE_BID: u8 = 0;
+const SIDE_ASK: u8 = 1;
+/// `SelfTradeBehavior::Abort`; we never rest orders, so it can't trigger
+const SELF_TRADE_ABORT: u8 = 0;
+
+/// Exact-in taker swap as an immediate-or-cancel order. `base_in` sells base
+/// into the bids, otherwise quote buys base from the asks. Input is rounded
+/// down to whole lots; `min_amount_out` is rounded up to whole lots so the
+/// fill can't come in under it.
+pub fn swap(keys: &PhoenixSwapKeys, amount_in: u64, min_amount_out: u64, base_in: bool) -> Instruction {
+    let (side, num_base_lots, num_quote_lots, min_base_lots, min_quote_lots) = if base_in {
+        (SIDE_ASK, amount_in / keys.base_lot_size, 0, 0, min_amount_out.div_ceil(keys.quote_lot_size))
+    } else {
+        (SIDE_BID, 0, amount_in / keys.quote_lot_size, min_amount_out.div_ceil(keys.base_lot_size), 0)
+    };
+
+    let mut data = Vec::with_capacity(70);
+    data.push(SWAP_TAG);
+    data.push(IMMEDIATE_OR_CANCEL);
+    data.push(side);
+    data.push(0); // price_in_ticks: None (take any price, bounded by the minimum fill)
+    data.extend_from_slice(&num_base_lots.to_le_bytes());
+    data.extend_from_slice(&num_quote_lots.to_le_bytes());
+    data.extend_from_slice(&min_base_lots.to_le_bytes());
+    data.extend_from_slice(&min_quote_lots.to_le_bytes());
+    data.push(SELF_TRADE_ABORT);
+    data.push(0); // match_limit: None
+    data.extend_from_slice(&0u128.to_le_bytes()); // client_order_id
+    data.push(0); // use_only_deposited_funds
+    data.push(0); // last_valid_slot: None
+    data.push(0); // last_valid_unix_timestamp_in_seconds: None
+
+    let accounts = vec![
+        AccountMeta::new_readonly(mev_core::constants::PHOENIX_PROGRAM, false),
+        AccountMeta::new_readonly(PhoenixSwapKeys::log_authority(), false),
+        AccountMeta::new(keys.market, false),
+        AccountMeta::new_readonly(keys.trader, true),
+        AccountMeta::new(keys.trader_base_account, false),
+        AccountMeta::new(keys.trader_quote_account, false),
+        AccountMeta::new(keys.base_vault, false),
+        AccountMeta::new(keys.quote_vault, false),
+        AccountMeta::new_readonly(mev_core::constants::TOKEN_PROGRAM_ID, false),
+    ];
+
+    Instruction {
+        program_id: mev_core::constants::PHOENIX_PROGRAM,
+        accounts,
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_swap_sizes_ioc_order_in_lots() {
+        let keys = PhoenixSwapKeys {
+            market: Pubkey::new_unique(),
+            base_mint: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            base_vault: Pubkey::new_unique(),
+            quote_vault: Pubkey::new_unique(),
+            base_lot_size: 1_000,
+            quote_lot_size: 10,
+            trader: Pubkey::new_unique(),
+            trader_base_account: Pubkey::new_unique(),
+            trader_quote_account: Pubkey::new_unique(),
+        };
+        let u64_at = |data: &[u8], at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+
+        // Sell 25_500 base atoms (25 lots) for at least 995 quote atoms (100 quote lots)
+        let sell = swap(&keys, 25_500, 995, true);
+        assert_eq!(sell.data.len(), 56);
+        assert_eq!(&sell.data[..4], &[SWAP_TAG, IMMEDIATE_OR_CANCEL, SIDE_ASK, 0]);
+        assert_eq!((u64_at(&sell.data, 4), u64_at(&sell.data, 12)), (25, 0));
+        assert_eq!((u64_at(&sell.data, 20), u64_at(&sell.data, 28)), (0, 100));
+        assert!(sell.accounts[3].is_signer);
+        assert_eq!(sell.accounts[2].pubkey, keys.market);
+
+        // Buy with 999 quote atoms (99 lots), needing at least 2 base lots
+        let buy = swap(&keys, 999, 1_001, false);
+        assert_eq!(buy.data[2], SIDE_BID);
+        assert_eq!((u64_at(&buy.data, 4), u64_at(&buy.data, 12)), (0, 99));
+        assert_eq!((u64_at(&buy.data, 20), u64_at(&buy.data, 28)), (2, 0));
+    }
+}
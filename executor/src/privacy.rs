@@ -0,0 +1,152 @@
+/// Order-Flow Privacy
+///
+/// Opt-in hardening against flow fingerprinting. With privacy on, every bundle
+/// differs in the details competitors key on: which tip account it pays (drawn
+/// from the endpoint's own list, never the one used last), when inside the slot
+/// it lands, where the tip and compute-budget instructions sit and which
+/// read-only decoy accounts ride along on the tip transfer.
+use rand::seq::SliceRandom;
+use rand::Rng;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::rng::SharedRng;
+
+/// Submission jitter never exceeds one slot
+const MAX_JITTER_CAP_MS: u64 = 400;
+
+#[derive(Debug, Clone, Default)]
+pub struct PrivacyConfig {
+    pub enabled: bool,
+    /// Upper bound on the random delay before each submission
+    pub max_jitter_ms: u64,
+    /// Random read-only accounts appended to the tip transfer
+    pub decoy_accounts: u8,
+    /// Source of every draw below; seeded for reproducible simulation runs
+    pub rng: Arc<SharedRng>,
+}
+
+impl PrivacyConfig {
+    pub fn new(enabled: bool, max_jitter_ms: u64, decoy_accounts: u8) -> Self {
+        Self {
+            enabled,
+            max_jitter_ms: max_jitter_ms.min(MAX_JITTER_CAP_MS),
+            decoy_accounts,
+            rng: Arc::default(),
+        }
+    }
+
+    pub fn with_rng(mut self, rng: Arc<SharedRng>) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    pub fn jitter(&self) -> Duration {
+        if !self.enabled || self.max_jitter_ms == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_millis(self.rng.with(|rng| rng.gen_range(0..=self.max_jitter_ms)))
+    }
+
+    /// Picks a tip account, avoiding `last` when the list allows it
+    pub fn pick_tip_account(&self, candidates: &[Pubkey], last: Option<Pubkey>) -> Option<Pubkey> {
+        self.rng.with(|rng| {
+            if !self.enabled {
+                return candidates.choose(rng).copied();
+            }
+            let fresh: Vec<Pubkey> = candidates.iter().copied().filter(|c| Some(*c) != last).collect();
+            fresh.choose(rng).or_else(|| candidates.choose(rng)).copied()
+        })
+    }
+
+    /// Adds decoy read-only metas to the tip transfer. The system program ignores extra accounts.
+    pub fn decorate_tip(&self, mut tip_ix: Instruction) -> Instruction {
+        if self.enabled {
+            self.rng.with(|rng| {
+                for _ in 0..self.decoy_accounts {
+                    tip_ix.accounts.push(AccountMeta::new_readonly(Pubkey::new_from_array(rng.gen()), false));
+                }
+            });
+        }
+        tip_ix
+    }
+
+    /// Assembles the bundle transaction. Trade legs keep their order (it is the route);
+    /// the compute-budget pair is shuffled and the tip goes first or last.
+    pub fn arrange(&self, compute_budget: Vec<Instruction>, trade_ixs: Vec<Instruction>, tip_ix: Instruction) -> Vec<Instruction> {
+        let mut compute_budget = compute_budget;
+        let mut ixs = Vec::with_capacity(compute_budget.len() + trade_ixs.len() + 1);
+
+        if !self.enabled {
+            ixs.extend(compute_budget);
+            ixs.extend(trade_ixs);
+            ixs.push(tip_ix);
+            return ixs;
+        }
+
+        let tip_first = self.rng.with(|rng| {
+            compute_budget.shuffle(rng);
+            rng.gen_bool(0.5)
+        });
+        ixs.extend(compute_budget);
+        if tip_first {
+            ixs.push(tip_ix);
+            ixs.extend(trade_ixs);
+        } else {
+            ixs.extend(trade_ixs);
+            ixs.push(tip_ix);
+        }
+        ixs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::system_instruction;
+
+    #[test]
+    fn test_disabled_is_deterministic() {
+        let privacy = PrivacyConfig::default();
+        let payer = Pubkey::new_unique();
+        let tip = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000);
+        let trade = system_instruction::transfer(&payer, &payer, 1);
+
+        assert_eq!(privacy.jitter(), Duration::ZERO);
+        assert_eq!(privacy.decorate_tip(tip.clone()).accounts.len(), 2);
+        let ixs = privacy.arrange(vec![], vec![trade.clone()], tip.clone());
+        assert_eq!(ixs, vec![trade, tip]);
+    }
+
+    #[test]
+    fn test_never_reuses_last_tip_account() {
+        let privacy = PrivacyConfig::new(true, 100, 0);
+        let accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+        for _ in 0..20 {
+            assert_eq!(privacy.pick_tip_account(&accounts, Some(accounts[0])), Some(accounts[1]));
+        }
+        // A single account is still better than no tip
+        assert_eq!(privacy.pick_tip_account(&accounts[..1], Some(accounts[0])), Some(accounts[0]));
+    }
+
+    #[test]
+    fn test_enabled_keeps_route_order_and_adds_decoys() {
+        let privacy = PrivacyConfig::new(true, 10_000, 2);
+        assert_eq!(privacy.max_jitter_ms, MAX_JITTER_CAP_MS);
+
+        let payer = Pubkey::new_unique();
+        let tip = privacy.decorate_tip(system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000));
+        assert_eq!(tip.accounts.len(), 4);
+        assert!(tip.accounts[2..].iter().all(|m| !m.is_writable && !m.is_signer));
+
+        let legs = vec![
+            system_instruction::transfer(&payer, &payer, 1),
+            system_instruction::transfer(&payer, &payer, 2),
+        ];
+        let ixs = privacy.arrange(vec![], legs.clone(), tip.clone());
+        let trade_positions: Vec<usize> = legs.iter().map(|l| ixs.iter().position(|i| i == l).unwrap()).collect();
+        assert!(trade_positions[0] < trade_positions[1]);
+        assert!(ixs.first() == Some(&tip) || ixs.last() == Some(&tip));
+    }
+}
@@ -0,0 +1,216 @@
+/// Opportunity Publisher
+///
+/// Stands in for the executor when `EXTERNAL_EXECUTION` hands execution to
+/// another system. Nothing is signed or sent: each opportunity the strategy
+/// would have dispatched is published as JSON, to a webhook (HTTP POST) and/or
+/// a NATS subject, with the swap keys of every hop already resolved so the
+/// consumer can build its own transaction without re-reading pool state.
+/// Pubkeys are base58 strings throughout. User-side accounts in the keys
+/// (owner, token accounts) are left for the consumer to fill with its wallet.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use serde::Serialize;
+use serde_json::Value;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::constants::*;
+use mev_core::{ArbitrageOpportunity, SwapStep};
+use strategy::ports::{ExecutionPort, PoolKeyProvider};
+use crate::http::HttpClient;
+
+/// Prefix of the ids handed back for published opportunities; they never resolve on chain
+pub const ID_PREFIX: &str = "published-";
+/// Bumped whenever a field of the payload changes meaning or goes away
+pub const PAYLOAD_VERSION: u32 = 1;
+
+/// One hop with the accounts its swap instruction needs
+#[derive(Debug, Serialize)]
+pub struct ResolvedHop {
+    pub venue: &'static str,
+    pub pool: String,
+    pub program_id: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub expected_output: u64,
+    /// Venue-specific swap keys; null for venues whose accounts derive from the mints (Pump.fun)
+    pub keys: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublishedOpportunity {
+    pub version: u32,
+    pub id: String,
+    pub published_at_ms: u64,
+    pub tip_lamports: u64,
+    pub max_slippage_bps: u16,
+    pub hops: Vec<ResolvedHop>,
+    pub opportunity: Value,
+}
+
+pub struct OpportunityPublisher {
+    payer: Pubkey,
+    keys: Arc<dyn PoolKeyProvider>,
+    http: Arc<HttpClient>,
+    webhook_url: Option<String>,
+    nats: Option<(async_nats::Client, String)>,
+    published: AtomicU64,
+}
+
+impl OpportunityPublisher {
+    pub fn new(payer: Pubkey, keys: Arc<dyn PoolKeyProvider>, http: Arc<HttpClient>, webhook_url: Option<String>) -> Self {
+        Self {
+            payer,
+            keys,
+            http,
+            webhook_url,
+            nats: None,
+            published: AtomicU64::new(0),
+        }
+    }
+
+    /// Also publishes every opportunity on `subject` of the NATS server at `url`
+    pub async fn connect_nats(mut self, url: &str, subject: String) -> anyhow::Result<Self> {
+        let client = async_nats::connect(url).await
+            .map_err(|e| anyhow::anyhow!("NATS connect failed: {}", e))?;
+        self.nats = Some((client, subject));
+        Ok(self)
+    }
+
+    async fn resolve(&self, step: &SwapStep) -> anyhow::Result<ResolvedHop> {
+        let (venue, keys) = match step.program_id {
+            RAYDIUM_V4_PROGRAM => ("raydium_v4", serde_json::to_value(self.keys.get_swap_keys(&step.pool).await?)?),
+            ORCA_WHIRLPOOL_PROGRAM => ("orca_whirlpool", serde_json::to_value(self.keys.get_orca_keys(&step.pool).await?)?),
+            RAYDIUM_CLMM_PROGRAM => ("raydium_clmm", serde_json::to_value(self.keys.get_raydium_clmm_keys(&step.pool).await?)?),
+            METEORA_PROGRAM_ID => ("meteora_dlmm", serde_json::to_value(self.keys.get_meteora_keys(&step.pool).await?)?),
+            PHOENIX_PROGRAM => ("phoenix", serde_json::to_value(self.keys.get_phoenix_keys(&step.pool).await?)?),
+            OPENBOOK_V2_PROGRAM => ("openbook_v2", serde_json::to_value(self.keys.get_openbook_keys(&step.pool).await?)?),
+            PUMP_FUN_PROGRAM => ("pump_fun", Value::Null),
+            other => return Err(anyhow::anyhow!("No key resolver for program {} (pool {})", other, step.pool)),
+        };
+        Ok(ResolvedHop {
+            venue,
+            pool: step.pool.to_string(),
+            program_id: step.program_id.to_string(),
+            input_mint: step.input_mint.to_string(),
+            output_mint: step.output_mint.to_string(),
+            expected_output: step.expected_output,
+            keys: base58_pubkeys(keys),
+        })
+    }
+}
+
+/// serde writes a `Pubkey` as its 32 raw bytes; external consumers want base58
+fn base58_pubkeys(value: Value) -> Value {
+    match value {
+        Value::Array(items) => {
+            let bytes: Vec<u8> = items.iter()
+                .filter_map(|v| v.as_u64().and_then(|b| u8::try_from(b).ok()))
+                .collect();
+            if items.len() == 32 && bytes.len() == 32 {
+                Value::String(bs58::encode(bytes).into_string())
+            } else {
+                Value::Array(items.into_iter().map(base58_pubkeys).collect())
+            }
+        }
+        Value::Object(fields) => Value::Object(fields.into_iter().map(|(k, v)| (k, base58_pubkeys(v))).collect()),
+        other => other,
+    }
+}
+
+#[async_trait::async_trait]
+impl ExecutionPort for OpportunityPublisher {
+    async fn build_bundle_instructions(
+        &self,
+        _opportunity: ArbitrageOpportunity,
+        _tip_lamports: u64,
+        _max_slippage_bps: u16,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        Err(anyhow::anyhow!("External execution: instructions are built by the consumer"))
+    }
+
+    async fn build_and_send_bundle(
+        &self,
+        opportunity: ArbitrageOpportunity,
+        _recent_blockhash: solana_sdk::hash::Hash,
+        tip_lamports: u64,
+        max_slippage_bps: u16,
+    ) -> anyhow::Result<String> {
+        let mut hops = Vec::with_capacity(opportunity.steps.len());
+        for step in &opportunity.steps {
+            hops.push(self.resolve(step).await?);
+        }
+        let id = format!("{}{}", ID_PREFIX, self.published.fetch_add(1, Ordering::Relaxed) + 1);
+        let payload = PublishedOpportunity {
+            version: PAYLOAD_VERSION,
+            id: id.clone(),
+            published_at_ms: mev_core::clock::system().unix_ms(),
+            tip_lamports,
+            max_slippage_bps,
+            hops,
+            opportunity: base58_pubkeys(serde_json::to_value(&opportunity)?),
+        };
+        let body = serde_json::to_vec(&payload)?;
+
+        // Either sink counts as published; only both failing fails the dispatch
+        let mut delivered = 0;
+        let mut errors = Vec::new();
+        if let Some(url) = &self.webhook_url {
+            let request = self.http.post(url).header("Content-Type", "application/json").body(body.clone());
+            match self.http.send(request).await {
+                Ok(resp) if resp.status().is_success() => delivered += 1,
+                Ok(resp) => errors.push(format!("webhook HTTP {}", resp.status())),
+                Err(e) => errors.push(format!("webhook: {}", e)),
+            }
+        }
+        if let Some((client, subject)) = &self.nats {
+            match client.publish(subject.clone(), body.into()).await {
+                Ok(()) => delivered += 1,
+                Err(e) => errors.push(format!("nats: {}", e)),
+            }
+        }
+        for error in &errors {
+            mev_core::telemetry::OPPORTUNITIES_PUBLISHED.with_label_values(&["failed"]).inc();
+            tracing::warn!("📡 Publishing {} failed: {}", id, error);
+        }
+        if delivered == 0 {
+            return Err(anyhow::anyhow!("Opportunity not published: {}", errors.join("; ")));
+        }
+        mev_core::telemetry::OPPORTUNITIES_PUBLISHED.with_label_values(&["delivered"]).inc_by(delivered as f64);
+        tracing::info!("📡 Published {} ({} hops, +{} lamports quoted)", id, opportunity.steps.len(), opportunity.expected_profit_lamports);
+        Ok(id)
+    }
+
+    fn pubkey(&self) -> &Pubkey {
+        &self.payer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pubkeys_serialize_as_base58() {
+        let pool = Pubkey::new_unique();
+        let opp = ArbitrageOpportunity {
+            steps: [SwapStep {
+                pool,
+                program_id: RAYDIUM_V4_PROGRAM,
+                input_mint: SOL_MINT,
+                output_mint: Pubkey::new_unique(),
+                expected_output: 42,
+            }].into_iter().collect(),
+            input_amount: 1_000,
+            ..Default::default()
+        };
+        let value = base58_pubkeys(serde_json::to_value(&opp).unwrap());
+        assert_eq!(value["steps"][0]["pool"], pool.to_string());
+        assert_eq!(value["steps"][0]["program_id"], RAYDIUM_V4_PROGRAM.to_string());
+        assert_eq!(value["steps"][0]["expected_output"], 42);
+        assert_eq!(value["input_amount"], 1_000);
+
+        // Short byte arrays and mixed arrays are left alone
+        let other = base58_pubkeys(serde_json::json!({ "data": [1, 2, 3], "mixed": [1, "a"] }));
+        assert_eq!(other["data"], serde_json::json!([1, 2, 3]));
+    }
+}
@@ -103,3 +103,71 @@ pub fn sell(
         data,
     }
 }
+
+/// Instructions for one bonding-curve hop, plus the amount it hands to the next hop.
+/// Buys ask for the expected tokens less `slippage_bps` with the whole SOL input as the cost cap,
+/// so they land unless the price moved past the tolerance; the tokens asked for are exactly
+/// what the next hop can sell. Sells require `min_amount_out`, or the expected SOL less slippage.
+pub fn swap_leg(
+    payer: Pubkey,
+    step: &mev_core::SwapStep,
+    amount_in: u64,
+    min_amount_out: u64,
+    slippage_bps: u16,
+) -> (Vec<Instruction>, u64) {
+    let sol_mint = mev_core::constants::SOL_MINT;
+    let buying = step.input_mint == sol_mint;
+    let token_mint = if buying { step.output_mint } else { step.input_mint };
+    let bonding_curve = step.pool;
+    let associated_bonding_curve = spl_associated_token_account::get_associated_token_address(&bonding_curve, &token_mint);
+    let user_ata = spl_associated_token_account::get_associated_token_address(&payer, &token_mint);
+    let tolerated = (step.expected_output as u128 * (10000 - slippage_bps.min(10000)) as u128 / 10000) as u64;
+
+    if buying {
+        let tokens = tolerated.max(min_amount_out);
+        let ixs = vec![
+            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                &payer,
+                &payer,
+                &token_mint,
+                &spl_token::id(),
+            ),
+            buy(payer, token_mint, bonding_curve, associated_bonding_curve, user_ata, tokens, amount_in),
+        ];
+        (ixs, tokens)
+    } else {
+        let min_sol_output = tolerated.max(min_amount_out);
+        let ixs = vec![sell(payer, token_mint, bonding_curve, associated_bonding_curve, user_ata, amount_in, min_sol_output)];
+        (ixs, step.expected_output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_leg_applies_slippage() {
+        let (payer, token) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let buy_step = mev_core::SwapStep {
+            pool: Pubkey::new_unique(),
+            program_id: mev_core::constants::PUMP_FUN_PROGRAM,
+            input_mint: mev_core::constants::SOL_MINT,
+            output_mint: token,
+            expected_output: 1_000_000,
+        };
+
+        // 1% slippage: ask for 990k tokens, spend at most the 0.1 SOL input
+        let (ixs, tokens) = swap_leg(payer, &buy_step, 100_000_000, 0, 100);
+        assert_eq!((ixs.len(), tokens), (2, 990_000));
+        assert_eq!(u64::from_le_bytes(ixs[1].data[8..16].try_into().unwrap()), 990_000);
+        assert_eq!(u64::from_le_bytes(ixs[1].data[16..24].try_into().unwrap()), 100_000_000);
+
+        let sell_step = mev_core::SwapStep { input_mint: token, output_mint: mev_core::constants::SOL_MINT, expected_output: 100_000_000, ..buy_step };
+        let (ixs, _) = swap_leg(payer, &sell_step, tokens, 0, 100);
+        assert_eq!(u64::from_le_bytes(ixs[0].data[16..24].try_into().unwrap()), 99_000_000);
+        // A stricter final-leg minimum wins
+        let (ixs, _) = swap_leg(payer, &sell_step, tokens, 99_500_000, 100);
+        assert_eq!(u64::from_le_bytes(ixs[0].data[16..24].try_into().unwrap()), 99_500_000);
+    }
+}
@@ -0,0 +1,104 @@
+/// Landing Race
+///
+/// With `RACE_MODE` on, a bundle goes to Jito and, at the same moment, a copy
+/// of its transaction without the tip goes to Helius Sender, paying its way
+/// with the priority fee alone. When the block engine is congested or the
+/// bundle loses its auction, the copy can still land the trade. Both are
+/// signed against the durable nonce and start by advancing it, so whichever
+/// lands first invalidates the other (and every Jito retry): the trade executes
+/// once at most.
+///
+/// The nonce stays leased until the trade settles. Trades dispatched in the
+/// meantime go out as plain bundles without a race.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::Signature;
+use crate::nonce::OwnedNonceLease;
+
+pub struct RaceTicket {
+    lease: OwnedNonceLease,
+    hash: Hash,
+    started: AtomicBool,
+    rpc_signature: Mutex<Option<Signature>>,
+}
+
+impl RaceTicket {
+    /// `hash` is the leased nonce's current value
+    pub fn new(lease: OwnedNonceLease, hash: Hash) -> Self {
+        Self { lease, hash, started: AtomicBool::new(false), rpc_signature: Mutex::new(None) }
+    }
+
+    /// Signs every raced transaction in place of a recent blockhash
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// `ixs` behind the nonce advance
+    pub fn wrap(&self, ixs: &[Instruction]) -> Vec<Instruction> {
+        self.lease.nonce().wrap(ixs)
+    }
+
+    /// True for the first bundle attempt only: the RPC copy is sent once
+    pub fn start(&self) -> bool {
+        !self.started.swap(true, Ordering::AcqRel)
+    }
+
+    pub fn record_rpc(&self, signature: Signature) {
+        *self.rpc_signature.lock().unwrap_or_else(|e| e.into_inner()) = Some(signature);
+    }
+
+    /// The RPC copy's signature, once a sender accepted it
+    pub fn rpc_signature(&self) -> Option<Signature> {
+        *self.rpc_signature.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The nonce lease, to hold until the trade settles
+    pub fn into_lease(self) -> OwnedNonceLease {
+        self.lease
+    }
+}
+
+/// The RPC copy of a bundle transaction: the same instructions, compute budget and nonce advance, without the tip
+pub fn untipped(bundle_ixs: &[Instruction], tip_ix: &Instruction) -> Vec<Instruction> {
+    bundle_ixs.iter().filter(|ix| *ix != tip_ix).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use solana_sdk::compute_budget::ComputeBudgetInstruction;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::system_instruction;
+    use crate::nonce::DurableNonce;
+
+    #[test]
+    fn test_rpc_copy_drops_only_the_tip_and_shares_the_nonce() {
+        let (account, payer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let nonce = Arc::new(DurableNonce::new(account, payer));
+        let hash = Hash::new_unique();
+        let ticket = RaceTicket::new(nonce.lease_owned().unwrap(), hash);
+
+        let leg = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let tip = system_instruction::transfer(&payer, &Pubkey::new_unique(), 10_000);
+        let price = ComputeBudgetInstruction::set_compute_unit_price(5_000);
+        let bundle = ticket.wrap(&[price.clone(), leg.clone(), tip.clone()]);
+        let copy = untipped(&bundle, &tip);
+        assert_eq!(copy, vec![system_instruction::advance_nonce_account(&account, &payer), price, leg]);
+        assert_eq!(ticket.hash(), hash);
+
+        // One RPC copy per trade, whatever the number of bundle attempts
+        assert!(ticket.start());
+        assert!(!ticket.start());
+        assert_eq!(ticket.rpc_signature(), None);
+        ticket.record_rpc(Signature::default());
+        assert_eq!(ticket.rpc_signature(), Some(Signature::default()));
+
+        // Nobody else signs against the nonce until the lease is let go
+        assert!(nonce.lease().is_none());
+        drop(ticket.into_lease());
+        assert!(nonce.lease().is_some());
+    }
+}
@@ -0,0 +1,92 @@
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use mev_core::raydium_clmm::{RaydiumClmmSwapKeys, MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64};
+
+/// Anchor Discriminator for Raydium CLMM "swap" instruction
+/// Calculated as sha256("global:swap")[..8]
+const SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+
+/// Exact-in swap. `zero_for_one` sells token 0 for token 1.
+/// A sqrt_price_limit of 0 means no limit beyond the program's bounds.
+pub fn swap(
+    keys: &RaydiumClmmSwapKeys,
+    amount_in: u64,
+    min_amount_out: u64,
+    mut sqrt_price_limit: u128,
+    zero_for_one: bool,
+) -> Instruction {
+    if sqrt_price_limit == 0 {
+        sqrt_price_limit = if zero_for_one { MIN_SQRT_PRICE_X64 + 1 } else { MAX_SQRT_PRICE_X64 - 1 };
+    }
+
+    let mut data = Vec::with_capacity(41);
+    data.extend_from_slice(&SWAP_DISCRIMINATOR);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&min_amount_out.to_le_bytes());
+    data.extend_from_slice(&sqrt_price_limit.to_le_bytes());
+    data.push(1); // is_base_input
+
+    let (user_in, user_out, vault_in, vault_out) = if zero_for_one {
+        (keys.user_token_0, keys.user_token_1, keys.token_vault_0, keys.token_vault_1)
+    } else {
+        (keys.user_token_1, keys.user_token_0, keys.token_vault_1, keys.token_vault_0)
+    };
+    let [first_tick_array, next_tick_array] = keys.tick_arrays(zero_for_one);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(keys.user_owner, true),
+        AccountMeta::new_readonly(keys.amm_config, false),
+        AccountMeta::new(keys.pool_state, false),
+        AccountMeta::new(user_in, false),
+        AccountMeta::new(user_out, false),
+        AccountMeta::new(vault_in, false),
+        AccountMeta::new(vault_out, false),
+        AccountMeta::new(keys.observation_state, false),
+        AccountMeta::new_readonly(mev_core::constants::TOKEN_PROGRAM_ID, false),
+        AccountMeta::new(first_tick_array, false),
+        // Remaining accounts: further tick arrays in swap direction
+        AccountMeta::new(next_tick_array, false),
+    ];
+
+    Instruction {
+        program_id: mev_core::constants::RAYDIUM_CLMM_PROGRAM,
+        accounts,
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_swap_orients_accounts_by_direction() {
+        let keys = RaydiumClmmSwapKeys {
+            pool_state: Pubkey::new_unique(),
+            amm_config: Pubkey::new_unique(),
+            observation_state: Pubkey::new_unique(),
+            mint_0: Pubkey::new_unique(),
+            mint_1: Pubkey::new_unique(),
+            token_vault_0: Pubkey::new_unique(),
+            token_vault_1: Pubkey::new_unique(),
+            user_owner: Pubkey::new_unique(),
+            user_token_0: Pubkey::new_unique(),
+            user_token_1: Pubkey::new_unique(),
+            tick_array_current: Pubkey::new_unique(),
+            tick_array_lower: Pubkey::new_unique(),
+            tick_array_upper: Pubkey::new_unique(),
+        };
+
+        let sell_0 = swap(&keys, 1_000, 990, 0, true);
+        assert_eq!(sell_0.data.len(), 41);
+        assert_eq!(&sell_0.data[..8], &SWAP_DISCRIMINATOR);
+        assert_eq!(u128::from_le_bytes(sell_0.data[24..40].try_into().unwrap()), MIN_SQRT_PRICE_X64 + 1);
+        assert_eq!((sell_0.accounts[3].pubkey, sell_0.accounts[5].pubkey), (keys.user_token_0, keys.token_vault_0));
+        assert_eq!(sell_0.accounts[10].pubkey, keys.tick_array_lower);
+        assert!(sell_0.accounts[0].is_signer);
+
+        let sell_1 = swap(&keys, 1_000, 990, 0, false);
+        assert_eq!((sell_1.accounts[3].pubkey, sell_1.accounts[6].pubkey), (keys.user_token_1, keys.token_vault_0));
+        assert_eq!(sell_1.accounts[10].pubkey, keys.tick_array_upper);
+    }
+}
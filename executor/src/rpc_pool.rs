@@ -0,0 +1,285 @@
+/// Health-Scored RPC Pool
+///
+/// Every RPC consumer goes through one pool of endpoints instead of a single
+/// `rpc_url`. Each endpoint keeps an EWMA of its latency and error rate, and
+/// calls go to the best-scoring one first, moving on to the next when an
+/// endpoint fails. A 429 puts the endpoint on cooldown so the next calls
+/// rotate away from it instead of retrying into the rate limit. Errors that are
+/// the caller's (account not found, preflight failure) don't count against it.
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::RpcError;
+
+/// Weight of the newest sample in the latency and error averages
+const EWMA_ALPHA: f64 = 0.2;
+/// Latency a fresh endpoint is assumed to have until it has answered
+const INITIAL_LATENCY_MS: f64 = 100.0;
+/// JSON-RPC "node is behind" / "unhealthy" codes
+const NODE_UNHEALTHY_CODES: [i64; 2] = [-32005, -32016];
+
+#[derive(Debug, Clone)]
+pub struct RpcPoolPolicy {
+    /// How long an endpoint that returned 429 is skipped
+    pub rate_limit_cooldown: Duration,
+    /// Error-rate multiplier on latency when scoring (score = latency * (1 + penalty * error rate))
+    pub error_penalty: f64,
+}
+
+impl Default for RpcPoolPolicy {
+    fn default() -> Self {
+        Self { rate_limit_cooldown: Duration::from_secs(30), error_penalty: 10.0 }
+    }
+}
+
+/// Whose fault a failed call was
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcFault {
+    /// The request itself was refused (missing account, failed simulation): not the endpoint's doing
+    Caller,
+    Endpoint,
+    RateLimited,
+}
+
+impl RpcFault {
+    pub fn classify(err: &ClientError) -> Self {
+        match err.kind() {
+            ClientErrorKind::Reqwest(e) if e.status().is_some_and(|s| s.as_u16() == 429) => RpcFault::RateLimited,
+            ClientErrorKind::Reqwest(_) | ClientErrorKind::Io(_) | ClientErrorKind::Middleware(_) => RpcFault::Endpoint,
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, message, .. }) => {
+                if *code == 429 || message.contains("Too many requests") {
+                    RpcFault::RateLimited
+                } else if NODE_UNHEALTHY_CODES.contains(code) {
+                    RpcFault::Endpoint
+                } else {
+                    RpcFault::Caller
+                }
+            }
+            ClientErrorKind::RpcError(RpcError::RpcRequestError(msg)) | ClientErrorKind::Custom(msg) => {
+                if msg.contains("429") { RpcFault::RateLimited } else { RpcFault::Endpoint }
+            }
+            _ => RpcFault::Caller,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            RpcFault::Caller => "ok",
+            RpcFault::Endpoint => "error",
+            RpcFault::RateLimited => "rate_limited",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Health {
+    latency_ms: f64,
+    error_rate: f64,
+    cooldown_until: Option<Instant>,
+}
+
+struct Endpoint {
+    url: String,
+    client: Arc<AsyncRpcClient>,
+    blocking: Arc<RpcClient>,
+    health: Mutex<Health>,
+}
+
+pub struct RpcPool {
+    endpoints: Vec<Endpoint>,
+    policy: RpcPoolPolicy,
+}
+
+impl RpcPool {
+    /// Endpoints in preference order; duplicates and blanks are dropped
+    pub fn new(urls: &[String], policy: RpcPoolPolicy) -> Self {
+        let mut endpoints: Vec<Endpoint> = Vec::with_capacity(urls.len());
+        for url in urls.iter().map(|u| u.trim()).filter(|u| !u.is_empty()) {
+            if endpoints.iter().any(|e| e.url == url) {
+                continue;
+            }
+            endpoints.push(Endpoint {
+                url: url.to_string(),
+                client: Arc::new(AsyncRpcClient::new(url.to_string())),
+                blocking: Arc::new(RpcClient::new(url.to_string())),
+                health: Mutex::new(Health { latency_ms: INITIAL_LATENCY_MS, error_rate: 0.0, cooldown_until: None }),
+            });
+        }
+        assert!(!endpoints.is_empty(), "RpcPool needs at least one endpoint");
+        Self { endpoints, policy }
+    }
+
+    /// One endpoint, default policy
+    pub fn single(url: &str) -> Self {
+        Self::new(&[url.to_string()], RpcPoolPolicy::default())
+    }
+
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    /// URL of the endpoint calls would go to now
+    pub fn url(&self) -> String {
+        self.endpoints[self.ranked(Instant::now())[0]].url.clone()
+    }
+
+    /// Healthiest async client, for callers that need a client handle rather than a call
+    pub fn client(&self) -> Arc<AsyncRpcClient> {
+        Arc::clone(&self.endpoints[self.ranked(Instant::now())[0]].client)
+    }
+
+    /// Healthiest blocking client
+    pub fn blocking_client(&self) -> Arc<RpcClient> {
+        Arc::clone(&self.endpoints[self.ranked(Instant::now())[0]].blocking)
+    }
+
+    fn score(&self, health: &Health) -> f64 {
+        health.latency_ms * (1.0 + self.policy.error_penalty * health.error_rate)
+    }
+
+    /// Endpoint indices, best first; endpoints on cooldown go last, soonest-free first
+    fn ranked(&self, now: Instant) -> Vec<usize> {
+        let snapshot: Vec<Health> = self.endpoints.iter().map(|e| *e.health.lock().unwrap()).collect();
+        let mut order: Vec<usize> = (0..snapshot.len()).collect();
+        order.sort_by(|&a, &b| {
+            let cooling = |h: &Health| h.cooldown_until.filter(|until| *until > now);
+            match (cooling(&snapshot[a]), cooling(&snapshot[b])) {
+                (None, None) => self.score(&snapshot[a]).total_cmp(&self.score(&snapshot[b])),
+                (None, Some(_)) => std::cmp::Ordering::Less,
+                (Some(_), None) => std::cmp::Ordering::Greater,
+                (Some(x), Some(y)) => x.cmp(&y),
+            }
+        });
+        order
+    }
+
+    /// Scores one call against endpoint `index`
+    pub fn record(&self, index: usize, latency: Duration, fault: Option<RpcFault>, now: Instant) {
+        let Some(endpoint) = self.endpoints.get(index) else {
+            return;
+        };
+        let failed = matches!(fault, Some(RpcFault::Endpoint | RpcFault::RateLimited));
+        {
+            let mut health = endpoint.health.lock().unwrap();
+            // A timed-out call says nothing good about latency, so only answers move it
+            if !failed {
+                health.latency_ms += EWMA_ALPHA * (latency.as_secs_f64() * 1000.0 - health.latency_ms);
+            }
+            health.error_rate += EWMA_ALPHA * (if failed { 1.0 } else { 0.0 } - health.error_rate);
+            if fault == Some(RpcFault::RateLimited) {
+                health.cooldown_until = Some(now + self.policy.rate_limit_cooldown);
+            }
+            mev_core::telemetry::RPC_ENDPOINT_LATENCY_MS.with_label_values(&[&index.to_string()]).set(health.latency_ms as i64);
+        }
+        let outcome = fault.map_or("ok", |f| f.label());
+        mev_core::telemetry::RPC_REQUESTS.with_label_values(&[&index.to_string(), outcome]).inc();
+        if failed {
+            mev_core::telemetry::RPC_ERRORS.inc();
+        }
+    }
+
+    /// Runs `op` on the healthiest endpoint, failing over to the others in score order
+    /// while the failure is the endpoint's
+    pub async fn call<T, F, Fut>(&self, op: F) -> ClientResult<T>
+    where
+        F: Fn(Arc<AsyncRpcClient>) -> Fut,
+        Fut: Future<Output = ClientResult<T>>,
+    {
+        let mut last_err = None;
+        for index in self.ranked(Instant::now()) {
+            let started = Instant::now();
+            let result = op(Arc::clone(&self.endpoints[index].client)).await;
+            match self.settle(index, started, result) {
+                Ok(value) => return Ok(value),
+                Err((err, true)) => last_err = Some(err),
+                Err((err, false)) => return Err(err),
+            }
+        }
+        Err(last_err.expect("pool has at least one endpoint"))
+    }
+
+    /// `call` for blocking clients
+    pub fn call_blocking<T, F>(&self, op: F) -> ClientResult<T>
+    where
+        F: Fn(&RpcClient) -> ClientResult<T>,
+    {
+        let mut last_err = None;
+        for index in self.ranked(Instant::now()) {
+            let started = Instant::now();
+            let result = op(&self.endpoints[index].blocking);
+            match self.settle(index, started, result) {
+                Ok(value) => return Ok(value),
+                Err((err, true)) => last_err = Some(err),
+                Err((err, false)) => return Err(err),
+            }
+        }
+        Err(last_err.expect("pool has at least one endpoint"))
+    }
+
+    /// Records the outcome; on error, also says whether another endpoint is worth trying
+    fn settle<T>(&self, index: usize, started: Instant, result: ClientResult<T>) -> Result<T, (ClientError, bool)> {
+        let now = Instant::now();
+        match result {
+            Ok(value) => {
+                self.record(index, now.duration_since(started), None, now);
+                Ok(value)
+            }
+            Err(err) => {
+                let fault = RpcFault::classify(&err);
+                self.record(index, now.duration_since(started), Some(fault), now);
+                if fault != RpcFault::Caller {
+                    tracing::warn!("🔄 RPC endpoint {} {}: {}. Failing over.", index, fault.label(), err);
+                }
+                Err((err, fault != RpcFault::Caller))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_to_healthiest_and_rotates_off_429() {
+        let urls: Vec<String> = ["http://a:8899", "http://b:8899", "http://a:8899", "http://c:8899"]
+            .iter().map(|u| u.to_string()).collect();
+        let pool = RpcPool::new(&urls, RpcPoolPolicy::default());
+        assert_eq!(pool.len(), 3);
+        let now = Instant::now();
+
+        // Slow answers push b behind the others; c erroring falls behind b
+        for _ in 0..10 {
+            pool.record(0, Duration::from_millis(40), None, now);
+            pool.record(1, Duration::from_millis(200), None, now);
+            pool.record(2, Duration::from_millis(40), Some(RpcFault::Endpoint), now);
+        }
+        assert_eq!(pool.ranked(now), vec![0, 1, 2]);
+
+        // A missing account is the caller's problem, not a failing endpoint
+        pool.record(0, Duration::from_millis(40), Some(RpcFault::Caller), now);
+        assert_eq!(pool.ranked(now)[0], 0);
+
+        // A 429 benches the best endpoint for the cooldown only
+        pool.record(0, Duration::from_millis(40), Some(RpcFault::RateLimited), now);
+        assert_eq!(pool.ranked(now), vec![1, 2, 0]);
+        assert_eq!(pool.ranked(now + Duration::from_secs(31))[0], 0);
+
+        let not_found = ClientError::from(ClientErrorKind::RpcError(RpcError::ForUser("AccountNotFound".into())));
+        assert_eq!(RpcFault::classify(&not_found), RpcFault::Caller);
+        let throttled = ClientError::from(ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code: 429,
+            message: "Too many requests for a specific RPC call".into(),
+            data: solana_client::rpc_request::RpcResponseErrorData::Empty,
+        }));
+        assert_eq!(RpcFault::classify(&throttled), RpcFault::RateLimited);
+    }
+}
@@ -0,0 +1,164 @@
+/// Tip Floor Policy
+///
+/// The Jito tip floor API publishes landed-tip percentiles. Which percentile we
+/// treat as the minimum tip, and how much of the expected profit we are willing
+/// to hand over on top, depends on how much is at stake: paper and micro runs
+/// don't need to outbid anyone, elite opportunities are worth overpaying for.
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct TipFloorResponse {
+    pub landed_tips_25th_percentile: f64,
+    pub landed_tips_50th_percentile: f64,
+    pub landed_tips_75th_percentile: f64,
+    pub landed_tips_95th_percentile: f64,
+    pub landed_tips_99th_percentile: f64,
+    pub ema_landed_tips_50th_percentile: f64,
+    pub ema_landed_tips_75th_percentile: f64,
+}
+
+/// One of the percentiles published by the tip floor API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TipPercentile {
+    P25,
+    P50,
+    P75,
+    P95,
+    P99,
+    Ema50,
+    Ema75,
+}
+
+impl TipPercentile {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "p25" | "25" => Ok(Self::P25),
+            "p50" | "50" => Ok(Self::P50),
+            "p75" | "75" => Ok(Self::P75),
+            "p95" | "95" => Ok(Self::P95),
+            "p99" | "99" => Ok(Self::P99),
+            "ema50" => Ok(Self::Ema50),
+            "ema75" => Ok(Self::Ema75),
+            other => Err(format!("Unknown tip percentile '{}' (expected p25, p50, p75, p95, p99, ema50 or ema75)", other)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::P25 => "p25",
+            Self::P50 => "p50",
+            Self::P75 => "p75",
+            Self::P95 => "p95",
+            Self::P99 => "p99",
+            Self::Ema50 => "ema50",
+            Self::Ema75 => "ema75",
+        }
+    }
+
+    /// Tip in SOL at this percentile. EMA percentiles fall back to the EMA median when empty.
+    fn select(&self, floor: &TipFloorResponse) -> f64 {
+        match self {
+            Self::P25 => floor.landed_tips_25th_percentile,
+            Self::P50 => floor.landed_tips_50th_percentile,
+            Self::P75 => floor.landed_tips_75th_percentile,
+            Self::P95 => floor.landed_tips_95th_percentile,
+            Self::P99 => floor.landed_tips_99th_percentile,
+            Self::Ema50 => floor.ema_landed_tips_50th_percentile,
+            Self::Ema75 if floor.ema_landed_tips_75th_percentile > 0.0 => floor.ema_landed_tips_75th_percentile,
+            Self::Ema75 => floor.ema_landed_tips_50th_percentile,
+        }
+    }
+}
+
+/// What the tip for one bundle was built from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TipDecision {
+    pub percentile: TipPercentile,
+    pub floor_lamports: u64,
+    pub profit_share: f64,
+    pub share_lamports: u64,
+}
+
+impl TipDecision {
+    pub fn competitive_tip(&self) -> u64 {
+        self.floor_lamports.max(self.share_lamports)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TipFloorPolicy {
+    pub percentile: TipPercentile,
+    pub elite_percentile: TipPercentile,
+    /// Fraction of expected profit offered on top of the floor
+    pub profit_share: f64,
+    pub profit_share_cap_lamports: u64,
+}
+
+impl Default for TipFloorPolicy {
+    /// The original heuristic: EMA 75th percentile floor, 10% of profit capped at 0.1 SOL
+    fn default() -> Self {
+        Self {
+            percentile: TipPercentile::Ema75,
+            elite_percentile: TipPercentile::Ema75,
+            profit_share: 0.10,
+            profit_share_cap_lamports: 100_000_000,
+        }
+    }
+}
+
+impl TipFloorPolicy {
+    pub fn decide(&self, floor: &TipFloorResponse, expected_profit_lamports: u64, is_elite: bool) -> TipDecision {
+        let percentile = if is_elite { self.elite_percentile } else { self.percentile };
+        let share_lamports = ((expected_profit_lamports as f64 * self.profit_share) as u64).min(self.profit_share_cap_lamports);
+        TipDecision {
+            percentile,
+            floor_lamports: (percentile.select(floor) * 1e9) as u64,
+            profit_share: self.profit_share,
+            share_lamports,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn floor() -> TipFloorResponse {
+        TipFloorResponse {
+            landed_tips_25th_percentile: 0.000_01,
+            landed_tips_50th_percentile: 0.000_02,
+            landed_tips_75th_percentile: 0.000_05,
+            landed_tips_95th_percentile: 0.000_5,
+            landed_tips_99th_percentile: 0.001,
+            ema_landed_tips_50th_percentile: 0.000_03,
+            ema_landed_tips_75th_percentile: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_elite_uses_its_own_percentile() {
+        let policy = TipFloorPolicy {
+            percentile: TipPercentile::P50,
+            elite_percentile: TipPercentile::P95,
+            profit_share: 0.05,
+            profit_share_cap_lamports: 1_000_000,
+        };
+
+        let normal = policy.decide(&floor(), 1_000_000, false);
+        assert_eq!((normal.percentile, normal.floor_lamports, normal.share_lamports), (TipPercentile::P50, 20_000, 50_000));
+        assert_eq!(normal.competitive_tip(), 50_000);
+
+        let elite = policy.decide(&floor(), 1_000_000, true);
+        assert_eq!(elite.floor_lamports, 500_000);
+        assert_eq!(elite.competitive_tip(), 500_000);
+    }
+
+    #[test]
+    fn test_default_matches_legacy_heuristic() {
+        // Empty EMA 75th falls back to the EMA median; the share is capped at 0.1 SOL
+        let decision = TipFloorPolicy::default().decide(&floor(), 10_000_000_000, false);
+        assert_eq!(decision.floor_lamports, 30_000);
+        assert_eq!(decision.share_lamports, 100_000_000);
+        assert!(TipPercentile::parse("P99").is_ok() && TipPercentile::parse("p42").is_err());
+    }
+}
@@ -0,0 +1,194 @@
+/// Token Account Setup
+///
+/// Bundles carry the account setup their legs need instead of relying on the
+/// wallet having been prepared beforehand. Every mint a leg reads or writes
+/// gets an idempotent `create_associated_token_account` unless the ATA cache
+/// already knows the payer's account exists. A route that spends SOL through
+/// a WSOL venue wraps its input first (transfer + `sync_native`), and with
+/// unwrapping on closes the WSOL account at the end so proceeds come back as
+/// native SOL. Pump.fun legs settle in native SOL and create their own token
+/// account, so they are left to the builder.
+///
+/// The cache belongs to the wallet manager: it is filled from ATA lookups,
+/// and mints a bundle had to create are re-checked on its next refresh.
+/// Flash-loan routes are funded in WSOL by the loan and are never wrapped.
+use std::collections::HashSet;
+use std::sync::RwLock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use mev_core::ArbitrageOpportunity;
+use mev_core::constants::{PUMP_FUN_PROGRAM, SOL_MINT};
+
+/// Mints whose payer ATA is known to exist, and those a bundle was built to create
+#[derive(Default)]
+pub struct AtaCache {
+    existing: RwLock<HashSet<Pubkey>>,
+    pending: RwLock<HashSet<Pubkey>>,
+}
+
+impl AtaCache {
+    pub fn mark_existing(&self, mints: impl IntoIterator<Item = Pubkey>) {
+        let mut existing = self.existing.write().unwrap();
+        let mut pending = self.pending.write().unwrap();
+        for mint in mints {
+            pending.remove(&mint);
+            existing.insert(mint);
+        }
+    }
+
+    pub fn exists(&self, mint: &Pubkey) -> bool {
+        self.existing.read().unwrap().contains(mint)
+    }
+
+    pub fn known(&self) -> Vec<Pubkey> {
+        self.existing.read().unwrap().iter().copied().collect()
+    }
+
+    /// Forgets everything, e.g. when the payer changes and every ATA address with it
+    pub fn clear(&self) {
+        self.existing.write().unwrap().clear();
+        self.pending.write().unwrap().clear();
+    }
+
+    /// Mints a bundle created an account for that no lookup has confirmed yet
+    pub fn pending(&self) -> Vec<Pubkey> {
+        self.pending.read().unwrap().iter().copied().collect()
+    }
+
+    fn note_created(&self, mint: Pubkey) {
+        self.pending.write().unwrap().insert(mint);
+    }
+
+    pub fn len(&self) -> usize {
+        self.existing.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenAccountPolicy {
+    /// Prepend idempotent ATA creation for mints not known to have one
+    pub create_atas: bool,
+    /// Wrap the input of a route that starts from SOL on a WSOL venue
+    pub wrap_sol: bool,
+    /// Close the WSOL account after the last leg, returning it as native SOL
+    pub unwrap_sol: bool,
+}
+
+impl Default for TokenAccountPolicy {
+    fn default() -> Self {
+        Self { create_atas: true, wrap_sol: true, unwrap_sol: true }
+    }
+}
+
+/// Instructions that go before the first leg and after the last
+#[derive(Debug, Default)]
+pub struct TokenAccountSetup {
+    pub prefix: Vec<Instruction>,
+    pub suffix: Vec<Instruction>,
+}
+
+impl TokenAccountSetup {
+    /// `legs` bracketed by the setup
+    pub fn around(self, legs: Vec<Instruction>) -> Vec<Instruction> {
+        let mut ixs = self.prefix;
+        ixs.extend(legs);
+        ixs.extend(self.suffix);
+        ixs
+    }
+}
+
+pub fn setup(payer: &Pubkey, opp: &ArbitrageOpportunity, cache: &AtaCache, policy: TokenAccountPolicy) -> TokenAccountSetup {
+    let mut setup = TokenAccountSetup::default();
+    let mut mints: Vec<Pubkey> = Vec::new();
+    for step in opp.steps.iter().filter(|s| s.program_id != PUMP_FUN_PROGRAM) {
+        for mint in [step.input_mint, step.output_mint] {
+            if !mints.contains(&mint) {
+                mints.push(mint);
+            }
+        }
+    }
+    let uses_wsol = mints.contains(&SOL_MINT);
+    let funded_by_loan = opp.flash_loan_amount > 0;
+    let wraps = policy.wrap_sol && !funded_by_loan
+        && opp.steps.first().is_some_and(|s| s.input_mint == SOL_MINT && s.program_id != PUMP_FUN_PROGRAM);
+    let unwraps = policy.unwrap_sol && !funded_by_loan && uses_wsol;
+
+    for mint in mints {
+        // A WSOL account closed at the end of every bundle never outlives it
+        let closed_each_time = mint == SOL_MINT && unwraps;
+        if policy.create_atas && (closed_each_time || !cache.exists(&mint)) {
+            setup.prefix.push(create_associated_token_account_idempotent(payer, payer, &mint, &spl_token::id()));
+            if !closed_each_time {
+                cache.note_created(mint);
+            }
+        }
+    }
+
+    let wsol_ata = get_associated_token_address(payer, &SOL_MINT);
+    if wraps {
+        setup.prefix.push(solana_sdk::system_instruction::transfer(payer, &wsol_ata, opp.input_amount));
+        setup.prefix.push(spl_token::instruction::sync_native(&spl_token::id(), &wsol_ata)
+            .expect("sync_native takes the token program id"));
+    }
+    if unwraps {
+        setup.suffix.push(spl_token::instruction::close_account(&spl_token::id(), &wsol_ata, payer, payer, &[])
+            .expect("close_account takes the token program id"));
+    }
+    setup
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::SwapStep;
+
+    #[test]
+    fn test_setup_creates_unknown_atas_and_wraps_sol() {
+        let payer = Pubkey::new_unique();
+        let (usdc, bonk) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let step = |input_mint, output_mint| SwapStep {
+            pool: Pubkey::new_unique(),
+            program_id: Pubkey::new_unique(),
+            input_mint,
+            output_mint,
+            expected_output: 1,
+        };
+        let opp = ArbitrageOpportunity {
+            steps: [step(SOL_MINT, usdc), step(usdc, bonk), step(bonk, SOL_MINT)].into_iter().collect(),
+            input_amount: 1_000_000_000,
+            ..Default::default()
+        };
+        let cache = AtaCache::default();
+        cache.mark_existing([usdc]);
+
+        // WSOL and BONK created, USDC known; wrap 1 SOL, close WSOL at the end
+        let built = setup(&payer, &opp, &cache, TokenAccountPolicy::default());
+        let programs: Vec<Pubkey> = built.prefix.iter().map(|ix| ix.program_id).collect();
+        assert_eq!(programs, vec![
+            spl_associated_token_account::id(),
+            spl_associated_token_account::id(),
+            solana_sdk::system_program::id(),
+            spl_token::id(),
+        ]);
+        assert_eq!(built.suffix.len(), 1);
+        assert_eq!(cache.pending(), vec![bonk]);
+
+        // Once BONK's account is confirmed only the per-bundle WSOL account is created
+        cache.mark_existing(cache.pending());
+        assert!(cache.pending().is_empty());
+        let built = setup(&payer, &opp, &cache, TokenAccountPolicy::default());
+        assert_eq!(built.prefix.len(), 3);
+
+        // A flash loan funds the input in WSOL: nothing to wrap or close
+        cache.mark_existing([SOL_MINT]);
+        let borrowed = ArbitrageOpportunity { flash_loan_amount: 1_000_000_000, ..opp.clone() };
+        let built = setup(&payer, &borrowed, &cache, TokenAccountPolicy { unwrap_sol: false, ..Default::default() });
+        assert!(built.prefix.is_empty() && built.suffix.is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+/// Direct TPU Sender
+///
+/// One of the fallback senders (`tpu` in `TX_SENDER_ORDER`). When every block
+/// engine failed, the fallback transaction goes straight to the QUIC TPU ports
+/// of the current leader and the leaders of the next `fanout_slots` slots
+/// instead of through an RPC node's forwarding queue.
+/// Leaders come from `getSlotLeaders`; their TPU addresses from
+/// `getClusterNodes`, cached for a few minutes since gossip rarely moves them.
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use solana_client::connection_cache::ConnectionCache;
+use solana_client::nonblocking::tpu_connection::TpuConnection;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::quic::QUIC_PORT_OFFSET;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
+use tokio::sync::RwLock;
+use crate::rpc_pool::RpcPool;
+
+/// How long the leader identity → TPU address map is trusted
+const CLUSTER_NODES_TTL: Duration = Duration::from_secs(300);
+/// QUIC connections kept per leader
+const CONNECTION_POOL_SIZE: usize = 2;
+
+#[derive(Default)]
+struct ClusterNodes {
+    fetched_at: Option<Instant>,
+    tpu_quic: HashMap<Pubkey, SocketAddr>,
+}
+
+pub struct TpuExecutor {
+    rpc: Arc<RpcPool>,
+    connections: Arc<ConnectionCache>,
+    /// Slots ahead of the current one whose leaders also get the transaction
+    fanout_slots: u64,
+    nodes: RwLock<ClusterNodes>,
+}
+
+impl TpuExecutor {
+    pub fn new(rpc: Arc<RpcPool>, fanout_slots: u64) -> Self {
+        Self {
+            rpc,
+            connections: Arc::new(ConnectionCache::new_quic("mev-tpu-fallback", CONNECTION_POOL_SIZE)),
+            fanout_slots: fanout_slots.max(1),
+            nodes: RwLock::new(ClusterNodes::default()),
+        }
+    }
+
+    /// QUIC TPU sockets of the current and upcoming leaders, nearest first
+    pub async fn leader_sockets(&self) -> anyhow::Result<Vec<SocketAddr>> {
+        let slot = self.rpc.call(|c| async move { c.get_slot().await }).await?;
+        let fanout = self.fanout_slots;
+        let leaders = self.rpc.call(|c| async move { c.get_slot_leaders(slot, fanout).await }).await?;
+        self.refresh_nodes().await?;
+        let nodes = self.nodes.read().await;
+        Ok(leader_targets(&leaders, &nodes.tpu_quic))
+    }
+
+    /// Sends the signed transaction to every upcoming leader; succeeds if any leader accepted it
+    pub async fn send_transaction(&self, tx: &Transaction) -> anyhow::Result<Signature> {
+        let wire = bincode::serialize(tx)?;
+        let targets = self.leader_sockets().await?;
+        if targets.is_empty() {
+            return Err(anyhow::anyhow!("No TPU address known for the upcoming leaders"));
+        }
+
+        let sends = targets.iter().map(|addr| {
+            let conn = self.connections.get_nonblocking_connection(addr);
+            let wire = &wire;
+            async move { (*addr, conn.send_data(wire).await) }
+        });
+        let mut delivered = 0;
+        for (addr, result) in futures::future::join_all(sends).await {
+            match result {
+                Ok(()) => delivered += 1,
+                Err(e) => tracing::debug!("🛰️ TPU send to {} failed: {}", addr, e),
+            }
+        }
+        if delivered == 0 {
+            return Err(anyhow::anyhow!("TPU send failed on all {} leader sockets", targets.len()));
+        }
+        tracing::info!("🛰️ Transaction sent straight to {}/{} leader TPUs", delivered, targets.len());
+        Ok(tx.signatures[0])
+    }
+
+    async fn refresh_nodes(&self) -> anyhow::Result<()> {
+        if self.nodes.read().await.fetched_at.is_some_and(|t| t.elapsed() < CLUSTER_NODES_TTL) {
+            return Ok(());
+        }
+        let contacts = self.rpc.call(|c| async move { c.get_cluster_nodes().await }).await?;
+        let tpu_quic = contacts.into_iter()
+            .filter_map(|node| Some((Pubkey::from_str(&node.pubkey).ok()?, quic_socket(node.tpu?))))
+            .collect();
+        *self.nodes.write().await = ClusterNodes { fetched_at: Some(Instant::now()), tpu_quic };
+        Ok(())
+    }
+}
+
+/// Gossip advertises the UDP TPU port; validators listen for QUIC at a fixed offset from it
+fn quic_socket(tpu: SocketAddr) -> SocketAddr {
+    SocketAddr::new(tpu.ip(), tpu.port() + QUIC_PORT_OFFSET)
+}
+
+/// One socket per distinct leader, in slot order. A leader holds four consecutive
+/// slots, so a fanout of eight usually reaches two or three validators.
+fn leader_targets(leaders: &[Pubkey], tpu_quic: &HashMap<Pubkey, SocketAddr>) -> Vec<SocketAddr> {
+    let mut targets: Vec<SocketAddr> = Vec::new();
+    for addr in leaders.iter().filter_map(|leader| tpu_quic.get(leader)) {
+        if !targets.contains(addr) {
+            targets.push(*addr);
+        }
+    }
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_targets_each_upcoming_leader_once_in_slot_order() {
+        let (a, b, unknown) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let tpu_a: SocketAddr = "10.0.0.1:8003".parse().unwrap();
+        let tpu_b: SocketAddr = "10.0.0.2:8003".parse().unwrap();
+        let nodes = HashMap::from([(a, quic_socket(tpu_a)), (b, quic_socket(tpu_b))]);
+
+        let schedule = [a, a, a, a, unknown, unknown, b, b];
+        let targets = leader_targets(&schedule, &nodes);
+        assert_eq!(targets, vec!["10.0.0.1:8009".parse().unwrap(), "10.0.0.2:8009".parse::<SocketAddr>().unwrap()]);
+        assert!(leader_targets(&[unknown], &nodes).is_empty());
+    }
+}
@@ -0,0 +1,285 @@
+/// Transaction Senders
+///
+/// Where the fallback transaction goes once every block engine has failed.
+/// Each landing service is an adapter behind `TxSender`: the RPC pool, Helius
+/// Sender, a Triton endpoint, bloXroute's Trader API, and direct TPU. A
+/// `SenderChain` holds the configured ones in fallback order and returns the
+/// first signature any of them accepts. Senders listed in the order but not
+/// configured are left out of the chain.
+use std::sync::Arc;
+use async_trait::async_trait;
+use base64::Engine;
+use serde::Deserialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
+use crate::http::HttpClient;
+use crate::rpc_pool::RpcPool;
+use crate::tpu::TpuExecutor;
+
+/// Fallback order when `TX_SENDER_ORDER` is unset: Helius Sender, then the leaders' TPUs, then plain RPC
+pub const DEFAULT_SENDER_ORDER: &str = "helius,tpu,rpc";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SenderKind {
+    Rpc,
+    Helius,
+    Triton,
+    Bloxroute,
+    Tpu,
+}
+
+impl SenderKind {
+    pub const ALL: [SenderKind; 5] = [SenderKind::Rpc, SenderKind::Helius, SenderKind::Triton, SenderKind::Bloxroute, SenderKind::Tpu];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SenderKind::Rpc => "rpc",
+            SenderKind::Helius => "helius",
+            SenderKind::Triton => "triton",
+            SenderKind::Bloxroute => "bloxroute",
+            SenderKind::Tpu => "tpu",
+        }
+    }
+
+    /// Comma-separated sender names, first tried first. Each sender may appear once.
+    pub fn parse_order(spec: &str) -> Result<Vec<SenderKind>, String> {
+        let mut order = Vec::new();
+        for name in spec.split(',').map(|n| n.trim().to_ascii_lowercase()).filter(|n| !n.is_empty()) {
+            let kind = Self::ALL.into_iter().find(|k| k.name() == name)
+                .ok_or_else(|| format!("Unknown transaction sender '{}'. Expected one of: rpc, helius, triton, bloxroute, tpu", name))?;
+            if order.contains(&kind) {
+                return Err(format!("Transaction sender '{}' is listed twice", name));
+            }
+            order.push(kind);
+        }
+        if order.is_empty() {
+            return Err("Transaction sender order is empty".to_string());
+        }
+        Ok(order)
+    }
+}
+
+#[async_trait]
+pub trait TxSender: Send + Sync {
+    fn kind(&self) -> SenderKind;
+
+    /// Submits a signed transaction; Ok once the service has accepted it, not once it lands
+    async fn send(&self, tx: &Transaction) -> anyhow::Result<Signature>;
+}
+
+/// `sendTransaction` through the health-scored RPC pool
+pub struct RpcSender {
+    rpc: Arc<RpcPool>,
+}
+
+impl RpcSender {
+    pub fn new(rpc: Arc<RpcPool>) -> Self {
+        Self { rpc }
+    }
+}
+
+#[async_trait]
+impl TxSender for RpcSender {
+    fn kind(&self) -> SenderKind {
+        SenderKind::Rpc
+    }
+
+    async fn send(&self, tx: &Transaction) -> anyhow::Result<Signature> {
+        Ok(self.rpc.call(|c| async move { c.send_transaction(tx).await }).await?)
+    }
+}
+
+/// A single vendor JSON-RPC endpoint that speaks `sendTransaction` (Helius Sender, Triton)
+pub struct EndpointSender {
+    kind: SenderKind,
+    client: RpcClient,
+    config: RpcSendTransactionConfig,
+}
+
+impl EndpointSender {
+    /// Helius Sender only accepts transactions with preflight off and its own retries disabled
+    pub fn helius(url: String) -> Self {
+        Self {
+            kind: SenderKind::Helius,
+            client: RpcClient::new(url),
+            config: RpcSendTransactionConfig { skip_preflight: true, max_retries: Some(0), ..Default::default() },
+        }
+    }
+
+    /// Triton endpoints take the token in the URL path and forward like any RPC node
+    pub fn triton(url: String) -> Self {
+        Self {
+            kind: SenderKind::Triton,
+            client: RpcClient::new(url),
+            config: RpcSendTransactionConfig::default(),
+        }
+    }
+
+    pub fn url(&self) -> String {
+        self.client.url()
+    }
+}
+
+#[async_trait]
+impl TxSender for EndpointSender {
+    fn kind(&self) -> SenderKind {
+        self.kind
+    }
+
+    async fn send(&self, tx: &Transaction) -> anyhow::Result<Signature> {
+        Ok(self.client.send_transaction_with_config(tx, self.config).await?)
+    }
+}
+
+#[derive(Deserialize)]
+struct BloxrouteSubmitResponse {
+    signature: String,
+}
+
+/// bloXroute Trader API `submit`, authenticated with the account's auth header
+pub struct BloxrouteSender {
+    http: Arc<HttpClient>,
+    submit_url: String,
+    auth_header: String,
+}
+
+impl BloxrouteSender {
+    pub fn new(http: Arc<HttpClient>, base_url: &str, auth_header: String) -> Self {
+        Self {
+            http,
+            submit_url: format!("{}/api/v2/submit", base_url.trim_end_matches('/')),
+            auth_header,
+        }
+    }
+}
+
+#[async_trait]
+impl TxSender for BloxrouteSender {
+    fn kind(&self) -> SenderKind {
+        SenderKind::Bloxroute
+    }
+
+    async fn send(&self, tx: &Transaction) -> anyhow::Result<Signature> {
+        let content = base64::engine::general_purpose::STANDARD.encode(bincode::serialize(tx)?);
+        let request = self.http.post(&self.submit_url)
+            .header("Authorization", &self.auth_header)
+            .json(&serde_json::json!({
+                "transaction": { "content": content },
+                "skipPreFlight": true,
+                "frontRunningProtection": false,
+            }));
+        let response = self.http.send(request).await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("bloXroute submit returned {}: {}", response.status(), response.text().await.unwrap_or_default()));
+        }
+        Ok(response.json::<BloxrouteSubmitResponse>().await?.signature.parse()?)
+    }
+}
+
+#[async_trait]
+impl TxSender for TpuExecutor {
+    fn kind(&self) -> SenderKind {
+        SenderKind::Tpu
+    }
+
+    async fn send(&self, tx: &Transaction) -> anyhow::Result<Signature> {
+        self.send_transaction(tx).await
+    }
+}
+
+/// Configured senders in fallback order
+#[derive(Clone, Default)]
+pub struct SenderChain {
+    senders: Vec<Arc<dyn TxSender>>,
+}
+
+impl SenderChain {
+    pub fn new(senders: Vec<Arc<dyn TxSender>>) -> Self {
+        Self { senders }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.senders.is_empty()
+    }
+
+    pub fn contains(&self, kind: SenderKind) -> bool {
+        self.senders.iter().any(|s| s.kind() == kind)
+    }
+
+    /// Sender names in the order they are tried
+    pub fn describe(&self) -> String {
+        self.senders.iter().map(|s| s.kind().name()).collect::<Vec<_>>().join(" -> ")
+    }
+
+    /// Tries each sender in turn; returns the first one that accepted the transaction
+    pub async fn send(&self, tx: &Transaction) -> anyhow::Result<(SenderKind, Signature)> {
+        let mut errors = Vec::new();
+        for sender in &self.senders {
+            let kind = sender.kind();
+            match sender.send(tx).await {
+                Ok(signature) => {
+                    mev_core::telemetry::TX_SENDS.with_label_values(&[kind.name(), "ok"]).inc();
+                    return Ok((kind, signature));
+                }
+                Err(e) => {
+                    mev_core::telemetry::TX_SENDS.with_label_values(&[kind.name(), "error"]).inc();
+                    tracing::warn!("⚠️ {} send failed: {}", kind.name(), e);
+                    errors.push(format!("{}: {}", kind.name(), e));
+                }
+            }
+        }
+        if errors.is_empty() {
+            return Err(anyhow::anyhow!("No transaction sender configured"));
+        }
+        Err(anyhow::anyhow!("All transaction senders failed ({})", errors.join("; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct FakeSender {
+        kind: SenderKind,
+        accepts: bool,
+        calls: AtomicU32,
+    }
+
+    #[async_trait]
+    impl TxSender for FakeSender {
+        fn kind(&self) -> SenderKind {
+            self.kind
+        }
+
+        async fn send(&self, _tx: &Transaction) -> anyhow::Result<Signature> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            if self.accepts { Ok(Signature::default()) } else { Err(anyhow::anyhow!("down")) }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_falls_through_in_configured_order() {
+        assert_eq!(SenderKind::parse_order(" Triton, rpc ").unwrap(), vec![SenderKind::Triton, SenderKind::Rpc]);
+        assert_eq!(SenderKind::parse_order(DEFAULT_SENDER_ORDER).unwrap(), vec![SenderKind::Helius, SenderKind::Tpu, SenderKind::Rpc]);
+        assert!(SenderKind::parse_order("rpc,jito").is_err());
+        assert!(SenderKind::parse_order("rpc,RPC").is_err());
+        assert!(SenderKind::parse_order(" , ").is_err());
+
+        let fake = |kind, accepts| Arc::new(FakeSender { kind, accepts, calls: AtomicU32::new(0) });
+        let (helius, triton, rpc) = (fake(SenderKind::Helius, false), fake(SenderKind::Triton, true), fake(SenderKind::Rpc, true));
+        let chain = SenderChain::new(vec![helius.clone(), triton.clone(), rpc.clone()]);
+        assert_eq!(chain.describe(), "helius -> triton -> rpc");
+
+        let tx = Transaction::default();
+        let (kind, _) = chain.send(&tx).await.unwrap();
+        assert_eq!(kind, SenderKind::Triton);
+        assert_eq!((helius.calls.load(Ordering::Relaxed), rpc.calls.load(Ordering::Relaxed)), (1, 0));
+
+        let err = SenderChain::new(vec![helius]).send(&tx).await.unwrap_err();
+        assert!(err.to_string().contains("helius: down"));
+        assert!(SenderChain::default().send(&tx).await.is_err());
+    }
+}
@@ -0,0 +1,345 @@
+// Port Definitions for Hexagonal Architecture
+// These traits define the boundaries between application and infrastructure layers
+//
+// Versioning: this crate follows semver. Adding a required trait method or changing
+// a signature is a breaking change and bumps the minor version while we are on 0.x.
+// New methods ship with a default body where one makes sense.
+
+// Re-exported so adapters only need this crate to name the types in the signatures
+pub use mev_core;
+pub use async_trait::async_trait;
+
+use anyhow::Result;
+use mev_core::ArbitrageOpportunity;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, hash::Hash};
+
+/// Port for AI/ML prediction services
+/// Allows swapping between different model implementations (ONNX, remote API, mock, etc.)
+#[async_trait::async_trait]
+pub trait AIModelPort: Send + Sync {
+    /// Predict confidence score for an arbitrage opportunity
+    fn predict_confidence(&self, opportunity: &ArbitrageOpportunity) -> Result<f32>;
+
+    /// Same, with the engine's extracted feature vector (latest schema, see
+    /// `strategy::adapters::features`). Models that only read the opportunity can ignore it.
+    fn predict_with_features(&self, opportunity: &ArbitrageOpportunity, features: &[f32]) -> Result<f32> {
+        let _ = features;
+        self.predict_confidence(opportunity)
+    }
+}
+
+/// Port for resolving pool keys required for instruction building
+/// Decouples the executor from specific RPC or local database clients
+#[async_trait::async_trait]
+pub trait PoolKeyProvider: Send + Sync {
+    async fn get_swap_keys(&self, pool_address: &Pubkey) -> Result<mev_core::raydium::RaydiumSwapKeys>;
+    async fn get_orca_keys(&self, pool_address: &Pubkey) -> Result<mev_core::orca::OrcaSwapKeys>;
+    async fn get_meteora_keys(&self, pool_address: &Pubkey) -> Result<mev_core::meteora::MeteoraSwapKeys>;
+
+    async fn get_raydium_clmm_keys(&self, pool_address: &Pubkey) -> Result<mev_core::raydium_clmm::RaydiumClmmSwapKeys> {
+        Err(anyhow::anyhow!("Raydium CLMM keys not supported by this provider (pool {})", pool_address))
+    }
+
+    async fn get_phoenix_keys(&self, market: &Pubkey) -> Result<mev_core::phoenix::PhoenixSwapKeys> {
+        Err(anyhow::anyhow!("Phoenix keys not supported by this provider (market {})", market))
+    }
+
+    async fn get_openbook_keys(&self, market: &Pubkey) -> Result<mev_core::openbook::OpenBookSwapKeys> {
+        Err(anyhow::anyhow!("OpenBook keys not supported by this provider (market {})", market))
+    }
+}
+
+/// Port for bundle execution services
+/// Abstracts the details of transaction submission (Jito, direct RPC, etc.)
+#[async_trait::async_trait]
+pub trait ExecutionPort: Send + Sync {
+    /// Build instructions for an opportunity (for simulation or external use)
+    async fn build_bundle_instructions(
+        &self,
+        opportunity: ArbitrageOpportunity,
+        tip_lamports: u64,
+        max_slippage_bps: u16,
+    ) -> Result<Vec<Instruction>>;
+
+    /// Build and send a complete bundle to the network
+    async fn build_and_send_bundle(
+        &self,
+        opportunity: ArbitrageOpportunity,
+        recent_blockhash: Hash,
+        tip_lamports: u64,
+        max_slippage_bps: u16,
+    ) -> Result<String>;
+
+    /// Get the public key of the execution account
+    fn pubkey(&self) -> &Pubkey;
+}
+
+/// Port for bundle simulation services
+#[async_trait::async_trait]
+pub trait BundleSimulator: Send + Sync {
+    async fn simulate_bundle(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+    ) -> std::result::Result<u64, String>;
+}
+
+/// Port for flash-loan liquidity (Solend, marginfi, Kamino, ...)
+/// Lets a route borrow its input and repay it within the same transaction,
+/// so opportunities larger than wallet inventory can still execute atomically
+pub trait FlashLoanProvider: Send + Sync {
+    /// Short protocol name for logs
+    fn name(&self) -> &'static str;
+
+    /// Whether `mint` can be borrowed from this provider
+    fn supports(&self, mint: &Pubkey) -> bool;
+
+    /// Fee charged on a loan of `mint`, in basis points
+    fn fee_bps(&self, mint: &Pubkey) -> u16;
+
+    /// Wraps the trade legs in borrow/repay of `amount` of `mint`.
+    /// `borrow_index` is the position the borrow instruction will take in the final
+    /// transaction; repay instructions check it through the instructions sysvar.
+    fn wrap(
+        &self,
+        borrower: &Pubkey,
+        mint: &Pubkey,
+        amount: u64,
+        trade_ixs: Vec<Instruction>,
+        borrow_index: usize,
+    ) -> Result<Vec<Instruction>>;
+}
+
+/// A mint's oracle price, per whole token
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferencePrice {
+    pub price: f64,
+    pub conf: f64,
+    pub decimals: u8,
+    pub publish_time: u64,
+}
+
+impl ReferencePrice {
+    pub fn per_atom(&self) -> f64 {
+        self.price / 10f64.powi(self.decimals as i32)
+    }
+}
+
+/// Port for external price oracles (Pyth, Switchboard, ...)
+/// Backs the route sanity check and values amounts of any mint in SOL
+pub trait PriceOraclePort: Send + Sync {
+    /// Short oracle name for logs
+    fn name(&self) -> &'static str;
+
+    /// The mint's latest price, if it was published recently enough to trust
+    fn reference(&self, mint: &Pubkey, now_secs: u64) -> Option<ReferencePrice>;
+
+    /// Lamports one raw unit of `mint` is worth; None without fresh prices for it and SOL
+    fn sol_per_atom(&self, mint: &Pubkey, now_secs: u64) -> Option<f64> {
+        let sol_mint = mev_core::constants::SOL_MINT;
+        if *mint == sol_mint {
+            return Some(1.0);
+        }
+        let (price, sol) = (self.reference(mint, now_secs)?, self.reference(&sol_mint, now_secs)?);
+        Some(price.per_atom() / sol.per_atom()).filter(|rate| rate.is_finite())
+    }
+
+    /// SOL-equivalent lamports of `amount` raw units of `mint`
+    fn sol_value(&self, mint: &Pubkey, amount: u128, now_secs: u64) -> Option<u64> {
+        if *mint == mev_core::constants::SOL_MINT {
+            return Some(amount.min(u64::MAX as u128) as u64);
+        }
+        Some((amount as f64 * self.sol_per_atom(mint, now_secs)?) as u64)
+    }
+}
+
+/// Port for independent quote sources (aggregator quote APIs) that re-price our hops
+#[async_trait::async_trait]
+pub trait QuoteSourcePort: Send + Sync {
+    /// Short source name for logs
+    fn name(&self) -> &'static str;
+
+    /// Output for `amount_in` swapped on the step's venue and pair; None when the source can't route it
+    async fn quote_hop(&self, step: &mev_core::SwapStep, amount_in: u64) -> Result<Option<u64>>;
+}
+
+/// Realized results of the most recent trades
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TradeStats {
+    pub trades: usize,
+    pub win_rate: f64,
+    /// Mean profit of winning trades (lamports)
+    pub avg_win: f64,
+    /// Mean loss of losing trades (lamports, positive)
+    pub avg_loss: f64,
+}
+
+/// Port for telemetry and metrics logging
+pub trait TelemetryPort: Send + Sync {
+    fn log_opportunity(&self, profitable: bool);
+    fn log_profit_sanity_rejection(&self);
+    fn log_safety_rejection(&self);
+    fn log_rug_rejection(&self);
+    fn log_dna_rejection(&self);
+    fn log_elite_match(&self);
+    fn log_slippage_rejection(&self);
+    fn log_execution_attempt(&self);
+    fn log_jito_success(&self);
+    fn log_jito_failed(&self);
+    fn log_rpc_fallback_success(&self);
+    fn log_rpc_fallback_failed(&self);
+    /// Fallback sent straight to leader TPUs (no Helius Sender configured)
+    fn log_tpu_fallback_success(&self) {}
+    fn log_tpu_fallback_failed(&self) {}
+    fn log_retry_success(&self, retry_number: usize);
+    fn log_endpoint_attempt(&self, endpoint_index: usize);
+    fn log_endpoint_success(&self, endpoint_index: usize);
+    /// Final status of a submitted bundle, for land rate by endpoint and tip
+    fn log_bundle_outcome(&self, _endpoint_index: usize, _tip_lamports: u64, _landed: bool) {}
+    fn log_realized_pnl(&self, lamports: i64);
+    fn log_build_latency(&self, ms: f64);
+    fn log_submit_latency(&self, ms: f64);
+    
+    /// NEW: Comprehensive landed trade reporting (Phase 3 Hardening)
+    fn log_trade_landed(&self, opportunity: ArbitrageOpportunity, signature: String, success: bool);
+    /// A raced trade settled through its RPC copy: the trade dispatched as `dispatched` is reported under `settled`
+    fn log_race_winner(&self, _dispatched: &str, _settled: &str) {}
+    
+    // Getters for Risk Management
+    fn get_total_loss(&self) -> u64;
+    fn get_win_rate(&self) -> f32;
+    /// Rolling window of landed results, for position sizing
+    fn recent_trade_stats(&self) -> TradeStats {
+        TradeStats::default()
+    }
+    /// Landed share of resolved Jito bundles; None until there are enough to go by
+    fn bundle_land_rate(&self) -> Option<f64> {
+        None
+    }
+}
+
+/// Exposure limit a route would breach
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExposureLimit {
+    /// Concurrent in-flight notional routed through one mint
+    MintNotional,
+    /// Dispatched routes through one pool in the last minute
+    PoolTrades,
+    /// Own capital committed by one trade, as a share of the wallet
+    WalletShare,
+}
+
+impl ExposureLimit {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExposureLimit::MintNotional => "mint_notional",
+            ExposureLimit::PoolTrades => "pool_trades",
+            ExposureLimit::WalletShare => "wallet_share",
+        }
+    }
+}
+
+/// Port for the risk layer's per-trade exposure checks, consulted before execution
+pub trait ExposurePort: Send + Sync {
+    /// First limit the route would breach at `now_ms` (the caller's clock), if any
+    fn check_exposure(&self, opportunity: &ArbitrageOpportunity, now_ms: u64) -> Option<ExposureLimit>;
+    /// Counts a dispatched route against the limits
+    fn record_dispatch(&self, opportunity: &ArbitrageOpportunity, now_ms: u64);
+}
+
+/// Port for the durable trade journal. Settlement is reported by the execution side.
+pub trait TradeJournalPort: Send + Sync {
+    /// A route refused by a gate (`reason` is the rejection label)
+    fn record_rejection(&self, opportunity: &ArbitrageOpportunity, reason: &'static str);
+    /// A route handed to the executor and accepted for sending
+    fn record_dispatch(&self, opportunity: &ArbitrageOpportunity, tip_lamports: u64, bundle_id: &str);
+    /// A route the executor could not send on any path
+    fn record_send_failure(&self, opportunity: &ArbitrageOpportunity, tip_lamports: u64, error: &str);
+}
+
+/// An opportunity the engine decided on, as a model training row
+#[derive(Debug, Clone)]
+pub struct TrainingEvaluation {
+    pub opportunity: ArbitrageOpportunity,
+    /// Model features at the latest schema, extracted at decision time
+    pub features: Vec<f32>,
+    /// `executed`, `execution_failed`, or the rejection label
+    pub decision: &'static str,
+    /// Simulation outcome when one ran: CU consumed, or the error
+    pub simulation: Option<std::result::Result<u64, String>>,
+    pub tip_lamports: u64,
+    /// Set for dispatched routes; their settlement arrives later under this id
+    pub bundle_id: Option<String>,
+}
+
+/// Port for the model training-data exporter
+pub trait TrainingExportPort: Send + Sync {
+    fn record_evaluation(&self, evaluation: TrainingEvaluation);
+}
+
+#[async_trait::async_trait]
+pub trait MarketIntelligencePort: Send + Sync {
+    /// Check if a token address is a known false positive or blacklisted
+    async fn is_blacklisted(&self, token_address: &Pubkey) -> Result<bool>;
+
+    /// Save a confirmed success story to the database (Phase 3 Hardening)
+    async fn save_story(&self, story: mev_core::SuccessStory) -> Result<()>;
+
+    /// Get high-level analysis of success stories (the "Success DNA")
+    async fn get_success_analysis(&self) -> Result<mev_core::SuccessAnalysis>;
+
+    /// Check if a token matching specific DNA traits should be traded
+    async fn match_dna(&self, dna: &mev_core::TokenDNA) -> Result<mev_core::DNAMatch>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    // An out-of-tree adapter only needs this crate: if this stops compiling, the change is breaking.
+    struct ConstantModel;
+
+    impl AIModelPort for ConstantModel {
+        fn predict_confidence(&self, _opportunity: &ArbitrageOpportunity) -> Result<f32> {
+            Ok(0.5)
+        }
+    }
+
+    struct FixedPrices;
+
+    impl PriceOraclePort for FixedPrices {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn reference(&self, mint: &Pubkey, _now_secs: u64) -> Option<ReferencePrice> {
+            let (price, decimals) = if *mint == mev_core::constants::SOL_MINT { (150.0, 9) } else { (1.5, 6) };
+            Some(ReferencePrice { price, conf: 0.0, decimals, publish_time: 0 })
+        }
+    }
+
+    struct NullSimulator;
+
+    #[async_trait]
+    impl BundleSimulator for NullSimulator {
+        async fn simulate_bundle(&self, _instructions: &[Instruction], _payer: &Pubkey) -> std::result::Result<u64, String> {
+            Ok(0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ports_are_object_safe_for_external_adapters() {
+        let model: Arc<dyn AIModelPort> = Arc::new(ConstantModel);
+        assert_eq!(model.predict_confidence(&ArbitrageOpportunity::default()).unwrap(), 0.5);
+
+        let sim: Arc<dyn BundleSimulator> = Arc::new(NullSimulator);
+        assert_eq!(sim.simulate_bundle(&[], &Pubkey::new_unique()).await, Ok(0));
+
+        // 100 tokens at $1.50 are worth 1 SOL at $150
+        let oracle: Arc<dyn PriceOraclePort> = Arc::new(FixedPrices);
+        let value = oracle.sol_value(&Pubkey::new_unique(), 100_000_000, 0).unwrap();
+        assert!(value.abs_diff(1_000_000_000) <= 1, "{}", value);
+        assert_eq!(oracle.sol_value(&mev_core::constants::SOL_MINT, 7, 0), Some(7));
+    }
+}
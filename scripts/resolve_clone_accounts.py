@@ -0,0 +1,108 @@
+#!/usr/bin/env python3
+"""
+Resolves every account a Raydium V4 swap touches for a set of pools, so
+solana-test-validator can be seeded with them.
+
+Prints one "<kind> <pubkey>" line per account, where kind is "program" or
+"account". Offsets mirror core/src/raydium.rs (AmmInfo / MarketStateV3).
+
+Usage: resolve_clone_accounts.py <rpc_url> <pool> [<pool> ...]
+"""
+
+import base64
+import json
+import sys
+import urllib.request
+
+B58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+RAYDIUM_V4_PROGRAM = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8"
+
+# AmmInfo (752 bytes)
+AMM_FIELDS = {
+    "base_vault": 336,
+    "quote_vault": 368,
+    "base_mint": 400,
+    "quote_mint": 432,
+    "lp_mint": 464,
+    "open_orders": 496,
+    "market_id": 528,
+    "market_program_id": 560,
+    "target_orders": 592,
+}
+
+# MarketStateV3 (388 bytes)
+MARKET_FIELDS = {
+    "coin_vault": 117,
+    "pc_vault": 165,
+    "event_queue": 253,
+    "bids": 285,
+    "asks": 317,
+}
+
+
+def b58encode(raw: bytes) -> str:
+    n = int.from_bytes(raw, "big")
+    out = ""
+    while n > 0:
+        n, rem = divmod(n, 58)
+        out = B58_ALPHABET[rem] + out
+    pad = len(raw) - len(raw.lstrip(b"\0"))
+    return "1" * pad + out
+
+
+def get_account_data(rpc_url: str, pubkey: str) -> bytes:
+    body = json.dumps({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "getAccountInfo",
+        "params": [pubkey, {"encoding": "base64"}],
+    }).encode()
+    req = urllib.request.Request(rpc_url, data=body, headers={"Content-Type": "application/json"})
+    with urllib.request.urlopen(req, timeout=30) as resp:
+        value = json.load(resp)["result"]["value"]
+    if value is None:
+        raise SystemExit(f"❌ Account {pubkey} not found")
+    return base64.b64decode(value["data"][0])
+
+
+def read_pubkey(data: bytes, offset: int) -> str:
+    return b58encode(data[offset:offset + 32])
+
+
+def resolve_pool(rpc_url: str, pool: str):
+    amm = get_account_data(rpc_url, pool)
+    if len(amm) < 752:
+        raise SystemExit(f"❌ {pool} is not a Raydium V4 pool ({len(amm)} bytes)")
+    fields = {name: read_pubkey(amm, off) for name, off in AMM_FIELDS.items()}
+
+    market = get_account_data(rpc_url, fields["market_id"])
+    if len(market) < 388:
+        raise SystemExit(f"❌ Market {fields['market_id']} too small ({len(market)} bytes)")
+    fields.update({name: read_pubkey(market, off) for name, off in MARKET_FIELDS.items()})
+
+    programs = {fields.pop("market_program_id")}
+    accounts = {pool, *fields.values()}
+    return programs, accounts
+
+
+def main():
+    if len(sys.argv) < 3:
+        print(__doc__, file=sys.stderr)
+        sys.exit(1)
+
+    rpc_url, pools = sys.argv[1], sys.argv[2:]
+    programs, accounts = {RAYDIUM_V4_PROGRAM}, set()
+    for pool in pools:
+        p, a = resolve_pool(rpc_url, pool)
+        programs |= p
+        accounts |= a
+
+    for program in sorted(programs):
+        print(f"program {program}")
+    for account in sorted(accounts):
+        print(f"account {account}")
+
+
+if __name__ == "__main__":
+    main()
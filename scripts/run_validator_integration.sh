@@ -0,0 +1,131 @@
+#!/bin/bash
+# scripts/run_validator_integration.sh
+# End-to-end execution harness: seeds solana-test-validator with a mainnet
+# snapshot of a known pool set, runs the executor integration tests against it,
+# then boots the engine in LiveMicro pointed at the local cluster.
+#
+# The first run needs MAINNET_RPC_URL to build the snapshot. Later runs (CI)
+# load it from SNAPSHOT_DIR and never touch mainnet.
+#
+# Env:
+#   POOLS            Comma separated Raydium V4 pools (default: SOL/USDC)
+#   SNAPSHOT_DIR     Where cloned accounts/programs live (default: tests/validator_snapshot)
+#   MAINNET_RPC_URL  Source for a fresh snapshot (default: public mainnet RPC)
+#   ENGINE_SECS      How long the LiveMicro smoke run lasts once the workers are up (default: 60, 0 skips it)
+#   ENGINE_BOOT_SECS How long the engine may take to start its workers (default: 120)
+#   REFRESH=1        Rebuild the snapshot even if one exists
+
+set -euo pipefail
+
+POOLS="${POOLS:-58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2}"
+SNAPSHOT_DIR="${SNAPSHOT_DIR:-tests/validator_snapshot}"
+MAINNET_RPC_URL="${MAINNET_RPC_URL:-https://api.mainnet-beta.solana.com}"
+ENGINE_SECS="${ENGINE_SECS:-60}"
+ENGINE_BOOT_SECS="${ENGINE_BOOT_SECS:-120}"
+ENGINE_BIN="${CARGO_TARGET_DIR:-target}/release/engine"
+LEDGER_DIR="$(mktemp -d)"
+LOG_DIR="logs/validator_integration_$(date +%Y%m%d_%H%M%S)"
+RPC_URL="http://127.0.0.1:8899"
+WS_URL="ws://127.0.0.1:8900"
+TEST_KEYPAIR="$LEDGER_DIR/payer.json"
+
+mkdir -p "$LOG_DIR" "$SNAPSHOT_DIR/accounts" "$SNAPSHOT_DIR/programs"
+
+cleanup() {
+    [ -n "${ENGINE_PID:-}" ] && kill -INT "$ENGINE_PID" 2>/dev/null || true
+    [ -n "${VALIDATOR_PID:-}" ] && kill "$VALIDATOR_PID" 2>/dev/null || true
+    rm -rf "$LEDGER_DIR"
+}
+trap cleanup EXIT
+
+# 1. Snapshot (only when missing or REFRESH=1)
+if [ "${REFRESH:-0}" = "1" ] || [ -z "$(ls -A "$SNAPSHOT_DIR/accounts" 2>/dev/null)" ]; then
+    echo "📸 Building snapshot for pools: $POOLS"
+    rm -f "$SNAPSHOT_DIR"/accounts/*.json "$SNAPSHOT_DIR"/programs/*.so
+    python3 scripts/resolve_clone_accounts.py "$MAINNET_RPC_URL" ${POOLS//,/ } | while read -r kind pubkey; do
+        if [ "$kind" = "program" ]; then
+            solana program dump -u "$MAINNET_RPC_URL" "$pubkey" "$SNAPSHOT_DIR/programs/$pubkey.so" > /dev/null
+        else
+            solana account -u "$MAINNET_RPC_URL" "$pubkey" --output json-compact --output-file "$SNAPSHOT_DIR/accounts/$pubkey.json" > /dev/null
+        fi
+        echo "   ├─ $kind $pubkey"
+    done
+    echo "$POOLS" > "$SNAPSHOT_DIR/pools.txt"
+fi
+POOLS="$(cat "$SNAPSHOT_DIR/pools.txt" 2>/dev/null || echo "$POOLS")"
+
+# 2. Build up front, so the timed runs below measure the engine rather than the compiler
+if [ "$ENGINE_SECS" -gt 0 ]; then
+    echo "🔨 Building engine (release)..."
+    cargo build -p engine --release 2>&1 | tee "$LOG_DIR/engine_build.log"
+fi
+cargo test -p executor --test validator_integration --no-run 2>&1 | tee "$LOG_DIR/executor_build.log"
+
+# 3. Validator
+PROGRAM_ARGS=()
+for so in "$SNAPSHOT_DIR"/programs/*.so; do
+    PROGRAM_ARGS+=(--bpf-program "$(basename "$so" .so)" "$so")
+done
+
+echo "🧪 Starting solana-test-validator..."
+solana-test-validator --reset --quiet \
+    --ledger "$LEDGER_DIR/ledger" \
+    --account-dir "$SNAPSHOT_DIR/accounts" \
+    "${PROGRAM_ARGS[@]}" > "$LOG_DIR/validator.log" 2>&1 &
+VALIDATOR_PID=$!
+
+for _ in $(seq 1 60); do
+    if solana -u "$RPC_URL" cluster-version > /dev/null 2>&1; then break; fi
+    sleep 1
+done
+solana -u "$RPC_URL" cluster-version > /dev/null || { echo "❌ Validator did not come up. See $LOG_DIR/validator.log"; exit 1; }
+echo "✅ Validator ready at $RPC_URL"
+
+# 4. Executor integration tests (swap really lands and balances move)
+echo "🔬 Running executor integration tests..."
+VALIDATOR_RPC_URL="$RPC_URL" VALIDATOR_POOLS="$POOLS" \
+    cargo test -p executor --test validator_integration -- --ignored --test-threads=1 2>&1 | tee "$LOG_DIR/executor_tests.log"
+
+# 5. Engine smoke run in LiveMicro against the local cluster
+if [ "$ENGINE_SECS" -gt 0 ]; then
+    solana-keygen new --no-bip39-passphrase --silent -o "$TEST_KEYPAIR" > /dev/null
+    solana -u "$RPC_URL" airdrop 5 "$(solana-keygen pubkey "$TEST_KEYPAIR")" > /dev/null
+    BALANCE_BEFORE=$(solana -u "$RPC_URL" balance --lamports "$TEST_KEYPAIR" | awk '{print $1}')
+
+    echo "🔥 Booting engine in LiveMicro for ${ENGINE_SECS}s..."
+    # Only the snapshot's programs exist locally, so skip the mainnet venue check
+    EXECUTION_MODE=LiveMicro RPC_URL="$RPC_URL" WS_URL="$WS_URL" JITO_URL="" \
+        KEYPAIR_PATH="$TEST_KEYPAIR" VERIFY_PROGRAM_IDS=false \
+        "$ENGINE_BIN" --no-tui > "$LOG_DIR/engine.log" 2>&1 &
+    ENGINE_PID=$!
+
+    # Booted means the pipeline is consuming events: wait for the first worker
+    for _ in $(seq 1 "$ENGINE_BOOT_SECS"); do
+        if grep -q "Worker 0 started" "$LOG_DIR/engine.log" || ! kill -0 "$ENGINE_PID" 2>/dev/null; then break; fi
+        sleep 1
+    done
+    if ! grep -q "Worker 0 started" "$LOG_DIR/engine.log"; then
+        echo "❌ Engine did not start its workers within ${ENGINE_BOOT_SECS}s. See $LOG_DIR/engine.log"
+        exit 1
+    fi
+    echo "✅ Engine workers up"
+
+    sleep "$ENGINE_SECS"
+    if ! kill -0 "$ENGINE_PID" 2>/dev/null; then
+        echo "❌ Engine exited early. See $LOG_DIR/engine.log"
+        exit 1
+    fi
+    if grep -q "panicked at" "$LOG_DIR/engine.log"; then
+        echo "❌ Engine panicked. See $LOG_DIR/engine.log"
+        exit 1
+    fi
+
+    BALANCE_AFTER=$(solana -u "$RPC_URL" balance --lamports "$TEST_KEYPAIR" | awk '{print $1}')
+    echo "💰 Payer balance: $BALANCE_BEFORE -> $BALANCE_AFTER lamports"
+    if grep -q "Trade Confirmed" "$LOG_DIR/engine.log" && [ "$BALANCE_BEFORE" = "$BALANCE_AFTER" ]; then
+        echo "❌ Engine reported a confirmed trade but the payer balance did not move"
+        exit 1
+    fi
+fi
+
+echo "✅ Validator integration passed. Logs in $LOG_DIR/"
@@ -8,19 +8,34 @@ from sklearn.model_selection import train_test_split, cross_val_score
 from skl2onnx import convert_sklearn
 from skl2onnx.common.data_types import FloatTensorType
 import onnx
+import glob
 
 # Configuration
 SCRIPT_DIR = os.path.dirname(os.path.abspath(__file__))
 ROOT_DIR = os.path.dirname(SCRIPT_DIR)
 ARBITRAGE_DATA_PATH = os.path.join(ROOT_DIR, "data", "arbitrage_data.csv")
 MODEL_PATH = os.path.join(ROOT_DIR, "ai_model.onnx")
+# Written by the engine with TRAINING_EXPORT_ENABLED (feature schema v2)
+TRAINING_EXPORT_GLOB = os.path.join(ROOT_DIR, "data", "training", "training_v2_*")
+LIVE_FEATURE_COLUMNS = [
+    'num_hops', 'total_fees_bps', 'max_price_impact_bps', 'route_liquidity', 'profit_ratio',
+    'max_volatility', 'youngest_pool_age', 'min_depth', 'mean_depth', 'hour_sin', 'hour_cos', 'recent_win_rate',
+]
 
 print("=" * 60)
 print("Solana MEV Bot - AI Model Training (5-Hop Engine)")
 print("=" * 60)
 
 # 1. Load Arbitrage Data
-if not os.path.exists(ARBITRAGE_DATA_PATH):
+live_files = sorted(glob.glob(TRAINING_EXPORT_GLOB))
+if live_files:
+    # Live export: features are already computed, the label is the realized outcome
+    frames = [pd.read_parquet(f) if f.endswith('.parquet') else pd.read_csv(f) for f in live_files]
+    data = pd.concat(frames, ignore_index=True)
+    data = data[data['decision'] == 'executed']
+    print(f"✅ Loaded {len(data)} dispatched trades from {len(live_files)} training export file(s)")
+    data['is_profitable'] = ((data['landed'].astype(str).str.lower() == 'true') & (data['realized_pnl'].fillna(0) > 0)).astype(int)
+elif not os.path.exists(ARBITRAGE_DATA_PATH):
     print(f"Error: {ARBITRAGE_DATA_PATH} not found.")
     print("Please run the bot in DRY_RUN mode first to collect data:")
     print("  bash scripts/collect_live_data.sh")
@@ -49,22 +64,22 @@ else:
 
 # 2. Feature Engineering
 print("\n📊 Feature Engineering...")
+if not live_files:
+    # Profit ratio: expected profit relative to input
+    data['profit_ratio'] = data['profit_lamports'] / data['input_amount']
 
-# Profit ratio: expected profit relative to input
-data['profit_ratio'] = data['profit_lamports'] / data['input_amount']
-
-# Route liquidity normalized (minimum liquidity across path)
-data['route_liquidity'] = np.log1p(data['min_liquidity'])  # Log scale for better distribution
+    # Route liquidity normalized (minimum liquidity across path)
+    data['route_liquidity'] = np.log1p(data['min_liquidity'])  # Log scale for better distribution
 
-# Target: Binary classification (1 if profitable above threshold, 0 otherwise)
-PROFIT_THRESHOLD = 500_000  # 0.0005 SOL minimum profit
-data['is_profitable'] = (data['profit_lamports'] > PROFIT_THRESHOLD).astype(int)
+    # Target: Binary classification (1 if profitable above threshold, 0 otherwise)
+    PROFIT_THRESHOLD = 500_000  # 0.0005 SOL minimum profit
+    data['is_profitable'] = (data['profit_lamports'] > PROFIT_THRESHOLD).astype(int)
 
 print(f"   - Profitable opportunities: {data['is_profitable'].sum()} / {len(data)}")
 print(f"   - Profitability rate: {data['is_profitable'].mean():.2%}")
 
-# Select features for model (5-hop aware)
-feature_columns = [
+# Select features for model (5-hop aware); the engine picks the schema from the input width
+feature_columns = LIVE_FEATURE_COLUMNS if live_files else [
     'num_hops',
     'total_fees_bps',
     'max_price_impact_bps',
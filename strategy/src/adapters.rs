@@ -1,50 +1,200 @@
 // ONNX Model Adapter - Infrastructure layer implementation of AIModelPort
 
+pub mod features;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use anyhow::Result;
 use mev_core::ArbitrageOpportunity;
-use ort::{session::Session, value::Value, inputs};
+use ort::{session::Session, value::{Value, ValueType}, inputs};
 use ndarray::Array1;
+use parking_lot::{Mutex, RwLock};
 use crate::ports::AIModelPort;
+use self::features::{FeatureContext, FeatureSchema, FeatureVector};
+
+/// A loaded session and the feature schema it takes
+struct LoadedModel {
+    session: Session,
+    schema: FeatureSchema,
+}
+
+impl LoadedModel {
+    /// The feature schema is picked from the width of the model's input
+    fn load(path: &Path) -> Result<Self> {
+        let session = Session::builder()?.commit_from_file(path)?;
+        let width = session.inputs.first()
+            .and_then(|input| match &input.input_type {
+                ValueType::Tensor { dimensions, .. } => dimensions.last().copied(),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow::anyhow!("Model has no tensor input"))?;
+        let schema = usize::try_from(width).ok()
+            .and_then(FeatureSchema::for_width)
+            .ok_or_else(|| anyhow::anyhow!(
+                "Model takes {} features; known schemas: {}",
+                width,
+                FeatureSchema::ALL.iter().map(|s| format!("v{} = {}", s.version, s.width())).collect::<Vec<_>>().join(", "),
+            ))?;
+        Ok(Self { session, schema })
+    }
+}
+
+/// Modification times of the model file: the one loaded, and the last one that failed to load
+#[derive(Default)]
+struct FileStamps {
+    loaded: Option<SystemTime>,
+    failed: Option<SystemTime>,
+}
 
 /// ONNX-based AI model adapter
 pub struct ONNXModelAdapter {
-    session: Session,
+    path: PathBuf,
+    model: RwLock<Arc<LoadedModel>>,
+    stamps: Mutex<FileStamps>,
 }
 
 impl ONNXModelAdapter {
     /// Create a new ONNX model adapter from a file path
     pub fn from_file(path: &str) -> Result<Self> {
-        let session = Session::builder()?.commit_from_file(path)?;
-        Ok(Self { session })
+        let path = PathBuf::from(path);
+        let loaded = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        let model = LoadedModel::load(&path)?;
+        Ok(Self {
+            path,
+            model: RwLock::new(Arc::new(model)),
+            stamps: Mutex::new(FileStamps { loaded, failed: None }),
+        })
+    }
+
+    /// Feature schema the loaded model was trained on
+    pub fn schema(&self) -> FeatureSchema {
+        self.model.read().schema
+    }
+
+    /// Swaps in the model file when it changed since the last load. Predictions already
+    /// running finish on the old session. A file that fails to load (e.g. still being
+    /// written) keeps the current model and is retried once it changes again.
+    pub fn reload_if_changed(&self) -> Result<bool> {
+        let modified = std::fs::metadata(&self.path)?.modified()?;
+        let mut stamps = self.stamps.lock();
+        if stamps.loaded == Some(modified) || stamps.failed == Some(modified) {
+            return Ok(false);
+        }
+        match LoadedModel::load(&self.path) {
+            Ok(model) => {
+                *self.model.write() = Arc::new(model);
+                *stamps = FileStamps { loaded: Some(modified), failed: None };
+                Ok(true)
+            }
+            Err(e) => {
+                stamps.failed = Some(modified);
+                Err(e)
+            }
+        }
+    }
+
+    /// Polls the model file every `interval`, calling `on_reload` after each swap.
+    /// `label` names the model in logs and metrics (champion, challenger).
+    pub async fn watch<F: Fn(&Self) + Send + 'static>(self: Arc<Self>, label: &'static str, interval: Duration, on_reload: F) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            match self.reload_if_changed() {
+                Ok(true) => {
+                    mev_core::telemetry::MODEL_RELOADS.with_label_values(&[label, "ok"]).inc();
+                    tracing::info!("🧠 Reloaded {} model {} (feature schema v{})", label, self.path.display(), self.schema().version);
+                    on_reload(&self);
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    mev_core::telemetry::MODEL_RELOADS.with_label_values(&[label, "error"]).inc();
+                    tracing::warn!("⚠️ {} model {} changed but failed to load, keeping the current one: {}", label, self.path.display(), e);
+                }
+            }
+        }
     }
 }
 
 impl AIModelPort for ONNXModelAdapter {
     fn predict_confidence(&self, opp: &ArbitrageOpportunity) -> Result<f32> {
-        // Prepare input features matching train_model.py
-        let route_liquidity = (opp.min_liquidity as f64).ln_1p() as f32;
-        let profit_ratio = opp.expected_profit_lamports as f32 / opp.input_amount as f32;
-        
-        let input_data = Array1::from_vec(vec![
-            opp.steps.len() as f32,          // num_hops
-            opp.total_fees_bps as f32,
-            opp.max_price_impact_bps as f32,
-            route_liquidity,
-            profit_ratio,
-        ]);
+        // Without engine context the market features read as zero
+        let features = FeatureVector::extract(opp, &FeatureContext::default());
+        self.predict_with_features(opp, features.values())
+    }
+
+    fn predict_with_features(&self, _opp: &ArbitrageOpportunity, features: &[f32]) -> Result<f32> {
+        let model = Arc::clone(&self.model.read());
+        if features.len() < model.schema.width() {
+            return Err(anyhow::anyhow!("Model needs {} features (schema v{}), got {}", model.schema.width(), model.schema.version, features.len()));
+        }
+        let input_data = Array1::from_vec(features[..model.schema.width()].to_vec());
 
         let input_tensor = input_data.insert_axis(ndarray::Axis(0));
         let input_value = Value::from_array(input_tensor.into_dyn())?;
-        
-        let outputs = self.session.run(inputs!["input" => input_value]?)?;
-        
+
+        let outputs = model.session.run(inputs!["input" => input_value]?)?;
+
         // GradientBoostingClassifier output (probability)
         let output_tensor = outputs["variable"].try_extract_tensor::<f32>()?;
-        
+
         Ok(output_tensor[[0, 0]])
     }
 }
 
+/// Champion/challenger pair: the champion's confidence is the one acted on, the
+/// challenger scores the same features in shadow. Both predictions go to the
+/// `model_shadow_confidence` histogram, and opportunities only one of them would
+/// pass at the confidence threshold are counted, so a candidate model can be
+/// compared on live traffic before it replaces `ai_model.onnx`.
+pub struct ShadowModel {
+    champion: Arc<dyn AIModelPort>,
+    challenger: Arc<dyn AIModelPort>,
+    threshold: f32,
+}
+
+impl ShadowModel {
+    pub fn new(champion: Arc<dyn AIModelPort>, challenger: Arc<dyn AIModelPort>, threshold: f32) -> Self {
+        Self { champion, challenger, threshold }
+    }
+
+    fn compare(&self, champion: &Result<f32>, challenger: Result<f32>) {
+        let challenger = match challenger {
+            Ok(confidence) => confidence,
+            Err(e) => {
+                tracing::debug!("🥊 Challenger model failed: {}", e);
+                return;
+            }
+        };
+        mev_core::telemetry::MODEL_SHADOW_CONFIDENCE.with_label_values(&["challenger"]).observe(challenger as f64);
+        let Ok(champion) = champion else {
+            return;
+        };
+        mev_core::telemetry::MODEL_SHADOW_CONFIDENCE.with_label_values(&["champion"]).observe(*champion as f64);
+        match (*champion >= self.threshold, challenger >= self.threshold) {
+            (true, false) => mev_core::telemetry::MODEL_SHADOW_DISAGREEMENTS.with_label_values(&["champion_only"]).inc(),
+            (false, true) => mev_core::telemetry::MODEL_SHADOW_DISAGREEMENTS.with_label_values(&["challenger_only"]).inc(),
+            _ => return,
+        }
+        tracing::debug!("🥊 Models disagree at {:.2}: champion {:.2}, challenger {:.2}", self.threshold, champion, challenger);
+    }
+}
+
+impl AIModelPort for ShadowModel {
+    fn predict_confidence(&self, opp: &ArbitrageOpportunity) -> Result<f32> {
+        let champion = self.champion.predict_confidence(opp);
+        self.compare(&champion, self.challenger.predict_confidence(opp));
+        champion
+    }
+
+    fn predict_with_features(&self, opp: &ArbitrageOpportunity, features: &[f32]) -> Result<f32> {
+        let champion = self.champion.predict_with_features(opp, features);
+        self.compare(&champion, self.challenger.predict_with_features(opp, features));
+        champion
+    }
+}
+
 /// Mock AI model for testing - always returns high confidence
 pub struct MockAIModel {
     confidence: f32,
@@ -61,3 +211,26 @@ impl AIModelPort for MockAIModel {
         Ok(self.confidence)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shadow_acts_on_the_champion_and_counts_disagreements() {
+        let disagreements = |label: &str| mev_core::telemetry::MODEL_SHADOW_DISAGREEMENTS.with_label_values(&[label]).get();
+        let before = disagreements("champion_only");
+        let shadow = ShadowModel::new(Arc::new(MockAIModel::new(0.9)), Arc::new(MockAIModel::new(0.3)), 0.5);
+        let opp = ArbitrageOpportunity::default();
+
+        assert_eq!(shadow.predict_with_features(&opp, &[]).unwrap(), 0.9);
+        assert_eq!(shadow.predict_confidence(&opp).unwrap(), 0.9);
+        assert_eq!(disagreements("champion_only") - before, 2.0);
+
+        // Agreement isn't counted
+        let agreeing = ShadowModel::new(Arc::new(MockAIModel::new(0.9)), Arc::new(MockAIModel::new(0.7)), 0.5);
+        let before = disagreements("champion_only");
+        agreeing.predict_confidence(&opp).unwrap();
+        assert_eq!(disagreements("champion_only"), before);
+    }
+}
@@ -0,0 +1,210 @@
+/// Model Feature Schema
+///
+/// The confidence model is trained on a fixed, ordered list of features, and
+/// the runtime has to hand it exactly that list. Schemas are append-only: each
+/// version keeps every column of the one before it and adds new ones at the
+/// end, so one extracted vector serves a model trained on any version by
+/// taking its first `width()` values. The training exporter writes the same
+/// columns under the same names.
+use std::collections::HashMap;
+use mev_core::ArbitrageOpportunity;
+use parking_lot::RwLock;
+use solana_sdk::pubkey::Pubkey;
+
+const FEATURE_COUNT: usize = 12;
+
+/// Every feature, in vector order
+static FEATURE_NAMES: [&str; FEATURE_COUNT] = [
+    // v1: the route-only features `train_model.py` has always used
+    "num_hops",
+    "total_fees_bps",
+    "max_price_impact_bps",
+    "route_liquidity",
+    "profit_ratio",
+    // v2: market and bot context
+    "max_volatility",
+    "youngest_pool_age",
+    "min_depth",
+    "mean_depth",
+    "hour_sin",
+    "hour_cos",
+    "recent_win_rate",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeatureSchema {
+    pub version: u32,
+    width: usize,
+}
+
+impl FeatureSchema {
+    pub const V1: FeatureSchema = FeatureSchema { version: 1, width: 5 };
+    pub const V2: FeatureSchema = FeatureSchema { version: 2, width: FEATURE_COUNT };
+    pub const LATEST: FeatureSchema = Self::V2;
+    pub const ALL: [FeatureSchema; 2] = [Self::V1, Self::V2];
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Column names, in the order the model takes them
+    pub fn names(&self) -> &'static [&'static str] {
+        &FEATURE_NAMES[..self.width]
+    }
+
+    pub fn for_version(version: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.version == version)
+    }
+
+    /// The schema a model with `width` inputs was trained on
+    pub fn for_width(width: usize) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.width() == width)
+    }
+}
+
+/// What the extractor needs beyond the opportunity itself
+#[derive(Debug, Clone, Default)]
+pub struct FeatureContext {
+    pub unix_secs: u64,
+    /// Highest normalized price volatility among the route's pools
+    pub max_volatility: f64,
+    /// Seconds since the newest pool on the route was first seen; None if none was
+    pub youngest_pool_age_secs: Option<u64>,
+    /// Input-side reserve of each hop's pool (raw units); empty when the pools aren't in the graph
+    pub hop_depths: Vec<u64>,
+    /// Share of recent landed trades that made money
+    pub recent_win_rate: f64,
+}
+
+/// A feature vector at `FeatureSchema::LATEST`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureVector {
+    values: Vec<f32>,
+}
+
+impl FeatureVector {
+    pub fn extract(opp: &ArbitrageOpportunity, ctx: &FeatureContext) -> Self {
+        let ln = |v: f64| v.max(0.0).ln_1p() as f32;
+        let profit_ratio = if opp.input_amount > 0 {
+            opp.expected_profit_lamports as f32 / opp.input_amount as f32
+        } else {
+            0.0
+        };
+        let (min_depth, mean_depth) = match ctx.hop_depths.iter().min() {
+            Some(min) => (
+                ln(*min as f64),
+                ctx.hop_depths.iter().map(|d| ln(*d as f64)).sum::<f32>() / ctx.hop_depths.len() as f32,
+            ),
+            None => (0.0, 0.0),
+        };
+        // Hour of day on the unit circle, so 23:00 and 00:00 sit next to each other
+        let hour = (ctx.unix_secs % 86_400) as f64 / 3_600.0;
+        let angle = hour / 24.0 * std::f64::consts::TAU;
+
+        Self {
+            values: vec![
+                opp.steps.len() as f32,
+                opp.total_fees_bps as f32,
+                opp.max_price_impact_bps as f32,
+                ln(opp.min_liquidity as f64),
+                profit_ratio,
+                ctx.max_volatility as f32,
+                ctx.youngest_pool_age_secs.map(|age| ln(age as f64)).unwrap_or(0.0),
+                min_depth,
+                mean_depth,
+                angle.sin() as f32,
+                angle.cos() as f32,
+                ctx.recent_win_rate as f32,
+            ],
+        }
+    }
+
+    /// The leading values a `schema` model takes
+    pub fn for_schema(&self, schema: FeatureSchema) -> &[f32] {
+        &self.values[..schema.width().min(self.values.len())]
+    }
+
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+}
+
+/// When each pool was first seen by this process, for the pool-age feature
+#[derive(Debug, Default)]
+pub struct PoolAges {
+    first_seen: RwLock<HashMap<Pubkey, u64>>,
+}
+
+impl PoolAges {
+    pub fn observe(&self, pool: Pubkey, unix_secs: u64) {
+        if self.first_seen.read().contains_key(&pool) {
+            return;
+        }
+        self.first_seen.write().entry(pool).or_insert(unix_secs);
+    }
+
+    /// Age of the most recently seen of `pools`
+    pub fn youngest(&self, pools: impl IntoIterator<Item = Pubkey>, unix_secs: u64) -> Option<u64> {
+        let first_seen = self.first_seen.read();
+        pools.into_iter()
+            .filter_map(|pool| first_seen.get(&pool))
+            .map(|seen| unix_secs.saturating_sub(*seen))
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::SwapStep;
+
+    #[test]
+    fn test_schemas_are_append_only_and_extraction_fills_them() {
+        assert_eq!(FeatureSchema::V2.names()[..FeatureSchema::V1.width()], *FeatureSchema::V1.names());
+        assert_eq!(FeatureSchema::for_width(5), Some(FeatureSchema::V1));
+        assert_eq!(FeatureSchema::for_width(FeatureSchema::LATEST.width()), Some(FeatureSchema::LATEST));
+        assert_eq!(FeatureSchema::for_width(7), None);
+
+        let opp = ArbitrageOpportunity {
+            steps: (0..3).map(|_| SwapStep {
+                pool: Pubkey::new_unique(),
+                program_id: mev_core::constants::RAYDIUM_V4_PROGRAM,
+                input_mint: Pubkey::new_unique(),
+                output_mint: Pubkey::new_unique(),
+                expected_output: 0,
+            }).collect(),
+            expected_profit_lamports: 10_000,
+            input_amount: 1_000_000,
+            total_fees_bps: 75,
+            max_price_impact_bps: 20,
+            min_liquidity: 1_000,
+            ..Default::default()
+        };
+        let ctx = FeatureContext {
+            // 06:00 UTC
+            unix_secs: 6 * 3_600,
+            max_volatility: 0.02,
+            youngest_pool_age_secs: Some(0),
+            hop_depths: vec![100, 10_000],
+            recent_win_rate: 0.6,
+        };
+        let features = FeatureVector::extract(&opp, &ctx);
+        assert_eq!(features.values().len(), FeatureSchema::LATEST.width());
+        assert_eq!(features.for_schema(FeatureSchema::V1)[..3], [3.0, 75.0, 20.0]);
+        assert!((features.values()[4] - 0.01).abs() < 1e-6);
+        assert!((features.values()[7] - 101f32.ln()).abs() < 1e-5);
+        assert!((features.values()[9] - 1.0).abs() < 1e-6 && features.values()[10].abs() < 1e-6);
+
+        // A route over pools nobody has seen, with no graph state: context features fall back to zero
+        let bare = FeatureVector::extract(&opp, &FeatureContext::default());
+        assert_eq!(bare.values()[6..9], [0.0, 0.0, 0.0]);
+
+        let ages = PoolAges::default();
+        let (old, new) = (Pubkey::new_unique(), Pubkey::new_unique());
+        ages.observe(old, 100);
+        ages.observe(new, 900);
+        ages.observe(old, 950);
+        assert_eq!(ages.youngest([old, new], 1_000), Some(100));
+        assert_eq!(ages.youngest([Pubkey::new_unique()], 1_000), None);
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::VecDeque;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::ArbitrageOpportunity;
+use crate::analytics::rejections::RejectionReason;
+use crate::policy::GateCheck;
+
+const DEFAULT_CAPACITY: usize = 64;
+
+/// What happened to an opportunity once the gates were done with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// Bundle handed to the executor (or found with no executor attached)
+    Executed,
+    Rejected(RejectionReason),
+    /// Passed every gate but the executor returned an error
+    ExecutionFailed,
+}
+
+impl Decision {
+    pub fn label(&self) -> String {
+        match self {
+            Decision::Executed => "executed".to_string(),
+            Decision::Rejected(reason) => format!("rejected: {}", reason.as_str()),
+            Decision::ExecutionFailed => "execution_failed".to_string(),
+        }
+    }
+}
+
+/// What `process_event` did with one pool update
+#[derive(Debug, Clone)]
+pub enum EventOutcome {
+    /// The update closed no cycle worth quoting
+    NoOpportunity,
+    Rejected(RejectionReason),
+    /// Bundle handed to the executor (or found with no executor attached)
+    Executed(ArbitrageOpportunity),
+    /// Passed every gate but the executor returned an error
+    ExecutionFailed,
+}
+
+impl EventOutcome {
+    /// Metric label: the rejection reason for rejections, the outcome otherwise
+    pub fn label(&self) -> &'static str {
+        match self {
+            EventOutcome::NoOpportunity => "no_opportunity",
+            EventOutcome::Rejected(reason) => reason.as_str(),
+            EventOutcome::Executed(_) => "executed",
+            EventOutcome::ExecutionFailed => "execution_failed",
+        }
+    }
+}
+
+/// One hop as it was priced when the decision was taken
+#[derive(Debug, Clone, PartialEq)]
+pub struct HopDetail {
+    pub pool: Pubkey,
+    pub program_id: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee_bps: u16,
+    pub impact_bps: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct RouteDecision {
+    pub at: DateTime<Utc>,
+    pub opportunity: ArbitrageOpportunity,
+    pub hops: Vec<HopDetail>,
+    pub decision: Decision,
+    /// Every policy gate the route reached, in order
+    pub trace: Vec<GateCheck>,
+}
+
+/// Bounded feed of the latest decisions, newest last. Read by the TUI route inspector.
+pub struct DecisionFeed {
+    entries: Mutex<VecDeque<RouteDecision>>,
+    capacity: usize,
+}
+
+impl Default for DecisionFeed {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl DecisionFeed {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn record(&self, opportunity: &ArbitrageOpportunity, hops: Vec<HopDetail>, decision: Decision, trace: Vec<GateCheck>) {
+        let mut entries = self.entries.lock();
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(RouteDecision {
+            at: Utc::now(),
+            opportunity: opportunity.clone(),
+            hops,
+            decision,
+            trace,
+        });
+    }
+
+    pub fn recent(&self) -> Vec<RouteDecision> {
+        self.entries.lock().iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_keeps_latest_decisions() {
+        let feed = DecisionFeed::new(2);
+        let opp = ArbitrageOpportunity::default();
+
+        feed.record(&opp, Vec::new(), Decision::Rejected(RejectionReason::MinProfit), Vec::new());
+        feed.record(&opp, Vec::new(), Decision::Executed, Vec::new());
+        feed.record(&opp, Vec::new(), Decision::ExecutionFailed, Vec::new());
+
+        let recent = feed.recent();
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].decision, Decision::Executed);
+        assert_eq!(recent[1].decision.label(), "execution_failed");
+        assert_eq!(Decision::Rejected(RejectionReason::Safety).label(), "rejected: safety");
+        assert_eq!(EventOutcome::Rejected(RejectionReason::Safety).label(), "safety");
+        assert_eq!(EventOutcome::NoOpportunity.label(), "no_opportunity");
+    }
+}
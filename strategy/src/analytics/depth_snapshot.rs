@@ -0,0 +1,92 @@
+/// Pre-trade depth snapshots
+///
+/// Everything the quoter held for each pool of a route at the moment it was
+/// dispatched: the pool state from the graph plus the DLMM bins or CLOB book it
+/// walked. The engine archives these next to the recorder's market data so a
+/// losing trade can be re-quoted against exactly what the bot saw.
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::{ArbitrageOpportunity, PoolUpdate};
+use mev_core::clob::OrderBook;
+use mev_core::meteora::DlmmLiquidity;
+
+/// Quoting inputs for one pool. Bins and books are shared, not copied.
+#[derive(Debug, Clone)]
+pub struct PoolDepth {
+    pub pool: Pubkey,
+    /// None if the pool left the graph between quote and capture
+    pub state: Option<PoolUpdate>,
+    pub dlmm: Option<Arc<DlmmLiquidity>>,
+    pub book: Option<Arc<OrderBook>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DepthSnapshot {
+    /// Unix ms, taken just before the bundle was built
+    pub captured_ms: u64,
+    pub bundle_id: String,
+    pub opportunity: ArbitrageOpportunity,
+    pub pools: Vec<PoolDepth>,
+}
+
+/// Bounded hand-off to the archiver; the hot path never waits on disk
+pub struct DepthArchive {
+    sender: mpsc::Sender<DepthSnapshot>,
+}
+
+impl DepthArchive {
+    pub fn new(capacity: usize) -> (Self, mpsc::Receiver<DepthSnapshot>) {
+        let (sender, receiver) = mpsc::channel(capacity);
+        (Self { sender }, receiver)
+    }
+
+    /// Drops the snapshot if the archiver is backed up
+    pub fn archive(&self, snapshot: DepthSnapshot) {
+        if self.sender.try_send(snapshot).is_err() {
+            tracing::warn!("⚠️ Depth archive backed up; snapshot dropped");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArbitrageStrategy;
+    use mev_core::SwapStep;
+
+    #[test]
+    fn test_snapshot_covers_each_pool_once_and_archive_never_blocks() {
+        let strategy = ArbitrageStrategy::default();
+        let (pool, sol, usdc) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        strategy.apply_update(PoolUpdate {
+            pool_address: pool,
+            program_id: mev_core::constants::RAYDIUM_V4_PROGRAM,
+            mint_a: sol,
+            mint_b: usdc,
+            reserve_a: 1_000_000_000_000,
+            reserve_b: 100_000_000_000_000,
+            price_sqrt: None,
+            liquidity: None,
+            fee_bps: 25,
+            timestamp: 7,
+        });
+
+        let step = |input_mint, output_mint| SwapStep { pool, program_id: mev_core::constants::RAYDIUM_V4_PROGRAM, input_mint, output_mint, expected_output: 0 };
+        let opp = ArbitrageOpportunity {
+            steps: vec![step(sol, usdc), step(usdc, sol)].into(),
+            ..Default::default()
+        };
+        let pools = strategy.depth_snapshot(&opp);
+        assert_eq!(pools.len(), 1);
+        assert_eq!(pools[0].state.as_ref().map(|s| s.reserve_a), Some(1_000_000_000_000));
+        assert!(pools[0].dlmm.is_none() && pools[0].book.is_none());
+
+        let (archive, mut rx) = DepthArchive::new(1);
+        for _ in 0..3 {
+            archive.archive(DepthSnapshot { captured_ms: 0, bundle_id: "b".into(), opportunity: opp.clone(), pools: pools.clone() });
+        }
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+use mev_core::ArbitrageOpportunity;
+use solana_sdk::pubkey::Pubkey;
+use crate::analytics::route_guard::{route_key, RouteKey};
+
+/// The executor polls for confirmation for up to 60s; anything older has landed or dropped
+pub const DEFAULT_IN_FLIGHT_TTL: Duration = Duration::from_secs(60);
+
+struct Entry {
+    route: RouteKey,
+    /// Mints the route passes through after leaving its start mint
+    mints: Vec<Pubkey>,
+    /// Input in SOL lamports, at the rate the route's profit was valued at
+    notional: u64,
+    opened: Instant,
+}
+
+/// Capital committed to bundles that were dispatched but not yet confirmed.
+/// Entries close when the trade is reported landed (or reverted) and otherwise expire.
+pub struct InFlightBook {
+    ttl: Duration,
+    entries: Mutex<Vec<Entry>>,
+}
+
+impl Default for InFlightBook {
+    fn default() -> Self {
+        Self::new(DEFAULT_IN_FLIGHT_TTL)
+    }
+}
+
+impl InFlightBook {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, entries: Mutex::new(Vec::new()) }
+    }
+
+    pub fn open(&self, opp: &ArbitrageOpportunity) {
+        let notional = opp.start_units_in_sol(opp.input_amount as i64).max(0) as u64;
+        let mut entries = self.entries.lock();
+        self.prune(&mut entries);
+        entries.push(Entry { route: route_key(opp), mints: exposed_mints(opp), notional, opened: Instant::now() });
+    }
+
+    /// Closes the oldest open bundle on the same route
+    pub fn close(&self, opp: &ArbitrageOpportunity) {
+        let route = route_key(opp);
+        let mut entries = self.entries.lock();
+        if let Some(i) = entries.iter().position(|e| e.route == route) {
+            entries.remove(i);
+        }
+    }
+
+    /// (open bundles, total input notional in lamports)
+    pub fn summary(&self) -> (usize, u64) {
+        let mut entries = self.entries.lock();
+        self.prune(&mut entries);
+        (entries.len(), entries.iter().map(|e| e.notional).sum())
+    }
+
+    /// Input notional of open bundles routed through `mint`, in lamports
+    pub fn mint_notional(&self, mint: &Pubkey) -> u64 {
+        let mut entries = self.entries.lock();
+        self.prune(&mut entries);
+        entries.iter().filter(|e| e.mints.contains(mint)).map(|e| e.notional).sum()
+    }
+
+    fn prune(&self, entries: &mut Vec<Entry>) {
+        let ttl = self.ttl;
+        entries.retain(|e| e.opened.elapsed() < ttl);
+    }
+}
+
+/// Distinct mints a route holds between hops. The start mint is left out: the
+/// capital is denominated in it and comes back to it.
+pub fn exposed_mints(opp: &ArbitrageOpportunity) -> Vec<Pubkey> {
+    let start = opp.steps.first().map(|s| s.input_mint);
+    let mut mints: Vec<Pubkey> = Vec::with_capacity(opp.steps.len());
+    for step in &opp.steps {
+        if Some(step.output_mint) != start && !mints.contains(&step.output_mint) {
+            mints.push(step.output_mint);
+        }
+    }
+    mints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::SwapStep;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn opp(pool: Pubkey, input_amount: u64) -> ArbitrageOpportunity {
+        ArbitrageOpportunity {
+            steps: [SwapStep {
+                pool,
+                program_id: Pubkey::new_unique(),
+                input_mint: Pubkey::new_unique(),
+                output_mint: Pubkey::new_unique(),
+                expected_output: 0,
+            }].into_iter().collect(),
+            input_amount,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_open_close_and_expiry() {
+        let book = InFlightBook::new(Duration::from_millis(50));
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        book.open(&opp(a, 100));
+        book.open(&opp(a, 200));
+        book.open(&opp(b, 50));
+        assert_eq!(book.summary(), (3, 350));
+
+        // Landing closes the oldest bundle on that route
+        book.close(&opp(a, 0));
+        assert_eq!(book.summary(), (2, 250));
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(book.summary(), (0, 0));
+    }
+
+    #[test]
+    fn test_notional_of_a_non_sol_cycle_is_in_lamports() {
+        let book = InFlightBook::default();
+        // 1 USDC (6 decimals) in, 0.01 USDC profit worth 100_000 lamports: 10 lamports per raw unit
+        let mut usdc_cycle = opp(Pubkey::new_unique(), 1_000_000);
+        usdc_cycle.expected_profit_lamports = 10_000;
+        usdc_cycle.profit_sol_lamports = Some(100_000);
+        book.open(&usdc_cycle);
+        assert_eq!(book.summary(), (1, 10_000_000));
+        assert_eq!(book.mint_notional(&usdc_cycle.steps[0].output_mint), 10_000_000);
+
+        // A SOL cycle is already in lamports
+        book.open(&opp(Pubkey::new_unique(), 5_000));
+        assert_eq!(book.summary(), (2, 10_005_000));
+    }
+}
@@ -1,2 +1,10 @@
 pub mod performance;
 pub mod volatility;
+pub mod trade_flow;
+pub mod rejections;
+pub mod decisions;
+pub mod route_guard;
+pub mod in_flight;
+pub mod quote_errors;
+pub mod depth_snapshot;
+pub mod model_drift;
@@ -0,0 +1,184 @@
+/// AI Model Output Drift
+///
+/// A model that loads fine can still go quietly wrong: a feature whose scale
+/// changed upstream, or a market regime the training set never saw, pins
+/// `predict_confidence` near 0 (nothing clears the threshold) or near 1
+/// (everything does). The monitor keeps a rolling window of outputs and flags
+/// two failure shapes: a collapse (almost every output saturated at 0 or 1, or
+/// no spread at all) and a shift (the window mean moved too far from the
+/// training baseline, or from the session's first full window when no baseline
+/// is configured). With fallback on, a drifted model is bypassed for the
+/// heuristic gate until its outputs look healthy again.
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use parking_lot::Mutex;
+
+/// Outputs within this distance of 0 or 1 count as saturated
+const SATURATION_EPS: f32 = 0.02;
+
+#[derive(Debug, Clone)]
+pub struct DriftPolicy {
+    /// Outputs kept in the rolling window
+    pub window: usize,
+    /// Share of saturated outputs (0-1) that counts as a collapse
+    pub max_saturated_share: f32,
+    /// Standard deviation below which the outputs count as collapsed
+    pub min_stddev: f32,
+    /// Mean confidence on the training set; None learns it from the first full window
+    pub baseline_mean: Option<f32>,
+    /// Largest tolerated distance between the window mean and the baseline
+    pub max_mean_shift: f32,
+    /// Bypass the model for the heuristic gate while drifted
+    pub fallback: bool,
+}
+
+impl Default for DriftPolicy {
+    fn default() -> Self {
+        Self {
+            window: 500,
+            max_saturated_share: 0.95,
+            min_stddev: 0.01,
+            baseline_mean: None,
+            max_mean_shift: 0.25,
+            fallback: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriftState {
+    /// Not enough outputs yet, or nothing wrong
+    Healthy,
+    Collapsed { mean: f32, stddev: f32, saturated_share: f32 },
+    Shifted { mean: f32, baseline: f32 },
+}
+
+impl DriftState {
+    pub fn is_drifted(&self) -> bool {
+        !matches!(self, DriftState::Healthy)
+    }
+
+    /// Value of the `model_drift_state` gauge
+    pub fn code(&self) -> i64 {
+        match self {
+            DriftState::Healthy => 0,
+            DriftState::Collapsed { .. } => 1,
+            DriftState::Shifted { .. } => 2,
+        }
+    }
+}
+
+struct Window {
+    outputs: VecDeque<f32>,
+    baseline: Option<f32>,
+    state: DriftState,
+}
+
+pub struct ModelDriftMonitor {
+    policy: DriftPolicy,
+    window: Mutex<Window>,
+    bypassed: AtomicBool,
+}
+
+impl ModelDriftMonitor {
+    pub fn new(policy: DriftPolicy) -> Self {
+        let window = Window {
+            outputs: VecDeque::with_capacity(policy.window),
+            baseline: policy.baseline_mean,
+            state: DriftState::Healthy,
+        };
+        Self { policy, window: Mutex::new(window), bypassed: AtomicBool::new(false) }
+    }
+
+    /// Records one model output and re-evaluates the window once it is full
+    pub fn observe(&self, confidence: f32) {
+        let mut window = self.window.lock();
+        if window.outputs.len() == self.policy.window {
+            window.outputs.pop_front();
+        }
+        window.outputs.push_back(confidence);
+        if window.outputs.len() < self.policy.window {
+            return;
+        }
+
+        let n = window.outputs.len() as f32;
+        let mean = window.outputs.iter().sum::<f32>() / n;
+        let stddev = (window.outputs.iter().map(|c| (c - mean).powi(2)).sum::<f32>() / n).sqrt();
+        let saturated_share = window.outputs.iter()
+            .filter(|c| **c <= SATURATION_EPS || **c >= 1.0 - SATURATION_EPS)
+            .count() as f32 / n;
+        let baseline = *window.baseline.get_or_insert(mean);
+
+        window.state = if saturated_share >= self.policy.max_saturated_share || stddev < self.policy.min_stddev {
+            DriftState::Collapsed { mean, stddev, saturated_share }
+        } else if (mean - baseline).abs() > self.policy.max_mean_shift {
+            DriftState::Shifted { mean, baseline }
+        } else {
+            DriftState::Healthy
+        };
+        self.bypassed.store(self.policy.fallback && window.state.is_drifted(), Ordering::Relaxed);
+
+        mev_core::telemetry::MODEL_CONFIDENCE_MEAN.set(mean as f64);
+        mev_core::telemetry::MODEL_CONFIDENCE_STDDEV.set(stddev as f64);
+        mev_core::telemetry::MODEL_DRIFT_STATE.set(window.state.code());
+    }
+
+    /// Starts over after the model was replaced: the old outputs and learned baseline
+    /// describe a different model
+    pub fn reset(&self) {
+        let mut window = self.window.lock();
+        window.outputs.clear();
+        window.baseline = self.policy.baseline_mean;
+        window.state = DriftState::Healthy;
+        self.bypassed.store(false, Ordering::Relaxed);
+        mev_core::telemetry::MODEL_DRIFT_STATE.set(DriftState::Healthy.code());
+    }
+
+    pub fn state(&self) -> DriftState {
+        self.window.lock().state
+    }
+
+    /// True while the model should be ignored in favour of the heuristic gate
+    pub fn is_bypassed(&self) -> bool {
+        self.bypassed.load(Ordering::Relaxed)
+    }
+
+    pub fn policy(&self) -> &DriftPolicy {
+        &self.policy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(monitor: &ModelDriftMonitor, outputs: impl Iterator<Item = f32>) {
+        for c in outputs {
+            monitor.observe(c);
+        }
+    }
+
+    #[test]
+    fn test_flags_collapse_and_shift_then_recovers() {
+        let policy = DriftPolicy { window: 100, baseline_mean: Some(0.6), fallback: true, ..Default::default() };
+        let monitor = ModelDriftMonitor::new(policy);
+
+        // Spread around the baseline
+        feed(&monitor, (0..100).map(|i| 0.4 + (i % 40) as f32 * 0.01));
+        assert_eq!(monitor.state(), DriftState::Healthy);
+        assert!(!monitor.is_bypassed());
+
+        // Every output pinned at 1.0
+        feed(&monitor, (0..100).map(|_| 1.0));
+        assert!(matches!(monitor.state(), DriftState::Collapsed { saturated_share, .. } if saturated_share == 1.0));
+        assert!(monitor.is_bypassed());
+
+        // Still spread out, but centred far below the training mean
+        feed(&monitor, (0..100).map(|i| 0.1 + (i % 20) as f32 * 0.01));
+        assert!(matches!(monitor.state(), DriftState::Shifted { baseline, .. } if baseline == 0.6));
+
+        feed(&monitor, (0..100).map(|i| 0.45 + (i % 30) as f32 * 0.01));
+        assert_eq!(monitor.state(), DriftState::Healthy);
+        assert!(!monitor.is_bypassed());
+    }
+}
@@ -0,0 +1,88 @@
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+
+/// Running quote error for one venue. Errors are signed, in bps of the quoted output:
+/// positive means the hop paid more than our math said.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VenueQuoteError {
+    pub samples: u64,
+    pub mean_error_bps: f64,
+    pub mean_abs_error_bps: f64,
+    pub worst_error_bps: f64,
+}
+
+#[derive(Default)]
+struct Accumulator {
+    samples: u64,
+    sum_bps: f64,
+    sum_abs_bps: f64,
+    worst_bps: f64,
+}
+
+/// Compares what each hop of a landed route actually paid with what it was quoted,
+/// per venue program, to show which DEX math drifts from the chain.
+#[derive(Default)]
+pub struct QuoteErrorTracker {
+    venues: DashMap<Pubkey, Accumulator>,
+}
+
+impl QuoteErrorTracker {
+    /// Records one hop and returns its signed error in bps. Zero quotes carry no signal and are ignored.
+    pub fn record(&self, program_id: Pubkey, quoted: u64, actual: u64) -> Option<f64> {
+        if quoted == 0 {
+            return None;
+        }
+        let error_bps = (actual as f64 - quoted as f64) / quoted as f64 * 10_000.0;
+
+        let mut acc = self.venues.entry(program_id).or_default();
+        acc.samples += 1;
+        acc.sum_bps += error_bps;
+        acc.sum_abs_bps += error_bps.abs();
+        if error_bps.abs() > acc.worst_bps.abs() {
+            acc.worst_bps = error_bps;
+        }
+        Some(error_bps)
+    }
+
+    pub fn venue(&self, program_id: &Pubkey) -> Option<VenueQuoteError> {
+        self.venues.get(program_id).map(|acc| summarize(&acc))
+    }
+
+    /// All venues seen so far, worst mean absolute error first
+    pub fn snapshot(&self) -> Vec<(Pubkey, VenueQuoteError)> {
+        let mut venues: Vec<_> = self.venues.iter().map(|e| (*e.key(), summarize(e.value()))).collect();
+        venues.sort_by(|a, b| b.1.mean_abs_error_bps.total_cmp(&a.1.mean_abs_error_bps));
+        venues
+    }
+}
+
+fn summarize(acc: &Accumulator) -> VenueQuoteError {
+    let n = acc.samples.max(1) as f64;
+    VenueQuoteError {
+        samples: acc.samples,
+        mean_error_bps: acc.sum_bps / n,
+        mean_abs_error_bps: acc.sum_abs_bps / n,
+        worst_error_bps: acc.worst_bps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_errors_aggregate_per_venue() {
+        let tracker = QuoteErrorTracker::default();
+        let (raydium, orca) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert_eq!(tracker.record(raydium, 10_000, 10_010), Some(10.0));
+        assert_eq!(tracker.record(raydium, 10_000, 9_970), Some(-30.0));
+        assert_eq!(tracker.record(orca, 10_000, 10_000), Some(0.0));
+        assert_eq!(tracker.record(orca, 0, 5), None);
+
+        let ray = tracker.venue(&raydium).unwrap();
+        assert_eq!((ray.samples, ray.mean_error_bps, ray.mean_abs_error_bps, ray.worst_error_bps), (2, -10.0, 20.0, -30.0));
+        assert_eq!(tracker.venue(&orca).unwrap().samples, 1);
+        assert_eq!(tracker.snapshot()[0].0, raydium);
+    }
+}
@@ -0,0 +1,227 @@
+use tokio::sync::mpsc;
+use tokio::io::AsyncWriteExt;
+use tokio::fs::OpenOptions;
+use chrono::Utc;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use mev_core::ArbitrageOpportunity;
+
+const CSV_HEADER: &str = "timestamp,reason,hops,route,input_amount,expected_profit,observed,threshold,deficit\n";
+
+/// Gate that turned an opportunity down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RejectionReason {
+    ProfitSanity,
+    TipExceedsProfit,
+    MinProfit,
+    AiConfidence,
+    Dna,
+    Safety,
+    Simulation,
+    RouteBlacklisted,
+    SlotConflict,
+    Unfunded,
+    FeatureDisabled,
+    OracleDeviation,
+    /// Input above the hard per-trade limit; no route is searched
+    TradeSizeLimit,
+    PoolRateLimited,
+    /// Token-2022 transfer fees leave no profit over the tip
+    TransferFee,
+    /// A risk exposure limit (mint notional, pool trades, wallet share) would be breached
+    Exposure,
+    /// Expected value after land probability and failure cost below the margin
+    ExpectedValue,
+    /// The cycle starts from a mint with no fresh oracle price, so its profit can't be valued in SOL
+    Unpriced,
+    /// A hop's output is above what an independent quote source gives for it
+    QuoteMismatch,
+}
+
+impl RejectionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RejectionReason::ProfitSanity => "profit_sanity",
+            RejectionReason::TipExceedsProfit => "tip_exceeds_profit",
+            RejectionReason::MinProfit => "min_profit",
+            RejectionReason::AiConfidence => "ai_confidence",
+            RejectionReason::Dna => "dna",
+            RejectionReason::Safety => "safety",
+            RejectionReason::Simulation => "simulation",
+            RejectionReason::RouteBlacklisted => "route_blacklisted",
+            RejectionReason::SlotConflict => "slot_conflict",
+            RejectionReason::Unfunded => "unfunded",
+            RejectionReason::FeatureDisabled => "feature_disabled",
+            RejectionReason::OracleDeviation => "oracle_deviation",
+            RejectionReason::TradeSizeLimit => "trade_size_limit",
+            RejectionReason::PoolRateLimited => "pool_rate_limited",
+            RejectionReason::TransferFee => "transfer_fee",
+            RejectionReason::Exposure => "exposure",
+            RejectionReason::ExpectedValue => "expected_value",
+            RejectionReason::Unpriced => "unpriced",
+            RejectionReason::QuoteMismatch => "quote_mismatch",
+        }
+    }
+}
+
+/// A single near-miss: what the gate observed, what it required and how far off we were.
+#[derive(Debug, Clone)]
+pub struct RejectionRecord {
+    pub reason: RejectionReason,
+    pub route: String,
+    pub hops: usize,
+    pub input_amount: u64,
+    pub expected_profit: u64,
+    pub observed: f64,
+    pub threshold: f64,
+    /// Distance to passing the gate. Zero for pass/fail gates (DNA, safety, simulation)
+    pub deficit: f64,
+}
+
+impl RejectionRecord {
+    pub fn new(reason: RejectionReason, opp: &ArbitrageOpportunity, observed: f64, threshold: f64) -> Self {
+        Self {
+            reason,
+            route: route_signature(opp),
+            hops: opp.steps.len(),
+            input_amount: opp.input_amount,
+            expected_profit: opp.expected_profit_lamports,
+            observed,
+            threshold,
+            deficit: (threshold - observed).abs(),
+        }
+    }
+
+    fn to_csv_line(&self) -> String {
+        format!(
+            "{},{},{},\"{}\",{},{},{},{},{}\n",
+            Utc::now().to_rfc3339(),
+            self.reason.as_str(),
+            self.hops,
+            self.route,
+            self.input_amount,
+            self.expected_profit,
+            self.observed,
+            self.threshold,
+            self.deficit
+        )
+    }
+}
+
+/// Compact route description: pool prefixes joined by "->"
+pub fn route_signature(opp: &ArbitrageOpportunity) -> String {
+    opp.steps.iter()
+        .map(|s| {
+            let p = s.pool.to_string();
+            p[..6.min(p.len())].to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("->")
+}
+
+/// Sampled journal of rejected opportunities.
+/// Each reason gets its own per-second budget so a noisy gate can't drown out the others.
+pub struct RejectionJournal {
+    sender: mpsc::Sender<String>,
+    max_per_sec: u32,
+    windows: Mutex<HashMap<RejectionReason, (Instant, u32)>>,
+}
+
+impl RejectionJournal {
+    /// Spawns a background writer appending CSV rows to `file_path`
+    pub async fn new(file_path: &str, max_per_sec: u32) -> Self {
+        let (tx, mut rx) = mpsc::channel::<String>(256);
+        let path = file_path.to_string();
+
+        tokio::spawn(async move {
+            let is_new = !std::path::Path::new(&path).exists();
+            let mut file = match OpenOptions::new().create(true).append(true).open(&path).await {
+                Ok(f) => f,
+                Err(e) => {
+                    eprintln!("❌ Rejection journal unavailable ({}): {}", path, e);
+                    return;
+                }
+            };
+            if is_new {
+                let _ = file.write_all(CSV_HEADER.as_bytes()).await;
+            }
+
+            while let Some(line) = rx.recv().await {
+                if let Err(e) = file.write_all(line.as_bytes()).await {
+                    eprintln!("❌ Rejection journal error: {}", e);
+                }
+            }
+        });
+
+        Self::with_sender(tx, max_per_sec)
+    }
+
+    fn with_sender(sender: mpsc::Sender<String>, max_per_sec: u32) -> Self {
+        Self {
+            sender,
+            max_per_sec,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns true if the record fits into this second's budget for its reason
+    fn admit(&self, reason: RejectionReason) -> bool {
+        if self.max_per_sec == 0 {
+            return false;
+        }
+        let now = Instant::now();
+        let mut windows = self.windows.lock();
+        let (start, count) = windows.entry(reason).or_insert((now, 0));
+        if now.duration_since(*start) >= Duration::from_secs(1) {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= self.max_per_sec {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+
+    /// Non-blocking: drops the record if sampled out or if the writer is backed up
+    pub fn record(&self, record: RejectionRecord) {
+        if !self.admit(record.reason) {
+            return;
+        }
+        let _ = self.sender.try_send(record.to_csv_line());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_is_per_reason() {
+        let (tx, mut rx) = mpsc::channel(16);
+        let journal = RejectionJournal::with_sender(tx, 2);
+        let opp = ArbitrageOpportunity::default();
+
+        for _ in 0..5 {
+            journal.record(RejectionRecord::new(RejectionReason::MinProfit, &opp, 10_000.0, 30_000.0));
+        }
+        journal.record(RejectionRecord::new(RejectionReason::AiConfidence, &opp, 0.5, 0.7));
+
+        let mut lines = Vec::new();
+        while let Ok(line) = rx.try_recv() {
+            lines.push(line);
+        }
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines.iter().filter(|l| l.contains(",min_profit,")).count(), 2);
+        assert!(lines[0].ends_with(",10000,30000,20000\n"));
+    }
+
+    #[test]
+    fn test_zero_budget_disables_journal() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let journal = RejectionJournal::with_sender(tx, 0);
+        journal.record(RejectionRecord::new(RejectionReason::Dna, &ArbitrageOpportunity::default(), 0.0, 1.0));
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+use mev_core::ArbitrageOpportunity;
+
+/// Ordered pool sequence of a route. Two cycles through the same pools share a key
+/// regardless of size, so a broken builder combination is caught on every attempt.
+pub type RouteKey = Vec<Pubkey>;
+
+pub fn route_key(opp: &ArbitrageOpportunity) -> RouteKey {
+    opp.steps.iter().map(|s| s.pool).collect()
+}
+
+/// Tracks on-chain reverts per route and benches routes that keep failing.
+pub struct RouteBlacklist {
+    // Route -> (consecutive reverts, first revert in the current streak)
+    reverts: DashMap<RouteKey, (u32, Instant)>,
+    // Route -> blacklisted until
+    banned: DashMap<RouteKey, Instant>,
+    max_reverts: u32,
+    revert_window: Duration,
+    cooldown: Duration,
+    notifier: Option<UnboundedSender<String>>,
+}
+
+impl RouteBlacklist {
+    pub fn new(max_reverts: u32, revert_window: Duration, cooldown: Duration, notifier: Option<UnboundedSender<String>>) -> Self {
+        Self {
+            reverts: DashMap::new(),
+            banned: DashMap::new(),
+            max_reverts: max_reverts.max(1),
+            revert_window,
+            cooldown,
+            notifier,
+        }
+    }
+
+    /// True while the route is serving its cooldown. Expired bans are cleared lazily.
+    pub fn is_blacklisted(&self, opp: &ArbitrageOpportunity) -> bool {
+        let key = route_key(opp);
+        let expired = match self.banned.get(&key) {
+            Some(until) => Instant::now() >= *until,
+            None => return false,
+        };
+        if expired {
+            self.banned.remove(&key);
+            tracing::info!("✅ Route cooldown expired, re-enabling: {}", describe(&key));
+            return false;
+        }
+        true
+    }
+
+    /// Records an on-chain revert. Returns true if this revert tipped the route into the blacklist.
+    pub fn record_revert(&self, opp: &ArbitrageOpportunity) -> bool {
+        let key = route_key(opp);
+        let now = Instant::now();
+
+        let count = {
+            let mut entry = self.reverts.entry(key.clone()).or_insert((0, now));
+            if now.duration_since(entry.1) > self.revert_window {
+                *entry = (0, now);
+            }
+            entry.0 += 1;
+            entry.0
+        };
+
+        if count < self.max_reverts {
+            return false;
+        }
+
+        self.reverts.remove(&key);
+        self.banned.insert(key.clone(), now + self.cooldown);
+        mev_core::telemetry::ROUTES_BLACKLISTED_TOTAL.inc();
+
+        let msg = format!(
+            "Route {} reverted {} times on-chain. Blacklisted for {}s.",
+            describe(&key), count, self.cooldown.as_secs()
+        );
+        tracing::warn!("⛔ ROUTE BLACKLIST: {}", msg);
+        if let Some(tx) = &self.notifier {
+            let _ = tx.send(msg);
+        }
+        true
+    }
+
+    /// A landed trade clears the revert streak for its route
+    pub fn record_success(&self, opp: &ArbitrageOpportunity) {
+        self.reverts.remove(&route_key(opp));
+    }
+
+    pub fn banned_count(&self) -> usize {
+        self.banned.len()
+    }
+
+    /// Routes still serving their cooldown, with the time left
+    pub fn active_cooldowns(&self) -> Vec<(String, Duration)> {
+        let now = Instant::now();
+        self.banned.iter()
+            .filter(|entry| *entry.value() > now)
+            .map(|entry| (describe(entry.key()), *entry.value() - now))
+            .collect()
+    }
+}
+
+fn describe(key: &RouteKey) -> String {
+    key.iter()
+        .map(|p| {
+            let s = p.to_string();
+            s[..6.min(s.len())].to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("->")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::SwapStep;
+
+    fn opp_through(pools: &[Pubkey]) -> ArbitrageOpportunity {
+        let mut opp = ArbitrageOpportunity::default();
+        for pool in pools {
+            opp.steps.push(SwapStep {
+                pool: *pool,
+                program_id: mev_core::constants::RAYDIUM_V4_PROGRAM,
+                input_mint: Pubkey::new_unique(),
+                output_mint: Pubkey::new_unique(),
+                expected_output: 0,
+            });
+        }
+        opp
+    }
+
+    #[test]
+    fn test_blacklists_after_max_reverts() {
+        let guard = RouteBlacklist::new(3, Duration::from_secs(600), Duration::from_secs(60), None);
+        let pools = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let opp = opp_through(&pools);
+
+        assert!(!guard.record_revert(&opp));
+        assert!(!guard.record_revert(&opp));
+        assert!(!guard.is_blacklisted(&opp));
+        assert!(guard.record_revert(&opp));
+        assert!(guard.is_blacklisted(&opp));
+
+        // Different route is unaffected
+        assert!(!guard.is_blacklisted(&opp_through(&[pools[1], pools[0]])));
+    }
+
+    #[test]
+    fn test_success_resets_streak() {
+        let guard = RouteBlacklist::new(2, Duration::from_secs(600), Duration::from_secs(60), None);
+        let opp = opp_through(&[Pubkey::new_unique(), Pubkey::new_unique()]);
+
+        guard.record_revert(&opp);
+        guard.record_success(&opp);
+        assert!(!guard.record_revert(&opp));
+        assert!(!guard.is_blacklisted(&opp));
+    }
+
+    #[test]
+    fn test_cooldown_expires() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let guard = RouteBlacklist::new(1, Duration::from_secs(600), Duration::ZERO, Some(tx));
+        let opp = opp_through(&[Pubkey::new_unique(), Pubkey::new_unique()]);
+
+        assert!(guard.record_revert(&opp));
+        assert!(rx.try_recv().is_ok());
+        assert!(!guard.is_blacklisted(&opp));
+        assert_eq!(guard.banned_count(), 0);
+    }
+}
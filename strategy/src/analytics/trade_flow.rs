@@ -0,0 +1,144 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use solana_sdk::pubkey::Pubkey;
+use parking_lot::RwLock;
+use mev_core::raydium::RaySwapLog;
+use crate::analytics::volatility::VolatilityTracker;
+
+const FLOW_WINDOW: Duration = Duration::from_secs(60);
+const MAX_TRADES_PER_POOL: usize = 512;
+
+struct Trade {
+    at: Instant,
+    is_buy: bool,
+    quote_volume: u64,
+}
+
+/// Buy/sell volume for one pool over the trailing window, in quote units
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FlowSnapshot {
+    pub buy_volume: u64,
+    pub sell_volume: u64,
+    pub trades: usize,
+}
+
+impl FlowSnapshot {
+    /// Net pressure in [-1, 1]: +1 all buys, -1 all sells
+    pub fn pressure(&self) -> f64 {
+        let total = self.buy_volume as f64 + self.sell_volume as f64;
+        if total == 0.0 {
+            return 0.0;
+        }
+        (self.buy_volume as f64 - self.sell_volume as f64) / total
+    }
+}
+
+/// Per-pool trade flow built from executed swaps (decoded `ray_log` events).
+/// Every swap also feeds its settled price into the volatility tracker, so
+/// volatility reacts to real fills and not only to account updates.
+pub struct TradeFlowTracker {
+    flows: RwLock<HashMap<Pubkey, VecDeque<Trade>>>,
+    volatility: Arc<VolatilityTracker>,
+}
+
+impl TradeFlowTracker {
+    pub fn new(volatility: Arc<VolatilityTracker>) -> Self {
+        Self {
+            flows: RwLock::new(HashMap::new()),
+            volatility,
+        }
+    }
+
+    pub fn record(&self, pool: Pubkey, swap: &RaySwapLog) {
+        self.record_at(pool, swap, Instant::now());
+    }
+
+    fn record_at(&self, pool: Pubkey, swap: &RaySwapLog, at: Instant) {
+        {
+            let mut flows = self.flows.write();
+            let trades = flows.entry(pool).or_insert_with(|| VecDeque::with_capacity(MAX_TRADES_PER_POOL));
+            while trades.front().is_some_and(|t| at.duration_since(t.at) > FLOW_WINDOW) || trades.len() >= MAX_TRADES_PER_POOL {
+                trades.pop_front();
+            }
+            trades.push_back(Trade { at, is_buy: swap.is_buy(), quote_volume: swap.quote_volume() });
+        }
+
+        // Same quote/base convention as the account-update samples
+        let (coin, pc) = swap.reserves_after();
+        if coin > 0 {
+            self.volatility.add_sample(pool, pc as f64 / coin as f64);
+        }
+    }
+
+    pub fn snapshot(&self, pool: Pubkey) -> FlowSnapshot {
+        self.snapshot_at(pool, Instant::now())
+    }
+
+    fn snapshot_at(&self, pool: Pubkey, now: Instant) -> FlowSnapshot {
+        let flows = self.flows.read();
+        let mut snapshot = FlowSnapshot::default();
+        for trade in flows.get(&pool).into_iter().flatten() {
+            if now.duration_since(trade.at) > FLOW_WINDOW {
+                continue;
+            }
+            if trade.is_buy {
+                snapshot.buy_volume += trade.quote_volume;
+            } else {
+                snapshot.sell_volume += trade.quote_volume;
+            }
+            snapshot.trades += 1;
+        }
+        snapshot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::raydium::SwapDirection;
+
+    fn swap(direction: SwapDirection, amount_in: u64, amount_out: u64) -> RaySwapLog {
+        RaySwapLog { direction, amount_in, amount_out, pool_coin: 1_000_000, pool_pc: 2_000_000 }
+    }
+
+    #[test]
+    fn test_pressure_from_buys_and_sells() {
+        let tracker = TradeFlowTracker::new(Arc::new(VolatilityTracker::new()));
+        let pool = Pubkey::new_unique();
+
+        tracker.record(pool, &swap(SwapDirection::PcToCoin, 3_000, 1_400));
+        tracker.record(pool, &swap(SwapDirection::CoinToPc, 500, 1_000));
+
+        let flow = tracker.snapshot(pool);
+        assert_eq!(flow, FlowSnapshot { buy_volume: 3_000, sell_volume: 1_000, trades: 2 });
+        assert!((flow.pressure() - 0.5).abs() < 1e-9);
+        assert_eq!(tracker.snapshot(Pubkey::new_unique()).pressure(), 0.0);
+    }
+
+    #[test]
+    fn test_old_trades_leave_the_window() {
+        let tracker = TradeFlowTracker::new(Arc::new(VolatilityTracker::new()));
+        let pool = Pubkey::new_unique();
+        let start = Instant::now();
+
+        tracker.record_at(pool, &swap(SwapDirection::PcToCoin, 5_000, 2_400), start);
+        tracker.record_at(pool, &swap(SwapDirection::CoinToPc, 100, 200), start + Duration::from_secs(50));
+
+        let later = tracker.snapshot_at(pool, start + FLOW_WINDOW + Duration::from_secs(1));
+        assert_eq!((later.buy_volume, later.sell_volume, later.trades), (0, 200, 1));
+    }
+
+    #[test]
+    fn test_swaps_feed_volatility() {
+        let volatility = Arc::new(VolatilityTracker::new());
+        let tracker = TradeFlowTracker::new(Arc::clone(&volatility));
+        let pool = Pubkey::new_unique();
+
+        for i in 0..6 {
+            let direction = if i % 2 == 0 { SwapDirection::PcToCoin } else { SwapDirection::CoinToPc };
+            tracker.record(pool, &swap(direction, 400_000, 150_000));
+        }
+        assert!(volatility.get_volatility_factor(pool) > 0.0);
+    }
+}
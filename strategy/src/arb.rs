@@ -2,9 +2,116 @@
 /// 
 /// Performs Depth First Search (DFS) to find profitable cycles in the market graph.
 /// Focusing on 3-hop cycles (Triangular Arbitrage): A -> B -> C -> A
+///
+/// Also hosts the log-space negative-cycle search (SPFA / Bellman-Ford), which
+/// scales with edges instead of paths and is the better fit for large graphs.
+use std::collections::VecDeque;
 use solana_sdk::pubkey::Pubkey;
 use crate::graph::{MarketGraph, Edge};
 
+// Cycles have to beat rounding noise in the log weights
+const NEGATIVE_CYCLE_EPSILON: f64 = 1e-12;
+
+/// How the strategy looks for cycles after a pool update
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CycleSearchMode {
+    /// Exhaustive bounded DFS; exact sizing at every hop, exponential in hop count
+    #[default]
+    Dfs,
+    /// Negative-cycle detection over `-ln(rate)` spot weights, then sized once
+    BellmanFord,
+}
+
+impl CycleSearchMode {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "dfs" => Ok(Self::Dfs),
+            "bellman_ford" | "bellman-ford" | "spfa" => Ok(Self::BellmanFord),
+            other => Err(format!("Unknown cycle search mode '{}': expected dfs or bellman_ford", other)),
+        }
+    }
+}
+
+/// Directed edge weighted by `-ln(rate)`, so a cycle with a rate product above 1 sums below 0
+#[derive(Debug, Clone, Copy)]
+pub struct RateEdge {
+    pub from: usize,
+    pub to: usize,
+    pub weight: f64,
+}
+
+impl RateEdge {
+    pub fn new(from: usize, to: usize, rate: f64) -> Option<Self> {
+        (rate.is_finite() && rate > 0.0).then(|| Self { from, to, weight: -rate.ln() })
+    }
+}
+
+/// SPFA from `source`. Returns the indices into `edges` of one negative cycle reachable
+/// from it, in traversal order, or None if every reachable cycle loses value.
+pub fn find_negative_cycle(node_count: usize, edges: &[RateEdge], source: usize) -> Option<Vec<usize>> {
+    if source >= node_count {
+        return None;
+    }
+
+    let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); node_count];
+    for (i, edge) in edges.iter().enumerate() {
+        if edge.from < node_count && edge.to < node_count {
+            outgoing[edge.from].push(i);
+        }
+    }
+
+    let mut dist = vec![f64::INFINITY; node_count];
+    let mut pred: Vec<Option<usize>> = vec![None; node_count];
+    let mut relaxations = vec![0usize; node_count];
+    let mut queued = vec![false; node_count];
+    let mut queue = VecDeque::from([source]);
+    dist[source] = 0.0;
+    queued[source] = true;
+
+    while let Some(u) = queue.pop_front() {
+        queued[u] = false;
+        for &i in &outgoing[u] {
+            let edge = &edges[i];
+            let candidate = dist[u] + edge.weight;
+            if candidate < dist[edge.to] - NEGATIVE_CYCLE_EPSILON {
+                dist[edge.to] = candidate;
+                pred[edge.to] = Some(i);
+                relaxations[edge.to] += 1;
+                // A shortest path never needs more than n-1 edges
+                if relaxations[edge.to] >= node_count {
+                    return extract_cycle(edge.to, &pred, edges, node_count);
+                }
+                if !queued[edge.to] {
+                    queued[edge.to] = true;
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn extract_cycle(from: usize, pred: &[Option<usize>], edges: &[RateEdge], node_count: usize) -> Option<Vec<usize>> {
+    // Walking back n steps is guaranteed to land on the cycle
+    let mut node = from;
+    for _ in 0..node_count {
+        node = edges[pred[node]?].from;
+    }
+
+    let mut cycle = Vec::new();
+    let mut current = node;
+    loop {
+        let i = pred[current]?;
+        cycle.push(i);
+        current = edges[i].from;
+        if current == node || cycle.len() > node_count {
+            break;
+        }
+    }
+    cycle.reverse();
+    Some(cycle)
+}
+
 #[derive(Debug, Clone)]
 pub struct SwapPath {
     pub hops: Vec<Edge>,
@@ -134,6 +241,38 @@ mod tests {
         assert!(p.expected_profit > 0);
     }
 
+    #[test]
+    fn test_negative_cycle_detection() {
+        // 0 -> 1 -> 2 -> 0 multiplies to 1.1; the 0 <-> 3 round trip loses value
+        let edges = vec![
+            RateEdge::new(0, 1, 2.0).unwrap(),
+            RateEdge::new(1, 2, 0.5).unwrap(),
+            RateEdge::new(2, 0, 1.1).unwrap(),
+            RateEdge::new(0, 3, 3.0).unwrap(),
+            RateEdge::new(3, 0, 0.3).unwrap(),
+        ];
+        let cycle = find_negative_cycle(4, &edges, 3).expect("should find the profitable loop");
+        let mut indices = cycle.clone();
+        indices.sort();
+        assert_eq!(indices, vec![0, 1, 2]);
+        for pair in cycle.windows(2) {
+            assert_eq!(edges[pair[0]].to, edges[pair[1]].from);
+        }
+
+        // Same graph without the premium on the last leg has no arbitrage
+        let mut fair = edges.clone();
+        fair[2] = RateEdge::new(2, 0, 0.99).unwrap();
+        assert!(find_negative_cycle(4, &fair, 0).is_none());
+        assert!(RateEdge::new(0, 1, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_cycle_search_mode_parse() {
+        assert_eq!(CycleSearchMode::parse("").unwrap(), CycleSearchMode::Dfs);
+        assert_eq!(CycleSearchMode::parse("Bellman_Ford").unwrap(), CycleSearchMode::BellmanFord);
+        assert!(CycleSearchMode::parse("astar").is_err());
+    }
+
     #[test]
     fn test_find_4_hop_cycle() {
         let mut graph = MarketGraph::new();
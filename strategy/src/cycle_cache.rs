@@ -0,0 +1,135 @@
+/// Slot-Synchronized Cycle Re-Pricing
+///
+/// Cycle search only runs through the pool an update touched, so a route that
+/// becomes profitable through several small moves on different pools, none of
+/// which was enough on its own, is never looked at again. The cache keeps the
+/// K strongest cycles the search has produced; once per slot they are all
+/// re-quoted against the latest graph in one pass, and the best one that
+/// clears goes through the normal gates.
+use std::collections::HashMap;
+use parking_lot::Mutex;
+use mev_core::{ArbitrageOpportunity, SwapStep};
+use smallvec::SmallVec;
+use crate::ArbitrageStrategy;
+use crate::analytics::route_guard::{route_key, RouteKey};
+
+struct CachedCycle {
+    steps: SmallVec<[SwapStep; 8]>,
+    /// Output minus input at the last quote; negative once the edge has closed
+    edge_lamports: i64,
+}
+
+pub struct CycleCache {
+    capacity: usize,
+    cycles: Mutex<HashMap<RouteKey, CachedCycle>>,
+}
+
+impl CycleCache {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, cycles: Mutex::new(HashMap::with_capacity(capacity + 1)) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cycles.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Keeps a cycle the search found; past capacity the one with the weakest edge goes
+    pub fn remember(&self, opp: &ArbitrageOpportunity) {
+        if self.capacity == 0 || opp.steps.is_empty() {
+            return;
+        }
+        let mut cycles = self.cycles.lock();
+        cycles.insert(route_key(opp), CachedCycle {
+            steps: opp.steps.clone(),
+            edge_lamports: opp.expected_profit_lamports as i64,
+        });
+        if cycles.len() > self.capacity {
+            if let Some(weakest) = cycles.iter().min_by_key(|(_, c)| c.edge_lamports).map(|(k, _)| k.clone()) {
+                cycles.remove(&weakest);
+            }
+        }
+    }
+
+    /// Re-quotes every cached cycle at `amount` against the current graph and returns the
+    /// most profitable one that still clears. Cycles with a pool gone from the graph are dropped.
+    pub fn reprice(&self, strategy: &ArbitrageStrategy, amount: u64) -> Option<ArbitrageOpportunity> {
+        let mut best: Option<ArbitrageOpportunity> = None;
+        self.cycles.lock().retain(|_, cycle| {
+            let Some(output) = strategy.route_output(&cycle.steps, amount) else {
+                return false;
+            };
+            cycle.edge_lamports = output as i64 - amount as i64;
+            if cycle.edge_lamports > 0 {
+                if let Some(opp) = strategy.requote(&cycle.steps, amount) {
+                    if best.as_ref().map_or(true, |b| opp.expected_profit_lamports > b.expected_profit_lamports) {
+                        best = Some(opp);
+                    }
+                }
+            }
+            true
+        });
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use mev_core::PoolUpdate;
+    use solana_sdk::pubkey::Pubkey;
+    use crate::analytics::volatility::VolatilityTracker;
+
+    fn pool(address: Pubkey, mint_a: Pubkey, mint_b: Pubkey, reserve_a: u128, reserve_b: u128) -> PoolUpdate {
+        PoolUpdate {
+            pool_address: address,
+            program_id: mev_core::constants::RAYDIUM_V4_PROGRAM,
+            mint_a,
+            mint_b,
+            reserve_a,
+            reserve_b,
+            price_sqrt: None,
+            liquidity: None,
+            fee_bps: 0,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_reprice_catches_edge_built_from_several_moves() {
+        let strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+        let (sol, usdc, usdt) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (p1, p2, p3) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let amount = 1_000_000_000;
+        const DEEP: u128 = 1_000_000_000_000_000;
+
+        strategy.apply_update(pool(p1, sol, usdc, DEEP, DEEP));
+        strategy.apply_update(pool(p2, usdc, usdt, DEEP, DEEP));
+        let opp = strategy.process_update(pool(p3, usdt, sol, DEEP, DEEP * 101 / 100), amount, 3).expect("1% edge");
+
+        let cache = CycleCache::new(4);
+        cache.remember(&opp);
+        assert_eq!(cache.len(), 1);
+
+        // Arbed back to parity: nothing to send, but the cycle stays cached
+        strategy.apply_update(pool(p3, usdt, sol, DEEP, DEEP));
+        assert!(cache.reprice(&strategy, amount).is_none());
+        assert_eq!(cache.len(), 1);
+
+        // Two small drifts on the other legs, neither of which ran a search, reopen it
+        strategy.apply_update(pool(p1, sol, usdc, DEEP, DEEP * 1_003 / 1_000));
+        strategy.apply_update(pool(p2, usdc, usdt, DEEP, DEEP * 1_003 / 1_000));
+        let repriced = cache.reprice(&strategy, amount).expect("combined edge");
+        assert_eq!(repriced.steps.len(), 3);
+        assert!(repriced.expected_profit_lamports > 5_000_000);
+
+        // A delisted pool takes its cycles with it
+        strategy.remove_pool(&p2);
+        assert!(cache.reprice(&strategy, amount).is_none());
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+/// Expected-Value Gate
+///
+/// Replaces the flat AI confidence cutoff with the trade's expected value:
+/// EV = p·profit − (1−p)·(tip + fees), where `profit` is what the route takes
+/// home after the tip and p is the model's probability scaled by the observed
+/// Jito land rate. A 0.7 on a fat route can be worth sending while a 0.9 on a
+/// route that barely covers its tip is not. Routes execute only when EV clears
+/// `EV_MARGIN_LAMPORTS`. Until enough bundles have resolved to trust the land
+/// rate, p is the model's probability alone.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvPolicy {
+    /// EV a route must exceed to execute (lamports, may be negative)
+    pub margin_lamports: i64,
+    /// Network fees lost when the trade does not land: base fee plus priority fee
+    pub failure_fee_lamports: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExpectedValue {
+    /// Probability the trade lands and pays
+    pub win_probability: f64,
+    pub ev_lamports: i64,
+}
+
+impl EvPolicy {
+    /// `confidence` is the model's probability (1.0 in heuristic mode), `land_rate` the share
+    /// of resolved bundles that landed, `net_profit` the profit after the tip.
+    pub fn evaluate(&self, confidence: f32, land_rate: Option<f64>, net_profit: u64, tip_lamports: u64) -> ExpectedValue {
+        let p = (confidence as f64 * land_rate.unwrap_or(1.0)).clamp(0.0, 1.0);
+        let loss = tip_lamports.saturating_add(self.failure_fee_lamports) as f64;
+        let ev = p * net_profit as f64 - (1.0 - p) * loss;
+        ExpectedValue { win_probability: p, ev_lamports: ev.round() as i64 }
+    }
+
+    pub fn admits(&self, ev: &ExpectedValue) -> bool {
+        ev.ev_lamports > self.margin_lamports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ev_weighs_profit_against_failure_cost() {
+        let policy = EvPolicy { margin_lamports: 0, failure_fee_lamports: 5_000 };
+
+        // p = 0.8 · 0.5 = 0.4: 0.4·100k − 0.6·15k = 31k
+        let ev = policy.evaluate(0.8, Some(0.5), 100_000, 10_000);
+        assert!((ev.win_probability - 0.4).abs() < 1e-6);
+        assert_eq!(ev.ev_lamports, 31_000);
+        assert!(policy.admits(&ev));
+
+        // A confident model on a thin route: 0.95·1k − 0.05·105k < 0
+        let thin = policy.evaluate(0.95, None, 1_000, 100_000);
+        assert!(thin.ev_lamports < 0);
+        assert!(!policy.admits(&thin));
+
+        // A modest confidence on a fat route clears a margin the flat cutoff would have refused
+        let fat = policy.evaluate(0.6, Some(0.9), 2_000_000, 50_000);
+        assert!(EvPolicy { margin_lamports: 500_000, ..policy }.admits(&fat));
+    }
+}
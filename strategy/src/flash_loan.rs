@@ -0,0 +1,128 @@
+/// Flash-Loan Funding
+///
+/// Sizing can push a route's input past what the wallet actually holds. Rather
+/// than dropping the route, its input is borrowed from a flash-loan provider and
+/// repaid in the same transaction; the loan fee comes out of expected profit so
+/// the usual profit gates see the real edge.
+use std::collections::HashMap;
+use std::sync::Arc;
+use parking_lot::RwLock;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::ArbitrageOpportunity;
+use crate::ports::FlashLoanProvider;
+
+/// Spendable wallet balances per mint, refreshed by the engine
+#[derive(Default)]
+pub struct Inventory {
+    balances: RwLock<HashMap<Pubkey, u64>>,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&self, mint: Pubkey, amount: u64) {
+        self.balances.write().insert(mint, amount);
+    }
+
+    pub fn available(&self, mint: &Pubkey) -> u64 {
+        self.balances.read().get(mint).copied().unwrap_or(0)
+    }
+}
+
+/// How an opportunity's input is paid for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Funding {
+    Inventory,
+    FlashLoan { fee: u64 },
+}
+
+pub struct FlashLoanPolicy {
+    provider: Arc<dyn FlashLoanProvider>,
+    inventory: Arc<Inventory>,
+}
+
+impl FlashLoanPolicy {
+    pub fn new(provider: Arc<dyn FlashLoanProvider>, inventory: Arc<Inventory>) -> Self {
+        Self { provider, inventory }
+    }
+
+    pub fn provider_name(&self) -> &'static str {
+        self.provider.name()
+    }
+
+    /// Funds from inventory when it covers the input, otherwise switches `opp` to a flash loan.
+    /// Returns None when neither can cover it.
+    pub fn fund(&self, opp: &mut ArbitrageOpportunity) -> Option<Funding> {
+        let mint = opp.steps.first()?.input_mint;
+        if opp.input_amount <= self.inventory.available(&mint) {
+            return Some(Funding::Inventory);
+        }
+        if !self.provider.supports(&mint) {
+            return None;
+        }
+        Some(Funding::FlashLoan { fee: self.borrow(opp) })
+    }
+
+    /// Marks the whole input as borrowed and charges the loan fee against profit
+    pub fn borrow(&self, opp: &mut ArbitrageOpportunity) -> u64 {
+        let mint = opp.steps.first().map(|s| s.input_mint).unwrap_or_default();
+        let fee = (opp.input_amount as u128 * self.provider.fee_bps(&mint) as u128).div_ceil(10_000) as u64;
+        opp.flash_loan_amount = opp.input_amount;
+        opp.expected_profit_lamports = opp.expected_profit_lamports.saturating_sub(fee);
+        fee
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::SwapStep;
+    use solana_sdk::instruction::Instruction;
+
+    struct SolOnly(Pubkey);
+
+    impl FlashLoanProvider for SolOnly {
+        fn name(&self) -> &'static str { "test" }
+        fn supports(&self, mint: &Pubkey) -> bool { *mint == self.0 }
+        fn fee_bps(&self, _: &Pubkey) -> u16 { 30 }
+        fn wrap(&self, _: &Pubkey, _: &Pubkey, _: u64, trade_ixs: Vec<Instruction>, _: usize) -> anyhow::Result<Vec<Instruction>> {
+            Ok(trade_ixs)
+        }
+    }
+
+    fn opp(mint: Pubkey, input_amount: u64) -> ArbitrageOpportunity {
+        ArbitrageOpportunity {
+            steps: [SwapStep {
+                pool: Pubkey::new_unique(),
+                program_id: Pubkey::new_unique(),
+                input_mint: mint,
+                output_mint: Pubkey::new_unique(),
+                expected_output: 0,
+            }].into_iter().collect(),
+            input_amount,
+            expected_profit_lamports: 5_000_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_borrows_only_beyond_inventory() {
+        let (sol, bonk) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let inventory = Arc::new(Inventory::new());
+        inventory.set(sol, 100_000_000);
+        let policy = FlashLoanPolicy::new(Arc::new(SolOnly(sol)), Arc::clone(&inventory));
+
+        let mut covered = opp(sol, 100_000_000);
+        assert_eq!(policy.fund(&mut covered), Some(Funding::Inventory));
+        assert_eq!(covered.flash_loan_amount, 0);
+
+        // 0.3% of 1 SOL comes out of the 5M profit
+        let mut large = opp(sol, 1_000_000_000);
+        assert_eq!(policy.fund(&mut large), Some(Funding::FlashLoan { fee: 3_000_000 }));
+        assert_eq!((large.flash_loan_amount, large.expected_profit_lamports), (1_000_000_000, 2_000_000));
+
+        assert_eq!(policy.fund(&mut opp(bonk, 1)), None);
+    }
+}
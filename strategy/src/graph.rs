@@ -81,7 +81,7 @@ impl MarketGraph {
 
     /// Calculates how much 'to_token' you get for 'amount_in'
     pub fn get_amount_out(&self, edge: &Edge, amount_in: u64) -> u64 {
-        if edge.program_id == mev_core::constants::ORCA_WHIRLPOOL_PROGRAM {
+        if mev_core::constants::is_clmm(&edge.program_id) {
             if let Some(price_sqrt) = edge.price_sqrt {
                 let liquidity = edge.liquidity.unwrap_or(0);
                 let a_to_b = edge.reserve_in > edge.reserve_out; // Heuristic for direction in graph
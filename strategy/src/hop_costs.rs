@@ -0,0 +1,94 @@
+/// Hop-Count Cost Model
+///
+/// Longer routes carry more fee, CU and revert risk, so they have to clear a
+/// higher bar. Each route length can set its own minimum gross profit and
+/// per-hop impact cap; lengths without an entry inherit the nearest shorter one.
+use std::collections::BTreeMap;
+use crate::params::MAX_PRICE_IMPACT_CEILING_BPS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HopCost {
+    pub min_profit_lamports: u64,
+    pub max_impact_bps: u16,
+}
+
+impl Default for HopCost {
+    fn default() -> Self {
+        // No length-specific cap: StrategyParams::max_price_impact_bps still applies
+        Self { min_profit_lamports: 0, max_impact_bps: MAX_PRICE_IMPACT_CEILING_BPS }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HopCostTable {
+    entries: BTreeMap<usize, HopCost>,
+}
+
+impl HopCostTable {
+    /// Parses `hops:min_profit_lamports:max_impact_bps` entries, e.g. `2:20000:100;3:35000:80;5:90000:50`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut entries = BTreeMap::new();
+        for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let parts: Vec<&str> = entry.split(':').map(str::trim).collect();
+            if parts.len() != 3 {
+                return Err(format!("Invalid hop cost '{}': expected <hops>:<min_profit>:<max_impact_bps>", entry));
+            }
+            let hops: usize = parts[0].parse().map_err(|_| format!("Invalid hop count in '{}'", entry))?;
+            let min_profit_lamports: u64 = parts[1].parse().map_err(|_| format!("Invalid min profit in '{}'", entry))?;
+            let max_impact_bps: u16 = parts[2].parse().map_err(|_| format!("Invalid impact bps in '{}'", entry))?;
+            if hops < 2 {
+                return Err(format!("Hop count must be >= 2 in '{}'", entry));
+            }
+            if max_impact_bps > MAX_PRICE_IMPACT_CEILING_BPS {
+                return Err(format!("Impact cap in '{}' exceeds the {} bps ceiling", entry, MAX_PRICE_IMPACT_CEILING_BPS));
+            }
+            entries.insert(hops, HopCost { min_profit_lamports, max_impact_bps });
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Thresholds for a route of `hops` legs
+    pub fn for_hops(&self, hops: usize) -> HopCost {
+        self.entries.range(..=hops).next_back().map(|(_, c)| *c).unwrap_or_default()
+    }
+
+    /// True if a closed cycle clears the bar for its length
+    pub fn admits(&self, hops: usize, gross_profit: u64, max_impact_bps: u16) -> bool {
+        let cost = self.for_hops(hops);
+        gross_profit >= cost.min_profit_lamports && max_impact_bps <= cost.max_impact_bps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longer_routes_inherit_nearest_shorter_entry() {
+        let table = HopCostTable::parse("2:20000:100; 4:60000:50").unwrap();
+        assert_eq!(table.for_hops(2).min_profit_lamports, 20_000);
+        assert_eq!(table.for_hops(3).min_profit_lamports, 20_000);
+        assert_eq!(table.for_hops(5), HopCost { min_profit_lamports: 60_000, max_impact_bps: 50 });
+    }
+
+    #[test]
+    fn test_admits_scales_with_hops() {
+        let table = HopCostTable::parse("2:20000:100;5:90000:40").unwrap();
+        assert!(table.admits(2, 25_000, 90));
+        assert!(!table.admits(5, 25_000, 30));
+        assert!(!table.admits(5, 100_000, 60));
+        assert!(HopCostTable::default().admits(5, 1, MAX_PRICE_IMPACT_CEILING_BPS));
+    }
+
+    #[test]
+    fn test_invalid_specs_rejected() {
+        assert!(HopCostTable::parse("1:100:50").is_err());
+        assert!(HopCostTable::parse("3:100").is_err());
+        assert!(HopCostTable::parse("3:100:5000").is_err());
+        assert!(HopCostTable::parse("").unwrap().is_empty());
+    }
+}
@@ -4,6 +4,21 @@ pub mod graph; // "The Brain" market graph
 pub mod arb;   // "The Finder" search engine
 pub mod analytics;
 pub mod safety;
+pub mod split;
+pub mod hop_costs;
+pub mod priority;
+pub mod slot_lock;
+pub mod sizing;
+pub mod position_sizing;
+pub mod flash_loan;
+pub mod flags;
+pub mod order_books;
+pub mod venue;
+pub mod pool_rate_limit;
+pub mod cycle_cache;
+pub mod params;
+pub mod ev_gate;
+pub mod policy;
 
 #[cfg(test)]
 mod hft_tests;
@@ -13,19 +28,50 @@ mod profit_sanity_tests;
 
 
 
-use mev_core::{PoolUpdate, ArbitrageOpportunity, SwapStep};
+use mev_core::{PoolUpdate, ArbitrageOpportunity, SwapStep, Trigger};
+use mev_core::meteora::DlmmLiquidity;
+use mev_core::clmm::{self, ClmmState, TickRange};
+use mev_core::clob::OrderBook;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use rayon::prelude::*;
 use tracing::{info, debug, error, warn};
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use solana_sdk::pubkey::Pubkey;
 use parking_lot::RwLock;  // Faster than std::sync::Mutex
 use smallvec::SmallVec;   // Stack-allocated vectors
 use crate::analytics::volatility::VolatilityTracker;
-use chrono::Timelike;
+use crate::analytics::trade_flow::TradeFlowTracker;
+use crate::analytics::rejections::{RejectionJournal, RejectionReason, RejectionRecord};
+use crate::analytics::decisions::{Decision, DecisionFeed, EventOutcome, HopDetail};
+use crate::analytics::route_guard::RouteBlacklist;
+use crate::analytics::in_flight::InFlightBook;
+use crate::analytics::depth_snapshot::{DepthArchive, DepthSnapshot, PoolDepth};
+use crate::analytics::model_drift::ModelDriftMonitor;
+use crate::split::SplitPolicy;
+use crate::hop_costs::HopCostTable;
+use crate::priority::ExecutionLane;
+use crate::slot_lock::{SlotClaim, SlotLockService};
+use crate::sizing::SizingPolicy;
+use crate::flash_loan::{FlashLoanPolicy, Funding};
+use crate::flags::{FeatureFlags, Flag};
+use crate::order_books::OrderBookCache;
+use crate::venue::Venue;
+use crate::safety::oracle_guard::OracleGuard;
+use crate::safety::liquidity_floor::LiquidityFloor;
+use crate::safety::quote_check::QuoteCrossCheck;
+use crate::pool_rate_limit::PoolRateLimiter;
+use crate::adapters::features::{FeatureContext, FeatureVector, PoolAges};
+use crate::cycle_cache::CycleCache;
+use crate::params::{StrategyParams, StrategyParamsHandle};
+use crate::ev_gate::EvPolicy;
+use crate::policy::{ExecutionPolicy, Gate, GateCheck, GateOutcome, PolicyStep, trace_summary};
+use crate::arb::{CycleSearchMode, RateEdge};
+use mev_core::clock::Clock;
 
-use crate::ports::{AIModelPort, ExecutionPort, BundleSimulator, TelemetryPort};
+use crate::ports::{AIModelPort, ExecutionPort, BundleSimulator, ExposurePort, PriceOraclePort, TelemetryPort, TradeJournalPort, TrainingEvaluation, TrainingExportPort};
 
 pub struct StrategyEngine {
     arb_strategy: ArbitrageStrategy,
@@ -35,8 +81,35 @@ pub struct StrategyEngine {
     performance_tracker: Option<Arc<crate::analytics::performance::PerformanceTracker>>,
     safety_checker: Option<Arc<crate::safety::token_validator::TokenSafetyChecker>>,
     volatility_tracker: Arc<VolatilityTracker>,
+    trade_flow: Arc<TradeFlowTracker>,
     telemetry: Option<Arc<dyn TelemetryPort>>,
     market_intelligence: Option<Arc<dyn crate::ports::MarketIntelligencePort>>,  // NEW
+    rejection_journal: Option<Arc<RejectionJournal>>,
+    decision_feed: Option<Arc<DecisionFeed>>,
+    route_blacklist: Option<Arc<RouteBlacklist>>,
+    split_policy: Option<SplitPolicy>,
+    execution_lane: Option<Arc<ExecutionLane>>,
+    slot_locks: Option<Arc<SlotLockService>>,
+    sizing: Option<SizingPolicy>,
+    flash_loans: Option<FlashLoanPolicy>,
+    in_flight: Option<Arc<InFlightBook>>,
+    oracle_guard: Option<Arc<OracleGuard>>,
+    price_oracle: Option<Arc<dyn PriceOraclePort>>,
+    quote_check: Option<Arc<QuoteCrossCheck>>,
+    policy: Arc<ExecutionPolicy>,
+    pool_rate_limit: Option<Arc<PoolRateLimiter>>,
+    exposure: Option<Arc<dyn ExposurePort>>,
+    depth_archive: Option<Arc<DepthArchive>>,
+    cycle_cache: Option<Arc<CycleCache>>,
+    model_drift: Option<Arc<ModelDriftMonitor>>,
+    trade_journal: Option<Arc<dyn TradeJournalPort>>,
+    training_export: Option<Arc<dyn TrainingExportPort>>,
+    ev_gate: Option<EvPolicy>,
+    paused: Option<Arc<std::sync::atomic::AtomicBool>>,
+    incremental_search: bool,
+    pool_ages: PoolAges,
+    feature_flags: FeatureFlags,
+    clock: Arc<dyn Clock>,
     pub total_simulated_pnl: Arc<std::sync::atomic::AtomicU64>,
 }
 
@@ -58,16 +131,393 @@ impl StrategyEngine {
             ai_model,
             performance_tracker,
             safety_checker,
+            trade_flow: Arc::new(TradeFlowTracker::new(Arc::clone(&volatility_tracker))),
             volatility_tracker,
             telemetry,
             market_intelligence,
+            rejection_journal: None,
+            decision_feed: None,
+            route_blacklist: None,
+            split_policy: None,
+            execution_lane: None,
+            slot_locks: None,
+            sizing: None,
+            flash_loans: None,
+            in_flight: None,
+            oracle_guard: None,
+            price_oracle: None,
+            quote_check: None,
+            policy: Arc::new(ExecutionPolicy::default()),
+            pool_rate_limit: None,
+            exposure: None,
+            depth_archive: None,
+            cycle_cache: None,
+            model_drift: None,
+            trade_journal: None,
+            training_export: None,
+            ev_gate: None,
+            paused: None,
+            incremental_search: false,
+            pool_ages: PoolAges::default(),
+            feature_flags: FeatureFlags::default(),
+            clock: mev_core::clock::system(),
             total_simulated_pnl: Arc::new(std::sync::atomic::AtomicU64::new(0)),
         }
     }
 
+    /// Attaches a sampled journal for near-miss analysis of rejected opportunities
+    pub fn with_rejection_journal(mut self, journal: Arc<RejectionJournal>) -> Self {
+        self.rejection_journal = Some(journal);
+        self
+    }
+
+    /// Keeps the latest decisions with per-hop detail for the TUI route inspector
+    pub fn with_decision_feed(mut self, feed: Arc<DecisionFeed>) -> Self {
+        self.decision_feed = Some(feed);
+        self
+    }
+
+    /// Skips routes that have been benched after repeated on-chain reverts
+    pub fn with_route_blacklist(mut self, blacklist: Arc<RouteBlacklist>) -> Self {
+        self.route_blacklist = Some(blacklist);
+        self
+    }
+
+    /// Allows cycles too deep for one bundle to be worked in sequential chunks
+    pub fn with_split_policy(mut self, policy: SplitPolicy) -> Self {
+        self.split_policy = Some(policy);
+        self
+    }
+
+    /// Scales minimum profit and impact caps with route length during search
+    pub fn with_hop_costs(mut self, table: HopCostTable) -> Self {
+        self.arb_strategy.set_hop_costs(table);
+        self
+    }
+
+    /// Bounds concurrent executions and reserves slots for top-percentile opportunities
+    pub fn with_execution_lane(mut self, lane: Arc<ExecutionLane>) -> Self {
+        self.execution_lane = Some(lane);
+        self
+    }
+
+    /// Chooses between exhaustive DFS and log-space negative-cycle search
+    pub fn with_cycle_search(mut self, mode: CycleSearchMode) -> Self {
+        self.arb_strategy.set_search_mode(mode);
+        self
+    }
+
+    /// Searches only cycles through each update's pool; the rest of the graph is left to `sweep_cycles`
+    pub fn with_incremental_search(mut self, enabled: bool) -> Self {
+        self.incremental_search = enabled;
+        self
+    }
+
+    /// Fans DFS searches of `min_hops` or more out over the first hop (0 = never) and returns
+    /// the best cycle found so far once `budget` runs out
+    pub fn with_parallel_search(mut self, min_hops: u8, budget: Option<Duration>) -> Self {
+        self.arb_strategy.set_parallel_search(min_hops, budget);
+        self
+    }
+
+    /// Lets a leg of a found cycle spread over up to `max_pools` pools of its pair (1 = off)
+    pub fn with_split_routing(mut self, max_pools: u8) -> Self {
+        self.arb_strategy.set_split_routing(max_pools);
+        self
+    }
+
+    /// Leaves pools whose SOL-equivalent depth is under the floor out of the search
+    pub fn with_liquidity_floor(mut self, floor: Arc<LiquidityFloor>) -> Self {
+        self.arb_strategy.set_liquidity_floor(floor);
+        self
+    }
+
+    /// Skips pools not updated within `secs` during search (0 = never)
+    pub fn with_edge_ttl(mut self, secs: u64) -> Self {
+        self.arb_strategy.set_edge_ttl(secs);
+        self
+    }
+
+    /// Lets only the highest-EV of several overlapping routes execute within a slot
+    pub fn with_slot_locks(mut self, locks: Arc<SlotLockService>) -> Self {
+        self.slot_locks = Some(locks);
+        self
+    }
+
+    /// Time source for opportunity timestamps and slot claims (a manual clock in replays)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.arb_strategy.set_clock(Arc::clone(&clock));
+        self.clock = clock;
+        self
+    }
+
+    /// Resizes each found route to the input that maximizes profit net of the tip
+    pub fn with_sizing(mut self, policy: SizingPolicy) -> Self {
+        self.sizing = Some(policy);
+        self
+    }
+
+    /// Borrows the input of routes larger than wallet inventory instead of dropping them
+    pub fn with_flash_loans(mut self, policy: FlashLoanPolicy) -> Self {
+        self.flash_loans = Some(policy);
+        self
+    }
+
+    /// Rolls sizing, flash loans and DLMM routing out to a share of routes only
+    pub fn with_feature_flags(mut self, flags: FeatureFlags) -> Self {
+        self.feature_flags = flags;
+        self
+    }
+
+    /// Refuses routes with a hop priced implausibly far above its oracle reference
+    pub fn with_oracle_guard(mut self, guard: Arc<OracleGuard>) -> Self {
+        self.oracle_guard = Some(guard);
+        self
+    }
+
+    /// Prices cycles that start from a mint other than SOL, so their profit is compared in lamports
+    pub fn with_price_oracle(mut self, oracle: Arc<dyn PriceOraclePort>) -> Self {
+        self.price_oracle = Some(oracle);
+        self
+    }
+
+    /// Re-quotes every hop with an independent source before the bundle is built
+    pub fn with_quote_check(mut self, check: Arc<QuoteCrossCheck>) -> Self {
+        self.quote_check = Some(check);
+        self
+    }
+
+    /// Which gates run between a found route and its bundle, in what order and with what parameters
+    pub fn with_execution_policy(mut self, policy: Arc<ExecutionPolicy>) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Caps how many routes per minute may go through any one pool
+    pub fn with_pool_rate_limit(mut self, limiter: Arc<PoolRateLimiter>) -> Self {
+        self.pool_rate_limit = Some(limiter);
+        self
+    }
+
+    /// Consults the risk layer's mint, pool and wallet-share limits before execution
+    pub fn with_exposure_guard(mut self, guard: Arc<dyn ExposurePort>) -> Self {
+        self.exposure = Some(guard);
+        self
+    }
+
+    /// Tracks notional of dispatched bundles until they land or expire
+    /// Archives the quoting state of every dispatched route's pools
+    pub fn with_depth_archive(mut self, archive: Arc<DepthArchive>) -> Self {
+        self.depth_archive = Some(archive);
+        self
+    }
+
+    pub fn with_in_flight(mut self, book: Arc<InFlightBook>) -> Self {
+        self.in_flight = Some(book);
+        self
+    }
+
+    /// Remembers the top cycles the search finds so `reprice_cycles` can re-quote them every slot
+    pub fn with_cycle_cache(mut self, cache: Arc<CycleCache>) -> Self {
+        self.cycle_cache = Some(cache);
+        self
+    }
+
+    /// Watches the AI model's outputs for drift, bypassing it while drifted if the policy says so
+    pub fn with_model_drift(mut self, monitor: Arc<ModelDriftMonitor>) -> Self {
+        self.model_drift = Some(monitor);
+        self
+    }
+
+    /// Journals every route that reaches the gates: the rejecting gate, or the dispatch and its tip
+    pub fn with_trade_journal(mut self, journal: Arc<dyn TradeJournalPort>) -> Self {
+        self.trade_journal = Some(journal);
+        self
+    }
+
+    /// Exports every decided opportunity with its model features, for retraining
+    pub fn with_training_export(mut self, export: Arc<dyn TrainingExportPort>) -> Self {
+        self.training_export = Some(export);
+        self
+    }
+
+    /// Gates on expected value (model probability x land rate) instead of the flat confidence cutoff
+    pub fn with_ev_gate(mut self, policy: EvPolicy) -> Self {
+        self.ev_gate = Some(policy);
+        self
+    }
+
+    /// Trading pause (manual or drawdown halt), checked again before each later chunk of a split route
+    pub fn with_pause_flag(mut self, paused: Arc<std::sync::atomic::AtomicBool>) -> Self {
+        self.paused = Some(paused);
+        self
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.as_ref().is_some_and(|p| p.load(std::sync::atomic::Ordering::Relaxed))
+    }
+
+    /// Trade-size, profit and impact thresholds for this run, usually the mode preset
+    pub fn with_params(self, params: StrategyParams) -> Self {
+        if let Err(e) = self.arb_strategy.params.set(params) {
+            warn!("⚠️ Invalid strategy params ({}). Keeping the defaults.", e);
+        }
+        self
+    }
+
+    pub fn params(&self) -> StrategyParams {
+        self.arb_strategy.params.get()
+    }
+
+    /// Shared handle for retuning the thresholds at runtime (control API)
+    pub fn params_handle(&self) -> Arc<StrategyParamsHandle> {
+        Arc::clone(&self.arb_strategy.params)
+    }
+
+    /// Best spot rate from `from` to `to` over pools directly between them (raw units, after fees)
+    pub fn spot_price(&self, from: &Pubkey, to: &Pubkey) -> Option<f64> {
+        self.arb_strategy.spot_price(from, to)
+    }
+
+    /// Current graph state of a pool, if it is part of the graph
+    pub fn pool_state(&self, pool: &Pubkey) -> Option<PoolUpdate> {
+        self.arb_strategy.pool_state(pool)
+    }
+
+    /// Re-walks a known route against current pool state; None once it no longer pays
+    pub fn requote(&self, steps: &[SwapStep], amount: u64) -> Option<ArbitrageOpportunity> {
+        self.arb_strategy.requote(steps, amount)
+    }
+
+    /// What a fixed route pays out right now, whether or not it is still profitable
+    pub fn route_output(&self, steps: &[SwapStep], amount: u64) -> Option<u64> {
+        self.arb_strategy.route_output(steps, amount)
+    }
+
+    /// Best single hop selling `amount` of `from` into `to` right now (used to unwind leftovers)
+    pub fn best_hop(&self, from: &Pubkey, to: &Pubkey, amount: u64) -> Option<SwapStep> {
+        self.arb_strategy.best_hop(from, to, amount)
+    }
+
+    /// Overwrites a pool's graph state without running a search (used by the reserve audit)
+    pub fn correct_pool_state(&self, update: PoolUpdate) {
+        self.arb_strategy.apply_update(update);
+    }
+
+    /// Takes a closed or drained pool out of the search graph
+    pub fn remove_pool(&self, pool: &Pubkey) -> bool {
+        self.arb_strategy.remove_pool(pool)
+    }
+
+    /// Drops pools silent for `max_age_secs` and the mints they leave unconnected
+    pub fn prune_graph(&self, max_age_secs: u64) -> PruneStats {
+        self.arb_strategy.prune(max_age_secs)
+    }
+
+    /// Refreshes the bins a Meteora DLMM pair is quoted against
+    pub fn update_dlmm_liquidity(&self, pool: Pubkey, liquidity: mev_core::meteora::DlmmLiquidity) {
+        self.arb_strategy.update_dlmm_liquidity(pool, liquidity);
+    }
+
+    /// Refreshes the tick arrays an Orca Whirlpool is quoted against
+    pub fn update_whirlpool_ticks(&self, pool: Pubkey, ticks: TickRange) {
+        self.arb_strategy.update_whirlpool_ticks(pool, ticks);
+    }
+
+    /// Shared order-book cache; books decoded from a market account land here before its update
+    pub fn order_books(&self) -> Arc<OrderBookCache> {
+        self.arb_strategy.order_books()
+    }
+
+    /// Refreshes a CLOB market's book out of band, e.g. from its book side accounts
+    pub fn update_order_book(&self, market: Pubkey, book: OrderBook) {
+        self.arb_strategy.update_order_book(market, book);
+    }
+
+    /// Model input for `opp` at the latest feature schema, from current graph and bot state
+    pub fn model_features(&self, opp: &ArbitrageOpportunity) -> FeatureVector {
+        let unix_secs = self.clock.unix_secs();
+        let hop_depths = opp.steps.iter()
+            .filter_map(|step| {
+                let state = self.pool_state(&step.pool)?;
+                Some(if step.input_mint == state.mint_a { state.reserve_a } else { state.reserve_b })
+            })
+            .collect();
+        let ctx = FeatureContext {
+            unix_secs,
+            max_volatility: opp.steps.iter()
+                .map(|step| self.volatility_tracker.get_volatility_factor(step.pool))
+                .fold(0.0, f64::max),
+            youngest_pool_age_secs: self.pool_ages.youngest(opp.steps.iter().map(|s| s.pool), unix_secs),
+            hop_depths,
+            recent_win_rate: self.telemetry.as_ref().map(|t| t.recent_trade_stats().win_rate).unwrap_or(0.0),
+        };
+        FeatureVector::extract(opp, &ctx)
+    }
+
+    /// Shared trade-flow tracker; swaps recorded here also feed the volatility model
+    pub fn trade_flow(&self) -> Arc<TradeFlowTracker> {
+        Arc::clone(&self.trade_flow)
+    }
+
+    fn journal_rejection(&self, reason: RejectionReason, opp: &ArbitrageOpportunity, observed: f64, threshold: f64, trace: &[GateCheck]) {
+        if let Some(journal) = &self.rejection_journal {
+            journal.record(RejectionRecord::new(reason, opp, observed, threshold));
+        }
+        if let Some(journal) = &self.trade_journal {
+            journal.record_rejection(opp, reason.as_str());
+        }
+        // Simulation failures are exported by the caller, which has the error
+        if reason != RejectionReason::Simulation {
+            self.export_training(opp, reason.as_str(), None, 0, None);
+        }
+        self.record_decision(opp, Decision::Rejected(reason), trace);
+    }
+
+    fn export_training(
+        &self,
+        opp: &ArbitrageOpportunity,
+        decision: &'static str,
+        simulation: Option<Result<u64, String>>,
+        tip_lamports: u64,
+        bundle_id: Option<String>,
+    ) {
+        if let Some(export) = &self.training_export {
+            export.record_evaluation(TrainingEvaluation {
+                opportunity: opp.clone(),
+                features: self.model_features(opp).values().to_vec(),
+                decision,
+                simulation,
+                tip_lamports,
+                bundle_id,
+            });
+        }
+    }
+
+    /// Lamports per raw unit of the mint a cycle starts from; None if it has no fresh oracle price
+    fn sol_rate(&self, opp: &ArbitrageOpportunity) -> Option<f64> {
+        match opp.start_mint() {
+            Some(mint) if mint != mev_core::constants::SOL_MINT => self.price_oracle.as_ref()?.sol_per_atom(&mint, self.clock.unix_secs()),
+            _ => Some(1.0),
+        }
+    }
+
+    fn record_decision(&self, opp: &ArbitrageOpportunity, decision: Decision, trace: &[GateCheck]) {
+        if let Some(feed) = &self.decision_feed {
+            feed.record(opp, self.arb_strategy.explain_route(opp), decision, trace.to_vec());
+        }
+    }
+
+    /// Per-trade input limit; None when the policy leaves `size_cap` out or switches it off
+    fn trade_size_cap(&self, params: &StrategyParams) -> Option<u64> {
+        let step = self.policy.step(Gate::SizeCap).filter(|s| s.is_enabled())?;
+        Some(step.param("max_lamports").map_or(params.max_trade_size_lamports, |cap| (cap as u64).min(params.max_trade_size_lamports)))
+    }
+
     pub async fn process_event(
-        &self, 
+        self: &Arc<Self>,
         update: Arc<PoolUpdate>, 
+        trigger: Trigger,
         initial_amount: u64,
         jito_tip_lamports: u64,
         jito_tip_percentage: f64,
@@ -75,227 +525,1037 @@ impl StrategyEngine {
         max_slippage_bps: u16,
         volatility_sensitivity: f64,
         max_slippage_ceiling: u16,
-        min_profit_threshold: u64,
         ai_confidence_threshold: f32,
-        sanity_profit_factor: u64,
         max_hops: u8,
-    ) -> anyhow::Result<Option<ArbitrageOpportunity>> {
-        // ... (Safety gates etc) ...
-        // ... (Update Graph & Find Cycle) ...
-
+    ) -> anyhow::Result<EventOutcome> {
         // 🛡️ SAFETY GATES (Institutional Grade)
-        const MAX_TRADE_SIZE: u64 = 1_000_000_000; // 1.0 SOL (Panic Limit)
-        
-        // REPLACED CONST WITH ARGUMENT: const MIN_PROFIT_THRESHOLD...
-        
+        // One read per event: a runtime retune never applies halfway through a route
+        let params = self.params();
+
         // Check 1: Is the bet too big?
-        if initial_amount > MAX_TRADE_SIZE {
+        if self.trade_size_cap(&params).is_some_and(|cap| initial_amount > cap) {
             error!("⛔ SAFETY TRIGGER: Trade size {} exceeds limit!", initial_amount);
-            return Ok(None);
+            return Ok(EventOutcome::Rejected(RejectionReason::TradeSizeLimit));
         }
 
         // 1. Update Graph & Find Cycle
-        let mut opportunity = match self.arb_strategy.process_update((*update).clone(), initial_amount, max_hops) {
+        let (node_a, node_b) = self.arb_strategy.apply_update((*update).clone());
+        if self.ai_model.is_some() {
+            self.pool_ages.observe(update.pool_address, self.clock.unix_secs());
+        }
+        let mut split_chunks: u8 = 1;
+        let found = if self.incremental_search {
+            self.arb_strategy.find_best_cycle_through(update.pool_address, node_a, node_b, initial_amount, max_hops)
+        } else {
+            self.arb_strategy.find_best_cycle(node_a, node_b, initial_amount, max_hops)
+        };
+        let mut opportunity = match found {
             Some(opp) => opp,
-            None => return Ok(None),
+            None => {
+                // 1.1 Full size breaches impact limits: see if the route works in K smaller chunks
+                let plan = self.split_policy.as_ref()
+                    .and_then(|policy| policy.plan(&self.arb_strategy, node_a, node_b, initial_amount, max_hops));
+                match plan {
+                    Some((opp, chunks)) => {
+                        info!("✂️ SPLIT: {}-hop route viable in {} chunks of {} lamports.", opp.steps.len(), chunks, opp.input_amount);
+                        split_chunks = chunks;
+                        opp
+                    }
+                    None => return Ok(EventOutcome::NoOpportunity),
+                }
+            }
         };
+        if let Some(cache) = &self.cycle_cache {
+            cache.remember(&opportunity);
+        }
+        opportunity.trigger = trigger;
+
+        self.pursue(
+            opportunity,
+            split_chunks,
+            initial_amount,
+            jito_tip_lamports,
+            jito_tip_percentage,
+            max_jito_tip_lamports,
+            max_slippage_bps,
+            volatility_sensitivity,
+            max_slippage_ceiling,
+            ai_confidence_threshold,
+            &params,
+        ).await
+    }
+
+    /// Slot tick: re-prices the cached top cycles against the current graph in one pass and
+    /// runs the best one that still clears through the same gates as an update-driven find.
+    pub async fn reprice_cycles(
+        &self,
+        initial_amount: u64,
+        jito_tip_lamports: u64,
+        jito_tip_percentage: f64,
+        max_jito_tip_lamports: u64,
+        max_slippage_bps: u16,
+        volatility_sensitivity: f64,
+        max_slippage_ceiling: u16,
+        ai_confidence_threshold: f32,
+    ) -> anyhow::Result<EventOutcome> {
+        let Some(cache) = &self.cycle_cache else {
+            return Ok(EventOutcome::NoOpportunity);
+        };
+        let params = self.params();
+        if self.trade_size_cap(&params).is_some_and(|cap| initial_amount > cap) {
+            return Ok(EventOutcome::Rejected(RejectionReason::TradeSizeLimit));
+        }
+        let Some(mut opportunity) = cache.reprice(&self.arb_strategy, initial_amount) else {
+            return Ok(EventOutcome::NoOpportunity);
+        };
+        info!("🔁 SLOT REPRICE: {}-hop cached cycle reopened ({} lamports).", opportunity.steps.len(), opportunity.expected_profit_lamports);
+        mev_core::telemetry::CYCLE_REPRICE_HITS.inc();
+        opportunity.trigger = Trigger::Reprice;
+
+        self.pursue(
+            opportunity,
+            1,
+            initial_amount,
+            jito_tip_lamports,
+            jito_tip_percentage,
+            max_jito_tip_lamports,
+            max_slippage_bps,
+            volatility_sensitivity,
+            max_slippage_ceiling,
+            ai_confidence_threshold,
+            &params,
+        ).await
+    }
+
+    /// Background sweep: a full search from `start`, for the cycles incremental search never
+    /// looks at (a route whose pools all went quiet while a pool elsewhere moved its price)
+    pub async fn sweep_cycles(
+        &self,
+        start: &Pubkey,
+        initial_amount: u64,
+        jito_tip_lamports: u64,
+        jito_tip_percentage: f64,
+        max_jito_tip_lamports: u64,
+        max_slippage_bps: u16,
+        volatility_sensitivity: f64,
+        max_slippage_ceiling: u16,
+        ai_confidence_threshold: f32,
+        max_hops: u8,
+    ) -> anyhow::Result<EventOutcome> {
+        let params = self.params();
+        if self.trade_size_cap(&params).is_some_and(|cap| initial_amount > cap) {
+            return Ok(EventOutcome::Rejected(RejectionReason::TradeSizeLimit));
+        }
+        let Some(mut opportunity) = self.arb_strategy.sweep(start, initial_amount, max_hops) else {
+            return Ok(EventOutcome::NoOpportunity);
+        };
+        info!("🧭 SWEEP: {}-hop cycle found by the full search ({} lamports).", opportunity.steps.len(), opportunity.expected_profit_lamports);
+        mev_core::telemetry::CYCLE_SWEEP_HITS.inc();
+        if let Some(cache) = &self.cycle_cache {
+            cache.remember(&opportunity);
+        }
+        opportunity.trigger = Trigger::Sweep;
+
+        self.pursue(
+            opportunity,
+            1,
+            initial_amount,
+            jito_tip_lamports,
+            jito_tip_percentage,
+            max_jito_tip_lamports,
+            max_slippage_bps,
+            volatility_sensitivity,
+            max_slippage_ceiling,
+            ai_confidence_threshold,
+            &params,
+        ).await
+    }
+
+    /// Sizing, funding and SOL valuation for a found route, then the execution policy's gates in order, then dispatch
+    async fn pursue(
+        &self,
+        mut opportunity: ArbitrageOpportunity,
+        split_chunks: u8,
+        initial_amount: u64,
+        jito_tip_lamports: u64,
+        jito_tip_percentage: f64,
+        max_jito_tip_lamports: u64,
+        max_slippage_bps: u16,
+        volatility_sensitivity: f64,
+        max_slippage_ceiling: u16,
+        ai_confidence_threshold: f32,
+        params: &StrategyParams,
+    ) -> anyhow::Result<EventOutcome> {
+        let min_profit_threshold = self.policy.step(Gate::MinProfit)
+            .and_then(|step| step.param("lamports"))
+            .map_or(params.min_profit_threshold_lamports, |lamports| lamports as u64);
+
+        // 1.1.4 Feature flags: routes outside a flag's rollout keep the old behavior
+        let flags = &self.feature_flags;
+        let uses_dlmm = opportunity.steps.iter().any(|s| s.program_id == mev_core::constants::METEORA_PROGRAM_ID);
+        if uses_dlmm && !flags.evaluate(Flag::Dlmm, &opportunity) {
+            debug!("🚩 FEATURE FLAG: DLMM route outside rollout. Skipping.");
+            self.journal_rejection(RejectionReason::FeatureDisabled, &opportunity, 0.0, 0.0, &[]);
+            return Ok(EventOutcome::Rejected(RejectionReason::FeatureDisabled));
+        }
+        let sizing_on = self.sizing.is_some() && flags.evaluate(Flag::Sizing, &opportunity);
+        let flash_on = self.flash_loans.is_some() && flags.evaluate(Flag::FlashLoans, &opportunity);
+
+        // 1.1.4.1 Start mint price: a cycle from RAY or BONK earns RAY or BONK, while thresholds
+        //         and tips are lamports. Without an oracle price its profit can't be compared.
+        let Some(sol_rate) = self.sol_rate(&opportunity) else {
+            debug!("⛔ UNPRICED: Cycle starts from {:?} with no fresh oracle price. Skipping.", opportunity.start_mint());
+            self.journal_rejection(RejectionReason::Unpriced, &opportunity, 0.0, 0.0, &[]);
+            return Ok(EventOutcome::Rejected(RejectionReason::Unpriced));
+        };
+
+        // 1.1.5 Optimal sizing: the route was found at the default size, find the size that pays most
+        if let (Some(policy), 1, true) = (&self.sizing, split_chunks, sizing_on) {
+            // The tip is set on the SOL value and comes back out in the start mint's units
+            let tip = |profit: u64| {
+                let tip_lamports = compute_tip((profit as f64 * sol_rate) as u64, jito_tip_percentage, jito_tip_lamports, max_jito_tip_lamports);
+                (tip_lamports as f64 / sol_rate) as u64
+            };
+            if let Some(sized) = policy.optimize(&self.arb_strategy, &opportunity, tip) {
+                debug!("📐 SIZING: {} -> {} lamports in, profit {} -> {}.",
+                    opportunity.input_amount, sized.input_amount, opportunity.expected_profit_lamports, sized.expected_profit_lamports);
+                opportunity = sized;
+            }
+        }
+
+        // 1.1.6 Funding: routes sized past inventory borrow their input for the duration of the bundle
+        if let (Some(flash), true) = (&self.flash_loans, flash_on) {
+            match flash.fund(&mut opportunity) {
+                Some(Funding::Inventory) => {}
+                Some(Funding::FlashLoan { fee }) => {
+                    debug!("🏦 FLASH LOAN: Borrowing {} lamports via {} (fee {}).", opportunity.flash_loan_amount, flash.provider_name(), fee);
+                    mev_core::telemetry::FLASH_LOAN_OPPORTUNITIES.inc();
+                }
+                None => {
+                    debug!("⛔ FUNDING: Input {} exceeds inventory and no flash loan covers it.", opportunity.input_amount);
+                    self.journal_rejection(RejectionReason::Unfunded, &opportunity, 0.0, 0.0, &[]);
+                    return Ok(EventOutcome::Rejected(RejectionReason::Unfunded));
+                }
+            }
+        }
+
+        // 1.1.7 Profit in SOL, net of any flash loan fee
+        value_in_sol(&mut opportunity, sol_rate);
+
+        // 1.2 Feed the rolling profit distribution used for execution priority
+        if let Some(lane) = &self.execution_lane {
+            lane.observe(opportunity.profit_in_sol());
+        }
 
         // 2. Dynamic Tip Calculation
-        let profit = opportunity.expected_profit_lamports;
-        
-        // 2.1 Profit Sanity Check: Reject unrealistic profits
-        // ALLOW HIGH RETURNS for LiveMicro to test execution paths.
-        // Cap at sanity_profit_factor x input instead of 10%.
-        let max_reasonable_profit = initial_amount.saturating_mul(sanity_profit_factor); 
-        if profit > max_reasonable_profit {
-            warn!("⛔ SANITY CHECK: Profit {} lamports ({}%) exceeds reasonable threshold {}. Likely stale data or calculation error. Rejecting opportunity.",
-                profit, 
-                (profit * 100) / initial_amount,
-                max_reasonable_profit
-            );
-            
-            if let Some(ref tel) = self.telemetry {
-                tel.log_profit_sanity_rejection();
+        let profit = opportunity.profit_in_sol();
+        let tip_lamports = compute_tip(profit, jito_tip_percentage, jito_tip_lamports, max_jito_tip_lamports);
+
+        // 3. Execution policy: every listed gate in order, each one traced
+        let mut ctx = GateContext {
+            params,
+            initial_amount,
+            profit,
+            tip_lamports,
+            net_profit: profit.saturating_sub(tip_lamports),
+            min_profit_threshold,
+            ai_confidence_threshold,
+            max_slippage_bps,
+            volatility_sensitivity,
+            max_slippage_ceiling,
+            effective_slippage: max_slippage_bps,
+            slot_claim: None,
+            simulation: None,
+        };
+        let trace = match self.run_policy(&mut opportunity, &mut ctx).await? {
+            Ok(trace) => trace,
+            Err(reason) => return Ok(EventOutcome::Rejected(reason)),
+        };
+
+        // 4. Infrastructure interaction via Ports
+        let Some(executor) = &self.executor else {
+            self.record_decision(&opportunity, Decision::Executed, &trace);
+            return Ok(EventOutcome::Executed(opportunity));
+        };
+        let effective_slippage = ctx.effective_slippage;
+
+        // 4.1 Track stats
+        self.total_simulated_pnl.fetch_add(opportunity.profit_in_sol(), std::sync::atomic::Ordering::SeqCst);
+
+        // 4.5 Log to Performance Tracker (Non-blocking)
+        if let Some(tracker) = &self.performance_tracker {
+            let token_label = format!("{:?}", opportunity.steps.last().map(|s| s.output_mint));
+            tracker.log_trade(&token_label, opportunity.profit_in_sol() as i64, "Live").await;
+        }
+
+        // 5. Atomic Execution (held for the whole dispatch, including split chunks)
+        let lane_permit = match &self.execution_lane {
+            Some(lane) => Some(lane.acquire(opportunity.profit_in_sol()).await?),
+            None => None,
+        };
+        if let Some(claim) = &ctx.slot_claim {
+            if !claim.commit() {
+                info!("🔒 SLOT LOCK: Preempted by a higher-EV overlapping route. Standing down.");
+                mev_core::telemetry::OPPORTUNITIES_SLOT_CONFLICT.inc();
+                self.journal_rejection(RejectionReason::SlotConflict, &opportunity, ctx.net_profit as f64, 0.0, &trace);
+                return Ok(EventOutcome::Rejected(RejectionReason::SlotConflict));
             }
-            return Ok(None);
         }
-        
-        let mut tip_lamports = (profit as f64 * jito_tip_percentage) as u64;
-        
-        // Apply floor and ceiling
-        tip_lamports = tip_lamports.max(jito_tip_lamports); // Floor at base tip
-        tip_lamports = tip_lamports.min(max_jito_tip_lamports); // Ceiling at max tip
-        
-        // Final sanity check: Tip must be less than profit
-        if tip_lamports >= profit {
-            warn!("⛔ SAFETY: Calculated tip {} is >= profit {}. Aborting trade.", tip_lamports, profit);
-            return Ok(None);
+        // Captured before the send so our own fill can't leak into it
+        let depth = self.depth_archive.as_ref()
+            .map(|_| (self.clock.unix_ms(), self.arb_strategy.depth_snapshot(&opportunity)));
+        match executor.build_and_send_bundle(
+            opportunity.clone(), 
+            solana_sdk::hash::Hash::default(), 
+            tip_lamports,
+            effective_slippage
+        ).await {
+            Ok(bundle_id) => {
+                info!("🔥 BUNDLE DISPATCHED: {}", bundle_id);
+                self.record_dispatch(&opportunity, tip_lamports, &bundle_id);
+                self.export_training(&opportunity, "executed", ctx.simulation.clone(), tip_lamports, Some(bundle_id.clone()));
+                if let (Some(archive), Some((captured_ms, pools))) = (&self.depth_archive, depth) {
+                    archive.archive(DepthSnapshot { captured_ms, bundle_id, opportunity: opportunity.clone(), pools });
+                }
+                if split_chunks > 1 {
+                    // The chunks wait out the re-quote delay on their own task; the worker moves on to the next event
+                    let (engine, executor, first) = (Arc::clone(self), Arc::clone(executor), opportunity.clone());
+                    let gates = ChunkGates {
+                        params: *params,
+                        initial_amount,
+                        jito_tip_lamports,
+                        jito_tip_percentage,
+                        max_jito_tip_lamports,
+                        min_profit_threshold,
+                        ai_confidence_threshold,
+                        max_slippage_bps,
+                        volatility_sensitivity,
+                        max_slippage_ceiling,
+                    };
+                    tokio::spawn(async move {
+                        let _lane_permit = lane_permit;
+                        engine.execute_remaining_chunks(&executor, &first, split_chunks, &gates).await;
+                    });
+                }
+                self.record_decision(&opportunity, Decision::Executed, &trace);
+                Ok(EventOutcome::Executed(opportunity))
+            },
+            Err(e) => {
+                error!("💥 Execution panic: {}", e);
+                if let Some(journal) = &self.trade_journal {
+                    journal.record_send_failure(&opportunity, tip_lamports, &e.to_string());
+                }
+                self.export_training(&opportunity, "execution_failed", ctx.simulation, tip_lamports, None);
+                self.record_decision(&opportunity, Decision::ExecutionFailed, &trace);
+                Ok(EventOutcome::ExecutionFailed)
+            }
+        }
+    }
+
+    /// The execution policy's gates in order against the route, each one traced. The first rejection is
+    /// journaled and ends the run; a cleared route comes back with its trace.
+    async fn run_policy(&self, opportunity: &mut ArbitrageOpportunity, ctx: &mut GateContext<'_>) -> anyhow::Result<Result<Vec<GateCheck>, RejectionReason>> {
+        let mut trace = Vec::with_capacity(self.policy.steps().len());
+        for step in self.policy.steps() {
+            if !step.is_enabled() {
+                trace.push(GateCheck { gate: step.gate, outcome: GateOutcome::Off, observed: 0.0, threshold: 0.0, elapsed_us: 0 });
+                continue;
+            }
+            let started = std::time::Instant::now();
+            let (outcome, observed, threshold) = match self.run_gate(step, opportunity, ctx).await? {
+                Verdict::Pass(observed, threshold) => (GateOutcome::Passed, observed, threshold),
+                Verdict::Skip => (GateOutcome::Skipped, 0.0, 0.0),
+                Verdict::Reject(reason, observed, threshold) => (GateOutcome::Rejected(reason), observed, threshold),
+            };
+            trace.push(GateCheck { gate: step.gate, outcome, observed, threshold, elapsed_us: started.elapsed().as_micros() as u64 });
+            if let GateOutcome::Rejected(reason) = outcome {
+                debug!("🧾 POLICY: {}", trace_summary(&trace));
+                self.journal_rejection(reason, opportunity, observed, threshold, &trace);
+                return Ok(Err(reason));
+            }
         }
+        debug!("🧾 POLICY: {}", trace_summary(&trace));
+        Ok(Ok(trace))
+    }
 
-        // Check 2: Is the profit worth the gas? (After tip)
-        let net_profit = profit.saturating_sub(tip_lamports);
-        if net_profit < min_profit_threshold {
-            debug!("⛔ SAFETY TRIGGER: Net profit {} is too small.", net_profit);
-            return Ok(None);
+    /// A sent bundle counts against every limit that meters dispatches: journal, rollout, pool rate, exposure, in-flight book
+    fn record_dispatch(&self, opportunity: &ArbitrageOpportunity, tip_lamports: u64, bundle_id: &str) {
+        if let Some(journal) = &self.trade_journal {
+            journal.record_dispatch(opportunity, tip_lamports, bundle_id);
+        }
+        self.feature_flags.record_dispatch(opportunity);
+        if let Some(limiter) = &self.pool_rate_limit {
+            limiter.record(opportunity, self.clock.unix_ms());
+        }
+        if let Some(guard) = &self.exposure {
+            guard.record_dispatch(opportunity, self.clock.unix_ms());
         }
+        if let Some(book) = &self.in_flight {
+            book.open(opportunity);
+        }
+    }
 
-        info!("💡 Profitable path found: {} lamports expected (Tip: {}).", profit, tip_lamports);
-        println!("🚀 ARB_FOUND: {} hops, profit: {} lamports", opportunity.steps.len(), opportunity.expected_profit_lamports);
+    /// One policy gate against the route. A gate with nothing to check with (no oracle, model or simulator) skips.
+    async fn run_gate(&self, step: &PolicyStep, opportunity: &mut ArbitrageOpportunity, ctx: &mut GateContext<'_>) -> anyhow::Result<Verdict> {
+        Ok(match step.gate {
+            // Refused before the search; a route here came from an event under the cap
+            Gate::SizeCap => Verdict::Pass(ctx.initial_amount as f64, self.trade_size_cap(ctx.params).unwrap_or(0) as f64),
 
-            // 2. AI validation layer
-            let ai_confidence = if let Some(model) = &self.ai_model {
-                model.predict_confidence(&opportunity).unwrap_or(0.0)
-            } else {
-                1.0 // Heuristic mode: assumes perfect confidence
-            }; 
-            
-            if ai_confidence < ai_confidence_threshold {
-                 debug!("⚠️ Opportunity rejected by AI Model (Confidence: {:.2} < Threshold: {:.2})", ai_confidence, ai_confidence_threshold);
-                 return Ok(None);
+            // Route Blacklist: don't pay fees again on a route that keeps reverting
+            Gate::RouteBlacklist => match &self.route_blacklist {
+                Some(blacklist) if blacklist.is_blacklisted(opportunity) => {
+                    debug!("⛔ ROUTE BLACKLIST: Skipping {}-hop route on cooldown.", opportunity.steps.len());
+                    mev_core::telemetry::OPPORTUNITIES_ROUTE_BLACKLISTED.inc();
+                    Verdict::Reject(RejectionReason::RouteBlacklisted, 0.0, 0.0)
+                }
+                Some(_) => Verdict::Pass(0.0, 0.0),
+                None => Verdict::Skip,
+            },
+
+            // Pool rate limit: an oscillating pool shouldn't turn every swing into a bundle
+            Gate::PoolRateLimit => match &self.pool_rate_limit {
+                Some(limiter) => match limiter.saturated_pool(opportunity, self.clock.unix_ms()) {
+                    Some((pool, limit)) => {
+                        debug!("⏳ POOL RATE LIMIT: {} already in {} routes this minute. Skipping.", pool, limit);
+                        mev_core::telemetry::OPPORTUNITIES_POOL_RATE_LIMITED.inc();
+                        Verdict::Reject(RejectionReason::PoolRateLimited, limit as f64, limit as f64)
+                    }
+                    None => Verdict::Pass(0.0, 0.0),
+                },
+                None => Verdict::Skip,
+            },
+
+            // Exposure limits: no piling into one mint or pool, no betting the wallet on one route
+            Gate::Exposure => match &self.exposure {
+                Some(guard) => match guard.check_exposure(opportunity, self.clock.unix_ms()) {
+                    Some(limit) => {
+                        debug!("🧯 EXPOSURE LIMIT: {}-hop route would breach {}. Skipping.", opportunity.steps.len(), limit.label());
+                        mev_core::telemetry::OPPORTUNITIES_EXPOSURE_REJECTED.with_label_values(&[limit.label()]).inc();
+                        Verdict::Reject(RejectionReason::Exposure, opportunity.input_amount as f64, 0.0)
+                    }
+                    None => Verdict::Pass(opportunity.input_amount as f64, 0.0),
+                },
+                None => Verdict::Skip,
+            },
+
+            // Profit Sanity Check: Reject unrealistic profits (the cap is per mode, see StrategyParams::preset).
+            // It is a share of the input, so it is checked in the start mint's own units.
+            Gate::ProfitSanity => {
+                let quoted_profit = opportunity.expected_profit_lamports;
+                let max_reasonable_profit = match step.param("factor") {
+                    Some(factor) => (ctx.initial_amount as f64 * factor) as u64,
+                    None => ctx.params.max_reasonable_profit(ctx.initial_amount),
+                };
+                if quoted_profit > max_reasonable_profit {
+                    warn!("⛔ SANITY CHECK: Profit {} lamports ({}%) exceeds reasonable threshold {}. Likely stale data or calculation error. Rejecting opportunity.",
+                        quoted_profit,
+                        (quoted_profit * 100) / ctx.initial_amount,
+                        max_reasonable_profit
+                    );
+                    if let Some(ref tel) = self.telemetry {
+                        tel.log_profit_sanity_rejection();
+                    }
+                    Verdict::Reject(RejectionReason::ProfitSanity, quoted_profit as f64, max_reasonable_profit as f64)
+                } else {
+                    Verdict::Pass(quoted_profit as f64, max_reasonable_profit as f64)
+                }
+            }
+
+            // Oracle Sanity: a second opinion on the quotes behind the profit
+            Gate::Oracle => match &self.oracle_guard {
+                Some(guard) => match guard.check(opportunity, self.clock.unix_secs()) {
+                    Some(hop) => {
+                        warn!("⛔ ORACLE SANITY: Hop {} prices {:.0} bps above the oracle (band {:.0} bps). Likely stale data or calculation error. Rejecting opportunity.",
+                            hop.step_index + 1, hop.deviation_bps, hop.allowed_bps);
+                        mev_core::telemetry::OPPORTUNITIES_ORACLE_REJECTED.inc();
+                        Verdict::Reject(RejectionReason::OracleDeviation, hop.deviation_bps, hop.allowed_bps)
+                    }
+                    None => Verdict::Pass(0.0, 0.0),
+                },
+                None => Verdict::Skip,
+            },
+
+            // Tip must be less than profit
+            Gate::Tip => {
+                if ctx.tip_lamports >= ctx.profit {
+                    warn!("⛔ SAFETY: Calculated tip {} is >= profit {}. Aborting trade.", ctx.tip_lamports, ctx.profit);
+                    Verdict::Reject(RejectionReason::TipExceedsProfit, ctx.profit as f64, ctx.tip_lamports as f64)
+                } else {
+                    Verdict::Pass(ctx.profit as f64, ctx.tip_lamports as f64)
+                }
             }
 
-            // 2.3 DNA Matching (Success Library)
-            if let Some(intel) = &self.market_intelligence {
-                // Estimate Market Cap: (SOL Reserves / Token Reserves) * Total Supply
-                // For Pump.fun, Total Supply is 1B (10^9 tokens, 6 decimals = 10^15 raw)
-                let initial_market_cap = if opportunity.total_fees_bps == 0 { // Heuristic for Pump.fun or new tokens
-                    (opportunity.min_liquidity as f64 * 5.0) as u64 // Rough estimate: 20% liquidity
+            // Is the profit worth the gas? (After tip)
+            Gate::MinProfit => {
+                if ctx.net_profit < ctx.min_profit_threshold {
+                    debug!("⛔ SAFETY TRIGGER: Net profit {} is too small.", ctx.net_profit);
+                    Verdict::Reject(RejectionReason::MinProfit, ctx.net_profit as f64, ctx.min_profit_threshold as f64)
                 } else {
-                    0 // Placeholder for others
-                };
+                    info!("💡 Profitable path found: {} lamports expected (Tip: {}).", ctx.profit, ctx.tip_lamports);
+                    println!("🚀 ARB_FOUND: {} hops, profit: {} lamports", opportunity.steps.len(), ctx.profit);
+                    Verdict::Pass(ctx.net_profit as f64, ctx.min_profit_threshold as f64)
+                }
+            }
+
+            // Slot lock: claim the route's pools now so a better overlapping route
+            // found by another worker during the slower gates can take them from us
+            Gate::SlotLock => match &self.slot_locks {
+                Some(locks) => {
+                    let pools: Vec<Pubkey> = opportunity.steps.iter().map(|s| s.pool).collect();
+                    match locks.try_claim(self.clock.slot(), &pools, ctx.net_profit) {
+                        Some(claim) => {
+                            ctx.slot_claim = Some(claim);
+                            Verdict::Pass(ctx.net_profit as f64, 0.0)
+                        }
+                        None => {
+                            debug!("🔒 SLOT LOCK: Overlapping route with higher EV owns these pools this slot.");
+                            mev_core::telemetry::OPPORTUNITIES_SLOT_CONFLICT.inc();
+                            Verdict::Reject(RejectionReason::SlotConflict, ctx.net_profit as f64, 0.0)
+                        }
+                    }
+                }
+                None => Verdict::Skip,
+            },
 
-                let dna = mev_core::TokenDNA {
-                    initial_liquidity: (opportunity.min_liquidity as u64), 
-                    initial_market_cap, 
-                    launch_hour_utc: chrono::Utc::now().hour() as u8,
-                    has_twitter: false, 
-                    mint_renounced: true, 
-                    market_volatility: 0.0, 
+            // AI validation layer
+            Gate::Ai => {
+                let ai_confidence = if let Some(model) = &self.ai_model {
+                    let features = self.model_features(opportunity);
+                    let confidence = model.predict_with_features(opportunity, features.values()).unwrap_or(0.0);
+                    opportunity.ai_confidence = Some(confidence);
+                    match &self.model_drift {
+                        // Keep scoring a bypassed model so the monitor sees it recover
+                        Some(drift) => {
+                            drift.observe(confidence);
+                            if drift.is_bypassed() { 1.0 } else { confidence }
+                        }
+                        None => confidence,
+                    }
+                } else {
+                    1.0 // Heuristic mode: assumes perfect confidence
                 };
+                let min_confidence = step.param("min_confidence").map_or(ctx.ai_confidence_threshold, |c| c as f32);
 
-                let dna_match = intel.match_dna(&dna).await.unwrap_or_default();
-                if !dna_match.is_match {
-                    warn!("⛔ DNA GATE: Token does not match success patterns. Rejecting.");
-                    if let Some(ref tel) = self.telemetry {
-                        tel.log_dna_rejection();
+                if let Some(policy) = &self.ev_gate {
+                    let land_rate = self.telemetry.as_ref().and_then(|t| t.bundle_land_rate());
+                    let ev = policy.evaluate(ai_confidence, land_rate, ctx.net_profit, ctx.tip_lamports);
+                    mev_core::telemetry::OPPORTUNITY_EXPECTED_VALUE.observe(ev.ev_lamports as f64);
+                    opportunity.expected_value_lamports = Some(ev.ev_lamports);
+                    if !policy.admits(&ev) {
+                        debug!("⚠️ Opportunity rejected on EV: {} lamports (p={:.2}, margin {})", ev.ev_lamports, ev.win_probability, policy.margin_lamports);
+                        return Ok(Verdict::Reject(RejectionReason::ExpectedValue, ev.ev_lamports as f64, policy.margin_lamports as f64));
                     }
-                    return Ok(None);
+                    info!("🚀 AI Approved: EV {} lamports (confidence {:.2}).", ev.ev_lamports, ai_confidence);
+                    Verdict::Pass(ev.ev_lamports as f64, policy.margin_lamports as f64)
+                } else if ai_confidence < min_confidence {
+                    debug!("⚠️ Opportunity rejected by AI Model (Confidence: {:.2} < Threshold: {:.2})", ai_confidence, min_confidence);
+                    Verdict::Reject(RejectionReason::AiConfidence, ai_confidence as f64, min_confidence as f64)
+                } else {
+                    info!("🚀 AI Approved: High confidence ({:.2}). Triggering execution pipeline...", ai_confidence);
+                    Verdict::Pass(ai_confidence as f64, min_confidence as f64)
                 }
-                
-                info!("🧬 DNA Match (Score: {})! Opportunity aligns with historical success patterns.", dna_match.score);
-                if dna_match.is_elite {
-                    info!("🌟 ELITE DNA MATCH! This token is in the top tier of successful launches.");
-                    if let Some(ref tel) = self.telemetry {
-                        tel.log_elite_match();
+            }
+
+            // DNA Matching (Success Library)
+            Gate::Dna => match &self.market_intelligence {
+                Some(intel) => {
+                    // Estimate Market Cap: (SOL Reserves / Token Reserves) * Total Supply
+                    // For Pump.fun, Total Supply is 1B (10^9 tokens, 6 decimals = 10^15 raw)
+                    let initial_market_cap = if opportunity.total_fees_bps == 0 { // Heuristic for Pump.fun or new tokens
+                        (opportunity.min_liquidity as f64 * 5.0) as u64 // Rough estimate: 20% liquidity
+                    } else {
+                        0 // Placeholder for others
+                    };
+
+                    let dna = mev_core::TokenDNA {
+                        initial_liquidity: (opportunity.min_liquidity as u64),
+                        initial_market_cap,
+                        launch_hour_utc: ((self.clock.unix_secs() / 3600) % 24) as u8,
+                        has_twitter: false,
+                        mint_renounced: true,
+                        market_volatility: 0.0,
+                    };
+
+                    let dna_match = intel.match_dna(&dna).await.unwrap_or_default();
+                    opportunity.dna_score = Some(dna_match.score);
+                    if !dna_match.is_match {
+                        warn!("⛔ DNA GATE: Token does not match success patterns. Rejecting.");
+                        if let Some(ref tel) = self.telemetry {
+                            tel.log_dna_rejection();
+                        }
+                        return Ok(Verdict::Reject(RejectionReason::Dna, dna_match.score as f64, 0.0));
+                    }
+
+                    info!("🧬 DNA Match (Score: {})! Opportunity aligns with historical success patterns.", dna_match.score);
+                    if dna_match.is_elite {
+                        info!("🌟 ELITE DNA MATCH! This token is in the top tier of successful launches.");
+                        if let Some(ref tel) = self.telemetry {
+                            tel.log_elite_match();
+                        }
+                    }
+
+                    // Populate Metadata
+                    opportunity.is_dna_match = dna_match.is_match;
+                    opportunity.is_elite_match = dna_match.is_elite;
+                    opportunity.initial_liquidity_lamports = Some(dna.initial_liquidity);
+                    opportunity.launch_hour_utc = Some(dna.launch_hour_utc);
+                    Verdict::Pass(dna_match.score as f64, 0.0)
+                }
+                None => Verdict::Skip,
+            },
+
+            // Safety Filter (Rug Shield)
+            Gate::Safety => match &self.safety_checker {
+                Some(checker) => {
+                    // Check all output mints in the path concurrently so their account fetches share one RPC batch
+                    let verdicts = futures::future::join_all(
+                        opportunity.steps.iter().map(|step| checker.is_safe_to_trade(&step.output_mint, &step.pool))
+                    ).await;
+                    for (step, verdict) in opportunity.steps.iter().zip(verdicts) {
+                        if !verdict.map_err(|e| anyhow::anyhow!("Safety check failed: {}", e))? {
+                            warn!("⛔ SAFETY: Token {} in pool {} failed safety check. Aborting trade.", step.output_mint, step.pool);
+                            if let Some(ref tel) = self.telemetry {
+                                tel.log_safety_rejection();
+                            }
+                            return Ok(Verdict::Reject(RejectionReason::Safety, 0.0, 0.0));
+                        }
+                    }
+
+                    // Token-2022 transfer fees come out of every leg that moves the token
+                    let taxed_quote = checker.profit_after_transfer_fees(opportunity);
+                    if taxed_quote < opportunity.expected_profit_lamports {
+                        let taxed_profit = opportunity.start_units_in_sol(taxed_quote as i64).max(0) as u64;
+                        if taxed_profit <= ctx.tip_lamports {
+                            warn!("⛔ SAFETY: Transfer fees cut profit {} -> {} (tip {}). Aborting trade.",
+                                ctx.profit, taxed_profit, ctx.tip_lamports);
+                            return Ok(Verdict::Reject(RejectionReason::TransferFee, taxed_profit as f64, ctx.tip_lamports as f64));
+                        }
+                        info!("💸 Transfer fees: expected profit {} -> {} lamports", ctx.profit, taxed_profit);
+                        opportunity.expected_profit_lamports = taxed_quote;
+                        if opportunity.profit_sol_lamports.is_some() {
+                            opportunity.profit_sol_lamports = Some(taxed_profit);
+                        }
+                        ctx.profit = taxed_profit;
                     }
+                    Verdict::Pass(ctx.profit as f64, ctx.tip_lamports as f64)
                 }
+                None => Verdict::Skip,
+            },
+
+            // Quote cross-check: an outside quote per hop catches our own math or decoding going wrong
+            Gate::QuoteCheck => match &self.quote_check {
+                Some(check) => match check.check(opportunity).await {
+                    Some(hop) => {
+                        warn!("⛔ QUOTE CHECK: Hop {} outputs {} vs {} from {} ({:.0} bps over, tolerance {}). Rejecting opportunity.",
+                            hop.step_index + 1, hop.ours, hop.reference, check.source_name(), hop.deviation_bps, check.tolerance_bps());
+                        Verdict::Reject(RejectionReason::QuoteMismatch, hop.deviation_bps, check.tolerance_bps() as f64)
+                    }
+                    None => Verdict::Pass(0.0, check.tolerance_bps() as f64),
+                },
+                None => Verdict::Skip,
+            },
+
+            // Dynamic Slippage Calculation: widened with the most volatile pool in the cycle, up to the ceiling
+            Gate::Slippage => {
+                let max_bps = step.param("max_bps").map_or(ctx.max_slippage_bps, |bps| bps as u16);
+                let ceiling = step.param("ceiling_bps").map_or(ctx.max_slippage_ceiling, |bps| bps as u16);
+                let max_vol = opportunity.steps.iter()
+                    .map(|s| self.volatility_tracker.get_volatility_factor(s.pool))
+                    .fold(0.0_f64, f64::max);
 
-                // Populate Metadata
-                opportunity.is_dna_match = dna_match.is_match;
-                opportunity.is_elite_match = dna_match.is_elite;
-                opportunity.initial_liquidity_lamports = Some(dna.initial_liquidity);
-                opportunity.launch_hour_utc = Some(dna.launch_hour_utc);
+                let mut effective_slippage = max_bps;
+                if max_vol > 0.0 {
+                    let vol_adjustment = 1.0 + max_vol * ctx.volatility_sensitivity;
+                    effective_slippage = ((max_bps as f64 * vol_adjustment) as u16).min(ceiling);
+                    if effective_slippage > max_bps {
+                        info!("📈 Volatility Detected ({:.4}). Adjusting slippage: {}bps -> {}bps", max_vol, max_bps, effective_slippage);
+                    }
+                }
+                ctx.effective_slippage = effective_slippage;
+                Verdict::Pass(effective_slippage as f64, ceiling as f64)
             }
 
-            info!("🚀 AI Approved: High confidence ({:.2}). Triggering execution pipeline...", ai_confidence);
-            
-            // 2.5 Safety Filter (Rug Shield)
-            if let Some(checker) = &self.safety_checker {
-                // Check all output mints in the path (excluding the start/end which is usually SOL/USDC)
-                for step in &opportunity.steps {
-                    if !checker.is_safe_to_trade(&step.output_mint, &step.pool).await.map_err(|e| anyhow::anyhow!("Safety check failed: {}", e))? {
-                        warn!("⛔ SAFETY: Token {} in pool {} failed safety check. Aborting trade.", step.output_mint, step.pool);
-                        if let Some(ref tel) = self.telemetry {
-                            tel.log_safety_rejection();
+            // Bundle simulation; elite DNA matches get double verification (2 simulations)
+            Gate::Simulation => match (&self.simulator, &self.executor) {
+                (Some(simulator), Some(executor)) => {
+                    let instructions = executor.build_bundle_instructions(
+                        opportunity.clone(),
+                        ctx.tip_lamports,
+                        ctx.effective_slippage
+                    ).await?;
+
+                    let sim_count = if opportunity.is_elite_match { 2 } else { 1 };
+                    for i in 0..sim_count {
+                        match simulator.simulate_bundle(&instructions, executor.pubkey()).await {
+                            Ok(units) => {
+                                if i == 0 {
+                                    info!("✅ Simulation confirmed: {} units.", units);
+                                    ctx.simulation = Some(Ok(units));
+                                }
+                            },
+                            Err(e) => {
+                                warn!("❌ Simulation fail (Run {}/{}): {}. Dropping trade.", i + 1, sim_count, e);
+                                self.export_training(opportunity, RejectionReason::Simulation.as_str(), Some(Err(e.to_string())), ctx.tip_lamports, None);
+                                return Ok(Verdict::Reject(RejectionReason::Simulation, 0.0, 0.0));
+                            }
                         }
-                        return Ok(None);
                     }
+                    let units = ctx.simulation.as_ref().and_then(|s| s.as_ref().ok()).copied().unwrap_or(0);
+                    Verdict::Pass(units as f64, 0.0)
+                }
+                _ => Verdict::Skip,
+            },
+        })
+    }
+
+    /// Works the rest of a split route: wait for our fill to land in pool state, re-quote, then the same
+    /// execution policy as the first chunk before each send. Stops at the first chunk the policy rejects,
+    /// or once trading is paused. Runs on its own task, off the worker.
+    async fn execute_remaining_chunks(
+        &self,
+        executor: &Arc<dyn ExecutionPort>,
+        first: &ArbitrageOpportunity,
+        chunks: u8,
+        gates: &ChunkGates,
+    ) {
+        let delay = self.split_policy.as_ref().map(|p| p.requote_delay).unwrap_or_default();
+        let mut last_output = first.steps.last().map(|s| s.expected_output).unwrap_or(0);
+
+        for chunk in 2..=chunks {
+            tokio::time::sleep(delay).await;
+
+            if self.is_paused() {
+                info!("✂️ SPLIT: Trading paused after {}/{} chunks. Stopping.", chunk - 1, chunks);
+                return;
+            }
+
+            let mut fresh = match self.arb_strategy.requote(&first.steps, first.input_amount) {
+                Some(opp) => opp,
+                None => {
+                    info!("✂️ SPLIT: Dislocation closed after {}/{} chunks.", chunk - 1, chunks);
+                    return;
                 }
+            };
+            fresh.trigger = first.trigger;
+
+            // Identical quote means our previous fill isn't reflected yet; sending would double-count the edge
+            let output = fresh.steps.last().map(|s| s.expected_output).unwrap_or(0);
+            if output == last_output {
+                warn!("✂️ SPLIT: Pool state not refreshed since chunk {}. Stopping.", chunk - 1);
+                return;
+            }
+            last_output = output;
+
+            // Chunks of a borrowed route stay borrowed
+            if let (Some(flash), true) = (&self.flash_loans, first.flash_loan_amount > 0) {
+                flash.borrow(&mut fresh);
             }
 
-            // 3. Infrastructure interaction via Ports
-            if let Some(executor) = &self.executor {
-                // Dynamic Slippage Calculation
-                let mut effective_slippage = max_slippage_bps;
-                
-                // Calculate max volatility among pools in the cycle
-                let mut max_vol = 0.0_f64;
-                for step in &opportunity.steps {
-                    max_vol = max_vol.max(self.volatility_tracker.get_volatility_factor(step.pool));
-                }
-                
-                if max_vol > 0.0 {
-                    let vol_adjustment = (1.0 + max_vol * volatility_sensitivity) as f64;
-                    effective_slippage = (max_slippage_bps as f64 * vol_adjustment) as u16;
-                    effective_slippage = effective_slippage.min(max_slippage_ceiling);
-                    
-                    if effective_slippage > max_slippage_bps {
-                        info!("📈 Volatility Detected ({:.4}). Adjusting slippage: {}bps -> {}bps", max_vol, max_slippage_bps, effective_slippage);
-                    }
-                }
+            let Some(sol_rate) = self.sol_rate(&fresh) else {
+                info!("✂️ SPLIT: Start mint lost its oracle price after {}/{} chunks. Stopping.", chunk - 1, chunks);
+                return;
+            };
+            value_in_sol(&mut fresh, sol_rate);
+            let profit = fresh.profit_in_sol();
+            let tip = compute_tip(profit, gates.jito_tip_percentage, gates.jito_tip_lamports, gates.max_jito_tip_lamports);
+
+            // Every chunk is a dispatch of its own: exposure, rate limits, blacklist, slot lock, safety, simulation
+            let mut ctx = gates.context(profit, tip);
+            let trace = match self.run_policy(&mut fresh, &mut ctx).await {
+                Ok(Ok(trace)) => trace,
+                Ok(Err(reason)) => {
+                    info!("✂️ SPLIT: Chunk {}/{} rejected by the execution policy ({}). Stopping.", chunk, chunks, reason.as_str());
+                    return;
+                }
+                Err(e) => {
+                    error!("💥 Split chunk {}/{} gate failed: {}. Abandoning remaining chunks.", chunk, chunks, e);
+                    return;
+                }
+            };
+            if let Some(claim) = &ctx.slot_claim {
+                if !claim.commit() {
+                    info!("✂️ SPLIT: Chunk {}/{} preempted by a higher-EV overlapping route. Stopping.", chunk, chunks);
+                    mev_core::telemetry::OPPORTUNITIES_SLOT_CONFLICT.inc();
+                    self.journal_rejection(RejectionReason::SlotConflict, &fresh, ctx.net_profit as f64, 0.0, &trace);
+                    return;
+                }
+            }
+
+            match executor.build_and_send_bundle(fresh.clone(), solana_sdk::hash::Hash::default(), ctx.tip_lamports, ctx.effective_slippage).await {
+                Ok(bundle_id) => {
+                    self.record_dispatch(&fresh, ctx.tip_lamports, &bundle_id);
+                    self.record_decision(&fresh, Decision::Executed, &trace);
+                    mev_core::telemetry::SPLIT_CHUNKS_EXECUTED.inc();
+                    info!("🔥 SPLIT CHUNK {}/{} DISPATCHED: {}", chunk, chunks, bundle_id);
+                }
+                Err(e) => {
+                    error!("💥 Split chunk {}/{} failed: {}. Abandoning remaining chunks.", chunk, chunks, e);
+                    if let Some(journal) = &self.trade_journal {
+                        journal.record_send_failure(&fresh, ctx.tip_lamports, &e.to_string());
+                    }
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// A gate's answer for one route, with the value it observed and its threshold
+enum Verdict {
+    Pass(f64, f64),
+    Reject(RejectionReason, f64, f64),
+    /// Nothing to check with
+    Skip,
+}
+
+/// What the gates of one route share: its amounts, the thresholds for this event, and what earlier gates settled
+struct GateContext<'a> {
+    params: &'a StrategyParams,
+    initial_amount: u64,
+    profit: u64,
+    tip_lamports: u64,
+    net_profit: u64,
+    min_profit_threshold: u64,
+    ai_confidence_threshold: f32,
+    max_slippage_bps: u16,
+    volatility_sensitivity: f64,
+    max_slippage_ceiling: u16,
+    effective_slippage: u16,
+    slot_claim: Option<SlotClaim>,
+    simulation: Option<Result<u64, String>>,
+}
+
+/// The event's thresholds, kept for the later chunks of a split route so each one clears the same gates
+struct ChunkGates {
+    params: StrategyParams,
+    initial_amount: u64,
+    jito_tip_lamports: u64,
+    jito_tip_percentage: f64,
+    max_jito_tip_lamports: u64,
+    min_profit_threshold: u64,
+    ai_confidence_threshold: f32,
+    max_slippage_bps: u16,
+    volatility_sensitivity: f64,
+    max_slippage_ceiling: u16,
+}
+
+impl ChunkGates {
+    fn context(&self, profit: u64, tip_lamports: u64) -> GateContext<'_> {
+        GateContext {
+            params: &self.params,
+            initial_amount: self.initial_amount,
+            profit,
+            tip_lamports,
+            net_profit: profit.saturating_sub(tip_lamports),
+            min_profit_threshold: self.min_profit_threshold,
+            ai_confidence_threshold: self.ai_confidence_threshold,
+            max_slippage_bps: self.max_slippage_bps,
+            volatility_sensitivity: self.volatility_sensitivity,
+            max_slippage_ceiling: self.max_slippage_ceiling,
+            effective_slippage: self.max_slippage_bps,
+            slot_claim: None,
+            simulation: None,
+        }
+    }
+}
+
+/// Values the profit of a cycle that starts from a mint other than SOL at `sol_rate` lamports per unit
+fn value_in_sol(opp: &mut ArbitrageOpportunity, sol_rate: f64) {
+    if opp.start_mint().is_some_and(|mint| mint != mev_core::constants::SOL_MINT) {
+        opp.profit_sol_lamports = Some((opp.expected_profit_lamports as f64 * sol_rate) as u64);
+    }
+}
+
+/// Jito tip as a share of profit, clamped to [floor, ceiling]
+fn compute_tip(profit: u64, tip_percentage: f64, floor: u64, ceiling: u64) -> u64 {
+    ((profit as f64 * tip_percentage) as u64).max(floor).min(ceiling)
+}
+
+/// Quotes one hop through a pool. Returns (input-side reserve used for impact, amount out).
+fn quote_pool(pool: &PoolUpdate, current_mint: Pubkey, amount_in: u64) -> (u64, u64) {
+    if mev_core::constants::is_clmm(&pool.program_id) {
+        let price_sqrt = pool.price_sqrt.unwrap_or(0);
+        let liquidity = pool.liquidity.unwrap_or(0);
+
+        // Virtual reserve approximation for impact calculation
+        let sqrt_p = price_sqrt as f64 / (1u128 << 64) as f64;
+        let a_to_b = pool.mint_a == current_mint;
+        let v_res_in = if a_to_b {
+            (liquidity as f64 / sqrt_p) as u64
+        } else {
+            (liquidity as f64 * sqrt_p) as u64
+        };
+
+        (v_res_in, mev_core::math::get_amount_out_clmm(amount_in, price_sqrt, liquidity, pool.fee_bps, a_to_b))
+    } else if pool.program_id == mev_core::constants::PUMP_FUN_PROGRAM {
+        let (sol, tokens) = if pool.mint_a == mev_core::constants::SOL_MINT {
+            (pool.reserve_a as u64, pool.reserve_b as u64)
+        } else {
+            (pool.reserve_b as u64, pool.reserve_a as u64)
+        };
+        let buy = current_mint == mev_core::constants::SOL_MINT;
+        let r_in = if buy { sol } else { tokens };
+        (r_in, mev_core::math::get_amount_out_bonding_curve(amount_in, sol, tokens, pool.fee_bps, buy))
+    } else {
+        let (r_in, r_out) = if pool.mint_a == current_mint {
+            (pool.reserve_a as u64, pool.reserve_b as u64)
+        } else {
+            (pool.reserve_b as u64, pool.reserve_a as u64)
+        };
+        (r_in, mev_core::math::get_amount_out_cpmm(amount_in, r_in, r_out, pool.fee_bps))
+    }
+}
+
+/// Quotes a Whirlpool hop by replaying the swap across its loaded ticks. Swaps that would
+/// run past the loaded arrays quote nothing rather than guess at the liquidity beyond.
+fn quote_whirlpool(pool: &PoolUpdate, ticks: &TickRange, current_mint: Pubkey, amount_in: u64) -> (u64, u64) {
+    let (res_in, _) = quote_pool(pool, current_mint, amount_in);
+    let sqrt_price_x64 = pool.price_sqrt.unwrap_or(0);
+    if sqrt_price_x64 < clmm::MIN_SQRT_PRICE_X64 || sqrt_price_x64 > clmm::MAX_SQRT_PRICE_X64 {
+        return (res_in, 0);
+    }
+    let state = ClmmState {
+        sqrt_price_x64,
+        tick_current_index: clmm::tick_index_from_sqrt_price(sqrt_price_x64),
+        liquidity: pool.liquidity.unwrap_or(0),
+        fee_rate: pool.fee_bps.saturating_mul(100),
+    };
+    let a_to_b = pool.mint_a == current_mint;
+    (res_in, clmm::swap_exact_in(&state, ticks, amount_in, a_to_b).map_or(0, |r| r.amount_out))
+}
+
+/// Marginal exchange rate of one hop after fees, in raw output units per input unit
+fn spot_rate(pool: &PoolUpdate, current_mint: Pubkey) -> f64 {
+    let fee_multiplier = 1.0 - pool.fee_bps as f64 / 10000.0;
+    let a_to_b = pool.mint_a == current_mint;
+    if mev_core::constants::is_clmm(&pool.program_id) {
+        let sqrt_p = pool.price_sqrt.unwrap_or(0) as f64 / (1u128 << 64) as f64;
+        let price = sqrt_p * sqrt_p;
+        if pool.liquidity.unwrap_or(0) == 0 || price == 0.0 {
+            return 0.0;
+        }
+        if a_to_b { price * fee_multiplier } else { fee_multiplier / price }
+    } else {
+        let (r_in, r_out) = if a_to_b { (pool.reserve_a, pool.reserve_b) } else { (pool.reserve_b, pool.reserve_a) };
+        if r_in == 0 {
+            return 0.0;
+        }
+        r_out as f64 / r_in as f64 * fee_multiplier
+    }
+}
+
+/// One venue's quote for a hop, as the search sees it
+struct EdgeQuote<'a> {
+    venue: &'a Venue,
+    res_in: u64,
+    amount_out: u64,
+    impact_bps: u16,
+}
 
-                // Optional Simulation
-                if let Some(simulator) = &self.simulator {
-                    let instructions = executor.build_bundle_instructions(
-                        opportunity.clone(), 
-                        tip_lamports, 
-                        effective_slippage
-                    ).await?;
+/// One pool's share of a leg
+struct LegPart<'a> {
+    venue: &'a Venue,
+    res_in: u64,
+    amount_in: u64,
+    amount_out: u64,
+}
 
-                    // Phase 11: DNA-based Simulation Scaling
-                    // Elite matches get double verification (2 simulations) to ensure stable execution
-                    let sim_count = if opportunity.is_elite_match { 2 } else { 1 };
-                    
-                    for i in 0..sim_count {
-                        match simulator.simulate_bundle(&instructions, executor.pubkey()).await {
-                            Ok(units) => {
-                                if i == 0 {
-                                    info!("✅ Simulation confirmed: {} units.", units);
-                                }
-                            },
-                            Err(e) => {
-                                warn!("❌ Simulation fail (Run {}/{}): {}. Dropping trade.", i + 1, sim_count, e);
-                                return Ok(None);
-                            }
-                        }
-                    }
-                }
+/// A route hop: the pair and the pools its input is spread over (usually one)
+type RouteLeg<'a> = (SmallVec<[&'a Venue; 4]>, Pubkey, Pubkey);
+
+/// Restricts a DFS to cycles whose last hop is `pool`, taken from `closing_from`
+struct EdgeScope {
+    pool: Pubkey,
+    closing_from: NodeIndex,
+    /// Hops from each node near `closing_from` to it, as far out as the search can go
+    distance: HashMap<NodeIndex, u8>,
+}
 
-                // 4. Track stats
-                self.total_simulated_pnl.fetch_add(opportunity.expected_profit_lamports, std::sync::atomic::Ordering::SeqCst);
-
-                // 4.5 Log to Performance Tracker (Non-blocking)
-                if let Some(tracker) = &self.performance_tracker {
-                    let token_label = format!("{:?}", opportunity.steps.last().map(|s| s.output_mint));
-                    tracker.log_trade(&token_label, opportunity.expected_profit_lamports as i64, "Live").await;
-                }
-
-                // 5. Atomic Execution
-                match executor.build_and_send_bundle(
-                    opportunity.clone(), 
-                    solana_sdk::hash::Hash::default(), 
-                    tip_lamports,
-                    effective_slippage
-                ).await {
-                    Ok(bundle_id) => {
-                        info!("🔥 BUNDLE DISPATCHED: {}", bundle_id);
-                        return Ok(Some(opportunity));
-                    },
-                    Err(e) => {
-                        error!("💥 Execution panic: {}", e);
-                        return Ok(None);
+impl EdgeScope {
+    fn new(graph: &DiGraph<Pubkey, Vec<Venue>>, pool: Pubkey, closing_from: NodeIndex, max_distance: u8) -> Self {
+        let mut distance = HashMap::from([(closing_from, 0u8)]);
+        let mut frontier = vec![closing_from];
+        for d in 1..=max_distance {
+            let mut next = Vec::new();
+            for node in frontier {
+                for neighbor in graph.neighbors_directed(node, petgraph::Direction::Incoming) {
+                    if let std::collections::hash_map::Entry::Vacant(slot) = distance.entry(neighbor) {
+                        slot.insert(d);
+                        next.push(neighbor);
                     }
                 }
-            } else {
-                return Ok(Some(opportunity));
             }
+            frontier = next;
         }
+        Self { pool, closing_from, distance }
+    }
+
+    /// Whether a path at `node` with `hops_left` can still get to `closing_from` and take the last hop
+    fn reaches(&self, node: NodeIndex, hops_left: u8) -> bool {
+        self.distance.get(&node).is_some_and(|&d| d < hops_left)
+    }
+
+    fn closes(&self, from: NodeIndex, pool: Pubkey) -> bool {
+        from == self.closing_from && pool == self.pool
+    }
+}
+
+/// The more profitable of two search results; ties keep `a`
+fn more_profitable(a: Option<ArbitrageOpportunity>, b: Option<ArbitrageOpportunity>) -> Option<ArbitrageOpportunity> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b.expected_profit_lamports > a.expected_profit_lamports { b } else { a }),
+        (a, b) => a.or(b),
     }
+}
+
+/// A route's pools grouped into legs, looked up on the graph; None if a pool has left it
+fn route_legs_on<'g>(graph: &'g DiGraph<Pubkey, Vec<Venue>>, nodes: &HashMap<Pubkey, NodeIndex>, steps: &[SwapStep]) -> Option<SmallVec<[RouteLeg<'g>; 8]>> {
+    mev_core::route_legs(steps)
+        .map(|leg| {
+            let (input_mint, output_mint) = (leg[0].input_mint, leg[0].output_mint);
+            let edge = graph.find_edge(*nodes.get(&input_mint)?, *nodes.get(&output_mint)?)?;
+            let venues = leg.iter()
+                .map(|step| graph[edge].iter().find(|p| p.pool_address() == step.pool))
+                .collect::<Option<SmallVec<[&Venue; 4]>>>()?;
+            Some((venues, input_mint, output_mint))
+        })
+        .collect()
+}
 
 pub struct ArbitrageStrategy {
-    graph: RwLock<DiGraph<Pubkey, Vec<PoolUpdate>>>,  // HFT: RwLock for concurrent reads, Vec for multi-pool support
+    graph: RwLock<DiGraph<Pubkey, Vec<Venue>>>,  // HFT: RwLock for concurrent reads, Vec for multi-pool support
     nodes: RwLock<HashMap<Pubkey, NodeIndex>>,   // Read-heavy workload
     volatility_tracker: Arc<VolatilityTracker>,
+    hop_costs: HopCostTable,
+    search_mode: CycleSearchMode,
+    /// Bin liquidity for Meteora DLMM pairs, refreshed outside the account stream
+    dlmm_liquidity: RwLock<HashMap<Pubkey, Arc<DlmmLiquidity>>>,
+    /// Initialized ticks around each Whirlpool's price, refreshed outside the account stream
+    whirlpool_ticks: RwLock<HashMap<Pubkey, Arc<TickRange>>>,
+    /// Decoded CLOB books, written by ingestion
+    order_books: Arc<OrderBookCache>,
+    /// Pools older than this (secs) are left out of cycle search; 0 = no limit
+    edge_ttl_secs: u64,
+    /// Per-hop impact cap and the engine's other thresholds
+    params: Arc<StrategyParamsHandle>,
+    clock: Arc<dyn Clock>,
+    /// DFS searches this deep or deeper fan the first hop out over the rayon pool; 0 = never
+    parallel_min_hops: u8,
+    /// Wall time one search may take before the best cycle so far is returned; None = unbounded
+    search_budget: Option<Duration>,
+    /// Pools one leg of a found cycle may be spread over; 1 = one pool per leg
+    split_max_pools: u8,
+    /// SOL-equivalent depth a pool needs to be searched; None = no floor
+    liquidity_floor: Option<Arc<LiquidityFloor>>,
+    /// Pools whose last update left them under the floor: kept in the graph, left out of the search
+    thin_pools: RwLock<HashSet<Pubkey>>,
+}
+
+/// What one `prune` pass removed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruneStats {
+    pub pools: usize,
+    pub nodes: usize,
 }
 
 impl Default for ArbitrageStrategy {
@@ -310,11 +1570,333 @@ impl ArbitrageStrategy {
             graph: RwLock::new(DiGraph::new()),
             nodes: RwLock::new(HashMap::new()),
             volatility_tracker,
+            hop_costs: HopCostTable::default(),
+            search_mode: CycleSearchMode::default(),
+            dlmm_liquidity: RwLock::new(HashMap::new()),
+            whirlpool_ticks: RwLock::new(HashMap::new()),
+            order_books: Arc::new(OrderBookCache::new()),
+            edge_ttl_secs: 0,
+            params: Arc::new(StrategyParamsHandle::default()),
+            clock: mev_core::clock::system(),
+            parallel_min_hops: 0,
+            search_budget: None,
+            split_max_pools: 1,
+            liquidity_floor: None,
+            thin_pools: RwLock::new(HashSet::new()),
+        }
+    }
+
+    pub fn set_hop_costs(&mut self, table: HopCostTable) {
+        self.hop_costs = table;
+    }
+
+    pub fn set_search_mode(&mut self, mode: CycleSearchMode) {
+        self.search_mode = mode;
+    }
+
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    pub fn set_edge_ttl(&mut self, secs: u64) {
+        self.edge_ttl_secs = secs;
+    }
+
+    pub fn set_parallel_search(&mut self, min_hops: u8, budget: Option<Duration>) {
+        self.parallel_min_hops = min_hops;
+        self.search_budget = budget;
+    }
+
+    pub fn set_split_routing(&mut self, max_pools: u8) {
+        self.split_max_pools = max_pools.max(1);
+    }
+
+    pub fn set_liquidity_floor(&mut self, floor: Arc<LiquidityFloor>) {
+        self.liquidity_floor = Some(floor);
+    }
+
+    /// Pools under the liquidity floor, to be skipped by a search; None when there is no floor
+    fn thin_pools(&self) -> Option<parking_lot::RwLockReadGuard<'_, HashSet<Pubkey>>> {
+        self.liquidity_floor.as_ref().map(|_| self.thin_pools.read())
+    }
+
+    /// Re-values a pool against the floor on each update; oracle moves count from its next one
+    fn mark_depth(&self, floor: &LiquidityFloor, pool: &PoolUpdate) {
+        let depth = floor.pool_depth_lamports(pool, self.clock.unix_secs());
+        let thin = !floor.admits(depth);
+        if thin == self.thin_pools.read().contains(&pool.pool_address) {
+            return;
+        }
+        if thin {
+            debug!("🏜️ {} holds ~{} SOL-equivalent lamports, under the {} floor; leaving it out of the search",
+                pool.pool_address, depth.unwrap_or(0), floor.min_lamports());
+            mev_core::telemetry::POOLS_BELOW_LIQUIDITY_FLOOR.with_label_values(&["search"]).inc();
+            self.thin_pools.write().insert(pool.pool_address);
+        } else {
+            self.thin_pools.write().remove(&pool.pool_address);
+        }
+    }
+
+    /// Oldest pool timestamp (unix secs) the search still trusts
+    fn fresh_after(&self) -> u64 {
+        if self.edge_ttl_secs == 0 {
+            return 0;
         }
+        self.clock.unix_secs().saturating_sub(self.edge_ttl_secs)
     }
 
     pub fn process_update(&self, update: PoolUpdate, initial_amount: u64, max_hops: u8) -> Option<ArbitrageOpportunity> {
+        let (node_a, node_b) = self.apply_update(update);
+        self.find_best_cycle(node_a, node_b, initial_amount, max_hops)
+    }
+
+    /// Best spot rate over the direct edge between two mints
+    pub fn spot_price(&self, from: &Pubkey, to: &Pubkey) -> Option<f64> {
+        // Graph before nodes, same order as the write path
+        let graph = self.graph.read();
+        let nodes = self.nodes.read();
+        let edge = graph.find_edge(*nodes.get(from)?, *nodes.get(to)?)?;
+        graph[edge].iter()
+            .map(|venue| venue.spot_rate(*from))
+            .filter(|rate| rate.is_finite() && *rate > 0.0)
+            .max_by(|a, b| a.total_cmp(b))
+    }
+
+    /// Best venue on the direct edge for `amount`, under the search's freshness and impact filters
+    pub fn best_hop(&self, from: &Pubkey, to: &Pubkey, amount: u64) -> Option<SwapStep> {
+        let graph = self.graph.read();
+        let nodes = self.nodes.read();
+        let edge = graph.find_edge(*nodes.get(from)?, *nodes.get(to)?)?;
+        let quote = self.quote_edge(&graph[edge], *from, amount, self.fresh_after(), self.params.get().max_price_impact_bps)
+            .into_iter()
+            .max_by_key(|q| q.amount_out)?;
+        let pool = quote.venue.state();
+        Some(SwapStep {
+            pool: pool.pool_address,
+            program_id: pool.program_id,
+            input_mint: *from,
+            output_mint: *to,
+            expected_output: quote.amount_out,
+        })
+    }
+
+    /// Replaces the bins a DLMM pair is quoted against
+    pub fn update_dlmm_liquidity(&self, pool: Pubkey, liquidity: DlmmLiquidity) {
+        self.dlmm_liquidity.write().insert(pool, Arc::new(liquidity));
+    }
+
+    /// Replaces the tick arrays a Whirlpool is quoted against
+    pub fn update_whirlpool_ticks(&self, pool: Pubkey, ticks: TickRange) {
+        self.whirlpool_ticks.write().insert(pool, Arc::new(ticks));
+    }
+
+    pub fn order_books(&self) -> Arc<OrderBookCache> {
+        Arc::clone(&self.order_books)
+    }
+
+    /// Stores a freshly decoded book and swaps it into the market's graph edges
+    pub fn update_order_book(&self, market: Pubkey, book: OrderBook) {
+        let book = Arc::new(book);
+        self.order_books.insert(market, Arc::clone(&book));
+        let mut graph = self.graph.write();
+        for venue in graph.edge_weights_mut().flatten() {
+            if let Venue::Book { state, book: edge_book } = venue {
+                if state.pool_address == market {
+                    *edge_book = Some(Arc::clone(&book));
+                }
+            }
+        }
+    }
+
+    /// Quotes one hop. CLOB markets fill level by level from the book on their edge and
+    /// DLMM pairs walk their cached bins; either quotes nothing until its liquidity arrives.
+    /// Whirlpools walk their cached tick arrays once loaded and use the single-curve quote until then.
+    fn quote_hop(&self, venue: &Venue, current_mint: Pubkey, amount_in: u64) -> (u64, u64) {
+        let pool = match venue {
+            Venue::Book { book: Some(book), .. } => {
+                let base_in = current_mint == book.base_mint;
+                return (book.depth_in(base_in), book.get_amount_out(amount_in, base_in));
+            }
+            Venue::Book { book: None, .. } => return (0, 0),
+            Venue::Amm(pool) => pool,
+        };
+        if pool.program_id == mev_core::constants::ORCA_WHIRLPOOL_PROGRAM {
+            if let Some(ticks) = self.whirlpool_ticks.read().get(&pool.pool_address).cloned() {
+                return quote_whirlpool(pool, &ticks, current_mint, amount_in);
+            }
+        }
+        if pool.program_id != mev_core::constants::METEORA_PROGRAM_ID {
+            return quote_pool(pool, current_mint, amount_in);
+        }
+        let Some(liquidity) = self.dlmm_liquidity.read().get(&pool.pool_address).cloned() else {
+            return (0, 0);
+        };
+        let x_to_y = current_mint == liquidity.token_x_mint;
+        (liquidity.depth_in(x_to_y), liquidity.get_amount_out(amount_in, x_to_y))
+    }
+
+    /// Quotes every fresh venue of an edge, dropping empty quotes and hops over the impact cap.
+    /// Fee tiers of one program on the same pair (Whirlpools at 1, 4, 30 bps...) then compete:
+    /// a tier is dropped when a cheaper or equal one of its program quotes at least as much,
+    /// so the search walks the cheapest tier with adequate depth and keeps a pricier one only
+    /// while it is the better price.
+    fn quote_edge<'a>(
+        &self,
+        venues: impl IntoIterator<Item = &'a Venue>,
+        current_mint: Pubkey,
+        amount_in: u64,
+        fresh_after: u64,
+        max_impact_bps: u16,
+    ) -> SmallVec<[EdgeQuote<'a>; 4]> {
+        let mut quotes: SmallVec<[EdgeQuote<'a>; 4]> = SmallVec::new();
+        let thin_pools = self.thin_pools();
+        for venue in venues {
+            if venue.state().timestamp < fresh_after {
+                continue; // Stale: its stream has likely gone quiet
+            }
+            if thin_pools.as_ref().is_some_and(|thin| thin.contains(&venue.pool_address())) {
+                continue; // Under the liquidity floor
+            }
+            let (res_in, amount_out) = self.quote_hop(venue, current_mint, amount_in);
+            if amount_out == 0 {
+                continue;
+            }
+            let impact = mev_core::math::calculate_price_impact(amount_in, res_in);
+            let impact_bps = (impact * 10000.0) as u16;
+            if impact_bps > max_impact_bps {
+                debug!("Skipping {} (fee tier {} bps): price impact {:.2}%", venue.pool_address(), venue.fee_tier_bps(), impact * 100.0);
+                continue;
+            }
+            quotes.push(EdgeQuote { venue, res_in, amount_out, impact_bps });
+        }
+
+        // Cheapest tier first; within a tier, the better quote first
+        quotes.sort_by(|a, b| a.venue.fee_tier_bps().cmp(&b.venue.fee_tier_bps()).then(b.amount_out.cmp(&a.amount_out)));
+        let mut kept: SmallVec<[EdgeQuote<'a>; 4]> = SmallVec::new();
+        for quote in quotes {
+            let program = quote.venue.state().program_id;
+            let dominated = kept.iter().any(|k| k.venue.state().program_id == program && k.amount_out >= quote.amount_out);
+            if dominated {
+                debug!("Skipping {}: a cheaper fee tier quotes at least as much", quote.venue.pool_address());
+                continue;
+            }
+            kept.push(quote);
+        }
+        kept
+    }
+
+    /// Quoting inputs of every distinct pool on the route
+    pub fn depth_snapshot(&self, opp: &ArbitrageOpportunity) -> Vec<PoolDepth> {
+        let mut pools: Vec<PoolDepth> = Vec::with_capacity(opp.steps.len());
+        for step in &opp.steps {
+            if pools.iter().any(|p| p.pool == step.pool) {
+                continue;
+            }
+            pools.push(PoolDepth {
+                pool: step.pool,
+                state: self.pool_state(&step.pool),
+                dlmm: self.dlmm_liquidity.read().get(&step.pool).cloned(),
+                book: self.order_books.get(&step.pool),
+            });
+        }
+        pools
+    }
+
+    pub fn pool_state(&self, pool: &Pubkey) -> Option<PoolUpdate> {
+        let graph = self.graph.read();
+        graph.edge_weights().flatten().map(Venue::state).find(|p| p.pool_address == *pool).cloned()
+    }
+
+    /// Drops a pool from every edge it quotes on, and edges left without pools.
+    /// Mint nodes stay so stored node indices remain valid. Returns false if the pool was unknown.
+    pub fn remove_pool(&self, pool: &Pubkey) -> bool {
+        let mut graph = self.graph.write();
+        let mut removed = false;
+        let mut empty = Vec::new();
+        for edge in graph.edge_indices() {
+            let pools = &mut graph[edge];
+            let before = pools.len();
+            pools.retain(|p| p.pool_address() != *pool);
+            removed |= pools.len() != before;
+            if pools.is_empty() {
+                empty.push(edge);
+            }
+        }
+        // remove_edge moves the last edge into the freed index: go from the back
+        for edge in empty.into_iter().rev() {
+            graph.remove_edge(edge);
+        }
+        drop(graph);
+        self.dlmm_liquidity.write().remove(pool);
+        self.whirlpool_ticks.write().remove(pool);
+        self.order_books.remove(pool);
+        removed
+    }
+
+    /// Removes pools whose last update is older than `max_age_secs`, then edges and mint
+    /// nodes left with nothing. `remove_node` moves the last node into the freed slot, so
+    /// the mint index is patched as it goes; searches holding an index from before the
+    /// prune re-check it under the graph lock.
+    pub fn prune(&self, max_age_secs: u64) -> PruneStats {
+        let cutoff = self.clock.unix_secs().saturating_sub(max_age_secs);
+        let mut stats = PruneStats::default();
+        let mut dead_pools = Vec::new();
+
+        let mut graph = self.graph.write();
+        let mut empty = Vec::new();
+        for edge in graph.edge_indices() {
+            let pools = &mut graph[edge];
+            pools.retain(|p| {
+                let keep = p.state().timestamp >= cutoff;
+                if !keep && !dead_pools.contains(&p.pool_address()) {
+                    dead_pools.push(p.pool_address());
+                }
+                keep
+            });
+            if pools.is_empty() {
+                empty.push(edge);
+            }
+        }
+        for edge in empty.into_iter().rev() {
+            graph.remove_edge(edge);
+        }
+
+        let mut nodes = self.nodes.write();
+        for index in (0..graph.node_count()).rev().map(NodeIndex::new) {
+            if graph.neighbors_undirected(index).next().is_some() {
+                continue;
+            }
+            if let Some(mint) = graph.remove_node(index) {
+                nodes.remove(&mint);
+                stats.nodes += 1;
+            }
+            // Higher indices were already visited, so the node moved here is one we keep
+            if let Some(moved) = graph.node_weight(index) {
+                nodes.insert(*moved, index);
+            }
+        }
+        drop(nodes);
+        drop(graph);
+
+        let mut dlmm = self.dlmm_liquidity.write();
+        let mut whirlpool_ticks = self.whirlpool_ticks.write();
+        let mut thin_pools = self.thin_pools.write();
+        for pool in &dead_pools {
+            dlmm.remove(pool);
+            whirlpool_ticks.remove(pool);
+            thin_pools.remove(pool);
+            self.order_books.remove(pool);
+        }
+        stats.pools = dead_pools.len();
+        stats
+    }
+
+    /// Writes the pool state into the graph and returns the nodes of its two mints
+    pub fn apply_update(&self, update: PoolUpdate) -> (NodeIndex, NodeIndex) {
         // HFT OPTIMIZATION: Minimize write-lock duration
+        if let Some(floor) = &self.liquidity_floor {
+            self.mark_depth(floor, &update);
+        }
         
         // 1. Fast path: Try read-only lookup first
         let (node_a, node_b) = {
@@ -322,12 +1904,13 @@ impl ArbitrageStrategy {
             (nodes_read.get(&update.mint_a).copied(), nodes_read.get(&update.mint_b).copied())
         };
         
-        // 2. If nodes exist, upgrade to write for edge update
+        // 2. Upgrade to write for the edge update. A prune may have moved either node
+        //    since the lookup, so the indices are only trusted if they still name our mints.
+        let mut graph = self.graph.write();
         let (node_a, node_b) = match (node_a, node_b) {
-            (Some(a), Some(b)) => (a, b),
+            (Some(a), Some(b)) if graph.node_weight(a) == Some(&update.mint_a) && graph.node_weight(b) == Some(&update.mint_b) => (a, b),
             _ => {
                 // Write path: Need to create new nodes
-                let mut graph = self.graph.write();
                 let mut nodes = self.nodes.write();
                 
                 let a = *nodes.entry(update.mint_a).or_insert_with(|| graph.add_node(update.mint_a));
@@ -340,76 +1923,511 @@ impl ArbitrageStrategy {
 
         // 3. Update the market graph
         {
-            let mut graph = self.graph.write();
-            let update_edge = |graph: &mut DiGraph<Pubkey, Vec<PoolUpdate>>, from, to, data: PoolUpdate| {
+            let update_edge = |graph: &mut DiGraph<Pubkey, Vec<Venue>>, from, to, data: Venue| {
                 if let Some(edge_idx) = graph.find_edge(from, to) {
                     let pools = &mut graph[edge_idx];
                     // Find existing pool with same address and update it, or add new pool
-                    if let Some(pool) = pools.iter_mut().find(|p| p.pool_address == data.pool_address) {
-                        let pool_addr = data.pool_address;
+                    if let Some(pool) = pools.iter_mut().find(|p| p.pool_address() == data.pool_address()) {
+                        let pool_addr = data.pool_address();
                         *pool = data;  // Update existing pool
                         tracing::debug!("Updated existing pool {} in edge", pool_addr);
                     } else {
-                        let pool_addr = data.pool_address;
+                        let pool_addr = data.pool_address();
                         let new_len = pools.len() + 1;
                         pools.push(data);  // Add new pool for cross-DEX
                         tracing::info!("🔗 Added new pool {} to edge (total: {})", pool_addr, new_len);
                     }
                 } else {
-                    let pool_addr = data.pool_address;  // Copy before move
+                    let pool_addr = data.pool_address();  // Copy before move
                     graph.add_edge(from, to, vec![data]);
                     tracing::debug!("Created new edge with pool {}", pool_addr);
                 }
             };
-            update_edge(&mut graph, node_a, node_b, update.clone());
-            update_edge(&mut graph, node_b, node_a, update.clone());
+            // CLOB markets take the latest decoded book along with their state
+            let venue = Venue::new(update.clone(), self.order_books.get(&update.pool_address));
+            update_edge(&mut graph, node_a, node_b, venue.clone());
+            update_edge(&mut graph, node_b, node_a, venue);
+        }
+        drop(graph);
+
+        // 3.5 Update Volatility Tracker
+        let price = if mev_core::constants::is_clmm(&update.program_id) {
+            let sqrt_p = update.price_sqrt.unwrap_or(0) as f64 / (1u128 << 64) as f64;
+            sqrt_p * sqrt_p
+        } else {
+            if update.reserve_a > 0 {
+                update.reserve_b as f64 / update.reserve_a as f64
+            } else {
+                0.0
+            }
+        };
+        if price > 0.0 {
+            self.volatility_tracker.add_sample(update.pool_address, price);
+        }
+
+        (node_a, node_b)
+    }
+
+    /// Searches for the best profitable cycle through either side of an updated pool
+    pub fn find_best_cycle(&self, node_a: NodeIndex, node_b: NodeIndex, initial_amount: u64, max_hops: u8) -> Option<ArbitrageOpportunity> {
+        // 4. Search for cycles (read-lock only)
+        let graph = self.graph.read();
+        // Indices from before a prune can point past the end of the node list
+        if graph.node_weight(node_a).is_none() || graph.node_weight(node_b).is_none() {
+            return None;
+        }
+        let mut best_opp: Option<ArbitrageOpportunity> = None;
+        let deadline = self.search_budget.map(|budget| Instant::now() + budget);
+
+        if self.search_mode == CycleSearchMode::BellmanFord {
+            best_opp = self.find_negative_cycle(&graph, node_a, node_b, initial_amount, max_hops);
+        } else {
+            // Search from A
+            self.search_from(&graph, node_a, initial_amount, max_hops, None, deadline, &mut best_opp);
+
+            // Search from B (in case the update is the last leg back to B, or B is the start token)
+            if node_b != node_a {
+                self.search_from(&graph, node_b, initial_amount, max_hops, None, deadline, &mut best_opp);
+            }
+        }
+
+        self.report_cycle(&graph, best_opp, deadline)
+    }
+
+    /// Full search of every cycle through `start` (the background sweep behind the incremental search)
+    pub fn sweep(&self, start: &Pubkey, initial_amount: u64, max_hops: u8) -> Option<ArbitrageOpportunity> {
+        let node = *self.nodes.read().get(start)?;
+        self.find_best_cycle(node, node, initial_amount, max_hops)
+    }
+
+    /// Searches only the cycles that trade through `pool`, in either direction: from each side,
+    /// out over the pool first and back any way, or out any way and back over the pool last.
+    /// Cycles elsewhere in the graph are left to the background sweep.
+    pub fn find_best_cycle_through(&self, pool: Pubkey, node_a: NodeIndex, node_b: NodeIndex, initial_amount: u64, max_hops: u8) -> Option<ArbitrageOpportunity> {
+        if self.search_mode == CycleSearchMode::BellmanFord {
+            return self.find_best_cycle(node_a, node_b, initial_amount, max_hops); // One pass over every edge either way
+        }
+        let graph = self.graph.read();
+        if graph.node_weight(node_a).is_none() || graph.node_weight(node_b).is_none() || node_a == node_b || max_hops < 2 {
+            return None;
+        }
+        let fresh_after = self.fresh_after();
+        let max_impact_bps = self.params.get().max_price_impact_bps;
+        let mut best_opp: Option<ArbitrageOpportunity> = None;
+        let deadline = self.search_budget.map(|budget| Instant::now() + budget);
+
+        for (start, other) in [(node_a, node_b), (node_b, node_a)] {
+            // Out over the pool first
+            let venue = graph.find_edge(start, other).into_iter()
+                .flat_map(|edge| graph[edge].iter())
+                .filter(|venue| venue.pool_address() == pool);
+            if let Some(first) = self.quote_edge(venue, graph[start], initial_amount, fresh_after, max_impact_bps).into_iter().next() {
+                let state = first.venue.state();
+                let mut visited: SmallVec<[NodeIndex; 8]> = SmallVec::from_slice(&[start, other]);
+                let mut steps: SmallVec<[SwapStep; 8]> = SmallVec::new();
+                steps.push(SwapStep {
+                    pool: state.pool_address,
+                    program_id: state.program_id,
+                    input_mint: graph[start],
+                    output_mint: graph[other],
+                    expected_output: first.amount_out,
+                });
+                self.find_cycles_recursive(&graph, other, start, first.amount_out, initial_amount, &mut visited, &mut steps, &mut best_opp, max_hops - 1, first.impact_bps, None, deadline);
+            }
+
+            // Back over the pool last: only paths that can still reach the other side
+            let scope = EdgeScope::new(&graph, pool, other, max_hops - 2);
+            self.search_from(&graph, start, initial_amount, max_hops, Some(&scope), deadline, &mut best_opp);
+        }
+
+        self.report_cycle(&graph, best_opp, deadline)
+    }
+
+    /// DFS of the cycles through `start`. At `parallel_min_hops` and deeper each first hop is
+    /// searched as its own task on the rayon pool and the most profitable result wins.
+    #[allow(clippy::too_many_arguments)]
+    fn search_from(
+        &self,
+        graph: &DiGraph<Pubkey, Vec<Venue>>,
+        start: NodeIndex,
+        initial_amount: u64,
+        max_hops: u8,
+        scope: Option<&EdgeScope>,
+        deadline: Option<Instant>,
+        best_opp: &mut Option<ArbitrageOpportunity>,
+    ) {
+        if self.parallel_min_hops == 0 || max_hops < self.parallel_min_hops {
+            let mut visited: SmallVec<[NodeIndex; 8]> = SmallVec::new();
+            visited.push(start);
+            self.find_cycles_recursive(graph, start, start, initial_amount, initial_amount, &mut visited, &mut SmallVec::new(), best_opp, max_hops, 0, scope, deadline);
+            return;
+        }
+
+        // Quoted here, as the sequential search would at its first level
+        let start_mint = graph[start];
+        let fresh_after = self.fresh_after();
+        let max_impact_bps = self.params.get().max_price_impact_bps;
+        let first_hops: Vec<(NodeIndex, SwapStep, u16)> = graph.edges(start)
+            .filter(|edge| edge.target() != start && scope.is_none_or(|scope| scope.reaches(edge.target(), max_hops - 1)))
+            .flat_map(|edge| {
+                let next = edge.target();
+                self.quote_edge(edge.weight(), start_mint, initial_amount, fresh_after, max_impact_bps)
+                    .into_iter()
+                    .map(move |quote| {
+                        let pool = quote.venue.state();
+                        let step = SwapStep {
+                            pool: pool.pool_address,
+                            program_id: pool.program_id,
+                            input_mint: start_mint,
+                            output_mint: graph[next],
+                            expected_output: quote.amount_out,
+                        };
+                        (next, step, quote.impact_bps)
+                    })
+            })
+            .collect();
+
+        let found = first_hops.into_par_iter()
+            .map(|(next, step, impact_bps)| {
+                let amount = step.expected_output;
+                let mut visited: SmallVec<[NodeIndex; 8]> = SmallVec::from_slice(&[start, next]);
+                let mut steps: SmallVec<[SwapStep; 8]> = SmallVec::new();
+                steps.push(step);
+                let mut local = None;
+                self.find_cycles_recursive(graph, next, start, amount, initial_amount, &mut visited, &mut steps, &mut local, max_hops - 1, impact_bps, scope, deadline);
+                local
+            })
+            .reduce(|| None, more_profitable);
+        *best_opp = more_profitable(best_opp.take(), found);
+    }
+
+    fn report_cycle(&self, graph: &DiGraph<Pubkey, Vec<Venue>>, best_opp: Option<ArbitrageOpportunity>, deadline: Option<Instant>) -> Option<ArbitrageOpportunity> {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            mev_core::telemetry::CYCLE_SEARCH_BUDGET_EXCEEDED.inc();
+            debug!("⏱️ Cycle search hit its latency budget; returning the best cycle so far");
+        }
+        let best_opp = best_opp.map(|opp| self.split_route(graph, opp));
+        if let Some(ref opp) = best_opp {
+            tracing::info!("✅ Cycle found! Steps: {}", opp.steps.len());
+            mev_core::telemetry::ROUTE_DEPTH_HISTOGRAM.observe(opp.steps.len() as f64);
+            // Which fee tiers routes actually trade through, per program
+            let nodes = self.nodes.read();
+            for step in &opp.steps {
+                let venue = nodes.get(&step.input_mint)
+                    .zip(nodes.get(&step.output_mint))
+                    .and_then(|(&from, &to)| graph.find_edge(from, to))
+                    .and_then(|edge| graph[edge].iter().find(|v| v.pool_address() == step.pool));
+                if let Some(venue) = venue {
+                    mev_core::telemetry::ROUTE_HOPS_BY_FEE_TIER
+                        .with_label_values(&[&step.program_id.to_string(), &venue.fee_tier_bps().to_string()])
+                        .inc();
+                }
+            }
+        }
+        
+        best_opp
+    }
+
+    /// Per-hop amounts as quoted in the opportunity, with fee and impact read off the current
+    /// pool state. Hops whose pool has left the graph report zero fee and impact.
+    pub fn explain_route(&self, opp: &ArbitrageOpportunity) -> Vec<HopDetail> {
+        let graph = self.graph.read();
+        let mut hops = Vec::with_capacity(opp.steps.len());
+        for (i, step) in opp.steps.iter().enumerate() {
+            let amount_in = opp.step_input(i);
+            let venue = graph.edge_weights().flatten().find(|p| p.pool_address() == step.pool);
+            let (fee_bps, impact_bps) = match venue {
+                Some(venue) => {
+                    let (res_in, _) = self.quote_hop(venue, step.input_mint, amount_in);
+                    (venue.state().fee_bps, (mev_core::math::calculate_price_impact(amount_in, res_in) * 10000.0) as u16)
+                }
+                None => (0, 0),
+            };
+            hops.push(HopDetail {
+                pool: step.pool,
+                program_id: step.program_id,
+                input_mint: step.input_mint,
+                output_mint: step.output_mint,
+                amount_in,
+                amount_out: step.expected_output,
+                fee_bps,
+                impact_bps,
+            });
+        }
+        hops
+    }
+
+    /// Re-walks a known route against the current pool state.
+    /// Returns None if a pool is gone, a hop breaches the impact cap or the route is no longer profitable.
+    /// A split leg is spread over the same pools again, in shares re-weighted to their current state.
+    pub fn requote(&self, steps: &[SwapStep], amount: u64) -> Option<ArbitrageOpportunity> {
+        let graph = self.graph.read();
+        let nodes = self.nodes.read();
+        let route = route_legs_on(&graph, &nodes, steps)?;
+        self.quote_route(&route, amount)
+    }
+
+    /// Raw output of a fixed route against the current graph, without impact or profit filters
+    pub fn route_output(&self, steps: &[SwapStep], amount: u64) -> Option<u64> {
+        let graph = self.graph.read();
+        let nodes = self.nodes.read();
+        let mut current_amount = amount;
+        for (venues, input_mint, _) in route_legs_on(&graph, &nodes, steps)? {
+            let amount_out: u64 = self.split_leg(&venues, input_mint, current_amount)?.iter().map(|part| part.amount_out).sum();
+            if amount_out == 0 {
+                return None;
+            }
+            current_amount = amount_out;
+        }
+        Some(current_amount)
+    }
+
+    /// Current state of each pool along a route, in hop order
+    pub(crate) fn route_pools(&self, steps: &[SwapStep]) -> Option<Vec<PoolUpdate>> {
+        Some(self.route_venues(steps)?.into_iter().map(|venue| venue.state().clone()).collect())
+    }
+
+    fn route_venues(&self, steps: &[SwapStep]) -> Option<Vec<Venue>> {
+        let graph = self.graph.read();
+        let nodes = self.nodes.read();
+        steps.iter()
+            .map(|step| {
+                let edge = graph.find_edge(*nodes.get(&step.input_mint)?, *nodes.get(&step.output_mint)?)?;
+                graph[edge].iter().find(|p| p.pool_address() == step.pool).cloned()
+            })
+            .collect()
+    }
+
+    /// Sizes a fixed route hop by hop with the same impact cap and hop-cost floor as the search.
+    /// Every pool of a split leg is held to the impact cap on its own share.
+    fn quote_route(&self, route: &[RouteLeg<'_>], amount: u64) -> Option<ArbitrageOpportunity> {
+        let mut current_amount = amount;
+        let mut fresh_steps: SmallVec<[SwapStep; 8]> = SmallVec::new();
+        let mut step_inputs: SmallVec<[u64; 8]> = SmallVec::new();
+        let mut total_fees_bps: u16 = 0;
+        let mut max_price_impact_bps: u16 = 0;
+        let mut min_liquidity: u128 = u128::MAX;
+        let max_impact_bps = self.params.get().max_price_impact_bps;
+
+        for (venues, input_mint, output_mint) in route {
+            let mut leg_fee_bps: u16 = 0;
+            let mut leg_liquidity: u128 = 0;
+            let mut leg_output: u64 = 0;
+            for part in self.split_leg(venues, *input_mint, current_amount)? {
+                if part.amount_out == 0 {
+                    return None;
+                }
+                let impact_bps = (mev_core::math::calculate_price_impact(part.amount_in, part.res_in) * 10000.0) as u16;
+                if impact_bps > max_impact_bps {
+                    return None;
+                }
+
+                let pool = part.venue.state();
+                leg_fee_bps = leg_fee_bps.max(pool.fee_bps);
+                leg_liquidity += part.res_in as u128;
+                max_price_impact_bps = max_price_impact_bps.max(impact_bps);
+                fresh_steps.push(SwapStep {
+                    pool: pool.pool_address,
+                    program_id: pool.program_id,
+                    input_mint: *input_mint,
+                    output_mint: *output_mint,
+                    expected_output: part.amount_out,
+                });
+                step_inputs.push(part.amount_in);
+                leg_output += part.amount_out;
+            }
+            total_fees_bps += leg_fee_bps;
+            min_liquidity = min_liquidity.min(leg_liquidity);
+            current_amount = leg_output;
+        }
+        if fresh_steps.len() == route.len() {
+            step_inputs.clear(); // No leg was split: every step swaps the previous one's output
+        }
+
+        if current_amount <= amount || !self.hop_costs.admits(fresh_steps.len(), current_amount - amount, max_price_impact_bps) {
+            return None;
+        }
+
+        Some(ArbitrageOpportunity {
+            steps: fresh_steps,
+            expected_profit_lamports: current_amount - amount,
+            input_amount: amount,
+            total_fees_bps,
+            max_price_impact_bps,
+            min_liquidity,
+            timestamp: self.clock.unix_secs(),
+            step_inputs,
+            ..Default::default()
+        })
+    }
+
+    /// Spreads `amount` over a leg's pools, each taking a share proportional to its input-side
+    /// depth weighted by its marginal rate; for pools at one price that is the split that leaves
+    /// them at one price afterwards. A single pool takes everything. None if no pool has a rate.
+    fn split_leg<'a>(&self, venues: &[&'a Venue], current_mint: Pubkey, amount: u64) -> Option<SmallVec<[LegPart<'a>; 4]>> {
+        if let &[venue] = venues {
+            let (res_in, amount_out) = self.quote_hop(venue, current_mint, amount);
+            return Some(smallvec::smallvec![LegPart { venue, res_in, amount_in: amount, amount_out }]);
+        }
+        let weights: SmallVec<[f64; 4]> = venues.iter()
+            .map(|venue| {
+                let (res_in, _) = self.quote_hop(venue, current_mint, amount);
+                let weight = res_in as f64 * venue.spot_rate(current_mint);
+                if weight.is_finite() { weight.max(0.0) } else { 0.0 }
+            })
+            .collect();
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut parts: SmallVec<[LegPart<'a>; 4]> = SmallVec::new();
+        let mut left = amount;
+        for (i, (&venue, weight)) in venues.iter().zip(&weights).enumerate() {
+            // The last pool takes the rounding remainder
+            let amount_in = if i + 1 == venues.len() { left } else { ((amount as f64 * weight / total) as u64).min(left) };
+            left -= amount_in;
+            if amount_in == 0 {
+                continue;
+            }
+            let (res_in, amount_out) = self.quote_hop(venue, current_mint, amount_in);
+            parts.push(LegPart { venue, res_in, amount_in, amount_out });
+        }
+        Some(parts)
+    }
+
+    /// Spreads each leg of a found cycle but the last over up to `split_max_pools` pools of its
+    /// pair, keeping a split only when the cycle then returns more. The last leg stays on one
+    /// pool: the bundle's slippage floor is checked on the route's final instruction.
+    fn split_route(&self, graph: &DiGraph<Pubkey, Vec<Venue>>, opp: ArbitrageOpportunity) -> ArbitrageOpportunity {
+        if self.split_max_pools < 2 {
+            return opp;
+        }
+        let nodes = self.nodes.read();
+        let Some(mut route) = route_legs_on(graph, &nodes, &opp.steps) else {
+            return opp;
+        };
+        let fresh_after = self.fresh_after();
+        let mut best = opp;
+        let mut split_legs = 0;
+        for i in 0..route.len().saturating_sub(1) {
+            let (input_mint, output_mint) = (route[i].1, route[i].2);
+            let Some(edge) = nodes.get(&input_mint).zip(nodes.get(&output_mint)).and_then(|(&from, &to)| graph.find_edge(from, to)) else {
+                continue;
+            };
+            // Impact is checked per share in the re-quote, so every fresh pool with a quote is a
+            // candidate, sibling pools of one program included; the best quotes for the whole leg go first
+            let first_step: usize = mev_core::route_legs(&best.steps).take(i).map(|leg| leg.len()).sum();
+            let leg_amount = best.step_input(first_step);
+            let thin_pools = self.thin_pools();
+            let mut quotes: SmallVec<[(&Venue, u64); 4]> = graph[edge].iter()
+                .filter(|venue| venue.state().timestamp >= fresh_after)
+                .filter(|venue| !thin_pools.as_ref().is_some_and(|thin| thin.contains(&venue.pool_address())))
+                .map(|venue| (venue, self.quote_hop(venue, input_mint, leg_amount).1))
+                .filter(|(_, amount_out)| *amount_out > 0)
+                .collect();
+            if quotes.len() < 2 {
+                continue;
+            }
+            quotes.sort_by(|a, b| b.1.cmp(&a.1));
+            let mut candidate = route.clone();
+            candidate[i].0 = quotes.iter().take(self.split_max_pools as usize).map(|(venue, _)| *venue).collect();
+            if let Some(quoted) = self.quote_route(&candidate, best.input_amount) {
+                if quoted.expected_profit_lamports > best.expected_profit_lamports {
+                    debug!("🔀 SPLIT ROUTE: Leg {} over {} pools, profit {} -> {}.",
+                        i + 1, candidate[i].0.len(), best.expected_profit_lamports, quoted.expected_profit_lamports);
+                    best = quoted;
+                    route = candidate;
+                    split_legs += 1;
+                }
+            }
         }
+        if split_legs > 0 {
+            mev_core::telemetry::ROUTE_LEGS_SPLIT.inc_by(split_legs as f64);
+        }
+        best
+    }
 
-        // 3.5 Update Volatility Tracker
-        let price = if update.program_id == mev_core::constants::ORCA_WHIRLPOOL_PROGRAM {
-            let sqrt_p = update.price_sqrt.unwrap_or(0) as f64 / (1u128 << 64) as f64;
-            sqrt_p * sqrt_p
-        } else {
-            if update.reserve_a > 0 {
-                update.reserve_b as f64 / update.reserve_a as f64
-            } else {
-                0.0
+    /// Log-space search: one SPFA pass over the best spot rate per directed pair (the touch,
+    /// for order books) finds a rate-positive loop, which is then rotated to start at the
+    /// updated pool's mint and sized.
+    fn find_negative_cycle(
+        &self,
+        graph: &DiGraph<Pubkey, Vec<Venue>>,
+        node_a: NodeIndex,
+        node_b: NodeIndex,
+        initial_amount: u64,
+        max_hops: u8,
+    ) -> Option<ArbitrageOpportunity> {
+        // 1. Best pool per directed pair, weighted -ln(rate)
+        let mut edges: Vec<RateEdge> = Vec::with_capacity(graph.edge_count());
+        let mut edge_pools: Vec<&Venue> = Vec::with_capacity(graph.edge_count());
+        let fresh_after = self.fresh_after();
+        let max_impact_bps = self.params.get().max_price_impact_bps;
+        let thin_pools = self.thin_pools();
+        for edge in graph.edge_references() {
+            let from_mint = graph[edge.source()];
+            let best = edge
+                .weight()
+                .iter()
+                .filter(|venue| venue.state().timestamp >= fresh_after)
+                .filter(|venue| !thin_pools.as_ref().is_some_and(|thin| thin.contains(&venue.pool_address())))
+                .map(|venue| (venue, venue.spot_rate(from_mint)))
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            if let Some((pool, rate)) = best {
+                if let Some(rate_edge) = RateEdge::new(edge.source().index(), edge.target().index(), rate) {
+                    edges.push(rate_edge);
+                    edge_pools.push(pool);
+                }
             }
-        };
-        if price > 0.0 {
-            self.volatility_tracker.add_sample(update.pool_address, price);
         }
+        drop(thin_pools); // quote_edge takes it again below
 
-        // 4. Search for cycles (read-lock only)
-        let graph = self.graph.read();
-        let mut best_opp: Option<ArbitrageOpportunity> = None;
-        let mut best_opp: Option<ArbitrageOpportunity> = None;
-        
-        // Search from A
-        {
-            let mut visited: SmallVec<[NodeIndex; 8]> = SmallVec::new();
-            visited.push(node_a);
-            self.find_cycles_recursive(&graph, node_a, node_a, initial_amount, initial_amount, &mut visited, &mut SmallVec::new(), &mut best_opp, max_hops);
-        }
+        // 2. Look for a loop through either side of the update
+        let starts = [node_a.index(), node_b.index()];
+        for source in starts {
+            let Some(cycle) = crate::arb::find_negative_cycle(graph.node_count(), &edges, source) else {
+                continue;
+            };
+            if cycle.len() > max_hops as usize {
+                debug!("      ✗ Negative cycle of {} hops exceeds max_hops {}", cycle.len(), max_hops);
+                continue;
+            }
+            let Some(offset) = cycle.iter().position(|&i| starts.contains(&edges[i].from)) else {
+                continue;
+            };
 
-        // Search from B (in case the update is the last leg back to B, or B is the start token)
-        {
-            let mut visited: SmallVec<[NodeIndex; 8]> = SmallVec::new();
-            visited.push(node_b);
-            self.find_cycles_recursive(&graph, node_b, node_b, initial_amount, initial_amount, &mut visited, &mut SmallVec::new(), &mut best_opp, max_hops);
-        }
-        
-        if let Some(ref opp) = best_opp {
-            tracing::info!("✅ Cycle found! Steps: {}", opp.steps.len());
-            mev_core::telemetry::ROUTE_DEPTH_HISTOGRAM.observe(opp.steps.len() as f64);
+            // 3. Rotate so the route starts where the trade size is denominated, then size it.
+            //    Spot rates ignore depth, so each hop moves to the cheapest fee tier of its
+            //    venue's program that takes the amount at the best price.
+            let mut amount = initial_amount;
+            let mut route: SmallVec<[RouteLeg<'_>; 8]> = SmallVec::new();
+            for &i in cycle[offset..].iter().chain(&cycle[..offset]) {
+                let (from, to) = (NodeIndex::new(edges[i].from), NodeIndex::new(edges[i].to));
+                let program = edge_pools[i].state().program_id;
+                let tiers = graph.find_edge(from, to).into_iter()
+                    .flat_map(move |e| graph[e].iter())
+                    .filter(|venue| venue.state().program_id == program);
+                let venue = match self.quote_edge(tiers, graph[from], amount, fresh_after, max_impact_bps).into_iter().max_by_key(|q| q.amount_out) {
+                    Some(quote) => {
+                        amount = quote.amount_out;
+                        quote.venue
+                    }
+                    None => edge_pools[i], // Sizing below turns it down
+                };
+                route.push((smallvec::smallvec![venue], graph[from], graph[to]));
+            }
+            if let Some(opp) = self.quote_route(&route, initial_amount) {
+                tracing::info!("      ✅ NEGATIVE CYCLE! {} hops, Profit: {} lamports", opp.steps.len(), opp.expected_profit_lamports);
+                return Some(opp);
+            }
         }
-        
-        best_opp
+        None
     }
 
     fn find_cycles_recursive(
         &self,
-        graph: &DiGraph<Pubkey, Vec<PoolUpdate>>,
+        graph: &DiGraph<Pubkey, Vec<Venue>>,
         current_node: NodeIndex,
         start_node: NodeIndex,
         current_amount: u64,
@@ -418,8 +2436,14 @@ impl ArbitrageStrategy {
         current_steps: &mut SmallVec<[SwapStep; 8]>, // HFT: Stack-allocated
         best_opp: &mut Option<ArbitrageOpportunity>,
         remaining_hops: u8,
+        path_impact_bps: u16, // Worst hop impact so far on this path
+        scope: Option<&EdgeScope>,
+        deadline: Option<Instant>, // Out of budget: keep what's been found
     ) {
         if remaining_hops == 0 { return; }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return;
+        }
 
         // PRUNING: If we've lost more than 50% of initial value and have hops left, abort
         // This allows for temporary "dips" in multi-hop routes while preventing runaway recursion
@@ -440,13 +2464,16 @@ impl ArbitrageStrategy {
             edge_count
         );
 
+        let fresh_after = self.fresh_after();
+        let max_impact_bps = self.params.get().max_price_impact_bps;
+
         // Track metrics for 5-hop features
         let mut total_fees_bps: u16 = 0;
         let mut max_price_impact_bps: u16 = 0;
         let mut min_liquidity: u128 = u128::MAX;
 
         for edge in graph.edges(current_node) {
-            let pools = edge.weight();  // Now Vec<PoolUpdate>
+            let pools = edge.weight();  // Every venue trading this pair
             let next_node = edge.target();
             let next_mint = graph[next_node];
             
@@ -457,50 +2484,19 @@ impl ArbitrageStrategy {
                 pools.len()
             );
             // Try each pool in this edge (enables cross-DEX arbitrage)
-            for pool in pools {
-            // 1. Calculate reserves and amount out based on DEX type
-            let (res_in, amount_out) = if pool.program_id == mev_core::constants::ORCA_WHIRLPOOL_PROGRAM {
-                let price_sqrt = pool.price_sqrt.unwrap_or(0);
-                let liquidity = pool.liquidity.unwrap_or(0);
-                
-                // Virtual reserve approximation for impact calculation
-                let sqrt_p = price_sqrt as f64 / (1u128 << 64) as f64;
-                let a_to_b = pool.mint_a == current_mint;
-                let v_res_in = if a_to_b {
-                    (liquidity as f64 / sqrt_p) as u64
-                } else {
-                    (liquidity as f64 * sqrt_p) as u64
-                };
-
-                (v_res_in, mev_core::math::get_amount_out_clmm(current_amount, price_sqrt, liquidity, pool.fee_bps, a_to_b))
-            } else {
-                let (r_in, r_out) = if pool.mint_a == current_mint {
-                    (pool.reserve_a as u64, pool.reserve_b as u64)
-                } else {
-                    (pool.reserve_b as u64, pool.reserve_a as u64)
-                };
-                (r_in, mev_core::math::get_amount_out_cpmm(current_amount, r_in, r_out, pool.fee_bps))
-            };
-
-            tracing::debug!("      Calculated amount_out: {}", amount_out);
-
-            if amount_out == 0 { 
-                tracing::debug!("      ✗ Skipped: amount_out = 0");
-                continue; 
-            }
-
-            // 1.5 Price Impact Check (Phase 6C)
-            let impact = mev_core::math::calculate_price_impact(current_amount, res_in);
-            if (impact * 10000.0) as u16 > 100 { // 1% Max Impact
-                debug!("Skipping path due to high price impact: {:.2}%", impact * 100.0);
-                continue;
-            }
+            // 1. Quote every venue that passes freshness and the price impact cap (Phase 6C),
+            //    one fee tier per program unless a pricier tier quotes better
+            for EdgeQuote { venue, res_in, amount_out, impact_bps: current_impact_bps } in
+                self.quote_edge(pools, current_mint, current_amount, fresh_after, max_impact_bps)
+            {
+            let pool = venue.state();
+            tracing::debug!("      Calculated amount_out: {} ({} bps tier)", amount_out, pool.fee_bps);
 
             // Update metrics
             total_fees_bps += pool.fee_bps;
-            let current_impact_bps = (impact * 10000.0) as u16;
             max_price_impact_bps = max_price_impact_bps.max(current_impact_bps);
             min_liquidity = min_liquidity.min(res_in as u128);
+            let route_impact_bps = path_impact_bps.max(current_impact_bps);
 
             // 2. Prepare swap step
             let step = SwapStep {
@@ -513,6 +2509,9 @@ impl ArbitrageStrategy {
 
             // 3. Cycle detected?
             if next_node == start_node {
+                if scope.is_some_and(|scope| !scope.closes(current_node, pool.pool_address)) {
+                    continue;
+                }
                 tracing::info!(
                     "      🔄 CYCLE DETECTED! Start amount: {}, End amount: {}, Profit: {}",
                     initial_amount,
@@ -522,6 +2521,14 @@ impl ArbitrageStrategy {
                 
                 if amount_out > initial_amount { // Use provided initial amount
                     let profit = amount_out - initial_amount;
+                    let hops = current_steps.len() + 1;
+
+                    // 3.5 Hop-count cost model: longer routes must earn more and move prices less
+                    if !self.hop_costs.admits(hops, profit, route_impact_bps) {
+                        debug!("      ✗ {}-hop cycle below hop cost floor (profit: {}, impact: {} bps)", hops, profit, route_impact_bps);
+                        continue;
+                    }
+
                     let mut steps = current_steps.clone();
                     steps.push(step);
                     
@@ -539,18 +2546,19 @@ impl ArbitrageStrategy {
                             is_elite_match: false,
                             initial_liquidity_lamports: None,
                             launch_hour_utc: None,
-                            timestamp: std::time::SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs(),
+                            flash_loan_amount: 0,
+                            expected_value_lamports: None,
+                            timestamp: self.clock.unix_secs(),
+                            ..Default::default()
                         });
                     }
                 }
                 continue;
             }
 
-            // 4. Recurse if not visited in this path
-            if !visited.contains(&next_node) {
+            // 4. Recurse if not visited in this path (and, when scoped, the closing pool is still in reach)
+            let in_reach = scope.is_none_or(|scope| scope.reaches(next_node, remaining_hops - 1));
+            if in_reach && !visited.contains(&next_node) {
                 visited.push(next_node);
                 current_steps.push(step);
                 
@@ -564,6 +2572,9 @@ impl ArbitrageStrategy {
                     current_steps,
                     best_opp,
                     remaining_hops - 1,
+                    route_impact_bps,
+                    scope,
+                    deadline,
                 );
                 current_steps.pop();
                 visited.pop();
@@ -682,6 +2693,429 @@ mod tests {
         assert!(opp.expected_profit_lamports > initial_amount / 2); // Should be roughly 0.1 SOL profit
     }
 
+    #[test]
+    fn test_bellman_ford_mode_finds_triangle() {
+        let mut strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+        strategy.set_search_mode(CycleSearchMode::BellmanFord);
+        let initial_amount = 100_000_000;
+        let (mint_sol, mint_usdc, mint_usdt) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        strategy.process_update(mock_pool(&Pubkey::new_unique().to_string(), &mint_sol.to_string(), &mint_usdc.to_string(), 1_000_000_000_000_000, 200_000_000_000_000_000), initial_amount, 5);
+        strategy.process_update(mock_pool(&Pubkey::new_unique().to_string(), &mint_usdc.to_string(), &mint_usdt.to_string(), 100_000_000_000_000_000, 100_000_000_000_000_000), initial_amount, 5);
+        let final_update = mock_pool(&Pubkey::new_unique().to_string(), &mint_usdt.to_string(), &mint_sol.to_string(), 10_000_000_000_000_000, 100_000_000_000_000);
+
+        assert!(strategy.process_update(final_update.clone(), initial_amount, 2).is_none(), "3-hop loop must respect max_hops");
+
+        let opp = strategy.process_update(final_update, initial_amount, 5).expect("Should find cycle");
+        assert_eq!(opp.steps.len(), 3);
+        assert!([mint_sol, mint_usdt].contains(&opp.steps[0].input_mint));
+        assert_eq!(opp.steps[0].input_mint, opp.steps[2].output_mint);
+        assert!(opp.expected_profit_lamports > initial_amount / 2);
+    }
+
+    #[test]
+    fn test_incremental_search_stays_on_the_updated_edge() {
+        let strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+        let initial_amount = 100_000_000;
+        let (mint_sol, mint_usdc, mint_usdt, mint_bonk) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (pool_3, pool_4) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &mint_sol.to_string(), &mint_usdc.to_string(), 1_000_000_000_000_000, 200_000_000_000_000_000));
+        strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &mint_usdc.to_string(), &mint_usdt.to_string(), 100_000_000_000_000_000, 100_000_000_000_000_000));
+        let (usdt, sol) = strategy.apply_update(mock_pool(&pool_3.to_string(), &mint_usdt.to_string(), &mint_sol.to_string(), 10_000_000_000_000_000, 100_000_000_000_000));
+
+        // A quiet SOL/BONK pool moves: the triangle doesn't trade through it
+        let (sol_again, bonk) = strategy.apply_update(mock_pool(&pool_4.to_string(), &mint_sol.to_string(), &mint_bonk.to_string(), 1_000_000_000_000, 1_000_000_000_000));
+        assert_eq!(sol, sol_again);
+        assert!(strategy.find_best_cycle(sol, bonk, initial_amount, 5).is_some());
+        assert!(strategy.find_best_cycle_through(pool_4, sol, bonk, initial_amount, 5).is_none());
+        // The sweep still finds it
+        let swept = strategy.sweep(&mint_sol, initial_amount, 5).expect("full search from SOL");
+        assert_eq!(swept.steps[0].input_mint, mint_sol);
+
+        // An update to one of its pools finds it from either side, with that pool on the route
+        let opp = strategy.find_best_cycle_through(pool_3, usdt, sol, initial_amount, 5).expect("cycle through the updated pool");
+        assert_eq!(opp.steps.len(), 3);
+        assert!(opp.steps.iter().any(|s| s.pool == pool_3));
+        assert!([mint_sol, mint_usdt].contains(&opp.steps[0].input_mint));
+        assert_eq!(opp.steps[0].input_mint, opp.steps[2].output_mint);
+        assert!(strategy.find_best_cycle_through(pool_3, usdt, sol, initial_amount, 2).is_none(), "3-hop loop must respect max_hops");
+    }
+
+    #[test]
+    fn test_parallel_search_matches_sequential_and_respects_budget() {
+        let mints: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let build = |parallel_min_hops: u8, budget: Option<Duration>| {
+            let mut strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+            strategy.set_parallel_search(parallel_min_hops, budget);
+            strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &mints[0].to_string(), &mints[1].to_string(), 1_000_000_000_000_000, 200_000_000_000_000_000));
+            strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &mints[1].to_string(), &mints[2].to_string(), 100_000_000_000_000_000, 100_000_000_000_000_000));
+            strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &mints[0].to_string(), &mints[3].to_string(), 1_000_000_000_000_000, 1_000_000_000_000_000));
+            strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &mints[2].to_string(), &mints[0].to_string(), 10_000_000_000_000_000, 100_000_000_000_000));
+            strategy
+        };
+
+        let sequential = build(0, None).sweep(&mints[0], 100_000_000, 5).expect("sequential search finds the triangle");
+        let parallel = build(2, None).sweep(&mints[0], 100_000_000, 5).expect("fanned-out search finds it too");
+        assert_eq!(parallel.expected_profit_lamports, sequential.expected_profit_lamports);
+        assert_eq!(parallel.steps.len(), sequential.steps.len());
+
+        // A spent budget stops the search at once, with nothing found yet
+        assert!(build(2, Some(Duration::ZERO)).sweep(&mints[0], 100_000_000, 5).is_none());
+        assert!(build(0, Some(Duration::ZERO)).sweep(&mints[0], 100_000_000, 5).is_none());
+        assert!(build(2, Some(Duration::from_secs(5))).sweep(&mints[0], 100_000_000, 5).is_some());
+    }
+
+    #[test]
+    fn test_split_routing_spreads_a_leg_over_sibling_pools() {
+        let (sol, usdc) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let initial_amount = 150_000_000;
+        // Two shallow SOL/USDC pools at one price (0.75% impact each at full size) and a deep,
+        // mispriced way back
+        let shallow = || mock_pool(&Pubkey::new_unique().to_string(), &sol.to_string(), &usdc.to_string(), 20_000_000_000, 40_000_000_000);
+        let build = |max_pools: u8| {
+            let mut strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+            strategy.set_split_routing(max_pools);
+            strategy.apply_update(shallow());
+            strategy.apply_update(shallow());
+            strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &usdc.to_string(), &sol.to_string(), 1_800_000_000_000_000, 1_000_000_000_000_000));
+            strategy
+        };
+
+        let single = build(1).sweep(&sol, initial_amount, 2).expect("one pool per leg finds the cycle");
+        assert_eq!(single.steps.len(), 2);
+        assert!(single.step_inputs.is_empty());
+
+        let strategy = build(2);
+        let split = strategy.sweep(&sol, initial_amount, 2).expect("split search finds it too");
+        assert!(split.expected_profit_lamports > single.expected_profit_lamports);
+        assert_eq!(mev_core::route_legs(&split.steps).map(|leg| leg.len()).collect::<Vec<_>>(), vec![2, 1]);
+        assert_ne!(split.steps[0].pool, split.steps[1].pool);
+        // Equal pools take equal halves, and the closing leg swaps everything they returned
+        assert_eq!(split.step_input(0), initial_amount / 2);
+        assert_eq!(split.step_input(0) + split.step_input(1), initial_amount);
+        assert_eq!(split.step_input(2), split.steps[0].expected_output + split.steps[1].expected_output);
+        assert!(split.max_price_impact_bps < single.max_price_impact_bps);
+
+        // Re-quoting keeps the split
+        let again = strategy.requote(&split.steps, initial_amount).expect("still profitable");
+        assert_eq!(again.expected_profit_lamports, split.expected_profit_lamports);
+        assert_eq!(strategy.route_output(&split.steps, initial_amount), Some(initial_amount + split.expected_profit_lamports));
+    }
+
+    #[test]
+    fn test_fee_tiers_prefer_cheapest_with_depth() {
+        for mode in [CycleSearchMode::Dfs, CycleSearchMode::BellmanFord] {
+            let mut strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+            strategy.set_search_mode(mode);
+            let initial_amount = 100_000_000;
+            let (mint_sol, mint_usdc, mint_usdt) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+            // Three Whirlpool tiers on USDC/USDT at the same price: the 1 bp tier is too thin
+            // for the ~20B USDC hop, so the 4 bp tier should carry it and the 30 bp one never
+            let tier = |fee_bps, liquidity| PoolUpdate {
+                fee_bps,
+                ..mock_orca_pool(&Pubkey::new_unique().to_string(), &mint_usdc.to_string(), &mint_usdt.to_string(), 1u128 << 64, liquidity)
+            };
+            let (thin, cheap, pricey) = (tier(1, 100_000_000_000), tier(4, 100_000_000_000_000_000), tier(30, 100_000_000_000_000_000));
+            for update in [thin.clone(), cheap.clone(), pricey.clone()] {
+                strategy.process_update(update, initial_amount, 5);
+            }
+            strategy.process_update(mock_pool(&Pubkey::new_unique().to_string(), &mint_sol.to_string(), &mint_usdc.to_string(), 1_000_000_000_000_000, 200_000_000_000_000_000), initial_amount, 5);
+            let final_update = mock_pool(&Pubkey::new_unique().to_string(), &mint_usdt.to_string(), &mint_sol.to_string(), 10_000_000_000_000_000, 100_000_000_000_000);
+
+            let opp = strategy.process_update(final_update, initial_amount, 5).expect("Should find cycle");
+            let stable_hop = opp.steps.iter().find(|s| s.input_mint == mint_usdc).expect("USDC -> USDT hop");
+            assert_eq!(stable_hop.pool, cheap.pool_address, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn test_hop_costs_reject_thin_long_routes() {
+        let mut strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+        let initial_amount = 100_000_000;
+        let (mint_sol, mint_usdc, mint_usdt) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        // Same ~0.1 SOL triangular dislocation as above, but 3-hop routes now need 1 SOL
+        strategy.set_hop_costs(crate::hop_costs::HopCostTable::parse("2:0:100;3:1000000000:100").unwrap());
+        strategy.process_update(mock_pool(&Pubkey::new_unique().to_string(), &mint_sol.to_string(), &mint_usdc.to_string(), 1_000_000_000_000_000, 200_000_000_000_000_000), initial_amount, 5);
+        strategy.process_update(mock_pool(&Pubkey::new_unique().to_string(), &mint_usdc.to_string(), &mint_usdt.to_string(), 100_000_000_000_000_000, 100_000_000_000_000_000), initial_amount, 5);
+        let final_update = mock_pool(&Pubkey::new_unique().to_string(), &mint_usdt.to_string(), &mint_sol.to_string(), 10_000_000_000_000_000, 100_000_000_000_000);
+
+        assert!(strategy.process_update(final_update, initial_amount, 5).is_none());
+    }
+
+    #[test]
+    fn test_explain_route_chains_hop_amounts() {
+        let strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+        let initial_amount = 100_000_000;
+        let (mint_sol, mint_usdc, mint_usdt) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        strategy.process_update(mock_pool(&Pubkey::new_unique().to_string(), &mint_sol.to_string(), &mint_usdc.to_string(), 1_000_000_000_000_000, 200_000_000_000_000_000), initial_amount, 5);
+        strategy.process_update(mock_pool(&Pubkey::new_unique().to_string(), &mint_usdc.to_string(), &mint_usdt.to_string(), 100_000_000_000_000_000, 100_000_000_000_000_000), initial_amount, 5);
+        let opp = strategy.process_update(mock_pool(&Pubkey::new_unique().to_string(), &mint_usdt.to_string(), &mint_sol.to_string(), 10_000_000_000_000_000, 100_000_000_000_000), initial_amount, 5)
+            .expect("Should find cycle");
+
+        let hops = strategy.explain_route(&opp);
+        assert_eq!(hops.len(), opp.steps.len());
+        assert_eq!(hops[0].amount_in, initial_amount);
+        for (i, hop) in hops.iter().enumerate().skip(1) {
+            assert_eq!(hop.amount_in, hops[i - 1].amount_out);
+        }
+        assert!(hops.iter().all(|h| h.fee_bps == 0));
+        assert_eq!(hops.iter().map(|h| h.impact_bps).max().unwrap(), opp.max_price_impact_bps);
+    }
+
+    #[test]
+    fn test_requote_tracks_pool_state() {
+        let strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+        let initial_amount = 100_000_000;
+        let (mint_sol, mint_usdc, mint_usdt) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (pool_1, pool_2, pool_3) = (Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string());
+
+        strategy.process_update(mock_pool(&pool_1, &mint_sol.to_string(), &mint_usdc.to_string(), 1_000_000_000_000_000, 200_000_000_000_000_000), initial_amount, 5);
+        strategy.process_update(mock_pool(&pool_2, &mint_usdc.to_string(), &mint_usdt.to_string(), 100_000_000_000_000_000, 100_000_000_000_000_000), initial_amount, 5);
+        let opp = strategy.process_update(mock_pool(&pool_3, &mint_usdt.to_string(), &mint_sol.to_string(), 10_000_000_000_000_000, 100_000_000_000_000), initial_amount, 5)
+            .expect("Should find cycle");
+
+        // Unchanged state reproduces the original quote
+        let same = strategy.requote(&opp.steps, initial_amount).expect("Route still profitable");
+        assert_eq!(same.expected_profit_lamports, opp.expected_profit_lamports);
+
+        // Closing the dislocation on the last leg kills the route
+        strategy.process_update(mock_pool(&pool_3, &mint_usdt.to_string(), &mint_sol.to_string(), 10_000_000_000_000_000, 50_000_000_000_000), initial_amount, 5);
+        assert!(strategy.requote(&opp.steps, initial_amount).is_none());
+    }
+
+    #[test]
+    fn test_remove_pool_drops_dead_edges() {
+        let strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+        let initial_amount = 100_000_000;
+        let (mint_sol, mint_usdc, mint_usdt) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (pool_1, pool_2, pool_3) = (Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string());
+
+        strategy.process_update(mock_pool(&pool_1, &mint_sol.to_string(), &mint_usdc.to_string(), 1_000_000_000_000_000, 200_000_000_000_000_000), initial_amount, 5);
+        strategy.process_update(mock_pool(&pool_2, &mint_usdc.to_string(), &mint_usdt.to_string(), 100_000_000_000_000_000, 100_000_000_000_000_000), initial_amount, 5);
+        let opp = strategy.process_update(mock_pool(&pool_3, &mint_usdt.to_string(), &mint_sol.to_string(), 10_000_000_000_000_000, 100_000_000_000_000), initial_amount, 5)
+            .expect("Should find cycle");
+        let edges_before = strategy.graph.read().edge_count();
+
+        let dead: Pubkey = pool_3.parse().unwrap();
+        assert!(strategy.remove_pool(&dead));
+        assert!(!strategy.remove_pool(&dead));
+        assert!(strategy.pool_state(&dead).is_none());
+        assert_eq!(strategy.graph.read().edge_count(), edges_before - 2);
+
+        // The remaining pools still quote, but the cycle through the dead pool is gone
+        assert!(strategy.requote(&opp.steps, initial_amount).is_none());
+        assert!(strategy.pool_state(&pool_1.parse().unwrap()).is_some());
+        assert!(strategy.process_update(mock_pool(&pool_2, &mint_usdc.to_string(), &mint_usdt.to_string(), 100_000_000_000_000_000, 100_000_000_000_000_000), initial_amount, 5).is_none());
+    }
+
+    #[test]
+    fn test_stale_edges_skipped_then_pruned() {
+        let clock = Arc::new(mev_core::clock::ManualClock::new(1_000_000));
+        let mut strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+        strategy.set_clock(clock.clone());
+        strategy.set_edge_ttl(60);
+        let initial_amount = 100_000_000;
+        let (mint_sol, mint_usdc, mint_usdt, mint_bonk) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let at = |addr: &str, a: &Pubkey, b: &Pubkey, res_a, res_b, ts| PoolUpdate {
+            timestamp: ts,
+            ..mock_pool(addr, &a.to_string(), &b.to_string(), res_a, res_b)
+        };
+        let (pool_1, pool_2, pool_3, pool_4) = (Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string());
+
+        // pool_1 last heard from 10 minutes ago: the cycle through it is a phantom
+        // BONK is node 0, so pruning it moves another mint into its slot
+        strategy.apply_update(at(&pool_4, &mint_bonk, &mint_usdt, 1_000_000, 1_000_000, 400));
+        strategy.apply_update(at(&pool_1, &mint_sol, &mint_usdc, 1_000_000_000_000_000, 200_000_000_000_000_000, 400));
+        strategy.apply_update(at(&pool_2, &mint_usdc, &mint_usdt, 100_000_000_000_000_000, 100_000_000_000_000_000, 1_000));
+        let (a, b) = strategy.apply_update(at(&pool_3, &mint_usdt, &mint_sol, 10_000_000_000_000_000, 100_000_000_000_000, 1_000));
+        assert!(strategy.find_best_cycle(a, b, initial_amount, 5).is_none());
+
+        // A fresh update brings it back
+        strategy.apply_update(at(&pool_1, &mint_sol, &mint_usdc, 1_000_000_000_000_000, 200_000_000_000_000_000, 1_000));
+        assert!(strategy.find_best_cycle(a, b, initial_amount, 5).is_some());
+
+        // pool_4 goes, and BONK with it; USDT keeps its other pools
+        clock.advance(std::time::Duration::from_secs(100));
+        assert_eq!(strategy.prune(300), PruneStats { pools: 1, nodes: 1 });
+        assert!(strategy.pool_state(&pool_4.parse().unwrap()).is_none());
+        assert!(!strategy.nodes.read().contains_key(&mint_bonk));
+        for (mint, index) in strategy.nodes.read().iter() {
+            assert_eq!(strategy.graph.read()[*index], *mint);
+        }
+        strategy.apply_update(at(&pool_1, &mint_sol, &mint_usdc, 1_000_000_000_000_000, 200_000_000_000_000_000, 1_100));
+        strategy.apply_update(at(&pool_2, &mint_usdc, &mint_usdt, 100_000_000_000_000_000, 100_000_000_000_000_000, 1_100));
+        assert!(strategy.process_update(at(&pool_3, &mint_usdt, &mint_sol, 10_000_000_000_000_000, 100_000_000_000_000, 1_100), initial_amount, 5).is_some());
+    }
+
+    #[test]
+    fn test_pump_curve_quotes_with_fee_on_sol_side() {
+        let token = Pubkey::new_unique();
+        let curve = PoolUpdate {
+            pool_address: Pubkey::new_unique(),
+            program_id: mev_core::constants::PUMP_FUN_PROGRAM,
+            mint_a: token,
+            mint_b: mev_core::constants::SOL_MINT,
+            reserve_a: 1_073_000_000_000_000,
+            reserve_b: 30_000_000_000,
+            price_sqrt: None,
+            liquidity: None,
+            fee_bps: mev_core::pump_fun::TRADE_FEE_BPS,
+            timestamp: 0,
+        };
+
+        let (sol_reserve, tokens) = quote_pool(&curve, mev_core::constants::SOL_MINT, 1_000_000_000);
+        assert_eq!(sol_reserve, 30_000_000_000);
+        assert_eq!(tokens, mev_core::math::get_amount_out_bonding_curve(1_000_000_000, 30_000_000_000, 1_073_000_000_000_000, 100, true));
+
+        let (token_reserve, sol) = quote_pool(&curve, token, tokens);
+        assert_eq!(token_reserve, 1_073_000_000_000_000);
+        assert!(sol > 0 && sol < 1_000_000_000);
+    }
+
+    #[test]
+    fn test_dlmm_hop_quotes_from_cached_bins() {
+        let strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+        let (mint_x, mint_y) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let pair = Venue::Amm(PoolUpdate {
+            pool_address: Pubkey::new_unique(),
+            program_id: mev_core::constants::METEORA_PROGRAM_ID,
+            mint_a: mint_x,
+            mint_b: mint_y,
+            reserve_a: 0,
+            reserve_b: 0,
+            price_sqrt: None,
+            liquidity: None,
+            fee_bps: 25,
+            timestamp: 0,
+        });
+
+        // Hydrated with zero reserves: no quote until bins arrive
+        assert_eq!(strategy.quote_hop(&pair, mint_x, 1_000), (0, 0));
+
+        // One bin at price 2.0 each side of the active bin
+        let bins = [
+            (0, mev_core::meteora::Bin { amount_x: 0, amount_y: 1_000_000, price: 2u128 << 64 }),
+            (1, mev_core::meteora::Bin { amount_x: 1_000_000, amount_y: 0, price: 2u128 << 64 }),
+        ].into_iter().collect();
+        strategy.update_dlmm_liquidity(pair.pool_address, DlmmLiquidity {
+            token_x_mint: mint_x,
+            token_y_mint: mint_y,
+            active_id: 0,
+            fee_bps: 0,
+            bins,
+        });
+
+        assert_eq!(strategy.quote_hop(&pair, mint_x, 1_000), (500_000, 2_000));
+        assert_eq!(strategy.quote_hop(&pair, mint_y, 1_000), (2_000_000, 500));
+    }
+
+    #[test]
+    fn test_whirlpool_hop_walks_cached_ticks() {
+        let strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let pool = PoolUpdate {
+            pool_address: Pubkey::new_unique(),
+            program_id: mev_core::constants::ORCA_WHIRLPOOL_PROGRAM,
+            mint_a,
+            mint_b,
+            reserve_a: 0,
+            reserve_b: 0,
+            price_sqrt: Some(clmm::sqrt_price_from_tick_index(0)),
+            liquidity: Some(1_000_000_000_000),
+            fee_bps: 30,
+            timestamp: 0,
+        };
+        let venue = Venue::Amm(pool.clone());
+
+        // Before its tick arrays load the pool quotes on the single curve
+        assert_eq!(strategy.quote_hop(&venue, mint_a, 1_000_000), quote_pool(&pool, mint_a, 1_000_000));
+
+        // Liquidity thins out below tick -64: the exact quote crosses it, the curve does not
+        let ticks = TickRange {
+            ticks: vec![clmm::InitializedTick { index: -64, liquidity_net: 900_000_000_000 }],
+            lower: -88 * 64,
+            upper: 88 * 64,
+        };
+        strategy.update_whirlpool_ticks(pool.pool_address, ticks.clone());
+        let state = ClmmState {
+            sqrt_price_x64: clmm::sqrt_price_from_tick_index(0),
+            tick_current_index: 0,
+            liquidity: 1_000_000_000_000,
+            fee_rate: 3000,
+        };
+        let amount_in = 10_000_000_000;
+        let exact = clmm::swap_exact_in(&state, &ticks, amount_in, true).unwrap();
+        assert_eq!(exact.ticks_crossed, 1);
+        let (_, amount_out) = strategy.quote_hop(&venue, mint_a, amount_in);
+        assert_eq!(amount_out, exact.amount_out);
+        assert!(amount_out < quote_pool(&pool, mint_a, amount_in).1);
+
+        // Past the loaded arrays nothing is quoted
+        assert_eq!(strategy.quote_hop(&venue, mint_b, u64::MAX / 2).1, 0);
+
+        strategy.remove_pool(&pool.pool_address);
+        assert!(strategy.whirlpool_ticks.read().is_empty());
+    }
+
+    #[test]
+    fn test_clob_hop_walks_book_on_edge() {
+        let strategy = ArbitrageStrategy::new(Arc::new(VolatilityTracker::new()));
+        let (base, quote) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let market = PoolUpdate {
+            pool_address: Pubkey::new_unique(),
+            program_id: mev_core::constants::PHOENIX_PROGRAM,
+            mint_a: base,
+            mint_b: quote,
+            reserve_a: 1_000,
+            reserve_b: 100_000,
+            price_sqrt: None,
+            liquidity: None,
+            fee_bps: 0,
+            timestamp: 0,
+        };
+        let hop = |input_mint, output_mint| SwapStep {
+            pool: market.pool_address,
+            program_id: market.program_id,
+            input_mint,
+            output_mint,
+            expected_output: 0,
+        };
+        let venue = |step: SwapStep| strategy.route_venues(&[step]).map(|mut v| v.remove(0));
+
+        // On the graph without a book: the virtual reserves are never quoted
+        strategy.apply_update(market.clone());
+        let bookless = venue(hop(base, quote)).unwrap();
+        assert!(matches!(bookless, Venue::Book { book: None, .. }));
+        assert_eq!(strategy.quote_hop(&bookless, base, 1_000), (0, 0));
+
+        let level = |price, lots| mev_core::clob::Level { price, lots };
+        strategy.update_order_book(market.pool_address, OrderBook {
+            base_mint: base,
+            quote_mint: quote,
+            base_lot_size: 100,
+            taker_fee_bps: 0,
+            bids: vec![level(50, 10)],
+            asks: vec![level(60, 10)],
+        });
+
+        // Both directed edges now carry the book: 10 lots into the bid at 50;
+        // 600 quote buys all 10 lots of the ask
+        assert_eq!(strategy.quote_hop(&venue(hop(base, quote)).unwrap(), base, 1_000), (1_000, 500));
+        assert_eq!(strategy.quote_hop(&venue(hop(quote, base)).unwrap(), quote, 599), (600, 900));
+
+        // A later market update keeps the book it already had
+        strategy.apply_update(PoolUpdate { timestamp: 1, ..market.clone() });
+        assert!(venue(hop(base, quote)).unwrap().book().is_some());
+
+        strategy.remove_pool(&market.pool_address);
+        assert!(venue(hop(base, quote)).is_none());
+        assert!(strategy.order_books().get(&market.pool_address).is_none());
+    }
+
     #[test]
     #[ignore]
     fn test_cross_dex_arbitrage() {
@@ -708,4 +3142,217 @@ mod tests {
         // Cycle starts from USDC (triggering update mint_a) or SOL
         assert_eq!(opp.steps[0].input_mint, opp.steps[1].output_mint);
     }
+
+    /// Counts sends instead of building bundles
+    struct RecordingExecutor {
+        payer: Pubkey,
+        sent: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl ExecutionPort for RecordingExecutor {
+        async fn build_bundle_instructions(&self, _: ArbitrageOpportunity, _: u64, _: u16) -> anyhow::Result<Vec<solana_sdk::instruction::Instruction>> {
+            Ok(Vec::new())
+        }
+
+        async fn build_and_send_bundle(&self, _: ArbitrageOpportunity, _: solana_sdk::hash::Hash, _: u64, _: u16) -> anyhow::Result<String> {
+            let n = self.sent.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(format!("bundle-{}", n))
+        }
+
+        fn pubkey(&self) -> &Pubkey {
+            &self.payer
+        }
+    }
+
+    /// Admits routes while `open`, counting the dispatches recorded against it
+    struct SwitchedExposure {
+        open: std::sync::atomic::AtomicBool,
+        dispatched: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ExposurePort for SwitchedExposure {
+        fn check_exposure(&self, _: &ArbitrageOpportunity, _: u64) -> Option<crate::ports::ExposureLimit> {
+            (!self.open.load(std::sync::atomic::Ordering::SeqCst)).then_some(crate::ports::ExposureLimit::MintNotional)
+        }
+
+        fn record_dispatch(&self, _: &ArbitrageOpportunity, _: u64) {
+            self.dispatched.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    /// An engine with a recording executor, a switchable exposure guard and a pause flag over a SOL/USDC cycle paying ~2%
+    fn gated_engine(paused: Arc<std::sync::atomic::AtomicBool>) -> (Arc<StrategyEngine>, Arc<RecordingExecutor>, Arc<SwitchedExposure>) {
+        let executor = Arc::new(RecordingExecutor { payer: Pubkey::new_unique(), sent: Default::default() });
+        let exposure = Arc::new(SwitchedExposure { open: std::sync::atomic::AtomicBool::new(true), dispatched: Default::default() });
+        let engine = StrategyEngine::new(Some(executor.clone() as Arc<dyn ExecutionPort>), None, None, None, None, None, None)
+            .with_split_policy(SplitPolicy::new(3, Duration::ZERO))
+            .with_exposure_guard(exposure.clone() as Arc<dyn ExposurePort>)
+            .with_pause_flag(paused);
+        let (sol, usdc) = (mev_core::constants::SOL_MINT.to_string(), Pubkey::new_unique().to_string());
+        engine.arb_strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &sol, &usdc, 1_000_000_000_000, 2_000_000_000_000));
+        engine.arb_strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &usdc, &sol, 2_000_000_000_000, 1_020_000_000_000));
+        (Arc::new(engine), executor, exposure)
+    }
+
+    fn chunk_gates() -> ChunkGates {
+        ChunkGates {
+            params: StrategyParams::default(),
+            initial_amount: 100_000_000,
+            jito_tip_lamports: 1_000,
+            jito_tip_percentage: 0.1,
+            max_jito_tip_lamports: 100_000,
+            min_profit_threshold: 0,
+            ai_confidence_threshold: 0.0,
+            max_slippage_bps: 50,
+            volatility_sensitivity: 0.0,
+            max_slippage_ceiling: 100,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_split_chunks_clear_the_execution_policy_again() {
+        let paused = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let (engine, executor, exposure) = gated_engine(Arc::clone(&paused));
+        let mut first = engine.arb_strategy.sweep(&mev_core::constants::SOL_MINT, 100_000_000, 2).expect("cycle pays");
+        // The first chunk's fill moved the pool: the re-quote differs once, then pool state stands still
+        first.steps.last_mut().unwrap().expected_output += 1;
+        let executor_port = executor.clone() as Arc<dyn ExecutionPort>;
+
+        engine.execute_remaining_chunks(&executor_port, &first, 3, &chunk_gates()).await;
+        assert_eq!(executor.sent.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(exposure.dispatched.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // A chunk that would breach exposure is not sent
+        exposure.open.store(false, std::sync::atomic::Ordering::SeqCst);
+        engine.execute_remaining_chunks(&executor_port, &first, 3, &chunk_gates()).await;
+        assert_eq!(executor.sent.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Nor is one after trading was paused
+        exposure.open.store(true, std::sync::atomic::Ordering::SeqCst);
+        paused.store(true, std::sync::atomic::Ordering::SeqCst);
+        engine.execute_remaining_chunks(&executor_port, &first, 3, &chunk_gates()).await;
+        assert_eq!(executor.sent.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_process_event_reports_dispatched_gated_and_skipped_outcomes() {
+        async fn event(engine: &Arc<StrategyEngine>, update: &PoolUpdate, initial_amount: u64) -> EventOutcome {
+            engine.process_event(Arc::new(update.clone()), Trigger::AccountUpdate, initial_amount, 1_000, 0.1, 100_000, 50, 0.0, 100, 0.0, 2)
+                .await.expect("event processed")
+        }
+        let (engine, executor, exposure) = gated_engine(Arc::new(std::sync::atomic::AtomicBool::new(false)));
+        let (sol, token) = (mev_core::constants::SOL_MINT.to_string(), Pubkey::new_unique().to_string());
+        engine.arb_strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &sol, &token, 1_000_000_000_000, 2_000_000_000_000));
+        let closing = mock_pool(&Pubkey::new_unique().to_string(), &token, &sol, 2_000_000_000_000, 1_020_000_000_000);
+
+        // Clears every gate: dispatched, and booked against exposure
+        assert!(matches!(event(&engine, &closing, 100_000_000).await, EventOutcome::Executed(opp) if opp.expected_profit_lamports > 0));
+        assert_eq!(executor.sent.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(exposure.dispatched.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // The same route with exposure full is gated by that gate, and never reaches the executor
+        exposure.open.store(false, std::sync::atomic::Ordering::SeqCst);
+        assert!(matches!(event(&engine, &closing, 100_000_000).await, EventOutcome::Rejected(RejectionReason::Exposure)));
+        assert!(matches!(event(&engine, &closing, u64::MAX).await, EventOutcome::Rejected(RejectionReason::TradeSizeLimit)));
+        assert_eq!(executor.sent.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // An update that closes no cycle is skipped before the gates
+        let (a, b) = (Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string());
+        let unrelated = mock_pool(&Pubkey::new_unique().to_string(), &a, &b, 1_000_000_000, 1_000_000_000);
+        assert!(matches!(event(&engine, &unrelated, 100_000_000).await, EventOutcome::NoOpportunity));
+        assert_eq!(EventOutcome::Rejected(RejectionReason::Exposure).label(), "exposure");
+    }
+
+    /// Keeps the routes journaled as dispatched
+    #[derive(Default)]
+    struct RecordingJournal(parking_lot::Mutex<Vec<ArbitrageOpportunity>>);
+
+    impl TradeJournalPort for RecordingJournal {
+        fn record_rejection(&self, _: &ArbitrageOpportunity, _: &'static str) {}
+
+        fn record_dispatch(&self, opportunity: &ArbitrageOpportunity, _: u64, _: &str) {
+            self.0.lock().push(opportunity.clone());
+        }
+
+        fn record_send_failure(&self, _: &ArbitrageOpportunity, _: u64, _: &str) {}
+    }
+
+    struct FixedModel(f32);
+
+    impl AIModelPort for FixedModel {
+        fn predict_confidence(&self, _: &ArbitrageOpportunity) -> anyhow::Result<f32> {
+            Ok(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_journaled_dispatches_carry_their_trigger_and_ai_score() {
+        let executor = Arc::new(RecordingExecutor { payer: Pubkey::new_unique(), sent: Default::default() });
+        let journal = Arc::new(RecordingJournal::default());
+        let engine = Arc::new(StrategyEngine::new(
+            Some(executor.clone() as Arc<dyn ExecutionPort>), None, Some(Arc::new(FixedModel(0.9)) as Arc<dyn AIModelPort>), None, None, None, None,
+        ).with_trade_journal(journal.clone() as Arc<dyn TradeJournalPort>));
+        let (sol, token) = (mev_core::constants::SOL_MINT.to_string(), Pubkey::new_unique().to_string());
+        engine.arb_strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &sol, &token, 1_000_000_000_000, 2_000_000_000_000));
+        let closing = mock_pool(&Pubkey::new_unique().to_string(), &token, &sol, 2_000_000_000_000, 1_020_000_000_000);
+
+        let outcome = engine.process_event(Arc::new(closing), Trigger::Backrun, 100_000_000, 1_000, 0.1, 100_000, 50, 0.0, 100, 0.5, 2).await.unwrap();
+        assert!(matches!(outcome, EventOutcome::Executed(_)));
+        let outcome = engine.sweep_cycles(&mev_core::constants::SOL_MINT, 100_000_000, 1_000, 0.1, 100_000, 50, 0.0, 100, 0.5, 2).await.unwrap();
+        assert!(matches!(outcome, EventOutcome::Executed(_)));
+
+        // The journal row is tagged with what found the route and the score it cleared the AI gate with
+        let journaled = journal.0.lock();
+        let tags: Vec<_> = journaled.iter().map(|opp| (opp.trigger, opp.trigger.strategy(), opp.ai_confidence, opp.dna_score)).collect();
+        assert_eq!(tags, vec![
+            (Trigger::Backrun, "backrun", Some(0.9), None),
+            (Trigger::Sweep, "sweep", Some(0.9), None),
+        ]);
+    }
+
+    /// Fixed prices per whole token
+    struct FixedOracle(HashMap<Pubkey, crate::ports::ReferencePrice>);
+
+    impl PriceOraclePort for FixedOracle {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn reference(&self, mint: &Pubkey, _: u64) -> Option<crate::ports::ReferencePrice> {
+            self.0.get(mint).copied()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_non_sol_cycles_are_valued_in_sol_or_rejected_unpriced() {
+        let (usdc, token) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let engine_with = |oracle: Option<Arc<dyn PriceOraclePort>>| {
+            let engine = StrategyEngine::new(None, None, None, None, None, None, None);
+            let engine = match oracle {
+                Some(oracle) => engine.with_price_oracle(oracle),
+                None => engine,
+            };
+            // USDC -> token -> USDC paying ~2%
+            engine.arb_strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &usdc.to_string(), &token.to_string(), 1_000_000_000_000, 2_000_000_000_000));
+            engine.arb_strategy.apply_update(mock_pool(&Pubkey::new_unique().to_string(), &token.to_string(), &usdc.to_string(), 2_000_000_000_000, 1_020_000_000_000));
+            engine
+        };
+        let sweep = move |engine: StrategyEngine| async move {
+            engine.sweep_cycles(&usdc, 100_000_000, 1_000, 0.1, 100_000, 50, 0.0, 100, 0.0, 2).await.unwrap()
+        };
+
+        // Thresholds and tips are lamports: without a USDC price the profit can't be compared
+        assert!(matches!(sweep(engine_with(None)).await, EventOutcome::Rejected(RejectionReason::Unpriced)));
+
+        // USDC at $1 and SOL at $100: one raw USDC unit (6 decimals) is 10 lamports
+        let price = |price, decimals| crate::ports::ReferencePrice { price, conf: 0.0, decimals, publish_time: 0 };
+        let oracle = FixedOracle(HashMap::from([(usdc, price(1.0, 6)), (mev_core::constants::SOL_MINT, price(100.0, 9))]));
+        let EventOutcome::Executed(opp) = sweep(engine_with(Some(Arc::new(oracle)))).await else {
+            panic!("a priced cycle clears the gates");
+        };
+        let rate = opp.profit_in_sol() as f64 / opp.expected_profit_lamports as f64;
+        assert!((rate - 10.0).abs() < 0.01, "valued at {} lamports per unit", rate);
+        assert!((opp.start_units_in_sol(opp.input_amount as i64) - 1_000_000_000).abs() < 1_000);
+    }
 }
@@ -0,0 +1,39 @@
+/// Order Book Cache
+///
+/// CLOB markets can't be quoted from reserves. Ingestion decodes each market
+/// account into an `OrderBook` and stores it here; when the market's update
+/// reaches the graph, its edges pick up the latest book and CLOB hops quote
+/// against it. Shared between ingestion and the strategy the same way the
+/// trade-flow tracker is.
+use std::collections::HashMap;
+use std::sync::Arc;
+use parking_lot::RwLock;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::clob::OrderBook;
+
+#[derive(Default)]
+pub struct OrderBookCache {
+    books: RwLock<HashMap<Pubkey, Arc<OrderBook>>>,
+}
+
+impl OrderBookCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&self, market: Pubkey, book: OrderBook) {
+        self.insert(market, Arc::new(book));
+    }
+
+    pub fn insert(&self, market: Pubkey, book: Arc<OrderBook>) {
+        self.books.write().insert(market, book);
+    }
+
+    pub fn get(&self, market: &Pubkey) -> Option<Arc<OrderBook>> {
+        self.books.read().get(market).cloned()
+    }
+
+    pub fn remove(&self, market: &Pubkey) {
+        self.books.write().remove(market);
+    }
+}
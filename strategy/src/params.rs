@@ -0,0 +1,130 @@
+/// Strategy Thresholds
+///
+/// The trade-size panic limit, minimum net profit, profit sanity cap and
+/// per-hop price-impact cap. Each execution mode has a preset; config can
+/// override individual values. The engine and the cycle search read them
+/// through a shared handle on every event, so the control API can retune
+/// them without a restart.
+use parking_lot::RwLock;
+
+/// Highest per-hop impact cap the search accepts; past 10% constant-product quotes stop resembling fills
+pub const MAX_PRICE_IMPACT_CEILING_BPS: u16 = 1_000;
+
+/// 1.0 SOL, the panic limit no mode may raise
+const TRADE_SIZE_CEILING_LAMPORTS: u64 = 1_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyPreset {
+    Simulation,
+    LiveMicro,
+    LiveProduction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrategyParams {
+    /// Events sized above this are refused outright
+    pub max_trade_size_lamports: u64,
+    /// Net profit, after the tip, a route has to clear
+    pub min_profit_threshold_lamports: u64,
+    /// Largest believable profit as a multiple of the input (0.1 = 10%); above it the quote is treated as stale
+    pub sanity_profit_factor: f64,
+    /// Per-hop price impact cap during search
+    pub max_price_impact_bps: u16,
+}
+
+impl StrategyParams {
+    pub fn preset(preset: StrategyPreset) -> Self {
+        match preset {
+            // Loose sanity cap so paper runs still surface outsized quotes for inspection
+            StrategyPreset::Simulation => Self {
+                max_trade_size_lamports: TRADE_SIZE_CEILING_LAMPORTS,
+                min_profit_threshold_lamports: 30_000,
+                sanity_profit_factor: 100.0,
+                max_price_impact_bps: 100,
+            },
+            // 0.02 SOL hard cap; high returns are let through to exercise the execution path
+            StrategyPreset::LiveMicro => Self {
+                max_trade_size_lamports: 20_000_000,
+                min_profit_threshold_lamports: 30_000,
+                sanity_profit_factor: 100.0,
+                max_price_impact_bps: 100,
+            },
+            // Full size, and a profit over 10% of the input is a bad quote, not an opportunity
+            StrategyPreset::LiveProduction => Self {
+                max_trade_size_lamports: TRADE_SIZE_CEILING_LAMPORTS,
+                min_profit_threshold_lamports: 50_000,
+                sanity_profit_factor: 0.1,
+                max_price_impact_bps: 100,
+            },
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_trade_size_lamports == 0 || self.max_trade_size_lamports > TRADE_SIZE_CEILING_LAMPORTS {
+            return Err(format!("max_trade_size_lamports must be in (0, {}]. Got: {}", TRADE_SIZE_CEILING_LAMPORTS, self.max_trade_size_lamports));
+        }
+        if !(self.sanity_profit_factor.is_finite() && self.sanity_profit_factor > 0.0) {
+            return Err(format!("sanity_profit_factor must be > 0. Got: {}", self.sanity_profit_factor));
+        }
+        if !(1..=MAX_PRICE_IMPACT_CEILING_BPS).contains(&self.max_price_impact_bps) {
+            return Err(format!("max_price_impact_bps must be in [1, {}]. Got: {}", MAX_PRICE_IMPACT_CEILING_BPS, self.max_price_impact_bps));
+        }
+        Ok(())
+    }
+
+    /// Most profit a route on `input` lamports can claim before it is rejected as stale data
+    pub fn max_reasonable_profit(&self, input: u64) -> u64 {
+        (input as f64 * self.sanity_profit_factor) as u64
+    }
+}
+
+impl Default for StrategyParams {
+    fn default() -> Self {
+        Self::preset(StrategyPreset::Simulation)
+    }
+}
+
+/// Current params, shared by the engine, its search and whoever retunes them at runtime
+#[derive(Debug, Default)]
+pub struct StrategyParamsHandle {
+    params: RwLock<StrategyParams>,
+}
+
+impl StrategyParamsHandle {
+    pub fn new(params: StrategyParams) -> Self {
+        Self { params: RwLock::new(params) }
+    }
+
+    pub fn get(&self) -> StrategyParams {
+        *self.params.read()
+    }
+
+    /// Takes effect on the next event; invalid params leave the current ones in place
+    pub fn set(&self, params: StrategyParams) -> Result<(), String> {
+        params.validate()?;
+        *self.params.write() = params;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_are_valid_and_updates_are_checked() {
+        for preset in [StrategyPreset::Simulation, StrategyPreset::LiveMicro, StrategyPreset::LiveProduction] {
+            assert!(StrategyParams::preset(preset).validate().is_ok(), "{:?}", preset);
+        }
+        let production = StrategyParams::preset(StrategyPreset::LiveProduction);
+        assert_eq!(production.max_reasonable_profit(1_000_000_000), 100_000_000);
+
+        let handle = StrategyParamsHandle::new(production);
+        assert!(handle.set(StrategyParams { max_price_impact_bps: 5_000, ..production }).is_err());
+        assert!(handle.set(StrategyParams { max_trade_size_lamports: 2_000_000_000, ..production }).is_err());
+        assert_eq!(handle.get(), production);
+
+        handle.set(StrategyParams { max_price_impact_bps: 50, ..production }).unwrap();
+        assert_eq!(handle.get().max_price_impact_bps, 50);
+    }
+}
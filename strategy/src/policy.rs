@@ -0,0 +1,303 @@
+/// Execution Policy
+///
+/// The checks between a found route and its bundle, as one ordered pipeline
+/// of named gates. `EXECUTION_POLICY` lists the gates in the order they run,
+/// each with optional parameters that override the global value for that
+/// gate alone:
+/// `route_blacklist,exposure,profit_sanity(factor=0.05),tip,min_profit(lamports=80000),ai,safety,simulation`.
+/// A gate left out never runs; an empty policy is every gate in the default
+/// order below. Listed gates can also be switched off and back on at runtime
+/// (control API `/policy`) without losing their place. `size_cap` refuses an
+/// event before the search, so it applies wherever it is listed.
+///
+/// Each route gets a trace of every gate it reached: passed, rejected, skipped
+/// (nothing to check with, e.g. no oracle attached) or off, with the value the
+/// gate observed, its threshold and how long it took.
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::analytics::rejections::RejectionReason;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gate {
+    /// Input above the per-trade limit
+    SizeCap,
+    RouteBlacklist,
+    PoolRateLimit,
+    Exposure,
+    /// Profit implausibly large for the input
+    ProfitSanity,
+    Oracle,
+    /// Tip must stay below the profit
+    Tip,
+    MinProfit,
+    SlotLock,
+    /// Model confidence, or expected value when the EV gate is on
+    Ai,
+    Dna,
+    /// Rug checks and Token-2022 transfer fees
+    Safety,
+    QuoteCheck,
+    /// Volatility-adjusted slippage; sets the bound, never rejects
+    Slippage,
+    Simulation,
+}
+
+impl Gate {
+    /// Default pipeline order
+    pub const ALL: [Gate; 15] = [
+        Gate::SizeCap,
+        Gate::RouteBlacklist,
+        Gate::PoolRateLimit,
+        Gate::Exposure,
+        Gate::ProfitSanity,
+        Gate::Oracle,
+        Gate::Tip,
+        Gate::MinProfit,
+        Gate::SlotLock,
+        Gate::Ai,
+        Gate::Dna,
+        Gate::Safety,
+        Gate::QuoteCheck,
+        Gate::Slippage,
+        Gate::Simulation,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Gate::SizeCap => "size_cap",
+            Gate::RouteBlacklist => "route_blacklist",
+            Gate::PoolRateLimit => "pool_rate_limit",
+            Gate::Exposure => "exposure",
+            Gate::ProfitSanity => "profit_sanity",
+            Gate::Oracle => "oracle",
+            Gate::Tip => "tip",
+            Gate::MinProfit => "min_profit",
+            Gate::SlotLock => "slot_lock",
+            Gate::Ai => "ai",
+            Gate::Dna => "dna",
+            Gate::Safety => "safety",
+            Gate::QuoteCheck => "quote_check",
+            Gate::Slippage => "slippage",
+            Gate::Simulation => "simulation",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Gate> {
+        Gate::ALL.into_iter().find(|g| g.name() == name.trim())
+    }
+
+    /// Parameters the gate accepts; each overrides a global value for this gate only
+    pub fn params(&self) -> &'static [&'static str] {
+        match self {
+            Gate::SizeCap => &["max_lamports"],
+            Gate::ProfitSanity => &["factor"],
+            Gate::MinProfit => &["lamports"],
+            Gate::Ai => &["min_confidence"],
+            Gate::Slippage => &["max_bps", "ceiling_bps"],
+            _ => &[],
+        }
+    }
+}
+
+pub struct PolicyStep {
+    pub gate: Gate,
+    params: Vec<(&'static str, f64)>,
+    enabled: AtomicBool,
+}
+
+impl PolicyStep {
+    fn new(gate: Gate, params: Vec<(&'static str, f64)>) -> Self {
+        Self { gate, params, enabled: AtomicBool::new(true) }
+    }
+
+    pub fn param(&self, key: &str) -> Option<f64> {
+        self.params.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+    }
+
+    pub fn params(&self) -> &[(&'static str, f64)] {
+        &self.params
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+}
+
+pub struct ExecutionPolicy {
+    steps: Vec<PolicyStep>,
+}
+
+impl Default for ExecutionPolicy {
+    fn default() -> Self {
+        Self { steps: Gate::ALL.into_iter().map(|gate| PolicyStep::new(gate, Vec::new())).collect() }
+    }
+}
+
+impl ExecutionPolicy {
+    /// Parses `gate` or `gate(key=value,...)` entries, comma separated, in run order. Empty is the default pipeline.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        if spec.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let mut steps: Vec<PolicyStep> = Vec::new();
+        for entry in split_top_level(spec) {
+            let (name, args) = match entry.split_once('(') {
+                Some((name, rest)) => {
+                    let args = rest.strip_suffix(')').ok_or_else(|| format!("unclosed '(' in '{}'", entry))?;
+                    (name.trim(), Some(args))
+                }
+                None => (entry, None),
+            };
+            let gate = Gate::parse(name).ok_or_else(|| format!("unknown gate '{}'", name))?;
+            if steps.iter().any(|s| s.gate == gate) {
+                return Err(format!("gate '{}' listed twice", name));
+            }
+            let mut params = Vec::new();
+            for arg in args.into_iter().flat_map(|a| a.split(',')).map(str::trim).filter(|a| !a.is_empty()) {
+                let (key, value) = arg.split_once('=')
+                    .ok_or_else(|| format!("expected key=value for '{}', got '{}'", name, arg))?;
+                let key = gate.params().iter().copied().find(|k| *k == key.trim())
+                    .ok_or_else(|| format!("gate '{}' takes no parameter '{}' ({})", name, key.trim(), gate.params().join(", ")))?;
+                let value: f64 = value.trim().parse().ok().filter(|v: &f64| v.is_finite() && *v >= 0.0)
+                    .ok_or_else(|| format!("invalid value '{}' for {}.{}", value.trim(), name, key))?;
+                params.push((key, value));
+            }
+            steps.push(PolicyStep::new(gate, params));
+        }
+        Ok(Self { steps })
+    }
+
+    pub fn steps(&self) -> &[PolicyStep] {
+        &self.steps
+    }
+
+    pub fn step(&self, gate: Gate) -> Option<&PolicyStep> {
+        self.steps.iter().find(|s| s.gate == gate)
+    }
+
+    /// Whether `gate` is in the pipeline and switched on
+    pub fn is_active(&self, gate: Gate) -> bool {
+        self.step(gate).is_some_and(PolicyStep::is_enabled)
+    }
+
+    /// Runtime switch for a listed gate. Returns false if the gate isn't in the pipeline.
+    pub fn set_enabled(&self, gate: Gate, enabled: bool) -> bool {
+        match self.step(gate) {
+            Some(step) => {
+                step.enabled.store(enabled, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The pipeline as it runs, e.g. `exposure → min_profit(lamports=80000) → !dna → safety`
+    pub fn describe(&self) -> String {
+        self.steps.iter()
+            .map(|s| {
+                let off = if s.is_enabled() { "" } else { "!" };
+                if s.params.is_empty() {
+                    format!("{}{}", off, s.gate.name())
+                } else {
+                    let args: Vec<String> = s.params.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                    format!("{}{}({})", off, s.gate.name(), args.join(","))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" → ")
+    }
+}
+
+/// Splits on commas outside parentheses
+fn split_top_level(spec: &str) -> Vec<&str> {
+    let (mut parts, mut depth, mut start) = (Vec::new(), 0i32, 0);
+    for (i, c) in spec.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(spec[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(spec[start..].trim());
+    parts.into_iter().filter(|p| !p.is_empty()).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GateOutcome {
+    Passed,
+    Rejected(RejectionReason),
+    /// Nothing to check with (no oracle, no model, no executor)
+    Skipped,
+    /// Switched off at runtime
+    Off,
+}
+
+impl GateOutcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GateOutcome::Passed => "ok",
+            GateOutcome::Rejected(_) => "REJECT",
+            GateOutcome::Skipped => "skip",
+            GateOutcome::Off => "off",
+        }
+    }
+}
+
+/// One gate's entry in a route's evaluation trace
+#[derive(Debug, Clone, PartialEq)]
+pub struct GateCheck {
+    pub gate: Gate,
+    pub outcome: GateOutcome,
+    pub observed: f64,
+    pub threshold: f64,
+    pub elapsed_us: u64,
+}
+
+/// One line per trace for logs, e.g. `exposure ok · min_profit ok (120000/50000) · dna off · safety REJECT`
+pub fn trace_summary(trace: &[GateCheck]) -> String {
+    trace.iter()
+        .map(|c| match c.outcome {
+            GateOutcome::Passed | GateOutcome::Rejected(_) if c.observed != 0.0 || c.threshold != 0.0 =>
+                format!("{} {} ({}/{})", c.gate.name(), c.outcome.label(), c.observed, c.threshold),
+            _ => format!("{} {}", c.gate.name(), c.outcome.label()),
+        })
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_ordered_gates_with_params_and_toggles() {
+        let policy = ExecutionPolicy::parse("exposure, min_profit(lamports=80000), slippage(max_bps=30, ceiling_bps=200), dna").unwrap();
+        let order: Vec<Gate> = policy.steps().iter().map(|s| s.gate).collect();
+        assert_eq!(order, vec![Gate::Exposure, Gate::MinProfit, Gate::Slippage, Gate::Dna]);
+        assert_eq!(policy.step(Gate::MinProfit).unwrap().param("lamports"), Some(80_000.0));
+        assert_eq!(policy.step(Gate::Slippage).unwrap().param("ceiling_bps"), Some(200.0));
+        assert!(!policy.is_active(Gate::Ai));
+
+        assert!(policy.set_enabled(Gate::Dna, false));
+        assert!(!policy.is_active(Gate::Dna));
+        assert!(!policy.set_enabled(Gate::Ai, true));
+        assert_eq!(policy.describe(), "exposure → min_profit(lamports=80000) → slippage(max_bps=30,ceiling_bps=200) → !dna");
+
+        assert_eq!(ExecutionPolicy::parse("").unwrap().steps().len(), Gate::ALL.len());
+        assert!(ExecutionPolicy::parse("exposure,exposure").is_err());
+        assert!(ExecutionPolicy::parse("dna(lamports=1)").is_err());
+        assert!(ExecutionPolicy::parse("min_profit(lamports=-1)").is_err());
+        assert!(ExecutionPolicy::parse("min_profit(lamports=1").is_err());
+        assert!(ExecutionPolicy::parse("warp_drive").is_err());
+
+        let trace = [
+            GateCheck { gate: Gate::MinProfit, outcome: GateOutcome::Passed, observed: 120_000.0, threshold: 50_000.0, elapsed_us: 1 },
+            GateCheck { gate: Gate::Dna, outcome: GateOutcome::Off, observed: 0.0, threshold: 0.0, elapsed_us: 0 },
+            GateCheck { gate: Gate::Safety, outcome: GateOutcome::Rejected(RejectionReason::Safety), observed: 0.0, threshold: 0.0, elapsed_us: 9 },
+        ];
+        assert_eq!(trace_summary(&trace), "min_profit ok (120000/50000) · dna off · safety REJECT");
+    }
+}
@@ -0,0 +1,139 @@
+/// Per-Pool Execution Rate Limits
+///
+/// A pool whose price keeps oscillating against another venue re-opens the
+/// same edge over and over, and without a cap every re-open becomes a bundle.
+/// Each pool may take part in at most N dispatched routes per rolling minute.
+/// Major pairs with real two-sided flow can be given their own limit, or none.
+use dashmap::DashMap;
+use mev_core::{ArbitrageOpportunity, SwapStep};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+
+const WINDOW_MS: u64 = 60_000;
+
+fn pair_key(a: Pubkey, b: Pubkey) -> (Pubkey, Pubkey) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+pub struct PoolRateLimiter {
+    /// Dispatches per pool per minute; 0 = unlimited
+    default_per_minute: u32,
+    pair_overrides: HashMap<(Pubkey, Pubkey), u32>,
+    /// Pool -> dispatch times (unix ms) inside the window, oldest first
+    dispatches: DashMap<Pubkey, VecDeque<u64>>,
+}
+
+impl PoolRateLimiter {
+    pub fn new(default_per_minute: u32) -> Self {
+        Self {
+            default_per_minute,
+            pair_overrides: HashMap::new(),
+            dispatches: DashMap::new(),
+        }
+    }
+
+    /// Gives pools trading `mint_a`/`mint_b` (either order) their own limit; 0 exempts them
+    pub fn with_pair_override(mut self, mint_a: Pubkey, mint_b: Pubkey, per_minute: u32) -> Self {
+        self.pair_overrides.insert(pair_key(mint_a, mint_b), per_minute);
+        self
+    }
+
+    /// Parses `mint_a/mint_b:per_minute,...`
+    pub fn parse_overrides(spec: &str) -> Result<Vec<(Pubkey, Pubkey, u32)>, String> {
+        spec.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (pair, limit) = entry.rsplit_once(':')
+                    .ok_or_else(|| format!("expected mint_a/mint_b:limit, got '{}'", entry))?;
+                let (a, b) = pair.split_once('/')
+                    .ok_or_else(|| format!("expected mint_a/mint_b, got '{}'", pair))?;
+                let mint = |s: &str| Pubkey::from_str(s.trim()).map_err(|_| format!("invalid mint '{}'", s));
+                let limit = limit.trim().parse::<u32>().map_err(|_| format!("invalid limit '{}'", limit))?;
+                Ok((mint(a)?, mint(b)?, limit))
+            })
+            .collect()
+    }
+
+    fn limit(&self, step: &SwapStep) -> u32 {
+        self.pair_overrides
+            .get(&pair_key(step.input_mint, step.output_mint))
+            .copied()
+            .unwrap_or(self.default_per_minute)
+    }
+
+    /// First pool of the route that has used up its minute, with its limit
+    pub fn saturated_pool(&self, opp: &ArbitrageOpportunity, now_ms: u64) -> Option<(Pubkey, u32)> {
+        opp.steps.iter().find_map(|step| {
+            let limit = self.limit(step);
+            if limit == 0 {
+                return None;
+            }
+            let mut times = self.dispatches.get_mut(&step.pool)?;
+            while times.front().is_some_and(|&t| now_ms.saturating_sub(t) >= WINDOW_MS) {
+                times.pop_front();
+            }
+            (times.len() >= limit as usize).then_some((step.pool, limit))
+        })
+    }
+
+    /// Counts a dispatch against every pool of the route
+    pub fn record(&self, opp: &ArbitrageOpportunity, now_ms: u64) {
+        for (i, step) in opp.steps.iter().enumerate() {
+            // A pool visited twice by one route is still one dispatch
+            if self.limit(step) == 0 || opp.steps[..i].iter().any(|s| s.pool == step.pool) {
+                continue;
+            }
+            let mut times = self.dispatches.entry(step.pool).or_default();
+            while times.front().is_some_and(|&t| now_ms.saturating_sub(t) >= WINDOW_MS) {
+                times.pop_front();
+            }
+            times.push_back(now_ms);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(pool: Pubkey, mint_a: Pubkey, mint_b: Pubkey) -> ArbitrageOpportunity {
+        let step = |input_mint, output_mint| SwapStep {
+            pool,
+            program_id: Pubkey::default(),
+            input_mint,
+            output_mint,
+            expected_output: 0,
+        };
+        ArbitrageOpportunity {
+            steps: vec![step(mint_a, mint_b), step(mint_b, mint_a)].into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pool_limit_rolls_over_and_major_pairs_override() {
+        let (sol, usdc, meme) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (major, minor) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let spec = format!("{}/{}:0", usdc, sol);
+        let (a, b, limit) = PoolRateLimiter::parse_overrides(&spec).unwrap()[0];
+        let limiter = PoolRateLimiter::new(2).with_pair_override(a, b, limit);
+
+        let meme_route = route(minor, sol, meme);
+        limiter.record(&meme_route, 0);
+        limiter.record(&meme_route, 10_000);
+        assert_eq!(limiter.saturated_pool(&meme_route, 20_000), Some((minor, 2)));
+        // The first dispatch leaves the window
+        assert_eq!(limiter.saturated_pool(&meme_route, 60_000), None);
+
+        // Exempt pair: never saturates
+        let major_route = route(major, sol, usdc);
+        for t in 0..10 {
+            limiter.record(&major_route, t);
+        }
+        assert_eq!(limiter.saturated_pool(&major_route, 10), None);
+
+        assert!(PoolRateLimiter::parse_overrides("not-a-pair:3").is_err());
+    }
+}
@@ -0,0 +1,71 @@
+/// Kelly Position Sizing
+///
+/// Trade size follows the bot's recent record instead of a fixed amount. The
+/// Kelly bet f* = p - (1 - p) / b, with p the rolling win rate and b the ratio
+/// of average win to average loss, is scaled down by the configured fraction
+/// and applied to the available balance. Until enough trades have landed to
+/// trust the estimate, or before the balance is known, the static size stands.
+use crate::ports::TradeStats;
+
+#[derive(Debug, Clone, Copy)]
+pub struct KellySizer {
+    /// Share of the full Kelly bet to take (0.1 = tenth-Kelly)
+    pub fraction: f64,
+    /// Landed trades needed before the record is used
+    pub min_trades: usize,
+    pub min_amount: u64,
+    pub max_amount: u64,
+}
+
+impl KellySizer {
+    pub fn new(fraction: f64, min_trades: usize, min_amount: u64, max_amount: u64) -> Self {
+        Self { fraction, min_trades, min_amount: min_amount.min(max_amount), max_amount }
+    }
+
+    /// Full Kelly fraction of the bankroll for `stats`; 0 when there is no edge
+    pub fn kelly(stats: &TradeStats) -> f64 {
+        let p = stats.win_rate.clamp(0.0, 1.0);
+        if stats.avg_loss <= 0.0 {
+            // Nothing lost yet: the payoff ratio is unbounded and f* tends to p
+            return p;
+        }
+        let b = stats.avg_win / stats.avg_loss;
+        if b <= 0.0 {
+            return 0.0;
+        }
+        (p - (1.0 - p) / b).max(0.0)
+    }
+
+    /// Input for the next trade given the record and the spendable balance
+    pub fn size(&self, stats: &TradeStats, balance: u64, default_amount: u64) -> u64 {
+        if stats.trades < self.min_trades || balance == 0 {
+            return default_amount;
+        }
+        let amount = balance as f64 * Self::kelly(stats) * self.fraction;
+        (amount as u64).clamp(self.min_amount, self.max_amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kelly_size_tracks_edge_and_clamps() {
+        let sizer = KellySizer::new(0.5, 20, 1_000_000, 20_000_000);
+        let stats = |trades, win_rate, avg_win, avg_loss| TradeStats { trades, win_rate, avg_win, avg_loss };
+
+        // Not enough history, or balance unknown: static size
+        assert_eq!(sizer.size(&stats(5, 0.9, 1.0, 1.0), 1_000_000_000, 7_000_000), 7_000_000);
+        assert_eq!(sizer.size(&stats(50, 0.9, 1.0, 1.0), 0, 7_000_000), 7_000_000);
+
+        // p = 0.6, b = 1: f* = 0.2, half-Kelly of 100M = 10M
+        let even = stats(50, 0.6, 1_000.0, 1_000.0);
+        assert!((KellySizer::kelly(&even) - 0.2).abs() < 1e-9);
+        assert_eq!(sizer.size(&even, 100_000_000, 7_000_000), 10_000_000);
+
+        // Losing record sits at the floor, a large bankroll at the cap
+        assert_eq!(sizer.size(&stats(50, 0.3, 1_000.0, 1_000.0), 100_000_000, 7_000_000), 1_000_000);
+        assert_eq!(sizer.size(&even, 10_000_000_000, 7_000_000), 20_000_000);
+    }
+}
@@ -0,0 +1,132 @@
+/// Execution Priority Lane
+///
+/// Execution slots are shared by every worker. When the bot is busy sending
+/// marginal bundles, a large opportunity would otherwise queue behind them.
+/// A few slots are held back for opportunities above a rolling profit
+/// percentile, so the big ones always find a free lane.
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+// Don't rank anything until the distribution means something
+const MIN_SAMPLES: usize = 20;
+
+/// Rolling window of detected gross profits
+pub struct ProfitDistribution {
+    samples: Mutex<VecDeque<u64>>,
+    capacity: usize,
+}
+
+impl ProfitDistribution {
+    pub fn new(capacity: usize) -> Self {
+        Self { samples: Mutex::new(VecDeque::with_capacity(capacity)), capacity: capacity.max(1) }
+    }
+
+    pub fn record(&self, profit: u64) {
+        let mut samples = self.samples.lock();
+        if samples.len() >= self.capacity {
+            samples.pop_front();
+        }
+        samples.push_back(profit);
+    }
+
+    /// Nearest-rank percentile, None until MIN_SAMPLES have been seen
+    pub fn percentile(&self, q: f64) -> Option<u64> {
+        let mut sorted: Vec<u64> = self.samples.lock().iter().copied().collect();
+        if sorted.len() < MIN_SAMPLES {
+            return None;
+        }
+        sorted.sort_unstable();
+        let rank = (q.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+}
+
+pub struct ExecutionLane {
+    normal: Arc<Semaphore>,
+    priority: Arc<Semaphore>,
+    percentile: f64,
+    distribution: ProfitDistribution,
+}
+
+impl ExecutionLane {
+    pub fn new(normal_slots: usize, priority_slots: usize, percentile: f64, window: usize) -> Self {
+        Self {
+            normal: Arc::new(Semaphore::new(normal_slots.max(1))),
+            priority: Arc::new(Semaphore::new(priority_slots)),
+            percentile,
+            distribution: ProfitDistribution::new(window),
+        }
+    }
+
+    /// Feeds a detected opportunity into the rolling distribution
+    pub fn observe(&self, profit: u64) {
+        self.distribution.record(profit);
+    }
+
+    pub fn is_priority(&self, profit: u64) -> bool {
+        self.distribution.percentile(self.percentile).is_some_and(|cutoff| profit > cutoff)
+    }
+
+    /// Waits for an execution slot. Priority opportunities take a reserved slot if one
+    /// is free and otherwise race for a normal one; marginal ones only ever wait for normal slots.
+    /// Errors once the lane is closed, so the caller stands down instead of sending unmetered.
+    pub async fn acquire(&self, profit: u64) -> anyhow::Result<OwnedSemaphorePermit> {
+        if self.is_priority(profit) {
+            mev_core::telemetry::OPPORTUNITIES_PRIORITY_BOOSTED.inc();
+            if let Ok(permit) = Arc::clone(&self.priority).try_acquire_owned() {
+                return Ok(permit);
+            }
+            return tokio::select! {
+                Ok(permit) = Arc::clone(&self.priority).acquire_owned() => Ok(permit),
+                Ok(permit) = Arc::clone(&self.normal).acquire_owned() => Ok(permit),
+                else => Err(anyhow::anyhow!("execution lane closed")),
+            };
+        }
+        Arc::clone(&self.normal).acquire_owned().await.map_err(|_| anyhow::anyhow!("execution lane closed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn warmed_lane(normal: usize, priority: usize) -> ExecutionLane {
+        let lane = ExecutionLane::new(normal, priority, 0.9, 100);
+        for profit in 1..=100 {
+            lane.observe(profit * 1_000);
+        }
+        lane
+    }
+
+    #[test]
+    fn test_needs_warmup_before_ranking() {
+        let lane = ExecutionLane::new(1, 1, 0.9, 100);
+        lane.observe(1_000);
+        assert!(!lane.is_priority(u64::MAX));
+        assert_eq!(warmed_lane(1, 1).distribution.percentile(0.9), Some(90_000));
+    }
+
+    #[tokio::test]
+    async fn test_big_fish_skips_marginal_backlog() {
+        let lane = warmed_lane(1, 1);
+        let _busy = lane.acquire(5_000).await.unwrap(); // Normal lane saturated by a marginal trade
+
+        let marginal = tokio::time::timeout(Duration::from_millis(50), lane.acquire(5_000)).await;
+        assert!(marginal.is_err());
+
+        let big = tokio::time::timeout(Duration::from_millis(50), lane.acquire(500_000)).await;
+        assert!(big.is_ok_and(|permit| permit.is_ok()));
+    }
+
+    #[tokio::test]
+    async fn test_closed_lane_errors_instead_of_panicking() {
+        let lane = warmed_lane(1, 1);
+        lane.normal.close();
+        assert!(lane.acquire(5_000).await.is_err());
+        lane.priority.close();
+        assert!(lane.acquire(500_000).await.is_err());
+    }
+}
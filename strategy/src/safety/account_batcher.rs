@@ -0,0 +1,180 @@
+/// Safety Check Account Batching
+///
+/// During a launch storm dozens of safety validations run at once, and each one
+/// fetches its mint, pool, vaults and LP burn accounts separately. Requests
+/// arriving within a short window are coalesced into as few getMultipleAccounts
+/// calls as the RPC allows: the first request of a window schedules the flush,
+/// later ones just join it.
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::Result;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::oneshot;
+
+pub const DEFAULT_BATCH_WINDOW: Duration = Duration::from_millis(20);
+
+/// getMultipleAccounts accepts at most this many keys per call
+const MAX_KEYS_PER_CALL: usize = 100;
+
+/// Anything that can answer getMultipleAccounts
+#[async_trait]
+pub trait AccountSource: Send + Sync {
+    async fn get_multiple_accounts(&self, keys: &[Pubkey]) -> Result<Vec<Option<Account>>>;
+}
+
+#[async_trait]
+impl AccountSource for RpcClient {
+    async fn get_multiple_accounts(&self, keys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        Ok(RpcClient::get_multiple_accounts(self, keys).await?)
+    }
+}
+
+type Reply = oneshot::Sender<std::result::Result<Vec<Option<Account>>, String>>;
+
+#[derive(Default)]
+struct Pending {
+    requests: Vec<(Vec<Pubkey>, Reply)>,
+    flush_scheduled: bool,
+}
+
+struct Inner {
+    source: Arc<dyn AccountSource>,
+    window: Duration,
+    pending: Mutex<Pending>,
+}
+
+pub struct AccountBatcher {
+    inner: Arc<Inner>,
+}
+
+impl AccountBatcher {
+    pub fn new(source: Arc<dyn AccountSource>, window: Duration) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                source,
+                window,
+                pending: Mutex::new(Pending::default()),
+            }),
+        }
+    }
+
+    async fn fetch(&self, keys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        let (tx, rx) = oneshot::channel();
+        let schedule = {
+            let mut pending = self.inner.pending.lock();
+            pending.requests.push((keys.to_vec(), tx));
+            !std::mem::replace(&mut pending.flush_scheduled, true)
+        };
+        if schedule {
+            // Spawned so the flush still runs if the scheduling caller is dropped
+            let inner = Arc::clone(&self.inner);
+            tokio::spawn(async move {
+                tokio::time::sleep(inner.window).await;
+                inner.flush().await;
+            });
+        }
+        rx.await
+            .map_err(|_| anyhow::anyhow!("Account batch dropped"))?
+            .map_err(|e| anyhow::anyhow!(e))
+    }
+}
+
+impl Inner {
+    async fn flush(&self) {
+        let requests = {
+            let mut pending = self.pending.lock();
+            pending.flush_scheduled = false;
+            std::mem::take(&mut pending.requests)
+        };
+        if requests.is_empty() {
+            return;
+        }
+
+        let mut keys: Vec<Pubkey> = requests.iter().flat_map(|(k, _)| k.iter().copied()).collect();
+        keys.sort_unstable();
+        keys.dedup();
+
+        let mut fetched: HashMap<Pubkey, Option<Account>> = HashMap::with_capacity(keys.len());
+        let mut failure: Option<String> = None;
+        for chunk in keys.chunks(MAX_KEYS_PER_CALL) {
+            mev_core::telemetry::SAFETY_ACCOUNT_BATCHES.inc();
+            match self.source.get_multiple_accounts(chunk).await {
+                Ok(accounts) => fetched.extend(chunk.iter().copied().zip(accounts)),
+                Err(e) => {
+                    failure = Some(e.to_string());
+                    break;
+                }
+            }
+        }
+        mev_core::telemetry::SAFETY_ACCOUNT_REQUESTS.inc_by(requests.len() as f64);
+
+        for (wanted, reply) in requests {
+            let result = match &failure {
+                Some(e) => Err(e.clone()),
+                None => Ok(wanted.iter().map(|k| fetched.get(k).cloned().flatten()).collect()),
+            };
+            let _ = reply.send(result);
+        }
+    }
+}
+
+#[async_trait]
+impl AccountSource for AccountBatcher {
+    async fn get_multiple_accounts(&self, keys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        self.fetch(keys).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct CountingSource {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl AccountSource for CountingSource {
+        async fn get_multiple_accounts(&self, keys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            // Lamports encode the first key byte so callers can check they got their own accounts
+            Ok(keys.iter().map(|k| Some(Account { lamports: k.to_bytes()[0] as u64, ..Default::default() })).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_share_one_call() {
+        let source = Arc::new(CountingSource::default());
+        let batcher = AccountBatcher::new(Arc::clone(&source) as Arc<dyn AccountSource>, Duration::from_millis(20));
+        let (a, b, c) = (Pubkey::new_from_array([1; 32]), Pubkey::new_from_array([2; 32]), Pubkey::new_from_array([3; 32]));
+
+        let (first, second, third) = tokio::join!(
+            batcher.get_multiple_accounts(&[a, b]),
+            batcher.get_multiple_accounts(&[b, c]),
+            batcher.get_multiple_accounts(&[c]),
+        );
+
+        assert_eq!(source.calls.load(Ordering::SeqCst), 1);
+        let lamports = |r: Result<Vec<Option<Account>>>| r.unwrap().into_iter().map(|a| a.unwrap().lamports).collect::<Vec<_>>();
+        assert_eq!(lamports(first), vec![1, 2]);
+        assert_eq!(lamports(second), vec![2, 3]);
+        assert_eq!(lamports(third), vec![3]);
+    }
+
+    #[tokio::test]
+    async fn test_requests_in_separate_windows_are_separate_calls() {
+        let source = Arc::new(CountingSource::default());
+        let batcher = AccountBatcher::new(Arc::clone(&source) as Arc<dyn AccountSource>, Duration::from_millis(5));
+
+        batcher.get_multiple_accounts(&[Pubkey::new_unique()]).await.unwrap();
+        batcher.get_multiple_accounts(&[Pubkey::new_unique()]).await.unwrap();
+        assert_eq!(source.calls.load(Ordering::SeqCst), 2);
+    }
+}
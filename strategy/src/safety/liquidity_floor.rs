@@ -0,0 +1,100 @@
+/// Liquidity Floor
+///
+/// `MIN_LIQUIDITY_LAMPORTS` used to be compared with raw vault amounts, which
+/// only means SOL on a SOL side: 5e9 units of a 6-decimal token is 5,000
+/// tokens whatever they are worth. The floor values a pool's depth in
+/// SOL-equivalent lamports instead. A SOL side counts as is and any other side
+/// at its oracle price against SOL; a balanced pool holds the same value on
+/// both sides, so depth is twice the smaller side that could be priced. Pools
+/// with no SOL side and no fresh oracle reference on either side are not
+/// judged. The same floor backs the safety check, the cycle search and the
+/// discovery filter.
+use std::sync::Arc;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::PoolUpdate;
+use mev_core::constants::{is_clmm, is_clob, METEORA_PROGRAM_ID, SOL_MINT};
+use crate::ports::PriceOraclePort;
+
+pub struct LiquidityFloor {
+    min_lamports: u64,
+    oracle: Option<Arc<dyn PriceOraclePort>>,
+}
+
+impl LiquidityFloor {
+    pub fn new(min_lamports: u64, oracle: Option<Arc<dyn PriceOraclePort>>) -> Self {
+        Self { min_lamports, oracle }
+    }
+
+    pub fn min_lamports(&self) -> u64 {
+        self.min_lamports
+    }
+
+    /// SOL-equivalent lamports of `amount` raw units of `mint`; None without a fresh price
+    pub fn value_lamports(&self, mint: &Pubkey, amount: u128, now_secs: u64) -> Option<u64> {
+        if *mint == SOL_MINT {
+            return Some(amount.min(u64::MAX as u128) as u64);
+        }
+        self.oracle.as_ref()?.sol_value(mint, amount, now_secs)
+    }
+
+    /// Depth of a pool holding `a` and `b` as (mint, raw amount); None if neither side has a price
+    pub fn depth_lamports(&self, a: (Pubkey, u128), b: (Pubkey, u128), now_secs: u64) -> Option<u64> {
+        [a, b].into_iter()
+            .filter_map(|(mint, amount)| self.value_lamports(&mint, amount, now_secs))
+            .min()
+            .map(|side| side.saturating_mul(2))
+    }
+
+    /// Depth of a graph pool. Concentrated pools count their virtual reserves at the current
+    /// price; DLMM pairs and order books carry no real reserves on their update and are not judged.
+    pub fn pool_depth_lamports(&self, pool: &PoolUpdate, now_secs: u64) -> Option<u64> {
+        if pool.program_id == METEORA_PROGRAM_ID || is_clob(&pool.program_id) {
+            return None;
+        }
+        let (reserve_a, reserve_b) = if is_clmm(&pool.program_id) {
+            let sqrt_p = pool.price_sqrt.unwrap_or(0) as f64 / (1u128 << 64) as f64;
+            if sqrt_p == 0.0 {
+                return None;
+            }
+            let liquidity = pool.liquidity.unwrap_or(0) as f64;
+            ((liquidity / sqrt_p) as u128, (liquidity * sqrt_p) as u128)
+        } else {
+            (pool.reserve_a, pool.reserve_b)
+        };
+        self.depth_lamports((pool.mint_a, reserve_a), (pool.mint_b, reserve_b), now_secs)
+    }
+
+    /// Whether a depth clears the floor; an unpriced pool is let through
+    pub fn admits(&self, depth_lamports: Option<u64>) -> bool {
+        depth_lamports.is_none_or(|depth| depth >= self.min_lamports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safety::oracle_guard::{PriceCache, ReferencePrice};
+
+    #[test]
+    fn test_depth_is_valued_in_sol_not_raw_units() {
+        let (usdc, bonk) = (mev_core::constants::USDC_MINT, Pubkey::new_unique());
+        let oracle = Arc::new(PriceCache::new(60));
+        oracle.update(SOL_MINT, ReferencePrice { price: 150.0, conf: 0.0, decimals: 9, publish_time: 1_000 });
+        oracle.update(usdc, ReferencePrice { price: 1.0, conf: 0.0, decimals: 6, publish_time: 1_000 });
+        let floor = LiquidityFloor::new(5_000_000_000, Some(oracle));
+
+        // 5e9 raw USDC is $5,000, about 33 SOL a side
+        let depth = floor.depth_lamports((usdc, 5_000_000_000), (bonk, 1), 1_010).unwrap();
+        assert!((66_666_666_000..66_666_667_000).contains(&depth), "{}", depth);
+        assert!(floor.admits(Some(depth)));
+
+        // 450 USDC (3 SOL) against 2 SOL: the smaller side decides
+        assert_eq!(floor.depth_lamports((usdc, 450_000_000), (SOL_MINT, 2_000_000_000), 1_010), Some(4_000_000_000));
+        assert!(!floor.admits(Some(4_000_000_000)));
+
+        // A stale reference or an unpriced pair is not judged
+        assert_eq!(floor.depth_lamports((usdc, 1), (bonk, 1), 2_000), None);
+        assert!(floor.admits(None));
+        assert_eq!(LiquidityFloor::new(1, None).value_lamports(&SOL_MINT, 7, 0), Some(7));
+    }
+}
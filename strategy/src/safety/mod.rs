@@ -1,4 +1,8 @@
 pub mod token_validator;
+pub mod account_batcher;
+pub mod oracle_guard;
+pub mod liquidity_floor;
+pub mod quote_check;
 
 #[cfg(test)]
 mod token_validator_tests;
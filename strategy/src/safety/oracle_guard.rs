@@ -0,0 +1,163 @@
+/// Oracle Sanity Guard
+///
+/// The profit sanity check caps profit relative to input, so a stale or
+/// mis-decoded pool that prices one hop well off market can still pass it.
+/// This guard gives a second opinion: each hop's implied price is compared
+/// with oracle references for both of its mints, and a route is refused when
+/// a hop pays more than the plausibility band above the reference. The band
+/// widens by the oracles' own confidence. Hops without a fresh reference on
+/// both sides are not judged. Prices come from any `PriceOraclePort`.
+use std::collections::HashMap;
+use std::sync::Arc;
+use parking_lot::RwLock;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::ArbitrageOpportunity;
+use crate::ports::PriceOraclePort;
+pub use crate::ports::ReferencePrice;
+
+/// A mint's oracle price account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OracleFeed {
+    pub mint: Pubkey,
+    pub price_account: Pubkey,
+    pub decimals: u8,
+}
+
+impl OracleFeed {
+    /// `mint:price_account:decimals,...` (empty = no feeds)
+    pub fn parse_list(spec: &str) -> Result<Vec<Self>, String> {
+        spec.split(',')
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(|feed| {
+                let parts: Vec<&str> = feed.split(':').map(str::trim).collect();
+                let [mint, account, decimals] = parts[..] else {
+                    return Err(format!("expected mint:price_account:decimals, got {}", feed));
+                };
+                Ok(Self {
+                    mint: mint.parse().map_err(|_| format!("invalid mint: {}", mint))?,
+                    price_account: account.parse().map_err(|_| format!("invalid price account: {}", account))?,
+                    decimals: decimals.parse().map_err(|_| format!("invalid decimals: {}", decimals))?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// In-process price store an oracle adapter writes into; answers for prices no older than `max_age_secs`
+pub struct PriceCache {
+    prices: RwLock<HashMap<Pubkey, ReferencePrice>>,
+    max_age_secs: u64,
+}
+
+impl PriceCache {
+    pub fn new(max_age_secs: u64) -> Self {
+        Self { prices: RwLock::new(HashMap::new()), max_age_secs }
+    }
+
+    pub fn update(&self, mint: Pubkey, price: ReferencePrice) {
+        self.prices.write().insert(mint, price);
+    }
+}
+
+impl PriceOraclePort for PriceCache {
+    fn name(&self) -> &'static str {
+        "cache"
+    }
+
+    fn reference(&self, mint: &Pubkey, now_secs: u64) -> Option<ReferencePrice> {
+        self.prices.read().get(mint)
+            .filter(|p| p.price > 0.0 && now_secs.saturating_sub(p.publish_time) <= self.max_age_secs)
+            .copied()
+    }
+}
+
+/// The hop that broke the band
+#[derive(Debug, Clone, Copy)]
+pub struct HopDeviation {
+    pub step_index: usize,
+    /// How far the hop prices above the oracle
+    pub deviation_bps: f64,
+    pub allowed_bps: f64,
+}
+
+pub struct OracleGuard {
+    oracle: Arc<dyn PriceOraclePort>,
+    max_deviation_bps: u32,
+}
+
+impl OracleGuard {
+    pub fn new(oracle: Arc<dyn PriceOraclePort>, max_deviation_bps: u32) -> Self {
+        Self { oracle, max_deviation_bps }
+    }
+
+    /// The hop furthest above the band, if any hop is
+    pub fn check(&self, opp: &ArbitrageOpportunity, now_secs: u64) -> Option<HopDeviation> {
+        let mut worst: Option<HopDeviation> = None;
+        for (step_index, step) in opp.steps.iter().enumerate() {
+            let hop_in = opp.step_input(step_index);
+            if hop_in == 0 {
+                continue;
+            }
+            let (Some(input), Some(output)) = (self.oracle.reference(&step.input_mint, now_secs), self.oracle.reference(&step.output_mint, now_secs)) else {
+                continue;
+            };
+            let implied = step.expected_output as f64 / hop_in as f64;
+            let reference = input.per_atom() / output.per_atom();
+            let deviation_bps = (implied / reference - 1.0) * 10_000.0;
+            let allowed_bps = self.max_deviation_bps as f64
+                + (input.conf / input.price + output.conf / output.price) * 10_000.0;
+            if deviation_bps > allowed_bps && worst.map_or(true, |w| deviation_bps > w.deviation_bps) {
+                worst = Some(HopDeviation { step_index, deviation_bps, allowed_bps });
+            }
+        }
+        worst
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::SwapStep;
+
+    #[test]
+    fn test_flags_hop_priced_above_oracle() {
+        let prices = Arc::new(PriceCache::new(60));
+        let (sol, usdc, meme) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        prices.update(sol, ReferencePrice { price: 150.0, conf: 0.0, decimals: 9, publish_time: 1_000 });
+        prices.update(usdc, ReferencePrice { price: 1.0, conf: 0.0, decimals: 6, publish_time: 1_000 });
+        let guard = OracleGuard::new(prices, 300);
+
+        let step = |input_mint, output_mint, expected_output| SwapStep {
+            pool: Pubkey::new_unique(),
+            program_id: Pubkey::new_unique(),
+            input_mint,
+            output_mint,
+            expected_output,
+        };
+        let route = |usdc_out| ArbitrageOpportunity {
+            steps: [
+                step(sol, usdc, usdc_out),
+                step(usdc, meme, 5_000_000),
+                step(meme, sol, 1_010_000_000),
+            ].into_iter().collect(),
+            input_amount: 1_000_000_000,
+            ..Default::default()
+        };
+
+        // 1 SOL -> 151 USDC is within 3% of 150; the unpriced meme hops aren't judged
+        assert!(guard.check(&route(151_000_000), 1_010).is_none());
+
+        // 1 SOL -> 180 USDC is 20% over the oracle
+        let hop = guard.check(&route(180_000_000), 1_010).unwrap();
+        assert_eq!(hop.step_index, 0);
+        assert!((hop.deviation_bps - 2_000.0).abs() < 1e-6);
+
+        // The same quote once the oracle is stale: no opinion
+        assert!(guard.check(&route(180_000_000), 1_100).is_none());
+
+        let feeds = OracleFeed::parse_list(&format!("{}:{}:9", sol, usdc)).unwrap();
+        assert_eq!(feeds, vec![OracleFeed { mint: sol, price_account: usdc, decimals: 9 }]);
+        assert!(OracleFeed::parse_list("abc:9").is_err());
+    }
+}
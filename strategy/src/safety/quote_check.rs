@@ -0,0 +1,123 @@
+/// Quote Cross-Check
+///
+/// Last gate before a bundle is built. Every hop is re-quoted by an
+/// independent source (Jupiter) for the same input on the same venue and
+/// pair, and a route whose own output on some hop beats that quote by more
+/// than `tolerance_bps` is dropped. A hop we overrate is what a swap-math or
+/// account-layout decoding bug looks like, and it is the direction that loses
+/// money. Quoting below the source is let through: it may simply have routed
+/// a deeper pool of the same venue. Hops the source can't route, or doesn't
+/// answer for in time, are not judged.
+use std::sync::Arc;
+use mev_core::ArbitrageOpportunity;
+use crate::ports::QuoteSourcePort;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HopMismatch {
+    pub step_index: usize,
+    pub ours: u64,
+    pub reference: u64,
+    /// How far our output is above the reference, in bps
+    pub deviation_bps: f64,
+}
+
+pub struct QuoteCrossCheck {
+    source: Arc<dyn QuoteSourcePort>,
+    tolerance_bps: u32,
+}
+
+impl QuoteCrossCheck {
+    pub fn new(source: Arc<dyn QuoteSourcePort>, tolerance_bps: u32) -> Self {
+        Self { source, tolerance_bps }
+    }
+
+    pub fn source_name(&self) -> &'static str {
+        self.source.name()
+    }
+
+    pub fn tolerance_bps(&self) -> u32 {
+        self.tolerance_bps
+    }
+
+    /// The hop furthest above its reference quote, if any is past the tolerance. Hops are quoted concurrently.
+    pub async fn check(&self, opp: &ArbitrageOpportunity) -> Option<HopMismatch> {
+        let quotes = futures::future::join_all(opp.steps.iter().enumerate()
+            .map(|(i, step)| self.source.quote_hop(step, opp.step_input(i)))).await;
+
+        let mut worst: Option<HopMismatch> = None;
+        for (step_index, (step, quote)) in opp.steps.iter().zip(quotes).enumerate() {
+            let reference = match quote {
+                Ok(Some(reference)) if reference > 0 => reference,
+                Ok(_) => {
+                    mev_core::telemetry::QUOTE_CHECK_HOPS.with_label_values(&["unrouted"]).inc();
+                    continue;
+                }
+                Err(e) => {
+                    tracing::debug!("🪐 {} quote for hop {} unavailable: {}", self.source.name(), step_index + 1, e);
+                    mev_core::telemetry::QUOTE_CHECK_HOPS.with_label_values(&["unavailable"]).inc();
+                    continue;
+                }
+            };
+            let deviation_bps = (step.expected_output as f64 / reference as f64 - 1.0) * 10_000.0;
+            if deviation_bps <= self.tolerance_bps as f64 {
+                mev_core::telemetry::QUOTE_CHECK_HOPS.with_label_values(&["matched"]).inc();
+                continue;
+            }
+            mev_core::telemetry::QUOTE_CHECK_HOPS.with_label_values(&["mismatch"]).inc();
+            if worst.map_or(true, |w| deviation_bps > w.deviation_bps) {
+                worst = Some(HopMismatch { step_index, ours: step.expected_output, reference, deviation_bps });
+            }
+        }
+        worst
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::SwapStep;
+    use solana_sdk::pubkey::Pubkey;
+
+    /// Quotes 100 out per 1 in, except on unknown venues
+    struct FlatQuotes {
+        unrouted: Pubkey,
+    }
+
+    #[async_trait::async_trait]
+    impl QuoteSourcePort for FlatQuotes {
+        fn name(&self) -> &'static str {
+            "flat"
+        }
+
+        async fn quote_hop(&self, step: &SwapStep, amount_in: u64) -> anyhow::Result<Option<u64>> {
+            Ok((step.program_id != self.unrouted).then_some(amount_in * 100))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flags_hop_quoted_above_the_reference() {
+        let unrouted = Pubkey::new_unique();
+        let check = QuoteCrossCheck::new(Arc::new(FlatQuotes { unrouted }), 100);
+        let step = |program_id, expected_output| SwapStep {
+            pool: Pubkey::new_unique(),
+            program_id,
+            input_mint: Pubkey::new_unique(),
+            output_mint: Pubkey::new_unique(),
+            expected_output,
+        };
+        let route = |first_out, second_out| ArbitrageOpportunity {
+            steps: [step(Pubkey::new_unique(), first_out), step(unrouted, 1), step(Pubkey::new_unique(), second_out)]
+                .into_iter().collect(),
+            input_amount: 1_000,
+            ..Default::default()
+        };
+
+        // 0.5% over and 20% under the reference both pass; the unrouted hop isn't judged
+        assert!(check.check(&route(100_500, 80)).await.is_none());
+
+        // The last hop books 150 for 1 in, 50% over the reference's 100
+        let hop = check.check(&route(100_000, 150)).await.unwrap();
+        assert_eq!((hop.step_index, hop.ours, hop.reference), (2, 150, 100));
+        assert!((hop.deviation_bps - 5_000.0).abs() < 1e-6);
+    }
+}
@@ -3,29 +3,41 @@ use solana_sdk::pubkey::Pubkey;
 use anyhow::Result;
 use std::str::FromStr;
 use dashmap::DashMap;
+use std::sync::Arc;
 use tracing::{debug, warn};
+use crate::safety::account_batcher::{AccountBatcher, AccountSource, DEFAULT_BATCH_WINDOW};
+use crate::safety::liquidity_floor::LiquidityFloor;
 
 mod checks;
 
 pub struct TokenSafetyChecker {
-    rpc: RpcClient,
+    rpc: Arc<RpcClient>,
+    /// Account fetches from concurrent validations share getMultipleAccounts calls
+    accounts: AccountBatcher,
     burn_addresses: Vec<Pubkey>,
     pub(crate) safe_cache: DashMap<Pubkey, std::time::Instant>,
     pub(crate) blacklist: DashMap<Pubkey, std::time::Instant>,
-    min_liquidity_lamports: u64,
+    /// Pool depth, valued in SOL, a mint's pool must hold to pass
+    liquidity: Arc<LiquidityFloor>,
     whitelist: Vec<Pubkey>,  // Known-safe tokens (stablecoins, wrapped SOL)
+    /// Highest Token-2022 transfer fee a route may absorb; above it the mint is rejected
+    max_transfer_fee_bps: u16,
+    /// Transfer fee (bps) of validated Token-2022 mints that charge one
+    transfer_fees: DashMap<Pubkey, u16>,
 }
 
 impl TokenSafetyChecker {
     pub fn new(rpc_url: &str, min_liquidity_lamports: u64) -> Self {
+        let rpc = Arc::new(RpcClient::new(rpc_url.to_string()));
         Self {
-            rpc: RpcClient::new(rpc_url.to_string()),
+            accounts: AccountBatcher::new(Arc::clone(&rpc) as Arc<dyn AccountSource>, DEFAULT_BATCH_WINDOW),
+            rpc,
             burn_addresses: vec![
                 Pubkey::from_str("11111111111111111111111111111111").unwrap(),
             ],
             safe_cache: DashMap::new(),
             blacklist: DashMap::new(),
-            min_liquidity_lamports,
+            liquidity: Arc::new(LiquidityFloor::new(min_liquidity_lamports, None)),
             whitelist: vec![
                 // USDC (Circle) - has freeze authority for regulatory compliance
                 Pubkey::from_str("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap(),
@@ -38,9 +50,38 @@ impl TokenSafetyChecker {
                 // Native SOL System Program (Indicator for SOL)
                 Pubkey::from_str("11111111111111111111111111111111").unwrap(),
             ],
+            max_transfer_fee_bps: 0,
+            transfer_fees: DashMap::new(),
         }
     }
 
+    pub fn with_max_transfer_fee_bps(mut self, bps: u16) -> Self {
+        self.max_transfer_fee_bps = bps;
+        self
+    }
+
+    /// Shares the search's floor, so non-SOL sides are valued at their oracle price
+    pub fn with_liquidity_floor(mut self, floor: Arc<LiquidityFloor>) -> Self {
+        self.liquidity = floor;
+        self
+    }
+
+    /// Transfer fee charged by `mint`, 0 for spl-token and fee-less Token-2022 mints
+    pub fn transfer_fee_bps(&self, mint: &Pubkey) -> u16 {
+        self.transfer_fees.get(mint).map(|bps| *bps).unwrap_or(0)
+    }
+
+    /// Expected profit once every fee-bearing transfer along the route is taxed.
+    /// Each intermediate token moves twice (pool -> us, us -> pool), so its fee applies twice.
+    pub fn profit_after_transfer_fees(&self, opp: &mev_core::ArbitrageOpportunity) -> u64 {
+        let kept = opp.steps.iter()
+            .flat_map(|step| [step.input_mint, step.output_mint])
+            .map(|mint| 1.0 - self.transfer_fee_bps(&mint) as f64 / 10_000.0)
+            .product::<f64>();
+        let output = (opp.input_amount + opp.expected_profit_lamports) as f64 * kept;
+        (output - opp.input_amount as f64).max(0.0) as u64
+    }
+
     pub async fn is_safe_to_trade(&self, mint: &Pubkey, pool_id: &Pubkey) -> Result<bool> {
         // SHORT-CIRCUIT: Whitelist check first (known-safe stablecoins)
         if self.whitelist.contains(mint) {
@@ -79,6 +120,7 @@ impl TokenSafetyChecker {
                 else if reason.contains("Distribution") { "distribution" }
                 else if reason.contains("Liquidity") { "liquidity" }
                 else if reason.contains("LP") { "lp_status" }
+                else if reason.contains("Token2022") { "token_2022" }
                 else { "other" };
             
             mev_core::telemetry::SAFETY_FAILURES.with_label_values(&[metric_reason]).inc();
@@ -92,23 +134,31 @@ impl TokenSafetyChecker {
     async fn run_deep_validation(&self, mint: &Pubkey, pool_id: &Pubkey) -> Result<()> {
         // 1. BATCH FETCH: Mint and Pool Account data
         let keys = vec![*mint, *pool_id];
-        let accounts = self.rpc.get_multiple_accounts(&keys).await?;
+        let accounts = self.accounts.get_multiple_accounts(&keys).await?;
         
         let mint_acc = accounts[0].as_ref().ok_or_else(|| anyhow::anyhow!("Mint not found"))?;
         let pool_acc = accounts[1].as_ref().ok_or_else(|| anyhow::anyhow!("Pool not found"))?;
+
+        // Hooks, delegates and fees live in Token-2022 extensions; reject before anything else is fetched
+        if mint_acc.owner == mev_core::constants::TOKEN_2022_PROGRAM_ID {
+            let fee_bps = checks::token_2022::check_token_2022_from_data(&mint_acc.data, mint, self.max_transfer_fee_bps)?;
+            if fee_bps > 0 {
+                self.transfer_fees.insert(*mint, fee_bps);
+            }
+        }
  
         // 2. Parallel Sub-checks using batched data
         let (auth_res, dist_res, liq_res): (Result<bool>, Result<bool>, Result<bool>) = tokio::join!(
             async { checks::authorities::check_authorities_from_data(&mint_acc.data, mint) },
             checks::check_holder_distribution(&self.rpc, mint),
-            checks::liquidity_depth::check_liquidity_from_data(&self.rpc, &pool_acc.data, pool_id, self.min_liquidity_lamports)
+            checks::liquidity_depth::check_liquidity_from_data(&self.accounts, &pool_acc.data, pool_id, &self.liquidity)
         );
 
         if !auth_res.unwrap_or(false) { return Err(anyhow::anyhow!("Authority Check Failed")); }
         if !dist_res.unwrap_or(false) { return Err(anyhow::anyhow!("Distribution Check Failed")); }
         if !liq_res.unwrap_or(false) { return Err(anyhow::anyhow!("Liquidity Check Failed")); }
 
-        match checks::lp_status::check_lp_status_from_data(&self.rpc, &pool_acc.data, pool_id, &self.burn_addresses).await {
+        match checks::lp_status::check_lp_status_from_data(&self.rpc, &self.accounts, &pool_acc.data, pool_id, &self.burn_addresses).await {
             Ok(true) => Ok(()),
             Ok(false) => {
                  // Secondary check: If it's Orca Whirlpool (no LP mint to burn), assume safe
@@ -132,7 +182,7 @@ impl TokenSafetyChecker {
 
     #[cfg(test)]
     pub fn get_min_liquidity(&self) -> u64 {
-        self.min_liquidity_lamports
+        self.liquidity.min_lamports()
     }
 }
 
@@ -12,7 +12,9 @@ pub async fn check_authorities(rpc: &RpcClient, mint: &Pubkey) -> Result<bool> {
 }
 
 pub fn check_authorities_from_data(data: &[u8], mint: &Pubkey) -> Result<bool> {
-    let mint_data = Mint::unpack(data)?;
+    // Token-2022 mints carry extensions after the base layout
+    let base = data.get(..Mint::LEN).ok_or_else(|| anyhow::anyhow!("Mint data too short"))?;
+    let mint_data = Mint::unpack(base)?;
     if mint_data.mint_authority.is_some() {
         tracing::warn!("⚠️ Token {} has active mint authority: {:?}", mint, mint_data.mint_authority);
         return Ok(false);
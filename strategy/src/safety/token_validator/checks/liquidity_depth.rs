@@ -4,32 +4,40 @@ use anyhow::Result;
 use mev_core::raydium::AmmInfo;
 use bytemuck;
 use tracing::{warn};
+use crate::safety::account_batcher::AccountSource;
+use crate::safety::liquidity_floor::LiquidityFloor;
 
 /// Checks if the pool has sufficient liquidity.
-pub async fn check_liquidity_depth(rpc: &RpcClient, pool_id: &Pubkey, min_liquidity_lamports: u64) -> Result<bool> {
+pub async fn check_liquidity_depth(rpc: &RpcClient, pool_id: &Pubkey, floor: &LiquidityFloor) -> Result<bool> {
     let account = rpc.get_account(pool_id).await?;
-    check_liquidity_from_data(rpc, &account.data, pool_id, min_liquidity_lamports).await
+    check_liquidity_from_data(rpc, &account.data, pool_id, floor).await
 }
 
-pub async fn check_liquidity_from_data(rpc: &RpcClient, data: &[u8], pool_id: &Pubkey, min_liquidity_lamports: u64) -> Result<bool> {
+/// Values the vaults' token balances (not their rent lamports) in SOL against the floor
+pub async fn check_liquidity_from_data(accounts: &(impl AccountSource + ?Sized), data: &[u8], pool_id: &Pubkey, floor: &LiquidityFloor) -> Result<bool> {
     // For Raydium pools, use the accessor methods from AmmInfo
     if data.len() >= 752 {
         if let Ok(amm_info) = bytemuck::try_from_bytes::<AmmInfo>(data) {
-            let base_vault = amm_info.base_vault();
-            let quote_vault = amm_info.quote_vault();
-            
             // Batch vault balance check
-            let vaults = vec![base_vault, quote_vault];
-            if let Ok(balances) = rpc.get_multiple_accounts(&vaults).await {
-                for (i, acc_opt) in balances.into_iter().enumerate() {
-                    if let Some(acc) = acc_opt {
-                        if acc.lamports >= min_liquidity_lamports {
-                            return Ok(true);
-                        }
-                        warn!("⚠️ Pool {} vault {} has insufficient balance: {} < {}", 
-                            pool_id, vaults[i], acc.lamports, min_liquidity_lamports);
-                    }
+            let vaults = vec![amm_info.base_vault(), amm_info.quote_vault()];
+            if let Ok(balances) = accounts.get_multiple_accounts(&vaults).await {
+                let amounts: Vec<u128> = balances.iter()
+                    .map(|acc| acc.as_ref()
+                        .and_then(|acc| acc.data.get(64..72))
+                        .map_or(0, |bytes| u64::from_le_bytes(bytes.try_into().unwrap_or([0; 8])) as u128))
+                    .collect();
+                let depth = floor.depth_lamports(
+                    (amm_info.base_mint(), amounts[0]),
+                    (amm_info.quote_mint(), amounts[1]),
+                    mev_core::clock::system().unix_secs(),
+                );
+                if floor.admits(depth) {
+                    return Ok(true);
                 }
+                mev_core::telemetry::POOLS_BELOW_LIQUIDITY_FLOOR.with_label_values(&["safety"]).inc();
+                warn!("⚠️ Pool {} holds ~{} SOL-equivalent lamports, below the {} floor",
+                    pool_id, depth.unwrap_or(0), floor.min_lamports());
+                return Ok(false);
             }
             
             warn!("⚠️ Pool {} has insufficient total liquidity depth", pool_id);
@@ -39,4 +47,4 @@ pub async fn check_liquidity_from_data(rpc: &RpcClient, data: &[u8], pool_id: &P
     
     // For other pool types (like Pump.fun which has virtual reserves already in the update), assume safe here
     Ok(true)
-}
\ No newline at end of file
+}
@@ -4,6 +4,7 @@ use anyhow::Result;
 use mev_core::raydium::AmmInfo;
 use bytemuck;
 use spl_associated_token_account;
+use crate::safety::account_batcher::AccountSource;
 
 /// Checks if the liquidity pool has burned LP tokens.
 pub async fn check_lp_status(rpc: &RpcClient, pool_id: &Pubkey, burn_addresses: &[Pubkey]) -> Result<bool> {
@@ -11,10 +12,17 @@ pub async fn check_lp_status(rpc: &RpcClient, pool_id: &Pubkey, burn_addresses:
         Ok(acc) => acc,
         Err(_) => return Ok(false),
     };
-    check_lp_status_from_data(rpc, &account.data, pool_id, burn_addresses).await
+    check_lp_status_from_data(rpc, rpc, &account.data, pool_id, burn_addresses).await
 }
 
-pub async fn check_lp_status_from_data(rpc: &RpcClient, data: &[u8], pool_id: &Pubkey, burn_addresses: &[Pubkey]) -> Result<bool> {
+/// `accounts` serves the burn-address ATA lookup, which can be batched with other validations
+pub async fn check_lp_status_from_data(
+    rpc: &RpcClient,
+    accounts: &(impl AccountSource + ?Sized),
+    data: &[u8],
+    pool_id: &Pubkey,
+    burn_addresses: &[Pubkey],
+) -> Result<bool> {
     if let Ok(amm_info) = bytemuck::try_from_bytes::<AmmInfo>(data) {
         let lp_mint = amm_info.lp_mint();
         let supply_resp = rpc.get_token_supply(&lp_mint).await?;
@@ -28,8 +36,8 @@ pub async fn check_lp_status_from_data(rpc: &RpcClient, data: &[u8], pool_id: &P
             .collect();
         
         let mut burned_amount = 0u64;
-        if let Ok(accounts) = rpc.get_multiple_accounts(&atas).await {
-            for acc_opt in accounts {
+        if let Ok(burn_accounts) = accounts.get_multiple_accounts(&atas).await {
+            for acc_opt in burn_accounts {
                 if let Some(acc) = acc_opt {
                     // This is a bit simplified, ideally should parse TokenAccount
                     // but lamports on an ATA of a burned LP token is a good proxy or we use data.
@@ -5,11 +5,13 @@ pub mod authorities;
 pub mod holder_distribution;
 pub mod lp_status;
 pub mod liquidity_depth;
+pub mod token_2022;
 
 pub use authorities::*;
 pub use holder_distribution::*;
 pub use lp_status::*;
 pub use liquidity_depth::*;
+pub use token_2022::*;
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -0,0 +1,124 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::program_pack::Pack;
+use spl_token::state::Mint;
+use anyhow::Result;
+
+/// Token-2022 pads the base mint out to the token account size before the type byte
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+
+const EXT_TRANSFER_FEE_CONFIG: u16 = 1;
+const EXT_NON_TRANSFERABLE: u16 = 9;
+const EXT_PERMANENT_DELEGATE: u16 = 12;
+const EXT_TRANSFER_HOOK: u16 = 14;
+
+/// Mint extensions that change what a swap leg actually moves
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MintExtensions {
+    /// Higher of the older/newer fee schedules, so an epoch rollover can't surprise us
+    pub transfer_fee_bps: u16,
+    pub transfer_hook: bool,
+    pub permanent_delegate: bool,
+    pub non_transferable: bool,
+}
+
+/// Walks the TLV extension area of a Token-2022 mint account
+pub fn decode_mint_extensions(data: &[u8]) -> Result<MintExtensions> {
+    let mut ext = MintExtensions::default();
+    if data.len() <= Mint::LEN {
+        return Ok(ext);
+    }
+    if data.get(ACCOUNT_TYPE_OFFSET) != Some(&ACCOUNT_TYPE_MINT) {
+        return Err(anyhow::anyhow!("Token2022 account is not a mint"));
+    }
+
+    let u16_at = |i: usize| data.get(i..i + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    let non_zero = |b: &[u8]| b.iter().any(|&x| x != 0);
+
+    let mut cursor = ACCOUNT_TYPE_OFFSET + 1;
+    while let (Some(kind), Some(len)) = (u16_at(cursor), u16_at(cursor + 2)) {
+        if kind == 0 {
+            break; // Uninitialized: rest of the buffer is padding
+        }
+        let start = cursor + 4;
+        let value = data.get(start..start + len as usize)
+            .ok_or_else(|| anyhow::anyhow!("Token2022 extension {} overruns mint data", kind))?;
+        match kind {
+            // authority(32) withdraw_authority(32) withheld(8) older{epoch(8) max(8) bps(2)} newer{..}
+            EXT_TRANSFER_FEE_CONFIG if value.len() >= 108 => {
+                let older = u16::from_le_bytes([value[88], value[89]]);
+                let newer = u16::from_le_bytes([value[106], value[107]]);
+                ext.transfer_fee_bps = older.max(newer);
+            }
+            EXT_NON_TRANSFERABLE => ext.non_transferable = true,
+            EXT_PERMANENT_DELEGATE => ext.permanent_delegate = non_zero(value),
+            // authority(32) program_id(32): either one being set means a hook can run
+            EXT_TRANSFER_HOOK => ext.transfer_hook = non_zero(value),
+            _ => {}
+        }
+        cursor = start + len as usize;
+    }
+    Ok(ext)
+}
+
+/// Rejects mints whose transfers can be intercepted, clawed back or taxed above `max_fee_bps`.
+/// Returns the transfer fee (bps) the route has to absorb.
+pub fn check_token_2022_from_data(data: &[u8], mint: &Pubkey, max_fee_bps: u16) -> Result<u16> {
+    let ext = decode_mint_extensions(data)?;
+    if ext.transfer_hook {
+        return Err(anyhow::anyhow!("Token2022 Extension Check Failed: transfer hook on {}", mint));
+    }
+    if ext.permanent_delegate {
+        return Err(anyhow::anyhow!("Token2022 Extension Check Failed: permanent delegate on {}", mint));
+    }
+    if ext.non_transferable {
+        return Err(anyhow::anyhow!("Token2022 Extension Check Failed: non-transferable {}", mint));
+    }
+    if ext.transfer_fee_bps > max_fee_bps {
+        return Err(anyhow::anyhow!("Token2022 Extension Check Failed: transfer fee {}bps on {} (max {}bps)",
+            ext.transfer_fee_bps, mint, max_fee_bps));
+    }
+    if ext.transfer_fee_bps > 0 {
+        tracing::warn!("⚠️ Token {} charges a {}bps transfer fee", mint, ext.transfer_fee_bps);
+    }
+    Ok(ext.transfer_fee_bps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mint_with(extensions: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut data = vec![0u8; ACCOUNT_TYPE_OFFSET];
+        data.push(ACCOUNT_TYPE_MINT);
+        for (kind, value) in extensions {
+            data.extend_from_slice(&kind.to_le_bytes());
+            data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+            data.extend_from_slice(value);
+        }
+        data
+    }
+
+    #[test]
+    fn test_extension_decoding_and_verdicts() {
+        let mint = Pubkey::new_unique();
+
+        // Plain spl-token layout: nothing to decode
+        assert_eq!(decode_mint_extensions(&[0u8; 82]).unwrap(), MintExtensions::default());
+
+        let mut fee = vec![0u8; 108];
+        fee[88..90].copy_from_slice(&50u16.to_le_bytes());
+        fee[106..108].copy_from_slice(&300u16.to_le_bytes());
+        let data = mint_with(&[(EXT_TRANSFER_FEE_CONFIG, fee), (3, vec![0u8; 32])]);
+        assert_eq!(decode_mint_extensions(&data).unwrap().transfer_fee_bps, 300);
+        assert_eq!(check_token_2022_from_data(&data, &mint, 500).unwrap(), 300);
+        assert!(check_token_2022_from_data(&data, &mint, 100).is_err());
+
+        // A cleared delegate is harmless; a live hook is not
+        assert!(check_token_2022_from_data(&mint_with(&[(EXT_PERMANENT_DELEGATE, vec![0u8; 32])]), &mint, 0).is_ok());
+        let mut hook = vec![0u8; 64];
+        hook[40] = 7;
+        let err = check_token_2022_from_data(&mint_with(&[(EXT_TRANSFER_HOOK, hook)]), &mint, 0).unwrap_err();
+        assert!(err.to_string().contains("transfer hook"));
+    }
+}
@@ -0,0 +1,184 @@
+/// Input Sizing
+///
+/// The search quotes every cycle at the configured trade size, which is almost
+/// never the size that extracts the most from a dislocation: too small leaves
+/// profit on the table, too large eats it in price impact. Once a route is
+/// found, it is re-quoted across an input range and resized to the amount that
+/// maximizes profit net of the Jito tip. Two-pool CPMM cycles have a closed
+/// form; anything else is ternary-searched over the profit curve. That curve
+/// is unimodal where routes quote, but reads zero wherever the impact cap or
+/// the hop-cost floor rejects a size, so the search breaks ties downward.
+use mev_core::{ArbitrageOpportunity, PoolUpdate};
+use mev_core::constants::is_clmm;
+use crate::ArbitrageStrategy;
+
+// Integer ternary search stops once the bracket is this narrow
+const SEARCH_RESOLUTION: u64 = 1_000;
+const MAX_SEARCH_ITERATIONS: usize = 64;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SizingPolicy {
+    pub min_amount: u64,
+    pub max_amount: u64,
+}
+
+impl SizingPolicy {
+    pub fn new(min_amount: u64, max_amount: u64) -> Self {
+        Self { min_amount: min_amount.min(max_amount), max_amount }
+    }
+
+    /// Resizes `opp` to the input with the highest profit after `tip`.
+    /// Returns None if no size in range beats the opportunity as found.
+    pub fn optimize(
+        &self,
+        strategy: &ArbitrageStrategy,
+        opp: &ArbitrageOpportunity,
+        tip: impl Fn(u64) -> u64,
+    ) -> Option<ArbitrageOpportunity> {
+        let net = |o: &ArbitrageOpportunity| o.expected_profit_lamports.saturating_sub(tip(o.expected_profit_lamports));
+        let quote = |amount: u64| strategy.requote(&opp.steps, amount);
+
+        let search = || ternary_search(self.min_amount, self.max_amount, |amount| {
+            quote(amount).map(|o| net(&o)).unwrap_or(0)
+        });
+
+        // The closed form knows nothing about the impact cap; search if its answer breaches it
+        let pools = strategy.route_pools(&opp.steps)?;
+        let sized = cpmm_pair_optimal_input(&pools, opp)
+            .and_then(|x| quote(x.clamp(self.min_amount, self.max_amount)))
+            .or_else(|| quote(search()))?;
+        (net(&sized) > net(opp)).then_some(sized)
+    }
+}
+
+/// Closed-form optimum for a two-hop constant-product cycle.
+///
+/// Composing two CPMM hops gives out(x) = A·x / (B + C·x) with
+/// A = γ1·γ2·R1out·R2out, B = R1in·R2in, C = γ1·(R2in + γ2·R1out);
+/// out(x) - x peaks where A·B = (B + C·x)², i.e. x* = (√(A·B) - B) / C.
+pub fn cpmm_pair_optimal_input(pools: &[PoolUpdate], opp: &ArbitrageOpportunity) -> Option<u64> {
+    if pools.len() != 2 || opp.steps.len() != 2 || pools.iter().any(|p| is_clmm(&p.program_id)) {
+        return None;
+    }
+    let oriented = |pool: &PoolUpdate, input_mint| {
+        let gamma = 1.0 - pool.fee_bps as f64 / 10000.0;
+        if pool.mint_a == input_mint {
+            (pool.reserve_a as f64, pool.reserve_b as f64, gamma)
+        } else {
+            (pool.reserve_b as f64, pool.reserve_a as f64, gamma)
+        }
+    };
+    let (r1_in, r1_out, g1) = oriented(&pools[0], opp.steps[0].input_mint);
+    let (r2_in, r2_out, g2) = oriented(&pools[1], opp.steps[1].input_mint);
+
+    let a = g1 * g2 * r1_out * r2_out;
+    let b = r1_in * r2_in;
+    let c = g1 * (r2_in + g2 * r1_out);
+    if a <= b || c <= 0.0 {
+        return None;
+    }
+    let x = ((a * b).sqrt() - b) / c;
+    (x.is_finite() && x >= 1.0).then_some(x as u64)
+}
+
+/// Maximizes a unimodal `f` over [lo, hi]. Ties go to the smaller amount: two zero
+/// probes most likely both sit past the impact cap, where every larger size is zero too.
+pub fn ternary_search(mut lo: u64, mut hi: u64, f: impl Fn(u64) -> u64) -> u64 {
+    for _ in 0..MAX_SEARCH_ITERATIONS {
+        if hi - lo <= SEARCH_RESOLUTION {
+            break;
+        }
+        let third = (hi - lo) / 3;
+        let (m1, m2) = (lo + third, hi - third);
+        if f(m1) < f(m2) {
+            lo = m1;
+        } else {
+            hi = m2;
+        }
+    }
+    // Settle within the final bracket, smallest first so a tie keeps it
+    [lo, lo + (hi - lo) / 2, hi]
+        .into_iter()
+        .map(|x| (f(x), x))
+        .reduce(|best, next| if next.0 > best.0 { next } else { best })
+        .map_or(lo, |(_, x)| x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::SwapStep;
+    use mev_core::constants::RAYDIUM_V4_PROGRAM;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn cpmm(mint_a: Pubkey, mint_b: Pubkey, reserve_a: u128, reserve_b: u128) -> PoolUpdate {
+        PoolUpdate {
+            pool_address: Pubkey::new_unique(),
+            program_id: RAYDIUM_V4_PROGRAM,
+            mint_a,
+            mint_b,
+            reserve_a,
+            reserve_b,
+            price_sqrt: None,
+            liquidity: None,
+            fee_bps: 25,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_ternary_search_finds_peak() {
+        let peak = 7_777_777u64;
+        let best = ternary_search(1, 100_000_000, |x| u64::MAX - x.abs_diff(peak));
+        assert!(best.abs_diff(peak) <= SEARCH_RESOLUTION);
+    }
+
+    #[test]
+    fn test_ternary_search_stops_at_binding_cap() {
+        // Unconstrained the profit would peak at 80M, but sizes past 30M break the impact cap
+        // and sizes under 2M don't cover the hop costs: both read zero
+        let (floor, cap, peak) = (2_000_000u64, 30_000_000u64, 80_000_000u64);
+        let profit = |x: u64| if x < floor || x > cap { 0 } else { x - x * x / (2 * peak) };
+
+        let best = ternary_search(1, 100_000_000, profit);
+        assert!(best <= cap && cap - best <= SEARCH_RESOLUTION, "best {}", best);
+        assert!(profit(best) > 0);
+
+        // Nothing quotes: the smallest amount, not the top of the range
+        assert_eq!(ternary_search(1, 100_000_000, |_| 0), 1);
+    }
+
+    #[test]
+    fn test_closed_form_matches_brute_force() {
+        let (sol, usdc) = (Pubkey::new_unique(), Pubkey::new_unique());
+        // 5% dislocation between two SOL/USDC pools
+        let pools = vec![
+            cpmm(sol, usdc, 1_000_000_000_000, 150_000_000_000),
+            cpmm(usdc, sol, 142_500_000_000, 1_000_000_000_000),
+        ];
+        let step = |pool: &PoolUpdate, input_mint, output_mint| SwapStep {
+            pool: pool.pool_address,
+            program_id: pool.program_id,
+            input_mint,
+            output_mint,
+            expected_output: 0,
+        };
+        let opp = ArbitrageOpportunity {
+            steps: [step(&pools[0], sol, usdc), step(&pools[1], usdc, sol)].into_iter().collect(),
+            ..Default::default()
+        };
+
+        let profit = |x: u64| {
+            let mid = mev_core::math::get_amount_out_cpmm(x, 1_000_000_000_000, 150_000_000_000, 25);
+            mev_core::math::get_amount_out_cpmm(mid, 142_500_000_000, 1_000_000_000_000, 25).saturating_sub(x)
+        };
+        let closed = cpmm_pair_optimal_input(&pools, &opp).unwrap();
+        let searched = ternary_search(1, 100_000_000_000, profit);
+        assert!(closed.abs_diff(searched) * 100 < searched, "closed {} vs searched {}", closed, searched);
+        assert!(profit(closed) >= profit(closed / 2) && profit(closed) >= profit(closed * 2));
+
+        // No dislocation, no optimum
+        let flat = vec![pools[0].clone(), cpmm(usdc, sol, 150_000_000_000, 1_000_000_000_000)];
+        assert!(cpmm_pair_optimal_input(&flat, &opp).is_none());
+    }
+}
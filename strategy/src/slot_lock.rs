@@ -0,0 +1,162 @@
+/// Slot-Local Execution Locks
+///
+/// Two workers can find overlapping cycles off the same burst of updates. Both
+/// bundles touch the same pools, so at best one lands and the other burns its
+/// fees. Before submitting, a worker claims every pool in its route for the
+/// current slot; a higher-EV claim preempts a lower one that has not been sent
+/// yet, and a claim that has been sent holds its pools for the rest of the slot.
+/// A claim dropped unsent (a later gate rejected the route) frees its pools.
+use parking_lot::Mutex;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+// Prune claims from old slots once the map grows past this
+const PRUNE_THRESHOLD: usize = 4096;
+
+const HELD: u8 = 0;
+const PREEMPTED: u8 = 1;
+const COMMITTED: u8 = 2;
+const RELEASED: u8 = 3;
+
+struct ClaimState {
+    slot: u64,
+    ev: u64,
+    state: AtomicU8,
+}
+
+/// Held by a worker between claiming its pools and submitting; released if dropped before `commit`
+pub struct SlotClaim {
+    state: Arc<ClaimState>,
+}
+
+impl SlotClaim {
+    /// Marks the claim as sent. Returns false if a higher-EV route took the pools first.
+    pub fn commit(&self) -> bool {
+        self.state.state.compare_exchange(HELD, COMMITTED, Ordering::AcqRel, Ordering::Acquire).is_ok()
+    }
+
+    pub fn is_preempted(&self) -> bool {
+        self.state.state.load(Ordering::Acquire) == PREEMPTED
+    }
+}
+
+impl Drop for SlotClaim {
+    fn drop(&mut self) {
+        let _ = self.state.state.compare_exchange(HELD, RELEASED, Ordering::AcqRel, Ordering::Acquire);
+    }
+}
+
+#[derive(Default)]
+pub struct SlotLockService {
+    claims: Mutex<HashMap<Pubkey, Arc<ClaimState>>>,
+}
+
+impl SlotLockService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Claims the pool set for `slot`. Returns None if an overlapping route in the same slot
+    /// already went out or carries at least as much EV.
+    pub fn try_claim(&self, slot: u64, pools: &[Pubkey], ev: u64) -> Option<SlotClaim> {
+        let mut key: Vec<Pubkey> = pools.to_vec();
+        key.sort_unstable();
+        key.dedup();
+
+        let mut claims = self.claims.lock();
+        if claims.len() > PRUNE_THRESHOLD {
+            claims.retain(|_, c| c.slot >= slot);
+        }
+
+        let mut losers: Vec<Arc<ClaimState>> = Vec::new();
+        for pool in &key {
+            if let Some(existing) = claims.get(pool) {
+                if existing.slot != slot {
+                    continue;
+                }
+                match existing.state.load(Ordering::Acquire) {
+                    COMMITTED => return None,
+                    HELD if existing.ev >= ev => return None,
+                    HELD => losers.push(Arc::clone(existing)),
+                    _ => {} // Preempted or released: free
+                }
+            }
+        }
+
+        // A loser may have committed since we looked; then it keeps the pools
+        for loser in &losers {
+            if loser.state.compare_exchange(HELD, PREEMPTED, Ordering::AcqRel, Ordering::Acquire) == Err(COMMITTED) {
+                return None;
+            }
+        }
+        if !losers.is_empty() {
+            mev_core::telemetry::OPPORTUNITIES_SLOT_PREEMPTED.inc_by(losers.len() as f64);
+        }
+
+        let state = Arc::new(ClaimState { slot, ev, state: AtomicU8::new(HELD) });
+        for pool in key {
+            claims.insert(pool, Arc::clone(&state));
+        }
+        Some(SlotClaim { state })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_higher_ev_preempts_unsent_overlap() {
+        let locks = SlotLockService::new();
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        let low = locks.try_claim(10, &[a, b], 1_000).unwrap();
+        assert!(locks.try_claim(10, &[b, c], 500).is_none());
+        let high = locks.try_claim(10, &[c, b], 5_000).unwrap();
+
+        assert!(low.is_preempted());
+        assert!(!low.commit());
+        assert!(high.commit());
+    }
+
+    #[test]
+    fn test_committed_claim_holds_for_the_slot() {
+        let locks = SlotLockService::new();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let sent = locks.try_claim(10, &[a, b], 1_000).unwrap();
+        assert!(sent.commit());
+        assert!(locks.try_claim(10, &[b], 1_000_000).is_none());
+
+        // Next slot is a clean slate
+        assert!(locks.try_claim(11, &[a, b], 1).is_some());
+    }
+
+    #[test]
+    fn test_claim_rejected_by_a_later_gate_frees_its_pools() {
+        let locks = SlotLockService::new();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let rejected = locks.try_claim(10, &[a, b], 5_000).unwrap();
+        assert!(locks.try_claim(10, &[b], 1_000).is_none());
+
+        // e.g. the simulation gate fails the route: the claim goes out of scope unsent
+        drop(rejected);
+        let next = locks.try_claim(10, &[b], 1_000).unwrap();
+        assert!(next.commit());
+
+        // Dropping after the send leaves the pools held
+        drop(next);
+        assert!(locks.try_claim(10, &[a, b], 1_000_000).is_none());
+    }
+
+    #[test]
+    fn test_disjoint_routes_do_not_interact() {
+        let locks = SlotLockService::new();
+        let first = locks.try_claim(10, &[Pubkey::new_unique()], 1).unwrap();
+        let second = locks.try_claim(10, &[Pubkey::new_unique()], 1_000).unwrap();
+        assert!(first.commit() && second.commit());
+    }
+}
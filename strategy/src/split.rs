@@ -0,0 +1,123 @@
+/// Split Execution
+///
+/// When a dislocation is too deep to take in one bundle without breaching the
+/// per-hop impact cap, the same route can be worked in K sequential chunks,
+/// re-quoting against fresh pool state between each one.
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+use petgraph::graph::NodeIndex;
+use solana_sdk::pubkey::Pubkey;
+use mev_core::ArbitrageOpportunity;
+use crate::ArbitrageStrategy;
+use crate::analytics::route_guard::{route_key, RouteKey};
+
+#[derive(Debug, Clone)]
+pub struct SplitPolicy {
+    /// Chunks used for routes without an override. 1 disables splitting.
+    pub default_chunks: u8,
+    /// Per-route overrides keyed by ordered pool sequence. 1 opts a route out.
+    pub route_chunks: HashMap<RouteKey, u8>,
+    /// Pause between chunks so our own fill shows up in pool state
+    pub requote_delay: Duration,
+}
+
+impl SplitPolicy {
+    pub fn new(default_chunks: u8, requote_delay: Duration) -> Self {
+        Self {
+            default_chunks: default_chunks.max(1),
+            route_chunks: HashMap::new(),
+            requote_delay,
+        }
+    }
+
+    /// Parses overrides of the form `poolA,poolB,poolC=3;poolD,poolE=1`
+    pub fn with_route_overrides(mut self, spec: &str) -> Result<Self, String> {
+        for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let (route, chunks) = entry.split_once('=')
+                .ok_or_else(|| format!("Invalid split override '{}': expected <pools>=<chunks>", entry))?;
+            let chunks: u8 = chunks.trim().parse()
+                .map_err(|_| format!("Invalid chunk count in '{}'", entry))?;
+            let key = route.split(',')
+                .map(|p| Pubkey::from_str(p.trim()).map_err(|_| format!("Invalid pool '{}' in '{}'", p.trim(), entry)))
+                .collect::<Result<RouteKey, String>>()?;
+            self.route_chunks.insert(key, chunks.max(1));
+        }
+        Ok(self)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.default_chunks > 1 || self.route_chunks.values().any(|&k| k > 1)
+    }
+
+    pub fn chunks_for(&self, key: &RouteKey) -> u8 {
+        self.route_chunks.get(key).copied().unwrap_or(self.default_chunks)
+    }
+
+    fn max_chunks(&self) -> u8 {
+        self.route_chunks.values().copied().fold(self.default_chunks, u8::max)
+    }
+
+    /// Finds a route that works at chunk size and sizes it per its override.
+    /// Returns the first chunk's opportunity and the total chunk count.
+    pub fn plan(
+        &self,
+        strategy: &ArbitrageStrategy,
+        node_a: NodeIndex,
+        node_b: NodeIndex,
+        total_amount: u64,
+        max_hops: u8,
+    ) -> Option<(ArbitrageOpportunity, u8)> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        // Probe with the smallest chunk any route may use, then resize to the route's own setting
+        let probe_chunks = self.max_chunks();
+        let probe = strategy.find_best_cycle(node_a, node_b, total_amount / probe_chunks as u64, max_hops)?;
+        let chunks = self.chunks_for(&route_key(&probe));
+        if chunks <= 1 {
+            return None;
+        }
+
+        let opp = if chunks == probe_chunks {
+            probe
+        } else {
+            strategy.requote(&probe.steps, total_amount / chunks as u64)?
+        };
+        Some((opp, chunks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_route_overrides() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let spec = format!("{},{}=4; {}=1", a, b, b);
+        let policy = SplitPolicy::new(2, Duration::from_millis(400)).with_route_overrides(&spec).unwrap();
+
+        assert_eq!(policy.chunks_for(&vec![a, b]), 4);
+        assert_eq!(policy.chunks_for(&vec![b]), 1);
+        assert_eq!(policy.chunks_for(&vec![b, a]), 2);
+        assert_eq!(policy.max_chunks(), 4);
+    }
+
+    #[test]
+    fn test_invalid_override_rejected() {
+        let policy = SplitPolicy::new(1, Duration::ZERO);
+        assert!(policy.clone().with_route_overrides("not-a-pool=2").is_err());
+        assert!(policy.with_route_overrides(&format!("{}", Pubkey::new_unique())).is_err());
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        let policy = SplitPolicy::new(1, Duration::ZERO);
+        assert!(!policy.is_enabled());
+        let opted_in = policy.with_route_overrides(&format!("{}=3", Pubkey::new_unique())).unwrap();
+        assert!(opted_in.is_enabled());
+    }
+}
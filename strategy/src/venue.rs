@@ -0,0 +1,104 @@
+/// Graph Edge Venues
+///
+/// Each directed edge of the market graph lists every venue that trades its
+/// mint pair. AMMs are described by their pool state alone. A CLOB market also
+/// carries the L2 book it had when the edge was last written, so the search
+/// fills hops level by level and the rate search prices them at the touch,
+/// instead of reading the mid-price virtual reserves as if the book were a pool.
+use std::sync::Arc;
+use mev_core::PoolUpdate;
+use mev_core::clob::OrderBook;
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Debug, Clone)]
+pub enum Venue {
+    /// Quoted from reserves, ticks, a bonding curve or cached DLMM bins
+    Amm(PoolUpdate),
+    /// CLOB market; quotes nothing until its first book arrives
+    Book { state: PoolUpdate, book: Option<Arc<OrderBook>> },
+}
+
+impl Venue {
+    /// Book venue for CLOB programs, AMM otherwise
+    pub fn new(state: PoolUpdate, book: Option<Arc<OrderBook>>) -> Self {
+        if mev_core::constants::is_clob(&state.program_id) {
+            Venue::Book { state, book }
+        } else {
+            Venue::Amm(state)
+        }
+    }
+
+    pub fn state(&self) -> &PoolUpdate {
+        match self {
+            Venue::Amm(state) | Venue::Book { state, .. } => state,
+        }
+    }
+
+    pub fn pool_address(&self) -> Pubkey {
+        self.state().pool_address
+    }
+
+    /// The venue's fee tier: what it charges per hop, in bps. Pools of one program on the
+    /// same pair differ only in this and their depth.
+    pub fn fee_tier_bps(&self) -> u16 {
+        self.state().fee_bps
+    }
+
+    pub fn book(&self) -> Option<&Arc<OrderBook>> {
+        match self {
+            Venue::Book { book, .. } => book.as_ref(),
+            Venue::Amm(_) => None,
+        }
+    }
+
+    /// Marginal rate after fees, in raw output units per input unit
+    pub fn spot_rate(&self, current_mint: Pubkey) -> f64 {
+        match self {
+            Venue::Amm(pool) => crate::spot_rate(pool, current_mint),
+            Venue::Book { book: Some(book), .. } => book.touch_rate(current_mint == book.base_mint),
+            Venue::Book { book: None, .. } => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mev_core::clob::Level;
+
+    #[test]
+    fn test_book_venue_rates_at_the_touch() {
+        let (base, quote) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let state = PoolUpdate {
+            pool_address: Pubkey::new_unique(),
+            program_id: mev_core::constants::PHOENIX_PROGRAM,
+            mint_a: base,
+            mint_b: quote,
+            // Mid-price virtual reserves: 55 quote per 100 base atoms
+            reserve_a: 2_000,
+            reserve_b: 1_100,
+            price_sqrt: None,
+            liquidity: None,
+            fee_bps: 0,
+            timestamp: 0,
+        };
+        assert_eq!(Venue::new(state.clone(), None).spot_rate(base), 0.0);
+
+        let book = OrderBook {
+            base_mint: base,
+            quote_mint: quote,
+            base_lot_size: 100,
+            taker_fee_bps: 0,
+            bids: vec![Level { price: 50, lots: 10 }],
+            asks: vec![Level { price: 60, lots: 10 }],
+        };
+        let venue = Venue::new(state, Some(Arc::new(book)));
+        assert_eq!(venue.book().map(|b| b.bids.len()), Some(1));
+        // Selling base hits the bid, buying it lifts the ask; neither sees the mid
+        assert!((venue.spot_rate(base) - 0.5).abs() < 1e-12);
+        assert!((venue.spot_rate(quote) - 100.0 / 60.0).abs() < 1e-12);
+
+        let amm = Venue::new(PoolUpdate { program_id: mev_core::constants::RAYDIUM_V4_PROGRAM, ..venue.state().clone() }, None);
+        assert!(matches!(amm, Venue::Amm(_)));
+    }
+}